@@ -0,0 +1,148 @@
+/* Generated by cbindgen from c/src/lib.rs, do not edit */
+
+#ifndef LIBLRS_H
+#define LIBLRS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The result of a function that can fail.
+typedef enum LrsStatus {
+  // The function succeeded and wrote its output.
+  LRS_STATUS_OK = 0,
+  // The function failed, see `lrs_last_error_code` and `lrs_last_error_message`.
+  LRS_STATUS_ERROR = 1,
+} LrsStatus;
+
+// A loaded Linear Referencing System. It is opaque: use it through the functions.
+typedef struct Lrs Lrs;
+
+// A geographical position: the longitude and the latitude, or projected coordinates.
+typedef struct LrsPoint {
+  // Position on x-axis or longitude.
+  double x;
+  // Position on y-axis or latitude.
+  double y;
+} LrsPoint;
+
+// The positions of a line string. Release it with `lrs_points_free`.
+typedef struct LrsPoints {
+  // The first position.
+  struct LrsPoint *points;
+  // How many positions there are.
+  size_t len;
+} LrsPoints;
+
+// The measure of a position projected on an LRM. Release it with `lrs_projection_free`.
+typedef struct LrsProjection {
+  // Name of the anchor of the measure.
+  char *anchor_name;
+  // Offset from the anchor, in the unit of the scale.
+  double scale_offset;
+  // Distance between the position and the LRM, positive on the left.
+  double orthogonal_offset;
+} LrsProjection;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Loads an LRS from the `len` bytes of a file. Returns null on error.
+//
+// # Safety
+// `data` must point to `len` readable bytes. They are not kept.
+struct Lrs *lrs_load(const uint8_t *data, size_t len);
+
+// Loads an LRS from a file. Returns null on error.
+//
+// # Safety
+// `path` must be a nul-terminated string.
+struct Lrs *lrs_open(const char *path);
+
+// Releases an LRS. Null is ignored.
+//
+// # Safety
+// `lrs` must have been returned by `lrs_load` or `lrs_open`, and not have been released yet.
+void lrs_free(struct Lrs *lrs);
+
+// How many LRMs compose the LRS.
+//
+// # Safety
+// `lrs` must be a valid LRS.
+size_t lrs_lrm_len(const struct Lrs *lrs);
+
+// Writes in `out_lrm_index` the index of the LRM with the identifier `lrm_id`.
+//
+// # Safety
+// `lrs` must be a valid LRS, `lrm_id` a nul-terminated string and `out_lrm_index` writable.
+enum LrsStatus lrs_find_lrm(const struct Lrs *lrs, const char *lrm_id, size_t *out_lrm_index);
+
+// Writes in `out` the position of the measure `anchor_name` + `scale_offset` on an LRM.
+//
+// # Safety
+// `lrs` must be a valid LRS, `anchor_name` a nul-terminated string and `out` writable.
+enum LrsStatus lrs_resolve(const struct Lrs *lrs,
+                           size_t lrm_index,
+                           const char *anchor_name,
+                           double scale_offset,
+                           struct LrsPoint *out);
+
+// Writes in `out` the line string of an LRM between two measures.
+// With a non-zero `lateral_offset_m`, the line string is moved perpendicularly to the LRM by that many meters,
+// positive on the left when facing increasing measures.
+//
+// # Safety
+// `lrs` must be a valid LRS, the anchor names nul-terminated strings and `out` writable.
+enum LrsStatus lrs_resolve_range(const struct Lrs *lrs,
+                                 size_t lrm_index,
+                                 const char *from_anchor_name,
+                                 double from_scale_offset,
+                                 const char *to_anchor_name,
+                                 double to_scale_offset,
+                                 double lateral_offset_m,
+                                 struct LrsPoints *out);
+
+// Releases the positions written by `lrs_resolve_range`, and sets them to null. Null is ignored.
+//
+// # Safety
+// `points` must have been written by `lrs_resolve_range`, and not have been released yet.
+void lrs_points_free(struct LrsPoints *points);
+
+// Writes in `out` the measure of a position projected on an LRM.
+//
+// # Safety
+// `lrs` must be a valid LRS and `out` writable.
+enum LrsStatus lrs_lookup(const struct Lrs *lrs,
+                          struct LrsPoint point,
+                          size_t lrm_index,
+                          struct LrsProjection *out);
+
+// Releases the anchor name written by `lrs_lookup`, and sets it to null. Null is ignored.
+//
+// # Safety
+// `projection` must have been written by `lrs_lookup`, and not have been released yet.
+void lrs_projection_free(struct LrsProjection *projection);
+
+// Stable code of the last error of the thread, such as `UNKNOWN_ANCHOR`, or null if there was none.
+const char *lrs_last_error_code(void);
+
+// Description of the last error of the thread, meant for humans, or null if there was none.
+const char *lrs_last_error_message(void);
+
+// Allocates `len` zeroed bytes in the memory of the library. Returns null if `len` is 0.
+uint8_t *lrs_alloc(size_t len);
+
+// Releases a buffer returned by `lrs_alloc`. Null is ignored.
+//
+// # Safety
+// `data` must have been returned by `lrs_alloc` with the same `len`, and not have been released yet.
+void lrs_dealloc(uint8_t *data, size_t len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LIBLRS_H */
@@ -0,0 +1,509 @@
+//! C bindings of liblrs, e.g. to embed an LRS in C or C++ software.
+//!
+//! The header is `include/liblrs.h`, generated with cbindgen.
+//!
+//! Ownership rules:
+//! - An `Lrs` returned by `lrs_load` or `lrs_open` is owned by the caller and must be released with `lrs_free`.
+//! - The `LrsPoints` and `LrsProjection` filled by the functions own their memory
+//!   and must be released with `lrs_points_free` and `lrs_projection_free`.
+//! - The strings given to the functions are borrowed: they must be valid UTF-8, nul-terminated, and are not kept.
+//! - The strings returned by `lrs_last_error_code` and `lrs_last_error_message` are owned by the library.
+//!   They are valid until the next call that fails on the same thread.
+//! - A buffer returned by `lrs_alloc` is owned by the caller and must be released with `lrs_dealloc`.
+//!   It lets a host that cannot allocate in the memory of the library, such as a WASI runtime, give it data and strings.
+//!
+//! The functions that can fail return an `LrsStatus`. On error, the output is not written,
+//! and `lrs_last_error_code` gives a stable code, such as `UNKNOWN_ANCHOR`.
+//! An `Lrs` can be shared between threads as long as it is not freed.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+
+use liblrs::error_code::LrsErrorCode;
+use liblrs::lrm_scale::LrmScaleMeasure;
+use liblrs::lrs_ext::ExtLrs;
+
+/// A loaded Linear Referencing System. It is opaque: use it through the functions.
+pub struct Lrs(ExtLrs);
+
+/// The result of a function that can fail.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LrsStatus {
+    /// The function succeeded and wrote its output.
+    Ok = 0,
+    /// The function failed, see `lrs_last_error_code` and `lrs_last_error_message`.
+    Error = 1,
+}
+
+/// A geographical position: the longitude and the latitude, or projected coordinates.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LrsPoint {
+    /// Position on x-axis or longitude.
+    pub x: f64,
+    /// Position on y-axis or latitude.
+    pub y: f64,
+}
+
+/// The positions of a line string. Release it with `lrs_points_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct LrsPoints {
+    /// The first position.
+    pub points: *mut LrsPoint,
+    /// How many positions there are.
+    pub len: usize,
+}
+
+/// The measure of a position projected on an LRM. Release it with `lrs_projection_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct LrsProjection {
+    /// Name of the anchor of the measure.
+    pub anchor_name: *mut c_char,
+    /// Offset from the anchor, in the unit of the scale.
+    pub scale_offset: f64,
+    /// Distance between the position and the LRM, positive on the left.
+    pub orthogonal_offset: f64,
+}
+
+thread_local! {
+    // The code and the message of the last error of the thread
+    static LAST_ERROR: RefCell<Option<(CString, CString)>> = const { RefCell::new(None) };
+}
+
+fn fail(code: &str, message: impl ToString) -> LrsStatus {
+    // A code or a message with a nul byte is cut there
+    let c_string = |text: String| {
+        let text = text.split('\0').next().unwrap_or_default().to_owned();
+        CString::new(text).unwrap_or_default()
+    };
+    LAST_ERROR.with(|error| {
+        *error.borrow_mut() = Some((c_string(code.to_owned()), c_string(message.to_string())));
+    });
+    LrsStatus::Error
+}
+
+fn fail_with(error: impl Into<LrsErrorCode>) -> LrsStatus {
+    let error = error.into();
+    fail(error.code(), &error)
+}
+
+fn invalid_argument(name: &str) -> LrsStatus {
+    fail(
+        "INVALID_ARGUMENT",
+        format!("{name} is null or is not valid UTF-8"),
+    )
+}
+
+// SAFETY: the caller gives a null pointer or a nul-terminated string that outlives the result
+unsafe fn str_arg<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(text) }.to_str().ok()
+}
+
+fn check_lrm(lrs: &Lrs, lrm_index: usize) -> Result<(), LrsStatus> {
+    if lrm_index < lrs.0.lrm_len() {
+        Ok(())
+    } else {
+        Err(fail_with(LrsErrorCode::LrmNotFound { lrm_index }))
+    }
+}
+
+/// Loads an LRS from the `len` bytes of a file. Returns null on error.
+///
+/// # Safety
+/// `data` must point to `len` readable bytes. They are not kept.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_load(data: *const u8, len: usize) -> *mut Lrs {
+    if data.is_null() {
+        invalid_argument("data");
+        return std::ptr::null_mut();
+    }
+    let data = unsafe { std::slice::from_raw_parts(data, len) };
+    match ExtLrs::load(data) {
+        Ok(lrs) => Box::into_raw(Box::new(Lrs(lrs))),
+        Err(error) => {
+            fail_with(error);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Loads an LRS from a file. Returns null on error.
+///
+/// # Safety
+/// `path` must be a nul-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_open(path: *const c_char) -> *mut Lrs {
+    let Some(path) = (unsafe { str_arg(path) }) else {
+        invalid_argument("path");
+        return std::ptr::null_mut();
+    };
+    match ExtLrs::open(path) {
+        Ok(lrs) => Box::into_raw(Box::new(Lrs(lrs))),
+        Err(error) => {
+            fail_with(error);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Releases an LRS. Null is ignored.
+///
+/// # Safety
+/// `lrs` must have been returned by `lrs_load` or `lrs_open`, and not have been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_free(lrs: *mut Lrs) {
+    if !lrs.is_null() {
+        drop(unsafe { Box::from_raw(lrs) });
+    }
+}
+
+/// How many LRMs compose the LRS.
+///
+/// # Safety
+/// `lrs` must be a valid LRS.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_lrm_len(lrs: *const Lrs) -> usize {
+    unsafe { lrs.as_ref() }.map_or(0, |lrs| lrs.0.lrm_len())
+}
+
+/// Writes in `out_lrm_index` the index of the LRM with the identifier `lrm_id`.
+///
+/// # Safety
+/// `lrs` must be a valid LRS, `lrm_id` a nul-terminated string and `out_lrm_index` writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_find_lrm(
+    lrs: *const Lrs,
+    lrm_id: *const c_char,
+    out_lrm_index: *mut usize,
+) -> LrsStatus {
+    let (Some(lrs), Some(lrm_id)) = (unsafe { lrs.as_ref() }, unsafe { str_arg(lrm_id) }) else {
+        return invalid_argument("lrs or lrm_id");
+    };
+    if out_lrm_index.is_null() {
+        return invalid_argument("out_lrm_index");
+    }
+    match lrs.0.get_lrm(lrm_id) {
+        Some(lrm_index) => {
+            unsafe { out_lrm_index.write(lrm_index) };
+            LrsStatus::Ok
+        }
+        None => fail("LRM_NOT_FOUND", format!("no lrm with the id {lrm_id}")),
+    }
+}
+
+/// Writes in `out` the position of the measure `anchor_name` + `scale_offset` on an LRM.
+///
+/// # Safety
+/// `lrs` must be a valid LRS, `anchor_name` a nul-terminated string and `out` writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_resolve(
+    lrs: *const Lrs,
+    lrm_index: usize,
+    anchor_name: *const c_char,
+    scale_offset: f64,
+    out: *mut LrsPoint,
+) -> LrsStatus {
+    let (Some(lrs), Some(anchor_name)) = (unsafe { lrs.as_ref() }, unsafe { str_arg(anchor_name) })
+    else {
+        return invalid_argument("lrs or anchor_name");
+    };
+    if out.is_null() {
+        return invalid_argument("out");
+    }
+    let measure = LrmScaleMeasure::new(anchor_name, scale_offset);
+    match lrs.0.resolve(lrm_index, &measure) {
+        Ok(point) => {
+            unsafe {
+                out.write(LrsPoint {
+                    x: point.x(),
+                    y: point.y(),
+                })
+            };
+            LrsStatus::Ok
+        }
+        Err(error) => fail_with(error),
+    }
+}
+
+/// Writes in `out` the line string of an LRM between two measures.
+/// With a non-zero `lateral_offset_m`, the line string is moved perpendicularly to the LRM by that many meters,
+/// positive on the left when facing increasing measures.
+///
+/// # Safety
+/// `lrs` must be a valid LRS, the anchor names nul-terminated strings and `out` writable.
+#[unsafe(no_mangle)]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn lrs_resolve_range(
+    lrs: *const Lrs,
+    lrm_index: usize,
+    from_anchor_name: *const c_char,
+    from_scale_offset: f64,
+    to_anchor_name: *const c_char,
+    to_scale_offset: f64,
+    lateral_offset_m: f64,
+    out: *mut LrsPoints,
+) -> LrsStatus {
+    let (Some(lrs), Some(from), Some(to)) = (
+        unsafe { lrs.as_ref() },
+        unsafe { str_arg(from_anchor_name) },
+        unsafe { str_arg(to_anchor_name) },
+    ) else {
+        return invalid_argument("lrs, from_anchor_name or to_anchor_name");
+    };
+    if out.is_null() {
+        return invalid_argument("out");
+    }
+    let from = LrmScaleMeasure::new(from, from_scale_offset);
+    let to = LrmScaleMeasure::new(to, to_scale_offset);
+    let lateral_offset_m = (lateral_offset_m != 0.).then_some(lateral_offset_m);
+    match lrs.0.resolve_range(lrm_index, &from, &to, lateral_offset_m) {
+        Ok(coords) => {
+            let points: Box<[_]> = coords
+                .into_iter()
+                .map(|coord| LrsPoint {
+                    x: coord.x,
+                    y: coord.y,
+                })
+                .collect();
+            let len = points.len();
+            let points = Box::into_raw(points) as *mut LrsPoint;
+            unsafe { out.write(LrsPoints { points, len }) };
+            LrsStatus::Ok
+        }
+        Err(error) => fail_with(error),
+    }
+}
+
+/// Releases the positions written by `lrs_resolve_range`, and sets them to null. Null is ignored.
+///
+/// # Safety
+/// `points` must have been written by `lrs_resolve_range`, and not have been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_points_free(points: *mut LrsPoints) {
+    let Some(points) = (unsafe { points.as_mut() }) else {
+        return;
+    };
+    if !points.points.is_null() {
+        let slice = std::ptr::slice_from_raw_parts_mut(points.points, points.len);
+        drop(unsafe { Box::from_raw(slice) });
+    }
+    points.points = std::ptr::null_mut();
+    points.len = 0;
+}
+
+/// Writes in `out` the measure of a position projected on an LRM.
+///
+/// # Safety
+/// `lrs` must be a valid LRS and `out` writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_lookup(
+    lrs: *const Lrs,
+    point: LrsPoint,
+    lrm_index: usize,
+    out: *mut LrsProjection,
+) -> LrsStatus {
+    let Some(lrs) = (unsafe { lrs.as_ref() }) else {
+        return invalid_argument("lrs");
+    };
+    if out.is_null() {
+        return invalid_argument("out");
+    }
+    if let Err(status) = check_lrm(lrs, lrm_index) {
+        return status;
+    }
+    let point = geo_types::Point::new(point.x, point.y);
+    match lrs.0.lookup(point, lrm_index) {
+        Ok(projection) => {
+            let measure = projection.measure.measure;
+            let anchor_name = CString::new(measure.anchor_name).unwrap_or_default();
+            unsafe {
+                out.write(LrsProjection {
+                    anchor_name: anchor_name.into_raw(),
+                    scale_offset: measure.scale_offset,
+                    orthogonal_offset: projection.orthogonal_offset,
+                })
+            };
+            LrsStatus::Ok
+        }
+        Err(error) => fail_with(error),
+    }
+}
+
+/// Releases the anchor name written by `lrs_lookup`, and sets it to null. Null is ignored.
+///
+/// # Safety
+/// `projection` must have been written by `lrs_lookup`, and not have been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_projection_free(projection: *mut LrsProjection) {
+    let Some(projection) = (unsafe { projection.as_mut() }) else {
+        return;
+    };
+    if !projection.anchor_name.is_null() {
+        drop(unsafe { CString::from_raw(projection.anchor_name) });
+    }
+    projection.anchor_name = std::ptr::null_mut();
+}
+
+/// Stable code of the last error of the thread, such as `UNKNOWN_ANCHOR`, or null if there was none.
+#[unsafe(no_mangle)]
+pub extern "C" fn lrs_last_error_code() -> *const c_char {
+    LAST_ERROR.with(|error| {
+        error
+            .borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |(code, _)| code.as_ptr())
+    })
+}
+
+/// Description of the last error of the thread, meant for humans, or null if there was none.
+#[unsafe(no_mangle)]
+pub extern "C" fn lrs_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|error| {
+        error
+            .borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |(_, message)| message.as_ptr())
+    })
+}
+
+/// Allocates `len` zeroed bytes in the memory of the library. Returns null if `len` is 0.
+#[unsafe(no_mangle)]
+pub extern "C" fn lrs_alloc(len: usize) -> *mut u8 {
+    if len == 0 {
+        return std::ptr::null_mut();
+    }
+    Box::into_raw(vec![0u8; len].into_boxed_slice()) as *mut u8
+}
+
+/// Releases a buffer returned by `lrs_alloc`. Null is ignored.
+///
+/// # Safety
+/// `data` must have been returned by `lrs_alloc` with the same `len`, and not have been released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn lrs_dealloc(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(data, len)) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo_types::coord;
+    use liblrs::builder::{AnchorOnLrm, Builder};
+    use liblrs::properties;
+
+    use super::*;
+
+    fn data() -> Vec<u8> {
+        let mut builder = Builder::new();
+        let start = builder.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = builder.add_node("b", coord! {x: 0.01, y: 0.}, properties!());
+        let geometry = geo_types::LineString::from(vec![(0., 0.), (0.01, 0.)]);
+        let traversal = builder.add_traversal_from_geometry("t", &geometry, start, end);
+        let anchors = [("0", 0.), ("1", 0.01)].map(|(name, x)| AnchorOnLrm {
+            anchor_index: builder.add_anchor(name, Some(name), coord! {x: x, y: 0.}, properties!()),
+            distance_along_lrm: x * 100_000.,
+        });
+        builder.add_lrm("lrm", traversal, &anchors, properties!());
+        builder.build_data(properties!()).to_vec()
+    }
+
+    fn last_error_code() -> &'static str {
+        unsafe { CStr::from_ptr(lrs_last_error_code()) }
+            .to_str()
+            .unwrap()
+    }
+
+    #[test]
+    fn resolve_and_lookup() {
+        let data = data();
+        let lrs = unsafe { lrs_load(data.as_ptr(), data.len()) };
+        assert!(!lrs.is_null());
+        assert_eq!(unsafe { lrs_lrm_len(lrs) }, 1);
+
+        let mut lrm_index = usize::MAX;
+        let status = unsafe { lrs_find_lrm(lrs, c"lrm".as_ptr(), &mut lrm_index) };
+        assert_eq!((status, lrm_index), (LrsStatus::Ok, 0));
+
+        let mut point = LrsPoint { x: 0., y: 0. };
+        let status = unsafe { lrs_resolve(lrs, 0, c"0".as_ptr(), 500., &mut point) };
+        assert_eq!(status, LrsStatus::Ok);
+        assert!((point.x - 0.005).abs() < 1e-6);
+
+        let status = unsafe { lrs_resolve(lrs, 0, c"unknown".as_ptr(), 0., &mut point) };
+        assert_eq!(status, LrsStatus::Error);
+        assert_eq!(last_error_code(), "UNKNOWN_ANCHOR");
+
+        let mut range = LrsPoints {
+            points: std::ptr::null_mut(),
+            len: 0,
+        };
+        let status = unsafe {
+            lrs_resolve_range(
+                lrs,
+                0,
+                c"0".as_ptr(),
+                0.,
+                c"0".as_ptr(),
+                500.,
+                0.,
+                &mut range,
+            )
+        };
+        assert_eq!(status, LrsStatus::Ok);
+        assert_eq!(range.len, 2);
+        unsafe { lrs_points_free(&mut range) };
+        assert!(range.points.is_null());
+
+        let mut projection = LrsProjection {
+            anchor_name: std::ptr::null_mut(),
+            scale_offset: 0.,
+            orthogonal_offset: 0.,
+        };
+        let position = LrsPoint { x: 0.005, y: 0. };
+        let status = unsafe { lrs_lookup(lrs, position, 0, &mut projection) };
+        assert_eq!(status, LrsStatus::Ok);
+        assert_eq!(unsafe { CStr::from_ptr(projection.anchor_name) }, c"0");
+        assert!((projection.scale_offset - 500.).abs() < 1.);
+        unsafe { lrs_projection_free(&mut projection) };
+
+        let status = unsafe { lrs_lookup(lrs, position, 0, std::ptr::null_mut()) };
+        assert_eq!(status, LrsStatus::Error);
+        assert_eq!(last_error_code(), "INVALID_ARGUMENT");
+        let status = unsafe { lrs_resolve(lrs, 0, c"0".as_ptr(), 0., std::ptr::null_mut()) };
+        assert_eq!(status, LrsStatus::Error);
+        assert_eq!(last_error_code(), "INVALID_ARGUMENT");
+
+        let status = unsafe { lrs_lookup(lrs, position, 1, &mut projection) };
+        assert_eq!(status, LrsStatus::Error);
+        assert_eq!(last_error_code(), "LRM_NOT_FOUND");
+
+        unsafe { lrs_free(lrs) };
+    }
+
+    #[test]
+    fn load_from_buffer() {
+        let data = data();
+        let buffer = lrs_alloc(data.len());
+        unsafe { std::ptr::copy_nonoverlapping(data.as_ptr(), buffer, data.len()) };
+        let lrs = unsafe { lrs_load(buffer, data.len()) };
+        unsafe { lrs_dealloc(buffer, data.len()) };
+        assert_eq!(unsafe { lrs_lrm_len(lrs) }, 1);
+        unsafe { lrs_free(lrs) };
+        assert!(lrs_alloc(0).is_null());
+    }
+
+    #[test]
+    fn load_error() {
+        let data = b"not an lrs";
+        assert!(unsafe { lrs_load(data.as_ptr(), data.len()) }.is_null());
+        assert_eq!(last_error_code(), "INVALID_FILE");
+        assert!(unsafe { lrs_open(c"/not/a/file.lrs".as_ptr()) }.is_null());
+    }
+}
@@ -6,12 +6,18 @@ rust = tomllib.load(open("Cargo.toml", "rb"))
 python = tomllib.load(open("python/Cargo.toml", "rb"))
 pyproject = tomllib.load(open("python/pyproject.toml", "rb"))
 wasm = json.load(open("wasm/package.json", "rb"))
+c = tomllib.load(open("c/Cargo.toml", "rb"))
+node = tomllib.load(open("node/Cargo.toml", "rb"))
+node_package = json.load(open("node/package.json", "rb"))
 
 rust_version = rust["package"]["version"]
 python_version = python["package"]["version"]
 pyproject_version = pyproject["project"]["version"]
 wasm_version = wasm["version"]
-if rust_version == python_version == pyproject_version == wasm_version:
+c_version = c["package"]["version"]
+node_version = node["package"]["version"]
+node_package_version = node_package["version"]
+if rust_version == python_version == pyproject_version == wasm_version == c_version == node_version == node_package_version:
     print("all versions are the same")
     sys.exit(0)
 else:
@@ -19,6 +25,9 @@ else:
           f"  rust: {rust_version}\n"
           f"  python: {python_version}\n"
           f"  pyproject: {pyproject_version}\n"
-          f"  wasm: {wasm_version}\n")
+          f"  wasm: {wasm_version}\n"
+          f"  c: {c_version}\n"
+          f"  node: {node_version}\n"
+          f"  node package: {node_package_version}\n")
 
     sys.exit(1)
@@ -0,0 +1,62 @@
+import assert from "node:assert/strict";
+import { writeFileSync, mkdtempSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { test } from "node:test";
+
+import { Lrs } from "../index.js";
+
+const feature = (geometry, properties) => ({ type: "Feature", geometry, properties });
+const GEOJSON = JSON.stringify({
+  type: "FeatureCollection",
+  features: [
+    feature({ type: "LineString", coordinates: [[0, 0], [0.01, 0]] }, { id: "lrm" }),
+    feature({ type: "Point", coordinates: [0, 0] }, { lrm_id: "lrm", name: "0", measure: 0 }),
+    feature({ type: "Point", coordinates: [0.01, 0] }, { lrm_id: "lrm", name: "1", measure: 1000 }),
+  ],
+});
+
+test("resolve and lookup", () => {
+  const lrs = Lrs.fromGeojson(GEOJSON);
+  assert.equal(lrs.lrmLen(), 1);
+  const lrm = lrs.findLrm("lrm");
+  assert.equal(lrm, 0);
+  assert.equal(lrs.findLrm("unknown"), null);
+
+  const point = lrs.resolve(lrm, { anchorName: "0", scaleOffset: 500 });
+  assert.ok(Math.abs(point.x - 0.005) < 1e-6);
+
+  const range = lrs.resolveRange(lrm, { anchorName: "0", scaleOffset: 0 }, { anchorName: "0", scaleOffset: 500 });
+  assert.ok(range instanceof Float64Array);
+  assert.equal(range.length, 4);
+
+  const [projection] = lrs.lookup(point, lrm);
+  assert.equal(projection.measure.anchorName, "0");
+  assert.ok(Math.abs(projection.measure.scaleOffset - 500) < 1);
+
+  assert.throws(() => lrs.resolve(lrm, { anchorName: "unknown", scaleOffset: 0 }), { code: "UNKNOWN_ANCHOR" });
+  assert.throws(() => lrs.lookup(point, 1), { code: "LRM_NOT_FOUND" });
+});
+
+test("batches", async () => {
+  const lrs = Lrs.fromGeojson(GEOJSON);
+  const coords = new Float64Array([0.0025, 0, 0.0075, 0, 10, 10]);
+  for (const batch of [lrs.lookupBatch(coords, 100), await lrs.lookupBatchAsync(coords, 100)]) {
+    assert.deepEqual(Array.from(batch.lrmIndices), [0, 0, -1]);
+    assert.deepEqual(batch.anchorNames.slice(0, 2), ["0", "0"]);
+    assert.equal(batch.anchorNames[2], null);
+    assert.ok(Number.isNaN(batch.scaleOffsets[2]));
+  }
+
+  const positions = lrs.resolveBatch(0, ["0", "unknown"], new Float64Array([250, 0]));
+  assert.equal(positions.length, 4);
+  assert.ok(Math.abs(positions[0] - 0.0025) < 1e-6);
+  assert.ok(Number.isNaN(positions[2]));
+});
+
+test("open", async () => {
+  const path = join(mkdtempSync(join(tmpdir(), "liblrs-")), "missing.lrs");
+  await assert.rejects(Lrs.open(path), /INVALID_FILE/);
+  writeFileSync(path, "not an lrs");
+  await assert.rejects(Lrs.open(path), /INVALID_FILE/);
+});
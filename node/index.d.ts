@@ -0,0 +1,138 @@
+/* auto-generated by NAPI-RS */
+/* eslint-disable */
+/** A Linear Referencing System. */
+export declare class Lrs {
+  /** Loads an LRS from the content of a file. */
+  static load(data: Buffer): Lrs
+  /**
+   * Reads an LRS file without blocking the event loop.
+   * The promise is rejected with an error whose message starts with the code, such as `INVALID_FILE`.
+   */
+  static open(path: string): Promise<Lrs>
+  /**
+   * Builds an LRS from a GeoJSON FeatureCollection: each LineString is an LRM with the `id` property,
+   * each Point with the `lrm_id`, `name` and `measure` properties is an anchor of that LRM.
+   */
+  static fromGeojson(geojson: string): Lrs
+  /** Coordinate reference system of the coordinates, such as `EPSG:4326`, if it was recorded. */
+  crs(): string | null
+  /** How many LRMs compose the LRS. */
+  lrmLen(): number
+  /** `id` of the LRM. */
+  getLrmScaleId(lrmIndex: number): string
+  /** Index of the LRM with an `id`, or `null` if there is none. */
+  findLrm(lrmId: string): number | null
+  /** The geometry of the LRM, as interleaved coordinates. */
+  getLrmGeom(lrmIndex: number): Float64Array
+  /** All the anchors of the LRM. */
+  getAnchors(lrmIndex: number): Array<Anchor>
+  /** The measures at both ends of the LRM and its length in meters. */
+  lrmBounds(lrmIndex: number): LrmBounds
+  /** The position of a measure on the LRM. */
+  resolve(lrmIndex: number, measure: LrmScaleMeasure): Point
+  /**
+   * The positions of many measures of the same LRM, as interleaved coordinates.
+   * The coordinates of a measure that cannot be resolved are `NaN`.
+   */
+  resolveBatch(lrmIndex: number, anchorNames: Array<string>, scaleOffsets: Float64Array): Float64Array
+  /**
+   * The geometry of the LRM between two measures, as interleaved coordinates.
+   * With a `lateralOffsetM`, the range is moved perpendicularly to the LRM by that many meters,
+   * positive on the left when facing increasing measures.
+   */
+  resolveRange(lrmIndex: number, from: LrmScaleMeasure, to: LrmScaleMeasure, lateralOffsetM?: number | undefined | null): Float64Array
+  /** Projects a point on the LRM. The nearest projection is the first item. */
+  lookup(point: Point, lrmIndex: number): Array<LrmProjection>
+  /** Projects a point on every LRM at most `maxDistance` away: meters for geographic coordinates, coordinate units for schematic ones. The nearest projection is the first item. */
+  lookupAll(point: Point, maxDistance: number): Array<LrmProjection>
+  /** Finds the nearest LRM at most `maxDistance` away, in the unit of `lookupAll`, and the measure of the point on it. */
+  locate(point: Point, maxDistance: number): LocatedPoint | null
+  /** Projects every point of interleaved coordinates on the nearest LRM at most `maxDistance` away, in the unit of `lookupAll`. */
+  lookupBatch(coords: Float64Array, maxDistance: number): BatchProjection
+  /** Same as `lookupBatch`, on the thread pool, without blocking the event loop. */
+  lookupBatchAsync(coords: Float64Array, maxDistance: number): Promise<BatchProjection>
+  /** The LRMs and their anchors as a GeoJSON FeatureCollection string. */
+  toGeojson(): string
+  /** The geometry of the LRM as a GeoJSON Feature string. */
+  lrmGeojson(lrmIndex: number): string
+  /** Properties of the LRS. */
+  lrsProperties(): Record<string, string>
+  /** Properties of the LRM. */
+  lrmProperties(lrmIndex: number): Record<string, string>
+}
+
+/** A reference point of an LRM, such as a milestone. */
+export interface Anchor {
+  /** Name of the anchor, `-` if it is unnamed. */
+  name: string
+  /** Projected position on the curve, if it is known. */
+  position?: Point
+  /** Position on the curve, between 0 and 1. */
+  curvePosition: number
+  /** Position on the scale. */
+  scalePosition: number
+}
+
+/** The nearest projection of every point of a batch, one item per point in each array. */
+export interface BatchProjection {
+  /** Index of the nearest LRM, -1 when no LRM is close enough. */
+  lrmIndices: Int32Array
+  /** Name of the anchor of the measure, `null` when no LRM is close enough. */
+  anchorNames: Array<string | undefined | null>
+  /** Offset of the measure from the anchor, `NaN` when no LRM is close enough. */
+  scaleOffsets: Float64Array
+  /** Distance from the LRM, positive on the left, `NaN` when no LRM is close enough. */
+  orthogonalOffsets: Float64Array
+}
+
+/** The nearest position of a point on all the LRMs. */
+export interface LocatedPoint {
+  /** Index of the LRM. */
+  lrmIndex: number
+  /** `id` of the LRM. */
+  lrmId: string
+  /** Name of the anchor of the measure. */
+  anchorName: string
+  /** Offset of the measure from the anchor, in the unit of the scale. */
+  scaleOffset: number
+  /** Distance in meters from the LRM: positive on the left, negative on the right. */
+  lateralOffsetM: number
+  /** Distance in meters between the point and the LRM. */
+  distanceM: number
+}
+
+/** The measures at both ends of an LRM and its length. */
+export interface LrmBounds {
+  /** The smallest measure of the LRM. */
+  minMeasure: LrmScaleMeasure
+  /** The largest measure of the LRM. */
+  maxMeasure: LrmScaleMeasure
+  /** The length of the LRM, in meters. */
+  length: number
+}
+
+/** The result of the projection of a point on an LRM. */
+export interface LrmProjection {
+  /** Index of the LRM. */
+  lrmIndex: number
+  /** The measure of the point on the LRM. */
+  measure: LrmScaleMeasure
+  /** Distance between the point and the LRM, positive on the left. */
+  orthogonalOffset: number
+}
+
+/** A measure on a scale: the name of an anchor and the offset from it. */
+export interface LrmScaleMeasure {
+  /** Name of the anchor. */
+  anchorName: string
+  /** Offset from the anchor, in the unit of the scale. */
+  scaleOffset: number
+}
+
+/** A geographical point: the longitude and the latitude, or projected coordinates. */
+export interface Point {
+  /** Position on x-axis or longitude. */
+  x: number
+  /** Position on y-axis or latitude. */
+  y: number
+}
@@ -0,0 +1,510 @@
+//! Native Node.js bindings of the [`ExtLrs`], for backend services.
+//!
+//! The geometries are exchanged as `Float64Array`s of interleaved coordinates: `[x0, y0, x1, y1, …]`.
+//! The files and the batches can be processed on the thread pool of libuv, without blocking the event loop.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use liblrs::builder::{Builder, GeoJsonMapping};
+use liblrs::error_code::LrsErrorCode;
+use liblrs::lrs_ext::ExtLrs;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// The errors thrown in JavaScript have a stable `code`, such as `LRM_NOT_FOUND`.
+type Result<T> = std::result::Result<T, Error<&'static str>>;
+
+fn lrs_error(error: impl Into<LrsErrorCode>) -> Error<&'static str> {
+    let error = error.into();
+    Error::new(error.code(), error.to_string())
+}
+
+// The tasks of libuv can only fail with a napi status: the code is given at the start of the message
+fn task_error(error: impl Into<LrsErrorCode>) -> Error {
+    let error = error.into();
+    Error::from_reason(format!("{}: {error}", error.code()))
+}
+
+fn coords_to_array(coords: impl IntoIterator<Item = geo_types::Coord>) -> Float64Array {
+    Float64Array::new(coords.into_iter().flat_map(|c| [c.x, c.y]).collect())
+}
+
+fn array_to_points(coords: &[f64]) -> Result<Vec<geo_types::Point>> {
+    if coords.len() % 2 != 0 {
+        return Err(Error::new(
+            "INVALID_ARGUMENT",
+            "the coordinates must be interleaved x and y",
+        ));
+    }
+    Ok(coords
+        .chunks_exact(2)
+        .map(|xy| geo_types::Point::new(xy[0], xy[1]))
+        .collect())
+}
+
+/// A geographical point: the longitude and the latitude, or projected coordinates.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct Point {
+    /// Position on x-axis or longitude.
+    pub x: f64,
+    /// Position on y-axis or latitude.
+    pub y: f64,
+}
+
+impl From<geo_types::Point> for Point {
+    fn from(value: geo_types::Point) -> Self {
+        Self {
+            x: value.x(),
+            y: value.y(),
+        }
+    }
+}
+
+impl From<Point> for geo_types::Point {
+    fn from(value: Point) -> Self {
+        Self::new(value.x, value.y)
+    }
+}
+
+/// A measure on a scale: the name of an anchor and the offset from it.
+#[napi(object)]
+#[derive(Clone)]
+pub struct LrmScaleMeasure {
+    /// Name of the anchor.
+    pub anchor_name: String,
+    /// Offset from the anchor, in the unit of the scale.
+    pub scale_offset: f64,
+}
+
+impl From<&liblrs::lrm_scale::LrmScaleMeasure> for LrmScaleMeasure {
+    fn from(value: &liblrs::lrm_scale::LrmScaleMeasure) -> Self {
+        Self {
+            anchor_name: value.anchor_name.clone(),
+            scale_offset: value.scale_offset,
+        }
+    }
+}
+
+impl From<&LrmScaleMeasure> for liblrs::lrm_scale::LrmScaleMeasure {
+    fn from(value: &LrmScaleMeasure) -> Self {
+        Self::new(&value.anchor_name, value.scale_offset)
+    }
+}
+
+/// A reference point of an LRM, such as a milestone.
+#[napi(object)]
+pub struct Anchor {
+    /// Name of the anchor, `-` if it is unnamed.
+    pub name: String,
+    /// Projected position on the curve, if it is known.
+    pub position: Option<Point>,
+    /// Position on the curve, between 0 and 1.
+    pub curve_position: f64,
+    /// Position on the scale.
+    pub scale_position: f64,
+}
+
+impl From<&liblrs::lrm_scale::Anchor> for Anchor {
+    fn from(value: &liblrs::lrm_scale::Anchor) -> Self {
+        let name = match value {
+            liblrs::lrm_scale::Anchor::Named(value) => value.name.clone(),
+            liblrs::lrm_scale::Anchor::Unnamed(_) => "-".to_owned(),
+        };
+        Self {
+            name,
+            position: value.point().map(Point::from),
+            curve_position: value.curve_position(),
+            scale_position: value.scale_position(),
+        }
+    }
+}
+
+/// The measures at both ends of an LRM and its length.
+#[napi(object)]
+pub struct LrmBounds {
+    /// The smallest measure of the LRM.
+    pub min_measure: LrmScaleMeasure,
+    /// The largest measure of the LRM.
+    pub max_measure: LrmScaleMeasure,
+    /// The length of the LRM, in meters.
+    pub length: f64,
+}
+
+/// The result of the projection of a point on an LRM.
+#[napi(object)]
+pub struct LrmProjection {
+    /// Index of the LRM.
+    pub lrm_index: u32,
+    /// The measure of the point on the LRM.
+    pub measure: LrmScaleMeasure,
+    /// Distance between the point and the LRM, positive on the left.
+    pub orthogonal_offset: f64,
+}
+
+impl From<&liblrs::lrs::LrmProjection> for LrmProjection {
+    fn from(value: &liblrs::lrs::LrmProjection) -> Self {
+        Self {
+            lrm_index: value.measure.lrm.0 as u32,
+            measure: (&value.measure.measure).into(),
+            orthogonal_offset: value.orthogonal_offset,
+        }
+    }
+}
+
+/// The nearest position of a point on all the LRMs.
+#[napi(object)]
+pub struct LocatedPoint {
+    /// Index of the LRM.
+    pub lrm_index: u32,
+    /// `id` of the LRM.
+    pub lrm_id: String,
+    /// Name of the anchor of the measure.
+    pub anchor_name: String,
+    /// Offset of the measure from the anchor, in the unit of the scale.
+    pub scale_offset: f64,
+    /// Distance in meters from the LRM: positive on the left, negative on the right.
+    pub lateral_offset_m: f64,
+    /// Distance in meters between the point and the LRM.
+    pub distance_m: f64,
+}
+
+impl From<liblrs::lrs_ext::LocatedPoint> for LocatedPoint {
+    fn from(value: liblrs::lrs_ext::LocatedPoint) -> Self {
+        Self {
+            lrm_index: value.lrm_index as u32,
+            lrm_id: value.lrm_id,
+            anchor_name: value.anchor_name,
+            scale_offset: value.scale_offset,
+            lateral_offset_m: value.lateral_offset_m,
+            distance_m: value.distance_m,
+        }
+    }
+}
+
+/// The nearest projection of every point of a batch, one item per point in each array.
+#[napi(object)]
+pub struct BatchProjection {
+    /// Index of the nearest LRM, -1 when no LRM is close enough.
+    pub lrm_indices: Int32Array,
+    /// Name of the anchor of the measure, `null` when no LRM is close enough.
+    pub anchor_names: Vec<Option<String>>,
+    /// Offset of the measure from the anchor, `NaN` when no LRM is close enough.
+    pub scale_offsets: Float64Array,
+    /// Distance from the LRM, positive on the left, `NaN` when no LRM is close enough.
+    pub orthogonal_offsets: Float64Array,
+}
+
+impl From<Vec<Vec<liblrs::lrs::LrmProjection>>> for BatchProjection {
+    fn from(projections: Vec<Vec<liblrs::lrs::LrmProjection>>) -> Self {
+        let mut lrm_indices = Vec::with_capacity(projections.len());
+        let mut anchor_names = Vec::with_capacity(projections.len());
+        let mut scale_offsets = Vec::with_capacity(projections.len());
+        let mut orthogonal_offsets = Vec::with_capacity(projections.len());
+        for projection in projections.iter().map(|projections| projections.first()) {
+            match projection {
+                Some(projection) => {
+                    lrm_indices.push(projection.measure.lrm.0 as i32);
+                    anchor_names.push(Some(projection.measure.measure.anchor_name.clone()));
+                    scale_offsets.push(projection.measure.measure.scale_offset);
+                    orthogonal_offsets.push(projection.orthogonal_offset);
+                }
+                None => {
+                    lrm_indices.push(-1);
+                    anchor_names.push(None);
+                    scale_offsets.push(f64::NAN);
+                    orthogonal_offsets.push(f64::NAN);
+                }
+            }
+        }
+        Self {
+            lrm_indices: Int32Array::new(lrm_indices),
+            anchor_names,
+            scale_offsets: Float64Array::new(scale_offsets),
+            orthogonal_offsets: Float64Array::new(orthogonal_offsets),
+        }
+    }
+}
+
+/// Reads an LRS file on the thread pool, see [`Lrs::open`].
+pub struct OpenTask {
+    path: String,
+}
+
+impl Task for OpenTask {
+    type Output = ExtLrs;
+    type JsValue = Lrs;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        ExtLrs::open(&self.path).map_err(task_error)
+    }
+
+    fn resolve(&mut self, _env: Env, lrs: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(Lrs { lrs: Arc::new(lrs) })
+    }
+}
+
+/// Projects a batch of points on the thread pool, see [`Lrs::lookup_batch_async`].
+pub struct LookupBatchTask {
+    lrs: Arc<ExtLrs>,
+    points: Vec<geo_types::Point>,
+    max_distance: f64,
+}
+
+impl Task for LookupBatchTask {
+    type Output = Vec<Vec<liblrs::lrs::LrmProjection>>;
+    type JsValue = BatchProjection;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        Ok(self.lrs.lookup_batch(&self.points, self.max_distance))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// A Linear Referencing System.
+#[napi]
+pub struct Lrs {
+    // Shared with the tasks running on the thread pool
+    lrs: Arc<ExtLrs>,
+}
+
+#[napi]
+impl Lrs {
+    /// Loads an LRS from the content of a file.
+    #[napi(factory)]
+    pub fn load(data: Buffer) -> Result<Self> {
+        ExtLrs::load(&data)
+            .map(|lrs| Self { lrs: Arc::new(lrs) })
+            .map_err(lrs_error)
+    }
+
+    /// Reads an LRS file without blocking the event loop.
+    /// The promise is rejected with an error whose message starts with the code, such as `INVALID_FILE`.
+    #[napi(ts_return_type = "Promise<Lrs>")]
+    pub fn open(path: String) -> AsyncTask<OpenTask> {
+        AsyncTask::new(OpenTask { path })
+    }
+
+    /// Builds an LRS from a GeoJSON FeatureCollection: each LineString is an LRM with the `id` property,
+    /// each Point with the `lrm_id`, `name` and `measure` properties is an anchor of that LRM.
+    #[napi(factory)]
+    pub fn from_geojson(geojson: String) -> Result<Self> {
+        let invalid = |reason: String| lrs_error(LrsErrorCode::InvalidFile { reason });
+        let collection = geojson
+            .parse::<geojson::FeatureCollection>()
+            .map_err(|e| invalid(e.to_string()))?;
+        let mut builder = Builder::from_geojson(&collection.features, &GeoJsonMapping::default())
+            .map_err(|e| invalid(e.to_string()))?;
+        builder
+            .build_lrs(HashMap::new())
+            .map(|lrs| Self { lrs: Arc::new(lrs) })
+            .map_err(lrs_error)
+    }
+
+    /// Coordinate reference system of the coordinates, such as `EPSG:4326`, if it was recorded.
+    #[napi]
+    pub fn crs(&self) -> Option<String> {
+        self.lrs.crs()
+    }
+
+    /// How many LRMs compose the LRS.
+    #[napi]
+    pub fn lrm_len(&self) -> u32 {
+        self.lrs.lrm_len() as u32
+    }
+
+    /// `id` of the LRM.
+    #[napi]
+    pub fn get_lrm_scale_id(&self, lrm_index: u32) -> Result<String> {
+        self.lrs
+            .get_lrm_scale_id(lrm_index as usize)
+            .map_err(lrs_error)
+    }
+
+    /// Index of the LRM with an `id`, or `null` if there is none.
+    #[napi]
+    pub fn find_lrm(&self, lrm_id: String) -> Option<u32> {
+        self.lrs.get_lrm(&lrm_id).map(|lrm_index| lrm_index as u32)
+    }
+
+    /// The geometry of the LRM, as interleaved coordinates.
+    #[napi]
+    pub fn get_lrm_geom(&self, lrm_index: u32) -> Result<Float64Array> {
+        self.lrs
+            .get_lrm_geom(lrm_index as usize)
+            .map(coords_to_array)
+            .map_err(lrs_error)
+    }
+
+    /// All the anchors of the LRM.
+    #[napi]
+    pub fn get_anchors(&self, lrm_index: u32) -> Result<Vec<Anchor>> {
+        self.lrs
+            .get_anchors(lrm_index as usize)
+            .map(|anchors| anchors.iter().map(Anchor::from).collect())
+            .map_err(lrs_error)
+    }
+
+    /// The measures at both ends of the LRM and its length in meters.
+    #[napi]
+    pub fn lrm_bounds(&self, lrm_index: u32) -> Result<LrmBounds> {
+        self.lrs
+            .lrm_bounds(lrm_index as usize)
+            .map(|bounds| LrmBounds {
+                min_measure: (&bounds.min_measure).into(),
+                max_measure: (&bounds.max_measure).into(),
+                length: bounds.length,
+            })
+            .map_err(lrs_error)
+    }
+
+    /// The position of a measure on the LRM.
+    #[napi]
+    pub fn resolve(&self, lrm_index: u32, measure: LrmScaleMeasure) -> Result<Point> {
+        self.lrs
+            .resolve(lrm_index as usize, &(&measure).into())
+            .map(Point::from)
+            .map_err(lrs_error)
+    }
+
+    /// The positions of many measures of the same LRM, as interleaved coordinates.
+    /// The coordinates of a measure that cannot be resolved are `NaN`.
+    #[napi]
+    pub fn resolve_batch(
+        &self,
+        lrm_index: u32,
+        anchor_names: Vec<String>,
+        scale_offsets: Float64Array,
+    ) -> Result<Float64Array> {
+        if anchor_names.len() != scale_offsets.len() {
+            return Err(Error::new(
+                "INVALID_ARGUMENT",
+                "anchorNames and scaleOffsets must have the same length",
+            ));
+        }
+        let measures: Vec<_> = anchor_names
+            .iter()
+            .zip(scale_offsets.iter())
+            .map(|(name, &offset)| liblrs::lrm_scale::LrmScaleMeasure::new(name, offset))
+            .collect();
+        let coords = self
+            .lrs
+            .resolve_many(lrm_index as usize, &measures)
+            .into_iter()
+            .map(|point| {
+                point.map_or(
+                    geo_types::Coord {
+                        x: f64::NAN,
+                        y: f64::NAN,
+                    },
+                    |point| point.0,
+                )
+            });
+        Ok(coords_to_array(coords))
+    }
+
+    /// The geometry of the LRM between two measures, as interleaved coordinates.
+    /// With a `lateralOffsetM`, the range is moved perpendicularly to the LRM by that many meters,
+    /// positive on the left when facing increasing measures.
+    #[napi]
+    pub fn resolve_range(
+        &self,
+        lrm_index: u32,
+        from: LrmScaleMeasure,
+        to: LrmScaleMeasure,
+        lateral_offset_m: Option<f64>,
+    ) -> Result<Float64Array> {
+        self.lrs
+            .resolve_range(
+                lrm_index as usize,
+                &(&from).into(),
+                &(&to).into(),
+                lateral_offset_m,
+            )
+            .map(coords_to_array)
+            .map_err(lrs_error)
+    }
+
+    /// Projects a point on the LRM. The nearest projection is the first item.
+    #[napi]
+    pub fn lookup(&self, point: Point, lrm_index: u32) -> Result<Vec<LrmProjection>> {
+        let lrm_index = lrm_index as usize;
+        if lrm_index >= self.lrs.lrm_len() {
+            return Err(lrs_error(LrsErrorCode::LrmNotFound { lrm_index }));
+        }
+        self.lrs
+            .lookup(point.into(), lrm_index)
+            .map(|projection| vec![LrmProjection::from(&projection)])
+            .map_err(lrs_error)
+    }
+
+    /// Projects a point on every LRM at most `maxDistance` away: meters for geographic coordinates, coordinate units for schematic ones. The nearest projection is the first item.
+    #[napi]
+    pub fn lookup_all(&self, point: Point, max_distance: f64) -> Vec<LrmProjection> {
+        self.lrs
+            .lookup_all(point.into(), max_distance)
+            .iter()
+            .map(LrmProjection::from)
+            .collect()
+    }
+
+    /// Finds the nearest LRM at most `maxDistance` away, in the unit of `lookupAll`, and the measure of the point on it.
+    #[napi]
+    pub fn locate(&self, point: Point, max_distance: f64) -> Option<LocatedPoint> {
+        self.lrs
+            .locate(point.into(), max_distance)
+            .map(LocatedPoint::from)
+    }
+
+    /// Projects every point of interleaved coordinates on the nearest LRM at most `maxDistance` away, in the unit of `lookupAll`.
+    #[napi]
+    pub fn lookup_batch(&self, coords: Float64Array, max_distance: f64) -> Result<BatchProjection> {
+        let points = array_to_points(&coords)?;
+        Ok(self.lrs.lookup_batch(&points, max_distance).into())
+    }
+
+    /// Same as `lookupBatch`, on the thread pool, without blocking the event loop.
+    #[napi(ts_return_type = "Promise<BatchProjection>")]
+    pub fn lookup_batch_async(
+        &self,
+        coords: Float64Array,
+        max_distance: f64,
+    ) -> Result<AsyncTask<LookupBatchTask>> {
+        Ok(AsyncTask::new(LookupBatchTask {
+            lrs: self.lrs.clone(),
+            points: array_to_points(&coords)?,
+            max_distance,
+        }))
+    }
+
+    /// The LRMs and their anchors as a GeoJSON FeatureCollection string.
+    #[napi]
+    pub fn to_geojson(&self) -> String {
+        self.lrs.to_geojson()
+    }
+
+    /// The geometry of the LRM as a GeoJSON Feature string.
+    #[napi]
+    pub fn lrm_geojson(&self, lrm_index: u32) -> Result<String> {
+        self.lrs.lrm_geojson(lrm_index as usize).map_err(lrs_error)
+    }
+
+    /// Properties of the LRS.
+    #[napi]
+    pub fn lrs_properties(&self) -> HashMap<String, String> {
+        self.lrs.lrs_properties().clone()
+    }
+
+    /// Properties of the LRM.
+    #[napi]
+    pub fn lrm_properties(&self, lrm_index: u32) -> Result<HashMap<String, String>> {
+        self.lrs
+            .lrm_properties(lrm_index as usize)
+            .cloned()
+            .map_err(lrs_error)
+    }
+}
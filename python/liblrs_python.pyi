@@ -2,6 +2,8 @@
 # ruff: noqa: E501, F401
 
 import builtins
+import numpy
+import numpy.typing
 import os
 import pathlib
 import typing
@@ -52,6 +54,74 @@ class Anchor:
         r"""
         Position on the scale.
         """
+    @property
+    def surveyed_position(self) -> typing.Optional[Point]:
+        r"""
+        Surveyed position, before the projection on the [`Curve`].
+        """
+    @surveyed_position.setter
+    def surveyed_position(self, value: typing.Optional[Point]) -> None:
+        r"""
+        Surveyed position, before the projection on the [`Curve`].
+        """
+    @property
+    def projection_offset(self) -> typing.Optional[builtins.float]:
+        r"""
+        Signed distance between the surveyed position and the [`Curve`], positive on the left.
+        """
+    @projection_offset.setter
+    def projection_offset(self, value: typing.Optional[builtins.float]) -> None:
+        r"""
+        Signed distance between the surveyed position and the [`Curve`], positive on the left.
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class AnchorMarker:
+    r"""
+    An [`Anchor`] placed on its [`Lrm`], to display it on a map.
+    """
+    @property
+    def name(self) -> typing.Optional[builtins.str]:
+        r"""
+        Name of the [`Anchor`], `None` for an unnamed [`Anchor`].
+        """
+    @name.setter
+    def name(self, value: typing.Optional[builtins.str]) -> None:
+        r"""
+        Name of the [`Anchor`], `None` for an unnamed [`Anchor`].
+        """
+    @property
+    def measure(self) -> LrmScaleMeasure:
+        r"""
+        The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+        """
+    @measure.setter
+    def measure(self, value: LrmScaleMeasure) -> None:
+        r"""
+        The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+        """
+    @property
+    def point(self) -> Point:
+        r"""
+        The position of the [`Anchor`] on the [`Lrm`].
+        """
+    @point.setter
+    def point(self, value: Point) -> None:
+        r"""
+        The position of the [`Anchor`] on the [`Lrm`].
+        """
+    @property
+    def heading(self) -> builtins.float:
+        r"""
+        The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+        """
+    @heading.setter
+    def heading(self, value: builtins.float) -> None:
+        r"""
+        The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+        """
+    def __repr__(self) -> builtins.str: ...
 
 @typing.final
 class AnchorOnLrm:
@@ -117,6 +187,40 @@ class Builder:
         
         segments represent the curve of the traversal
         """
+    def add_traversal_from_geometry(self, traversal_id: builtins.str, geometry: typing.Any, start_node_index: builtins.int, end_node_index: builtins.int) -> builtins.int:
+        r"""
+        Add a traversal made of a single segment with the same id
+        
+        The geometry is a LineString with a `__geo_interface__`, such as a shapely LineString
+        """
+    def add_traversals_from_table(self, table: typing.Any, id_column: builtins.str = 'id', geometry_column: builtins.str = 'geometry') -> builtins.dict[builtins.str, builtins.int]:
+        r"""
+        Add a traversal for each row of a table, such as a (geo)pandas DataFrame or a dict of lists
+        
+        Each traversal has a single segment, whose geometry is a shapely LineString with at least two distinct coordinates.
+        The traversals ending at the same coordinates share the same node.
+        Return the index of each traversal by its id
+        """
+    def add_lrms_from_table(self, table: typing.Any, lrm_id_column: builtins.str = 'lrm_id', name_column: builtins.str = 'name', measure_column: builtins.str = 'measure', geometry_column: builtins.str = 'geometry') -> None:
+        r"""
+        Add the anchors of a table, such as a (geo)pandas DataFrame or a dict of lists, and their lrms
+        
+        Each row is an anchor of the lrm of its `lrm_id`, at the distance `measure` from the start of the lrm.
+        Its geometry is a shapely Point, that is projected on the traversal with the same id as the lrm.
+        One lrm is added for each `lrm_id`, so the traversals must already be added.
+        """
+    def set_compression(self, compressed: builtins.bool) -> None:
+        r"""
+        Compresses the built file. It is decompressed transparently when loaded
+        """
+    def set_crs(self, crs: builtins.str) -> None:
+        r"""
+        Records the coordinate reference system of the coordinates, as an authority code such as `EPSG:4326`
+        """
+    def set_traversal_properties(self, traversal_index: builtins.int, properties: typing.Mapping[builtins.str, builtins.str]) -> None:
+        r"""
+        Set the metadata of a traversal, such as the name of the line or its operator
+        """
     def add_lrm(self, id: builtins.str, traversal_index: builtins.int, anchors: typing.Sequence[AnchorOnLrm], properties: typing.Mapping[builtins.str, builtins.str]) -> None:
         r"""
         Add a linear referencing model
@@ -124,6 +228,11 @@ class Builder:
         It is composed by the traversal identified by traversal_index (that represents the curve)
         and the anchors (that represent the milestones)
         """
+    def add_lrm_with_alternates(self, id: builtins.str, traversal_index: builtins.int, alternate_traversal_indices: typing.Sequence[builtins.int], anchors: typing.Sequence[AnchorOnLrm], properties: typing.Mapping[builtins.str, builtins.str]) -> None:
+        r"""
+        Add a linear referencing model whose scale also applies to alternate traversals,
+        e.g. the other tracks of a multi-track line
+        """
     def get_traversal_indexes(self) -> builtins.dict[builtins.str, builtins.int]:
         r"""
         List all the traversals by their id and index
@@ -183,276 +292,1161 @@ class DataIssueReporter:
     def report_ignoring_traversal_edges(self, traversal_ref: builtins.str, ignored_count: builtins.int, total_count: builtins.int, first_node: builtins.int, last_node: builtins.int) -> None: ...
 
 @typing.final
-class LrmProjection:
+class LocatedPoint:
     r"""
-    The result of a projection onto an [`LrmScale`].
+    The nearest position of a [`Point`] on all the [`Lrm`]s.
     """
     @property
-    def lrm_handle(self) -> builtins.int:
+    def lrm_index(self) -> builtins.int:
         r"""
-        Handle of the [`Lrm`] where this measurement applies to
+        Index of the [`Lrm`].
         """
-    @lrm_handle.setter
-    def lrm_handle(self, value: builtins.int) -> None:
+    @lrm_index.setter
+    def lrm_index(self, value: builtins.int) -> None:
         r"""
-        Handle of the [`Lrm`] where this measurement applies to
+        Index of the [`Lrm`].
         """
     @property
-    def measure(self) -> LrmScaleMeasure:
+    def lrm_id(self) -> builtins.str:
         r"""
-        Contains `measure` ([`LrmScaleMeasure`]) and `lrm` ([`LrmHandle`]).
+        `id` of the [`Lrm`].
         """
-    @measure.setter
-    def measure(self, value: LrmScaleMeasure) -> None:
+    @lrm_id.setter
+    def lrm_id(self, value: builtins.str) -> None:
         r"""
-        Contains `measure` ([`LrmScaleMeasure`]) and `lrm` ([`LrmHandle`]).
+        `id` of the [`Lrm`].
         """
     @property
-    def orthogonal_offset(self) -> builtins.float:
+    def anchor_name(self) -> builtins.str:
         r"""
-        How far from the [`Lrm`] is the [`Point`] that has been projected.
+        Name of the [`Anchor`] of the measure.
         """
-    @orthogonal_offset.setter
-    def orthogonal_offset(self, value: builtins.float) -> None:
+    @anchor_name.setter
+    def anchor_name(self, value: builtins.str) -> None:
         r"""
-        How far from the [`Lrm`] is the [`Point`] that has been projected.
+        Name of the [`Anchor`] of the measure.
         """
-
-@typing.final
-class LrmScaleMeasure:
-    r"""
-    Represent a position on an [`LrmScale`] relative as an `offset` to an [`Anchor`].
-    """
-    def __new__(cls, anchor_name: builtins.str, scale_offset: builtins.float) -> LrmScaleMeasure:
+    @property
+    def scale_offset(self) -> builtins.float:
         r"""
-        Build a new [`LrmMeasure`] from an [`Anchor`] `name` and the `offset` on the [`LrmScale`].
+        Offset of the measure from the [`Anchor`], in the unit of the scale.
+        """
+    @scale_offset.setter
+    def scale_offset(self, value: builtins.float) -> None:
+        r"""
+        Offset of the measure from the [`Anchor`], in the unit of the scale.
+        """
+    @property
+    def lateral_offset_m(self) -> builtins.float:
+        r"""
+        Distance in meters from the [`Lrm`]: positive if the [`Point`] is on the left of the [`Lrm`], negative on the right.
+        """
+    @lateral_offset_m.setter
+    def lateral_offset_m(self, value: builtins.float) -> None:
+        r"""
+        Distance in meters from the [`Lrm`]: positive if the [`Point`] is on the left of the [`Lrm`], negative on the right.
+        """
+    @property
+    def distance_m(self) -> builtins.float:
+        r"""
+        Distance in meters between the [`Point`] and the [`Lrm`].
+        """
+    @distance_m.setter
+    def distance_m(self, value: builtins.float) -> None:
+        r"""
+        Distance in meters between the [`Point`] and the [`Lrm`].
         """
     def __repr__(self) -> builtins.str: ...
 
 @typing.final
-class Lrs:
+class Lrm:
     r"""
-    Holds the whole Linear Referencing System.
+    An [`Lrm`] of the [`Lrs`] and its [`Anchor`]s, as given by `lrs[index]`, `lrs["id"]` or `for lrm in lrs`.
+    
+    It can be indexed by the position or the name of an [`Anchor`], and iterated over its [`Anchor`]s.
     """
-    def __new__(cls, data: bytes) -> Lrs:
-        r"""
-        Load the data.
-        """
-    def lrm_len(self) -> builtins.int:
+    @property
+    def index(self) -> builtins.int:
         r"""
-        How many LRMs compose the LRS.
+        Index of the [`Lrm`] in the [`Lrs`].
         """
-    def get_lrm_geom(self, index: builtins.int) -> builtins.list[Point]:
+    @property
+    def id(self) -> builtins.str:
         r"""
-        Return the geometry of the LRM.
+        `id` of the [`Lrm`].
         """
-    def get_lrm_scale_id(self, index: builtins.int) -> builtins.str:
+    @property
+    def properties(self) -> builtins.dict[builtins.str, builtins.str]:
         r"""
-        `id` of the [`LrmScale`].
+        Metadata to describe the [`Lrm`].
         """
-    def get_anchors(self, lrm_index: builtins.int) -> builtins.list[Anchor]:
+    @property
+    def anchors(self) -> builtins.list[Anchor]:
         r"""
-        All the [`Anchor`]s of a LRM.
+        The [`Anchor`]s of the [`Lrm`].
         """
-    def resolve(self, lrm_index: builtins.int, measure: LrmScaleMeasure) -> Point:
+    def __len__(self) -> builtins.int: ...
+    def __getitem__(self, key: builtins.int | builtins.str) -> Anchor:
         r"""
-        Get the position given a [`LrmScaleMeasure`].
+        The [`Anchor`] at a position, or the first one with a name.
         """
-    def locate_point(self, lrm_index: builtins.int, measure: LrmScaleMeasure) -> builtins.float:
+    def __iter__(self) -> typing.Iterator[Anchor]: ...
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class LrmBbox:
+    r"""
+    Where an [`Lrm`] is, to frame it on a map, see `get_lrm_bbox`.
+    """
+    @property
+    def bbox_min(self) -> Point:
         r"""
-        Get the positon along the curve given a [`LrmScaleMeasure`]
-        The value will be between 0.0 and 1.0, both included
+        South-west corner of the bounding box of the [`Lrm`].
         """
-    def resolve_range(self, lrm_index: builtins.int, from_measure: LrmScaleMeasure, to_measure: LrmScaleMeasure) -> builtins.list[Point]:
+    @bbox_min.setter
+    def bbox_min(self, value: Point) -> None:
         r"""
-        Given two [`LrmScaleMeasure`]s, return a range of [`Point`] that represent a line string.
+        South-west corner of the bounding box of the [`Lrm`].
         """
-    def find_lrm(self, lrm_id: builtins.str) -> typing.Optional[builtins.int]:
+    @property
+    def bbox_max(self) -> Point:
         r"""
-        Given a ID returns the corresponding lrs index (or None if not found)
+        North-east corner of the bounding box of the [`Lrm`].
         """
-    def lookup(self, point: Point, lrm_handle: builtins.int) -> builtins.list[LrmProjection]:
+    @bbox_max.setter
+    def bbox_max(self, value: Point) -> None:
         r"""
-        Projects a [`Point`] on all applicable [`Traversal`]s to a given [`Lrm`].
-        The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
-        The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+        North-east corner of the bounding box of the [`Lrm`].
         """
-    def lookup_lrms(self, point: Point) -> builtins.list[LrmProjection]:
+    @property
+    def center(self) -> Point:
         r"""
-        Projects a [`Point`] on all applicable [`Traversal`]s nearby.
-        The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
-        The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+        The middle of the [`Lrm`], half way along its geometry.
         """
-    def lrs_properties(self) -> builtins.dict[builtins.str, builtins.str]:
+    @center.setter
+    def center(self, value: Point) -> None:
         r"""
-        [`Properties`] of the lrs
+        The middle of the [`Lrm`], half way along its geometry.
         """
-    def lrm_properties(self, lrm_index: builtins.int) -> builtins.dict[builtins.str, builtins.str]:
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class LrmBounds:
+    r"""
+    The measures at both ends of an [`Lrm`] and its length.
+    """
+    @property
+    def min_measure(self) -> LrmScaleMeasure:
         r"""
-        [`Properties`] for a given lrm
+        The smallest measure of the [`Lrm`].
         """
-    def anchor_properties(self, lrm_index: builtins.int, anchor_index: builtins.int) -> builtins.dict[builtins.str, builtins.str]:
+    @min_measure.setter
+    def min_measure(self, value: LrmScaleMeasure) -> None:
         r"""
-        [`Properties`] for a given anchor
+        The smallest measure of the [`Lrm`].
         """
-    def get_node(self, node_index: builtins.int) -> Node:
+    @property
+    def max_measure(self) -> LrmScaleMeasure:
         r"""
-        Return a single [`Node`]
+        The largest measure of the [`Lrm`].
         """
-    def get_nodes(self) -> builtins.list[Node]:
+    @max_measure.setter
+    def max_measure(self, value: LrmScaleMeasure) -> None:
         r"""
-        Return all the [`Node`] of the lrs
+        The largest measure of the [`Lrm`].
         """
-    def get_segment(self, segment_index: builtins.int) -> Segment:
+    @property
+    def length(self) -> builtins.float:
         r"""
-        Return a single [`Segment`]
+        The length of the [`Lrm`] in meters.
         """
-    def get_segments(self) -> builtins.list[Segment]:
+    @length.setter
+    def length(self, value: builtins.float) -> None:
         r"""
-        All the [`Segment`] of the lrs
+        The length of the [`Lrm`] in meters.
         """
+    def __repr__(self) -> builtins.str: ...
 
 @typing.final
-class Node:
+class LrmIntersection:
     r"""
-    A Node is a topological element of the [`Lrs`] that represents a intersection (or an extremity) of an [`Lrm`]
+    Where two [`Lrm`]s cross or touch.
     """
     @property
-    def id(self) -> builtins.str:
+    def measure_a(self) -> LrmScaleMeasure:
         r"""
-        Identifies this [`Node`].
+        The measure of the intersection on the first [`Lrm`].
         """
-    @id.setter
-    def id(self, value: builtins.str) -> None:
+    @measure_a.setter
+    def measure_a(self, value: LrmScaleMeasure) -> None:
         r"""
-        Identifies this [`Node`].
+        The measure of the intersection on the first [`Lrm`].
         """
     @property
-    def geometry(self) -> typing.Optional[Point]:
+    def measure_b(self) -> LrmScaleMeasure:
         r"""
-        Coordinates of the [`Node`]. They are in the same projection system (e.g. spherical or planar) as the [`Lrs`]
+        The measure of the intersection on the second [`Lrm`].
         """
-    @geometry.setter
-    def geometry(self, value: typing.Optional[Point]) -> None:
+    @measure_b.setter
+    def measure_b(self, value: LrmScaleMeasure) -> None:
         r"""
-        Coordinates of the [`Node`]. They are in the same projection system (e.g. spherical or planar) as the [`Lrs`]
+        The measure of the intersection on the second [`Lrm`].
         """
     @property
-    def properties(self) -> builtins.dict[builtins.str, builtins.str]:
+    def point(self) -> Point:
         r"""
-        Metadata to describe the [`Node`].
+        The geographical position of the intersection.
         """
-    @properties.setter
-    def properties(self, value: builtins.dict[builtins.str, builtins.str]) -> None:
+    @point.setter
+    def point(self, value: Point) -> None:
         r"""
-        Metadata to describe the [`Node`].
+        The geographical position of the intersection.
         """
+    def __repr__(self) -> builtins.str: ...
 
 @typing.final
-class Point:
+class LrmMatch:
     r"""
-    A geographical [`Point`], it can be either a projected or spherical coordinates.
+    An [`Lrm`] whose id matches a query, see `search_lrms`.
     """
     @property
-    def x(self) -> builtins.float:
+    def lrm_index(self) -> builtins.int:
         r"""
-        Position on x-axis or `longitude`.
+        Index of the [`Lrm`].
         """
-    @x.setter
-    def x(self, value: builtins.float) -> None:
+    @lrm_index.setter
+    def lrm_index(self, value: builtins.int) -> None:
         r"""
-        Position on x-axis or `longitude`.
+        Index of the [`Lrm`].
         """
     @property
-    def y(self) -> builtins.float:
+    def lrm_id(self) -> builtins.str:
         r"""
-        Position on y-axis or `latitude`.
+        `id` of the [`Lrm`].
         """
-    @y.setter
-    def y(self, value: builtins.float) -> None:
+    @lrm_id.setter
+    def lrm_id(self, value: builtins.str) -> None:
         r"""
-        Position on y-axis or `latitude`.
+        `id` of the [`Lrm`].
         """
-    def __new__(cls, x: builtins.float, y: builtins.float) -> Point:
+    @property
+    def score(self) -> builtins.float:
         r"""
-        Build a new geographical point.
-        
-        When using spherical coordinates, longitude is x and latitude y
+        How well the [`Lrm`] matches the query, between 0 (excluded) and 1 for an exact match.
+        """
+    @score.setter
+    def score(self, value: builtins.float) -> None:
+        r"""
+        How well the [`Lrm`] matches the query, between 0 (excluded) and 1 for an exact match.
         """
     def __repr__(self) -> builtins.str: ...
 
 @typing.final
-class Segment:
+class LrmProjection:
     r"""
-    A segment is a topological element of the [`Lrs`] that represents a piece of the [`Curve`] of an [`Lrm`]
-    
-    It has a start and end [`Node`].
+    The result of a projection onto an [`LrmScale`].
     """
     @property
-    def id(self) -> builtins.str:
+    def lrm_handle(self) -> builtins.int:
         r"""
-        Identifies this [`Segment`]
+        Handle of the [`Lrm`] where this measurement applies to
         """
-    @id.setter
-    def id(self, value: builtins.str) -> None:
+    @lrm_handle.setter
+    def lrm_handle(self, value: builtins.int) -> None:
         r"""
-        Identifies this [`Segment`]
+        Handle of the [`Lrm`] where this measurement applies to
         """
     @property
-    def properties(self) -> builtins.dict[builtins.str, builtins.str]:
+    def measure(self) -> LrmScaleMeasure:
         r"""
-        Metadata to describe the [`Segment`]
+        Contains `measure` ([`LrmScaleMeasure`]) and `lrm` ([`LrmHandle`]).
         """
-    @properties.setter
-    def properties(self, value: builtins.dict[builtins.str, builtins.str]) -> None:
+    @measure.setter
+    def measure(self, value: LrmScaleMeasure) -> None:
         r"""
-        Metadata to describe the [`Segment`]
+        Contains `measure` ([`LrmScaleMeasure`]) and `lrm` ([`LrmHandle`]).
         """
     @property
-    def start_node(self) -> builtins.int:
+    def orthogonal_offset(self) -> builtins.float:
         r"""
-        Start [`Node`]
+        How far from the [`Lrm`] is the [`Point`] that has been projected.
         """
-    @start_node.setter
-    def start_node(self, value: builtins.int) -> None:
+    @orthogonal_offset.setter
+    def orthogonal_offset(self, value: builtins.float) -> None:
         r"""
-        Start [`Node`]
+        How far from the [`Lrm`] is the [`Point`] that has been projected.
         """
     @property
-    def end_node(self) -> builtins.int:
+    def traversal_handle(self) -> builtins.int:
         r"""
-        End [`Node`]
+        Handle of the [`Traversal`] of the [`Lrm`] nearest to the [`Point`]: the main one or an alternate one.
         """
-    @end_node.setter
-    def end_node(self, value: builtins.int) -> None:
+    @traversal_handle.setter
+    def traversal_handle(self, value: builtins.int) -> None:
         r"""
-        End [`Node`]
+        Handle of the [`Traversal`] of the [`Lrm`] nearest to the [`Point`]: the main one or an alternate one.
         """
+    def __repr__(self) -> builtins.str: ...
 
 @typing.final
-class SegmentOfTraversal:
+class LrmScaleMeasure:
     r"""
-    A traversal is composed by segments
+    Represent a position on an [`LrmScale`] relative as an `offset` to an [`Anchor`].
     """
     @property
-    def segment_index(self) -> builtins.int:
+    def anchor_name(self) -> builtins.str:
         r"""
-        Index of the considered segment. Use the value returned by [`Builder::add_segment`]
+        `name` of the reference [`Anchor`].
         """
-    @segment_index.setter
-    def segment_index(self, value: builtins.int) -> None:
+    @anchor_name.setter
+    def anchor_name(self, value: builtins.str) -> None:
         r"""
-        Index of the considered segment. Use the value returned by [`Builder::add_segment`]
+        `name` of the reference [`Anchor`].
         """
     @property
-    def reversed(self) -> builtins.bool:
+    def scale_offset(self) -> builtins.float:
         r"""
-        When integrating the segment in the traversal, should we consider the coordinates in the reverse order
+        `offset` to the reference [`Anchor`].
         """
-    @reversed.setter
-    def reversed(self, value: builtins.bool) -> None:
+    @scale_offset.setter
+    def scale_offset(self, value: builtins.float) -> None:
         r"""
-        When integrating the segment in the traversal, should we consider the coordinates in the reverse order
+        `offset` to the reference [`Anchor`].
         """
-    def __new__(cls, segment_index: builtins.int, reversed: builtins.bool) -> SegmentOfTraversal: ...
+    def __new__(cls, anchor_name: builtins.str, scale_offset: builtins.float) -> LrmScaleMeasure:
+        r"""
+        Build a new [`LrmMeasure`] from an [`Anchor`] `name` and the `offset` on the [`LrmScale`].
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class Lrs:
+    r"""
+    Holds the whole Linear Referencing System.
+    """
+    def __new__(cls, data: bytes) -> Lrs:
+        r"""
+        Load the data.
+        """
+    @staticmethod
+    def open(path: builtins.str | os.PathLike | pathlib.Path) -> Lrs:
+        r"""
+        Load the data from a file. The file is memory-mapped instead of being read in Python memory.
+        
+        The whole LRS is still loaded: only the copy of the file is saved.
+        
+        It can be used in a `with` block to release the memory at the end of the block.
+        """
+    def close(self) -> None:
+        r"""
+        Releases the memory of the LRS. It has no LRM anymore.
+        """
+    def __enter__(self) -> Lrs: ...
+    def __exit__(self, *_args: typing.Any) -> builtins.bool:
+        r"""
+        Closes the LRS at the end of a `with` block.
+        """
+    def to_bytes(self) -> builtins.bytes:
+        r"""
+        Serializes the LRS, e.g. to save it after editing it. `Lrs(data)` loads it back.
+        """
+    @staticmethod
+    def from_golden_dataset(dataset: builtins.str) -> Lrs:
+        r"""
+        Builds the network of a golden dataset, such as `tests/conformance/golden.txt`.
+        """
+    def check_conformance(self, dataset: builtins.str, tolerance: builtins.float) -> builtins.list[builtins.str]:
+        r"""
+        Runs the cases of a golden dataset through the python facing functions.
+
+        Returns the messages of the failed cases: it is empty when every case passes.
+        """
+    def __reduce__(self) -> tuple[typing.Any, ...]:
+        r"""
+        Pickles the LRS as a call to `Lrs(data)`, e.g. to send it to `multiprocessing` workers or to cache it with joblib.
+        
+        The data is the one the LRS was loaded from, as is.
+        """
+    def __len__(self) -> builtins.int: ...
+    def __getitem__(self, key: builtins.int | builtins.str) -> Lrm:
+        r"""
+        The [`Lrm`] at a position, or the one with an `id`.
+        """
+    def __iter__(self) -> typing.Iterator[Lrm]: ...
+    def __repr__(self) -> builtins.str: ...
+    def get_lrm(self, index: builtins.int) -> Lrm:
+        r"""
+        The [`Lrm`] at a position, with its id, properties and [`Anchor`]s.
+        """
+    def crs(self) -> typing.Optional[builtins.str]:
+        r"""
+        Coordinate reference system of the coordinates, such as `EPSG:4326`, if it was recorded.
+        """
+    def lrm_len(self) -> builtins.int:
+        r"""
+        How many LRMs compose the LRS.
+        """
+    def get_lrm_geom(self, index: builtins.int) -> builtins.list[Point]:
+        r"""
+        Return the geometry of the LRM.
+        """
+    def get_lrm_geom_shapely(self, index: builtins.int) -> typing.Any:
+        r"""
+        Return the geometry of the LRM as a shapely LineString. shapely must be installed.
+        """
+    def get_oriented_lrm_geom(self, index: builtins.int) -> builtins.list[Point]:
+        r"""
+        Return the geometry of the LRM, ordered by increasing measures.
+        """
+    def measures_increase(self, index: builtins.int) -> builtins.bool:
+        r"""
+        Whether the measures of the LRM increase along the geometry returned by `get_lrm_geom`.
+        """
+    def is_loop(self, index: builtins.int) -> builtins.bool:
+        r"""
+        Whether the LRM is a loop, where measures wrap around past its origin.
+        """
+    def lrm_bounds(self, index: builtins.int) -> LrmBounds:
+        r"""
+        The measures at both ends of the LRM and its length in meters.
+        """
+    def get_lrm_bbox(self, lrm_index: builtins.int) -> LrmBbox:
+        r"""
+        The bounding box of the [`Lrm`] and its middle, e.g. to zoom on it without loading its geometry.
+        """
+    def get_lrm_scale_id(self, index: builtins.int) -> builtins.str:
+        r"""
+        `id` of the [`LrmScale`].
+        """
+    def get_anchors(self, lrm_index: builtins.int) -> builtins.list[Anchor]:
+        r"""
+        All the [`Anchor`]s of a LRM.
+        """
+    def anchors_between(self, lrm_index: builtins.int, from_measure: LrmScaleMeasure, to_measure: LrmScaleMeasure) -> builtins.list[MeasuredAnchor]:
+        r"""
+        The [`Anchor`]s of a LRM between two measures, both included, sorted by increasing measures.
+        It allows to go through the anchors of a long LRM page by page.
+        """
+    def intersections(self, lrm_index_a: builtins.int, lrm_index_b: builtins.int) -> builtins.list[LrmIntersection]:
+        r"""
+        Where the [`Traversal`]s of two [`Lrm`]s cross or touch, sorted along the first [`Lrm`].
+        """
+    def network_distance(self, from_lrm_index: builtins.int, from_measure: LrmScaleMeasure, to_lrm_index: builtins.int, to_measure: LrmScaleMeasure) -> builtins.float:
+        r"""
+        The distance in meters along the network between measures of two [`Lrm`]s, following the connections between [`Traversal`]s.
+        Fails when they are not connected.
+        """
+    def stats(self) -> LrsStats:
+        r"""
+        Summary of the content of the [`Lrs`], to sanity-check a file.
+        """
+    def to_geojson(self) -> builtins.str:
+        r"""
+        The LRMs and their anchors as a GeoJSON FeatureCollection, e.g. to inspect them in a GIS.
+        """
+    def to_geodataframes(self) -> builtins.dict[builtins.str, typing.Any]:
+        r"""
+        The LRS as GeoDataFrames, to analyze and plot it in a notebook. geopandas and shapely must be installed.
+        
+        Returns a dict with the GeoDataFrames `lrms` (LineStrings), `anchors` (Points) and,
+        when the LRS has events, `point_events` (Points) and `linear_events` (LineStrings).
+        The measures are columns with the anchor name and the offset, and the properties are columns too.
+        """
+    def resolve(self, lrm_index: builtins.int, measure: LrmScaleMeasure) -> Point:
+        r"""
+        Get the position given a [`LrmScaleMeasure`].
+        """
+    def resolve_shapely(self, lrm_index: builtins.int, measure: LrmScaleMeasure) -> typing.Any:
+        r"""
+        Get the position given a [`LrmScaleMeasure`], as a shapely Point. shapely must be installed.
+        """
+    def anchor_markers(self, lrm_index: builtins.int) -> builtins.list[AnchorMarker]:
+        r"""
+        All the [`Anchor`]s of a LRM, in the same order as `get_anchors`, placed on the [`Lrm`].
+        An [`Anchor`] before the start or after the end of the [`Lrm`] is placed at that end.
+        """
+    def measure_ticks(self, lrm_index: builtins.int, step: builtins.float, tick_length_m: builtins.float) -> builtins.list[MeasureTick]:
+        r"""
+        Graduations of the scale of the [`Lrm`] every `step` scale units, e.g. every 100 meters.
+        Each tick is a segment of `tick_length_m` meters across the [`Lrm`], centered on the graduation.
+        There is no graduation if `step` is not a positive finite number.
+        """
+    def resolve_oriented(self, lrm_index: builtins.int, measure: LrmScaleMeasure) -> OrientedPoint:
+        r"""
+        Get the position given a [`LrmScaleMeasure`] and the direction of increasing measures there,
+        e.g. to rotate the icon of a train on a map.
+        """
+    def resolve_with_offset(self, lrm_index: builtins.int, measure: LrmScaleMeasure, lateral_offset_m: builtins.float) -> Point:
+        r"""
+        Get the position given a [`LrmScaleMeasure`], moved perpendicularly to the [`Lrm`] by `lateral_offset_m` meters.
+        The offset is positive on the left when facing increasing measures, negative on the right.
+        """
+    def resolve_on_traversal(self, lrm_index: builtins.int, measure: LrmScaleMeasure, traversal_index: builtins.int) -> Point:
+        r"""
+        Get the position given a [`LrmScaleMeasure`] on one of the [`Traversal`]s of the [`Lrm`], e.g. an alternate track.
+        """
+    def lrm_traversals(self, lrm_index: builtins.int) -> builtins.list[builtins.int]:
+        r"""
+        Indices of the [`Traversal`]s of an [`Lrm`]: the main one first, then the alternate ones.
+        """
+    def resolve_batch(self, lrm_index: builtins.int, measures: typing.Sequence[LrmScaleMeasure]) -> builtins.list[Point]:
+        r"""
+        Get the positions of many [`LrmScaleMeasure`]s of the same LRM, in parallel.
+        Fails if any of them cannot be resolved.
+        """
+    def locate_point(self, lrm_index: builtins.int, measure: LrmScaleMeasure) -> builtins.float:
+        r"""
+        Get the positon along the curve given a [`LrmScaleMeasure`]
+        The value will be between 0.0 and 1.0, both included
+        """
+    def measure_at(self, lrm_index: builtins.int, curve_position: builtins.float) -> LrmScaleMeasure:
+        r"""
+        Get the [`LrmScaleMeasure`] given a position along the curve, the inverse of `locate_point`.
+        The measure is given from the nearest named [`Anchor`].
+        """
+    def resolve_range(self, lrm_index: builtins.int, from_measure: LrmScaleMeasure, to_measure: LrmScaleMeasure, lateral_offset_m: typing.Optional[builtins.float] = None) -> builtins.list[Point]:
+        r"""
+        Given two [`LrmScaleMeasure`]s, return a range of [`Point`] that represent a line string.
+        With a `lateral_offset_m`, the range is moved perpendicularly to the [`Lrm`] by that many meters,
+        positive on the left when facing increasing measures, negative on the right.
+        """
+    def resolve_range_shapely(self, lrm_index: builtins.int, from_measure: LrmScaleMeasure, to_measure: LrmScaleMeasure, lateral_offset_m: typing.Optional[builtins.float] = None) -> typing.Any:
+        r"""
+        Same as `resolve_range`, as a shapely LineString. shapely must be installed.
+        """
+    def measure_ranges_in_polygon(self, lrm_index: builtins.int, polygon: typing.Sequence[Point]) -> builtins.list[MeasureRange]:
+        r"""
+        The ranges of measures of the [`Lrm`] inside a polygon, such as a work zone, a flooded area or an administrative boundary.
+        The polygon is given by the [`Point`]s of its boundary. The ranges are sorted by increasing measures.
+        """
+    def convert_measure(self, from_lrm_index: builtins.int, to_lrm_index: builtins.int, measure: LrmScaleMeasure) -> LrmScaleMeasure:
+        r"""
+        Converts a [`LrmScaleMeasure`] of an LRM into a [`LrmScaleMeasure`] of an other LRM.
+        Both LRMs must share the same traversal.
+        """
+    def find_lrm(self, lrm_id: builtins.str) -> typing.Optional[builtins.int]:
+        r"""
+        Given a ID returns the corresponding lrs index (or None if not found)
+        """
+    def search_lrms(self, query: builtins.str, limit: builtins.int) -> builtins.list[LrmMatch]:
+        r"""
+        The [`Lrm`]s whose id, or the id of their [`Traversal`], matches a `query` typed in a search box.
+        The id can start with the `query`, contain it, or be close to it with a few typos.
+        The best `limit` matches are returned, the best one first.
+        """
+    def lookup(self, point: typing.Any, lrm_handle: builtins.int) -> builtins.list[LrmProjection]:
+        r"""
+        Projects a [`Point`] on all applicable [`Traversal`]s to a given [`Lrm`].
+        The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
+        The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+        The [`Point`] can also be a shapely Point, or any point with a `__geo_interface__`.
+        Raises an `LrsError` if the point can not be projected, with the `LRM_NOT_FOUND` code for an unknown `lrm_handle`.
+        """
+    def lookup_lrms(self, point: Point) -> builtins.list[LrmProjection]:
+        r"""
+        Projects a [`Point`] on all applicable [`Traversal`]s nearby.
+        The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
+        The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+        """
+    def lookup_all(self, point: typing.Any, max_distance: builtins.float) -> builtins.list[LrmProjection]:
+        r"""
+        Projects a [`Point`] on every [`Lrm`] at most `max_distance` away: meters for geographic coordinates, coordinate units for schematic ones.
+        The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+        The [`Point`] can also be a shapely Point, or any point with a `__geo_interface__`.
+        """
+    def locate(self, point: typing.Any, max_distance: builtins.float) -> typing.Optional[LocatedPoint]:
+        r"""
+        Finds the nearest [`Lrm`] at most `max_distance` away, in the unit of `lookup_all` from a [`Point`], and the measure of the [`Point`] on it.
+        Returns `None` when no [`Lrm`] is close enough.
+        The [`Point`] can also be a shapely Point, or any point with a `__geo_interface__`.
+        """
+    def lookup_batch(self, points: typing.Sequence[Point], max_distance: builtins.float) -> builtins.list[builtins.list[LrmProjection]]:
+        r"""
+        Projects many [`Point`]s on every [`Lrm`] at most `max_distance` away, in the unit of `lookup_all`, in parallel.
+        There is one list of projections for every [`Point`], in the same order.
+        """
+    def lookup_array(self, lons: numpy.typing.NDArray[numpy.float64], lats: numpy.typing.NDArray[numpy.float64], max_distance: builtins.float) -> ProjectionArrays:
+        r"""
+        Finds the nearest [`Lrm`] at most `max_distance` away, in the unit of `lookup_all` from every position given by two NumPy arrays,
+        in parallel and without holding the GIL.
+        Returns, for every position: the index of the [`Lrm`], the anchor name and the offset of the measure,
+        and the lateral offset in meters, positive on the left, see [`ProjectionArrays`].
+        """
+    def resolve_array(self, lrm_index: builtins.int, anchor_names: typing.Sequence[builtins.str], scale_offsets: numpy.typing.NDArray[numpy.float64]) -> PointArrays:
+        r"""
+        Get the positions of many measures of the same LRM, given by their anchor names and a NumPy array of offsets,
+        in parallel and without holding the GIL.
+        Returns the longitudes and the latitudes as two NumPy arrays, see [`PointArrays`].
+        """
+    def project_trace(self, points: typing.Sequence[Point], max_distance: builtins.float, backward_tolerance: builtins.float = 0.0) -> builtins.list[TraceMatch]:
+        r"""
+        Map-matches an ordered sequence of positions, such as a GNSS log, onto the [`Lrm`]s.
+        A position can be matched on an [`Lrm`] at most `max_distance` away, in the unit of `lookup_all`.
+        The measures are monotonic along each [`Lrm`]: a position going backward by more than `backward_tolerance` is ignored.
+        """
+    def lrs_properties(self) -> builtins.dict[builtins.str, builtins.str]:
+        r"""
+        [`Properties`] of the lrs
+        """
+    def lrm_properties(self, lrm_index: builtins.int) -> builtins.dict[builtins.str, builtins.str]:
+        r"""
+        [`Properties`] for a given lrm
+        """
+    def traversal_properties(self, lrm_index: builtins.int) -> builtins.dict[builtins.str, builtins.str]:
+        r"""
+        [`Properties`] of the traversal of a given lrm
+        """
+    def anchor_properties(self, lrm_index: builtins.int, anchor_index: builtins.int) -> builtins.dict[builtins.str, builtins.str]:
+        r"""
+        [`Properties`] for a given anchor
+        """
+    def get_node(self, node_index: builtins.int) -> Node:
+        r"""
+        Return a single [`Node`]
+        """
+    def get_nodes(self) -> builtins.list[Node]:
+        r"""
+        Return all the [`Node`] of the lrs
+        """
+    def get_segment(self, segment_index: builtins.int) -> Segment:
+        r"""
+        Return a single [`Segment`]
+        """
+    def get_segments(self) -> builtins.list[Segment]:
+        r"""
+        All the [`Segment`] of the lrs
+        """
+
+class LrsError(builtins.TypeError):
+    r"""
+    Error raised by the functions of the [`Lrs`].
+
+    Match on `code`, such as `LRM_NOT_FOUND`, rather than on the message. It derives from `TypeError`, that was raised before.
+    """
+    code: builtins.str
+    lrm_index: typing.Optional[builtins.int]
+    anchor_name: typing.Optional[builtins.str]
+    begin: typing.Optional[builtins.float]
+    end: typing.Optional[builtins.float]
+
+@typing.final
+class LrsStats:
+    r"""
+    Counts and sizes describing an [`Lrs`], to sanity-check a file.
+    """
+    @property
+    def lrm_count(self) -> builtins.int:
+        r"""
+        Number of [`Lrm`]s.
+        """
+    @lrm_count.setter
+    def lrm_count(self, value: builtins.int) -> None:
+        r"""
+        Number of [`Lrm`]s.
+        """
+    @property
+    def traversal_count(self) -> builtins.int:
+        r"""
+        Number of [`Traversal`]s.
+        """
+    @traversal_count.setter
+    def traversal_count(self, value: builtins.int) -> None:
+        r"""
+        Number of [`Traversal`]s.
+        """
+    @property
+    def invalid_traversal_count(self) -> builtins.int:
+        r"""
+        Number of [`Traversal`]s without a valid geometry.
+        """
+    @invalid_traversal_count.setter
+    def invalid_traversal_count(self, value: builtins.int) -> None:
+        r"""
+        Number of [`Traversal`]s without a valid geometry.
+        """
+    @property
+    def anchor_count(self) -> builtins.int:
+        r"""
+        Number of named [`Anchor`]s over all the [`Lrm`]s.
+        """
+    @anchor_count.setter
+    def anchor_count(self, value: builtins.int) -> None:
+        r"""
+        Number of named [`Anchor`]s over all the [`Lrm`]s.
+        """
+    @property
+    def unnamed_anchor_count(self) -> builtins.int:
+        r"""
+        Number of unnamed [`Anchor`]s over all the [`Lrm`]s.
+        """
+    @unnamed_anchor_count.setter
+    def unnamed_anchor_count(self, value: builtins.int) -> None:
+        r"""
+        Number of unnamed [`Anchor`]s over all the [`Lrm`]s.
+        """
+    @property
+    def total_length(self) -> builtins.float:
+        r"""
+        Sum of the lengths of the valid [`Traversal`]s, in meters.
+        """
+    @total_length.setter
+    def total_length(self, value: builtins.float) -> None:
+        r"""
+        Sum of the lengths of the valid [`Traversal`]s, in meters.
+        """
+    @property
+    def bbox_min(self) -> typing.Optional[Point]:
+        r"""
+        South-west corner of the bounding box of the network.
+        """
+    @bbox_min.setter
+    def bbox_min(self, value: typing.Optional[Point]) -> None:
+        r"""
+        South-west corner of the bounding box of the network.
+        """
+    @property
+    def bbox_max(self) -> typing.Optional[Point]:
+        r"""
+        North-east corner of the bounding box of the network.
+        """
+    @bbox_max.setter
+    def bbox_max(self, value: typing.Optional[Point]) -> None:
+        r"""
+        North-east corner of the bounding box of the network.
+        """
+    @property
+    def min_lrm_length(self) -> builtins.float:
+        r"""
+        Length of the shortest [`Lrm`], 0 if there is none.
+        """
+    @min_lrm_length.setter
+    def min_lrm_length(self, value: builtins.float) -> None:
+        r"""
+        Length of the shortest [`Lrm`], 0 if there is none.
+        """
+    @property
+    def median_lrm_length(self) -> builtins.float:
+        r"""
+        Median length of the [`Lrm`]s, 0 if there is none.
+        """
+    @median_lrm_length.setter
+    def median_lrm_length(self, value: builtins.float) -> None:
+        r"""
+        Median length of the [`Lrm`]s, 0 if there is none.
+        """
+    @property
+    def mean_lrm_length(self) -> builtins.float:
+        r"""
+        Average length of the [`Lrm`]s, 0 if there is none.
+        """
+    @mean_lrm_length.setter
+    def mean_lrm_length(self, value: builtins.float) -> None:
+        r"""
+        Average length of the [`Lrm`]s, 0 if there is none.
+        """
+    @property
+    def max_lrm_length(self) -> builtins.float:
+        r"""
+        Length of the longest [`Lrm`], 0 if there is none.
+        """
+    @max_lrm_length.setter
+    def max_lrm_length(self, value: builtins.float) -> None:
+        r"""
+        Length of the longest [`Lrm`], 0 if there is none.
+        """
+
+@typing.final
+class MeasuredAnchor:
+    r"""
+    An [`Anchor`] and its measure, see `anchors_between`.
+    """
+    @property
+    def anchor(self) -> Anchor:
+        r"""
+        The anchor.
+        """
+    @anchor.setter
+    def anchor(self, value: Anchor) -> None:
+        r"""
+        The anchor.
+        """
+    @property
+    def measure(self) -> LrmScaleMeasure:
+        r"""
+        The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+        """
+    @measure.setter
+    def measure(self, value: LrmScaleMeasure) -> None:
+        r"""
+        The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class MeasureRange:
+    r"""
+    A range of measures of an [`Lrm`], see `measure_ranges_in_polygon`.
+    """
+    @property
+    def from_measure(self) -> LrmScaleMeasure:
+        r"""
+        The smallest measure of the range.
+        """
+    @from_measure.setter
+    def from_measure(self, value: LrmScaleMeasure) -> None:
+        r"""
+        The smallest measure of the range.
+        """
+    @property
+    def to_measure(self) -> LrmScaleMeasure:
+        r"""
+        The largest measure of the range.
+        """
+    @to_measure.setter
+    def to_measure(self, value: LrmScaleMeasure) -> None:
+        r"""
+        The largest measure of the range.
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class MeasureTick:
+    r"""
+    A graduation of the scale of an [`Lrm`], to display it on a map.
+    """
+    @property
+    def measure(self) -> LrmScaleMeasure:
+        r"""
+        The measure of the graduation.
+        """
+    @measure.setter
+    def measure(self, value: LrmScaleMeasure) -> None:
+        r"""
+        The measure of the graduation.
+        """
+    @property
+    def point(self) -> Point:
+        r"""
+        The geographical position of the graduation on the [`Lrm`].
+        """
+    @point.setter
+    def point(self, value: Point) -> None:
+        r"""
+        The geographical position of the graduation on the [`Lrm`].
+        """
+    @property
+    def heading(self) -> builtins.float:
+        r"""
+        The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+        """
+    @heading.setter
+    def heading(self, value: builtins.float) -> None:
+        r"""
+        The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+        """
+    @property
+    def left(self) -> Point:
+        r"""
+        The end of the tick on the left of increasing measures.
+        """
+    @left.setter
+    def left(self, value: Point) -> None:
+        r"""
+        The end of the tick on the left of increasing measures.
+        """
+    @property
+    def right(self) -> Point:
+        r"""
+        The end of the tick on the right of increasing measures.
+        """
+    @right.setter
+    def right(self, value: Point) -> None:
+        r"""
+        The end of the tick on the right of increasing measures.
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class Node:
+    r"""
+    A Node is a topological element of the [`Lrs`] that represents a intersection (or an extremity) of an [`Lrm`]
+    """
+    @property
+    def id(self) -> builtins.str:
+        r"""
+        Identifies this [`Node`].
+        """
+    @id.setter
+    def id(self, value: builtins.str) -> None:
+        r"""
+        Identifies this [`Node`].
+        """
+    @property
+    def geometry(self) -> typing.Optional[Point]:
+        r"""
+        Coordinates of the [`Node`]. They are in the same projection system (e.g. spherical or planar) as the [`Lrs`]
+        """
+    @geometry.setter
+    def geometry(self, value: typing.Optional[Point]) -> None:
+        r"""
+        Coordinates of the [`Node`]. They are in the same projection system (e.g. spherical or planar) as the [`Lrs`]
+        """
+    @property
+    def properties(self) -> builtins.dict[builtins.str, builtins.str]:
+        r"""
+        Metadata to describe the [`Node`].
+        """
+    @properties.setter
+    def properties(self, value: builtins.dict[builtins.str, builtins.str]) -> None:
+        r"""
+        Metadata to describe the [`Node`].
+        """
+
+@typing.final
+class OrientedPoint:
+    r"""
+    A position on an [`Lrm`] and the direction of increasing measures there.
+    """
+    @property
+    def point(self) -> Point:
+        r"""
+        The geographical position.
+        """
+    @point.setter
+    def point(self, value: Point) -> None:
+        r"""
+        The geographical position.
+        """
+    @property
+    def heading(self) -> builtins.float:
+        r"""
+        The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+        """
+    @heading.setter
+    def heading(self, value: builtins.float) -> None:
+        r"""
+        The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+        """
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class Point:
+    r"""
+    A geographical [`Point`], it can be either a projected or spherical coordinates.
+    """
+    @property
+    def x(self) -> builtins.float:
+        r"""
+        Position on x-axis or `longitude`.
+        """
+    @x.setter
+    def x(self, value: builtins.float) -> None:
+        r"""
+        Position on x-axis or `longitude`.
+        """
+    @property
+    def y(self) -> builtins.float:
+        r"""
+        Position on y-axis or `latitude`.
+        """
+    @y.setter
+    def y(self, value: builtins.float) -> None:
+        r"""
+        Position on y-axis or `latitude`.
+        """
+    def __new__(cls, x: builtins.float, y: builtins.float) -> Point:
+        r"""
+        Build a new geographical point.
+        
+        When using spherical coordinates, longitude is x and latitude y
+        """
+    def __repr__(self) -> builtins.str: ...
+    @property
+    def __geo_interface__(self) -> builtins.dict[builtins.str, typing.Any]:
+        r"""
+        The point as a GeoJSON-like dict, so that `shapely.geometry.shape` can convert it.
+        """
+
+@typing.final
+class PointArrays:
+    r"""
+    The positions of the measures given to `resolve_array`, with NaN for the measures that cannot be resolved.
+    
+    It can be unpacked as `lons, lats`.
+    """
+    @property
+    def lons(self) -> numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Longitudes, or positions on the x-axis.
+        """
+    @property
+    def lats(self) -> numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Latitudes, or positions on the y-axis.
+        """
+    def __len__(self) -> builtins.int: ...
+    def __iter__(self) -> typing.Iterator[numpy.typing.NDArray[numpy.float64]]: ...
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class ProjectionArrays:
+    r"""
+    The nearest projection of every position given to `lookup_array`, with one item per position in each array.
+    
+    It can be unpacked as `lrm_indices, anchor_names, scale_offsets, lateral_offsets`.
+    """
+    @property
+    def lrm_indices(self) -> numpy.typing.NDArray[numpy.int64]:
+        r"""
+        Index of the nearest [`Lrm`], -1 when no [`Lrm`] is close enough.
+        """
+    @property
+    def anchor_names(self) -> builtins.list[typing.Optional[builtins.str]]:
+        r"""
+        Name of the [`Anchor`] of the measure, `None` when no [`Lrm`] is close enough.
+        """
+    @property
+    def scale_offsets(self) -> numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Offset of the measure from the [`Anchor`], NaN when no [`Lrm`] is close enough.
+        """
+    @property
+    def lateral_offsets(self) -> numpy.typing.NDArray[numpy.float64]:
+        r"""
+        Distance in meters from the [`Lrm`], positive on the left, NaN when no [`Lrm`] is close enough.
+        """
+    def __len__(self) -> builtins.int: ...
+    def __iter__(self) -> typing.Iterator[typing.Any]: ...
+    def __repr__(self) -> builtins.str: ...
+
+@typing.final
+class Segment:
+    r"""
+    A segment is a topological element of the [`Lrs`] that represents a piece of the [`Curve`] of an [`Lrm`]
+    
+    It has a start and end [`Node`].
+    """
+    @property
+    def id(self) -> builtins.str:
+        r"""
+        Identifies this [`Segment`]
+        """
+    @id.setter
+    def id(self, value: builtins.str) -> None:
+        r"""
+        Identifies this [`Segment`]
+        """
+    @property
+    def properties(self) -> builtins.dict[builtins.str, builtins.str]:
+        r"""
+        Metadata to describe the [`Segment`]
+        """
+    @properties.setter
+    def properties(self, value: builtins.dict[builtins.str, builtins.str]) -> None:
+        r"""
+        Metadata to describe the [`Segment`]
+        """
+    @property
+    def start_node(self) -> builtins.int:
+        r"""
+        Start [`Node`]
+        """
+    @start_node.setter
+    def start_node(self, value: builtins.int) -> None:
+        r"""
+        Start [`Node`]
+        """
+    @property
+    def end_node(self) -> builtins.int:
+        r"""
+        End [`Node`]
+        """
+    @end_node.setter
+    def end_node(self, value: builtins.int) -> None:
+        r"""
+        End [`Node`]
+        """
+
+@typing.final
+class SegmentOfTraversal:
+    r"""
+    A traversal is composed by segments
+    """
+    @property
+    def segment_index(self) -> builtins.int:
+        r"""
+        Index of the considered segment. Use the value returned by [`Builder::add_segment`]
+        """
+    @segment_index.setter
+    def segment_index(self, value: builtins.int) -> None:
+        r"""
+        Index of the considered segment. Use the value returned by [`Builder::add_segment`]
+        """
+    @property
+    def reversed(self) -> builtins.bool:
+        r"""
+        When integrating the segment in the traversal, should we consider the coordinates in the reverse order
+        """
+    @reversed.setter
+    def reversed(self, value: builtins.bool) -> None:
+        r"""
+        When integrating the segment in the traversal, should we consider the coordinates in the reverse order
+        """
+    def __new__(cls, segment_index: builtins.int, reversed: builtins.bool) -> SegmentOfTraversal: ...
 
+@typing.final
+class TraceMatch:
+    r"""
+    A part of a trace matched on a single [`Lrm`].
+    """
+    @property
+    def lrm_handle(self) -> builtins.int:
+        r"""
+        Handle of the [`Lrm`] where the positions were matched.
+        """
+    @lrm_handle.setter
+    def lrm_handle(self, value: builtins.int) -> None:
+        r"""
+        Handle of the [`Lrm`] where the positions were matched.
+        """
+    @property
+    def entry(self) -> LrmScaleMeasure:
+        r"""
+        Measure of the first matched position.
+        """
+    @entry.setter
+    def entry(self, value: LrmScaleMeasure) -> None:
+        r"""
+        Measure of the first matched position.
+        """
+    @property
+    def exit(self) -> LrmScaleMeasure:
+        r"""
+        Measure of the farthest matched position.
+        """
+    @exit.setter
+    def exit(self, value: LrmScaleMeasure) -> None:
+        r"""
+        Measure of the farthest matched position.
+        """
+    @property
+    def first_point(self) -> builtins.int:
+        r"""
+        Index of the first position of the trace covered by this match.
+        """
+    @first_point.setter
+    def first_point(self, value: builtins.int) -> None:
+        r"""
+        Index of the first position of the trace covered by this match.
+        """
+    @property
+    def last_point(self) -> builtins.int:
+        r"""
+        Index of the last position of the trace covered by this match.
+        """
+    @last_point.setter
+    def last_point(self, value: builtins.int) -> None:
+        r"""
+        Index of the last position of the trace covered by this match.
+        """
+    def __repr__(self) -> builtins.str: ...
@@ -1,25 +1,57 @@
 //! High level extensions meant for an easy usage
 //! Those functions are exposed in wasm-bindings
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use liblrs::lrs::LrmHandle;
-use liblrs::lrs::{LrsBase, Properties};
+use liblrs::conformance::ConformanceAdapter;
+use liblrs::error_code::LrsErrorCode;
+use liblrs::lrs::Properties;
 use liblrs::lrs_ext::*;
-use pyo3::{exceptions::PyTypeError, prelude::*};
-use pyo3_stub_gen::{define_stub_info_gatherer, derive::*};
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1, PyUntypedArrayMethods};
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyIterator, PyList, PyTuple};
+use pyo3_stub_gen::{create_exception, define_stub_info_gatherer, derive::*};
 
 /// Holds the whole Linear Referencing System.
 #[gen_stub_pyclass]
 #[pyclass]
 pub struct Lrs {
     lrs: ExtLrs,
+    /// Where the LRS was read from, pickled as is instead of serializing the LRS again.
+    source: Source,
+}
+
+/// The data of an [`Lrs`], see [`Lrs::__reduce__`].
+enum Source {
+    /// The bytes given to `Lrs(data)` or built by the [`Builder`].
+    Bytes(Py<PyBytes>),
+    /// The file given to `Lrs.open`.
+    File(PathBuf),
+    /// The LRS was closed.
+    Closed,
 }
 
 #[pymodule]
 fn liblrs_python(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Lrs>()?;
+    m.add_class::<Lrm>()?;
     m.add_class::<LrmScaleMeasure>()?;
+    m.add_class::<LocatedPoint>()?;
+    m.add_class::<LrmMatch>()?;
+    m.add_class::<AnchorMarker>()?;
+    m.add_class::<MeasuredAnchor>()?;
+    m.add_class::<MeasureRange>()?;
+    m.add_class::<MeasureTick>()?;
+    m.add_class::<OrientedPoint>()?;
+    m.add_class::<LrmBounds>()?;
+    m.add_class::<LrmBbox>()?;
+    m.add_class::<LrmIntersection>()?;
+    m.add_class::<LrsStats>()?;
+    m.add_class::<TraceMatch>()?;
+    m.add_class::<ProjectionArrays>()?;
+    m.add_class::<PointArrays>()?;
     m.add_class::<Anchor>()?;
     m.add_class::<Point>()?;
     m.add_class::<Segment>()?;
@@ -28,9 +60,32 @@ fn liblrs_python(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<SegmentOfTraversal>()?;
     m.add_class::<Builder>()?;
     m.add_class::<DataIssueReporter>()?;
+    m.add("LrsError", m.py().get_type::<LrsError>())?;
     Ok(())
 }
 
+create_exception!(
+    liblrs_python,
+    LrsError,
+    PyTypeError,
+    "Error raised by the functions of the [`Lrs`].\n\nMatch on `code`, such as `LRM_NOT_FOUND`, rather than on the message. It derives from `TypeError`, that was raised before."
+);
+
+/// Builds an [`LrsError`] with the fields describing the error: `code`, `lrm_index`, `anchor_name`, `begin` and `end`.
+fn lrs_error(error: impl Into<LrsErrorCode>) -> PyErr {
+    let error = error.into();
+    let err = LrsError::new_err(error.to_string());
+    Python::attach(|py| {
+        let value = err.value(py);
+        value.setattr("code", error.code())?;
+        value.setattr("lrm_index", error.lrm_index())?;
+        value.setattr("anchor_name", error.anchor_name())?;
+        value.setattr("begin", error.range().map(|range| range.0))?;
+        value.setattr("end", error.range().map(|range| range.1))
+    })
+    .map_or_else(|setattr_err| setattr_err, |_| err)
+}
+
 #[derive(Clone, Copy, Debug)]
 /// A geographical [`Point`], it can be either a projected or spherical coordinates.
 #[gen_stub_pyclass]
@@ -56,8 +111,91 @@ impl Point {
     }
 
     fn __repr__(&self) -> String {
-        format!("{self:?}")
+        format!("Point(x={:?}, y={:?})", self.x, self.y)
+    }
+
+    /// The point as a GeoJSON-like dict, so that `shapely.geometry.shape` can convert it.
+    #[getter]
+    fn __geo_interface__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let interface = PyDict::new(py);
+        interface.set_item("type", "Point")?;
+        interface.set_item("coordinates", (self.x, self.y))?;
+        Ok(interface)
+    }
+}
+
+/// A [`Point`] or a point given by its `__geo_interface__`, such as a shapely Point
+fn extract_point(point: &Bound<'_, PyAny>) -> PyResult<geo_types::Point> {
+    if let Ok(point) = point.extract::<Point>() {
+        return Ok(point.into());
+    }
+    match geo_interface_coords(point)?[..] {
+        [coord] => Ok(coord.into()),
+        _ => Err(PyValueError::new_err("expected a Point")),
+    }
+}
+
+/// Builds a shapely geometry from its type and coordinates. shapely must be installed.
+fn shapely_geometry<'py>(
+    py: Python<'py>,
+    geometry_type: &str,
+    coordinates: impl IntoPyObject<'py>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let interface = PyDict::new(py);
+    interface.set_item("type", geometry_type)?;
+    interface.set_item("coordinates", coordinates)?;
+    py.import("shapely.geometry")?
+        .call_method1("shape", (interface,))
+}
+
+fn xy(coords: Vec<geo_types::Coord>) -> Vec<(f64, f64)> {
+    coords.into_iter().map(|coord| (coord.x, coord.y)).collect()
+}
+
+/// Builds a GeoDataFrame with one row per dict and its geometry, `None` when it could not be computed
+fn geodataframe<'py>(
+    py: Python<'py>,
+    rows: Vec<Bound<'py, PyDict>>,
+    geometries: Vec<Option<Bound<'py, PyAny>>>,
+    crs: Option<String>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item("geometry", geometries)?;
+    kwargs.set_item("crs", crs)?;
+    py.import("geopandas")?
+        .getattr("GeoDataFrame")?
+        .call((rows,), Some(&kwargs))
+}
+
+/// A row of a GeoDataFrame, starting with one column per property, see `Lrs.to_geodataframes`
+fn properties_row<'py>(
+    py: Python<'py>,
+    properties: impl IntoIterator<Item = (String, Bound<'py, PyAny>)>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let row = PyDict::new(py);
+    for (key, value) in properties {
+        row.set_item(key, value)?;
     }
+    Ok(row)
+}
+
+fn typed_properties<'py>(
+    py: Python<'py>,
+    properties: &liblrs::events::TypedProperties,
+) -> PyResult<Vec<(String, Bound<'py, PyAny>)>> {
+    use liblrs::events::PropertyValue;
+    properties
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                PropertyValue::Text(text) => text.into_pyobject(py)?.into_any(),
+                PropertyValue::Integer(integer) => integer.into_pyobject(py)?.into_any(),
+                PropertyValue::Float(float) => float.into_pyobject(py)?.into_any(),
+                PropertyValue::Boolean(boolean) => boolean.into_pyobject(py)?.to_owned().into_any(),
+            };
+            Ok((key.clone(), value))
+        })
+        .collect()
 }
 
 impl From<geo_types::Point> for Point {
@@ -195,7 +333,10 @@ impl LrmScaleMeasure {
     }
 
     fn __repr__(&self) -> String {
-        format!("{self:?}")
+        format!(
+            "LrmScaleMeasure(anchor_name={:?}, scale_offset={:?})",
+            self.anchor_name, self.scale_offset
+        )
     }
 }
 
@@ -275,7 +416,7 @@ impl From<AnchorOnLrm> for liblrs::builder::AnchorOnLrm {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[gen_stub_pyclass]
 #[pyclass]
 /// An `Anchor` is a reference point for a given [`Curve`].
@@ -293,12 +434,96 @@ pub struct Anchor {
     /// Position on the scale.
     #[pyo3(get, set)]
     pub scale_position: f64,
+    /// Surveyed position, before the projection on the [`Curve`].
+    #[pyo3(get, set)]
+    pub surveyed_position: Option<Point>,
+    /// Signed distance between the surveyed position and the [`Curve`], positive on the left.
+    #[pyo3(get, set)]
+    pub projection_offset: Option<f64>,
 }
 
+#[gen_stub_pymethods]
 #[pymethods]
 impl Anchor {
     fn __repr__(&self) -> String {
-        format!("{self:?}")
+        format!(
+            "Anchor(name={:?}, scale_position={}, curve_position={})",
+            self.name, self.scale_position, self.curve_position
+        )
+    }
+}
+
+/// Position of an item of a sequence of `len` items, negative positions counting from the end as in Python
+fn sequence_index(index: isize, len: usize) -> PyResult<usize> {
+    let position = if index < 0 {
+        len.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index as usize)
+    };
+    position
+        .filter(|&position| position < len)
+        .ok_or_else(|| PyIndexError::new_err(format!("index {index} out of range")))
+}
+
+#[derive(Clone, Debug)]
+#[gen_stub_pyclass]
+#[pyclass]
+/// An [`Lrm`] of the [`Lrs`] and its [`Anchor`]s, as given by `lrs[index]`, `lrs["id"]` or `for lrm in lrs`.
+///
+/// It can be indexed by the position or the name of an [`Anchor`], and iterated over its [`Anchor`]s.
+pub struct Lrm {
+    /// Index of the [`Lrm`] in the [`Lrs`].
+    #[pyo3(get)]
+    pub index: usize,
+    /// `id` of the [`Lrm`].
+    #[pyo3(get)]
+    pub id: String,
+    /// Metadata to describe the [`Lrm`].
+    #[pyo3(get)]
+    pub properties: Properties,
+    /// The [`Anchor`]s of the [`Lrm`].
+    #[pyo3(get)]
+    pub anchors: Vec<Anchor>,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Lrm {
+    fn __len__(&self) -> usize {
+        self.anchors.len()
+    }
+
+    /// The [`Anchor`] at a position, or the first one with a name.
+    fn __getitem__(
+        &self,
+        #[gen_stub(override_type(type_repr = "builtins.int | builtins.str"))] key: &Bound<
+            '_,
+            PyAny,
+        >,
+    ) -> PyResult<Anchor> {
+        if let Ok(name) = key.extract::<String>() {
+            return self
+                .anchors
+                .iter()
+                .find(|anchor| anchor.name == name)
+                .cloned()
+                .ok_or_else(|| PyKeyError::new_err(name));
+        }
+        let index = sequence_index(key.extract()?, self.anchors.len())?;
+        Ok(self.anchors[index].clone())
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, self.anchors.clone())?.try_iter()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Lrm(index={}, id={:?}, anchors={})",
+            self.index,
+            self.id,
+            self.anchors.len()
+        )
     }
 }
 
@@ -313,6 +538,8 @@ impl From<&liblrs::lrm_scale::Anchor> for Anchor {
             position: value.point().map(|p| p.into()),
             curve_position: value.curve_position(),
             scale_position: value.scale_position(),
+            surveyed_position: value.surveyed_point().map(|p| p.into()),
+            projection_offset: value.projection_offset(),
         }
     }
 }
@@ -330,6 +557,9 @@ pub struct LrmProjection {
     /// How far from the [`Lrm`] is the [`Point`] that has been projected.
     #[pyo3(get, set)]
     pub orthogonal_offset: f64,
+    /// Handle of the [`Traversal`] of the [`Lrm`] nearest to the [`Point`]: the main one or an alternate one.
+    #[pyo3(get, set)]
+    pub traversal_handle: usize,
 }
 
 impl From<&liblrs::lrs::LrmProjection> for LrmProjection {
@@ -341,178 +571,1525 @@ impl From<&liblrs::lrs::LrmProjection> for LrmProjection {
                 scale_offset: value.measure.measure.scale_offset,
             },
             orthogonal_offset: value.orthogonal_offset,
+            traversal_handle: value.traversal.0,
         }
     }
 }
 
 #[gen_stub_pymethods]
 #[pymethods]
-impl Lrs {
-    /// Load the data.
-    #[new]
-    pub fn load(#[gen_stub(override_type(type_repr = "bytes"))] data: &[u8]) -> PyResult<Lrs> {
-        ExtLrs::load(data)
-            .map(|lrs| Self { lrs })
-            .map_err(|e| PyTypeError::new_err(e.to_string()))
+impl LrmProjection {
+    fn __repr__(&self) -> String {
+        format!(
+            "LrmProjection(lrm_handle={}, measure={}, orthogonal_offset={:?})",
+            self.lrm_handle,
+            self.measure.__repr__(),
+            self.orthogonal_offset
+        )
     }
+}
 
-    /// How many LRMs compose the LRS.
-    pub fn lrm_len(&self) -> usize {
-        self.lrs.lrm_len()
-    }
+#[gen_stub_pyclass]
+#[pyclass]
+/// The nearest position of a [`Point`] on all the [`Lrm`]s.
+pub struct LocatedPoint {
+    /// Index of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub lrm_index: usize,
+    /// `id` of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub lrm_id: String,
+    /// Name of the [`Anchor`] of the measure.
+    #[pyo3(get, set)]
+    pub anchor_name: String,
+    /// Offset of the measure from the [`Anchor`], in the unit of the scale.
+    #[pyo3(get, set)]
+    pub scale_offset: f64,
+    /// Distance in meters from the [`Lrm`]: positive if the [`Point`] is on the left of the [`Lrm`], negative on the right.
+    #[pyo3(get, set)]
+    pub lateral_offset_m: f64,
+    /// Distance in meters between the [`Point`] and the [`Lrm`].
+    #[pyo3(get, set)]
+    pub distance_m: f64,
+}
 
-    /// Return the geometry of the LRM.
-    pub fn get_lrm_geom(&self, index: usize) -> PyResult<Vec<Point>> {
-        self.lrs
-            .get_lrm_geom(index)
-            .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
-            .map_err(|e| PyTypeError::new_err(e.to_string()))
+impl From<liblrs::lrs_ext::LocatedPoint> for LocatedPoint {
+    fn from(value: liblrs::lrs_ext::LocatedPoint) -> Self {
+        Self {
+            lrm_index: value.lrm_index,
+            lrm_id: value.lrm_id,
+            anchor_name: value.anchor_name,
+            scale_offset: value.scale_offset,
+            lateral_offset_m: value.lateral_offset_m,
+            distance_m: value.distance_m,
+        }
     }
+}
 
-    /// `id` of the [`LrmScale`].
-    pub fn get_lrm_scale_id(&self, index: usize) -> String {
-        self.lrs.get_lrm_scale_id(index)
+#[gen_stub_pymethods]
+#[pymethods]
+impl LocatedPoint {
+    fn __repr__(&self) -> String {
+        format!(
+            "LocatedPoint(lrm_index={}, lrm_id={:?}, anchor_name={:?}, scale_offset={:?}, lateral_offset_m={:?})",
+            self.lrm_index, self.lrm_id, self.anchor_name, self.scale_offset, self.lateral_offset_m
+        )
     }
+}
 
-    /// All the [`Anchor`]s of a LRM.
-    pub fn get_anchors(&self, lrm_index: usize) -> Vec<Anchor> {
-        self.lrs
-            .get_anchors(lrm_index)
-            .iter()
-            .map(Anchor::from)
-            .collect()
-    }
+#[gen_stub_pyclass]
+#[pyclass]
+/// An [`Lrm`] whose id matches a query, see `search_lrms`.
+pub struct LrmMatch {
+    /// Index of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub lrm_index: usize,
+    /// `id` of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub lrm_id: String,
+    /// How well the [`Lrm`] matches the query, between 0 (excluded) and 1 for an exact match.
+    #[pyo3(get, set)]
+    pub score: f64,
+}
 
-    /// Get the position given a [`LrmScaleMeasure`].
-    pub fn resolve(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> PyResult<Point> {
-        self.lrs
-            .resolve(lrm_index, &measure.into())
-            .map(Point::from)
-            .map_err(|e| PyTypeError::new_err(e.to_string()))
+impl From<liblrs::lrs_ext::LrmMatch> for LrmMatch {
+    fn from(value: liblrs::lrs_ext::LrmMatch) -> Self {
+        Self {
+            lrm_index: value.lrm_index,
+            lrm_id: value.lrm_id,
+            score: value.score,
+        }
     }
+}
 
-    /// Get the positon along the curve given a [`LrmScaleMeasure`]
-    /// The value will be between 0.0 and 1.0, both included
-    pub fn locate_point(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> PyResult<f64> {
-        self.lrs.lrs.lrms[lrm_index]
-            .scale
-            .locate_point(&measure.into())
-            .map_err(|e| PyTypeError::new_err(e.to_string()))
+#[gen_stub_pymethods]
+#[pymethods]
+impl LrmMatch {
+    fn __repr__(&self) -> String {
+        format!(
+            "LrmMatch(lrm_index={}, lrm_id={:?}, score={:?})",
+            self.lrm_index, self.lrm_id, self.score
+        )
     }
+}
 
-    /// Given two [`LrmScaleMeasure`]s, return a range of [`Point`] that represent a line string.
-    pub fn resolve_range(
-        &self,
-        lrm_index: usize,
-        from_measure: &LrmScaleMeasure,
-        to_measure: &LrmScaleMeasure,
-    ) -> PyResult<Vec<Point>> {
-        self.lrs
-            .resolve_range(lrm_index, &from_measure.into(), &to_measure.into())
-            .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
-            .map_err(|e| PyTypeError::new_err(e.to_string()))
-    }
+#[gen_stub_pyclass]
+#[pyclass]
+/// An [`Anchor`] placed on its [`Lrm`], to display it on a map.
+pub struct AnchorMarker {
+    /// Name of the [`Anchor`], `None` for an unnamed [`Anchor`].
+    #[pyo3(get, set)]
+    pub name: Option<String>,
+    /// The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+    #[pyo3(get, set)]
+    pub measure: LrmScaleMeasure,
+    /// The position of the [`Anchor`] on the [`Lrm`].
+    #[pyo3(get, set)]
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    #[pyo3(get, set)]
+    pub heading: f64,
+}
 
-    /// Given a ID returns the corresponding lrs index (or None if not found)
-    pub fn find_lrm(&self, lrm_id: &str) -> Option<usize> {
-        self.lrs.lrs.get_lrm(lrm_id).map(|handle| handle.0)
+impl From<liblrs::lrs_ext::AnchorMarker> for AnchorMarker {
+    fn from(value: liblrs::lrs_ext::AnchorMarker) -> Self {
+        Self {
+            name: value.name,
+            measure: (&value.measure).into(),
+            point: value.point.into(),
+            heading: value.heading,
+        }
     }
+}
 
-    /// Projects a [`Point`] on all applicable [`Traversal`]s to a given [`Lrm`].
-    /// The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
-    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
-    fn lookup(&self, point: Point, lrm_handle: usize) -> Vec<LrmProjection> {
-        self.lrs
-            .lrs
-            .lookup(point.into(), LrmHandle(lrm_handle))
-            .iter()
-            .map(LrmProjection::from)
-            .collect()
+#[gen_stub_pymethods]
+#[pymethods]
+impl AnchorMarker {
+    fn __repr__(&self) -> String {
+        format!(
+            "AnchorMarker(name={}, measure={}, point={})",
+            self.name
+                .as_ref()
+                .map_or("None".to_owned(), |name| format!("{name:?}")),
+            self.measure.__repr__(),
+            self.point.__repr__()
+        )
     }
+}
 
-    /// Projects a [`Point`] on all applicable [`Traversal`]s nearby.
-    /// The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
-    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
-    fn lookup_lrms(&self, point: Point) -> Vec<LrmProjection> {
-        self.lrs
-            .lrs
-            .lookup_lrms(point.into())
-            .iter()
-            .map(LrmProjection::from)
-            .collect()
-    }
+#[gen_stub_pyclass]
+#[pyclass]
+/// An [`Anchor`] and its measure, see `anchors_between`.
+pub struct MeasuredAnchor {
+    /// The anchor.
+    #[pyo3(get, set)]
+    pub anchor: Anchor,
+    /// The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+    #[pyo3(get, set)]
+    pub measure: LrmScaleMeasure,
+}
 
-    /// [`Properties`] of the lrs
-    pub fn lrs_properties(&self) -> Properties {
-        self.lrs.lrs_properties().clone()
+impl From<liblrs::lrs_ext::MeasuredAnchor> for MeasuredAnchor {
+    fn from(value: liblrs::lrs_ext::MeasuredAnchor) -> Self {
+        Self {
+            anchor: (&value.anchor).into(),
+            measure: (&value.measure).into(),
+        }
     }
+}
 
-    /// [`Properties`] for a given lrm
-    pub fn lrm_properties(&self, lrm_index: usize) -> Properties {
-        self.lrs.lrm_properties(lrm_index).clone()
+#[gen_stub_pymethods]
+#[pymethods]
+impl MeasuredAnchor {
+    fn __repr__(&self) -> String {
+        format!(
+            "MeasuredAnchor(anchor={}, measure={})",
+            self.anchor.__repr__(),
+            self.measure.__repr__()
+        )
     }
+}
 
-    /// [`Properties`] for a given anchor
-    pub fn anchor_properties(&self, lrm_index: usize, anchor_index: usize) -> Properties {
-        self.lrs.anchor_properties(lrm_index, anchor_index).clone()
-    }
+#[gen_stub_pyclass]
+#[pyclass]
+/// A range of measures of an [`Lrm`], see `measure_ranges_in_polygon`.
+pub struct MeasureRange {
+    /// The smallest measure of the range.
+    #[pyo3(get, set)]
+    pub from_measure: LrmScaleMeasure,
+    /// The largest measure of the range.
+    #[pyo3(get, set)]
+    pub to_measure: LrmScaleMeasure,
+}
 
-    /// Return a single [`Node`]
-    pub fn get_node(&self, node_index: usize) -> Node {
-        (&self.lrs.lrs.nodes[node_index]).into()
+impl From<liblrs::lrs_ext::MeasureRange> for MeasureRange {
+    fn from(value: liblrs::lrs_ext::MeasureRange) -> Self {
+        Self {
+            from_measure: (&value.from).into(),
+            to_measure: (&value.to).into(),
+        }
     }
+}
 
-    /// Return all the [`Node`] of the lrs
-    pub fn get_nodes(&self) -> Vec<Node> {
-        self.lrs.lrs.nodes.iter().map(|node| node.into()).collect()
+#[gen_stub_pymethods]
+#[pymethods]
+impl MeasureRange {
+    fn __repr__(&self) -> String {
+        format!(
+            "MeasureRange(from_measure={}, to_measure={})",
+            self.from_measure.__repr__(),
+            self.to_measure.__repr__()
+        )
     }
+}
 
-    /// Return a single [`Segment`]
-    pub fn get_segment(&self, segment_index: usize) -> Segment {
-        (&self.lrs.lrs.segments[segment_index]).into()
+#[gen_stub_pyclass]
+#[pyclass]
+/// A graduation of the scale of an [`Lrm`], to display it on a map.
+pub struct MeasureTick {
+    /// The measure of the graduation.
+    #[pyo3(get, set)]
+    pub measure: LrmScaleMeasure,
+    /// The geographical position of the graduation on the [`Lrm`].
+    #[pyo3(get, set)]
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    #[pyo3(get, set)]
+    pub heading: f64,
+    /// The end of the tick on the left of increasing measures.
+    #[pyo3(get, set)]
+    pub left: Point,
+    /// The end of the tick on the right of increasing measures.
+    #[pyo3(get, set)]
+    pub right: Point,
+}
+
+impl From<liblrs::lrs_ext::MeasureTick> for MeasureTick {
+    fn from(value: liblrs::lrs_ext::MeasureTick) -> Self {
+        Self {
+            measure: (&value.measure).into(),
+            point: value.point.into(),
+            heading: value.heading,
+            left: value.left.into(),
+            right: value.right.into(),
+        }
     }
+}
 
-    /// All the [`Segment`] of the lrs
-    pub fn get_segments(&self) -> Vec<Segment> {
-        self.lrs.lrs.segments.iter().map(|seg| seg.into()).collect()
+#[gen_stub_pymethods]
+#[pymethods]
+impl MeasureTick {
+    fn __repr__(&self) -> String {
+        format!(
+            "MeasureTick(measure={}, point={})",
+            self.measure.__repr__(),
+            self.point.__repr__()
+        )
     }
 }
 
 #[gen_stub_pyclass]
 #[pyclass]
-struct Builder {
-    inner: liblrs::builder::Builder<'static>,
+/// A position on an [`Lrm`] and the direction of increasing measures there.
+pub struct OrientedPoint {
+    /// The geographical position.
+    #[pyo3(get, set)]
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    #[pyo3(get, set)]
+    pub heading: f64,
 }
 
-#[gen_stub_pymethods]
-#[pymethods]
-impl Builder {
-    #[new]
-    /// Instantiate a new builder
-    fn new() -> Self {
+impl From<liblrs::lrs_ext::OrientedPoint> for OrientedPoint {
+    fn from(value: liblrs::lrs_ext::OrientedPoint) -> Self {
         Self {
-            inner: liblrs::builder::Builder::new(),
+            point: value.point.into(),
+            heading: value.heading,
         }
     }
+}
 
-    /// Add a new topological node (e.g. a railway switch)
-    pub fn add_node(&mut self, id: &str, coord: Point, properties: Properties) -> usize {
-        self.inner.add_node(id, coord.into(), properties)
-    }
-
-    /// Add a new anchor by its coordinates
-    #[pyo3(signature = (id, coord, properties, name=None))]
-    pub fn add_anchor(
-        &mut self,
-        id: &str,
-        coord: Point,
-        properties: Properties,
-        name: Option<&str>,
-    ) -> usize {
-        self.inner.add_anchor(id, name, coord.into(), properties)
+#[gen_stub_pymethods]
+#[pymethods]
+impl OrientedPoint {
+    fn __repr__(&self) -> String {
+        format!(
+            "OrientedPoint(point={}, heading={:?})",
+            self.point.__repr__(),
+            self.heading
+        )
     }
+}
 
-    /// Add a new anchor by its position along the curve
+#[gen_stub_pyclass]
+#[pyclass]
+/// The measures at both ends of an [`Lrm`] and its length.
+pub struct LrmBounds {
+    /// The smallest measure of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub min_measure: LrmScaleMeasure,
+    /// The largest measure of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub max_measure: LrmScaleMeasure,
+    /// The length of the [`Lrm`] in meters.
+    #[pyo3(get, set)]
+    pub length: f64,
+}
+
+impl From<&liblrs::lrs::LrmBounds> for LrmBounds {
+    fn from(value: &liblrs::lrs::LrmBounds) -> Self {
+        Self {
+            min_measure: (&value.min_measure).into(),
+            max_measure: (&value.max_measure).into(),
+            length: value.length,
+        }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl LrmBounds {
+    fn __repr__(&self) -> String {
+        format!(
+            "LrmBounds(min_measure={}, max_measure={}, length={:?})",
+            self.min_measure.__repr__(),
+            self.max_measure.__repr__(),
+            self.length
+        )
+    }
+}
+
+#[gen_stub_pyclass]
+#[pyclass]
+/// Where an [`Lrm`] is, to frame it on a map, see `get_lrm_bbox`.
+pub struct LrmBbox {
+    /// South-west corner of the bounding box of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub bbox_min: Point,
+    /// North-east corner of the bounding box of the [`Lrm`].
+    #[pyo3(get, set)]
+    pub bbox_max: Point,
+    /// The middle of the [`Lrm`], half way along its geometry.
+    #[pyo3(get, set)]
+    pub center: Point,
+}
+
+impl From<liblrs::lrs_ext::LrmBbox> for LrmBbox {
+    fn from(value: liblrs::lrs_ext::LrmBbox) -> Self {
+        Self {
+            bbox_min: value.bbox.min().into(),
+            bbox_max: value.bbox.max().into(),
+            center: value.center.into(),
+        }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl LrmBbox {
+    fn __repr__(&self) -> String {
+        format!(
+            "LrmBbox(bbox_min={}, bbox_max={}, center={})",
+            self.bbox_min.__repr__(),
+            self.bbox_max.__repr__(),
+            self.center.__repr__()
+        )
+    }
+}
+
+#[gen_stub_pyclass]
+#[pyclass]
+/// Where two [`Lrm`]s cross or touch.
+pub struct LrmIntersection {
+    /// The measure of the intersection on the first [`Lrm`].
+    #[pyo3(get, set)]
+    pub measure_a: LrmScaleMeasure,
+    /// The measure of the intersection on the second [`Lrm`].
+    #[pyo3(get, set)]
+    pub measure_b: LrmScaleMeasure,
+    /// The geographical position of the intersection.
+    #[pyo3(get, set)]
+    pub point: Point,
+}
+
+impl From<&liblrs::lrs::LrmIntersection> for LrmIntersection {
+    fn from(value: &liblrs::lrs::LrmIntersection) -> Self {
+        Self {
+            measure_a: (&value.measure_a).into(),
+            measure_b: (&value.measure_b).into(),
+            point: value.point.into(),
+        }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl LrmIntersection {
+    fn __repr__(&self) -> String {
+        format!(
+            "LrmIntersection(measure_a={}, measure_b={}, point={})",
+            self.measure_a.__repr__(),
+            self.measure_b.__repr__(),
+            self.point.__repr__()
+        )
+    }
+}
+
+#[gen_stub_pyclass]
+#[pyclass]
+/// Counts and sizes describing an [`Lrs`], to sanity-check a file.
+pub struct LrsStats {
+    /// Number of [`Lrm`]s.
+    #[pyo3(get, set)]
+    pub lrm_count: usize,
+    /// Number of [`Traversal`]s.
+    #[pyo3(get, set)]
+    pub traversal_count: usize,
+    /// Number of [`Traversal`]s without a valid geometry.
+    #[pyo3(get, set)]
+    pub invalid_traversal_count: usize,
+    /// Number of named [`Anchor`]s over all the [`Lrm`]s.
+    #[pyo3(get, set)]
+    pub anchor_count: usize,
+    /// Number of unnamed [`Anchor`]s over all the [`Lrm`]s.
+    #[pyo3(get, set)]
+    pub unnamed_anchor_count: usize,
+    /// Sum of the lengths of the valid [`Traversal`]s, in meters.
+    #[pyo3(get, set)]
+    pub total_length: f64,
+    /// South-west corner of the bounding box of the network.
+    #[pyo3(get, set)]
+    pub bbox_min: Option<Point>,
+    /// North-east corner of the bounding box of the network.
+    #[pyo3(get, set)]
+    pub bbox_max: Option<Point>,
+    /// Length of the shortest [`Lrm`], 0 if there is none.
+    #[pyo3(get, set)]
+    pub min_lrm_length: f64,
+    /// Median length of the [`Lrm`]s, 0 if there is none.
+    #[pyo3(get, set)]
+    pub median_lrm_length: f64,
+    /// Average length of the [`Lrm`]s, 0 if there is none.
+    #[pyo3(get, set)]
+    pub mean_lrm_length: f64,
+    /// Length of the longest [`Lrm`], 0 if there is none.
+    #[pyo3(get, set)]
+    pub max_lrm_length: f64,
+}
+
+impl From<&liblrs::stats::LrsStats> for LrsStats {
+    fn from(value: &liblrs::stats::LrsStats) -> Self {
+        let lengths = value.lrm_lengths;
+        Self {
+            lrm_count: value.lrm_count,
+            traversal_count: value.traversal_count,
+            invalid_traversal_count: value.invalid_traversal_count,
+            anchor_count: value.anchor_count,
+            unnamed_anchor_count: value.unnamed_anchor_count,
+            total_length: value.total_length,
+            bbox_min: value.bbox.map(|bbox| bbox.min().into()),
+            bbox_max: value.bbox.map(|bbox| bbox.max().into()),
+            min_lrm_length: lengths.map_or(0., |lengths| lengths.min),
+            median_lrm_length: lengths.map_or(0., |lengths| lengths.median),
+            mean_lrm_length: lengths.map_or(0., |lengths| lengths.mean),
+            max_lrm_length: lengths.map_or(0., |lengths| lengths.max),
+        }
+    }
+}
+
+#[gen_stub_pyclass]
+#[pyclass]
+/// The nearest projection of every position given to `lookup_array`, with one item per position in each array.
+///
+/// It can be unpacked as `lrm_indices, anchor_names, scale_offsets, lateral_offsets`.
+pub struct ProjectionArrays {
+    /// Index of the nearest [`Lrm`], -1 when no [`Lrm`] is close enough.
+    #[pyo3(get)]
+    pub lrm_indices: Py<PyArray1<i64>>,
+    /// Name of the [`Anchor`] of the measure, `None` when no [`Lrm`] is close enough.
+    #[pyo3(get)]
+    pub anchor_names: Vec<Option<String>>,
+    /// Offset of the measure from the [`Anchor`], NaN when no [`Lrm`] is close enough.
+    #[pyo3(get)]
+    pub scale_offsets: Py<PyArray1<f64>>,
+    /// Distance in meters from the [`Lrm`], positive on the left, NaN when no [`Lrm`] is close enough.
+    #[pyo3(get)]
+    pub lateral_offsets: Py<PyArray1<f64>>,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl ProjectionArrays {
+    fn __len__(&self) -> usize {
+        self.anchor_names.len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        (
+            self.lrm_indices.clone_ref(py),
+            self.anchor_names.clone(),
+            self.scale_offsets.clone_ref(py),
+            self.lateral_offsets.clone_ref(py),
+        )
+            .into_pyobject(py)?
+            .try_iter()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ProjectionArrays(len={})", self.anchor_names.len())
+    }
+}
+
+#[gen_stub_pyclass]
+#[pyclass]
+/// The positions of the measures given to `resolve_array`, with NaN for the measures that cannot be resolved.
+///
+/// It can be unpacked as `lons, lats`.
+pub struct PointArrays {
+    /// Longitudes, or positions on the x-axis.
+    #[pyo3(get)]
+    pub lons: Py<PyArray1<f64>>,
+    /// Latitudes, or positions on the y-axis.
+    #[pyo3(get)]
+    pub lats: Py<PyArray1<f64>>,
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl PointArrays {
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.lons.bind(py).len()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        (self.lons.clone_ref(py), self.lats.clone_ref(py))
+            .into_pyobject(py)?
+            .try_iter()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> String {
+        format!("PointArrays(len={})", self.lons.bind(py).len())
+    }
+}
+
+#[gen_stub_pyclass]
+#[pyclass]
+/// A part of a trace matched on a single [`Lrm`].
+pub struct TraceMatch {
+    /// Handle of the [`Lrm`] where the positions were matched.
+    #[pyo3(get, set)]
+    pub lrm_handle: usize,
+    /// Measure of the first matched position.
+    #[pyo3(get, set)]
+    pub entry: LrmScaleMeasure,
+    /// Measure of the farthest matched position.
+    #[pyo3(get, set)]
+    pub exit: LrmScaleMeasure,
+    /// Index of the first position of the trace covered by this match.
+    #[pyo3(get, set)]
+    pub first_point: usize,
+    /// Index of the last position of the trace covered by this match.
+    #[pyo3(get, set)]
+    pub last_point: usize,
+}
+
+impl From<&liblrs::trace::TraceMatch> for TraceMatch {
+    fn from(value: &liblrs::trace::TraceMatch) -> Self {
+        Self {
+            lrm_handle: value.lrm.0,
+            entry: (&value.entry).into(),
+            exit: (&value.exit).into(),
+            first_point: value.points.start,
+            last_point: value.points.end - 1,
+        }
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl TraceMatch {
+    fn __repr__(&self) -> String {
+        format!(
+            "TraceMatch(lrm_handle={}, entry={}, exit={}, first_point={}, last_point={})",
+            self.lrm_handle,
+            self.entry.__repr__(),
+            self.exit.__repr__(),
+            self.first_point,
+            self.last_point
+        )
+    }
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Lrs {
+    /// Load the data.
+    #[new]
+    pub fn load(
+        #[gen_stub(override_type(type_repr = "bytes"))] data: Bound<'_, PyBytes>,
+    ) -> PyResult<Lrs> {
+        let lrs = ExtLrs::load(data.as_bytes()).map_err(lrs_error)?;
+        Ok(Self {
+            lrs,
+            source: Source::Bytes(data.unbind()),
+        })
+    }
+
+    /// Load the data from a file. The file is memory-mapped instead of being read in Python memory.
+    ///
+    /// The whole LRS is still loaded: only the copy of the file is saved.
+    ///
+    /// It can be used in a `with` block to release the memory at the end of the block.
+    #[staticmethod]
+    pub fn open(py: Python<'_>, path: PathBuf) -> PyResult<Lrs> {
+        let lrs = py.detach(|| ExtLrs::open(&path)).map_err(lrs_error)?;
+        Ok(Self {
+            lrs,
+            source: Source::File(path),
+        })
+    }
+
+    /// Releases the memory of the LRS. It has no LRM anymore.
+    pub fn close(&mut self) {
+        self.lrs.close();
+        self.source = Source::Closed;
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Closes the LRS at the end of a `with` block.
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, PyTuple>) -> bool {
+        self.close();
+        false
+    }
+
+    /// Serializes the LRS, e.g. to save it after editing it. `Lrs(data)` loads it back.
+    pub fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.lrs.to_bytes())
+    }
+
+    /// Builds the network of a golden dataset, such as `tests/conformance/golden.txt`.
+    #[staticmethod]
+    pub fn from_golden_dataset(py: Python<'_>, dataset: &str) -> PyResult<Lrs> {
+        let network = liblrs::conformance::parse_network(dataset)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let data = network
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Lrs::load(PyBytes::new(py, &data))
+    }
+
+    /// Runs the cases of a golden dataset through the python facing functions.
+    ///
+    /// Returns the messages of the failed cases: it is empty when every case passes.
+    pub fn check_conformance(&self, dataset: &str, tolerance: f64) -> PyResult<Vec<String>> {
+        let cases = liblrs::conformance::parse_cases(dataset)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let report = liblrs::conformance::run(self, &cases, tolerance);
+        Ok(report
+            .failures
+            .into_iter()
+            .map(|failure| format!("case {}: {}", failure.case_index, failure.message))
+            .collect())
+    }
+
+    /// Pickles the LRS as a call to `Lrs(data)`, e.g. to send it to `multiprocessing` workers or to cache it with joblib.
+    ///
+    /// The data is the one the LRS was loaded from, as is.
+    fn __reduce__<'py>(slf: &Bound<'py, Self>) -> PyResult<Bound<'py, PyTuple>> {
+        let py = slf.py();
+        let data = match &slf.borrow().source {
+            Source::Bytes(data) => data.bind(py).clone(),
+            Source::File(path) => PyBytes::new(py, &std::fs::read(path)?),
+            Source::Closed => slf.borrow().to_bytes(py),
+        };
+        PyTuple::new(
+            py,
+            [
+                slf.get_type().into_any(),
+                PyTuple::new(py, [data])?.into_any(),
+            ],
+        )
+    }
+
+    fn __len__(&self) -> usize {
+        self.lrs.lrm_len()
+    }
+
+    /// The [`Lrm`] at a position, or the one with an `id`.
+    fn __getitem__(
+        &self,
+        #[gen_stub(override_type(type_repr = "builtins.int | builtins.str"))] key: &Bound<
+            '_,
+            PyAny,
+        >,
+    ) -> PyResult<Lrm> {
+        let index = match key.extract::<String>() {
+            Ok(id) => self.find_lrm(&id).ok_or_else(|| PyKeyError::new_err(id))?,
+            Err(_) => sequence_index(key.extract()?, self.lrs.lrm_len())?,
+        };
+        self.get_lrm(index)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        let lrms = (0..self.lrs.lrm_len())
+            .map(|index| self.get_lrm(index))
+            .collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, lrms)?.try_iter()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Lrs(lrms={}, crs={})",
+            self.lrs.lrm_len(),
+            self.lrs
+                .crs()
+                .map_or("None".to_owned(), |crs| format!("{crs:?}"))
+        )
+    }
+
+    /// The [`Lrm`] at a position, with its id, properties and [`Anchor`]s.
+    pub fn get_lrm(&self, index: usize) -> PyResult<Lrm> {
+        Ok(Lrm {
+            index,
+            id: self.get_lrm_scale_id(index)?,
+            properties: self.lrm_properties(index)?,
+            anchors: self.get_anchors(index)?,
+        })
+    }
+
+    /// Coordinate reference system of the coordinates, such as `EPSG:4326`, if it was recorded.
+    pub fn crs(&self) -> Option<String> {
+        self.lrs.crs()
+    }
+
+    /// How many LRMs compose the LRS.
+    pub fn lrm_len(&self) -> usize {
+        self.lrs.lrm_len()
+    }
+
+    /// Return the geometry of the LRM.
+    pub fn get_lrm_geom(&self, index: usize) -> PyResult<Vec<Point>> {
+        self.lrs
+            .get_lrm_geom(index)
+            .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
+            .map_err(lrs_error)
+    }
+
+    /// Return the geometry of the LRM as a shapely LineString. shapely must be installed.
+    pub fn get_lrm_geom_shapely<'py>(
+        &self,
+        py: Python<'py>,
+        index: usize,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let coords = self.lrs.get_lrm_geom(index).map_err(lrs_error)?;
+        shapely_geometry(py, "LineString", xy(coords))
+    }
+
+    /// Return the geometry of the LRM, ordered by increasing measures.
+    pub fn get_oriented_lrm_geom(&self, index: usize) -> PyResult<Vec<Point>> {
+        self.lrs
+            .get_oriented_lrm_geom(index)
+            .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
+            .map_err(lrs_error)
+    }
+
+    /// Whether the measures of the LRM increase along the geometry returned by `get_lrm_geom`.
+    pub fn measures_increase(&self, index: usize) -> PyResult<bool> {
+        self.lrs.measures_increase(index).map_err(lrs_error)
+    }
+
+    /// Whether the LRM is a loop, where measures wrap around past its origin.
+    pub fn is_loop(&self, index: usize) -> PyResult<bool> {
+        self.lrs.is_loop(index).map_err(lrs_error)
+    }
+
+    /// The measures at both ends of the LRM and its length in meters.
+    pub fn lrm_bounds(&self, index: usize) -> PyResult<LrmBounds> {
+        self.lrs
+            .lrm_bounds(index)
+            .map(|bounds| LrmBounds::from(&bounds))
+            .map_err(lrs_error)
+    }
+
+    /// `id` of the [`LrmScale`].
+    pub fn get_lrm_scale_id(&self, index: usize) -> PyResult<String> {
+        self.lrs.get_lrm_scale_id(index).map_err(lrs_error)
+    }
+
+    /// All the [`Anchor`]s of a LRM.
+    pub fn get_anchors(&self, lrm_index: usize) -> PyResult<Vec<Anchor>> {
+        self.lrs
+            .get_anchors(lrm_index)
+            .map(|anchors| anchors.iter().map(Anchor::from).collect())
+            .map_err(lrs_error)
+    }
+
+    /// The [`Anchor`]s of a LRM between two measures, both included, sorted by increasing measures.
+    /// It allows to go through the anchors of a long LRM page by page.
+    pub fn anchors_between(
+        &self,
+        lrm_index: usize,
+        from_measure: &LrmScaleMeasure,
+        to_measure: &LrmScaleMeasure,
+    ) -> PyResult<Vec<MeasuredAnchor>> {
+        self.lrs
+            .anchors_between(lrm_index, &from_measure.into(), &to_measure.into())
+            .map(|anchors| anchors.into_iter().map(MeasuredAnchor::from).collect())
+            .map_err(lrs_error)
+    }
+
+    /// Where the [`Traversal`]s of two [`Lrm`]s cross or touch, sorted along the first [`Lrm`].
+    pub fn intersections(
+        &self,
+        lrm_index_a: usize,
+        lrm_index_b: usize,
+    ) -> PyResult<Vec<LrmIntersection>> {
+        self.lrs
+            .intersections(lrm_index_a, lrm_index_b)
+            .map(|intersections| intersections.iter().map(LrmIntersection::from).collect())
+            .map_err(lrs_error)
+    }
+
+    /// The distance in meters along the network between measures of two [`Lrm`]s, following the connections between [`Traversal`]s.
+    /// Fails when they are not connected.
+    pub fn network_distance(
+        &self,
+        from_lrm_index: usize,
+        from_measure: &LrmScaleMeasure,
+        to_lrm_index: usize,
+        to_measure: &LrmScaleMeasure,
+    ) -> PyResult<f64> {
+        self.lrs
+            .network_distance(
+                from_lrm_index,
+                &from_measure.into(),
+                to_lrm_index,
+                &to_measure.into(),
+            )
+            .map_err(lrs_error)
+    }
+
+    /// Summary of the content of the [`Lrs`], to sanity-check a file.
+    pub fn stats(&self) -> LrsStats {
+        LrsStats::from(&self.lrs.stats())
+    }
+
+    /// The LRMs and their anchors as a GeoJSON FeatureCollection, e.g. to inspect them in a GIS.
+    pub fn to_geojson(&self) -> String {
+        self.lrs.to_geojson()
+    }
+
+    /// The LRS as GeoDataFrames, to analyze and plot it in a notebook. geopandas and shapely must be installed.
+    ///
+    /// Returns a dict with the GeoDataFrames `lrms` (LineStrings), `anchors` (Points) and,
+    /// when the LRS has events, `point_events` (Points) and `linear_events` (LineStrings).
+    /// The measures are columns with the anchor name and the offset, and the properties are columns too.
+    pub fn to_geodataframes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let lrs = &self.lrs;
+        let lrm_id = |lrm_index: usize| lrs.get_lrm_scale_id(lrm_index).ok();
+        let crs = lrs.crs();
+        let (mut lrm_rows, mut lrm_geometries) = (vec![], vec![]);
+        let (mut anchor_rows, mut anchor_geometries) = (vec![], vec![]);
+        for lrm_index in 0..lrs.lrm_len() {
+            let properties = lrs.lrm_properties(lrm_index).map_err(lrs_error)?;
+            let row = properties_row(
+                py,
+                properties
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), value.into_pyobject(py)?.into_any())))
+                    .collect::<PyResult<Vec<_>>>()?,
+            )?;
+            row.set_item("lrm_index", lrm_index)?;
+            row.set_item("id", lrm_id(lrm_index))?;
+            if let Ok(bounds) = lrs.lrm_bounds(lrm_index) {
+                row.set_item("start_anchor", &bounds.min_measure.anchor_name)?;
+                row.set_item("start_offset", bounds.min_measure.scale_offset)?;
+                row.set_item("end_anchor", &bounds.max_measure.anchor_name)?;
+                row.set_item("end_offset", bounds.max_measure.scale_offset)?;
+                row.set_item("length", bounds.length)?;
+            }
+            lrm_rows.push(row);
+            lrm_geometries.push(match lrs.get_lrm_geom(lrm_index) {
+                Ok(coords) => Some(shapely_geometry(py, "LineString", xy(coords))?),
+                Err(_) => None,
+            });
+
+            for marker in lrs.anchor_markers(lrm_index).unwrap_or_default() {
+                let row = PyDict::new(py);
+                row.set_item("lrm_index", lrm_index)?;
+                row.set_item("lrm_id", lrm_id(lrm_index))?;
+                row.set_item("name", marker.name)?;
+                row.set_item("anchor_name", &marker.measure.anchor_name)?;
+                row.set_item("scale_offset", marker.measure.scale_offset)?;
+                row.set_item("heading", marker.heading)?;
+                anchor_rows.push(row);
+                anchor_geometries.push(Some(shapely_geometry(
+                    py,
+                    "Point",
+                    (marker.point.x(), marker.point.y()),
+                )?));
+            }
+        }
+
+        let geodataframes = PyDict::new(py);
+        geodataframes.set_item(
+            "lrms",
+            geodataframe(py, lrm_rows, lrm_geometries, crs.clone())?,
+        )?;
+        geodataframes.set_item(
+            "anchors",
+            geodataframe(py, anchor_rows, anchor_geometries, crs.clone())?,
+        )?;
+
+        if !lrs.all_point_events().is_empty() {
+            let (mut rows, mut geometries) = (vec![], vec![]);
+            for event in &lrs.all_point_events() {
+                let row = properties_row(py, typed_properties(py, &event.properties)?)?;
+                row.set_item("id", &event.id)?;
+                row.set_item("lrm_index", event.lrm.0)?;
+                row.set_item("lrm_id", lrm_id(event.lrm.0))?;
+                row.set_item("anchor_name", &event.measure.anchor_name)?;
+                row.set_item("scale_offset", event.measure.scale_offset)?;
+                row.set_item("valid_from", &event.validity.from)?;
+                row.set_item("valid_to", &event.validity.to)?;
+                rows.push(row);
+                geometries.push(match lrs.resolve(event.lrm.0, &event.measure) {
+                    Ok(point) => Some(shapely_geometry(py, "Point", (point.x(), point.y()))?),
+                    Err(_) => None,
+                });
+            }
+            geodataframes.set_item(
+                "point_events",
+                geodataframe(py, rows, geometries, crs.clone())?,
+            )?;
+        }
+
+        if !lrs.all_linear_events().is_empty() {
+            let (mut rows, mut geometries) = (vec![], vec![]);
+            for event in &lrs.all_linear_events() {
+                let row = properties_row(py, typed_properties(py, &event.properties)?)?;
+                row.set_item("id", &event.id)?;
+                row.set_item("lrm_index", event.lrm.0)?;
+                row.set_item("lrm_id", lrm_id(event.lrm.0))?;
+                row.set_item("begin_anchor", &event.begin.anchor_name)?;
+                row.set_item("begin_offset", event.begin.scale_offset)?;
+                row.set_item("end_anchor", &event.end.anchor_name)?;
+                row.set_item("end_offset", event.end.scale_offset)?;
+                row.set_item("valid_from", &event.validity.from)?;
+                row.set_item("valid_to", &event.validity.to)?;
+                rows.push(row);
+                geometries.push(
+                    match lrs.resolve_range(event.lrm.0, &event.begin, &event.end, None) {
+                        Ok(coords) => Some(shapely_geometry(py, "LineString", xy(coords))?),
+                        Err(_) => None,
+                    },
+                );
+            }
+            geodataframes.set_item("linear_events", geodataframe(py, rows, geometries, crs)?)?;
+        }
+        Ok(geodataframes)
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`].
+    pub fn resolve(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> PyResult<Point> {
+        self.lrs
+            .resolve(lrm_index, &measure.into())
+            .map(Point::from)
+            .map_err(lrs_error)
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`], as a shapely Point. shapely must be installed.
+    pub fn resolve_shapely<'py>(
+        &self,
+        py: Python<'py>,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let point = self
+            .lrs
+            .resolve(lrm_index, &measure.into())
+            .map_err(lrs_error)?;
+        shapely_geometry(py, "Point", (point.x(), point.y()))
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`] and the direction of increasing measures there,
+    /// e.g. to rotate the icon of a train on a map.
+    pub fn resolve_oriented(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> PyResult<OrientedPoint> {
+        self.lrs
+            .resolve_oriented(lrm_index, &measure.into())
+            .map(OrientedPoint::from)
+            .map_err(lrs_error)
+    }
+
+    /// All the [`Anchor`]s of a LRM, in the same order as `get_anchors`, placed on the [`Lrm`].
+    /// An [`Anchor`] before the start or after the end of the [`Lrm`] is placed at that end.
+    pub fn anchor_markers(&self, lrm_index: usize) -> PyResult<Vec<AnchorMarker>> {
+        self.lrs
+            .anchor_markers(lrm_index)
+            .map(|markers| markers.into_iter().map(AnchorMarker::from).collect())
+            .map_err(lrs_error)
+    }
+
+    /// Graduations of the scale of the [`Lrm`] every `step` scale units, e.g. every 100 meters.
+    /// Each tick is a segment of `tick_length_m` meters across the [`Lrm`], centered on the graduation.
+    /// There is no graduation if `step` is not a positive finite number.
+    pub fn measure_ticks(
+        &self,
+        lrm_index: usize,
+        step: f64,
+        tick_length_m: f64,
+    ) -> PyResult<Vec<MeasureTick>> {
+        self.lrs
+            .measure_ticks(lrm_index, step, tick_length_m)
+            .map(|ticks| ticks.into_iter().map(MeasureTick::from).collect())
+            .map_err(lrs_error)
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`], moved perpendicularly to the [`Lrm`] by `lateral_offset_m` meters.
+    /// The offset is positive on the left when facing increasing measures, negative on the right.
+    pub fn resolve_with_offset(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+        lateral_offset_m: f64,
+    ) -> PyResult<Point> {
+        self.lrs
+            .resolve_with_offset(lrm_index, &measure.into(), lateral_offset_m)
+            .map(Point::from)
+            .map_err(lrs_error)
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`] on one of the [`Traversal`]s of the [`Lrm`], e.g. an alternate track.
+    pub fn resolve_on_traversal(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+        traversal_index: usize,
+    ) -> PyResult<Point> {
+        self.lrs
+            .resolve_on_traversal(lrm_index, &measure.into(), traversal_index)
+            .map(Point::from)
+            .map_err(lrs_error)
+    }
+
+    /// Indices of the [`Traversal`]s of an [`Lrm`]: the main one first, then the alternate ones.
+    pub fn lrm_traversals(&self, lrm_index: usize) -> PyResult<Vec<usize>> {
+        self.lrs.lrm_traversals(lrm_index).map_err(lrs_error)
+    }
+
+    /// Get the positions of many [`LrmScaleMeasure`]s of the same LRM, in parallel.
+    /// Fails if any of them cannot be resolved.
+    pub fn resolve_batch(
+        &self,
+        lrm_index: usize,
+        measures: Vec<LrmScaleMeasure>,
+    ) -> PyResult<Vec<Point>> {
+        let measures: Vec<_> = measures.iter().map(|measure| measure.into()).collect();
+        self.lrs
+            .resolve_batch(lrm_index, &measures)
+            .into_iter()
+            .map(|point| point.map(Point::from).map_err(lrs_error))
+            .collect()
+    }
+
+    /// Get the positon along the curve given a [`LrmScaleMeasure`]
+    /// The value will be between 0.0 and 1.0, both included
+    pub fn locate_point(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> PyResult<f64> {
+        self.lrs
+            .lrms()
+            .get(lrm_index)
+            .ok_or_else(|| lrs_error(LrsErrorCode::LrmNotFound { lrm_index }))?
+            .scale
+            .locate_point(&measure.into())
+            .map_err(lrs_error)
+    }
+
+    /// Get the [`LrmScaleMeasure`] given a position along the curve, the inverse of `locate_point`.
+    /// The measure is given from the nearest named [`Anchor`].
+    pub fn measure_at(&self, lrm_index: usize, curve_position: f64) -> PyResult<LrmScaleMeasure> {
+        self.lrs
+            .measure_at(lrm_index, curve_position)
+            .map(|measure| LrmScaleMeasure::from(&measure))
+            .map_err(lrs_error)
+    }
+
+    /// Given two [`LrmScaleMeasure`]s, return a range of [`Point`] that represent a line string.
+    /// With a `lateral_offset_m`, the range is moved perpendicularly to the [`Lrm`] by that many meters,
+    /// positive on the left when facing increasing measures, negative on the right.
+    #[pyo3(signature = (lrm_index, from_measure, to_measure, lateral_offset_m = None))]
+    pub fn resolve_range(
+        &self,
+        lrm_index: usize,
+        from_measure: &LrmScaleMeasure,
+        to_measure: &LrmScaleMeasure,
+        lateral_offset_m: Option<f64>,
+    ) -> PyResult<Vec<Point>> {
+        self.lrs
+            .resolve_range(
+                lrm_index,
+                &from_measure.into(),
+                &to_measure.into(),
+                lateral_offset_m,
+            )
+            .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
+            .map_err(lrs_error)
+    }
+
+    /// Same as `resolve_range`, as a shapely LineString. shapely must be installed.
+    #[pyo3(signature = (lrm_index, from_measure, to_measure, lateral_offset_m = None))]
+    pub fn resolve_range_shapely<'py>(
+        &self,
+        py: Python<'py>,
+        lrm_index: usize,
+        from_measure: &LrmScaleMeasure,
+        to_measure: &LrmScaleMeasure,
+        lateral_offset_m: Option<f64>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let coords = self
+            .lrs
+            .resolve_range(
+                lrm_index,
+                &from_measure.into(),
+                &to_measure.into(),
+                lateral_offset_m,
+            )
+            .map_err(lrs_error)?;
+        shapely_geometry(py, "LineString", xy(coords))
+    }
+
+    /// The ranges of measures of the [`Lrm`] inside a polygon, such as a work zone, a flooded area or an administrative boundary.
+    /// The polygon is given by the [`Point`]s of its boundary. The ranges are sorted by increasing measures.
+    pub fn measure_ranges_in_polygon(
+        &self,
+        lrm_index: usize,
+        polygon: Vec<Point>,
+    ) -> PyResult<Vec<MeasureRange>> {
+        let polygon = geo_types::Polygon::new(
+            polygon.into_iter().map(geo_types::Coord::from).collect(),
+            vec![],
+        );
+        self.lrs
+            .measure_ranges_in_polygon(lrm_index, &polygon)
+            .map(|ranges| ranges.into_iter().map(MeasureRange::from).collect())
+            .map_err(lrs_error)
+    }
+
+    /// Converts a [`LrmScaleMeasure`] of an LRM into a [`LrmScaleMeasure`] of an other LRM.
+    /// Both LRMs must share the same traversal.
+    pub fn convert_measure(
+        &self,
+        from_lrm_index: usize,
+        to_lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> PyResult<LrmScaleMeasure> {
+        self.lrs
+            .convert_measure(from_lrm_index, to_lrm_index, &measure.into())
+            .map(|measure| LrmScaleMeasure::from(&measure))
+            .map_err(lrs_error)
+    }
+
+    /// Given a ID returns the corresponding lrs index (or None if not found)
+    pub fn find_lrm(&self, lrm_id: &str) -> Option<usize> {
+        self.lrs.get_lrm(lrm_id)
+    }
+
+    /// The [`Lrm`]s whose id, or the id of their [`Traversal`], matches a `query` typed in a search box.
+    /// The id can start with the `query`, contain it, or be close to it with a few typos.
+    /// The best `limit` matches are returned, the best one first.
+    pub fn search_lrms(&self, query: &str, limit: usize) -> Vec<LrmMatch> {
+        self.lrs
+            .search_lrms(query, limit)
+            .into_iter()
+            .map(LrmMatch::from)
+            .collect()
+    }
+
+    /// Projects a [`Point`] on all applicable [`Traversal`]s to a given [`Lrm`].
+    /// The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
+    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+    /// The [`Point`] can also be a shapely Point, or any point with a `__geo_interface__`.
+    /// Raises an `LrsError` if the point can not be projected, with the `LRM_NOT_FOUND` code for an unknown `lrm_handle`.
+    fn lookup(&self, point: &Bound<'_, PyAny>, lrm_handle: usize) -> PyResult<Vec<LrmProjection>> {
+        self.lookup_point(extract_point(point)?, lrm_handle)
+    }
+
+    /// Projects a [`Point`] on all applicable [`Traversal`]s nearby.
+    /// The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
+    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+    fn lookup_lrms(&self, point: Point) -> Vec<LrmProjection> {
+        self.lrs
+            .lookup_lrms(point.into())
+            .iter()
+            .map(LrmProjection::from)
+            .collect()
+    }
+
+    /// Projects a [`Point`] on every [`Lrm`] at most `max_distance` away: meters for geographic coordinates, coordinate units for schematic ones.
+    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+    /// The [`Point`] can also be a shapely Point, or any point with a `__geo_interface__`.
+    fn lookup_all(
+        &self,
+        point: &Bound<'_, PyAny>,
+        max_distance: f64,
+    ) -> PyResult<Vec<LrmProjection>> {
+        Ok(self
+            .lrs
+            .lookup_all(extract_point(point)?, max_distance)
+            .iter()
+            .map(LrmProjection::from)
+            .collect())
+    }
+
+    /// Finds the nearest [`Lrm`] at most `max_distance` away, in the unit of `lookup_all` from a [`Point`], and the measure of the [`Point`] on it.
+    /// Returns `None` when no [`Lrm`] is close enough.
+    /// The [`Point`] can also be a shapely Point, or any point with a `__geo_interface__`.
+    fn locate(
+        &self,
+        point: &Bound<'_, PyAny>,
+        max_distance: f64,
+    ) -> PyResult<Option<LocatedPoint>> {
+        Ok(self
+            .lrs
+            .locate(extract_point(point)?, max_distance)
+            .map(LocatedPoint::from))
+    }
+
+    /// Projects many [`Point`]s on every [`Lrm`] at most `max_distance` away, in the unit of `lookup_all`, in parallel.
+    /// There is one list of projections for every [`Point`], in the same order.
+    fn lookup_batch(&self, points: Vec<Point>, max_distance: f64) -> Vec<Vec<LrmProjection>> {
+        let points: Vec<_> = points.into_iter().map(|point| point.into()).collect();
+        self.lrs
+            .lookup_batch(&points, max_distance)
+            .iter()
+            .map(|projections| projections.iter().map(LrmProjection::from).collect())
+            .collect()
+    }
+
+    /// Finds the nearest [`Lrm`] at most `max_distance` away, in the unit of `lookup_all` from every position given by two NumPy arrays,
+    /// in parallel and without holding the GIL.
+    /// Returns, for every position: the index of the [`Lrm`], the anchor name and the offset of the measure,
+    /// and the lateral offset in meters, positive on the left, see [`ProjectionArrays`].
+    #[allow(clippy::type_complexity)]
+    fn lookup_array<'py>(
+        &self,
+        py: Python<'py>,
+        lons: PyReadonlyArray1<'py, f64>,
+        lats: PyReadonlyArray1<'py, f64>,
+        max_distance: f64,
+    ) -> PyResult<ProjectionArrays> {
+        let (lons, lats) = (lons.as_array(), lats.as_array());
+        if lons.len() != lats.len() {
+            return Err(PyValueError::new_err(
+                "lons and lats must have the same length",
+            ));
+        }
+        let points: Vec<_> = lons
+            .iter()
+            .zip(lats.iter())
+            .map(|(&x, &y)| geo_types::Point::new(x, y))
+            .collect();
+        let projections = py.detach(|| self.lrs.lookup_batch(&points, max_distance));
+
+        let mut lrm_indices = Vec::with_capacity(points.len());
+        let mut anchor_names = Vec::with_capacity(points.len());
+        let mut scale_offsets = Vec::with_capacity(points.len());
+        let mut lateral_offsets = Vec::with_capacity(points.len());
+        for projection in projections.iter().map(|projections| projections.first()) {
+            match projection {
+                Some(projection) => {
+                    lrm_indices.push(projection.measure.lrm.0 as i64);
+                    anchor_names.push(Some(projection.measure.measure.anchor_name.clone()));
+                    scale_offsets.push(projection.measure.measure.scale_offset);
+                    lateral_offsets.push(projection.orthogonal_offset);
+                }
+                None => {
+                    lrm_indices.push(-1);
+                    anchor_names.push(None);
+                    scale_offsets.push(f64::NAN);
+                    lateral_offsets.push(f64::NAN);
+                }
+            }
+        }
+        Ok(ProjectionArrays {
+            lrm_indices: lrm_indices.into_pyarray(py).unbind(),
+            anchor_names,
+            scale_offsets: scale_offsets.into_pyarray(py).unbind(),
+            lateral_offsets: lateral_offsets.into_pyarray(py).unbind(),
+        })
+    }
+
+    /// Get the positions of many measures of the same LRM, given by their anchor names and a NumPy array of offsets,
+    /// in parallel and without holding the GIL.
+    /// Returns the longitudes and the latitudes as two NumPy arrays, see [`PointArrays`].
+    fn resolve_array<'py>(
+        &self,
+        py: Python<'py>,
+        lrm_index: usize,
+        anchor_names: Vec<String>,
+        scale_offsets: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<PointArrays> {
+        if lrm_index >= self.lrs.lrm_len() {
+            return Err(lrs_error(LrsErrorCode::LrmNotFound { lrm_index }));
+        }
+        let scale_offsets = scale_offsets.as_array();
+        if anchor_names.len() != scale_offsets.len() {
+            return Err(PyValueError::new_err(
+                "anchor_names and scale_offsets must have the same length",
+            ));
+        }
+        let measures: Vec<_> = anchor_names
+            .iter()
+            .zip(scale_offsets.iter())
+            .map(|(anchor_name, &scale_offset)| {
+                liblrs::lrm_scale::LrmScaleMeasure::new(anchor_name, scale_offset)
+            })
+            .collect();
+        let points = py.detach(|| self.lrs.resolve_batch(lrm_index, &measures));
+        let (lons, lats): (Vec<_>, Vec<_>) = points
+            .into_iter()
+            .map(|point| point.map_or((f64::NAN, f64::NAN), |point| (point.x(), point.y())))
+            .unzip();
+        Ok(PointArrays {
+            lons: lons.into_pyarray(py).unbind(),
+            lats: lats.into_pyarray(py).unbind(),
+        })
+    }
+
+    /// Map-matches an ordered sequence of positions, such as a GNSS log, onto the [`Lrm`]s.
+    /// A position can be matched on an [`Lrm`] at most `max_distance` away, in the unit of `lookup_all`.
+    /// The measures are monotonic along each [`Lrm`]: a position going backward by more than `backward_tolerance` is ignored.
+    #[pyo3(signature = (points, max_distance, backward_tolerance = 0.))]
+    fn project_trace(
+        &self,
+        points: Vec<Point>,
+        max_distance: f64,
+        backward_tolerance: f64,
+    ) -> Vec<TraceMatch> {
+        let points: Vec<_> = points.into_iter().map(|point| point.into()).collect();
+        let options = liblrs::trace::TraceOptions {
+            max_distance,
+            backward_tolerance,
+        };
+        self.lrs
+            .project_trace(&points, options)
+            .iter()
+            .map(TraceMatch::from)
+            .collect()
+    }
+
+    /// [`Properties`] of the lrs
+    pub fn lrs_properties(&self) -> Properties {
+        self.lrs.lrs_properties().clone()
+    }
+
+    /// [`Properties`] for a given lrm
+    pub fn lrm_properties(&self, lrm_index: usize) -> PyResult<Properties> {
+        self.lrs
+            .lrm_properties(lrm_index)
+            .cloned()
+            .map_err(lrs_error)
+    }
+
+    /// [`Properties`] of the traversal of a given lrm
+    pub fn traversal_properties(&self, lrm_index: usize) -> PyResult<Properties> {
+        self.lrs
+            .traversal_properties(lrm_index)
+            .cloned()
+            .map_err(lrs_error)
+    }
+
+    /// [`Properties`] for a given anchor
+    pub fn anchor_properties(&self, lrm_index: usize, anchor_index: usize) -> PyResult<Properties> {
+        self.lrs
+            .anchor_properties(lrm_index, anchor_index)
+            .cloned()
+            .map_err(lrs_error)
+    }
+
+    /// Return a single [`Node`]
+    pub fn get_node(&self, node_index: usize) -> PyResult<Node> {
+        self.lrs
+            .nodes()
+            .get(node_index)
+            .map(Node::from)
+            .ok_or_else(|| lrs_error(LrsErrorCode::InvalidHandle))
+    }
+
+    /// Return all the [`Node`] of the lrs
+    pub fn get_nodes(&self) -> Vec<Node> {
+        self.lrs.nodes().iter().map(|node| node.into()).collect()
+    }
+
+    /// Return a single [`Segment`]
+    pub fn get_segment(&self, segment_index: usize) -> PyResult<Segment> {
+        self.lrs
+            .segments()
+            .get(segment_index)
+            .map(Segment::from)
+            .ok_or_else(|| lrs_error(LrsErrorCode::InvalidHandle))
+    }
+
+    /// All the [`Segment`] of the lrs
+    pub fn get_segments(&self) -> Vec<Segment> {
+        self.lrs.segments().iter().map(|seg| seg.into()).collect()
+    }
+}
+
+impl Lrs {
+    fn lookup_point(
+        &self,
+        point: geo_types::Point,
+        lrm_handle: usize,
+    ) -> PyResult<Vec<LrmProjection>> {
+        let projection = self.lrs.lookup(point, lrm_handle).map_err(lrs_error)?;
+        Ok(vec![LrmProjection::from(&projection)])
+    }
+}
+
+/// Runs the golden dataset through the python facing functions.
+impl ConformanceAdapter for Lrs {
+    fn find_lrm(&self, lrm_id: &str) -> Option<usize> {
+        Lrs::find_lrm(self, lrm_id)
+    }
+
+    fn resolve(
+        &self,
+        lrm_index: usize,
+        measure: &liblrs::lrm_scale::LrmScaleMeasure,
+    ) -> Result<geo_types::Point, String> {
+        Lrs::resolve(self, lrm_index, &measure.into())
+            .map(|point| point.into())
+            .map_err(|e| e.to_string())
+    }
+
+    fn lookup(
+        &self,
+        point: geo_types::Point,
+        lrm_index: usize,
+    ) -> Result<(liblrs::lrm_scale::LrmScaleMeasure, f64), String> {
+        self.lookup_point(point, lrm_index)
+            .map_err(|e| e.to_string())?
+            .first()
+            .map(|projection| ((&projection.measure).into(), projection.orthogonal_offset))
+            .ok_or("could not project the point".to_owned())
+    }
+}
+
+#[gen_stub_pyclass]
+#[pyclass]
+struct Builder {
+    inner: liblrs::builder::Builder<'static>,
+    // The nodes added at the extremities of the traversals read from tables, by coordinates
+    nodes_by_coord: HashMap<(u64, u64), usize>,
+}
+
+/// The coordinates of a Point or a LineString given by its `__geo_interface__`, such as a shapely geometry
+fn geo_interface_coords(geometry: &Bound<'_, PyAny>) -> PyResult<Vec<geo_types::Coord>> {
+    let interface = geometry.getattr("__geo_interface__")?;
+    let geometry_type: String = interface.get_item("type")?.extract()?;
+    let coordinates = interface.get_item("coordinates")?;
+    let coord = |position: Vec<f64>| match position[..] {
+        [x, y, ..] => Ok(geo_types::coord! {x: x, y: y}),
+        _ => Err(PyValueError::new_err(
+            "a position needs at least two coordinates",
+        )),
+    };
+    match geometry_type.as_str() {
+        "Point" => Ok(vec![coord(coordinates.extract()?)?]),
+        "LineString" => coordinates
+            .extract::<Vec<Vec<f64>>>()?
+            .into_iter()
+            .map(coord)
+            .collect(),
+        other => Err(PyValueError::new_err(format!(
+            "expected a Point or a LineString, got a {other}"
+        ))),
+    }
+}
+
+/// The values of a column of a table, such as a pandas DataFrame or a dict of lists
+fn table_column<'py>(table: &Bound<'py, PyAny>, column: &str) -> PyResult<Vec<Bound<'py, PyAny>>> {
+    table.get_item(column)?.try_iter()?.collect()
+}
+
+#[gen_stub_pymethods]
+#[pymethods]
+impl Builder {
+    #[new]
+    /// Instantiate a new builder
+    fn new() -> Self {
+        Self {
+            inner: liblrs::builder::Builder::new(),
+            nodes_by_coord: HashMap::new(),
+        }
+    }
+
+    /// Add a new topological node (e.g. a railway switch)
+    pub fn add_node(&mut self, id: &str, coord: Point, properties: Properties) -> usize {
+        self.inner.add_node(id, coord.into(), properties)
+    }
+
+    /// Add a new anchor by its coordinates
+    #[pyo3(signature = (id, coord, properties, name=None))]
+    pub fn add_anchor(
+        &mut self,
+        id: &str,
+        coord: Point,
+        properties: Properties,
+        name: Option<&str>,
+    ) -> usize {
+        self.inner.add_anchor(id, name, coord.into(), properties)
+    }
+
+    /// Add a new anchor by its position along the curve
     #[pyo3(signature = (id, position_on_curve, properties, name=None))]
     pub fn add_projected_anchor(
         &mut self,
@@ -553,6 +2130,143 @@ impl Builder {
         self.inner.add_traversal(traversal_id, &segments)
     }
 
+    /// Add a traversal made of a single segment with the same id
+    ///
+    /// The geometry is a LineString with a `__geo_interface__`, such as a shapely LineString
+    pub fn add_traversal_from_geometry(
+        &mut self,
+        traversal_id: &str,
+        geometry: &Bound<'_, PyAny>,
+        start_node_index: usize,
+        end_node_index: usize,
+    ) -> PyResult<usize> {
+        let geometry: geo_types::LineString = geo_interface_coords(geometry)?.into();
+        Ok(self.inner.add_traversal_from_geometry(
+            traversal_id,
+            &geometry,
+            start_node_index,
+            end_node_index,
+        ))
+    }
+
+    /// Add a traversal for each row of a table, such as a (geo)pandas DataFrame or a dict of lists
+    ///
+    /// Each traversal has a single segment, whose geometry is a shapely LineString with at least two distinct coordinates.
+    /// The traversals ending at the same coordinates share the same node.
+    /// Return the index of each traversal by its id
+    #[pyo3(signature = (table, id_column="id", geometry_column="geometry"))]
+    pub fn add_traversals_from_table(
+        &mut self,
+        table: &Bound<'_, PyAny>,
+        id_column: &str,
+        geometry_column: &str,
+    ) -> PyResult<HashMap<String, usize>> {
+        let ids = table_column(table, id_column)?;
+        let geometries = table_column(table, geometry_column)?;
+        let mut traversals = HashMap::new();
+        for (id, geometry) in ids.iter().zip(&geometries) {
+            let id = id.str()?.to_string();
+            let coords = geo_interface_coords(geometry)?;
+            let (Some(&first), Some(&last)) = (coords.first(), coords.last()) else {
+                return Err(PyValueError::new_err(format!(
+                    "traversal {id} has an empty geometry"
+                )));
+            };
+            // The anchors could not be projected on a single point
+            if coords.iter().all(|&coord| coord == first) {
+                return Err(PyValueError::new_err(format!(
+                    "traversal {id} needs at least two distinct coordinates"
+                )));
+            }
+            let [start, end] = [first, last].map(|coord| {
+                let next_index = self.inner.get_node_count();
+                *self
+                    .nodes_by_coord
+                    .entry((coord.x.to_bits(), coord.y.to_bits()))
+                    .or_insert_with(|| {
+                        self.inner
+                            .add_node(&next_index.to_string(), coord, Properties::new())
+                    })
+            });
+            let index = self
+                .inner
+                .add_traversal_from_geometry(&id, &coords.into(), start, end);
+            traversals.insert(id, index);
+        }
+        Ok(traversals)
+    }
+
+    /// Add the anchors of a table, such as a (geo)pandas DataFrame or a dict of lists, and their lrms
+    ///
+    /// Each row is an anchor of the lrm of its `lrm_id`, at the distance `measure` from the start of the lrm.
+    /// Its geometry is a shapely Point, that is projected on the traversal with the same id as the lrm.
+    /// One lrm is added for each `lrm_id`, so the traversals must already be added.
+    #[pyo3(signature = (table, lrm_id_column="lrm_id", name_column="name", measure_column="measure", geometry_column="geometry"))]
+    pub fn add_lrms_from_table(
+        &mut self,
+        table: &Bound<'_, PyAny>,
+        lrm_id_column: &str,
+        name_column: &str,
+        measure_column: &str,
+        geometry_column: &str,
+    ) -> PyResult<()> {
+        let lrm_ids = table_column(table, lrm_id_column)?;
+        let names = table_column(table, name_column)?;
+        let measures = table_column(table, measure_column)?;
+        let geometries = table_column(table, geometry_column)?;
+        let traversals = self.inner.get_traversal_indexes();
+
+        let mut lrms: Vec<(String, usize, Vec<liblrs::builder::AnchorOnLrm>)> = vec![];
+        for (((lrm_id, name), measure), geometry) in
+            lrm_ids.iter().zip(&names).zip(&measures).zip(&geometries)
+        {
+            let lrm_id = lrm_id.str()?.to_string();
+            let name = name.str()?.to_string();
+            let coord = *geo_interface_coords(geometry)?.first().ok_or_else(|| {
+                PyValueError::new_err(format!("anchor {name} has an empty geometry"))
+            })?;
+            let position = match lrms.iter().position(|(id, _, _)| *id == lrm_id) {
+                Some(position) => position,
+                None => {
+                    let traversal = *traversals.get(&lrm_id).ok_or_else(|| {
+                        PyValueError::new_err(format!("unknown traversal {lrm_id}"))
+                    })?;
+                    lrms.push((lrm_id, traversal, vec![]));
+                    lrms.len() - 1
+                }
+            };
+            let anchor_index = self
+                .inner
+                .add_anchor(&name, Some(&name), coord, Properties::new());
+            lrms[position].2.push(liblrs::builder::AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm: measure.extract()?,
+            });
+        }
+
+        for (id, traversal, anchors) in lrms {
+            self.inner
+                .add_lrm(&id, traversal, &anchors, Properties::new());
+        }
+        Ok(())
+    }
+
+    /// Compresses the built file. It is decompressed transparently when loaded
+    pub fn set_compression(&mut self, compressed: bool) {
+        self.inner.set_compression(compressed)
+    }
+
+    /// Records the coordinate reference system of the coordinates, as an authority code such as `EPSG:4326`
+    pub fn set_crs(&mut self, crs: &str) {
+        self.inner.set_crs(crs)
+    }
+
+    /// Set the metadata of a traversal, such as the name of the line or its operator
+    pub fn set_traversal_properties(&mut self, traversal_index: usize, properties: Properties) {
+        self.inner
+            .set_traversal_properties(traversal_index, properties)
+    }
+
     /// Add a linear referencing model
     ///
     /// It is composed by the traversal identified by traversal_index (that represents the curve)
@@ -569,6 +2283,26 @@ impl Builder {
             .add_lrm(id, traversal_index, &anchors, properties)
     }
 
+    /// Add a linear referencing model whose scale also applies to alternate traversals,
+    /// e.g. the other tracks of a multi-track line
+    pub fn add_lrm_with_alternates(
+        &mut self,
+        id: &str,
+        traversal_index: usize,
+        alternate_traversal_indices: Vec<usize>,
+        anchors: Vec<AnchorOnLrm>,
+        properties: Properties,
+    ) {
+        let anchors: Vec<_> = anchors.into_iter().map(|anchor| anchor.into()).collect();
+        self.inner.add_lrm_with_alternates(
+            id,
+            traversal_index,
+            &alternate_traversal_indices,
+            &anchors,
+            properties,
+        )
+    }
+
     /// List all the traversals by their id and index
     pub fn get_traversal_indexes(&mut self) -> std::collections::HashMap<String, usize> {
         self.inner.get_traversal_indexes()
@@ -601,12 +2335,9 @@ impl Builder {
     }
 
     /// Builds the lrs to be used directly
-    pub fn build_lrs(&mut self, properties: Properties) -> PyResult<Lrs> {
-        let lrs = self
-            .inner
-            .build_lrs(properties)
-            .map_err(|e| PyTypeError::new_err(e.to_string()))?;
-        Ok(Lrs { lrs })
+    pub fn build_lrs(&mut self, py: Python<'_>, properties: Properties) -> PyResult<Lrs> {
+        let data = PyBytes::new(py, self.inner.build_data(properties));
+        Lrs::load(data)
     }
 
     /// Compute the euclidean distance between two lrms
@@ -615,8 +2346,11 @@ impl Builder {
     }
 
     /// List all the node indices of a traversal
-    pub fn get_nodes_of_traversal(&self, lrm_index: usize) -> Vec<usize> {
-        self.inner.get_nodes_of_traversal(lrm_index).to_vec()
+    pub fn get_nodes_of_traversal(&self, lrm_index: usize) -> PyResult<Vec<usize>> {
+        self.inner
+            .get_nodes_of_traversal(lrm_index)
+            .map(<[usize]>::to_vec)
+            .ok_or_else(|| lrs_error(LrsErrorCode::InvalidHandle))
     }
 
     /// Get the coordinates of a node identified by its index
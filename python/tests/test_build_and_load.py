@@ -1,5 +1,7 @@
+import numpy
+import pytest
 from pathlib import Path
-from liblrs_python import Builder, Point, Lrs, SegmentOfTraversal, AnchorOnLrm
+from liblrs_python import Builder, Point, Lrs, LrsError, SegmentOfTraversal, AnchorOnLrm, LrmScaleMeasure
 
 def build_lrm() -> Builder:
     """Helper function that builds an example LRS with one lrm"""
@@ -47,3 +49,184 @@ def test_build_to_file(tmp_path: Path):
     with open(path, "rb") as lrs_file:
         lrs = Lrs(lrs_file.read())
         assert lrs.lrm_len() == 1
+
+
+class Geometry:
+    """Stands for a shapely geometry: only its `__geo_interface__` is read"""
+
+    def __init__(self, geometry_type, coordinates):
+        self.__geo_interface__ = {"type": geometry_type, "coordinates": coordinates}
+
+
+def test_build_from_tables():
+    builder = Builder()
+    # A dict of lists, as a pandas DataFrame, is read column by column
+    traversals = {
+        "id": ["a", "b"],
+        "geometry": [
+            Geometry("LineString", [(0, 0), (0, 0.01)]),
+            Geometry("LineString", [(0, 0.01), (0.01, 0.01)]),
+        ],
+    }
+    indexes = builder.add_traversals_from_table(traversals)
+    assert indexes == {"a": 0, "b": 1}
+    # Both traversals share the node at their common extremity
+    assert builder.get_nodes_of_traversal(0)[-1] == builder.get_nodes_of_traversal(1)[0]
+
+    anchors = {
+        "lrm_id": ["a", "a"],
+        "name": ["0", "1"],
+        "measure": [0.0, 1000.0],
+        "geometry": [Geometry("Point", (0, 0)), Geometry("Point", (0, 0.01))],
+    }
+    builder.add_lrms_from_table(anchors)
+    lrs = builder.build_lrs({})
+    assert lrs.lrm_len() == 1
+    assert lrs.get_lrm_scale_id(0) == "a"
+
+
+def test_build_from_invalid_tables():
+    builder = Builder()
+    with pytest.raises(ValueError):
+        builder.add_traversals_from_table(
+            {"id": ["a"], "geometry": [Geometry("LineString", [(0, 0), (0, 0)])]}
+        )
+    builder.add_traversals_from_table(
+        {"id": ["a"], "geometry": [Geometry("LineString", [(0, 0), (0, 0.01)])]}
+    )
+    with pytest.raises(ValueError):
+        builder.add_lrms_from_table(
+            {
+                "lrm_id": ["a"],
+                "name": ["0"],
+                "measure": [0.0],
+                "geometry": [Geometry("LineString", [])],
+            }
+        )
+
+
+def test_numpy_arrays():
+    builder = Builder()
+    builder.add_traversals_from_table(
+        {"id": ["a"], "geometry": [Geometry("LineString", [(0, 0), (0, 0.01)])]}
+    )
+    builder.add_lrms_from_table(
+        {
+            "lrm_id": ["a", "a"],
+            "name": ["0", "1"],
+            "measure": [0.0, 1000.0],
+            "geometry": [Geometry("Point", (0, 0)), Geometry("Point", (0, 0.01))],
+        }
+    )
+    lrs = builder.build_lrs({})
+
+    lons, lats = lrs.resolve_array(0, ["0", "0"], numpy.array([500.0, 5000.0]))
+    assert lons[0] == pytest.approx(0.0)
+    assert lats[0] == pytest.approx(0.005)
+    assert numpy.isnan(lats[1])
+
+    lrms, anchors, offsets, lateral = lrs.lookup_array(
+        numpy.array([0.0, 1.0]), numpy.array([0.005, 0.005]), 100.0
+    )
+    assert list(lrms) == [0, -1]
+    assert anchors == ["0", None]
+    assert offsets[0] == pytest.approx(500.0)
+    assert numpy.isnan(lateral[1])
+
+    projections = lrs.lookup_array(numpy.array([0.0]), numpy.array([0.005]), 100.0)
+    assert len(projections) == 1
+    assert projections.anchor_names == ["0"]
+    assert projections.scale_offsets[0] == pytest.approx(500.0)
+
+
+def test_shapely():
+    lrs = build_lrm().build_lrs({})
+    assert Point(1, 2).__geo_interface__ == {"type": "Point", "coordinates": (1.0, 2.0)}
+
+    # Any point with a __geo_interface__ can be looked up
+    projection = lrs.lookup(Geometry("Point", (0.0, 0.5)), 0)[0]
+    assert projection.measure.scale_offset == pytest.approx(lrs.lookup(Point(0, 0.5), 0)[0].measure.scale_offset)
+
+    shapely = pytest.importorskip("shapely")
+    line = lrs.get_lrm_geom_shapely(0)
+    assert isinstance(line, shapely.LineString)
+    assert list(line.coords) == [(p.x, p.y) for p in lrs.get_lrm_geom(0)]
+    assert isinstance(lrs.resolve_shapely(0, LrmScaleMeasure("GH", 0)), shapely.Point)
+    assert isinstance(lrs.resolve_range_shapely(0, LrmScaleMeasure("GH", 0), LrmScaleMeasure("GH", 100)), shapely.LineString)
+
+
+def test_lookup_invalid_lrm():
+    lrs = build_lrm().build_lrs({})
+    with pytest.raises(LrsError) as error:
+        lrs.lookup(Point(0, 0.5), 42)
+    assert error.value.code == "LRM_NOT_FOUND"
+    assert error.value.lrm_index == 42
+
+
+def test_to_geodataframes():
+    pytest.importorskip("geopandas")
+    frames = build_lrm().build_lrs({}).to_geodataframes()
+    assert set(frames) == {"lrms", "anchors"}
+
+    lrms = frames["lrms"]
+    assert list(lrms.id) == ["My first Lrm"]
+    assert lrms.geometry[0].geom_type == "LineString"
+
+    anchors = frames["anchors"]
+    assert list(anchors.name) == ["GH", "RH"]
+    assert list(anchors.scale_offset) == [0.0, 0.0]
+    assert anchors.geometry[0].geom_type == "Point"
+
+
+def test_pickle():
+    import copy
+    import pickle
+
+    lrs = build_lrm().build_lrs({})
+    for copied in [pickle.loads(pickle.dumps(lrs)), copy.deepcopy(lrs)]:
+        assert copied.lrm_len() == 1
+        assert copied.get_lrm_scale_id(0) == "My first Lrm"
+    assert Lrs(lrs.to_bytes()).lrm_len() == 1
+
+    data = build_lrm().build_lrs({}).to_bytes()
+    assert Lrs(data).__reduce__()[1] == (data,)
+
+
+def test_iterate_and_index():
+    lrs = build_lrm().build_lrs({})
+    assert len(lrs) == 1
+    assert [lrm.id for lrm in lrs] == ["My first Lrm"]
+
+    lrm = lrs["My first Lrm"]
+    assert lrm.index == 0
+    assert lrs[-1].id == lrm.id
+    assert repr(lrm) == 'Lrm(index=0, id="My first Lrm", anchors=2)'
+    with pytest.raises(KeyError):
+        lrs["unknown"]
+    with pytest.raises(IndexError):
+        lrs[1]
+
+    assert len(lrm) == 2
+    assert [anchor.name for anchor in lrm] == ["GH", "RH"]
+    assert lrm["RH"].name == lrm[1].name == "RH"
+    assert repr(lrm[0]).startswith('Anchor(name="GH"')
+
+
+def test_open(tmp_path: Path):
+    path = tmp_path / "lrs.lrs"
+    build_lrm().save(path, {})
+    with Lrs.open(path) as lrs:
+        assert lrs.lrm_len() == 1
+    assert lrs.lrm_len() == 0
+
+
+def test_typed_results():
+    lrs = build_lrm().build_lrs({})
+    assert repr(Point(1, 2)) == "Point(x=1.0, y=2.0)"
+    measure = LrmScaleMeasure("GH", 100)
+    assert repr(measure) == 'LrmScaleMeasure(anchor_name="GH", scale_offset=100.0)'
+
+    projection = lrs.lookup(lrs.resolve(0, measure), 0)[0]
+    assert projection.measure.anchor_name == "GH"
+    assert repr(projection).startswith('LrmProjection(lrm_handle=0, measure=LrmScaleMeasure(anchor_name="GH"')
+    assert repr(lrs.lrm_bounds(0)).startswith('LrmBounds(min_measure=LrmScaleMeasure(anchor_name="GH"')
@@ -0,0 +1,9 @@
+from pathlib import Path
+from liblrs_python import Lrs
+
+GOLDEN_DATASET = Path(__file__).parents[2] / "tests" / "conformance" / "golden.txt"
+
+def test_golden_dataset():
+    dataset = GOLDEN_DATASET.read_text()
+    lrs = Lrs.from_golden_dataset(dataset)
+    assert lrs.check_conformance(dataset, 1e-6) == []
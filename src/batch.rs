@@ -0,0 +1,67 @@
+//! Operations on many items in a single call, such as projecting all the assets of a network.
+//!
+//! With the `rayon` feature, the items are processed in parallel.
+
+use geo::Point;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::curves::Curve;
+use crate::lrm_scale::LrmScaleMeasure;
+use crate::lrs::{LrmHandle, LrmProjection, Lrs, LrsBase, LrsError};
+
+/// Applies `f` to every item, in parallel if the `rayon` feature is enabled.
+/// The results are in the same order as the `items`.
+fn map_items<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    return items.par_iter().map(f).collect();
+    #[cfg(not(feature = "rayon"))]
+    return items.iter().map(f).collect();
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Projects every [`Point`] on every [`crate::lrs::Lrm`] that is at most `max_distance` away,
+    /// see [`LrsBase::lookup_all`].
+    /// There is one item in the result for every [`Point`], in the same order.
+    pub fn lookup_batch(&self, points: &[Point], max_distance: f64) -> Vec<Vec<LrmProjection>> {
+        map_items(points, |&point| self.lookup_all(point, max_distance))
+    }
+
+    /// Finds the geographical position of every measure on the same [`crate::lrs::Lrm`], see [`Lrs::resolve`].
+    /// There is one item in the result for every measure, in the same order.
+    pub fn resolve_batch(
+        &self,
+        lrm: LrmHandle,
+        measures: &[LrmScaleMeasure],
+    ) -> Vec<Result<Point, LrsError>> {
+        map_items(measures, |measure| self.resolve(lrm, measure))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::point;
+
+    use crate::lrs::tests::lrs;
+
+    use super::*;
+
+    #[test]
+    fn batch() {
+        let lrs = lrs();
+        let points = [point! {x: 50., y: 0.5}, point! {x: 50., y: 10.}];
+        let projections = lrs.lookup_batch(&points, 1.);
+        assert_eq!(projections.len(), 2);
+        assert_eq!(projections[0].len(), 1);
+        assert_eq!(projections[0][0].measure.measure.scale_offset, 5.);
+        assert!(projections[1].is_empty());
+
+        let measures = [
+            LrmScaleMeasure::new("a", 5.),
+            LrmScaleMeasure::new("unknown", 5.),
+        ];
+        let positions = lrs.resolve_batch(LrmHandle(0), &measures);
+        assert_eq!(positions[0], Ok(point! {x: 50., y: 0.}));
+        assert!(positions[1].is_err());
+    }
+}
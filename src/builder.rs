@@ -1,19 +1,271 @@
 //! Tools to make it easier to build an LRS
 //! It also avoids the need to manipulate flatbuffer data
+//!
+//! With geometries and kilometer posts already in memory, an LRS file is built with a [`Builder`]:
+//!
+//! ```
+//! use geo::{coord, line_string};
+//! use liblrs::builder::{AnchorOnLrm, Builder};
+//! use liblrs::properties;
+//!
+//! let mut builder = Builder::new();
+//! let start = builder.add_node("a", coord! {x: 2.35, y: 48.85}, properties!());
+//! let end = builder.add_node("b", coord! {x: 2.37, y: 48.85}, properties!());
+//! let geometry = line_string![(x: 2.35, y: 48.85), (x: 2.37, y: 48.85)];
+//! let traversal = builder.add_traversal_from_geometry("line", &geometry, start, end);
+//! let anchors = [("0", 2.35, 0.), ("1", 2.37, 1000.)].map(|(name, x, distance_along_lrm)| {
+//!     AnchorOnLrm {
+//!         anchor_index: builder.add_anchor(name, Some(name), coord! {x: x, y: 48.85}, properties!()),
+//!         distance_along_lrm,
+//!     }
+//! });
+//! builder.add_lrm("line", traversal, &anchors, properties!());
+//! let bytes = builder.build_bytes(properties!("source" => "example")).unwrap();
+//! # assert!(liblrs::lrs_ext::ExtLrs::load(&bytes).is_ok());
+//! ```
 
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+#[cfg(feature = "osm")]
+use std::path::PathBuf;
 
 use flatbuffers::{ForwardsUOffset, Vector, WIPOffset};
-use geo::{Coord, Distance};
+use geo::{Coord, Distance, LineString};
+use thiserror::Error;
 
 use crate::curves::{Curve, CurveError, CurveProjection, SphericalLineStringCurve};
 
-use crate::lrs::Properties;
-use crate::lrs_ext::ExtLrs;
+#[cfg(feature = "osm")]
+use crate::DataIssueReporter;
+use crate::events::{TypedProperties, measure_to_fb, typed_properties_to_fb};
+use crate::file_format;
+use crate::lrm_scale::{self, LrmScaleMeasure};
+pub use crate::lrs::SegmentOfTraversal;
+use crate::lrs::{Properties, Validity, check_date, quantize, to_fb};
+use crate::lrs_ext::{ExtLrs, ExtLrsError};
 use crate::lrs_generated::{self, *};
+#[cfg(feature = "osm-routes")]
+use crate::osm_helpers::milestone_position;
+#[cfg(feature = "osm")]
 use crate::osm_helpers::sort_edges;
-use crate::{DataIssueReporter, properties};
+use crate::properties;
+use crate::spatial_index::spatial_indexes;
+use crate::validation::{ValidationOptions, ValidationReport};
+use crate::with_any_lrs;
+
+/// Why the [`Builder`] refused to build an LRS.
+#[derive(Debug, Error, PartialEq)]
+pub enum BuilderError {
+    /// A [`Segment`] starts or ends at a [`Node`] that was not added.
+    #[error("segment {segment_id} references the unknown node {node_index}")]
+    UnknownNode {
+        /// `id` of the [`Segment`].
+        segment_id: String,
+        /// Index of the missing [`Node`].
+        node_index: usize,
+    },
+    /// A [`Traversal`] references a [`Segment`] that was not added.
+    #[error("traversal {traversal_id} references the unknown segment {segment_index}")]
+    UnknownSegment {
+        /// `id` of the [`Traversal`].
+        traversal_id: String,
+        /// Index of the missing [`Segment`].
+        segment_index: usize,
+    },
+    /// The geometry of a [`Traversal`] has less than two distinct coordinates.
+    #[error("traversal {0} has an invalid geometry")]
+    InvalidGeometry(String),
+    /// An LRM references a [`Traversal`] that was not added.
+    #[error("lrm {lrm_id} references the unknown traversal {traversal_index}")]
+    UnknownLrmTraversal {
+        /// `id` of the LRM.
+        lrm_id: String,
+        /// Index of the missing [`Traversal`].
+        traversal_index: usize,
+    },
+    /// An LRM references an [`Anchor`] that was not added.
+    #[error("lrm {lrm_id} references the unknown anchor {anchor_index}")]
+    UnknownAnchor {
+        /// `id` of the LRM.
+        lrm_id: String,
+        /// Index of the missing [`Anchor`].
+        anchor_index: usize,
+    },
+    /// An LRM has no named [`Anchor`]: none of its measures could be expressed.
+    #[error("lrm {0} has no named anchor")]
+    NoNamedAnchor(String),
+    /// A GeoJSON or GDAL feature lacks a property required by the mapping.
+    #[cfg(any(feature = "geojson", feature = "gdal"))]
+    #[error("feature {feature_index} has no property {property}")]
+    MissingProperty {
+        /// Index of the feature.
+        feature_index: usize,
+        /// Name of the missing property.
+        property: String,
+    },
+    /// A GeoJSON or GDAL anchor references an LRM without LineString feature.
+    #[cfg(any(feature = "geojson", feature = "gdal"))]
+    #[error("feature {feature_index} references the unknown lrm {lrm_id}")]
+    UnknownLrm {
+        /// Index of the feature.
+        feature_index: usize,
+        /// `id` of the missing LRM.
+        lrm_id: String,
+    },
+    /// A CSV file could not be read.
+    #[cfg(feature = "csv")]
+    #[error("invalid csv: {0}")]
+    Csv(String),
+    /// A column required by the [`crate::csv::CsvMapping`] is missing.
+    #[cfg(feature = "csv")]
+    #[error("missing column {0}")]
+    MissingColumn(String),
+    /// A CSV value is empty or is not a number.
+    #[cfg(feature = "csv")]
+    #[error("invalid value in column {column} at line {line}")]
+    InvalidValue {
+        /// Line of the record.
+        line: u64,
+        /// Name of the column.
+        column: String,
+    },
+    /// A CSV record references an LRM whose [`Traversal`] was not added.
+    #[cfg(feature = "csv")]
+    #[error("line {line} references the unknown traversal {lrm_id}")]
+    UnknownTraversal {
+        /// Line of the record.
+        line: u64,
+        /// `id` of the LRM and of its missing [`Traversal`].
+        lrm_id: String,
+    },
+    /// A shapefile could not be read.
+    #[cfg(feature = "shapefile")]
+    #[error("invalid shapefile: {0}")]
+    Shapefile(String),
+    /// A railML file could not be read.
+    #[cfg(feature = "railml")]
+    #[error("invalid railML: {0}")]
+    RailMl(String),
+    /// A NeTEx file could not be read.
+    #[cfg(feature = "netex")]
+    #[error("invalid NeTEx: {0}")]
+    NeTEx(String),
+    /// GDAL could not read a layer or a feature.
+    #[cfg(feature = "gdal")]
+    #[error("gdal error: {0}")]
+    Gdal(String),
+    /// A RINF file could not be read.
+    #[cfg(feature = "rinf")]
+    #[error("invalid RINF: {0}")]
+    Rinf(String),
+    /// An OpenStreetMap file could not be opened or read.
+    #[cfg(feature = "osm-routes")]
+    #[error("invalid OpenStreetMap file: {0}")]
+    Osm(String),
+    /// A [`Connection`] is at a [`Node`] that is not an extremity of one of its [`Traversal`]s,
+    /// e.g. after [`Builder::clean_topology`] split a [`Segment`] there.
+    #[error("connection at node {node_index} is not at an extremity of traversal {traversal_id}")]
+    ConnectionNotAtExtremity {
+        /// Index of the [`Node`] of the [`Connection`].
+        node_index: usize,
+        /// `id` of the [`Traversal`].
+        traversal_id: String,
+    },
+    /// A date of a [`Validity`] is not an ISO 8601 date (`YYYY-MM-DD`).
+    #[error("invalid date {0}, expected YYYY-MM-DD")]
+    InvalidDate(String),
+    /// An [`Anchor`] could not be projected on the [`Curve`] of its [`Traversal`].
+    #[error(transparent)]
+    Curve(#[from] CurveError),
+    /// The built data could not be read back.
+    #[error(transparent)]
+    Load(#[from] ExtLrsError),
+}
+
+/// Names of the GeoJSON properties read by [`Builder::from_geojson`].
+#[cfg(feature = "geojson")]
+#[derive(Clone, Debug)]
+pub struct GeoJsonMapping {
+    /// On a LineString feature, identifier of its [`Traversal`] and of its LRM.
+    /// The `id` of the feature is used when the property is missing.
+    pub traversal_id: String,
+    /// On a Point feature, identifier of the LRM of the [`Anchor`].
+    pub lrm_id: String,
+    /// On a Point feature, name of the [`Anchor`]. Without it, the [`Anchor`] is unnamed.
+    pub anchor_name: String,
+    /// On a Point feature, distance of the [`Anchor`] from the start of the LRM.
+    pub measure: String,
+}
+
+#[cfg(feature = "geojson")]
+impl Default for GeoJsonMapping {
+    fn default() -> Self {
+        Self {
+            traversal_id: "id".to_owned(),
+            lrm_id: "lrm_id".to_owned(),
+            anchor_name: "name".to_owned(),
+            measure: "measure".to_owned(),
+        }
+    }
+}
+
+// The other properties of a GeoJSON feature, as strings
+#[cfg(feature = "geojson")]
+fn geojson_properties(feature: &geojson::Feature, mapped: &[&str]) -> Properties {
+    feature
+        .properties
+        .iter()
+        .flatten()
+        .filter(|(key, _)| !mapped.contains(&key.as_str()))
+        .map(|(key, value)| match value {
+            geojson::JsonValue::String(value) => (key.clone(), value.clone()),
+            value => (key.clone(), value.to_string()),
+        })
+        .collect()
+}
+
+// A string or numeric property of a GeoJSON feature, as a string
+#[cfg(feature = "geojson")]
+fn geojson_string(feature: &geojson::Feature, property: &str) -> Option<String> {
+    match feature.property(property)? {
+        geojson::JsonValue::String(value) => Some(value.clone()),
+        geojson::JsonValue::Number(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Approximate length of a degree of latitude, in meters.
+const METERS_PER_DEGREE: f64 = 111_320.;
+
+/// The point of the `geometry` closest to `coord`: the index of its line, its ratio along the line
+/// and its distance to `coord` in meters.
+fn closest_on_geometry(geometry: &[Coord], coord: Coord) -> Option<(usize, f64, f64)> {
+    // The longitudes are scaled to compare the distances around `coord`
+    let scale = coord.y.to_radians().cos();
+    geometry
+        .windows(2)
+        .enumerate()
+        .map(|(line, window)| {
+            let (ax, ay) = ((window[0].x - coord.x) * scale, window[0].y - coord.y);
+            let (dx, dy) = (
+                (window[1].x - window[0].x) * scale,
+                window[1].y - window[0].y,
+            );
+            let squared_length = dx * dx + dy * dy;
+            let ratio = if squared_length > 0. {
+                (-(ax * dx + ay * dy) / squared_length).clamp(0., 1.)
+            } else {
+                0.
+            };
+            let closest = geo::Point::new(
+                window[0].x + ratio * (window[1].x - window[0].x),
+                window[0].y + ratio * (window[1].y - window[0].y),
+            );
+            let distance = geo::Haversine.distance(closest, geo::Point::from(coord));
+            (line, ratio, distance)
+        })
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+}
 
 /// The linear position of an [`Anchor`] doesn’t always match the measured distance.
 /// For example if a road was transformed into a bypass, resulting in a longer road,
@@ -28,25 +280,27 @@ pub struct AnchorOnLrm {
     pub distance_along_lrm: f64,
 }
 
-#[derive(Copy, Clone)]
-/// A [`Traversal`] is composed by many [`Segment`]s.
-pub struct SegmentOfTraversal {
-    /// Index of the considered [`Segment`]. Use the value returned by [`Builder::add_segment`]
-    pub segment_index: usize,
-    /// When integrating the [`Segment`] in the [`Traversal`], if we should consider the coordinates in reverse order.
-    pub reversed: bool,
+/// An [`Anchor`] of a kilometer-post survey: only its name and where it was measured.
+/// Its distance along the LRM is derived by [`Builder::add_surveyed_lrm`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SurveyedAnchor {
+    /// Nominal name of the anchor, such as the kilometer written on the post.
+    pub name: String,
+    /// Surveyed coordinates of the anchor.
+    pub coord: Coord,
+    /// Properties of the anchor.
+    pub properties: Properties,
 }
 
-impl From<SegmentOfTraversal> for lrs_generated::SegmentOfTraversal {
-    fn from(val: SegmentOfTraversal) -> Self {
-        lrs_generated::SegmentOfTraversal::new(
-            val.segment_index as u64,
-            match val.reversed {
-                true => Direction::Decreasing,
-                false => Direction::Increasing,
-            },
-        )
-    }
+/// The positions derived by [`Builder::add_surveyed_lrm`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SurveyReport {
+    /// Distance of each anchor from the start of the traversal, in the order of the survey.
+    pub distances: Vec<f64>,
+    /// Distance between consecutive anchors along the traversal, sorted by their position.
+    pub intervals: Vec<f64>,
+    /// Index in the survey and offset of the anchors farther from the traversal than the threshold.
+    pub far_anchors: Vec<(usize, f64)>,
 }
 
 enum AnchorPosition {
@@ -54,6 +308,24 @@ enum AnchorPosition {
     Curve(f64),
 }
 
+/// What [`Builder::clean_topology`] fixed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TopologyReport {
+    /// Each snapped [`Node`], with the [`Node`] it was snapped onto.
+    pub snapped_nodes: Vec<(usize, usize)>,
+    /// Each [`Segment`] split at a [`Node`], with the [`Node`] and the new [`Segment`] after the [`Node`].
+    pub split_segments: Vec<(usize, usize, usize)>,
+    /// Number of [`Connection`]s added between the [`Traversal`]s whose extremities were snapped together.
+    pub added_connections: usize,
+}
+
+struct TempConnection {
+    node_index: usize,
+    from_traversal: usize,
+    to_traversal: usize,
+    bidirectional: bool,
+}
+
 struct TempSegment {
     id: String,
     geometry: Vec<Coord>,
@@ -61,11 +333,19 @@ struct TempSegment {
     end_node_index: u64,
 }
 
+// What an LRM references, checked when building
+struct TempLrm {
+    id: String,
+    traversal_indices: Vec<usize>,
+    anchor_indices: Vec<usize>,
+}
+
 struct TempTraversal {
     id: String,
     curve: SphericalLineStringCurve,
     segments: Vec<SegmentOfTraversal>,
     lrms: Vec<usize>,
+    properties: Properties,
 }
 
 impl TempTraversal {
@@ -90,17 +370,40 @@ pub struct Builder<'fbb> {
     temp_traversal: Vec<TempTraversal>,
     // Temporary [`Anchor`]s because we need to project them on the [`Traversal`] of each LRM they belong to.
     temp_anchors: Vec<AnchorPosition>,
+    // The [`Traversal`]s and [`Anchor`]s of each LRM, that might not exist
+    temp_lrms: Vec<TempLrm>,
     // Position of every node
     nodes_coords: Vec<Coord>,
     // Every node of a given traversal
     nodes_of_traversal: Vec<Vec<usize>>,
     // Id of every node
     nodes_ids: Vec<String>,
+    // Options recorded in the [`BuildInfo`]
+    build_options: Properties,
+    // Source datasets recorded in the [`BuildInfo`]
+    sources: Vec<String>,
+    // Coordinate reference system of the coordinates
+    crs: Option<String>,
+    // When positive, the coordinates of the segments are quantized to multiples of this step
+    coordinate_precision: f64,
+    // The finished file, with its footer
+    data: Vec<u8>,
+    // Whether the finished file is compressed
+    #[cfg(feature = "compression")]
+    compressed: bool,
+    // Connections between traversals, their extremities are only known once the traversals are oriented
+    temp_connections: Vec<TempConnection>,
+    // Index of each connection in `temp_connections` by node and traversals, to merge the duplicates
+    connection_index: HashMap<(usize, usize, usize), usize>,
+    // Validity of the anchors, LRMs and events added from now on
+    validity: Validity,
 
     // Final objects that will be in the binary file.
     nodes: Vec<WIPOffset<Node<'fbb>>>,
     anchors: Vec<WIPOffset<Anchor<'fbb>>>,
     lrms: Vec<WIPOffset<LinearReferencingMethod<'fbb>>>,
+    point_events: Vec<WIPOffset<PointEvent<'fbb>>>,
+    linear_events: Vec<WIPOffset<LinearEvent<'fbb>>>,
 }
 
 impl<'fbb> Builder<'fbb> {
@@ -109,15 +412,7 @@ impl<'fbb> Builder<'fbb> {
         &mut self,
         properties: Properties,
     ) -> Option<WIPOffset<Vector<'fbb, ForwardsUOffset<Property<'fbb>>>>> {
-        let properties_vec: Vec<_> = properties
-            .iter()
-            .map(|(k, v)| {
-                let key = Some(self.fbb.create_string(k));
-                let value = Some(self.fbb.create_string(v));
-                Property::create(&mut self.fbb, &PropertyArgs { key, value })
-            })
-            .collect();
-        Some(self.fbb.create_vector(&properties_vec))
+        Some(to_fb(&mut self.fbb, &properties))
     }
 
     /// Create a new [`Builder`] (default `size = 1024`).
@@ -128,9 +423,11 @@ impl<'fbb> Builder<'fbb> {
         }
     }
 
-    /// Gives the indexes of all the nodes of a traversal
-    pub fn get_nodes_of_traversal(&self, traversal_idx: usize) -> &[usize] {
-        &self.nodes_of_traversal[traversal_idx]
+    /// Gives the indexes of all the nodes of a traversal, `None` if the traversal was not added
+    pub fn get_nodes_of_traversal(&self, traversal_idx: usize) -> Option<&[usize]> {
+        self.nodes_of_traversal
+            .get(traversal_idx)
+            .map(Vec::as_slice)
     }
 
     /// Add a new [`Node`].
@@ -158,11 +455,14 @@ impl<'fbb> Builder<'fbb> {
     ) -> usize {
         let properties = self.build_properties(properties);
         let geometry = Point::new(coord.x, coord.y);
+        let (valid_from, valid_to) = self.validity.to_fb(&mut self.fbb);
         let anchor_arg = AnchorArgs {
             id: Some(self.fbb.create_string(id)),
             name: name.map(|n| self.fbb.create_string(n)),
             geometry: Some(&geometry),
             properties,
+            valid_from,
+            valid_to,
             ..Default::default()
         };
 
@@ -182,10 +482,13 @@ impl<'fbb> Builder<'fbb> {
         properties: Properties,
     ) -> usize {
         let properties = self.build_properties(properties);
+        let (valid_from, valid_to) = self.validity.to_fb(&mut self.fbb);
         let anchor_arg = AnchorArgs {
             id: Some(self.fbb.create_string(id)),
             name: name.map(|n| self.fbb.create_string(n)),
             properties,
+            valid_from,
+            valid_to,
             ..Default::default()
         };
 
@@ -216,22 +519,38 @@ impl<'fbb> Builder<'fbb> {
 
     /// Add a new [`Traversal`], created from the [`Segment`]s provided through `Builder::add_segment`.
     /// The existing [`Segment`]s are consumed and will not be accessible anymore.
+    /// An unknown [`Segment`] is reported by [`Builder::build_bytes`].
     pub fn add_traversal(&mut self, traversal_id: &str, segments: &[SegmentOfTraversal]) -> usize {
+        let (coords, nodes_of_traversal) = self.traversal_geometry(segments);
+        self.temp_traversal.push(TempTraversal {
+            id: traversal_id.to_owned(),
+            curve: SphericalLineStringCurve::new(geo::LineString::new(coords), 100.),
+            segments: segments.to_vec(),
+            lrms: vec![],
+            properties: properties!(),
+        });
+        self.nodes_of_traversal.push(nodes_of_traversal);
+
+        self.temp_traversal.len() - 1
+    }
+
+    /// Private helper that gives the coordinates and the [`Node`]s of a [`Traversal`] made of the `segments`.
+    fn traversal_geometry(&self, segments: &[SegmentOfTraversal]) -> (Vec<Coord>, Vec<usize>) {
         let mut coords = vec![];
         let mut nodes_of_traversal = vec![];
         for segment in segments {
-            let start_node = self.temp_segments[segment.segment_index].start_node_index as usize;
-            let end_node = self.temp_segments[segment.segment_index].end_node_index as usize;
+            // An unknown segment is skipped here and reported by `check_structure`
+            let Some(temp_segment) = self.temp_segments.get(segment.segment_index) else {
+                continue;
+            };
+            let start_node = temp_segment.start_node_index as usize;
+            let end_node = temp_segment.end_node_index as usize;
             if segment.reversed {
                 if nodes_of_traversal.is_empty() {
                     nodes_of_traversal.push(end_node);
                 }
                 nodes_of_traversal.push(start_node);
-                for &coord in self.temp_segments[segment.segment_index]
-                    .geometry
-                    .iter()
-                    .rev()
-                {
+                for &coord in temp_segment.geometry.iter().rev() {
                     coords.push(coord);
                 }
             } else {
@@ -239,25 +558,435 @@ impl<'fbb> Builder<'fbb> {
                     nodes_of_traversal.push(start_node);
                 }
                 nodes_of_traversal.push(end_node);
-                for &coord in self.temp_segments[segment.segment_index].geometry.iter() {
+                for &coord in temp_segment.geometry.iter() {
                     coords.push(coord)
                 }
             }
         }
+        (coords, nodes_of_traversal)
+    }
 
-        self.temp_traversal.push(TempTraversal {
-            id: traversal_id.to_owned(),
-            curve: SphericalLineStringCurve::new(geo::LineString::new(coords), 100.),
-            segments: segments.to_vec(),
-            lrms: vec![],
+    /// Add a new [`Traversal`] made of a single [`Segment`] with the given `geometry`, between two [`Node`]s.
+    /// The [`Segment`] has the same `id` as the [`Traversal`].
+    pub fn add_traversal_from_geometry(
+        &mut self,
+        traversal_id: &str,
+        geometry: &LineString,
+        start_node_index: usize,
+        end_node_index: usize,
+    ) -> usize {
+        let segment_index =
+            self.add_segment(traversal_id, &geometry.0, start_node_index, end_node_index);
+        let segment = SegmentOfTraversal {
+            segment_index,
+            reversed: false,
+        };
+        self.add_traversal(traversal_id, &[segment])
+    }
+
+    /// Add a new [`Traversal`] with [`Builder::add_traversal_from_geometry`], or `None` if the `geometry` is empty.
+    /// Its extremities are shared with the [`Traversal`]s ending at the same coordinates:
+    /// `nodes` keeps the index of the [`Node`]s already added, by coordinates.
+    #[cfg(any(
+        feature = "geojson",
+        feature = "geopackage",
+        feature = "shapefile",
+        feature = "gdal",
+        feature = "railml",
+        feature = "netex",
+        feature = "rinf",
+        feature = "yaml",
+        feature = "geoparquet",
+        feature = "postgres"
+    ))]
+    pub(crate) fn add_traversal_with_shared_nodes(
+        &mut self,
+        traversal_id: &str,
+        geometry: &LineString,
+        nodes: &mut HashMap<(u64, u64), usize>,
+    ) -> Option<usize> {
+        let mut node_at = |coord: Coord| {
+            let next_index = self.nodes_coords.len();
+            let index = *nodes
+                .entry((coord.x.to_bits(), coord.y.to_bits()))
+                .or_insert(next_index);
+            if index == next_index {
+                self.add_node(&index.to_string(), coord, properties!());
+            }
+            index
+        };
+        let (start, end) = (node_at(*geometry.0.first()?), node_at(*geometry.0.last()?));
+        Some(self.add_traversal_from_geometry(traversal_id, geometry, start, end))
+    }
+
+    // Length of a traversal, for the importers that place the anchors at a distance from its start
+    #[cfg(feature = "netex")]
+    pub(crate) fn traversal_length(&self, traversal_index: usize) -> f64 {
+        self.temp_traversal[traversal_index].curve.length()
+    }
+
+    /// Sets the metadata of a [`Traversal`], such as the name of the line, its operator or its country.
+    /// It will be available in [`crate::lrs::Traversal::properties`].
+    pub fn set_traversal_properties(&mut self, traversal_index: usize, properties: Properties) {
+        self.temp_traversal[traversal_index].properties = properties;
+    }
+
+    /// Allows to go from a [`Traversal`] to an other at a [`Node`].
+    /// The [`Node`] must be an extremity of both [`Traversal`]s.
+    /// If `bidirectional`, it is also allowed to go from `to_traversal` to `from_traversal`.
+    pub fn add_connection(
+        &mut self,
+        node_index: usize,
+        from_traversal: usize,
+        to_traversal: usize,
+        bidirectional: bool,
+    ) -> Result<(), BuilderError> {
+        for traversal in [from_traversal, to_traversal] {
+            if self.traversal_end(traversal, node_index).is_none() {
+                return Err(BuilderError::ConnectionNotAtExtremity {
+                    node_index,
+                    // An unknown traversal is reported by its index
+                    traversal_id: self
+                        .temp_traversal
+                        .get(traversal)
+                        .map_or_else(|| traversal.to_string(), |t| t.id.clone()),
+                });
+            }
+        }
+        self.push_connection(TempConnection {
+            node_index,
+            from_traversal,
+            to_traversal,
+            bidirectional,
         });
-        self.nodes_of_traversal.push(nodes_of_traversal);
+        Ok(())
+    }
 
-        self.temp_traversal.len() - 1
+    /// Private helper that adds a connection, unless an equivalent one exists.
+    /// When the new connection is bidirectional, the existing one becomes bidirectional.
+    /// Returns whether the connection was added.
+    fn push_connection(&mut self, connection: TempConnection) -> bool {
+        let node = connection.node_index;
+        let key = (node, connection.from_traversal, connection.to_traversal);
+        let reversed = (node, connection.to_traversal, connection.from_traversal);
+        let existing = self.connection_index.get(&key).copied().or_else(|| {
+            self.connection_index
+                .get(&reversed)
+                .copied()
+                .filter(|&idx| connection.bidirectional || self.temp_connections[idx].bidirectional)
+        });
+        match existing {
+            Some(idx) => {
+                self.temp_connections[idx].bidirectional |= connection.bidirectional;
+                false
+            }
+            None => {
+                self.connection_index
+                    .insert(key, self.temp_connections.len());
+                self.temp_connections.push(connection);
+                true
+            }
+        }
+    }
+
+    /// Adds a bidirectional [`Connection`] at every [`Node`] where exactly two [`Traversal`]s meet by their extremities.
+    ///
+    /// The importers sharing the [`Node`]s of their [`Traversal`]s call it once all their [`Traversal`]s are added.
+    /// Call it when the [`Traversal`]s are added one by one and continue each other.
+    /// The [`Node`]s where more [`Traversal`]s meet, such as railway switches, are skipped
+    /// as it is not possible to go from one branch to the other: add their [`Connection`]s with [`Builder::add_connection`].
+    pub fn connect_traversal_extremities(&mut self) {
+        let mut traversals_at_node = HashMap::<usize, Vec<usize>>::new();
+        for (traversal, nodes) in self.nodes_of_traversal.iter().enumerate() {
+            for node in [nodes.first(), nodes.last()].into_iter().flatten() {
+                let traversals = traversals_at_node.entry(*node).or_default();
+                if !traversals.contains(&traversal) {
+                    traversals.push(traversal);
+                }
+            }
+        }
+
+        let mut nodes: Vec<_> = traversals_at_node.into_iter().collect();
+        nodes.sort();
+        for (node_index, traversals) in nodes {
+            if let [from_traversal, to_traversal] = traversals[..] {
+                self.push_connection(TempConnection {
+                    node_index,
+                    from_traversal,
+                    to_traversal,
+                    bidirectional: true,
+                });
+            }
+        }
+    }
+
+    /// Cleans the topology of the [`Segment`]s added so far, as the source geometries rarely match exactly.
+    ///
+    /// - The [`Node`]s closer than `tolerance` meters are snapped together: the [`Segment`]s ending at a snapped [`Node`]
+    ///   end at the one it was snapped onto, and their geometry is moved there.
+    ///   The [`Traversal`]s whose extremities were snapped together are connected.
+    /// - The [`Segment`]s passing closer than `tolerance` meters from a [`Node`] are split there,
+    ///   so that the [`Traversal`]s using them go through the [`Node`].
+    ///
+    /// The indexes of the [`Traversal`]s are kept, and their geometries are updated. The snapped [`Node`]s are kept in the file, unused.
+    /// The [`Anchor`]s of the LRMs already added are not projected again: their positions move by less than the `tolerance`.
+    /// The `tolerance` is recorded in the build options.
+    pub fn clean_topology(&mut self, tolerance: f64) -> TopologyReport {
+        self.set_build_option("snap_tolerance", &tolerance.to_string());
+        let mut report = TopologyReport::default();
+        let coords = self.nodes_coords.clone();
+        let distance =
+            |a: Coord, b: Coord| geo::Haversine.distance(geo::Point::from(a), geo::Point::from(b));
+        // The longitudes covered by the tolerance at a latitude, so that only the nearby nodes are compared
+        let max_dx = |latitude: f64| {
+            tolerance / (METERS_PER_DEGREE * latitude.to_radians().cos().max(f64::EPSILON))
+        };
+        let max_dy = tolerance / METERS_PER_DEGREE;
+
+        // The nodes of the segments, sorted by longitude
+        let mut nodes: Vec<usize> = self
+            .temp_segments
+            .iter()
+            .flat_map(|segment| [segment.start_node_index, segment.end_node_index])
+            .map(|node| node as usize)
+            .collect();
+        nodes.sort_by(|&a, &b| coords[a].x.total_cmp(&coords[b].x).then(a.cmp(&b)));
+        nodes.dedup();
+
+        let mut snapped_to: Vec<usize> = (0..coords.len()).collect();
+        for (idx, &node) in nodes.iter().enumerate() {
+            if snapped_to[node] != node {
+                continue;
+            }
+            let dx = max_dx(coords[node].y);
+            for &other in nodes[idx + 1..]
+                .iter()
+                .take_while(|&&other| coords[other].x - coords[node].x <= dx)
+            {
+                if snapped_to[other] == other && distance(coords[node], coords[other]) <= tolerance
+                {
+                    snapped_to[other] = node;
+                    report.snapped_nodes.push((other, node));
+                }
+            }
+        }
+
+        // Connect the traversals ending at nodes snapped together
+        let mut traversals_at_node = HashMap::<usize, Vec<usize>>::new();
+        for (traversal, traversal_nodes) in self.nodes_of_traversal.iter().enumerate() {
+            for node in [traversal_nodes.first(), traversal_nodes.last()]
+                .into_iter()
+                .flatten()
+            {
+                let traversals = traversals_at_node.entry(*node).or_default();
+                if !traversals.contains(&traversal) {
+                    traversals.push(traversal);
+                }
+            }
+        }
+        let mut snapped_together = HashMap::<usize, Vec<usize>>::new();
+        for &(node, onto) in &report.snapped_nodes {
+            snapped_together
+                .entry(onto)
+                .or_insert_with(|| vec![onto])
+                .push(node);
+        }
+        let mut snapped_together: Vec<_> = snapped_together.into_iter().collect();
+        snapped_together.sort();
+        for (node_index, snapped) in snapped_together {
+            for (idx, a) in snapped.iter().enumerate() {
+                for b in &snapped[idx + 1..] {
+                    for &from_traversal in traversals_at_node.get(a).into_iter().flatten() {
+                        for &to_traversal in traversals_at_node.get(b).into_iter().flatten() {
+                            if from_traversal != to_traversal
+                                && self.push_connection(TempConnection {
+                                    node_index,
+                                    from_traversal,
+                                    to_traversal,
+                                    bidirectional: true,
+                                })
+                            {
+                                report.added_connections += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for connection in &mut self.temp_connections {
+            connection.node_index = snapped_to[connection.node_index];
+        }
+        self.connection_index = self
+            .temp_connections
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(idx, c)| ((c.node_index, c.from_traversal, c.to_traversal), idx))
+            .collect();
+        for segment in &mut self.temp_segments {
+            let start = snapped_to[segment.start_node_index as usize];
+            if start != segment.start_node_index as usize {
+                segment.start_node_index = start as u64;
+                if let Some(first) = segment.geometry.first_mut() {
+                    *first = coords[start];
+                }
+            }
+            let end = snapped_to[segment.end_node_index as usize];
+            if end != segment.end_node_index as usize {
+                segment.end_node_index = end as u64;
+                if let Some(last) = segment.geometry.last_mut() {
+                    *last = coords[end];
+                }
+            }
+        }
+
+        // Split the segments at the first node they pass by, until there is none
+        nodes.retain(|&node| snapped_to[node] == node);
+        let mut next_segment = HashMap::<usize, usize>::new();
+        let mut segment_index = 0;
+        while segment_index < self.temp_segments.len() {
+            let segment = &self.temp_segments[segment_index];
+            let extremities = [
+                segment.start_node_index as usize,
+                segment.end_node_index as usize,
+            ];
+            let bbox = geo::BoundingRect::bounding_rect(&LineString::new(segment.geometry.clone()));
+            let Some(bbox) = bbox else {
+                segment_index += 1;
+                continue;
+            };
+            let dx = max_dx(bbox.max().y.abs().max(bbox.min().y.abs()));
+            let first = nodes.partition_point(|&node| coords[node].x < bbox.min().x - dx);
+            let mut split: Option<(usize, usize, f64)> = None;
+            for &node in nodes[first..]
+                .iter()
+                .take_while(|&&node| coords[node].x <= bbox.max().x + dx)
+            {
+                let coord = coords[node];
+                if extremities.contains(&node)
+                    || coord.y < bbox.min().y - max_dy
+                    || coord.y > bbox.max().y + max_dy
+                {
+                    continue;
+                }
+                let Some((line, ratio, distance)) = closest_on_geometry(&segment.geometry, coord)
+                else {
+                    continue;
+                };
+                let is_extremity = (line == 0 && ratio == 0.)
+                    || (line + 2 == segment.geometry.len() && ratio == 1.);
+                if distance <= tolerance
+                    && !is_extremity
+                    && split.is_none_or(|(_, l, r)| (line, ratio) < (l, r))
+                {
+                    split = Some((node, line, ratio));
+                }
+            }
+            let Some((node, line, _)) = split else {
+                segment_index += 1;
+                continue;
+            };
+
+            let coord = coords[node];
+            let new_index = self.temp_segments.len();
+            let segment = &mut self.temp_segments[segment_index];
+            let mut after = vec![coord];
+            after.extend(
+                segment
+                    .geometry
+                    .drain(line + 1..)
+                    .skip_while(|&next| next == coord),
+            );
+            if segment.geometry.last() != Some(&coord) {
+                segment.geometry.push(coord);
+            }
+            let new_segment = TempSegment {
+                id: format!("{}/{}", segment.id, self.nodes_ids[node]),
+                geometry: after,
+                start_node_index: node as u64,
+                end_node_index: segment.end_node_index,
+            };
+            segment.end_node_index = node as u64;
+            self.temp_segments.push(new_segment);
+            if let Some(next) = next_segment.insert(segment_index, new_index) {
+                next_segment.insert(new_index, next);
+            }
+            report.split_segments.push((segment_index, node, new_index));
+        }
+
+        if report.snapped_nodes.is_empty() && report.split_segments.is_empty() {
+            return report;
+        }
+        for traversal_index in 0..self.temp_traversal.len() {
+            let segments: Vec<_> = self.temp_traversal[traversal_index]
+                .segments
+                .iter()
+                .flat_map(|segment| {
+                    let mut parts = vec![segment.segment_index];
+                    while let Some(&next) = parts.last().and_then(|last| next_segment.get(last)) {
+                        parts.push(next);
+                    }
+                    if segment.reversed {
+                        parts.reverse();
+                    }
+                    let reversed = segment.reversed;
+                    parts
+                        .into_iter()
+                        .map(move |segment_index| SegmentOfTraversal {
+                            segment_index,
+                            reversed,
+                        })
+                })
+                .collect();
+            let (coords, nodes_of_traversal) = self.traversal_geometry(&segments);
+            let traversal = &mut self.temp_traversal[traversal_index];
+            traversal.curve = SphericalLineStringCurve::new(LineString::new(coords), 100.);
+            traversal.segments = segments;
+            self.nodes_of_traversal[traversal_index] = nodes_of_traversal;
+        }
+        report
+    }
+
+    /// Private helper that tells which extremity of the traversal is the node.
+    fn traversal_end(&self, traversal: usize, node_index: usize) -> Option<TraversalEnd> {
+        let nodes = self.nodes_of_traversal.get(traversal)?;
+        if nodes.first() == Some(&node_index) {
+            Some(TraversalEnd::Start)
+        } else if nodes.last() == Some(&node_index) {
+            Some(TraversalEnd::End)
+        } else {
+            None
+        }
+    }
+
+    /// Private function that builds the connections data for serialization.
+    /// The connections whose [`Node`] is no longer an extremity of their [`Traversal`]s are skipped:
+    /// [`Builder::build_bytes`] reports them.
+    fn build_connections(&mut self) -> Vec<WIPOffset<Connection<'fbb>>> {
+        let connections: Vec<_> = self
+            .temp_connections
+            .iter()
+            .filter_map(|connection| {
+                Some(ConnectionArgs {
+                    node_index: connection.node_index as u64,
+                    from_traversal_index: connection.from_traversal as u64,
+                    from_end: self
+                        .traversal_end(connection.from_traversal, connection.node_index)?,
+                    to_traversal_index: connection.to_traversal as u64,
+                    to_end: self.traversal_end(connection.to_traversal, connection.node_index)?,
+                    bidirectional: connection.bidirectional,
+                })
+            })
+            .collect();
+        connections
+            .iter()
+            .map(|args| Connection::create(&mut self.fbb, args))
+            .collect()
     }
 
     /// Create a linear referencing method where the distance is provided.
     /// The [`Anchor`]s will be projected on the [`Curve`].
+    /// An unknown [`Traversal`] or [`Anchor`] is reported by [`Builder::build_bytes`].
     pub fn add_lrm(
         &mut self,
         id: &str,
@@ -265,12 +994,162 @@ impl<'fbb> Builder<'fbb> {
         anchors: &[AnchorOnLrm],
         properties: Properties,
     ) {
+        self.add_lrm_with_alternates(id, traversal_index, &[], anchors, properties)
+    }
+
+    /// Adds an [`Anchor`] every `step` units of the LRM between the given anchors, see [`lrm_scale::LrmScale::generate_anchors`].
+    ///
+    /// Returns the given anchors with the generated ones, to be used with [`Builder::add_lrm`].
+    /// This makes usable a line imported with only anchors at its ends.
+    pub fn generate_anchors(
+        &mut self,
+        lrm_id: &str,
+        traversal_index: usize,
+        anchors: &[AnchorOnLrm],
+        step: f64,
+    ) -> Result<Vec<AnchorOnLrm>, BuilderError> {
+        let curve = &self
+            .temp_traversal
+            .get(traversal_index)
+            .ok_or_else(|| BuilderError::UnknownLrmTraversal {
+                lrm_id: lrm_id.to_owned(),
+                traversal_index,
+            })?
+            .curve;
+        let mut sorted = anchors.to_vec();
+        sorted.sort_by(|a, b| a.distance_along_lrm.total_cmp(&b.distance_along_lrm));
+        let scale_anchors = sorted
+            .iter()
+            .map(|anchor| {
+                let position = self.temp_anchors.get(anchor.anchor_index).ok_or_else(|| {
+                    BuilderError::UnknownAnchor {
+                        lrm_id: lrm_id.to_owned(),
+                        anchor_index: anchor.anchor_index,
+                    }
+                })?;
+                let curve_position = match *position {
+                    AnchorPosition::Curve(position) => position,
+                    AnchorPosition::Geographical(coord) => {
+                        curve.project(coord.into())?.distance_along_curve
+                    }
+                };
+                Ok(lrm_scale::Anchor::new_unnamed(
+                    anchor.distance_along_lrm,
+                    curve_position,
+                    None,
+                    properties!(),
+                ))
+            })
+            .collect::<Result<_, BuilderError>>()?;
+        let scale = lrm_scale::LrmScale {
+            id: lrm_id.to_owned(),
+            anchors: scale_anchors,
+        };
+
+        for anchor in scale.generate_anchors(step) {
+            if let lrm_scale::Anchor::Named(anchor) = anchor {
+                sorted.push(AnchorOnLrm {
+                    anchor_index: self.add_projected_anchor(
+                        &format!("{lrm_id}/{}", anchor.name),
+                        Some(&anchor.name),
+                        anchor.curve_position,
+                        anchor.properties,
+                    ),
+                    distance_along_lrm: anchor.scale_position,
+                });
+            }
+        }
+        Ok(sorted)
+    }
+
+    /// Create a linear referencing method from surveyed [`Anchor`]s, that only have coordinates and a name.
+    ///
+    /// Each anchor is projected on the [`Curve`] and its distance along the LRM is its distance from the start of the traversal.
+    /// The anchors farther from the traversal than `max_offset` are kept, but listed in the [`SurveyReport`]:
+    /// they are often a typo in the survey or an anchor of another line.
+    pub fn add_surveyed_lrm(
+        &mut self,
+        id: &str,
+        traversal_index: usize,
+        anchors: &[SurveyedAnchor],
+        max_offset: f64,
+        properties: Properties,
+    ) -> Result<SurveyReport, BuilderError> {
+        let curve = &self
+            .temp_traversal
+            .get(traversal_index)
+            .ok_or_else(|| BuilderError::UnknownLrmTraversal {
+                lrm_id: id.to_owned(),
+                traversal_index,
+            })?
+            .curve;
+        let projections = anchors
+            .iter()
+            .map(|anchor| curve.project(anchor.coord.into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let length = curve.length();
+
+        let mut report = SurveyReport {
+            distances: projections
+                .iter()
+                .map(|p| p.distance_along_curve * length)
+                .collect(),
+            ..Default::default()
+        };
+        report.far_anchors = projections
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.offset.abs() > max_offset)
+            .map(|(index, p)| (index, p.offset.abs()))
+            .collect();
+        let mut sorted = report.distances.clone();
+        sorted.sort_by(f64::total_cmp);
+        report.intervals = sorted.windows(2).map(|w| w[1] - w[0]).collect();
+
+        let anchors_on_lrm: Vec<_> = anchors
+            .iter()
+            .zip(&report.distances)
+            .map(|(anchor, &distance)| AnchorOnLrm {
+                anchor_index: self.add_anchor(
+                    &format!("{id}/{}", anchor.name),
+                    Some(&anchor.name),
+                    anchor.coord,
+                    anchor.properties.clone(),
+                ),
+                distance_along_lrm: distance,
+            })
+            .collect();
+        self.add_lrm(id, traversal_index, &anchors_on_lrm, properties);
+        Ok(report)
+    }
+
+    /// Create a linear referencing method that also applies on other traversals, such as the other track of a double-track line.
+    /// The [`Anchor`]s will be projected on the [`Curve`] of the main traversal.
+    pub fn add_lrm_with_alternates(
+        &mut self,
+        id: &str,
+        traversal_index: usize,
+        alternate_traversal_indices: &[usize],
+        anchors: &[AnchorOnLrm],
+        properties: Properties,
+    ) {
+        self.temp_lrms.push(TempLrm {
+            id: id.to_owned(),
+            traversal_indices: std::iter::once(traversal_index)
+                .chain(alternate_traversal_indices.iter().copied())
+                .collect(),
+            anchor_indices: anchors.iter().map(|a| a.anchor_index).collect(),
+        });
         let id = Some(self.fbb.create_string(id));
         let properties = self.build_properties(properties);
         let mut anchors = anchors.to_vec();
         anchors.sort_by_key(|anchor| (anchor.distance_along_lrm * 10e6) as i64);
+
+        let (anchors, projected_anchors, measure_direction) =
+            self.project_anchors(&anchors, traversal_index);
         let anchor_indices = anchors.iter().map(|a| a.anchor_index as u64);
         let distances = anchors.iter().map(|a| a.distance_along_lrm);
+        let (valid_from, valid_to) = self.validity.to_fb(&mut self.fbb);
 
         let args = LinearReferencingMethodArgs {
             id,
@@ -278,51 +1157,203 @@ impl<'fbb> Builder<'fbb> {
             traversal_index: traversal_index as u32,
             anchor_indices: Some(self.fbb.create_vector_from_iter(anchor_indices)),
             distances: Some(self.fbb.create_vector_from_iter(distances)),
-            projected_anchors: Some(self.project_anchors(&anchors, traversal_index)),
+            projected_anchors: Some(projected_anchors),
+            measure_direction,
+            alternate_traversal_indices: Some(
+                self.fbb.create_vector_from_iter(
+                    alternate_traversal_indices
+                        .iter()
+                        .map(|&index| index as u32),
+                ),
+            ),
+            valid_from,
+            valid_to,
             ..Default::default()
         };
-        self.temp_traversal[traversal_index]
-            .lrms
-            .push(self.lrms.len());
+        for &index in std::iter::once(&traversal_index).chain(alternate_traversal_indices) {
+            if let Some(traversal) = self.temp_traversal.get_mut(index) {
+                traversal.lrms.push(self.lrms.len());
+            }
+        }
         self.lrms
             .push(LinearReferencingMethod::create(&mut self.fbb, &args));
     }
 
+    /// Add an object located at a single measure of an LRM, such as a signal.
+    /// It will be available in [`crate::lrs::Lrs::point_events`].
+    pub fn add_point_event(
+        &mut self,
+        id: &str,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+        properties: TypedProperties,
+    ) -> usize {
+        let (valid_from, valid_to) = self.validity.to_fb(&mut self.fbb);
+        let args = PointEventArgs {
+            id: Some(self.fbb.create_string(id)),
+            lrm_index: lrm_index as u32,
+            measure: Some(measure_to_fb(&mut self.fbb, measure)),
+            properties: Some(typed_properties_to_fb(&mut self.fbb, &properties)),
+            valid_from,
+            valid_to,
+        };
+        self.point_events
+            .push(PointEvent::create(&mut self.fbb, &args));
+        self.point_events.len() - 1
+    }
+
+    /// Add an object located over a range of measures of an LRM, such as a bridge.
+    /// It will be available in [`crate::lrs::Lrs::linear_events`].
+    pub fn add_linear_event(
+        &mut self,
+        id: &str,
+        lrm_index: usize,
+        begin: &LrmScaleMeasure,
+        end: &LrmScaleMeasure,
+        properties: TypedProperties,
+    ) -> usize {
+        let (valid_from, valid_to) = self.validity.to_fb(&mut self.fbb);
+        let args = LinearEventArgs {
+            id: Some(self.fbb.create_string(id)),
+            lrm_index: lrm_index as u32,
+            begin: Some(measure_to_fb(&mut self.fbb, begin)),
+            end: Some(measure_to_fb(&mut self.fbb, end)),
+            properties: Some(typed_properties_to_fb(&mut self.fbb, &properties)),
+            valid_from,
+            valid_to,
+        };
+        self.linear_events
+            .push(LinearEvent::create(&mut self.fbb, &args));
+        self.linear_events.len() - 1
+    }
+
     /// Private helper that projects [`Anchor`]s onto a [`Curve`].
+    /// Also returns how the measures evolve along the [`Curve`], as the `anchors` are sorted by distance along the LRM.
+    ///
+    /// The [`Anchor`]s that can not be placed on the [`Curve`] are left out, with the returned `anchors`:
+    /// the unknown [`Traversal`], [`Anchor`] or invalid geometry is reported by [`Builder::build_bytes`].
     fn project_anchors(
         &mut self,
         anchors: &[AnchorOnLrm],
         traversal_idx: usize,
-    ) -> WIPOffset<Vector<'fbb, ForwardsUOffset<lrs_generated::ProjectedAnchor<'fbb>>>> {
-        let curve = &self.temp_traversal[traversal_idx].curve;
+    ) -> (
+        Vec<AnchorOnLrm>,
+        WIPOffset<Vector<'fbb, ForwardsUOffset<lrs_generated::ProjectedAnchor<'fbb>>>>,
+        Direction,
+    ) {
+        let curve = self.temp_traversal.get(traversal_idx).map(|t| &t.curve);
 
-        let projected_anchors: Vec<_> = anchors
+        let (anchors, projected_anchors): (Vec<_>, Vec<_>) = anchors
             .iter()
-            .map(|anchor| match self.temp_anchors[anchor.anchor_index] {
-                AnchorPosition::Curve(distance_along_curve) => (None, distance_along_curve),
-                AnchorPosition::Geographical(coord) => {
-                    let projected = curve
-                        .project(coord.into())
-                        .expect("could not project anchor");
-                    let geometry = lrs_generated::Point::new(
-                        projected.projected_coords.x(),
-                        projected.projected_coords.y(),
-                    );
-
-                    (Some(geometry), projected.distance_along_curve)
-                }
+            .filter_map(|anchor| {
+                let projected = match self.temp_anchors.get(anchor.anchor_index)? {
+                    &AnchorPosition::Curve(distance_along_curve) => {
+                        (None, distance_along_curve, None)
+                    }
+                    AnchorPosition::Geographical(coord) => {
+                        let projected = curve?.project((*coord).into()).ok()?;
+                        let geometry = lrs_generated::Point::new(
+                            projected.projected_coords.x(),
+                            projected.projected_coords.y(),
+                        );
+
+                        (
+                            Some(geometry),
+                            projected.distance_along_curve,
+                            Some(projected.offset),
+                        )
+                    }
+                };
+                Some((*anchor, projected))
             })
-            .map(|(geom, distance_along_curve)| {
+            .unzip();
+        let measure_direction = match (projected_anchors.first(), projected_anchors.last()) {
+            (Some((_, first, _)), Some((_, last, _))) if last < first => Direction::Decreasing,
+            _ => Direction::Increasing,
+        };
+
+        let projected_anchors: Vec<_> = projected_anchors
+            .into_iter()
+            .map(|(geom, distance_along_curve, offset)| {
                 ProjectedAnchor::create(
                     &mut self.fbb,
                     &ProjectedAnchorArgs {
                         geometry: geom.as_ref(),
                         distance_along_curve,
+                        offset,
                     },
                 )
             })
             .collect();
-        self.fbb.create_vector(&projected_anchors)
+        (
+            anchors,
+            self.fbb.create_vector(&projected_anchors),
+            measure_direction,
+        )
+    }
+
+    /// Records an option used to build the file. It will be available in [`crate::lrs::Lrs::build_info`].
+    pub fn set_build_option(&mut self, key: &str, value: &str) {
+        self.build_options.insert(key.to_owned(), value.to_owned());
+    }
+
+    /// Sets when the [`Anchor`]s, LRMs and events added from now on are valid, see [`Validity`].
+    ///
+    /// To store the successive kilometrages of a network in one file, add the objects of each epoch after setting its validity.
+    /// Returns [`BuilderError::InvalidDate`] if a date is not a `YYYY-MM-DD` date: the validity is then unchanged.
+    pub fn set_validity(&mut self, validity: Validity) -> Result<(), BuilderError> {
+        for date in validity.from.iter().chain(&validity.to) {
+            check_date(date).map_err(|_| BuilderError::InvalidDate(date.clone()))?;
+        }
+        self.validity = validity;
+        Ok(())
+    }
+
+    /// Records the coordinate reference system of the coordinates, as an authority code such as `EPSG:4326`.
+    /// It will be available in [`crate::lrs::Lrs::crs`].
+    pub fn set_crs(&mut self, crs: &str) {
+        self.crs = Some(crs.to_owned());
+    }
+
+    /// Quantizes the coordinates of the [`Segment`]s to multiples of `precision`, in the unit of the coordinates,
+    /// e.g. `1e-7` degrees for about 1 cm or `1e-6` for about 10 cm.
+    ///
+    /// Each coordinate is stored as the 32 bits difference with the previous one instead of two 64 bits floats:
+    /// the file is smaller, especially once compressed. The precision is recorded in the file and the coordinates
+    /// are restored when it is loaded. A [`Segment`] whose coordinates do not fit in 32 bits keeps its exact geometry.
+    pub fn set_coordinate_precision(&mut self, precision: f64) {
+        self.coordinate_precision = precision;
+    }
+
+    /// Compresses the built file, see [`crate::lrs::Lrs::to_compressed_bytes`].
+    #[cfg(feature = "compression")]
+    pub fn set_compression(&mut self, compressed: bool) {
+        self.compressed = compressed;
+    }
+
+    /// Records the identifier of a source dataset. It will be available in [`crate::lrs::Lrs::build_info`].
+    pub fn add_source(&mut self, source: &str) {
+        self.sources.push(source.to_owned());
+    }
+
+    /// Private function that builds the provenance of the file
+    fn build_provenance(&mut self) -> WIPOffset<BuildInfo<'fbb>> {
+        let liblrs_version = Some(self.fbb.create_string(env!("CARGO_PKG_VERSION")));
+        let options = self.build_properties(self.build_options.clone());
+        let sources: Vec<_> = self
+            .sources
+            .iter()
+            .map(|source| self.fbb.create_string(source))
+            .collect();
+        let sources = Some(self.fbb.create_vector(&sources));
+        BuildInfo::create(
+            &mut self.fbb,
+            &BuildInfoArgs {
+                liblrs_version,
+                options,
+                sources,
+            },
+        )
     }
 
     /// Save the flatbuffer to the given file.
@@ -336,10 +1367,22 @@ impl<'fbb> Builder<'fbb> {
             .temp_segments
             .iter()
             .map(|segment| {
-                let points_iter = segment.geometry.iter().map(|c| Point::new(c.x, c.y));
+                let quantized = Some(self.coordinate_precision)
+                    .filter(|precision| precision.is_finite() && *precision > 0.)
+                    .and_then(|precision| quantize(&segment.geometry, precision));
+                // A quantized segment keeps an empty geometry, as it is required
+                let points: Vec<_> = match quantized {
+                    Some(_) => vec![],
+                    None => segment
+                        .geometry
+                        .iter()
+                        .map(|c| Point::new(c.x, c.y))
+                        .collect(),
+                };
                 (
                     self.fbb.create_string(&segment.id),
-                    self.fbb.create_vector_from_iter(points_iter),
+                    self.fbb.create_vector(&points),
+                    quantized.map(|quantized| self.fbb.create_vector(&quantized)),
                     segment.start_node_index,
                     segment.end_node_index,
                 )
@@ -347,18 +1390,21 @@ impl<'fbb> Builder<'fbb> {
             .collect();
         segments
             .into_iter()
-            .map(|(id, points, start_node_index, end_node_index)| {
-                Segment::create(
-                    &mut self.fbb,
-                    &SegmentArgs {
-                        id: Some(id),
-                        properties: None,
-                        geometry: Some(points),
-                        start_node_index,
-                        end_node_index,
-                    },
-                )
-            })
+            .map(
+                |(id, points, quantized_geometry, start_node_index, end_node_index)| {
+                    Segment::create(
+                        &mut self.fbb,
+                        &SegmentArgs {
+                            id: Some(id),
+                            properties: None,
+                            geometry: Some(points),
+                            start_node_index,
+                            end_node_index,
+                            quantized_geometry,
+                        },
+                    )
+                },
+            )
             .collect()
     }
 
@@ -377,7 +1423,7 @@ impl<'fbb> Builder<'fbb> {
                 let args = TraversalArgs {
                     id: Some(self.fbb.create_string(&traversal.id)),
                     segments: Some(segments_of_traversal),
-                    properties: None,
+                    properties: Some(to_fb(&mut self.fbb, &traversal.properties)),
                 };
                 Traversal::create(&mut self.fbb, &args)
             })
@@ -386,17 +1432,14 @@ impl<'fbb> Builder<'fbb> {
 
     /// Return the binary data.
     pub fn build_data(&mut self, properties: Properties) -> &[u8] {
-        use geo_index::rtree::RTreeBuilder;
-        use geo_index::rtree::sort::HilbertSort;
-
         let segments = self.build_segments();
         let traversals = self.build_traversals();
 
-        let mut rtree_builder = RTreeBuilder::<f64>::new(traversals.len() as u32);
-        for traversal in &self.temp_traversal {
-            rtree_builder.add_rect(&traversal.curve.bbox());
-        }
-        let tree = rtree_builder.finish::<HilbertSort>();
+        let indexes = spatial_indexes(self.temp_traversal.iter().map(|t| &t.curve));
+
+        let build_info = self.build_provenance();
+        let connections = self.build_connections();
+        let crs = self.crs.as_deref().map(|crs| self.fbb.create_string(crs));
 
         let lrs_args = LrsArgs {
             properties: self.build_properties(properties),
@@ -406,17 +1449,137 @@ impl<'fbb> Builder<'fbb> {
             anchors: Some(self.fbb.create_vector(&self.anchors)),
             linear_referencing_methods: Some(self.fbb.create_vector(&self.lrms)),
             geometry_type: GeometryType::Geographic,
-            lrm_spatial_index: Some(self.fbb.create_vector(&tree.into_inner())),
+            lrm_spatial_index: Some(self.fbb.create_vector(&indexes.lrm_rtree)),
+            build_info: Some(build_info),
+            fragment_spatial_index: Some(self.fbb.create_vector(&indexes.fragment_rtree)),
+            fragment_traversals: Some(self.fbb.create_vector(&indexes.fragment_traversals)),
+            connections: Some(self.fbb.create_vector(&connections)),
+            point_events: Some(self.fbb.create_vector(&self.point_events)),
+            linear_events: Some(self.fbb.create_vector(&self.linear_events)),
+            crs,
+            schema_version: file_format::SCHEMA_VERSION,
+            coordinate_precision: self.coordinate_precision.max(0.),
         };
 
         let lrs = Lrs::create(&mut self.fbb, &lrs_args);
-        self.fbb.finish(lrs, None);
-        self.fbb.finished_data()
-    }
-
-    /// Builds the LRS from the data.
-    pub fn build_lrs(&mut self, properties: Properties) -> Result<ExtLrs, String> {
-        ExtLrs::load(self.build_data(properties))
+        lrs_generated::finish_lrs_buffer(&mut self.fbb, lrs);
+        #[cfg(feature = "compression")]
+        if self.compressed {
+            self.data = file_format::seal_compressed(self.fbb.finished_data());
+            return &self.data;
+        }
+        self.data = file_format::seal(self.fbb.finished_data());
+        &self.data
+    }
+
+    /// Checks the content of the [`Builder`] and returns the binary data of the LRS.
+    ///
+    /// Unlike [`Builder::build_data`], it fails if a [`Segment`] references an unknown [`Node`],
+    /// if a [`Traversal`] references an unknown [`Segment`] or has an invalid geometry, if an LRM references an unknown [`Traversal`] or [`Anchor`],
+    /// if a [`Connection`] is not at an extremity of its [`Traversal`]s,
+    /// if an LRM has no named [`Anchor`], or if the data cannot be read back.
+    pub fn build_bytes(&mut self, properties: Properties) -> Result<Vec<u8>, BuilderError> {
+        self.check_structure()?;
+        let data = self.build_data(properties).to_vec();
+        let lrs = ExtLrs::load(&data)?;
+        let unnamed = lrs.lrms().iter().find(|lrm| {
+            !lrm.scale
+                .anchors
+                .iter()
+                .any(|anchor| matches!(anchor, lrm_scale::Anchor::Named(_)))
+        });
+        if let Some(lrm) = unnamed {
+            return Err(BuilderError::NoNamedAnchor(lrm.scale.id.clone()));
+        }
+        Ok(data)
+    }
+
+    /// Returns the binary data of the LRS with a [`ValidationReport`] of its content.
+    ///
+    /// Only the errors that prevent writing the data fail, as with [`Builder::build_bytes`].
+    /// The other issues, even with an [`crate::validation::Severity::Error`], are in the report
+    /// and it is up to the caller to decide whether to publish the data.
+    pub fn build_with_report(
+        &mut self,
+        properties: Properties,
+        options: &ValidationOptions,
+    ) -> Result<(Vec<u8>, ValidationReport), BuilderError> {
+        self.check_structure()?;
+        let data = self.build_data(properties).to_vec();
+        let lrs = ExtLrs::load(&data)?;
+        let report = with_any_lrs!(&lrs.lrs, lrs => lrs.validate(options));
+        Ok((data, report))
+    }
+
+    // Segments must reference existing nodes, traversals must have a valid geometry
+    // and lrms must reference existing traversals and anchors
+    fn check_structure(&self) -> Result<(), BuilderError> {
+        for segment in &self.temp_segments {
+            for node_index in [segment.start_node_index, segment.end_node_index] {
+                if node_index as usize >= self.nodes_coords.len() {
+                    return Err(BuilderError::UnknownNode {
+                        segment_id: segment.id.clone(),
+                        node_index: node_index as usize,
+                    });
+                }
+            }
+        }
+        for traversal in &self.temp_traversal {
+            if let Some(segment) = traversal
+                .segments
+                .iter()
+                .find(|segment| segment.segment_index >= self.temp_segments.len())
+            {
+                return Err(BuilderError::UnknownSegment {
+                    traversal_id: traversal.id.clone(),
+                    segment_index: segment.segment_index,
+                });
+            }
+        }
+        if let Some(traversal) = self.temp_traversal.iter().find(|t| !t.curve.is_valid()) {
+            return Err(BuilderError::InvalidGeometry(traversal.id.clone()));
+        }
+        for lrm in &self.temp_lrms {
+            if let Some(&traversal_index) = lrm
+                .traversal_indices
+                .iter()
+                .find(|&&index| index >= self.temp_traversal.len())
+            {
+                return Err(BuilderError::UnknownLrmTraversal {
+                    lrm_id: lrm.id.clone(),
+                    traversal_index,
+                });
+            }
+            if let Some(&anchor_index) = lrm
+                .anchor_indices
+                .iter()
+                .find(|&&index| index >= self.temp_anchors.len())
+            {
+                return Err(BuilderError::UnknownAnchor {
+                    lrm_id: lrm.id.clone(),
+                    anchor_index,
+                });
+            }
+        }
+        for connection in &self.temp_connections {
+            for traversal in [connection.from_traversal, connection.to_traversal] {
+                if self
+                    .traversal_end(traversal, connection.node_index)
+                    .is_none()
+                {
+                    return Err(BuilderError::ConnectionNotAtExtremity {
+                        node_index: connection.node_index,
+                        traversal_id: self.temp_traversal[traversal].id.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the LRS from the data.
+    pub fn build_lrs(&mut self, properties: Properties) -> Result<ExtLrs, ExtLrsError> {
+        ExtLrs::load(self.build_data(properties))
     }
 
     /// Return the mapping between a traversal id and its index in the builder.
@@ -430,6 +1593,7 @@ impl<'fbb> Builder<'fbb> {
 
     /// Read the topology from an OpenStreetMap source.
     /// It will read incoming [`Node`]s and [`Segment`]s to create the [`Traversal`]s.
+    #[cfg(feature = "osm")]
     pub fn read_from_osm(
         &mut self,
         input_file: &PathBuf,
@@ -440,6 +1604,8 @@ impl<'fbb> Builder<'fbb> {
     ) {
         let mut default_reporter = ();
         let reporter = reporter.unwrap_or(&mut default_reporter);
+        self.add_source(&input_file.to_string_lossy());
+        self.set_build_option("lrm_tag", lrm_tag);
         let mut reader = osm4routing::Reader::new().merge_ways().read_tag(lrm_tag);
 
         for (key, value) in required.iter() {
@@ -494,6 +1660,240 @@ impl<'fbb> Builder<'fbb> {
         }
     }
 
+    /// Read the topology and the [`Anchor`]s from GeoJSON features.
+    ///
+    /// Each LineString feature becomes a [`Traversal`] and an LRM with the same `id`.
+    /// Extremities with the same coordinates share the same [`Node`], where the [`Traversal`]s continuing each other are connected,
+    /// see [`Builder::connect_traversal_extremities`].
+    /// Each Point feature becomes an [`Anchor`] of the LRM named by its `lrm_id` property, at its `measure`.
+    /// The names of the properties are given by the `mapping`, the other properties are kept.
+    /// Other geometries are ignored.
+    #[cfg(feature = "geojson")]
+    pub fn from_geojson(
+        features: &[geojson::Feature],
+        mapping: &GeoJsonMapping,
+    ) -> Result<Self, BuilderError> {
+        let missing = |feature_index: usize, property: &str| BuilderError::MissingProperty {
+            feature_index,
+            property: property.to_owned(),
+        };
+        let to_coord = |position: &[f64]| Coord {
+            x: position[0],
+            y: position[1],
+        };
+
+        let mut builder = Self::new();
+        let mut nodes = HashMap::<(u64, u64), usize>::new();
+        let mut lrms = vec![];
+        for (feature_index, feature) in features.iter().enumerate() {
+            let Some(geojson::Value::LineString(positions)) =
+                feature.geometry.as_ref().map(|geometry| &geometry.value)
+            else {
+                continue;
+            };
+            let id = geojson_string(feature, &mapping.traversal_id)
+                .or_else(|| match feature.id.as_ref()? {
+                    geojson::feature::Id::String(id) => Some(id.clone()),
+                    geojson::feature::Id::Number(id) => Some(id.to_string()),
+                })
+                .ok_or_else(|| missing(feature_index, &mapping.traversal_id))?;
+            let geometry: LineString = positions.iter().map(|p| to_coord(p)).collect();
+            let traversal = builder
+                .add_traversal_with_shared_nodes(&id, &geometry, &mut nodes)
+                .ok_or_else(|| BuilderError::InvalidGeometry(id.clone()))?;
+            let properties = geojson_properties(feature, &[&mapping.traversal_id]);
+            builder.set_traversal_properties(traversal, properties);
+            lrms.push((id, traversal, vec![]));
+        }
+
+        for (feature_index, feature) in features.iter().enumerate() {
+            let Some(geojson::Value::Point(position)) =
+                feature.geometry.as_ref().map(|geometry| &geometry.value)
+            else {
+                continue;
+            };
+            let lrm_id = geojson_string(feature, &mapping.lrm_id)
+                .ok_or_else(|| missing(feature_index, &mapping.lrm_id))?;
+            let distance_along_lrm = feature
+                .property(&mapping.measure)
+                .and_then(|measure| match measure {
+                    geojson::JsonValue::String(measure) => measure.parse().ok(),
+                    measure => measure.as_f64(),
+                })
+                .ok_or_else(|| missing(feature_index, &mapping.measure))?;
+            let Some((_, _, anchors)) = lrms.iter_mut().find(|(id, _, _)| *id == lrm_id) else {
+                return Err(BuilderError::UnknownLrm {
+                    feature_index,
+                    lrm_id,
+                });
+            };
+            let name = geojson_string(feature, &mapping.anchor_name);
+            let id = name.clone().unwrap_or_else(|| feature_index.to_string());
+            let mapped = [
+                mapping.lrm_id.as_str(),
+                mapping.anchor_name.as_str(),
+                mapping.measure.as_str(),
+            ];
+            let properties = geojson_properties(feature, &mapped);
+            let anchor_index =
+                builder.add_anchor(&id, name.as_deref(), to_coord(position), properties);
+            anchors.push(AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm,
+            });
+        }
+
+        for (id, traversal, anchors) in lrms {
+            builder.add_lrm(&id, traversal, &anchors, properties!());
+        }
+        builder.connect_traversal_extremities();
+        Ok(builder)
+    }
+
+    /// Read the railway route relations from an OpenStreetMap source.
+    ///
+    /// Each relation with `type=route` and `route=railway` or `route=tracks` becomes a [`Traversal`]
+    /// made of its `railway=*` ways, identified by its `lrm_tag` (or by its OpenStreetMap id).
+    /// The `railway=milestone` nodes of the relation, with a `railway:position` or `pk` tag, are the [`Anchor`]s of its LRM.
+    /// A relation without milestones has no LRM.
+    #[cfg(feature = "osm-routes")]
+    pub fn read_osm_routes(
+        &mut self,
+        input_file: &PathBuf,
+        lrm_tag: &str,
+        reporter: Option<&mut dyn DataIssueReporter>,
+    ) -> Result<(), BuilderError> {
+        use osmpbfreader::{OsmId, OsmObj};
+
+        let mut default_reporter = ();
+        let reporter = reporter.unwrap_or(&mut default_reporter);
+        self.add_source(&input_file.to_string_lossy());
+        self.set_build_option("lrm_tag", lrm_tag);
+        self.set_build_option("osm_routes", "true");
+
+        let file = std::fs::File::open(input_file).map_err(|e| BuilderError::Osm(e.to_string()))?;
+        let objs = osmpbfreader::OsmPbfReader::new(file)
+            .get_objs_and_deps(|obj| {
+                obj.is_relation()
+                    && obj.tags().contains("type", "route")
+                    && (obj.tags().contains("route", "railway")
+                        || obj.tags().contains("route", "tracks"))
+            })
+            .map_err(|e| BuilderError::Osm(e.to_string()))?;
+        let coord_of = |id: osmpbfreader::NodeId| {
+            objs.get(&OsmId::Node(id))
+                .and_then(OsmObj::node)
+                .map(|node| Coord {
+                    x: node.lon(),
+                    y: node.lat(),
+                })
+        };
+
+        let mut nodes_index = HashMap::new();
+        let mut segments_index = HashMap::new();
+        for relation in objs.values().filter_map(OsmObj::relation) {
+            let lrm_id = match relation.tags.get(lrm_tag) {
+                Some(value) => value.to_string(),
+                None => relation.id.0.to_string(),
+            };
+
+            let ways: Vec<_> = relation
+                .refs
+                .iter()
+                .filter_map(|member| objs.get(&member.member)?.way())
+                .filter(|way| way.tags.contains_key("railway"))
+                .collect();
+            let mut edges = vec![];
+            for way in &ways {
+                let geometry: Option<Vec<_>> = way.nodes.iter().map(|&id| coord_of(id)).collect();
+                let (Some(geometry), Some(first), Some(last)) =
+                    (geometry, way.nodes.first(), way.nodes.last())
+                else {
+                    continue;
+                };
+                for (id, coord) in [(first, geometry[0]), (last, geometry[geometry.len() - 1])] {
+                    if !nodes_index.contains_key(id) {
+                        let index = self.add_node(&id.0.to_string(), coord, properties!());
+                        nodes_index.insert(*id, index);
+                    }
+                }
+                let way_id = way.id.0.to_string();
+                if !segments_index.contains_key(&way_id) {
+                    let index =
+                        self.add_segment(&way_id, &geometry, nodes_index[first], nodes_index[last]);
+                    segments_index.insert(way_id.clone(), index);
+                }
+                edges.push(osm4routing::Edge {
+                    id: way_id,
+                    source: osm4routing::NodeId(first.0),
+                    target: osm4routing::NodeId(last.0),
+                    geometry,
+                    ..Default::default()
+                });
+            }
+            if edges.is_empty() {
+                continue;
+            }
+
+            let segments: Vec<_> = sort_edges(edges, &lrm_id, reporter)
+                .into_iter()
+                .map(|(edge, reversed)| SegmentOfTraversal {
+                    segment_index: segments_index[&edge.id],
+                    reversed,
+                })
+                .collect();
+            let traversal = self.add_traversal(&lrm_id, &segments);
+
+            // The milestones are members of the relation or nodes of its ways
+            let mut milestones: Vec<_> = relation
+                .refs
+                .iter()
+                .filter_map(|member| match member.member {
+                    OsmId::Node(id) => Some(id),
+                    _ => None,
+                })
+                .chain(ways.iter().flat_map(|way| way.nodes.iter().copied()))
+                .collect();
+            milestones.sort();
+            milestones.dedup();
+            let anchors: Vec<_> = milestones
+                .into_iter()
+                .filter_map(|id| objs.get(&OsmId::Node(id))?.node())
+                .filter(|node| node.tags.contains("railway", "milestone"))
+                .filter_map(|node| {
+                    let position = ["railway:position", "pk"]
+                        .iter()
+                        .find_map(|tag| node.tags.get(*tag))?;
+                    let distance_along_lrm = milestone_position(position)?;
+                    let coord = Coord {
+                        x: node.lon(),
+                        y: node.lat(),
+                    };
+                    let properties = properties!("osm_id" => node.id.0.to_string());
+                    let anchor_index = self.add_anchor(
+                        &node.id.0.to_string(),
+                        Some(position.as_str()),
+                        coord,
+                        properties,
+                    );
+                    Some(AnchorOnLrm {
+                        anchor_index,
+                        distance_along_lrm,
+                    })
+                })
+                .collect();
+            if !anchors.is_empty() {
+                let properties = relation
+                    .tags
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect();
+                self.add_lrm(&lrm_id, traversal, &anchors, properties);
+            }
+        }
+        Ok(())
+    }
+
     /// Gives the euclidean distance between two traversals
     /// While working on spherical coordinates, this usually doesn’t make much sense,
     /// this is good enough to sort curves by distance
@@ -529,6 +1929,11 @@ impl<'fbb> Builder<'fbb> {
         self.nodes_ids[node_index].clone()
     }
 
+    /// Returns the number of nodes added so far
+    pub fn get_node_count(&self) -> usize {
+        self.nodes_coords.len()
+    }
+
     /// Orient the traversal according to two points
     ///
     /// In the end, the first coordinate must be closer to the beginning than the second
@@ -553,15 +1958,40 @@ impl<'fbb> Builder<'fbb> {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use crate::events::PropertyValue;
     use crate::lrm_scale::LrmScaleMeasure;
+    use crate::lrs::{
+        AnyLrs, LrmHandle, Lrs, LrsBase, LrsError, NodeHandle, TraversalEnd, TraversalHandle,
+    };
 
     use super::*;
     use approx::assert_relative_eq;
     use geo::{coord, point};
 
+    // The builder writes geographic coordinates
+    pub(crate) fn geographic(lrs: &ExtLrs) -> &Lrs<SphericalLineStringCurve> {
+        match &lrs.lrs {
+            AnyLrs::Geographic(lrs) => lrs,
+            AnyLrs::Schematic(_) => panic!("the builder writes geographic coordinates"),
+        }
+    }
+
     pub fn build_traversal(builder: &mut Builder) -> usize {
-        let s1 = builder.add_segment("s1", &[coord! {x: 0., y: 0.}, coord! {x:1., y: 0.}], 0, 1);
-        let s2 = builder.add_segment("s2", &[coord! {x: 1., y: 0.}, coord! {x:2., y: 0.}], 1, 2);
+        let n: Vec<_> = (0..3)
+            .map(|i| builder.add_node(&format!("n{i}"), coord! {x: i as f64, y: 0.}, properties!()))
+            .collect();
+        let s1 = builder.add_segment(
+            "s1",
+            &[coord! {x: 0., y: 0.}, coord! {x:1., y: 0.}],
+            n[0],
+            n[1],
+        );
+        let s2 = builder.add_segment(
+            "s2",
+            &[coord! {x: 1., y: 0.}, coord! {x:2., y: 0.}],
+            n[1],
+            n[2],
+        );
         let sot1 = super::SegmentOfTraversal {
             segment_index: s1,
             reversed: false,
@@ -573,6 +2003,45 @@ pub(crate) mod tests {
         builder.add_traversal("traversal", &[sot1, sot2])
     }
 
+    // The lrm `lrm` on the traversal of `build_traversal`, with the anchors `0` and `1` a kilometer apart,
+    // a point event and a linear event, for the tests of the export formats
+    #[cfg(any(feature = "geopackage", feature = "geoparquet", feature = "kml"))]
+    pub(crate) fn geographic_lrs() -> Lrs<SphericalLineStringCurve> {
+        let mut builder = Builder::new();
+        let traversal = build_traversal(&mut builder);
+        let anchors = [("0", 0.), ("1", 1.)].map(|(name, x)| AnchorOnLrm {
+            anchor_index: builder.add_anchor(
+                name,
+                Some(name),
+                coord! {x: x, y: 0.},
+                properties!("kind" => "<post>"),
+            ),
+            distance_along_lrm: x * 1000.,
+        });
+        builder.add_lrm("lrm", traversal, &anchors, properties!());
+        builder.add_point_event(
+            "signal",
+            0,
+            &LrmScaleMeasure::new("0", 500.),
+            HashMap::new(),
+        );
+        builder.add_linear_event(
+            "bridge",
+            0,
+            &LrmScaleMeasure::new("0", 200.),
+            &LrmScaleMeasure::new("1", 100.),
+            HashMap::new(),
+        );
+        Lrs::from_bytes(builder.build_data(properties!())).unwrap()
+    }
+
+    /// Adds `count` nodes, for the tests where their position does not matter.
+    pub(crate) fn add_nodes(builder: &mut Builder, count: usize) {
+        for i in 0..count {
+            builder.add_node(&format!("n{i}"), coord! {x: 0., y: 0.}, properties!());
+        }
+    }
+
     #[test]
     fn traversal_nodes_order() {
         // Nominal case
@@ -659,7 +2128,7 @@ pub(crate) mod tests {
         };
         b.add_lrm("lrm", traversal, &[aol, aol2], properties!());
 
-        b.add_node("a", coord! {x:0., y:1.}, properties!("source" => "test"));
+        let a = b.add_node("a", coord! {x:0., y:1.}, properties!("source" => "test"));
         b.add_node("b", coord! {x:1., y:1.}, properties!());
 
         let lrs = b.build_lrs(properties!()).unwrap();
@@ -675,11 +2144,12 @@ pub(crate) mod tests {
         assert_relative_eq!(lrm.x(), 0.);
         assert_relative_eq!(lrm.y(), 0.);
 
-        assert_eq!(lrs.lrs.nodes.len(), 2);
-        assert_eq!(lrs.lrs.nodes[0].properties["source"], "test");
+        // The three nodes of the traversal and the two added ones
+        assert_eq!(geographic(&lrs).nodes.len(), 5);
+        assert_eq!(geographic(&lrs).nodes[a].properties["source"], "test");
 
-        assert_eq!(lrs.lrs.segments.len(), 2);
-        assert_eq!(lrs.lrs.segments[0].id, "s1");
+        assert_eq!(geographic(&lrs).segments.len(), 2);
+        assert_eq!(geographic(&lrs).segments[0].id, "s1");
     }
 
     #[test]
@@ -694,7 +2164,799 @@ pub(crate) mod tests {
         );
         let lrs = b.build_lrs(properties!()).unwrap();
 
-        let p = lrs.lrm_properties(0);
+        let p = lrs.lrm_properties(0).unwrap();
         assert_eq!(p["test_key"], "test_value")
     }
+
+    #[test]
+    fn build_info() {
+        let mut b = Builder::new();
+        build_traversal(&mut b);
+        b.set_build_option("lrm_tag", "ref");
+        b.add_source("extract.osm.pbf");
+        b.set_crs("EPSG:4326");
+        let lrs = b.build_lrs(properties!()).unwrap();
+        assert_eq!(geographic(&lrs).crs.as_deref(), Some("EPSG:4326"));
+
+        let build_info = geographic(&lrs).build_info().unwrap();
+        assert_eq!(build_info.liblrs_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(build_info.options["lrm_tag"], "ref");
+        assert_eq!(build_info.sources, vec!["extract.osm.pbf".to_owned()]);
+    }
+
+    #[test]
+    fn coordinate_precision() {
+        let geometry: Vec<_> = (0..100)
+            .map(|i| coord! {x: 2.123456789 + i as f64 * 1e-3, y: 48.8})
+            .collect();
+        let build = |precision| {
+            let mut b = Builder::new();
+            add_nodes(&mut b, 2);
+            let segment_index = b.add_segment("s", &geometry, 0, 1);
+            b.add_traversal(
+                "traversal",
+                &[SegmentOfTraversal {
+                    segment_index,
+                    reversed: false,
+                }],
+            );
+            b.set_coordinate_precision(precision);
+            b.build_data(properties!()).to_vec()
+        };
+
+        let exact = build(0.);
+        let quantized = build(1e-7);
+        assert!(quantized.len() < exact.len());
+        let lrs = ExtLrs::load(&quantized).unwrap();
+        let read = &geographic(&lrs).segments[0].geometry;
+        assert_eq!(read.0.len(), geometry.len());
+        for (read, written) in read.0.iter().zip(&geometry) {
+            assert_relative_eq!(read.x, written.x, epsilon = 1e-7);
+            assert_relative_eq!(read.y, written.y, epsilon = 1e-7);
+        }
+        let curve = &*geographic(&lrs).traversals[0].curve;
+        assert_relative_eq!(curve.as_linestring().0[99].x, 2.222456789, epsilon = 1e-7);
+
+        // Written again, the coordinates stay quantized
+        let rewritten = ExtLrs::load(&lrs.to_bytes()).unwrap();
+        assert_eq!(geographic(&rewritten).coordinate_precision, 1e-7);
+        assert_eq!(
+            geographic(&rewritten).segments[0].geometry,
+            geographic(&lrs).segments[0].geometry
+        );
+
+        // The coordinates do not fit in 32 bits: the exact geometry is kept
+        let lrs = ExtLrs::load(&build(1e-12)).unwrap();
+        assert_eq!(geographic(&lrs).segments[0].geometry.0, geometry);
+    }
+
+    #[test]
+    fn missing_geometry() {
+        let mut b = Builder::new();
+        build_traversal(&mut b);
+        let without_geometry = b.add_traversal("without geometry", &[]);
+        let a = b.add_projected_anchor("a", Some("a"), 0., properties!());
+        let c = b.add_projected_anchor("c", Some("c"), 1., properties!());
+        b.add_lrm(
+            "lrm",
+            without_geometry,
+            &[
+                AnchorOnLrm {
+                    anchor_index: a,
+                    distance_along_lrm: 0.,
+                },
+                AnchorOnLrm {
+                    anchor_index: c,
+                    distance_along_lrm: 1000.,
+                },
+            ],
+            properties!(),
+        );
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let measure = LrmScaleMeasure::new("a", 1200.);
+        assert_eq!(
+            lrs.resolve(0, &measure).unwrap_err(),
+            ExtLrsError::Lrs(LrsError::GeometryUnavailable)
+        );
+        assert!(lrs.get_lrm_geom(0).is_err());
+        assert!(lrs.lookup_lrms(point! {x: 0.5, y: 0.}).is_empty());
+
+        let lrm = &geographic(&lrs).lrms[0];
+        let position = lrm.scale.locate_point(&measure).unwrap();
+        let normalized = lrm.scale.locate_anchor(position).unwrap();
+        assert_eq!(normalized.anchor_name, "c");
+        assert_relative_eq!(normalized.scale_offset, 200.);
+    }
+
+    #[test]
+    fn fragment_index() {
+        // An L-shaped traversal: its bounding box is much larger than its fragments
+        let mut b = Builder::new();
+        let horizontal = (0..16).map(|i| coord! {x: i as f64 * 0.01, y: 0.});
+        let vertical = (1..16).map(|i| coord! {x: 0.15, y: i as f64 * 0.01});
+        let coords: Vec<_> = horizontal.chain(vertical).collect();
+        add_nodes(&mut b, 2);
+        let segment_index = b.add_segment("s", &coords, 0, 1);
+        b.add_traversal(
+            "l",
+            &[super::SegmentOfTraversal {
+                segment_index,
+                reversed: false,
+            }],
+        );
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let index = geographic(&lrs).fragment_index.as_ref().unwrap();
+        assert_eq!(index.traversals.len(), 2);
+        assert_eq!(
+            geographic(&lrs).traversals_containing(point! {x: 0.05, y: 0.}),
+            [TraversalHandle(0)]
+        );
+        assert_eq!(
+            geographic(&lrs).traversals_containing(point! {x: 0.15, y: 0.1}),
+            [TraversalHandle(0)]
+        );
+        assert!(
+            geographic(&lrs)
+                .traversals_containing(point! {x: 0.01, y: 0.14})
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn connections() {
+        // Three traversals meeting at node 1, like a railway switch
+        let mut b = Builder::new();
+        add_nodes(&mut b, 4);
+        let traversal = |b: &mut Builder, id, end_node, x, y| {
+            let segment_index = b.add_segment(
+                id,
+                &[coord! {x: 0., y: 0.}, coord! {x: x, y: y}],
+                1,
+                end_node,
+            );
+            b.add_traversal(
+                id,
+                &[super::SegmentOfTraversal {
+                    segment_index,
+                    reversed: false,
+                }],
+            )
+        };
+        let main = traversal(&mut b, "main", 0, -1., 0.);
+        b.reverse(main);
+        let straight = traversal(&mut b, "straight", 2, 1., 0.);
+        let diverging = traversal(&mut b, "diverging", 3, 1., 0.1);
+
+        b.add_connection(1, main, straight, true).unwrap();
+        b.add_connection(1, main, diverging, false).unwrap();
+        b.add_connection(1, main, diverging, false).unwrap();
+        assert_eq!(
+            b.add_connection(0, straight, diverging, true),
+            Err(BuilderError::ConnectionNotAtExtremity {
+                node_index: 0,
+                traversal_id: "straight".to_owned()
+            })
+        );
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(b.build_data(properties!())).unwrap();
+
+        assert_eq!(lrs.connections_at(NodeHandle(1)).len(), 2);
+        assert!(lrs.connections_at(NodeHandle(0)).is_empty());
+        assert_eq!(
+            lrs.connections_from(TraversalHandle(main), TraversalEnd::End),
+            [
+                (TraversalHandle(straight), TraversalEnd::Start),
+                (TraversalHandle(diverging), TraversalEnd::Start)
+            ]
+        );
+        assert_eq!(
+            lrs.connections_from(TraversalHandle(straight), TraversalEnd::Start),
+            [(TraversalHandle(main), TraversalEnd::End)]
+        );
+        assert!(
+            lrs.connections_from(TraversalHandle(diverging), TraversalEnd::Start)
+                .is_empty()
+        );
+
+        // The switch is not connected: the branches cannot be connected together
+        b = Builder::new();
+        add_nodes(&mut b, 4);
+        traversal(&mut b, "main", 0, -1., 0.);
+        traversal(&mut b, "straight", 2, 1., 0.);
+        traversal(&mut b, "diverging", 3, 1., 0.1);
+        b.connect_traversal_extremities();
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(b.build_data(properties!())).unwrap();
+        assert!(lrs.connections_at(NodeHandle(1)).is_empty());
+
+        b = Builder::new();
+        add_nodes(&mut b, 4);
+        let main = traversal(&mut b, "main", 0, -1., 0.);
+        let straight = traversal(&mut b, "straight", 2, 1., 0.);
+        b.add_connection(1, straight, main, false).unwrap();
+        b.connect_traversal_extremities();
+        b.connect_traversal_extremities();
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(b.build_data(properties!())).unwrap();
+        assert_eq!(
+            lrs.connections_from(TraversalHandle(main), TraversalEnd::Start),
+            [(TraversalHandle(straight), TraversalEnd::Start)]
+        );
+        assert_eq!(lrs.connections_at(NodeHandle(1)).len(), 1);
+    }
+
+    #[test]
+    fn transform_at_load() {
+        let mut b = Builder::new();
+        let traversal = build_traversal(&mut b);
+        let anchor_index = b.add_anchor("a", Some("a"), coord! {x: 0., y: 0.}, properties!());
+        b.add_lrm(
+            "lrm",
+            traversal,
+            &[AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm: 0.,
+            }],
+            properties!(),
+        );
+        let node = b.add_node("n", coord! {x: 1., y: 1.}, properties!());
+        let shift = |c: Coord| coord! {x: c.x + 1., y: c.y};
+        let lrs = crate::lrs::Lrs::<SphericalLineStringCurve>::from_bytes_with_transform(
+            b.build_data(properties!()),
+            shift,
+        )
+        .unwrap();
+
+        let linestring = lrs.get_linestring(TraversalHandle(0)).unwrap();
+        assert_eq!(linestring.0[0], coord! {x: 1., y: 0.});
+        assert_eq!(lrs.nodes[node].geometry, Some(point! {x: 2., y: 1.}));
+        assert_eq!(
+            lrs.lrms[0].scale.anchors[0].point(),
+            Some(point! {x: 1., y: 0.})
+        );
+        assert!(lrs.rtree_data.is_none());
+        assert_eq!(
+            lrs.traversals_containing(point! {x: 2.5, y: 0.}),
+            [TraversalHandle(0)]
+        );
+    }
+
+    #[test]
+    fn traversal_properties() {
+        let mut b = Builder::new();
+        let traversal = build_traversal(&mut b);
+        b.set_traversal_properties(traversal, properties!("operator" => "SNCF"));
+        let start = b.add_anchor("a", Some("a"), coord! {x: 0., y: 0.}, properties!());
+        let anchors = [AnchorOnLrm {
+            anchor_index: start,
+            distance_along_lrm: 0.,
+        }];
+        b.add_lrm("lrm", traversal, &anchors, properties!("line" => "1"));
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        assert_eq!(lrs.traversal_properties(0).unwrap()["operator"], "SNCF");
+        assert_eq!(lrs.lrm_properties(0).unwrap()["line"], "1");
+        assert!(lrs.traversal_properties(1).is_err());
+    }
+
+    #[test]
+    fn events() {
+        let mut b = Builder::new();
+        let traversal = build_traversal(&mut b);
+        let start = b.add_anchor("a", Some("a"), coord! {x: 0., y: 0.}, properties!());
+        let anchors = [AnchorOnLrm {
+            anchor_index: start,
+            distance_along_lrm: 0.,
+        }];
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+        b.add_point_event(
+            "signal",
+            0,
+            &LrmScaleMeasure::new("a", 100.),
+            HashMap::from([
+                ("kind".to_owned(), PropertyValue::Text("main".to_owned())),
+                ("aspects".to_owned(), PropertyValue::Integer(3)),
+            ]),
+        );
+        b.add_linear_event(
+            "speed restriction",
+            0,
+            &LrmScaleMeasure::new("a", 200.),
+            &LrmScaleMeasure::new("a", 500.),
+            HashMap::from([
+                ("maxspeed".to_owned(), PropertyValue::Float(40.)),
+                ("temporary".to_owned(), PropertyValue::Boolean(true)),
+            ]),
+        );
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let point_events = lrs.point_events(0).unwrap();
+        assert_eq!(point_events.len(), 1);
+        assert_eq!(point_events[0].id, "signal");
+        assert_eq!(point_events[0].lrm, LrmHandle(0));
+        assert_eq!(point_events[0].measure.scale_offset, 100.);
+        assert_eq!(
+            point_events[0].properties["kind"],
+            PropertyValue::Text("main".to_owned())
+        );
+        assert_eq!(
+            point_events[0].properties["aspects"],
+            PropertyValue::Integer(3)
+        );
+
+        let linear_events = lrs.linear_events(0).unwrap();
+        assert_eq!(linear_events.len(), 1);
+        assert_eq!(linear_events[0].begin.anchor_name, "a");
+        assert_eq!(linear_events[0].end.scale_offset, 500.);
+        assert_eq!(
+            linear_events[0].properties["maxspeed"],
+            PropertyValue::Float(40.)
+        );
+        assert_eq!(linear_events[0].properties["temporary"].to_string(), "true");
+
+        assert_eq!(lrs.point_events(1).err(), Some(ExtLrsError::LrmNotFound(1)));
+    }
+
+    #[test]
+    fn alternate_traversals() {
+        use crate::lrs::{Lrs, LrsBase};
+
+        let mut b = Builder::new();
+        let mut track = |id: &str, y: f64| {
+            let geometry = [coord! {x: 0., y: y}, coord! {x: 0.01, y: y}];
+            let start = b.add_node(&format!("{id} start"), geometry[0], properties!());
+            let end = b.add_node(&format!("{id} end"), geometry[1], properties!());
+            let segment_index = b.add_segment(id, &geometry, start, end);
+            b.add_traversal(
+                id,
+                &[SegmentOfTraversal {
+                    segment_index,
+                    reversed: false,
+                }],
+            )
+        };
+        let track_1 = track("track 1", 0.);
+        let track_2 = track("track 2", 0.0001);
+        let start = b.add_anchor("0", Some("0"), coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_anchor("1", Some("1"), coord! {x: 0.01, y: 0.}, properties!());
+        let anchors = [(start, 0.), (end, 1000.)].map(|(anchor_index, distance)| AnchorOnLrm {
+            anchor_index,
+            distance_along_lrm: distance,
+        });
+        b.add_lrm_with_alternates("line", track_1, &[track_2], &anchors, properties!());
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(b.build_data(properties!())).unwrap();
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(&lrs.to_bytes()).unwrap();
+        assert_eq!(lrs.lrms[0].alternate_traversals, [TraversalHandle(1)]);
+        assert_eq!(lrs.traversals[1].lrms, [LrmHandle(0)]);
+
+        let projection = lrs
+            .lookup(point! {x: 0.005, y: 0.00011}, LrmHandle(0))
+            .unwrap();
+        assert_eq!(projection.traversal, TraversalHandle(1));
+        assert_eq!(projection.measure.measure.anchor_name, "0");
+        assert_relative_eq!(
+            projection.measure.measure.scale_offset,
+            500.,
+            epsilon = 1e-3
+        );
+        assert_eq!(lrs.lookup_lrms(point! {x: 0.005, y: 0.00011}).len(), 1);
+
+        let measure = LrmScaleMeasure::new("0", 500.);
+        let on_track_2 = lrs
+            .resolve_on(LrmHandle(0), &measure, TraversalHandle(1))
+            .unwrap();
+        assert_relative_eq!(on_track_2.x(), 0.005, epsilon = 1e-6);
+        assert_relative_eq!(on_track_2.y(), 0.0001, epsilon = 1e-9);
+        assert_eq!(
+            lrs.resolve_on(LrmHandle(0), &measure, TraversalHandle(2)),
+            Err(LrsError::TraversalNotInLrm)
+        );
+    }
+
+    #[test]
+    fn build_bytes() {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 1., y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 1., y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        let anchor_index = b.add_anchor("0", Some("0"), coord! {x: 0., y: 0.}, properties!());
+        let anchors = [AnchorOnLrm {
+            anchor_index,
+            distance_along_lrm: 0.,
+        }];
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+
+        let lrs = ExtLrs::load(&b.build_bytes(properties!()).unwrap()).unwrap();
+        assert_eq!(geographic(&lrs).segments[0].id, "t");
+        assert_eq!(geographic(&lrs).traversals[0].id, "t");
+        assert_eq!(lrs.get_lrm_geom(0).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn build_bytes_errors() {
+        // The segment references nodes that were never added
+        let mut b = Builder::new();
+        let segment_index =
+            b.add_segment("s1", &[coord! {x: 0., y: 0.}, coord! {x:1., y: 0.}], 0, 1);
+        b.add_traversal(
+            "traversal",
+            &[SegmentOfTraversal {
+                segment_index,
+                reversed: false,
+            }],
+        );
+        assert_eq!(
+            b.build_bytes(properties!()),
+            Err(BuilderError::UnknownNode {
+                segment_id: "s1".to_owned(),
+                node_index: 0
+            })
+        );
+
+        let mut b = Builder::new();
+        let node = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}]);
+        b.add_traversal_from_geometry("t", &geometry, node, node);
+        assert_eq!(
+            b.build_bytes(properties!()),
+            Err(BuilderError::InvalidGeometry("t".to_owned()))
+        );
+
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 1., y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 1., y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        let anchor_index = b.add_anchor("0", None, coord! {x: 0., y: 0.}, properties!());
+        let anchors = [AnchorOnLrm {
+            anchor_index,
+            distance_along_lrm: 0.,
+        }];
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+        assert_eq!(
+            b.build_bytes(properties!()),
+            Err(BuilderError::NoNamedAnchor("lrm".to_owned()))
+        );
+
+        // The anchors can not be projected on a traversal with a single coordinate
+        let mut b = Builder::new();
+        let node = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, node, node);
+        let anchor_index = b.add_anchor("0", Some("0"), coord! {x: 0., y: 0.}, properties!());
+        let anchors = [AnchorOnLrm {
+            anchor_index,
+            distance_along_lrm: 0.,
+        }];
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+        assert_eq!(
+            b.build_bytes(properties!()),
+            Err(BuilderError::InvalidGeometry("t".to_owned()))
+        );
+
+        let mut b = Builder::new();
+        let traversal = build_traversal(&mut b);
+        b.add_lrm("unknown traversal", traversal + 1, &anchors, properties!());
+        assert_eq!(
+            b.build_bytes(properties!()),
+            Err(BuilderError::UnknownLrmTraversal {
+                lrm_id: "unknown traversal".to_owned(),
+                traversal_index: traversal + 1
+            })
+        );
+
+        let mut b = Builder::new();
+        let traversal = build_traversal(&mut b);
+        b.add_lrm("unknown anchor", traversal, &anchors, properties!());
+        assert_eq!(
+            b.build_bytes(properties!()),
+            Err(BuilderError::UnknownAnchor {
+                lrm_id: "unknown anchor".to_owned(),
+                anchor_index
+            })
+        );
+        assert_eq!(
+            b.generate_anchors("unknown anchor", traversal, &anchors, 1000.)
+                .err(),
+            Some(BuilderError::UnknownAnchor {
+                lrm_id: "unknown anchor".to_owned(),
+                anchor_index
+            })
+        );
+        assert_eq!(
+            b.generate_anchors("unknown traversal", traversal + 1, &anchors, 1000.)
+                .err(),
+            Some(BuilderError::UnknownLrmTraversal {
+                lrm_id: "unknown traversal".to_owned(),
+                traversal_index: traversal + 1
+            })
+        );
+        assert_eq!(b.get_nodes_of_traversal(traversal + 1), None);
+
+        // The unknown segment is only reported when building
+        let mut b = Builder::new();
+        let unknown = SegmentOfTraversal {
+            segment_index: 0,
+            reversed: false,
+        };
+        let traversal = b.add_traversal("t", &[unknown]);
+        assert_eq!(b.get_nodes_of_traversal(traversal), Some(&[][..]));
+        assert_eq!(
+            b.build_bytes(properties!()),
+            Err(BuilderError::UnknownSegment {
+                traversal_id: "t".to_owned(),
+                segment_index: 0
+            })
+        );
+    }
+
+    #[test]
+    fn build_with_report() {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 0.01, y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 0.01, y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        let first = b.add_anchor("0", Some("0"), coord! {x: 0., y: 0.}, properties!());
+        // About 1 km north of the traversal
+        let last = b.add_anchor("1", Some("1"), coord! {x: 0.01, y: 0.01}, properties!());
+        let anchors = [
+            AnchorOnLrm {
+                anchor_index: first,
+                distance_along_lrm: 0.,
+            },
+            AnchorOnLrm {
+                anchor_index: last,
+                distance_along_lrm: 1110.,
+            },
+        ];
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+
+        let (data, report) = b
+            .build_with_report(properties!(), &ValidationOptions::default())
+            .unwrap();
+        assert!(ExtLrs::load(&data).is_ok());
+        assert!(!report.has_errors());
+        assert!(matches!(
+            report.issues[..],
+            [crate::validation::ValidationIssue::AnchorTooFar {
+                anchor_index: 1,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn add_surveyed_lrm() {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 0.02, y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 0.02, y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        let anchor = |name: &str, x, y| SurveyedAnchor {
+            name: name.to_owned(),
+            coord: coord! {x: x, y: y},
+            properties: properties!(),
+        };
+        let anchors = [
+            anchor("1", 0.01, 0.0001),
+            anchor("0", 0., 0.),
+            // About 1 km from the traversal
+            anchor("2", 0.02, 0.01),
+        ];
+        assert_eq!(
+            b.add_surveyed_lrm("lrm", traversal + 1, &anchors, 50., properties!())
+                .err(),
+            Some(BuilderError::UnknownLrmTraversal {
+                lrm_id: "lrm".to_owned(),
+                traversal_index: traversal + 1
+            })
+        );
+        let report = b
+            .add_surveyed_lrm("lrm", traversal, &anchors, 50., properties!())
+            .unwrap();
+        assert_eq!(report.distances[1], 0.);
+        assert_relative_eq!(report.distances[0], 1113., epsilon = 1.);
+        assert_eq!(report.intervals.len(), 2);
+        assert_relative_eq!(report.intervals[1], 1113., epsilon = 1.);
+        assert_eq!(report.far_anchors.len(), 1);
+        assert_eq!(report.far_anchors[0].0, 2);
+
+        let lrs = b.build_lrs(properties!()).unwrap();
+        let point = lrs.resolve(0, &LrmScaleMeasure::new("1", 0.)).unwrap();
+        assert_relative_eq!(point.x(), 0.01, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn generate_anchors() {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 0.05, y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 0.05, y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        let first = b.add_anchor("begin", Some("begin"), coord! {x: 0., y: 0.}, properties!());
+        let last = b.add_projected_anchor("end", Some("end"), 1., properties!());
+        let anchors = [
+            AnchorOnLrm {
+                anchor_index: last,
+                distance_along_lrm: 5500.,
+            },
+            AnchorOnLrm {
+                anchor_index: first,
+                distance_along_lrm: 0.,
+            },
+        ];
+        let anchors = b
+            .generate_anchors("lrm", traversal, &anchors, 1000.)
+            .unwrap();
+        assert_eq!(anchors.len(), 7);
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+
+        let lrs = b.build_lrs(properties!()).unwrap();
+        let scale = &geographic(&lrs).lrms[0].scale;
+        assert_eq!(scale.anchors.len(), 7);
+        assert_eq!(scale.anchors[3].scale_position(), 3000.);
+        assert_relative_eq!(scale.anchors[3].curve_position(), 3. / 5.5);
+        let point = lrs.resolve(0, &LrmScaleMeasure::new("3", 0.)).unwrap();
+        assert_relative_eq!(point.x(), 0.05 * 3. / 5.5, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn validity() {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 0.01, y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 0.01, y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+
+        // Before 2020, the kilometrage started a bit after the start of the traversal
+        for (validity, origin) in [
+            (Validity::new(None, Some("2020-01-01")).unwrap(), 0.1),
+            (Validity::new(Some("2020-01-01"), None).unwrap(), 0.),
+        ] {
+            b.set_validity(validity).unwrap();
+            let anchors = [("0", origin), ("1", 1.)].map(|(name, position)| AnchorOnLrm {
+                anchor_index: b.add_projected_anchor(name, Some(name), position, properties!()),
+                distance_along_lrm: (position - origin) * 1000.,
+            });
+            b.add_lrm("line", traversal, &anchors, properties!());
+        }
+        b.add_point_event(
+            "signal",
+            1,
+            &LrmScaleMeasure::new("0", 500.),
+            HashMap::new(),
+        );
+        let data = b.build_data(properties!()).to_vec();
+
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(&data).unwrap();
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(&lrs.to_bytes()).unwrap();
+        assert_eq!(lrs.get_lrm_at("line", "2019-06-01"), Ok(Some(LrmHandle(0))));
+        assert_eq!(lrs.get_lrm_at("line", "2020-01-01"), Ok(Some(LrmHandle(1))));
+        assert_eq!(lrs.lrms_at("2021-01-01").unwrap().count(), 1);
+
+        let measure = LrmScaleMeasure::new("0", 0.);
+        let old = lrs
+            .resolve_at(LrmHandle(0), &measure, "2019-06-01")
+            .unwrap();
+        let new = lrs
+            .resolve_at(LrmHandle(1), &measure, "2021-01-01")
+            .unwrap();
+        assert!(old.x() > new.x());
+        // The anchors of the new LRM are not valid before 2020
+        assert!(
+            lrs.resolve_at(LrmHandle(1), &measure, "2019-06-01")
+                .is_err()
+        );
+
+        let events = |date| lrs.point_events_at(LrmHandle(1), date).map(Iterator::count);
+        assert_eq!(events("2021-01-01"), Ok(1));
+        assert_eq!(events("2019-06-01"), Ok(0));
+
+        // The dates are only compared as strings in the ISO 8601 format
+        let invalid = || LrsError::InvalidDate("2020-1-5".to_owned());
+        assert_eq!(lrs.get_lrm_at("line", "2020-1-5"), Err(invalid()));
+        assert_eq!(events("2020-1-5"), Err(invalid()));
+        assert!(lrs.lrm_at(LrmHandle(0), "2020-1-5").is_err());
+        assert!(!lrs.lrms[0].validity.contains("2020-1-5"));
+        assert!(Validity::new(Some("05/01/2020"), None).is_err());
+        let validity = Validity {
+            from: Some("2020-01-05T00:00".to_owned()),
+            to: None,
+        };
+        assert_eq!(
+            b.set_validity(validity).err(),
+            Some(BuilderError::InvalidDate("2020-01-05T00:00".to_owned()))
+        );
+    }
+
+    #[test]
+    fn clean_topology() {
+        let mut b = Builder::new();
+        let coords = [
+            (0., 0.),
+            (0.01, 0.),
+            // About 1 mm from the previous node
+            (0.010_000_01, 0.),
+            (0.02, 0.),
+            // About 10 cm from the second traversal
+            (0.015, 0.000_001),
+            (0.015, 0.01),
+        ];
+        for (i, &(x, y)) in coords.iter().enumerate() {
+            b.add_node(&i.to_string(), coord! {x: x, y: y}, properties!());
+        }
+        for (id, start, end) in [("a", 0, 1), ("b", 2, 3), ("c", 4, 5)] {
+            let geometry = LineString::from(vec![coords[start], coords[end]]);
+            b.add_traversal_from_geometry(id, &geometry, start, end);
+        }
+
+        let report = b.clean_topology(0.5);
+        assert_eq!(report.snapped_nodes, [(2, 1)]);
+        assert_eq!(report.split_segments, [(1, 4, 3)]);
+        assert_eq!(report.added_connections, 1);
+        assert_eq!(b.get_nodes_of_traversal(1), Some(&[1, 4, 3][..]));
+        assert_eq!(b.clean_topology(0.5), TopologyReport::default());
+
+        let lrs = b.build_lrs(properties!()).unwrap();
+        assert_eq!(geographic(&lrs).connections.len(), 1);
+        assert_eq!(geographic(&lrs).traversals[1].id, "b");
+        let geometry = geographic(&lrs).traversals[1].curve.as_linestring();
+        assert_eq!(geometry.0[0], coord! {x: 0.01, y: 0.});
+        assert!(geometry.0.contains(&coord! {x: 0.015, y: 0.000_001}));
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn from_geojson() {
+        let features = |json: &str| match json.parse::<geojson::GeoJson>().unwrap() {
+            geojson::GeoJson::FeatureCollection(collection) => collection.features,
+            _ => unreachable!(),
+        };
+        let json = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"ref": "a", "gauge": 1435},
+             "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 0]]}},
+            {"type": "Feature", "id": "b", "properties": {},
+             "geometry": {"type": "LineString", "coordinates": [[1, 0], [2, 0]]}},
+            {"type": "Feature", "properties": {"line": "a", "km": "0", "pk": 0},
+             "geometry": {"type": "Point", "coordinates": [0, 0]}},
+            {"type": "Feature", "properties": {"line": "b", "km": "10", "pk": 10000, "kind": "post"},
+             "geometry": {"type": "Point", "coordinates": [2, 0]}}
+        ]}"#;
+        let mapping = GeoJsonMapping {
+            traversal_id: "ref".to_owned(),
+            lrm_id: "line".to_owned(),
+            anchor_name: "km".to_owned(),
+            measure: "pk".to_owned(),
+        };
+        let lrs = Builder::from_geojson(&features(json), &mapping)
+            .unwrap()
+            .build_lrs(properties!())
+            .unwrap();
+        assert_eq!(geographic(&lrs).traversals[0].id, "a");
+        assert_eq!(geographic(&lrs).traversals[1].id, "b");
+        assert_eq!(geographic(&lrs).traversals[0].properties["gauge"], "1435");
+        assert_eq!(geographic(&lrs).nodes.len(), 3);
+        // `b` continues `a`
+        assert_eq!(geographic(&lrs).connections.len(), 1);
+        assert_eq!(geographic(&lrs).connections[0].node, NodeHandle(1));
+        assert_eq!(geographic(&lrs).lrms[1].scale.id, "b");
+        let lrm_scale::Anchor::Named(anchor) = &geographic(&lrs).lrms[1].scale.anchors[0] else {
+            panic!("the anchor should be named");
+        };
+        assert_eq!(anchor.name, "10");
+        assert_eq!(anchor.properties["kind"], "post");
+
+        let json = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"lrm_id": "c", "measure": 0},
+             "geometry": {"type": "Point", "coordinates": [0, 0]}}
+        ]}"#;
+        assert_eq!(
+            Builder::from_geojson(&features(json), &GeoJsonMapping::default()).err(),
+            Some(BuilderError::UnknownLrm {
+                feature_index: 0,
+                lrm_id: "c".to_owned()
+            })
+        );
+    }
 }
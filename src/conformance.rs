@@ -0,0 +1,490 @@
+//! Golden dataset to check that every binding behaves the same way
+//!
+//! A case is an input and the expected output for a given LRS file.
+//! Each language layer (Python, WASM, C…) implements a thin [`ConformanceAdapter`]
+//! and runs the same cases with [`run`]. As long as all of them pass,
+//! all the layers return identical measures and offsets.
+//!
+//! The cases are stored as text, one case per line, with `;` separated fields:
+//!
+//! ```text
+//! # comments and empty lines are ignored
+//! resolve;<lrm id>;<anchor name>;<scale offset>;<expected x>;<expected y>
+//! lookup;<lrm id>;<x>;<y>;<expected anchor name>;<expected scale offset>;<expected orthogonal offset>
+//! ```
+//!
+//! The same text describes the network of the cases, see [`ConformanceNetwork`]:
+//!
+//! ```text
+//! traversal;<id>;<x> <y>,<x> <y>…
+//! anchor;<lrm id>;<anchor name>;<x>;<y>;<distance along the lrm>
+//! ```
+//!
+//! The golden dataset shared by all the bindings is [`GOLDEN_DATASET`].
+
+use geo::{Coord, Point, coord, point};
+use thiserror::Error;
+
+#[cfg(any(test, feature = "builder"))]
+use crate::builder::{AnchorOnLrm, Builder, BuilderError};
+use crate::lrm_scale::LrmScaleMeasure;
+use crate::lrs_ext::ExtLrs;
+#[cfg(any(test, feature = "builder"))]
+use crate::properties;
+
+/// The golden dataset that every binding must pass, with its network.
+pub const GOLDEN_DATASET: &str = include_str!("../tests/conformance/golden.txt");
+
+/// Errors when reading a golden dataset.
+#[derive(Error, Debug, PartialEq)]
+pub enum ConformanceError {
+    /// The kind of case (first field of the line) is not known.
+    #[error("line {0}: unknown case kind {1}")]
+    UnknownKind(usize, String),
+    /// The line does not have the expected number of fields.
+    #[error("line {0}: expected {1} fields, got {2}")]
+    WrongFieldCount(usize, usize, usize),
+    /// A numerical field could not be parsed.
+    #[error("line {0}: invalid number {1}")]
+    InvalidNumber(usize, String),
+    /// An anchor references an LRM whose traversal was not given before.
+    #[error("line {0}: unknown lrm {1}")]
+    UnknownLrm(usize, String),
+}
+
+/// A single input of the golden dataset with its expected output.
+#[derive(Clone, Debug)]
+pub enum ConformanceCase {
+    /// Resolves a measure to a geographical position.
+    Resolve {
+        /// `id` of the LRM on which the measure applies.
+        lrm_id: String,
+        /// The measure to resolve.
+        measure: LrmScaleMeasure,
+        /// The expected geographical position.
+        expected: Point,
+    },
+    /// Projects a geographical position on a LRM.
+    Lookup {
+        /// `id` of the LRM on which the point is projected.
+        lrm_id: String,
+        /// The point to project.
+        point: Point,
+        /// The expected measure.
+        expected: LrmScaleMeasure,
+        /// The expected distance between the point and the LRM.
+        expected_offset: f64,
+    },
+}
+
+/// An anchor of a [`ConformanceNetwork`].
+#[derive(Clone, Debug)]
+pub struct ConformanceAnchor {
+    /// `id` of the LRM of the anchor.
+    pub lrm_id: String,
+    /// Name of the anchor.
+    pub name: String,
+    /// Coordinates of the anchor.
+    pub coord: Coord,
+    /// Distance of the anchor along the LRM.
+    pub distance_along_lrm: f64,
+}
+
+/// The network on which the cases of a golden dataset are run.
+///
+/// Every traversal has an LRM with the same `id`.
+#[derive(Clone, Debug, Default)]
+pub struct ConformanceNetwork {
+    /// `id` and coordinates of every traversal.
+    pub traversals: Vec<(String, Vec<Coord>)>,
+    /// The anchors of all the LRMs.
+    pub anchors: Vec<ConformanceAnchor>,
+}
+
+#[cfg(any(test, feature = "builder"))]
+impl ConformanceNetwork {
+    /// Builds the LRS of the network, as an LRS file.
+    pub fn build(&self) -> Result<Vec<u8>, BuilderError> {
+        let mut builder = Builder::new();
+        for (id, coords) in &self.traversals {
+            let (Some(&first), Some(&last)) = (coords.first(), coords.last()) else {
+                return Err(BuilderError::InvalidGeometry(id.clone()));
+            };
+            let start = builder.add_node(&format!("{id} start"), first, properties!());
+            let end = builder.add_node(&format!("{id} end"), last, properties!());
+            let geometry = geo::LineString::new(coords.clone());
+            let traversal = builder.add_traversal_from_geometry(id, &geometry, start, end);
+            let anchors: Vec<_> = self
+                .anchors
+                .iter()
+                .filter(|anchor| &anchor.lrm_id == id)
+                .map(|anchor| AnchorOnLrm {
+                    anchor_index: builder.add_anchor(
+                        &format!("{id} {}", anchor.name),
+                        Some(&anchor.name),
+                        anchor.coord,
+                        properties!(),
+                    ),
+                    distance_along_lrm: anchor.distance_along_lrm,
+                })
+                .collect();
+            builder.add_lrm(id, traversal, &anchors, properties!());
+        }
+        builder.build_bytes(properties!())
+    }
+}
+
+/// What a binding must expose to run the golden dataset.
+///
+/// Implementations should call the public functions of the binding
+/// in order to exercise the type conversions of that layer.
+pub trait ConformanceAdapter {
+    /// Index of the LRM identified by its `id`.
+    fn find_lrm(&self, lrm_id: &str) -> Option<usize>;
+    /// Geographical position of a measure.
+    fn resolve(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> Result<Point, String>;
+    /// Measure and orthogonal offset of a point projected on a LRM.
+    fn lookup(&self, point: Point, lrm_index: usize) -> Result<(LrmScaleMeasure, f64), String>;
+}
+
+/// A case that did not return the expected result.
+#[derive(Clone, Debug)]
+pub struct ConformanceFailure {
+    /// Index of the case in the dataset.
+    pub case_index: usize,
+    /// Human readable description of the difference.
+    pub message: String,
+}
+
+/// The outcome of running a golden dataset.
+#[derive(Clone, Debug, Default)]
+pub struct ConformanceReport {
+    /// Number of cases that returned the expected result.
+    pub passed: usize,
+    /// All the cases that did not return the expected result.
+    pub failures: Vec<ConformanceFailure>,
+}
+
+impl ConformanceReport {
+    /// Every case returned the expected result.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+fn parse_number(line: usize, field: &str) -> Result<f64, ConformanceError> {
+    field
+        .trim()
+        .parse()
+        .map_err(|_| ConformanceError::InvalidNumber(line, field.to_owned()))
+}
+
+fn check_field_count(
+    line: usize,
+    fields: &[&str],
+    expected: usize,
+) -> Result<(), ConformanceError> {
+    if fields.len() == expected {
+        Ok(())
+    } else {
+        Err(ConformanceError::WrongFieldCount(
+            line,
+            expected,
+            fields.len(),
+        ))
+    }
+}
+
+// The fields of the lines that are not comments, with their line number
+fn records(data: &str) -> impl Iterator<Item = (usize, Vec<&str>)> {
+    data.lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| (line_number, line.split(';').collect()))
+}
+
+/// Reads the network of a golden dataset. See the module documentation for the format.
+pub fn parse_network(data: &str) -> Result<ConformanceNetwork, ConformanceError> {
+    let mut network = ConformanceNetwork::default();
+    for (line_number, fields) in records(data) {
+        match fields[0].trim() {
+            "traversal" => {
+                check_field_count(line_number, &fields, 3)?;
+                let coords = fields[2]
+                    .split(',')
+                    .map(
+                        |position| match position.split_whitespace().collect::<Vec<_>>()[..] {
+                            [x, y] => Ok(coord! {
+                                x: parse_number(line_number, x)?,
+                                y: parse_number(line_number, y)?,
+                            }),
+                            _ => Err(ConformanceError::InvalidNumber(
+                                line_number,
+                                position.to_owned(),
+                            )),
+                        },
+                    )
+                    .collect::<Result<_, _>>()?;
+                network
+                    .traversals
+                    .push((fields[1].trim().to_owned(), coords));
+            }
+            "anchor" => {
+                check_field_count(line_number, &fields, 6)?;
+                let lrm_id = fields[1].trim();
+                if !network.traversals.iter().any(|(id, _)| id == lrm_id) {
+                    return Err(ConformanceError::UnknownLrm(line_number, lrm_id.to_owned()));
+                }
+                network.anchors.push(ConformanceAnchor {
+                    lrm_id: lrm_id.to_owned(),
+                    name: fields[2].trim().to_owned(),
+                    coord: coord! {
+                        x: parse_number(line_number, fields[3])?,
+                        y: parse_number(line_number, fields[4])?,
+                    },
+                    distance_along_lrm: parse_number(line_number, fields[5])?,
+                });
+            }
+            "resolve" | "lookup" => {}
+            kind => return Err(ConformanceError::UnknownKind(line_number, kind.to_owned())),
+        }
+    }
+    Ok(network)
+}
+
+/// Reads the cases of a golden dataset. See the module documentation for the format.
+pub fn parse_cases(data: &str) -> Result<Vec<ConformanceCase>, ConformanceError> {
+    let mut cases = vec![];
+    for (line_number, fields) in records(data) {
+        match fields[0].trim() {
+            "resolve" => {
+                check_field_count(line_number, &fields, 6)?;
+                cases.push(ConformanceCase::Resolve {
+                    lrm_id: fields[1].trim().to_owned(),
+                    measure: LrmScaleMeasure::new(
+                        fields[2].trim(),
+                        parse_number(line_number, fields[3])?,
+                    ),
+                    expected: point! {
+                        x: parse_number(line_number, fields[4])?,
+                        y: parse_number(line_number, fields[5])?,
+                    },
+                });
+            }
+            "lookup" => {
+                check_field_count(line_number, &fields, 7)?;
+                cases.push(ConformanceCase::Lookup {
+                    lrm_id: fields[1].trim().to_owned(),
+                    point: point! {
+                        x: parse_number(line_number, fields[2])?,
+                        y: parse_number(line_number, fields[3])?,
+                    },
+                    expected: LrmScaleMeasure::new(
+                        fields[4].trim(),
+                        parse_number(line_number, fields[5])?,
+                    ),
+                    expected_offset: parse_number(line_number, fields[6])?,
+                });
+            }
+            "traversal" | "anchor" => {}
+            kind => return Err(ConformanceError::UnknownKind(line_number, kind.to_owned())),
+        }
+    }
+    Ok(cases)
+}
+
+fn run_case(
+    adapter: &impl ConformanceAdapter,
+    case: &ConformanceCase,
+    tolerance: f64,
+) -> Result<(), String> {
+    match case {
+        ConformanceCase::Resolve {
+            lrm_id,
+            measure,
+            expected,
+        } => {
+            let lrm_index = adapter
+                .find_lrm(lrm_id)
+                .ok_or(format!("unknown lrm {lrm_id}"))?;
+            let point = adapter.resolve(lrm_index, measure)?;
+            if (point.x() - expected.x()).abs() > tolerance
+                || (point.y() - expected.y()).abs() > tolerance
+            {
+                return Err(format!(
+                    "resolve on {lrm_id}: expected {expected:?}, got {point:?}"
+                ));
+            }
+        }
+        ConformanceCase::Lookup {
+            lrm_id,
+            point,
+            expected,
+            expected_offset,
+        } => {
+            let lrm_index = adapter
+                .find_lrm(lrm_id)
+                .ok_or(format!("unknown lrm {lrm_id}"))?;
+            let (measure, offset) = adapter.lookup(*point, lrm_index)?;
+            if measure.anchor_name != expected.anchor_name
+                || (measure.scale_offset - expected.scale_offset).abs() > tolerance
+                || (offset - expected_offset).abs() > tolerance
+            {
+                return Err(format!(
+                    "lookup on {lrm_id}: expected {expected:?} with offset {expected_offset}, got {measure:?} with offset {offset}"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Runs all the cases through the adapter.
+///
+/// Numerical values are considered equal if they differ by less than `tolerance`.
+pub fn run(
+    adapter: &impl ConformanceAdapter,
+    cases: &[ConformanceCase],
+    tolerance: f64,
+) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+    for (case_index, case) in cases.iter().enumerate() {
+        match run_case(adapter, case, tolerance) {
+            Ok(()) => report.passed += 1,
+            Err(message) => report.failures.push(ConformanceFailure {
+                case_index,
+                message,
+            }),
+        }
+    }
+    report
+}
+
+/// The reference implementation: other bindings must return the same values.
+impl ConformanceAdapter for ExtLrs {
+    fn find_lrm(&self, lrm_id: &str) -> Option<usize> {
+        self.get_lrm(lrm_id)
+    }
+
+    fn resolve(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> Result<Point, String> {
+        ExtLrs::resolve(self, lrm_index, measure).map_err(|e| e.to_string())
+    }
+
+    fn lookup(&self, point: Point, lrm_index: usize) -> Result<(LrmScaleMeasure, f64), String> {
+        ExtLrs::lookup(self, point, lrm_index)
+            .map(|projection| (projection.measure.measure, projection.orthogonal_offset))
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::coord;
+
+    use super::*;
+    use crate::builder::{AnchorOnLrm, Builder, SegmentOfTraversal};
+    use crate::properties;
+
+    fn lrs() -> ExtLrs {
+        let mut b = Builder::new();
+        let (start, end) = (coord! {x: 0., y: 0.}, coord! {x: 0.01, y: 0.});
+        let nodes = (
+            b.add_node("start", start, properties!()),
+            b.add_node("end", end, properties!()),
+        );
+        let segment_index = b.add_segment("s", &[start, end], nodes.0, nodes.1);
+        let traversal = b.add_traversal(
+            "t",
+            &[SegmentOfTraversal {
+                segment_index,
+                reversed: false,
+            }],
+        );
+        let a = b.add_anchor("a", Some("a"), coord! {x: 0., y: 0.}, properties!());
+        let c = b.add_anchor("b", Some("b"), coord! {x: 0.01, y: 0.}, properties!());
+        b.add_lrm(
+            "lrm",
+            traversal,
+            &[
+                AnchorOnLrm {
+                    anchor_index: a,
+                    distance_along_lrm: 0.,
+                },
+                AnchorOnLrm {
+                    anchor_index: c,
+                    distance_along_lrm: 1000.,
+                },
+            ],
+            properties!(),
+        );
+        b.build_lrs(properties!()).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let cases =
+            parse_cases("# a comment\n\nresolve;lrm;a;500;0.005;0\nlookup;lrm;0.005;0;a;500;0\n")
+                .unwrap();
+        assert_eq!(cases.len(), 2);
+
+        assert_eq!(
+            parse_cases("resolve;lrm;a;500").unwrap_err(),
+            ConformanceError::WrongFieldCount(1, 6, 4)
+        );
+        assert_eq!(
+            parse_cases("locate;lrm").unwrap_err(),
+            ConformanceError::UnknownKind(1, "locate".to_owned())
+        );
+        assert_eq!(
+            parse_cases("resolve;lrm;a;five;0;0").unwrap_err(),
+            ConformanceError::InvalidNumber(1, "five".to_owned())
+        );
+    }
+
+    #[test]
+    fn network() {
+        let network =
+            parse_network("traversal;t;0 0, 0.01 0\nanchor;t;a;0;0;0\nresolve;t;a;0;0;0").unwrap();
+        assert_eq!(network.traversals[0].1.len(), 2);
+        assert_eq!(network.anchors[0].name, "a");
+        assert_eq!(
+            parse_network("anchor;t;a;0;0;0").unwrap_err(),
+            ConformanceError::UnknownLrm(1, "t".to_owned())
+        );
+        assert_eq!(
+            parse_network("traversal;t;0 0,0.01").unwrap_err(),
+            ConformanceError::InvalidNumber(1, "0.01".to_owned())
+        );
+
+        // A traversal with a single coordinate is refused instead of projecting the anchors on it
+        let network = parse_network("traversal;t;0 0\nanchor;t;a;0;0;0").unwrap();
+        assert_eq!(
+            network.build().unwrap_err(),
+            BuilderError::InvalidGeometry("t".to_owned())
+        );
+    }
+
+    #[test]
+    fn golden_dataset() {
+        let network = parse_network(GOLDEN_DATASET).unwrap();
+        let lrs = ExtLrs::load(&network.build().unwrap()).unwrap();
+        let cases = parse_cases(GOLDEN_DATASET).unwrap();
+        let report = run(&lrs, &cases, 1e-6);
+        assert!(report.is_success(), "{:?}", report.failures);
+        assert_eq!(report.passed, cases.len());
+    }
+
+    #[test]
+    fn run_cases() {
+        let cases = parse_cases(
+            "resolve;lrm;a;500;0.005;0\nlookup;lrm;0.005;0;a;500;0\nresolve;unknown;a;0;0;0\nresolve;lrm;b;0;1;1",
+        )
+        .unwrap();
+        let report = run(&lrs(), &cases, 1e-6);
+        assert_eq!(report.passed, 2);
+        assert!(!report.is_success());
+        assert_eq!(report.failures[0].case_index, 2);
+        assert_eq!(report.failures[1].case_index, 3);
+    }
+}
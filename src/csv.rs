@@ -0,0 +1,185 @@
+//! Reads the kilometer posts listed in CSV files with [`Builder::add_anchors_from_csv`].
+
+use geo::Coord;
+
+use crate::builder::{AnchorOnLrm, Builder, BuilderError};
+use crate::properties;
+
+/// Names of the CSV columns read by [`Builder::add_anchors_from_csv`].
+#[derive(Clone, Debug)]
+pub struct CsvMapping {
+    /// Identifier of the LRM, and of the `Traversal` on which the `Anchor`s are projected.
+    pub lrm_id: String,
+    /// Name of the `Anchor`.
+    pub anchor_name: String,
+    /// Distance of the `Anchor` from the start of the LRM.
+    pub measure: String,
+    /// Longitude of the `Anchor`.
+    pub lon: String,
+    /// Latitude of the `Anchor`.
+    pub lat: String,
+    /// Separator of the columns, often `;` in the files produced by spreadsheets.
+    pub delimiter: u8,
+}
+
+impl Default for CsvMapping {
+    fn default() -> Self {
+        Self {
+            lrm_id: "lrm_id".to_owned(),
+            anchor_name: "anchor_name".to_owned(),
+            measure: "measure".to_owned(),
+            lon: "lon".to_owned(),
+            lat: "lat".to_owned(),
+            delimiter: b',',
+        }
+    }
+}
+
+impl Builder<'_> {
+    /// Add the `Anchor`s listed in a CSV file, such as the kilometer posts exported by an infrastructure manager.
+    ///
+    /// The file has a header, with the columns given by the `mapping`. The other columns are kept as properties.
+    /// Each record is an `Anchor` of the LRM of its `lrm_id`, projected on the `Traversal` with the same `id`.
+    /// One LRM is added for each `lrm_id`, so the `Traversal`s must already be added.
+    pub fn add_anchors_from_csv(
+        &mut self,
+        reader: impl std::io::Read,
+        mapping: &CsvMapping,
+    ) -> Result<(), BuilderError> {
+        let csv_error = |e: ::csv::Error| BuilderError::Csv(e.to_string());
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(mapping.delimiter)
+            .trim(::csv::Trim::All)
+            .from_reader(reader);
+        let headers = reader.headers().map_err(csv_error)?.clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| BuilderError::MissingColumn(name.to_owned()))
+        };
+        let lrm_id_column = column(&mapping.lrm_id)?;
+        let name_column = column(&mapping.anchor_name)?;
+        let measure_column = column(&mapping.measure)?;
+        let lon_column = column(&mapping.lon)?;
+        let lat_column = column(&mapping.lat)?;
+        let mapped = [
+            lrm_id_column,
+            name_column,
+            measure_column,
+            lon_column,
+            lat_column,
+        ];
+
+        let traversals = self.get_traversal_indexes();
+        let mut lrms: Vec<(String, usize, Vec<AnchorOnLrm>)> = vec![];
+        for record in reader.records() {
+            let record = record.map_err(csv_error)?;
+            let line = record.position().map_or(0, |position| position.line());
+            let value = |index: usize| record.get(index).filter(|value| !value.is_empty());
+            let number = |index: usize| {
+                value(index)
+                    .and_then(|value| value.replace(',', ".").parse::<f64>().ok())
+                    .ok_or_else(|| BuilderError::InvalidValue {
+                        line,
+                        column: headers[index].to_owned(),
+                    })
+            };
+            let lrm_id = value(lrm_id_column).ok_or_else(|| BuilderError::InvalidValue {
+                line,
+                column: mapping.lrm_id.clone(),
+            })?;
+            let name = value(name_column).ok_or_else(|| BuilderError::InvalidValue {
+                line,
+                column: mapping.anchor_name.clone(),
+            })?;
+            let distance_along_lrm = number(measure_column)?;
+            let coord = Coord {
+                x: number(lon_column)?,
+                y: number(lat_column)?,
+            };
+
+            let position = match lrms.iter().position(|(id, _, _)| id == lrm_id) {
+                Some(position) => position,
+                None => {
+                    let traversal =
+                        *traversals
+                            .get(lrm_id)
+                            .ok_or_else(|| BuilderError::UnknownTraversal {
+                                line,
+                                lrm_id: lrm_id.to_owned(),
+                            })?;
+                    lrms.push((lrm_id.to_owned(), traversal, vec![]));
+                    lrms.len() - 1
+                }
+            };
+            let properties = headers
+                .iter()
+                .zip(record.iter())
+                .enumerate()
+                .filter(|(index, _)| !mapped.contains(index))
+                .map(|(_, (key, value))| (key.to_owned(), value.to_owned()))
+                .collect();
+            let anchor_index = self.add_anchor(name, Some(name), coord, properties);
+            lrms[position].2.push(AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm,
+            });
+        }
+
+        for (id, traversal, anchors) in lrms {
+            self.add_lrm(&id, traversal, &anchors, properties!());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use geo::coord;
+
+    use crate::builder::tests::{build_traversal, geographic};
+
+    use super::*;
+
+    #[test]
+    fn add_anchors_from_csv() {
+        let mut b = Builder::new();
+        build_traversal(&mut b);
+        b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        b.add_node("b", coord! {x: 1., y: 0.}, properties!());
+        b.add_node("c", coord! {x: 2., y: 0.}, properties!());
+        let csv = "lrm_id;anchor_name;measure;lon;lat;kind
+traversal;0;0;0;0.1;post
+traversal;1;1000,5;1;0.1;post
+";
+        let mapping = CsvMapping {
+            delimiter: b';',
+            ..Default::default()
+        };
+        b.add_anchors_from_csv(csv.as_bytes(), &mapping).unwrap();
+
+        let lrs = b.build_lrs(properties!()).unwrap();
+        let scale = &geographic(&lrs).lrms[0].scale;
+        assert_eq!(scale.id, "traversal");
+        assert_eq!(scale.anchors.len(), 2);
+        assert_relative_eq!(scale.anchors[1].scale_position(), 1000.5);
+        assert_relative_eq!(scale.anchors[1].curve_position(), 0.5, epsilon = 1e-6);
+        assert_eq!(scale.anchors[1].properties()["kind"], "post");
+
+        let csv = "lrm_id,anchor_name,measure,lon,lat\nother,0,0,0,0\n";
+        assert_eq!(
+            b.add_anchors_from_csv(csv.as_bytes(), &CsvMapping::default()),
+            Err(BuilderError::UnknownTraversal {
+                line: 2,
+                lrm_id: "other".to_owned()
+            })
+        );
+        let csv = "lrm_id,anchor_name,measure,lon\n";
+        assert_eq!(
+            b.add_anchors_from_csv(csv.as_bytes(), &CsvMapping::default()),
+            Err(BuilderError::MissingColumn("lat".to_owned()))
+        );
+    }
+}
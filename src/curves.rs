@@ -4,10 +4,13 @@
 //! For now the implementation is based on a [`LineString`],
 //! but other implementations could be considered such as splines.
 
+use std::cell::RefCell;
+
 use geo::kernels::RobustKernel;
-use geo::line_measures::{Densifiable, LengthMeasurable};
+use geo::line_intersection::{LineIntersection, line_intersection};
+use geo::line_measures::LengthMeasurable;
 use geo::prelude::*;
-use geo::{Line, LineString, Point, Rect, coord};
+use geo::{Coord, Line, LineString, Point, Polygon, Rect, coord};
 use num_traits::{One, Zero, float::Float};
 use thiserror::Error;
 
@@ -15,14 +18,16 @@ use thiserror::Error;
 /// It provides basic primitives to locate/project points on it.
 /// A [`Curve`] can be part of a larger [`Curve`] (e.g. for optimisation purposes and to have better bounding boxes).
 /// The [`Curve`] can be implemented.
-pub trait Curve {
+/// It must be [`Send`] and [`Sync`], so that an [`crate::lrs::Lrs`] can be shared between threads.
+pub trait Curve: Send + Sync {
     /// Is the curve represented in spherical coordinates (like longitude;latitude)
     ///
     /// This has an influence on how to compute distances and bearings
     const IS_SPHERICAL: bool;
 
     /// Builds a new [`Curve`] from a [`LineString`].
-    /// `max_extent` is the maximum distance that is considered to be “on the curve”.
+    /// `max_extent` is the maximum distance that is considered to be “on the curve”,
+    /// in the same unit as [`Curve::length`].
     /// `max_extent` plays a role in the bounding box.
     fn new(geom: LineString, max_extent: f64) -> Self;
 
@@ -30,6 +35,7 @@ pub trait Curve {
     fn length(&self) -> f64;
 
     /// How far from the [`Curve`] could be considered to be still on the [`Curve`].
+    /// In meters for a [`SphericalLineStringCurve`], in the units of the coordinates for a [`PlanarLineStringCurve`].
     fn max_extent(&self) -> f64;
 
     /// Is the geometry valid. Depending on the representation.
@@ -37,6 +43,10 @@ pub trait Curve {
     /// If there are exactly two [`Coord`]s, they must be different.
     fn is_valid(&self) -> bool;
 
+    /// Is the [`Curve`] a loop, like a test ring or a circular metro line: its last [`Coord`] is its first one.
+    /// A closed geometry of only two [`Coord`]s is not a loop, as it is not valid.
+    fn is_closed(&self) -> bool;
+
     /// Projects the [`Point`] to the closest position on the [`Curve`].
     /// Will fail if the [`Curve`] is invalid (e.g. no [`Point`] on it)
     /// or if the [`Point`] is too far away.
@@ -47,13 +57,26 @@ pub trait Curve {
     fn resolve(&self, distance_along_curve: f64) -> Result<Point, CurveError>;
 
     /// Bounding box of the [`Curve`] with a buffer of `max_extent`.
-    fn bbox(&self) -> Rect;
+    /// Will return an error if the [`Curve`] has no [`Coord`].
+    fn bbox(&self) -> Result<Rect, CurveError>;
 
     /// Computes the normal at a given position on the [`Curve`].
     /// Will return an error if the [`Curve`] is invalid or the `offset` is outside of the [`Curve`].
     /// Points to the positive side (left).
     fn get_normal(&self, curve_position: f64) -> Result<(f64, f64), CurveError>;
 
+    /// The [`Point`] at `offset` from the position `curve_position`, perpendicularly to the [`Curve`].
+    /// As for [`CurveProjection::offset`], a positive `offset` is on the left and a negative one on the right.
+    fn offset_point(&self, curve_position: f64, offset: f64) -> Result<Point, CurveError>;
+
+    /// The direction of the [`Curve`] at a given position, in degrees clockwise from the north (the y axis), between 0 and 360.
+    /// Will return an error if the [`Curve`] is invalid or the position is outside of the [`Curve`].
+    fn bearing(&self, curve_position: f64) -> Result<f64, CurveError>;
+
+    /// The geometry of the [`Curve`] moved perpendicularly by `offset`, positive on the left and negative on the right.
+    /// At each vertex, the offset follows the bisector of the adjacent segments so that parallel segments stay at `offset`.
+    fn offset_linestring(&self, offset: f64) -> Result<LineString, CurveError>;
+
     /// Returns the [`Point`] where the [`Curve`] and the segment ([`Line`]) intersect.
     /// If the segment intersects the [`Curve`] multiple times, an intersection is chosen randomly.
     /// TODO: implement return of all the points of intersection.
@@ -66,10 +89,120 @@ pub trait Curve {
     /// Get a range of the `Curve`
     fn sublinestring(&self, from: f64, to: f64) -> Option<LineString>;
 
+    /// Get a range of the `Curve` that can go past its end on a loop.
+    ///
+    /// On a closed `Curve`, when `from` is after `to`, the range goes to the end and continues from the start.
+    /// Otherwise, it is the same as [`Curve::sublinestring`].
+    fn sublinestring_wrapping(&self, from: f64, to: f64) -> Option<LineString> {
+        if !self.is_closed() || from <= to {
+            return self.sublinestring(from, to);
+        }
+        let mut coords = self.sublinestring(from, 1.)?.into_inner();
+        let start = self.sublinestring(0., to)?.into_inner();
+        coords.extend(start.into_iter().skip(1));
+        Some(LineString::new(coords))
+    }
+
+    /// The parts of the `Curve` inside an `area`, such as a work zone, as ranges `(from, to)` of positions with `from < to`.
+    /// The ranges are sorted along the `Curve`. A part only touching the boundary of the `area` is ignored.
+    fn ranges_in(&self, area: &Polygon) -> Vec<(f64, f64)>;
+
     /// Reverses the direction of the `Curve`
     fn reverse(&mut self);
 }
 
+// Offset of each vertex, given the unit normals on the left of the segments between them.
+// On a loop, the first and the last vertices are offset the same way.
+// The offset is longer in a turn, up to twice the normal in a sharp turn.
+fn vertex_offsets(normals: &[Coord], closed: bool) -> Vec<Coord> {
+    (0..=normals.len())
+        .map(|vertex| {
+            let before = match vertex {
+                0 if closed => normals.last(),
+                0 => None,
+                _ => normals.get(vertex - 1),
+            };
+            let after = match normals.get(vertex) {
+                None if closed => normals.first(),
+                after => after,
+            };
+            match (before, after) {
+                (Some(&before), Some(&after)) => {
+                    let sum = before + after;
+                    let norm = sum.x.hypot(sum.y);
+                    // The line goes back on itself
+                    if norm < 1e-9 {
+                        return after;
+                    }
+                    let bisector = sum / norm;
+                    let cos = (bisector.x * after.x + bisector.y * after.y).max(0.5);
+                    bisector / cos
+                }
+                (Some(&normal), None) | (None, Some(&normal)) => normal,
+                (None, None) => coord! {x: 0., y: 0.},
+            }
+        })
+        .collect()
+}
+
+// Ranges of positions of `geom` inside `area`, see `Curve::ranges_in`.
+// The segments are cut where they cross the boundary of the `area`, and each piece is kept if its middle is inside.
+fn ranges_in_area(
+    geom: &LineString,
+    length: f64,
+    segment_length: impl Fn(&Line) -> f64,
+    area: &Polygon,
+) -> Vec<(f64, f64)> {
+    let boundary: Vec<Line> = std::iter::once(area.exterior())
+        .chain(area.interiors())
+        .flat_map(|ring| ring.lines())
+        .collect();
+    let mut ranges: Vec<(f64, f64)> = vec![];
+    let mut cum_length = 0.;
+    for segment in geom.lines() {
+        let delta = segment.delta();
+        let squared_length = delta.x * delta.x + delta.y * delta.y;
+        let length_of_segment = segment_length(&segment);
+        if squared_length == 0. || length <= 0. {
+            cum_length += length_of_segment;
+            continue;
+        }
+        let fraction = |coord: Coord| {
+            let from_start = coord - segment.start;
+            ((from_start.x * delta.x + from_start.y * delta.y) / squared_length).clamp(0., 1.)
+        };
+        let mut cuts = vec![0., 1.];
+        for edge in &boundary {
+            match line_intersection(segment, *edge) {
+                Some(LineIntersection::SinglePoint { intersection, .. }) => {
+                    cuts.push(fraction(intersection))
+                }
+                Some(LineIntersection::Collinear { intersection }) => {
+                    cuts.push(fraction(intersection.start));
+                    cuts.push(fraction(intersection.end));
+                }
+                None => {}
+            }
+        }
+        cuts.sort_by(f64::total_cmp);
+        for cut in cuts.windows(2) {
+            let (start, end) = (cut[0], cut[1]);
+            let middle = segment.start + delta * ((start + end) / 2.);
+            if end <= start || !area.contains(&middle) {
+                continue;
+            }
+            let from = (cum_length + start * length_of_segment) / length;
+            let to = ((cum_length + end * length_of_segment) / length).min(1.);
+            match ranges.last_mut() {
+                Some(last) if last.1 >= from => last.1 = to,
+                _ => ranges.push((from, to)),
+            }
+        }
+        cum_length += length_of_segment;
+    }
+    ranges
+}
+
 /// Errors when manipulating the [`Curve`]s.
 #[derive(Error, Debug, PartialEq)]
 pub enum CurveError {
@@ -105,6 +238,13 @@ pub struct PlanarLineStringCurve {
     length: f64,
 }
 
+impl PlanarLineStringCurve {
+    /// Same as [`Curve::resolve`], with the same signature as [`SphericalLineStringCurve::resolver`].
+    pub fn resolver(&self) -> impl Fn(f64) -> Result<Point, CurveError> + '_ {
+        |distance_along_curve| self.resolve(distance_along_curve)
+    }
+}
+
 impl Curve for PlanarLineStringCurve {
     const IS_SPHERICAL: bool = false;
 
@@ -129,6 +269,10 @@ impl Curve for PlanarLineStringCurve {
         self.geom.coords_count() >= 2 && (self.geom.coords_count() > 2 || !self.geom.is_closed())
     }
 
+    fn is_closed(&self) -> bool {
+        self.is_valid() && self.geom.is_closed()
+    }
+
     fn as_linestring(&self) -> LineString {
         self.geom.clone()
     }
@@ -145,8 +289,9 @@ impl Curve for PlanarLineStringCurve {
                     .point_at_ratio_from_start(&Euclidean, distance_along_curve)
                     .ok_or(CurveError::InvalidGeometry)?;
 
-                let begin = self.geom.coords().next().unwrap();
-                let end = self.geom.coords().next_back().unwrap();
+                let (Some(begin), Some(end)) = (self.geom.0.first(), self.geom.0.last()) else {
+                    return Err(CurveError::InvalidGeometry);
+                };
 
                 let sign = match RobustKernel::orient2d(point.into(), *end, *begin) {
                     Orientation::Clockwise => 1.,
@@ -168,16 +313,18 @@ impl Curve for PlanarLineStringCurve {
         if !(0. ..=1.).contains(&distance_along_curve) || distance_along_curve.is_nan() {
             Err(CurveError::NotOnTheCurve)
         } else {
-            Ok(self
-                .geom
+            self.geom
                 .point_at_ratio_from_start(&Euclidean, distance_along_curve)
-                .unwrap())
+                .ok_or(CurveError::InvalidGeometry)
         }
     }
 
-    fn bbox(&self) -> Rect {
-        let bounding_rect = self.geom.bounding_rect().unwrap();
-        Rect::new(
+    fn bbox(&self) -> Result<Rect, CurveError> {
+        let bounding_rect = self
+            .geom
+            .bounding_rect()
+            .ok_or(CurveError::InvalidGeometry)?;
+        Ok(Rect::new(
             coord! {
                 x: bounding_rect.min().x - self.max_extent,
                 y: bounding_rect.min().y - self.max_extent,
@@ -186,7 +333,7 @@ impl Curve for PlanarLineStringCurve {
                 x: bounding_rect.max().x + self.max_extent,
                 y: bounding_rect.max().y + self.max_extent,
             },
-        )
+        ))
     }
 
     fn intersect_segment(&self, segment: Line) -> Option<Point> {
@@ -227,15 +374,75 @@ impl Curve for PlanarLineStringCurve {
         Ok(normal)
     }
 
+    fn offset_linestring(&self, offset: f64) -> Result<LineString, CurveError> {
+        if !self.is_valid() {
+            return Err(CurveError::InvalidGeometry);
+        }
+        let mut coords = self.geom.0.clone();
+        coords.dedup();
+        let normals: Vec<_> = coords
+            .windows(2)
+            .map(|segment| {
+                let (dx, dy) = (segment[1].x - segment[0].x, segment[1].y - segment[0].y);
+                let length = dx.hypot(dy);
+                coord! {x: -dy / length, y: dx / length}
+            })
+            .collect();
+        let offsets = vertex_offsets(&normals, self.is_closed());
+        Ok(coords
+            .iter()
+            .zip(offsets)
+            .map(|(&vertex, vertex_offset)| vertex + vertex_offset * offset)
+            .collect())
+    }
+
+    fn bearing(&self, curve_position: f64) -> Result<f64, CurveError> {
+        if !self.is_valid() {
+            return Err(CurveError::InvalidGeometry);
+        }
+        if !(0. ..=1.).contains(&curve_position) {
+            return Err(CurveError::NotOnTheCurve);
+        }
+        let distance_along_curve = curve_position * self.length;
+        let mut accumulated_length = 0.;
+        let mut bearing = None;
+        for segment in self.geom.lines() {
+            let segment_length = segment.length(&Euclidean);
+            if segment_length == 0. {
+                continue;
+            }
+            let (dx, dy) = (
+                segment.end.x - segment.start.x,
+                segment.end.y - segment.start.y,
+            );
+            bearing = Some(dx.atan2(dy).to_degrees().rem_euclid(360.));
+            if accumulated_length + segment_length >= distance_along_curve {
+                break;
+            }
+            accumulated_length += segment_length;
+        }
+        bearing.ok_or(CurveError::InvalidGeometry)
+    }
+
+    fn offset_point(&self, curve_position: f64, offset: f64) -> Result<Point, CurveError> {
+        let point = self.resolve(curve_position)?;
+        // The left side is 90° counter-clockwise from the direction of the curve
+        let bearing = self.bearing(curve_position)?.to_radians();
+        Ok(Point::new(
+            point.x() - bearing.cos() * offset,
+            point.y() + bearing.sin() * offset,
+        ))
+    }
+
     fn sublinestring(&self, from: f64, to: f64) -> Option<LineString> {
         if from < f64::zero() {
             self.sublinestring(f64::zero(), to)
         } else if from > f64::one() {
             self.sublinestring(f64::one(), to)
         } else if to < f64::zero() {
-            self.sublinestring(f64::zero(), to)
+            self.sublinestring(from, f64::zero())
         } else if to > f64::one() {
-            self.sublinestring(f64::one(), to)
+            self.sublinestring(from, f64::one())
         } else if from > to {
             self.sublinestring(to, from)
                 .map(|linestring| LineString::from_iter(linestring.points().rev()))
@@ -256,7 +463,7 @@ impl Curve for PlanarLineStringCurve {
                     let segment_fraction = (end_fractional_length - cum_length) / length;
                     let point = segment.point_at_ratio_from_start(&Euclidean, segment_fraction);
                     points.push(point);
-                    return Some(LineString::from_iter(points.into_iter()));
+                    return Some(LineString::from_iter(points));
                 }
                 if cum_length > start_fractional_length {
                     points.push(segment.start.into());
@@ -270,6 +477,15 @@ impl Curve for PlanarLineStringCurve {
         }
     }
 
+    fn ranges_in(&self, area: &Polygon) -> Vec<(f64, f64)> {
+        ranges_in_area(
+            &self.geom,
+            self.length,
+            |line| line.length(&Euclidean),
+            area,
+        )
+    }
+
     fn reverse(&mut self) {
         let mut points = self.geom.clone().into_inner();
         points.reverse();
@@ -291,12 +507,12 @@ impl Curve for PlanarLineStringCurve {
 /// The coordinates are reprensented by `f64`.
 /// That means a precison of about 1_000_000th of a mm for a [`Curve`] that spans around the Earth.
 pub struct SphericalLineStringCurve {
-    /// The max distance that is considered of being part of the [`Curve`].
+    /// The max distance that is considered of being part of the [`Curve`], in meters.
     /// It is used to compute the bounding box.
     pub max_extent: f64,
 
     /// The coordinates are considered to be spherical.
-    /// All distance and length calculations are expressed in the same units as coordinates.
+    /// All distance and length calculations are expressed in meters.
     pub geom: LineString,
 
     /// In meters.
@@ -306,9 +522,51 @@ pub struct SphericalLineStringCurve {
     pub densify_by: f64,
 }
 
+thread_local! {
+    // The normals, bearings and intersections of a curve are computed on its densified geometry,
+    // that is only needed during the call: each thread densifies into the same buffer.
+    static DENSIFY_SCRATCH: RefCell<Vec<Point>> = const { RefCell::new(Vec::new()) };
+}
+
 impl SphericalLineStringCurve {
     const DEFAULT_DENSIFY_BY: f64 = 100.0;
 
+    // Same points as `Haversine.densify(&self.geom, self.densify_by)`, but written in a reused buffer
+    fn densify_into(&self, buffer: &mut Vec<Point>) {
+        buffer.clear();
+        for line in self.geom.lines() {
+            let (start, end) = (line.start_point(), line.end_point());
+            buffer.push(start);
+            if self.densify_by > 0. {
+                let num_segments = (Haversine.distance(start, end) / self.densify_by).ceil() as u64;
+                let frac = 1. / num_segments as f64;
+                for segment_num in 1..num_segments {
+                    let ratio = frac * segment_num as f64;
+                    buffer.push(Haversine.point_at_ratio_between(start, end, ratio));
+                }
+            }
+        }
+        if let Some(&last) = self.geom.0.last() {
+            buffer.push(last.into());
+        }
+    }
+
+    // Gives the points of the densified geometry using the per-thread scratch buffer
+    fn with_densified<T>(&self, f: impl FnOnce(&[Point]) -> T) -> T {
+        DENSIFY_SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+            Ok(mut buffer) => {
+                self.densify_into(&mut buffer);
+                f(&buffer)
+            }
+            // The buffer is already in use higher in the stack
+            Err(_) => {
+                let mut buffer = vec![];
+                self.densify_into(&mut buffer);
+                f(&buffer)
+            }
+        })
+    }
+
     // Re-implentation to force using geodesic distances when available
     fn line_locate_point(&self, p: &Point) -> Option<f64> {
         let total_length = self.length;
@@ -361,6 +619,44 @@ impl SphericalLineStringCurve {
             None
         }
     }
+
+    /// Same as [`Curve::resolve`], to resolve many positions on the same [`Curve`]:
+    /// the length of each segment is computed once instead of at every call.
+    pub fn resolver(&self) -> impl Fn(f64) -> Result<Point, CurveError> + '_ {
+        let mut accumulated_length = 0.;
+        // Index, start and length of every segment, the same values as computed by `resolve`.
+        // The segments without length are skipped: no fraction of them can be computed.
+        let segments: Vec<(usize, f64, f64)> = self
+            .geom
+            .lines()
+            .enumerate()
+            .map(|(index, segment)| {
+                let start = accumulated_length;
+                let segment_length = segment.length(&Geodesic);
+                accumulated_length += segment_length;
+                (index, start, segment_length)
+            })
+            .filter(|&(_, _, segment_length)| segment_length > 0.)
+            .collect();
+
+        move |distance_along_curve| {
+            if !(0. ..=1.).contains(&distance_along_curve) || distance_along_curve.is_nan() {
+                return Err(CurveError::NotOnTheCurve);
+            }
+            let fractional_length = self.length * distance_along_curve;
+            let position = segments.partition_point(|&(_, start, segment_length)| {
+                start + segment_length < fractional_length
+            });
+            let &(index, start, segment_length) =
+                segments.get(position).ok_or(CurveError::NotOnTheCurve)?;
+            let segment_fraction = (fractional_length - start) / segment_length;
+            Ok(Geodesic.point_at_ratio_between(
+                Point::from(self.geom.0[index]),
+                Point::from(self.geom.0[index + 1]),
+                segment_fraction,
+            ))
+        }
+    }
 }
 
 impl Curve for SphericalLineStringCurve {
@@ -380,6 +676,10 @@ impl Curve for SphericalLineStringCurve {
         self.length
     }
 
+    fn is_closed(&self) -> bool {
+        self.is_valid() && self.geom.is_closed()
+    }
+
     fn as_linestring(&self) -> LineString {
         self.geom.clone()
     }
@@ -403,10 +703,13 @@ impl Curve for SphericalLineStringCurve {
 
         match self.line_locate_point(&point) {
             Some(distance_along_curve) => {
-                let projected_coords = self.line_interpolate_point(distance_along_curve).unwrap();
+                let projected_coords = self
+                    .line_interpolate_point(distance_along_curve)
+                    .ok_or(CurveError::NotFiniteCoordinates)?;
 
-                let begin = self.geom.coords().next().unwrap();
-                let end = self.geom.coords().next_back().unwrap();
+                let (Some(begin), Some(end)) = (self.geom.0.first(), self.geom.0.last()) else {
+                    return Err(CurveError::InvalidGeometry);
+                };
 
                 let sign = match RobustKernel::orient2d(point.into(), *end, *begin) {
                     Orientation::Clockwise => 1.,
@@ -455,9 +758,13 @@ impl Curve for SphericalLineStringCurve {
         Err(CurveError::NotOnTheCurve)
     }
 
-    fn bbox(&self) -> Rect {
-        let min_point = geo::Point(self.geom.bounding_rect().unwrap().min());
-        let max_point = geo::Point(self.geom.bounding_rect().unwrap().max());
+    fn bbox(&self) -> Result<Rect, CurveError> {
+        let bounding_rect = self
+            .geom
+            .bounding_rect()
+            .ok_or(CurveError::InvalidGeometry)?;
+        let min_point = geo::Point(bounding_rect.min());
+        let max_point = geo::Point(bounding_rect.max());
 
         // add max_extend distance in South and then West direction
         let min_point_extended = Geodesic.destination(min_point, 180., self.max_extent);
@@ -467,27 +774,29 @@ impl Curve for SphericalLineStringCurve {
         let max_point_extended = Geodesic.destination(max_point, 0., self.max_extent);
         let max_point_extended = Geodesic.destination(max_point_extended, 90., self.max_extent);
 
-        Rect::new(min_point_extended, max_point_extended)
+        Ok(Rect::new(min_point_extended, max_point_extended))
     }
 
     // Important:
     // - the SphericalLineStringCurve is densified for long curves
     // to get the intersection(s) closer to the real closest path.
     fn intersect_segment(&self, segment: Line) -> Option<Point> {
-        self.geom
-            .densify(&Haversine, self.densify_by)
-            .lines()
-            .flat_map(|curve_line| {
-                match geo::line_intersection::line_intersection(segment, curve_line) {
-                    Some(LineIntersection::SinglePoint {
-                        intersection,
-                        is_proper: _,
-                    }) => Some(intersection.into()),
-                    Some(LineIntersection::Collinear { intersection: _ }) => None,
-                    None => None,
-                }
-            })
-            .next()
+        self.with_densified(|points| {
+            points
+                .windows(2)
+                .map(|window| Line::new(window[0], window[1]))
+                .flat_map(|curve_line| {
+                    match geo::line_intersection::line_intersection(segment, curve_line) {
+                        Some(LineIntersection::SinglePoint {
+                            intersection,
+                            is_proper: _,
+                        }) => Some(intersection.into()),
+                        Some(LineIntersection::Collinear { intersection: _ }) => None,
+                        None => None,
+                    }
+                })
+                .next()
+        })
     }
 
     // Important :
@@ -497,26 +806,86 @@ impl Curve for SphericalLineStringCurve {
     // to get the intersection(s) closer to the real closest path.
     fn get_normal(&self, curve_position: f64) -> Result<(f64, f64), CurveError> {
         let distance_along_curve = curve_position * self.length;
-        // go through each segment and look for the one that frame the distance that we seek
-        let mut accumulated_length = 0.;
-        for segment in self.geom.densify(&Haversine, self.densify_by).lines() {
-            let segment_length = segment.length(&Geodesic);
-            if accumulated_length + segment_length >= distance_along_curve {
+        self.with_densified(|points| {
+            // go through each segment and look for the one that frame the distance that we seek
+            let mut accumulated_length = 0.;
+            for window in points.windows(2) {
                 // get Points from the segment that frame the point
-                let start = geo::Point(segment.start);
-                let end = geo::Point(segment.end);
-
-                // get bearing from start Point and end Point of the segment, and add 90° clockwise rotation to it
-                let normal_vector_bearing = Geodesic.bearing(start, end) + 90.;
+                let (start, end) = (window[0], window[1]);
+                let segment_length = Geodesic.distance(start, end);
+                if accumulated_length + segment_length >= distance_along_curve {
+                    // get bearing from start Point and end Point of the segment, and add 90° clockwise rotation to it
+                    let normal_vector_bearing = Geodesic.bearing(start, end) + 90.;
 
-                // get end Point from the end of the segment for the normal vector bearing value and 1m of (haversine) length
-                let end_normal = Geodesic.destination(end, normal_vector_bearing, 1.);
+                    // get end Point from the end of the segment for the normal vector bearing value and 1m of (haversine) length
+                    let end_normal = Geodesic.destination(end, normal_vector_bearing, 1.);
 
-                return Ok((end_normal.x() - end.x(), end_normal.y() - end.y()));
+                    return Ok((end_normal.x() - end.x(), end_normal.y() - end.y()));
+                }
+                accumulated_length += segment_length;
             }
-            accumulated_length += segment_length;
+            Err(CurveError::NotFiniteCoordinates)
+        })
+    }
+
+    fn offset_linestring(&self, offset: f64) -> Result<LineString, CurveError> {
+        if !self.is_valid() {
+            return Err(CurveError::InvalidGeometry);
         }
-        Err(CurveError::NotFiniteCoordinates)
+        let mut points: Vec<_> = self.geom.points().collect();
+        points.dedup();
+        // The normals are in a local frame where x points to the east and y to the north
+        let normals: Vec<_> = points
+            .windows(2)
+            .map(|segment| {
+                let bearing = (Geodesic.bearing(segment[0], segment[1]) - 90.).to_radians();
+                coord! {x: bearing.sin(), y: bearing.cos()}
+            })
+            .collect();
+        let offsets = vertex_offsets(&normals, self.is_closed());
+        Ok(points
+            .iter()
+            .zip(offsets)
+            .map(|(&vertex, vertex_offset)| {
+                let bearing = vertex_offset.x.atan2(vertex_offset.y).to_degrees();
+                let length = vertex_offset.x.hypot(vertex_offset.y);
+                Geodesic.destination(vertex, bearing, length * offset)
+            })
+            .collect())
+    }
+
+    fn bearing(&self, curve_position: f64) -> Result<f64, CurveError> {
+        if !self.is_valid() {
+            return Err(CurveError::InvalidGeometry);
+        }
+        if !(0. ..=1.).contains(&curve_position) {
+            return Err(CurveError::NotOnTheCurve);
+        }
+        let distance_along_curve = curve_position * self.length;
+        self.with_densified(|points| {
+            let mut accumulated_length = 0.;
+            let mut bearing = None;
+            for window in points.windows(2) {
+                let (start, end) = (window[0], window[1]);
+                let segment_length = Geodesic.distance(start, end);
+                if segment_length == 0. {
+                    continue;
+                }
+                bearing = Some(Geodesic.bearing(start, end).rem_euclid(360.));
+                if accumulated_length + segment_length >= distance_along_curve {
+                    break;
+                }
+                accumulated_length += segment_length;
+            }
+            bearing.ok_or(CurveError::InvalidGeometry)
+        })
+    }
+
+    fn offset_point(&self, curve_position: f64, offset: f64) -> Result<Point, CurveError> {
+        let point = self.resolve(curve_position)?;
+        // The left side is 90° counter-clockwise from the direction of the curve
+        let bearing = self.bearing(curve_position)? - 90.;
+        Ok(Geodesic.destination(point, bearing, offset))
     }
 
     fn sublinestring(&self, from: f64, to: f64) -> Option<LineString> {
@@ -551,7 +920,7 @@ impl Curve for SphericalLineStringCurve {
                     let segment_fraction = (end_fractional_length - cum_length) / length;
                     let point = segment.point_at_ratio_from_start(&Euclidean, segment_fraction);
                     points.push(point);
-                    return Some(LineString::from_iter(points.into_iter()));
+                    return Some(LineString::from_iter(points));
                 }
                 cum_length += length;
             }
@@ -561,6 +930,10 @@ impl Curve for SphericalLineStringCurve {
         }
     }
 
+    fn ranges_in(&self, area: &Polygon) -> Vec<(f64, f64)> {
+        ranges_in_area(&self.geom, self.length, |line| line.length(&Geodesic), area)
+    }
+
     fn reverse(&mut self) {
         let mut points = self.geom.clone().into_inner();
         points.reverse();
@@ -568,6 +941,28 @@ impl Curve for SphericalLineStringCurve {
     }
 }
 
+/// Streams the geometry of the [`Curve`] to any [`geozero`] sink, such as FlatGeobuf, MVT or WKB.
+#[cfg(feature = "geozero")]
+impl geozero::GeozeroGeometry for PlanarLineStringCurve {
+    fn process_geom<P: geozero::GeomProcessor>(
+        &self,
+        processor: &mut P,
+    ) -> geozero::error::Result<()> {
+        geozero::GeozeroGeometry::process_geom(&self.geom, processor)
+    }
+}
+
+/// Streams the geometry of the [`Curve`] to any [`geozero`] sink, such as FlatGeobuf, MVT or WKB.
+#[cfg(feature = "geozero")]
+impl geozero::GeozeroGeometry for SphericalLineStringCurve {
+    fn process_geom<P: geozero::GeomProcessor>(
+        &self,
+        processor: &mut P,
+    ) -> geozero::error::Result<()> {
+        geozero::GeozeroGeometry::process_geom(&self.geom, processor)
+    }
+}
+
 /// Represents a [`Point`] in space projected on the [`Curve`].
 #[derive(Clone, Copy, Debug)]
 pub struct CurveProjection {
@@ -601,6 +996,16 @@ mod tests {
     const NANCY: geo::Coord = coord! {x: 6.18, y: 48.69};
     const REYKJAVIK: geo::Coord = coord! {x: -21.83, y: 64.13};
 
+    #[cfg(feature = "geozero")]
+    #[test]
+    fn geozero_geometry() {
+        use geozero::ToGeo;
+
+        let linestring = line_string![(x: 0., y: 0.), (x: 2., y: 0.)];
+        let c = PlanarLineStringCurve::new(linestring.clone(), 1.);
+        assert_eq!(c.to_geo().unwrap(), geo::Geometry::LineString(linestring));
+    }
+
     #[test]
     fn planar_length() {
         let c = PlanarLineStringCurve::new(line_string![(x: 0., y: 0.), (x: 2., y: 0.)], 1.);
@@ -622,6 +1027,41 @@ mod tests {
         assert!(!curve.is_valid());
     }
 
+    #[test]
+    fn planar_loop() {
+        let square = line_string![(x: 0., y: 0.), (x: 1., y: 0.), (x: 1., y: 1.), (x: 0., y: 1.), (x: 0., y: 0.)];
+        let c = PlanarLineStringCurve::new(square, 1.);
+        assert!(c.is_closed());
+        assert_eq!(
+            c.sublinestring_wrapping(0.875, 0.125).unwrap(),
+            line_string![(x: 0., y: 0.5), (x: 0., y: 0.), (x: 0.5, y: 0.)]
+        );
+        assert_eq!(
+            c.sublinestring_wrapping(0.125, 0.375).unwrap(),
+            c.sublinestring(0.125, 0.375).unwrap()
+        );
+
+        let c = PlanarLineStringCurve::new(line_string![(x: 0., y: 0.), (x: 2., y: 0.)], 1.);
+        assert!(!c.is_closed());
+        assert_eq!(
+            c.sublinestring_wrapping(0.75, 0.25).unwrap(),
+            line_string![(x: 1.5, y: 0.), (x: 0.5, y: 0.)]
+        );
+    }
+
+    #[test]
+    fn planar_sublinestring_out_of_range() {
+        let c = PlanarLineStringCurve::new(line_string![(x: 0., y: 0.), (x: 2., y: 0.)], 1.);
+        assert_eq!(
+            c.sublinestring(0.5, 1.5).unwrap(),
+            line_string![(x: 1., y: 0.), (x: 2., y: 0.)]
+        );
+        assert_eq!(
+            c.sublinestring(0.5, -0.5).unwrap(),
+            line_string![(x: 1., y: 0.), (x: 0., y: 0.)]
+        );
+    }
+
     #[test]
     fn planar_projection() {
         let c = PlanarLineStringCurve::new(line_string![(x: 0., y: 0.), (x: 2., y: 0.)], 1.);
@@ -647,7 +1087,7 @@ mod tests {
     #[test]
     fn planar_bbox() {
         let c = PlanarLineStringCurve::new(line_string![(x: 0., y: 0.), (x: 2., y: 0.)], 1.);
-        let bbox = c.bbox();
+        let bbox = c.bbox().unwrap();
 
         assert_eq!(bbox.min(), coord! {x: -1., y: -1.});
         assert_eq!(bbox.max(), coord! {x: 3., y: 1.});
@@ -685,6 +1125,81 @@ mod tests {
         assert_relative_eq!(normal_c.1, 1.);
     }
 
+    #[test]
+    fn planar_offset_point() {
+        let c = PlanarLineStringCurve::new(
+            line_string![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.)],
+            1.,
+        );
+        let offset_point = |position, offset| c.offset_point(position, offset).unwrap();
+        assert_relative_eq!(
+            offset_point(0.25, 1.),
+            point! {x: 1., y: 1.},
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            offset_point(0.75, -0.5),
+            point! {x: 2.5, y: 1.},
+            epsilon = 1e-12
+        );
+        assert!(c.offset_point(1.5, 1.).is_err());
+        assert_relative_eq!(offset_point(1., 1.), point! {x: 1., y: 2.}, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn planar_bearing() {
+        let c = PlanarLineStringCurve::new(
+            line_string![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 0.), (x: 0., y: -2.)],
+            1.,
+        );
+        assert_relative_eq!(c.bearing(0.).unwrap(), 90.);
+        assert_relative_eq!(c.bearing(1.).unwrap(), 225.);
+        assert!(c.bearing(1.1).is_err());
+    }
+
+    #[test]
+    fn planar_offset_linestring() {
+        let c = PlanarLineStringCurve::new(
+            line_string![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.)],
+            1.,
+        );
+        assert_eq!(
+            c.offset_linestring(1.).unwrap(),
+            line_string![(x: 0., y: 1.), (x: 1., y: 1.), (x: 1., y: 2.)]
+        );
+
+        let square = PlanarLineStringCurve::new(
+            line_string![(x: 0., y: 0.), (x: 2., y: 0.), (x: 2., y: 2.), (x: 0., y: 2.), (x: 0., y: 0.)],
+            1.,
+        );
+        assert_eq!(
+            square.offset_linestring(-0.5).unwrap(),
+            line_string![(x: -0.5, y: -0.5), (x: 2.5, y: -0.5), (x: 2.5, y: 2.5), (x: -0.5, y: 2.5), (x: -0.5, y: -0.5)]
+        );
+    }
+
+    #[test]
+    fn planar_ranges_in() {
+        let c = PlanarLineStringCurve::new(
+            line_string![(x: 0., y: 0.), (x: 10., y: 0.), (x: 10., y: 10.)],
+            1.,
+        );
+        let area = Rect::new(coord! {x: 5., y: -1.}, coord! {x: 15., y: 5.}).to_polygon();
+        assert_eq!(c.ranges_in(&area), [(0.25, 0.75)]);
+
+        // Only touches the start of the curve
+        let area = Rect::new(coord! {x: -5., y: -1.}, coord! {x: 0., y: 1.}).to_polygon();
+        assert!(c.ranges_in(&area).is_empty());
+
+        let area = Polygon::new(
+            line_string![(x: 0., y: -2.), (x: 20., y: -2.), (x: 20., y: 2.), (x: 0., y: 2.), (x: 0., y: -2.)],
+            vec![
+                line_string![(x: 2.5, y: -1.), (x: 5., y: -1.), (x: 5., y: 1.), (x: 2.5, y: 1.), (x: 2.5, y: -1.)],
+            ],
+        );
+        assert_eq!(c.ranges_in(&area), [(0., 0.125), (0.25, 0.6)]);
+    }
+
     #[test]
     fn spherical_length() {
         let paris_to_new_york = SphericalLineStringCurve::new(line_string![PARIS, NEW_YORK], 1.);
@@ -800,6 +1315,23 @@ mod tests {
         assert_eq!(48.695256847531994, brest_to_nancy_p.y());
     }
 
+    #[test]
+    fn spherical_resolver() {
+        let curve = SphericalLineStringCurve::new(line_string![PARIS, LILLE, BREST, NANCY], 1.);
+        let resolve = curve.resolver();
+        for position in [0., 0.1, 0.25, 0.5, 0.75, 1.] {
+            assert_eq!(resolve(position), curve.resolve(position));
+        }
+        assert_eq!(resolve(1.1), Err(CurveError::NotOnTheCurve));
+        assert_eq!(resolve(f64::NAN), Err(CurveError::NotOnTheCurve));
+
+        // A repeated coordinate makes a segment without length
+        let curve = SphericalLineStringCurve::new(line_string![PARIS, PARIS, LILLE, LILLE], 1.);
+        let resolve = curve.resolver();
+        assert_eq!(resolve(0.), Ok(PARIS.into()));
+        assert_relative_eq!(resolve(1.).unwrap(), LILLE.into());
+    }
+
     #[test]
     fn spherical_bbox() {
         let paris_to_new_york = SphericalLineStringCurve::new(
@@ -812,7 +1344,7 @@ mod tests {
             ],
             100.,
         );
-        let bbox = paris_to_new_york.bbox();
+        let bbox = paris_to_new_york.bbox().unwrap();
 
         assert_eq!(
             bbox.min(),
@@ -857,6 +1389,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn spherical_densify() {
+        let mut curve = SphericalLineStringCurve::new(line_string![PARIS, REYKJAVIK, NEW_YORK], 1.);
+        curve.densify_by = 100_000.;
+
+        let mut buffer = vec![];
+        curve.densify_into(&mut buffer);
+        let expected = Haversine.densify(&curve.geom, curve.densify_by);
+        assert_eq!(LineString::from(buffer.clone()), expected);
+
+        // The buffer is reused
+        curve.densify_into(&mut buffer);
+        assert_eq!(LineString::from(buffer), expected);
+
+        let nested =
+            curve.with_densified(|outer| curve.with_densified(|inner| inner.len() == outer.len()));
+        assert!(nested);
+    }
+
+    #[test]
+    fn spherical_offset_point() {
+        let c = SphericalLineStringCurve::new(line_string![(x: 0., y: 0.), (x: 0.1, y: 0.)], 1.);
+        for offset in [3.5, -3.5] {
+            let point = c.offset_point(0.5, offset).unwrap();
+            assert_relative_eq!(point.x(), 0.05, epsilon = 1e-9);
+            let projection = c.project(point).unwrap();
+            assert_relative_eq!(projection.offset, offset, epsilon = 1e-6);
+            assert_relative_eq!(projection.distance_along_curve, 0.5, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn spherical_bearing() {
+        let c = SphericalLineStringCurve::new(
+            line_string![(x: 0., y: 0.), (x: 0.1, y: 0.), (x: 0.1, y: -0.1)],
+            1.,
+        );
+        assert_relative_eq!(c.bearing(0.25).unwrap(), 90., epsilon = 1e-6);
+        assert_relative_eq!(c.bearing(0.75).unwrap(), 180., epsilon = 1e-6);
+        assert!(c.bearing(-0.1).is_err());
+    }
+
+    #[test]
+    fn spherical_offset_linestring() {
+        let c = SphericalLineStringCurve::new(
+            line_string![(x: 0., y: 0.), (x: 0.1, y: 0.), (x: 0.1, y: 0.1)],
+            1.,
+        );
+        for offset in [10., -10.] {
+            let offset_linestring = c.offset_linestring(offset).unwrap();
+            assert_eq!(offset_linestring.0.len(), 3);
+            // The side given by the projection is the one of the line between both ends of the curve
+            let start = offset_linestring.points().next().unwrap();
+            assert_relative_eq!(c.project(start).unwrap().offset, offset, epsilon = 1e-2);
+            let end = offset_linestring.points().next_back().unwrap();
+            assert_relative_eq!(c.project(end).unwrap().offset.abs(), 10., epsilon = 1e-2);
+            // The corner is on the bisector of the turn, at 10 m from both segments
+            let corner = offset_linestring.points().nth(1).unwrap();
+            let distance = Geodesic.distance(corner, point! {x: 0.1, y: 0.});
+            assert_relative_eq!(distance, 10. * 2f64.sqrt(), epsilon = 1e-2);
+        }
+    }
+
     #[test]
     fn spherical_normal() {
         // Earth radius is equal to 6371008.8m, considering geo::MEAN_EARTH_RADIUS.
@@ -883,4 +1478,21 @@ mod tests {
         );
         assert_relative_eq!(latitudinal_normal.1, 0., epsilon = 1e-7);
     }
+
+    #[test]
+    fn empty_geometry() {
+        let planar = PlanarLineStringCurve::new(LineString::new(vec![]), 1.);
+        assert_eq!(planar.bbox(), Err(CurveError::InvalidGeometry));
+        assert!(planar.resolve(0.5).is_err());
+        assert!(planar.project(point! {x: 0., y: 0.}).is_err());
+        assert!(planar.get_normal(0.5).is_err());
+        assert!(planar.sublinestring(0., 1.).is_none());
+
+        let spherical = SphericalLineStringCurve::new(LineString::new(vec![]), 1.);
+        assert_eq!(spherical.bbox(), Err(CurveError::InvalidGeometry));
+        assert!(spherical.resolve(0.5).is_err());
+        assert!(spherical.project(point! {x: 0., y: 0.}).is_err());
+        assert!(spherical.get_normal(0.5).is_err());
+        assert!(spherical.sublinestring(0., 1.).is_none());
+    }
 }
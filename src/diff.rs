@@ -0,0 +1,400 @@
+//! Compares two versions of an [`Lrs`], for instance to review a new release of the data.
+//!
+//! The [`Lrm`]s are matched by their identifier and the [`Anchor`]s by their name.
+//! [`lrs_remap`] converts the measures of the old version into measures of the new one.
+
+use std::collections::HashMap;
+
+use crate::curves::Curve;
+use crate::lrm_scale::{Anchor, LrmScaleMeasure};
+use crate::lrs::{Lrm, LrmHandle, LrmMeasure, Lrs, LrsBase, LrsError};
+
+/// The changes between two versions of an [`Lrs`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LrsDiff {
+    /// Identifiers of the [`Lrm`]s that only exist in the new version.
+    pub added_lrms: Vec<String>,
+    /// Identifiers of the [`Lrm`]s that only exist in the old version.
+    pub removed_lrms: Vec<String>,
+    /// The [`Lrm`]s that exist in both versions, but are different.
+    pub modified_lrms: Vec<LrmDiff>,
+}
+
+impl LrsDiff {
+    /// Both versions have the same [`Lrm`]s.
+    pub fn is_empty(&self) -> bool {
+        self.added_lrms.is_empty() && self.removed_lrms.is_empty() && self.modified_lrms.is_empty()
+    }
+}
+
+/// The changes of an [`Lrm`] between two versions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LrmDiff {
+    /// Identifier of the [`Lrm`].
+    pub id: String,
+    /// Names of the [`Anchor`]s that only exist in the new version.
+    pub added_anchors: Vec<String>,
+    /// Names of the [`Anchor`]s that only exist in the old version.
+    pub removed_anchors: Vec<String>,
+    /// The [`Anchor`]s that exist in both versions, but at a different position.
+    pub moved_anchors: Vec<MovedAnchor>,
+    /// When the geometry changed, the Hausdorff distance between both versions.
+    ///
+    /// It is computed on the vertices of the geometries, in the unit of the [`Curve`] (meters for spherical curves).
+    /// It is infinite if the [`Traversal`](crate::lrs::Traversal) of one version does not exist.
+    pub geometry_distance: Option<f64>,
+    /// The properties of the [`Lrm`] changed.
+    pub properties_changed: bool,
+}
+
+impl LrmDiff {
+    fn is_empty(&self) -> bool {
+        self.added_anchors.is_empty()
+            && self.removed_anchors.is_empty()
+            && self.moved_anchors.is_empty()
+            && self.geometry_distance.is_none()
+            && !self.properties_changed
+    }
+}
+
+/// An [`Anchor`] whose position changed between two versions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MovedAnchor {
+    /// Name of the [`Anchor`].
+    pub name: String,
+    /// How much the position on the scale changed (new - old), in scale units.
+    pub measure_delta: f64,
+    /// How much the position on the [`Curve`] changed (new - old), as a fraction of the [`Curve`].
+    pub curve_position_delta: f64,
+}
+
+/// Lists the differences between the `old` and the `new` versions of an [`Lrs`].
+///
+/// The [`Lrm`]s are listed in the order of the `new` version, then the removed ones in the order of the `old` version.
+pub fn lrs_diff<CurveImpl: Curve>(old: &Lrs<CurveImpl>, new: &Lrs<CurveImpl>) -> LrsDiff {
+    let old_lrms: HashMap<_, _> = old
+        .lrms
+        .iter()
+        .map(|lrm| (lrm.scale.id.as_str(), lrm))
+        .collect();
+    let new_ids: Vec<_> = new.lrms.iter().map(|lrm| lrm.scale.id.as_str()).collect();
+
+    let mut diff = LrsDiff::default();
+    for new_lrm in &new.lrms {
+        match old_lrms.get(new_lrm.scale.id.as_str()) {
+            None => diff.added_lrms.push(new_lrm.scale.id.clone()),
+            Some(old_lrm) => {
+                let lrm_diff = lrm_diff(old, old_lrm, new, new_lrm);
+                if !lrm_diff.is_empty() {
+                    diff.modified_lrms.push(lrm_diff);
+                }
+            }
+        }
+    }
+    diff.removed_lrms = old
+        .lrms
+        .iter()
+        .filter(|lrm| !new_ids.contains(&lrm.scale.id.as_str()))
+        .map(|lrm| lrm.scale.id.clone())
+        .collect();
+    diff
+}
+
+fn lrm_diff<CurveImpl: Curve>(
+    old: &Lrs<CurveImpl>,
+    old_lrm: &Lrm,
+    new: &Lrs<CurveImpl>,
+    new_lrm: &Lrm,
+) -> LrmDiff {
+    let named = |lrm: &Lrm| -> Vec<(String, f64, f64)> {
+        lrm.scale
+            .anchors
+            .iter()
+            .filter_map(|anchor| match anchor {
+                Anchor::Named(anchor) => Some((
+                    anchor.name.clone(),
+                    anchor.scale_position,
+                    anchor.curve_position,
+                )),
+                Anchor::Unnamed(_) => None,
+            })
+            .collect()
+    };
+    let old_anchors = named(old_lrm);
+    let new_anchors = named(new_lrm);
+
+    let mut diff = LrmDiff {
+        id: new_lrm.scale.id.clone(),
+        properties_changed: old_lrm.properties != new_lrm.properties,
+        ..Default::default()
+    };
+    for (name, scale_position, curve_position) in &new_anchors {
+        match old_anchors.iter().find(|(old_name, ..)| old_name == name) {
+            None => diff.added_anchors.push(name.clone()),
+            Some((_, old_scale_position, old_curve_position)) => {
+                let measure_delta = scale_position - old_scale_position;
+                let curve_position_delta = curve_position - old_curve_position;
+                if measure_delta != 0. || curve_position_delta != 0. {
+                    diff.moved_anchors.push(MovedAnchor {
+                        name: name.clone(),
+                        measure_delta,
+                        curve_position_delta,
+                    });
+                }
+            }
+        }
+    }
+    diff.removed_anchors = old_anchors
+        .into_iter()
+        .filter(|(name, ..)| new_anchors.iter().all(|(new_name, ..)| new_name != name))
+        .map(|(name, ..)| name)
+        .collect();
+
+    match (
+        old.traversal(old_lrm.traversal),
+        new.traversal(new_lrm.traversal),
+    ) {
+        (Ok(old_traversal), Ok(new_traversal)) => {
+            let (old_curve, new_curve) = (&*old_traversal.curve, &*new_traversal.curve);
+            if old_curve.as_linestring() != new_curve.as_linestring() {
+                diff.geometry_distance = Some(hausdorff_distance(old_curve, new_curve));
+            }
+        }
+        // The geometry of an invalid traversal handle can not be compared
+        _ => diff.geometry_distance = Some(f64::INFINITY),
+    }
+    diff
+}
+
+/// The largest distance from a vertex of a [`Curve`] to the other [`Curve`].
+/// It is infinite if a [`Curve`] is not valid (e.g. it has no geometry).
+fn hausdorff_distance<CurveImpl: Curve>(a: &CurveImpl, b: &CurveImpl) -> f64 {
+    let directed = |from: &CurveImpl, to: &CurveImpl| {
+        from.as_linestring()
+            .points()
+            .map(|point| match to.project(point) {
+                Ok(projection) => projection.offset.abs(),
+                Err(_) => f64::INFINITY,
+            })
+            .fold(0., f64::max)
+    };
+    directed(a, b).max(directed(b, a))
+}
+
+/// What happened to a position between two versions of an [`Lrs`], see [`lrs_remap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemapStatus {
+    /// The [`Lrm`] still exists and the measure still designates the same place.
+    Unchanged,
+    /// The [`Lrm`] still exists, but the place has a new measure (e.g. the anchors were recalibrated).
+    Recalibrated,
+    /// The [`Lrm`] still exists, but its geometry moved away from the place.
+    /// The new measure is the nearest position on the [`Lrm`].
+    Rerouted,
+    /// The [`Lrm`] no longer exists, but an other [`Lrm`] goes through the place.
+    Reassigned,
+    /// No [`Lrm`] of the new version goes through the place.
+    Removed,
+}
+
+/// Tolerances used by [`lrs_remap`], in the unit of the [`Curve`] (meters for spherical curves).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RemapOptions {
+    /// How far the new geometry can be from the place before it is considered as rerouted.
+    pub max_distance: f64,
+    /// How far along the [`Curve`] the old measure can be from the place before it is considered as recalibrated.
+    pub tolerance: f64,
+}
+
+impl Default for RemapOptions {
+    fn default() -> Self {
+        Self {
+            max_distance: 20.,
+            tolerance: 0.01,
+        }
+    }
+}
+
+/// A position of an old version of an [`Lrs`] expressed in the new version.
+#[derive(Clone, Debug)]
+pub struct RemappedPosition {
+    /// What happened to the position.
+    pub status: RemapStatus,
+    /// The measure in the new version, unless the position was removed.
+    pub measure: Option<LrmMeasure>,
+    /// Distance between the old place and the new measure, in the unit of the [`Curve`].
+    /// It is infinite when the position was removed.
+    pub offset: f64,
+}
+
+/// Converts a `measure` of the `lrm` in the `old` version of an [`Lrs`] into a measure of the `new` version.
+///
+/// The measure is resolved to a place with the old geometry, then projected on the [`Lrm`] with the same identifier in the new version.
+/// When that [`Lrm`] does not exist anymore, the nearest [`Lrm`] within `max_distance` is used.
+pub fn lrs_remap<CurveImpl: Curve>(
+    old: &Lrs<CurveImpl>,
+    new: &Lrs<CurveImpl>,
+    lrm: LrmHandle,
+    measure: &LrmScaleMeasure,
+    options: &RemapOptions,
+) -> Result<RemappedPosition, LrsError> {
+    let point = old.resolve(lrm, measure)?;
+    let id = &old.lrm(lrm)?.scale.id;
+
+    let Some(new_handle) = new.get_lrm(id) else {
+        return Ok(
+            match new
+                .lookup_all(point, options.max_distance)
+                .into_iter()
+                .next()
+            {
+                Some(projection) => RemappedPosition {
+                    status: RemapStatus::Reassigned,
+                    offset: projection.orthogonal_offset.abs(),
+                    measure: Some(projection.measure),
+                },
+                None => RemappedPosition {
+                    status: RemapStatus::Removed,
+                    measure: None,
+                    offset: f64::INFINITY,
+                },
+            },
+        );
+    };
+
+    let projection = new.lookup(point, new_handle)?;
+    let offset = projection.orthogonal_offset.abs();
+    if offset > options.max_distance {
+        return Ok(RemappedPosition {
+            status: RemapStatus::Rerouted,
+            measure: Some(projection.measure),
+            offset,
+        });
+    }
+
+    // Does the old measure still designate the projected place?
+    let new_lrm = new.lrm(new_handle)?;
+    let length = new.get_curve(new_lrm.traversal)?.length();
+    let projected = new_lrm.scale.locate_point(&projection.measure.measure)?;
+    let unchanged = new_lrm
+        .scale
+        .locate_point(measure)
+        .is_ok_and(|position| (position - projected).abs() * length <= options.tolerance);
+    Ok(if unchanged {
+        RemappedPosition {
+            status: RemapStatus::Unchanged,
+            measure: Some(LrmMeasure {
+                measure: measure.clone(),
+                lrm: new_handle,
+            }),
+            offset,
+        }
+    } else {
+        RemappedPosition {
+            status: RemapStatus::Recalibrated,
+            measure: Some(projection.measure),
+            offset,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use geo::line_string;
+
+    use crate::curves::PlanarLineStringCurve;
+    use crate::lrs::tests::lrs;
+    use crate::lrs::{LrmHandle, TraversalHandle};
+    use crate::properties;
+
+    use super::*;
+
+    #[test]
+    fn lrs_diff() {
+        let old = lrs();
+        assert!(super::lrs_diff(&old, &lrs()).is_empty());
+
+        let mut new = lrs();
+        new.remove_lrm(LrmHandle(1)).unwrap();
+        new.add_lrm("new", TraversalHandle(1), vec![], properties!())
+            .unwrap();
+        let mut anchors = new.lrms[0].scale.anchors.clone();
+        if let Anchor::Named(anchor) = &mut anchors[1] {
+            anchor.scale_position += 2.;
+        }
+        anchors.push(Anchor::new_named("c", 20., 1., None, properties!()));
+        new.set_anchors(LrmHandle(0), anchors).unwrap();
+        *new.traversals[0].curve =
+            PlanarLineStringCurve::new(line_string![(x: 0., y: 0.), (x: 200., y: 3.)], 1.);
+
+        let diff = super::lrs_diff(&old, &new);
+        assert_eq!(diff.added_lrms, ["new"]);
+        assert_eq!(diff.removed_lrms, ["id2"]);
+        assert_eq!(diff.modified_lrms.len(), 1);
+        let lrm_diff = &diff.modified_lrms[0];
+        assert_eq!(lrm_diff.id, "id");
+        assert_eq!(lrm_diff.added_anchors, ["c"]);
+        assert!(lrm_diff.removed_anchors.is_empty());
+        assert_eq!(
+            lrm_diff.moved_anchors,
+            [MovedAnchor {
+                name: "b".to_owned(),
+                measure_delta: 2.,
+                curve_position_delta: 0.,
+            }]
+        );
+        assert_relative_eq!(lrm_diff.geometry_distance.unwrap(), 3., epsilon = 1e-3);
+        assert!(!lrm_diff.properties_changed);
+
+        new.lrms[0].traversal = TraversalHandle(9);
+        let diff = super::lrs_diff(&old, &new);
+        assert_eq!(diff.modified_lrms[0].geometry_distance, Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn lrs_remap() {
+        let old = lrs();
+        let options = RemapOptions::default();
+        let measure = LrmScaleMeasure::new("a", 5.);
+        let remap = |new: &Lrs<PlanarLineStringCurve>| {
+            super::lrs_remap(&old, new, LrmHandle(0), &measure, &options).unwrap()
+        };
+
+        let remapped = remap(&lrs());
+        assert_eq!(remapped.status, RemapStatus::Unchanged);
+        assert_eq!(remapped.measure.unwrap().measure.scale_offset, 5.);
+
+        // The anchor b is now 12 scale units after a
+        let mut new = lrs();
+        let mut anchors = new.lrms[0].scale.anchors.clone();
+        if let Anchor::Named(anchor) = &mut anchors[1] {
+            anchor.scale_position = 12.;
+        }
+        new.set_anchors(LrmHandle(0), anchors).unwrap();
+        let remapped = remap(&new);
+        assert_eq!(remapped.status, RemapStatus::Recalibrated);
+        let new_measure = remapped.measure.unwrap().measure;
+        assert_eq!(new_measure.anchor_name, "a");
+        assert_relative_eq!(new_measure.scale_offset, 6.);
+
+        let mut new = lrs();
+        *new.traversals[0].curve =
+            PlanarLineStringCurve::new(line_string![(x: 0., y: 50.), (x: 200., y: 50.)], 1.);
+        let remapped = remap(&new);
+        assert_eq!(remapped.status, RemapStatus::Rerouted);
+        assert_relative_eq!(remapped.offset, 50.);
+
+        let mut new = lrs();
+        new.remove_lrm(LrmHandle(0)).unwrap();
+        let remapped = remap(&new);
+        assert_eq!(remapped.status, RemapStatus::Reassigned);
+        assert_eq!(remapped.measure.unwrap().lrm, new.get_lrm("id2").unwrap());
+        assert_relative_eq!(remapped.offset, 1.);
+
+        new.remove_lrm(LrmHandle(0)).unwrap();
+        let remapped = remap(&new);
+        assert_eq!(remapped.status, RemapStatus::Removed);
+        assert!(remapped.measure.is_none());
+    }
+}
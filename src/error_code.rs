@@ -0,0 +1,209 @@
+//! Errors of the high level API, in a form that can be exposed identically by every binding.
+//!
+//! An [`LrsErrorCode`] has a stable [`LrsErrorCode::code`] to match on, and the data describing
+//! what went wrong, such as the index of the LRM or the name of the anchor. The bindings expose the
+//! same fields: they should be used instead of the message, which is meant for humans and can change.
+
+use thiserror::Error;
+
+use crate::curves::CurveError;
+use crate::lrm_scale::LrmScaleError;
+use crate::lrs::LrsError;
+
+/// Error returned by the bindings, converted from [`crate::lrs_ext::ExtLrsError`].
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum LrsErrorCode {
+    /// There is no LRM at this index.
+    #[error("there is no lrm at index {lrm_index}")]
+    LrmNotFound {
+        /// Index of the LRM that was requested.
+        lrm_index: usize,
+    },
+    /// An index other than the one of an LRM, such as the one of a traversal or an anchor, is not valid.
+    #[error("invalid handle")]
+    InvalidHandle,
+    /// The anchor of a measure is not an anchor of the LRM.
+    #[error("unknown anchor {}", anchor_name.as_deref().unwrap_or_default())]
+    UnknownAnchor {
+        /// Index of the LRM of the measure, if it is known.
+        lrm_index: Option<usize>,
+        /// Name of the anchor of the measure, if it is known.
+        anchor_name: Option<String>,
+    },
+    /// A scale needs at least two named anchors.
+    #[error("a scale needs at least two named anchor")]
+    NotEnoughNamedAnchors,
+    /// Two anchors of the same scale have the same name.
+    #[error("duplicated anchor: {anchor_name}")]
+    DuplicatedAnchorName {
+        /// The duplicated name.
+        anchor_name: String,
+    },
+    /// No anchor matches the position.
+    #[error("no anchor found")]
+    NoAnchorFound,
+    /// The geometry is not valid.
+    #[error("the curve geometry is not valid")]
+    InvalidGeometry,
+    /// At least one coordinate is not a finite number.
+    #[error("the coordinates are not finite")]
+    NotFiniteCoordinates,
+    /// The point is not on the curve.
+    #[error("the point is not on the curve")]
+    NotOnTheCurve,
+    /// The range of positions on the curve, between 0 and 1, is not valid.
+    #[error("the range [{begin}, {end}] is not valid")]
+    InvalidRange {
+        /// Start of the range.
+        begin: f64,
+        /// End of the range.
+        end: f64,
+    },
+    /// The traversal is not used by the LRM.
+    #[error("the traversal is not used by the lrm")]
+    TraversalNotInLrm,
+    /// The LRMs are not defined on the same traversal.
+    #[error("the lrms do not share the same traversal")]
+    NoSharedTraversal,
+    /// There is no path in the network between the positions.
+    #[error("the positions are not connected")]
+    NoRoute,
+    /// The traversal has no usable geometry.
+    #[error("the geometry of the traversal is not available")]
+    GeometryUnavailable,
+    /// Two elements have the same identifier.
+    #[error("the identifier {id} is used twice")]
+    DuplicateId {
+        /// The duplicated identifier.
+        id: String,
+    },
+    /// A date is not an ISO 8601 date (`YYYY-MM-DD`).
+    #[error("invalid date {date}, expected YYYY-MM-DD")]
+    InvalidDate {
+        /// The malformed date.
+        date: String,
+    },
+    /// The file could not be read: it is missing, corrupted or not supported.
+    #[error("invalid file: {reason}")]
+    InvalidFile {
+        /// What is wrong with the file.
+        reason: String,
+    },
+}
+
+impl LrsErrorCode {
+    /// A stable identifier of the kind of error, such as `LRM_NOT_FOUND`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::LrmNotFound { .. } => "LRM_NOT_FOUND",
+            Self::InvalidHandle => "INVALID_HANDLE",
+            Self::UnknownAnchor { .. } => "UNKNOWN_ANCHOR",
+            Self::NotEnoughNamedAnchors => "NOT_ENOUGH_NAMED_ANCHORS",
+            Self::DuplicatedAnchorName { .. } => "DUPLICATED_ANCHOR_NAME",
+            Self::NoAnchorFound => "NO_ANCHOR_FOUND",
+            Self::InvalidGeometry => "INVALID_GEOMETRY",
+            Self::NotFiniteCoordinates => "NOT_FINITE_COORDINATES",
+            Self::NotOnTheCurve => "NOT_ON_THE_CURVE",
+            Self::InvalidRange { .. } => "INVALID_RANGE",
+            Self::TraversalNotInLrm => "TRAVERSAL_NOT_IN_LRM",
+            Self::NoSharedTraversal => "NO_SHARED_TRAVERSAL",
+            Self::NoRoute => "NO_ROUTE",
+            Self::GeometryUnavailable => "GEOMETRY_UNAVAILABLE",
+            Self::DuplicateId { .. } => "DUPLICATE_ID",
+            Self::InvalidDate { .. } => "INVALID_DATE",
+            Self::InvalidFile { .. } => "INVALID_FILE",
+        }
+    }
+
+    /// Index of the LRM concerned by the error, if it is known.
+    pub fn lrm_index(&self) -> Option<usize> {
+        match self {
+            Self::LrmNotFound { lrm_index } => Some(*lrm_index),
+            Self::UnknownAnchor { lrm_index, .. } => *lrm_index,
+            _ => None,
+        }
+    }
+
+    /// Name of the anchor concerned by the error, if it is known.
+    pub fn anchor_name(&self) -> Option<&str> {
+        match self {
+            Self::UnknownAnchor { anchor_name, .. } => anchor_name.as_deref(),
+            Self::DuplicatedAnchorName { anchor_name } => Some(anchor_name),
+            _ => None,
+        }
+    }
+
+    /// The invalid range of positions on the curve, for [`LrsErrorCode::InvalidRange`].
+    pub fn range(&self) -> Option<(f64, f64)> {
+        match self {
+            Self::InvalidRange { begin, end } => Some((*begin, *end)),
+            _ => None,
+        }
+    }
+
+    /// Gives the LRM and the anchor name of the measure to an [`LrsErrorCode::UnknownAnchor`] without them.
+    pub fn on_measure(self, lrm_index: usize, anchor_name: &str) -> Self {
+        match self {
+            Self::UnknownAnchor {
+                lrm_index: None,
+                anchor_name: None,
+            } => Self::UnknownAnchor {
+                lrm_index: Some(lrm_index),
+                anchor_name: Some(anchor_name.to_owned()),
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<CurveError> for LrsErrorCode {
+    fn from(error: CurveError) -> Self {
+        match error {
+            CurveError::InvalidGeometry => Self::InvalidGeometry,
+            CurveError::NotFiniteCoordinates => Self::NotFiniteCoordinates,
+            CurveError::NotOnTheCurve => Self::NotOnTheCurve,
+            CurveError::InvalidRange(begin, end) => Self::InvalidRange { begin, end },
+        }
+    }
+}
+
+impl From<LrmScaleError> for LrsErrorCode {
+    fn from(error: LrmScaleError) -> Self {
+        match error {
+            LrmScaleError::NoEnoughNamedAnchor => Self::NotEnoughNamedAnchors,
+            LrmScaleError::DuplicatedAnchorName(anchor_name) => {
+                Self::DuplicatedAnchorName { anchor_name }
+            }
+            LrmScaleError::UnknownAnchorName => Self::UnknownAnchor {
+                lrm_index: None,
+                anchor_name: None,
+            },
+            LrmScaleError::NoAnchorFound => Self::NoAnchorFound,
+        }
+    }
+}
+
+impl From<LrsError> for LrsErrorCode {
+    fn from(error: LrsError) -> Self {
+        match error {
+            LrsError::InvalidHandle => Self::InvalidHandle,
+            LrsError::CurveError(error) => error.into(),
+            LrsError::LrmScaleError(error) => error.into(),
+            LrsError::TraversalNotInLrm => Self::TraversalNotInLrm,
+            LrsError::NoSharedTraversal => Self::NoSharedTraversal,
+            LrsError::NoRoute => Self::NoRoute,
+            LrsError::GeometryUnavailable => Self::GeometryUnavailable,
+            LrsError::DuplicateId(id) => Self::DuplicateId { id },
+            LrsError::InvalidDate(date) => Self::InvalidDate { date },
+            LrsError::OpenFileError
+            | LrsError::ReadFileError
+            | LrsError::InvalidArchive(_)
+            | LrsError::CorruptFile(_)
+            | LrsError::UnsupportedFile(_)
+            | LrsError::IncompleteArchive(_)
+            | LrsError::GeometryTypeMismatch(_, _) => Self::InvalidFile {
+                reason: error.to_string(),
+            },
+        }
+    }
+}
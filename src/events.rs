@@ -0,0 +1,369 @@
+//! Dynamic segmentation: overlays attribute tables on the [`Lrs`].
+//!
+//! An attribute table (speed limits, electrification, track class…) is a list of [`Event`]s,
+//! each giving [`Properties`] to a range of measures of an [`Lrm`](crate::lrs::Lrm).
+//! The overlay cuts the [`Lrm`](crate::lrs::Lrm)s where the combination of attributes changes,
+//! and returns every piece with its geometry and all the attributes that apply on it.
+//!
+//! Events can also be stored in the LRS file as [`PointEvent`]s and [`LinearEvent`]s.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use flatbuffers::{FlatBufferBuilder, ForwardsUOffset, Vector, WIPOffset};
+
+use geo::LineString;
+use geo::orient::Direction;
+use thiserror::Error;
+
+use crate::curves::{Curve, CurveError};
+use crate::lrm_scale::{CurvePosition, LrmScaleMeasure};
+use crate::lrs::{LrmHandle, LrmRange, Lrs, LrsBase, LrsError, Properties, Validity};
+use crate::lrs_generated;
+
+/// Attributes that apply on a range of measures of an [`Lrm`](crate::lrs::Lrm).
+/// It is typically a row of an external table, like a speed limit.
+#[derive(Clone, Debug)]
+pub struct Event {
+    /// Identifies the [`Lrm`](crate::lrs::Lrm) on which the event applies.
+    pub lrm_id: String,
+    /// Begin of the range.
+    pub begin: LrmScaleMeasure,
+    /// End of the range.
+    pub end: LrmScaleMeasure,
+    /// The attributes, like `"maxspeed" => "160"`.
+    pub properties: Properties,
+}
+
+/// A piece of an [`Lrm`](crate::lrs::Lrm) where the combination of attributes is constant.
+pub struct EventSegment {
+    /// The measures covered by the segment.
+    pub range: LrmRange,
+    /// The geometry of the segment.
+    pub geometry: LineString,
+    /// All the attributes of the [`Event`]s covering the segment.
+    pub properties: Properties,
+}
+
+/// The value of a typed property of a [`PointEvent`] or a [`LinearEvent`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    /// Free text.
+    Text(String),
+    /// A whole number, like a number of tracks.
+    Integer(i64),
+    /// A decimal number, like a speed limit.
+    Float(f64),
+    /// A flag, like whether the track is electrified.
+    Boolean(bool),
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::Text(value) => write!(f, "{value}"),
+            PropertyValue::Integer(value) => write!(f, "{value}"),
+            PropertyValue::Float(value) => write!(f, "{value}"),
+            PropertyValue::Boolean(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+impl From<lrs_generated::TypedProperty<'_>> for PropertyValue {
+    fn from(fb_property: lrs_generated::TypedProperty) -> Self {
+        match fb_property.value_type() {
+            lrs_generated::ValueType::Integer => Self::Integer(fb_property.integer_value()),
+            lrs_generated::ValueType::Float => Self::Float(fb_property.float_value()),
+            lrs_generated::ValueType::Boolean => Self::Boolean(fb_property.boolean_value()),
+            _ => Self::Text(fb_property.text_value().unwrap_or_default().to_owned()),
+        }
+    }
+}
+
+/// A key-value map where the values keep their type.
+pub type TypedProperties = HashMap<String, PropertyValue>;
+
+/// Builds a [`TypedProperties`] from its flatbuffer representation.
+pub(crate) fn typed_properties_from_fb(
+    properties: Option<Vector<ForwardsUOffset<lrs_generated::TypedProperty>>>,
+) -> TypedProperties {
+    properties
+        .unwrap_or_default()
+        .iter()
+        .map(|property| (property.key().to_owned(), PropertyValue::from(property)))
+        .collect()
+}
+
+/// Builds the flatbuffer representation of [`TypedProperties`].
+pub(crate) fn typed_properties_to_fb<'fbb>(
+    fbb: &mut FlatBufferBuilder<'fbb>,
+    properties: &TypedProperties,
+) -> WIPOffset<Vector<'fbb, ForwardsUOffset<lrs_generated::TypedProperty<'fbb>>>> {
+    let properties_vec: Vec<_> = properties
+        .iter()
+        .map(|(k, v)| {
+            let mut args = lrs_generated::TypedPropertyArgs {
+                key: Some(fbb.create_string(k)),
+                ..Default::default()
+            };
+            match v {
+                PropertyValue::Text(value) => args.text_value = Some(fbb.create_string(value)),
+                PropertyValue::Integer(value) => {
+                    args.value_type = lrs_generated::ValueType::Integer;
+                    args.integer_value = *value;
+                }
+                PropertyValue::Float(value) => {
+                    args.value_type = lrs_generated::ValueType::Float;
+                    args.float_value = *value;
+                }
+                PropertyValue::Boolean(value) => {
+                    args.value_type = lrs_generated::ValueType::Boolean;
+                    args.boolean_value = *value;
+                }
+            }
+            lrs_generated::TypedProperty::create(fbb, &args)
+        })
+        .collect();
+    fbb.create_vector(&properties_vec)
+}
+
+/// Builds the flatbuffer representation of a [`LrmScaleMeasure`].
+pub(crate) fn measure_to_fb<'fbb>(
+    fbb: &mut FlatBufferBuilder<'fbb>,
+    measure: &LrmScaleMeasure,
+) -> WIPOffset<lrs_generated::Measure<'fbb>> {
+    let args = lrs_generated::MeasureArgs {
+        anchor_name: Some(fbb.create_string(&measure.anchor_name)),
+        offset: measure.scale_offset,
+    };
+    lrs_generated::Measure::create(fbb, &args)
+}
+
+impl From<lrs_generated::Measure<'_>> for LrmScaleMeasure {
+    fn from(fb_measure: lrs_generated::Measure) -> Self {
+        Self::new(fb_measure.anchor_name(), fb_measure.offset())
+    }
+}
+
+/// An object located at a single measure of an [`Lrm`](crate::lrs::Lrm), like a signal.
+#[derive(Clone, Debug)]
+pub struct PointEvent {
+    /// Identifies the event.
+    pub id: String,
+    /// The [`Lrm`](crate::lrs::Lrm) on which the event is located.
+    pub lrm: LrmHandle,
+    /// Where the event is.
+    pub measure: LrmScaleMeasure,
+    /// Metadata to describe the event.
+    pub properties: TypedProperties,
+    /// When the event is valid.
+    pub validity: Validity,
+}
+
+impl From<lrs_generated::PointEvent<'_>> for PointEvent {
+    fn from(fb_event: lrs_generated::PointEvent) -> Self {
+        Self {
+            id: fb_event.id().to_owned(),
+            lrm: LrmHandle(fb_event.lrm_index() as usize),
+            measure: fb_event.measure().into(),
+            properties: typed_properties_from_fb(fb_event.properties()),
+            validity: Validity::from_fb(fb_event.valid_from(), fb_event.valid_to()),
+        }
+    }
+}
+
+/// An object located over a range of measures of an [`Lrm`](crate::lrs::Lrm), like a bridge or a speed restriction.
+#[derive(Clone, Debug)]
+pub struct LinearEvent {
+    /// Identifies the event.
+    pub id: String,
+    /// The [`Lrm`](crate::lrs::Lrm) on which the event is located.
+    pub lrm: LrmHandle,
+    /// Begin of the range.
+    pub begin: LrmScaleMeasure,
+    /// End of the range.
+    pub end: LrmScaleMeasure,
+    /// Metadata to describe the event.
+    pub properties: TypedProperties,
+    /// When the event is valid.
+    pub validity: Validity,
+}
+
+impl From<lrs_generated::LinearEvent<'_>> for LinearEvent {
+    fn from(fb_event: lrs_generated::LinearEvent) -> Self {
+        Self {
+            id: fb_event.id().to_owned(),
+            lrm: LrmHandle(fb_event.lrm_index() as usize),
+            begin: fb_event.begin().into(),
+            end: fb_event.end().into(),
+            properties: typed_properties_from_fb(fb_event.properties()),
+            validity: Validity::from_fb(fb_event.valid_from(), fb_event.valid_to()),
+        }
+    }
+}
+
+/// Errors when overlaying [`Event`]s.
+#[derive(Error, Debug, PartialEq)]
+pub enum EventError {
+    /// The [`Lrm`](crate::lrs::Lrm) of the event does not exist in the [`Lrs`].
+    #[error("unknown lrm {0}")]
+    UnknownLrm(String),
+    /// An error occured while locating the event on the [`Lrs`].
+    #[error("lrs error")]
+    LrsError(#[from] LrsError),
+}
+
+/// Overlays all the `events` on the `lrs`.
+///
+/// The result is sorted by [`Lrm`](crate::lrs::Lrm) and then by measure.
+/// Consecutive pieces with the same attributes are merged, and the stretches without any event are omitted.
+/// When several events set the same attribute on a stretch, the last one in `events` wins.
+pub fn overlay<CurveImpl: Curve>(
+    lrs: &Lrs<CurveImpl>,
+    events: &[Event],
+) -> Result<Vec<EventSegment>, EventError> {
+    let mut located = vec![];
+    for event in events {
+        let lrm = lrs
+            .get_lrm(&event.lrm_id)
+            .ok_or_else(|| EventError::UnknownLrm(event.lrm_id.clone()))?;
+        let scale = lrs.get_lrm_by_handle(lrm)?;
+        let begin = scale.locate_point(&event.begin).map_err(LrsError::from)?;
+        let end = scale.locate_point(&event.end).map_err(LrsError::from)?;
+        located.push((lrm, begin.min(end), begin.max(end), &event.properties));
+    }
+    located.sort_by_key(|(lrm, ..)| lrm.0);
+
+    let mut pieces: Vec<(LrmHandle, CurvePosition, CurvePosition, Properties)> = vec![];
+    for same_lrm in located.chunk_by(|a, b| a.0 == b.0) {
+        let lrm = same_lrm[0].0;
+        let mut breakpoints: Vec<_> = same_lrm
+            .iter()
+            .flat_map(|&(_, begin, end, _)| [begin, end])
+            .collect();
+        breakpoints.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        breakpoints.dedup();
+
+        for window in breakpoints.windows(2) {
+            let (begin, end) = (window[0], window[1]);
+            let mut properties = Properties::new();
+            for (_, _, _, event_properties) in same_lrm
+                .iter()
+                .filter(|&&(_, from, to, _)| from <= begin && end <= to)
+            {
+                properties.extend(event_properties.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+            if properties.is_empty() {
+                continue;
+            }
+            match pieces.last_mut() {
+                Some(last) if last.0 == lrm && last.2 == begin && last.3 == properties => {
+                    last.2 = end
+                }
+                _ => pieces.push((lrm, begin, end, properties)),
+            }
+        }
+    }
+
+    pieces
+        .into_iter()
+        .map(|(lrm, begin, end, properties)| {
+            let scale = lrs.get_lrm_by_handle(lrm)?;
+            let curve = lrs.get_curve(lrs.get_lrm_traversal(lrm)?)?;
+            let geometry = curve
+                .sublinestring(begin, end)
+                .ok_or(LrsError::from(CurveError::InvalidRange(begin, end)))?;
+            Ok(EventSegment {
+                range: LrmRange {
+                    lrm,
+                    begin: scale.locate_anchor(begin).map_err(LrsError::from)?,
+                    end: scale.locate_anchor(end).map_err(LrsError::from)?,
+                    direction: Direction::Default,
+                },
+                geometry,
+                properties,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use geo::coord;
+
+    use crate::lrs::tests::lrs;
+    use crate::properties;
+
+    use super::*;
+
+    fn event(lrm_id: &str, begin: f64, end: f64, properties: Properties) -> Event {
+        Event {
+            lrm_id: lrm_id.to_owned(),
+            begin: LrmScaleMeasure::new("a", begin),
+            end: LrmScaleMeasure::new("a", end),
+            properties,
+        }
+    }
+
+    #[test]
+    fn overlay() {
+        let lrs = lrs();
+        let events = [
+            event("id", 0., 10., properties!("maxspeed" => "160")),
+            event("id", 15., 5., properties!("electrified" => "yes")),
+            event("id", 10., 20., properties!("maxspeed" => "160")),
+            event("id2", 0., 5., properties!("maxspeed" => "80")),
+        ];
+        let segments = super::overlay(&lrs, &events).unwrap();
+        assert_eq!(segments.len(), 4);
+
+        // Only the speed limit
+        assert_eq!(segments[0].range.lrm, LrmHandle(0));
+        assert_eq!(segments[0].range.begin.anchor_name, "a");
+        assert_relative_eq!(segments[0].range.begin.scale_offset, 0.);
+        assert_relative_eq!(segments[0].range.end.scale_offset, 5.);
+        assert_eq!(segments[0].properties, properties!("maxspeed" => "160"));
+        assert_eq!(
+            segments[0].geometry.0,
+            vec![coord! {x: 0., y: 0.}, coord! {x: 50., y: 0.}]
+        );
+
+        // Both events, across the boundary of the speed limit events
+        assert_eq!(segments[1].range.begin.anchor_name, "a");
+        assert_relative_eq!(segments[1].range.begin.scale_offset, 5.);
+        assert_eq!(segments[1].range.end.anchor_name, "b");
+        assert_relative_eq!(segments[1].range.end.scale_offset, 5.);
+        assert_eq!(
+            segments[1].properties,
+            properties!("maxspeed" => "160", "electrified" => "yes")
+        );
+
+        assert_eq!(segments[2].properties, properties!("maxspeed" => "160"));
+        assert_eq!(
+            segments[2].geometry.0.last(),
+            Some(&coord! {x: 200., y: 0.})
+        );
+
+        assert_eq!(segments[3].range.lrm, LrmHandle(1));
+        assert_eq!(
+            segments[3].geometry.0,
+            vec![coord! {x: 0., y: -1.}, coord! {x: 50., y: -1.}]
+        );
+
+        // The last event wins
+        let events = [
+            event("id", 0., 10., properties!("maxspeed" => "160")),
+            event("id", 0., 10., properties!("maxspeed" => "120")),
+        ];
+        let segments = super::overlay(&lrs, &events).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].properties, properties!("maxspeed" => "120"));
+
+        assert_eq!(
+            super::overlay(&lrs, &[event("unknown", 0., 1., properties!())]).err(),
+            Some(EventError::UnknownLrm("unknown".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! The container around the FlatBuffers payload of an LRS file.
+//!
+//! A file is the FlatBuffers payload, starting with the `LRSD` file identifier, followed by a footer of [`FOOTER_LEN`] bytes:
+//!
+//! | bytes | content                                      |
+//! |-------|----------------------------------------------|
+//! | 4     | CRC-32 of the payload, little endian         |
+//! | 2     | version of the container, little endian      |
+//! | 2     | flags, see [`FLAG_ZSTD`]                     |
+//! | 4     | the magic bytes [`MAGIC`]                    |
+//!
+//! As FlatBuffers ignore trailing bytes, the payload can still be read by any FlatBuffers implementation,
+//! unless it is compressed. The checksum is computed on the stored payload, compressed or not.
+//! Files written before the footer was introduced have no file identifier and are read without any check.
+//!
+//! Independently of the container, the payload records the [`SCHEMA_VERSION`] it was written with.
+//! Files of a previous version remain readable and can be rewritten with [`crate::lrs::AnyLrs::upgrade_bytes`],
+//! or with the `migrate` command of the `liblrs` tool.
+
+use std::borrow::Cow;
+
+use crate::lrs::LrsError;
+use crate::lrs_generated;
+
+/// The last bytes of an LRS file.
+pub const MAGIC: &[u8; 4] = b"LRS\x1a";
+
+/// Size of the footer appended after the payload.
+pub const FOOTER_LEN: usize = 12;
+
+/// Version of the container written by this version of the library.
+pub const CONTAINER_VERSION: u16 = 1;
+
+/// Version of the FlatBuffers schema written by this version of the library.
+///
+/// Version 0 are the files written before the version was recorded.
+/// Since version 2, the geometries of the segments can be quantized, see [`crate::builder::Builder::set_coordinate_precision`].
+/// All the previous versions can be read: a change that would prevent it must come with a dedicated reader.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Reads the version of the schema of a file, without loading it.
+pub fn schema_version(buf: &[u8]) -> Result<u32, LrsError> {
+    let payload = open(buf)?;
+    let lrs = lrs_generated::root_as_lrs(&payload).map_err(LrsError::InvalidArchive)?;
+    Ok(lrs.schema_version())
+}
+
+/// Fails if the file was written with a version of the schema that this version of the library can not read.
+pub(crate) fn check_schema_version(version: u32) -> Result<(), LrsError> {
+    if version > SCHEMA_VERSION {
+        Err(LrsError::UnsupportedFile(format!(
+            "schema version {version} is newer than the supported version {SCHEMA_VERSION}"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Flag set when the payload is compressed with Zstandard (RFC 8878).
+/// Reading such a file requires the `compression` or the `decompression` feature.
+pub const FLAG_ZSTD: u16 = 1;
+
+/// Largest decompressed payload accepted when reading a compressed file, so that a small hostile file can not exhaust the memory.
+pub const MAX_DECOMPRESSED_LEN: u64 = 1 << 32;
+
+/// Appends the footer to a finished FlatBuffers payload.
+pub(crate) fn seal(payload: &[u8]) -> Vec<u8> {
+    with_footer(payload.to_vec(), 0)
+}
+
+/// Compresses a finished FlatBuffers payload and appends the footer.
+#[cfg(feature = "compression")]
+pub(crate) fn seal_compressed(payload: &[u8]) -> Vec<u8> {
+    let compressed = zstd::bulk::compress(payload, zstd::DEFAULT_COMPRESSION_LEVEL)
+        .expect("compressing to a Vec can not fail");
+    with_footer(compressed, FLAG_ZSTD)
+}
+
+fn with_footer(mut data: Vec<u8>, flags: u16) -> Vec<u8> {
+    let checksum = crc32fast::hash(&data);
+    data.reserve(FOOTER_LEN);
+    data.extend_from_slice(&checksum.to_le_bytes());
+    data.extend_from_slice(&CONTAINER_VERSION.to_le_bytes());
+    data.extend_from_slice(&flags.to_le_bytes());
+    data.extend_from_slice(MAGIC);
+    data
+}
+
+/// `true` if the payload of the file is compressed.
+#[cfg(feature = "compression")]
+pub(crate) fn is_compressed(buf: &[u8]) -> bool {
+    buf.len() >= FOOTER_LEN
+        && buf.ends_with(MAGIC)
+        && u16::from_le_bytes([buf[buf.len() - 6], buf[buf.len() - 5]]) & FLAG_ZSTD != 0
+}
+
+/// Checks the integrity of a file and returns its FlatBuffers payload, decompressed if needed.
+///
+/// Fails with [`LrsError::CorruptFile`] if the file is truncated or its content does not match the checksum.
+pub(crate) fn open(buf: &[u8]) -> Result<Cow<'_, [u8]>, LrsError> {
+    if buf.len() < FOOTER_LEN || !buf.ends_with(MAGIC) {
+        // Without footer, the file must predate it: it cannot have the file identifier
+        return if buf.len() >= 8 && lrs_generated::lrs_buffer_has_identifier(buf) {
+            Err(LrsError::CorruptFile("the file is truncated".to_owned()))
+        } else {
+            Ok(Cow::Borrowed(buf))
+        };
+    }
+
+    let (payload, footer) = buf.split_at(buf.len() - FOOTER_LEN);
+    let checksum = u32::from_le_bytes([footer[0], footer[1], footer[2], footer[3]]);
+    let version = u16::from_le_bytes([footer[4], footer[5]]);
+    let flags = u16::from_le_bytes([footer[6], footer[7]]);
+    if version > CONTAINER_VERSION {
+        return Err(LrsError::CorruptFile(format!(
+            "unknown container version {version}"
+        )));
+    }
+    if crc32fast::hash(payload) != checksum {
+        return Err(LrsError::CorruptFile(
+            "the checksum does not match the content".to_owned(),
+        ));
+    }
+    if flags & FLAG_ZSTD == 0 {
+        return Ok(Cow::Borrowed(payload));
+    }
+    decompress(payload, MAX_DECOMPRESSED_LEN).map(Cow::Owned)
+}
+
+#[cfg(feature = "compression")]
+fn decompress(compressed: &[u8], max_len: u64) -> Result<Vec<u8>, LrsError> {
+    let decoder = zstd::stream::read::Decoder::new(compressed)
+        .map_err(|err| LrsError::CorruptFile(err.to_string()))?;
+    read_at_most(decoder, max_len)
+}
+
+/// Without the `compression` feature, the pure Rust decoder also builds for WebAssembly.
+#[cfg(all(feature = "decompression", not(feature = "compression")))]
+fn decompress(compressed: &[u8], max_len: u64) -> Result<Vec<u8>, LrsError> {
+    let decoder = ruzstd::decoding::StreamingDecoder::new(compressed)
+        .map_err(|err| LrsError::CorruptFile(err.to_string()))?;
+    read_at_most(decoder, max_len)
+}
+
+/// Reads the decompressed payload, failing as soon as it gets longer than `max_len` bytes.
+#[cfg(any(feature = "compression", feature = "decompression"))]
+fn read_at_most(decoder: impl std::io::Read, max_len: u64) -> Result<Vec<u8>, LrsError> {
+    use std::io::Read;
+
+    let mut payload = vec![];
+    decoder
+        .take(max_len.saturating_add(1))
+        .read_to_end(&mut payload)
+        .map_err(|err| LrsError::CorruptFile(err.to_string()))?;
+    if payload.len() as u64 > max_len {
+        return Err(LrsError::CorruptFile(format!(
+            "the decompressed payload is longer than {max_len} bytes"
+        )));
+    }
+    Ok(payload)
+}
+
+#[cfg(not(any(feature = "compression", feature = "decompression")))]
+fn decompress(_compressed: &[u8], _max_len: u64) -> Result<Vec<u8>, LrsError> {
+    Err(LrsError::UnsupportedFile(
+        "the file is compressed, the `compression` or `decompression` feature is required"
+            .to_owned(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::PlanarLineStringCurve;
+    use crate::lrs::tests::lrs;
+    use crate::lrs::{AnyLrs, GeometryType, Lrs};
+
+    #[test]
+    fn integrity() {
+        let data = lrs().to_bytes();
+        assert!(data.ends_with(MAGIC));
+        assert!(Lrs::<PlanarLineStringCurve>::from_bytes(&data).is_ok());
+
+        let truncated = &data[..data.len() / 2];
+        assert!(matches!(
+            Lrs::<PlanarLineStringCurve>::from_bytes(truncated),
+            Err(LrsError::CorruptFile(_))
+        ));
+
+        let mut altered = data.clone();
+        altered[data.len() / 2] ^= 0xff;
+        assert!(matches!(
+            Lrs::<PlanarLineStringCurve>::from_bytes(&altered),
+            Err(LrsError::CorruptFile(_))
+        ));
+
+        // A file written before the footer was introduced is given as is to FlatBuffers
+        let legacy = b"neither identifier nor footer";
+        assert_eq!(open(legacy), Ok(Cow::Borrowed(&legacy[..])));
+    }
+
+    fn file_with_schema_version(schema_version: u32) -> Vec<u8> {
+        let mut fbb = flatbuffers::FlatBufferBuilder::new();
+        let anchors = fbb.create_vector::<flatbuffers::WIPOffset<lrs_generated::Anchor>>(&[]);
+        let args = lrs_generated::LrsArgs {
+            anchors: Some(anchors),
+            geometry_type: lrs_generated::GeometryType::Schematic,
+            schema_version,
+            ..Default::default()
+        };
+        let lrs = lrs_generated::Lrs::create(&mut fbb, &args);
+        fbb.finish(lrs, None);
+        fbb.finished_data().to_vec()
+    }
+
+    #[test]
+    fn schema_versions() {
+        let current = lrs().to_bytes();
+        assert_eq!(schema_version(&current), Ok(SCHEMA_VERSION));
+
+        let previous = file_with_schema_version(0);
+        assert!(Lrs::<PlanarLineStringCurve>::from_bytes(&previous).is_ok());
+        let upgraded = Lrs::<PlanarLineStringCurve>::upgrade_bytes(&previous).unwrap();
+        assert_eq!(schema_version(&upgraded), Ok(SCHEMA_VERSION));
+        let upgraded = AnyLrs::upgrade_bytes(&previous).unwrap();
+        assert_eq!(
+            GeometryType::from_bytes(&upgraded),
+            Ok(GeometryType::Schematic)
+        );
+        assert_eq!(schema_version(&upgraded), Ok(SCHEMA_VERSION));
+
+        let next = file_with_schema_version(SCHEMA_VERSION + 1);
+        assert!(matches!(
+            Lrs::<PlanarLineStringCurve>::from_bytes(&next),
+            Err(LrsError::UnsupportedFile(_))
+        ));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compression() {
+        let lrs = lrs();
+        let data = lrs.to_compressed_bytes();
+        let flags = &data[data.len() - 6..data.len() - 4];
+        assert_eq!(flags, FLAG_ZSTD.to_le_bytes());
+        let decompressed = Lrs::<PlanarLineStringCurve>::from_bytes(&data).unwrap();
+        assert_eq!(decompressed.lrms.len(), lrs.lrms.len());
+        assert_eq!(decompressed.lrms[1].scale, lrs.lrms[1].scale);
+        assert_eq!(decompressed.properties, lrs.properties);
+        // Upgrading a compressed file keeps it compressed
+        assert!(is_compressed(&AnyLrs::upgrade_bytes(&data).unwrap()));
+
+        let payload = &data[..data.len() - FOOTER_LEN];
+        let len = decompress(payload, u64::MAX).unwrap().len() as u64;
+        assert_eq!(
+            decompress(payload, len).map(|payload| payload.len() as u64),
+            Ok(len)
+        );
+        assert!(matches!(
+            decompress(payload, len - 1),
+            Err(LrsError::CorruptFile(_))
+        ));
+    }
+}
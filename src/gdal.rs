@@ -0,0 +1,186 @@
+//! Reads the layers of any vector format supported by GDAL with [`Builder::from_gdal`].
+
+use std::collections::HashMap;
+
+use geo::{Coord, LineString};
+
+use crate::builder::{AnchorOnLrm, Builder, BuilderError};
+use crate::properties;
+
+/// Names of the layers and fields read by [`Builder::from_gdal`].
+///
+/// Both layers can be the same, as only the LineStrings are read as traversals and the Points as anchors.
+#[derive(Clone, Debug)]
+pub struct GdalMapping {
+    /// Layer of the LineStrings, each a `Traversal` with its LRM.
+    pub traversals: String,
+    /// Field identifying the `Traversal` and its LRM.
+    pub traversal_id: String,
+    /// Layer of the Points, each an `Anchor`.
+    pub anchors: String,
+    /// Field with the id of the LRM of the `Anchor`.
+    pub lrm_id: String,
+    /// Field with the name of the `Anchor`, that is unnamed when the field is empty.
+    pub anchor_name: String,
+    /// Field with the measure of the `Anchor`.
+    pub measure: String,
+}
+
+impl Default for GdalMapping {
+    fn default() -> Self {
+        Self {
+            traversals: "traversals".to_owned(),
+            traversal_id: "id".to_owned(),
+            anchors: "anchors".to_owned(),
+            lrm_id: "lrm_id".to_owned(),
+            anchor_name: "name".to_owned(),
+            measure: "measure".to_owned(),
+        }
+    }
+}
+
+impl Builder<'_> {
+    /// Read the topology and the `Anchor`s from the layers of any vector format supported by GDAL (GML, FileGDB, DXF…).
+    ///
+    /// Each LineString of the `traversals` layer becomes a `Traversal` and an LRM with the same `id`.
+    /// Extremities with the same coordinates share the same `Node`, where the `Traversal`s continuing each other are connected,
+    /// see [`Builder::connect_traversal_extremities`].
+    /// Each Point of the `anchors` layer becomes an `Anchor` of the LRM named by its `lrm_id` field, at its `measure`.
+    /// The names of the layers and fields are given by the `mapping`, the other fields and geometries are ignored.
+    /// The coordinate reference system of the `traversals` layer is kept when it has an authority code.
+    pub fn from_gdal(
+        dataset: &::gdal::Dataset,
+        mapping: &GdalMapping,
+    ) -> Result<Self, BuilderError> {
+        use ::gdal::vector::LayerAccess;
+
+        let gdal_error = |e: ::gdal::errors::GdalError| BuilderError::Gdal(e.to_string());
+        let missing = |feature_index: usize, property: &str| BuilderError::MissingProperty {
+            feature_index,
+            property: property.to_owned(),
+        };
+        let to_coord = |(x, y, _z): (f64, f64, f64)| Coord { x, y };
+
+        let mut builder = Self::new();
+        let mut nodes = HashMap::<(u64, u64), usize>::new();
+        let mut lrms = vec![];
+        let mut layer = dataset
+            .layer_by_name(&mapping.traversals)
+            .map_err(gdal_error)?;
+        if let Some(srs) = layer.spatial_ref() {
+            if let (Ok(authority), Ok(code)) = (srs.auth_name(), srs.auth_code()) {
+                builder.set_crs(&format!("{authority}:{code}"));
+            }
+        }
+        for (feature_index, feature) in layer.features().enumerate() {
+            let Some(geometry) = feature
+                .geometry()
+                .filter(|geometry| geometry.geometry_name() == "LINESTRING")
+            else {
+                continue;
+            };
+            let id = feature
+                .field_as_string_by_name(&mapping.traversal_id)
+                .map_err(gdal_error)?
+                .ok_or_else(|| missing(feature_index, &mapping.traversal_id))?;
+            let geometry: LineString = geometry.get_point_vec().into_iter().map(to_coord).collect();
+            let traversal = builder
+                .add_traversal_with_shared_nodes(&id, &geometry, &mut nodes)
+                .ok_or_else(|| BuilderError::InvalidGeometry(id.clone()))?;
+            lrms.push((id, traversal, vec![]));
+        }
+
+        let mut layer = dataset
+            .layer_by_name(&mapping.anchors)
+            .map_err(gdal_error)?;
+        for (feature_index, feature) in layer.features().enumerate() {
+            let Some(geometry) = feature
+                .geometry()
+                .filter(|geometry| geometry.geometry_name() == "POINT")
+            else {
+                continue;
+            };
+            let lrm_id = feature
+                .field_as_string_by_name(&mapping.lrm_id)
+                .map_err(gdal_error)?
+                .ok_or_else(|| missing(feature_index, &mapping.lrm_id))?;
+            let distance_along_lrm = feature
+                .field_as_double_by_name(&mapping.measure)
+                .map_err(gdal_error)?
+                .ok_or_else(|| missing(feature_index, &mapping.measure))?;
+            let Some((_, _, anchors)) = lrms.iter_mut().find(|(id, _, _)| *id == lrm_id) else {
+                return Err(BuilderError::UnknownLrm {
+                    feature_index,
+                    lrm_id,
+                });
+            };
+            let name = feature
+                .field_as_string_by_name(&mapping.anchor_name)
+                .map_err(gdal_error)?
+                .filter(|name| !name.is_empty());
+            let id = name.clone().unwrap_or_else(|| feature_index.to_string());
+            let anchor_index = builder.add_anchor(
+                &id,
+                name.as_deref(),
+                to_coord(geometry.get_point(0)),
+                properties!(),
+            );
+            anchors.push(AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm,
+            });
+        }
+
+        for (id, traversal, anchors) in lrms {
+            builder.add_lrm(&id, traversal, &anchors, properties!());
+        }
+        builder.connect_traversal_extremities();
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::builder::tests::geographic;
+    use crate::lrm_scale::LrmScaleMeasure;
+
+    use super::*;
+
+    #[test]
+    fn from_gdal() {
+        let path = std::env::temp_dir().join(format!("liblrs-{}-gdal.geojson", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"type": "FeatureCollection", "name": "network", "features": [
+            {"type": "Feature", "properties": {"line": "a"},
+             "geometry": {"type": "LineString", "coordinates": [[0, 0], [0.02, 0]]}},
+            {"type": "Feature", "properties": {"lrm": "a", "km": "0", "pk": 0},
+             "geometry": {"type": "Point", "coordinates": [0, 0]}},
+            {"type": "Feature", "properties": {"lrm": "a", "km": "1", "pk": 1000},
+             "geometry": {"type": "Point", "coordinates": [0.01, 0]}}
+        ]}"#,
+        )
+        .unwrap();
+        let mapping = GdalMapping {
+            traversals: "network".to_owned(),
+            traversal_id: "line".to_owned(),
+            anchors: "network".to_owned(),
+            lrm_id: "lrm".to_owned(),
+            anchor_name: "km".to_owned(),
+            measure: "pk".to_owned(),
+        };
+        let builder = ::gdal::Dataset::open(&path)
+            .map_err(|e| BuilderError::Gdal(e.to_string()))
+            .and_then(|dataset| Builder::from_gdal(&dataset, &mapping));
+        std::fs::remove_file(&path).unwrap();
+
+        let lrs = builder.unwrap().build_lrs(properties!()).unwrap();
+        assert_eq!(geographic(&lrs).traversals[0].id, "a");
+        assert_eq!(geographic(&lrs).lrms[0].scale.id, "a");
+        assert_eq!(geographic(&lrs).lrms[0].scale.anchors.len(), 2);
+        let point = lrs.resolve(0, &LrmScaleMeasure::new("0", 500.)).unwrap();
+        assert_relative_eq!(point.x(), 0.005, epsilon = 1e-6);
+    }
+}
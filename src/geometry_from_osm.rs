@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use clap::Parser;
 
-use liblrs::{builder::Builder, properties};
+use liblrs::{builder::Builder, curves::SphericalLineStringCurve, lrs::Lrs, properties};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -19,6 +19,20 @@ struct Args {
     /// OpenStreetMap tag identifying the LRM. The french railway network uses `ref:FR:SNCF_Reseau`.
     #[arg(short, long)]
     lrm_tag: String,
+
+    /// Compress the output file. It is then only readable by liblrs with the `compression` feature.
+    #[cfg(feature = "compression")]
+    #[arg(long)]
+    compress: bool,
+
+    /// Build the traversals from the railway route relations, with the milestones as anchors.
+    #[cfg(feature = "osm-routes")]
+    #[arg(long)]
+    routes: bool,
+
+    /// Print a summary of the generated [`Lrs`] to sanity-check it.
+    #[arg(long)]
+    stats: bool,
 }
 
 /// Example: to generate an LRS from an OpenStreetMap dump
@@ -39,16 +53,40 @@ fn main() {
     );
 
     let mut builder = Builder::new();
-    builder.read_from_osm(
-        &cli_args.input_osm_file,
-        &cli_args.lrm_tag,
-        required,
-        to_reject,
-        Some(&mut liblrs::LoggingDataIssueReporter),
-    );
+    #[cfg(feature = "osm-routes")]
+    let routes = cli_args.routes;
+    #[cfg(not(feature = "osm-routes"))]
+    let routes = false;
+    if routes {
+        #[cfg(feature = "osm-routes")]
+        if let Err(e) = builder.read_osm_routes(
+            &cli_args.input_osm_file,
+            &cli_args.lrm_tag,
+            Some(&mut liblrs::LoggingDataIssueReporter),
+        ) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    } else {
+        builder.read_from_osm(
+            &cli_args.input_osm_file,
+            &cli_args.lrm_tag,
+            required,
+            to_reject,
+            Some(&mut liblrs::LoggingDataIssueReporter),
+        );
+    }
 
+    #[cfg(feature = "compression")]
+    builder.set_compression(cli_args.compress);
     builder.save(
         &cli_args.output_lrs,
         properties!("source" => "OpenStreetMap", "licence" => "OdBL"),
     );
+
+    if cli_args.stats {
+        let lrs = Lrs::<SphericalLineStringCurve>::new(&cli_args.output_lrs)
+            .expect("the generated file should be readable");
+        print!("{}", lrs.stats());
+    }
 }
@@ -0,0 +1,406 @@
+//! Reads and writes an [`Lrs`] as a GeoPackage, the SQLite based format of the OGC.
+//!
+//! Each concept is a table (a layer in a GIS):
+//! - the traversals: a `geom` LineString and an `id`,
+//! - the LRMs: an `id` and the `traversal_id` of their traversal, without geometry,
+//! - the anchors: a `geom` Point, the `lrm_id` of their LRM, an optional `name` and their `measure`.
+//!
+//! The properties are not kept.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use geo::{LineString, Point};
+use rusqlite::{Connection, params};
+use thiserror::Error;
+
+use crate::builder::{AnchorOnLrm, Builder};
+use crate::curves::Curve;
+use crate::lrm_scale::Anchor;
+use crate::lrs::{Lrs, LrsError};
+use crate::properties;
+use crate::wkb;
+
+/// Errors when reading or writing a GeoPackage.
+#[derive(Debug, Error)]
+pub enum GeoPackageError {
+    /// The SQLite database could not be read or written.
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    /// A geometry is not a GeoPackage LineString or Point.
+    #[error("invalid geometry in table {0}")]
+    InvalidGeometry(String),
+    /// An LRM references a traversal that is not in the traversals table.
+    #[error("lrm {lrm_id} references the unknown traversal {traversal_id}")]
+    UnknownTraversal {
+        /// `id` of the LRM.
+        lrm_id: String,
+        /// `id` of the missing traversal.
+        traversal_id: String,
+    },
+    /// An anchor references an LRM that is not in the LRMs table.
+    #[error("an anchor references the unknown lrm {0}")]
+    UnknownLrm(String),
+    /// The built [`Lrs`] could not be loaded.
+    #[error(transparent)]
+    Lrs(#[from] LrsError),
+}
+
+/// Names of the tables of a GeoPackage.
+#[derive(Clone, Debug)]
+pub struct LayerMapping {
+    /// Table of the traversals.
+    pub traversals: String,
+    /// Table of the LRMs.
+    pub lrms: String,
+    /// Table of the anchors.
+    pub anchors: String,
+}
+
+impl Default for LayerMapping {
+    fn default() -> Self {
+        Self {
+            traversals: "traversals".to_owned(),
+            lrms: "lrms".to_owned(),
+            anchors: "anchors".to_owned(),
+        }
+    }
+}
+
+// WGS 84, the only coordinate reference system identified when writing
+const WGS84: i32 = 4326;
+// Undefined geographic coordinate reference system
+const UNDEFINED_SRS: i32 = 0;
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Reads an [`Lrs`] from the tables of a GeoPackage, named by the `layer_mapping`.
+    ///
+    /// The traversals continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    /// The anchors are projected on the traversal of their LRM.
+    pub fn from_geopackage<P: AsRef<Path>>(
+        path: P,
+        layer_mapping: &LayerMapping,
+    ) -> Result<Self, GeoPackageError> {
+        let mut builder = Builder::new();
+        builder.add_source(&path.as_ref().to_string_lossy());
+        let connection = Connection::open(path)?;
+
+        let srs = connection
+            .query_row(
+                "SELECT s.organization, s.organization_coordsys_id FROM gpkg_spatial_ref_sys s
+                 JOIN gpkg_geometry_columns g ON g.srs_id = s.srs_id WHERE g.table_name = ?1",
+                params![layer_mapping.traversals],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)),
+            )
+            .ok();
+        if let Some((organization, code)) = srs.filter(|(organization, _)| organization != "NONE") {
+            builder.set_crs(&format!("{organization}:{code}"));
+        }
+
+        let mut traversals = HashMap::new();
+        let mut nodes = HashMap::new();
+        let sql = format!("SELECT geom, id FROM {}", quote(&layer_mapping.traversals));
+        for row in connection.prepare(&sql)?.query_map([], |row| {
+            Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (geom, id) = row?;
+            let geometry = read_linestring(&geom).ok_or_else(|| {
+                GeoPackageError::InvalidGeometry(layer_mapping.traversals.clone())
+            })?;
+            let traversal = builder
+                .add_traversal_with_shared_nodes(&id, &geometry, &mut nodes)
+                .ok_or_else(|| {
+                    GeoPackageError::InvalidGeometry(layer_mapping.traversals.clone())
+                })?;
+            traversals.entry(id).or_insert(traversal);
+        }
+
+        let mut lrms: Vec<(String, usize, Vec<AnchorOnLrm>)> = vec![];
+        let sql = format!(
+            "SELECT id, traversal_id FROM {}",
+            quote(&layer_mapping.lrms)
+        );
+        for row in connection.prepare(&sql)?.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (lrm_id, traversal_id) = row?;
+            let traversal = *traversals.get(&traversal_id).ok_or_else(|| {
+                GeoPackageError::UnknownTraversal {
+                    lrm_id: lrm_id.clone(),
+                    traversal_id,
+                }
+            })?;
+            lrms.push((lrm_id, traversal, vec![]));
+        }
+
+        let sql = format!(
+            "SELECT geom, lrm_id, name, measure FROM {}",
+            quote(&layer_mapping.anchors)
+        );
+        for row in connection.prepare(&sql)?.query_map([], |row| {
+            Ok((
+                row.get::<_, Vec<u8>>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, f64>(3)?,
+            ))
+        })? {
+            let (geom, lrm_id, name, distance_along_lrm) = row?;
+            let point = read_point(&geom)
+                .ok_or_else(|| GeoPackageError::InvalidGeometry(layer_mapping.anchors.clone()))?;
+            let Some((_, _, anchors)) = lrms.iter_mut().find(|(id, _, _)| *id == lrm_id) else {
+                return Err(GeoPackageError::UnknownLrm(lrm_id));
+            };
+            let id = name
+                .clone()
+                .unwrap_or_else(|| distance_along_lrm.to_string());
+            let anchor_index =
+                builder.add_anchor(&id, name.as_deref(), point.into(), properties!());
+            anchors.push(AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm,
+            });
+        }
+
+        for (id, traversal, anchors) in lrms {
+            builder.add_lrm(&id, traversal, &anchors, properties!());
+        }
+        builder.connect_traversal_extremities();
+        Ok(Self::from_bytes(builder.build_data(properties!()))?)
+    }
+
+    /// Writes the [`Lrs`] in a new GeoPackage, with the default [`LayerMapping`].
+    ///
+    /// The coordinates are declared in WGS 84 if the [`Lrs`] has no other coordinate reference system.
+    /// The anchors without a point are placed on their traversal.
+    pub fn to_geopackage<P: AsRef<Path>>(&self, path: P) -> Result<(), GeoPackageError> {
+        let mut connection = Connection::open(path)?;
+        let srs_id = match self.crs.as_deref() {
+            None | Some("EPSG:4326") => WGS84,
+            Some(_) => UNDEFINED_SRS,
+        };
+        let layers = LayerMapping::default();
+
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(METADATA)?;
+        for (table, geometry_type) in [
+            (&layers.traversals, Some("LINESTRING")),
+            (&layers.lrms, None),
+            (&layers.anchors, Some("POINT")),
+        ] {
+            let data_type = if geometry_type.is_some() {
+                "features"
+            } else {
+                "attributes"
+            };
+            transaction.execute(
+                "INSERT INTO gpkg_contents (table_name, data_type, identifier, srs_id) VALUES (?1, ?2, ?1, ?3)",
+                params![table, data_type, geometry_type.map(|_| srs_id)],
+            )?;
+            if let Some(geometry_type) = geometry_type {
+                transaction.execute(
+                    "INSERT INTO gpkg_geometry_columns VALUES (?1, 'geom', ?2, ?3, 0, 0)",
+                    params![table, geometry_type, srs_id],
+                )?;
+            }
+        }
+        transaction.execute_batch(&format!(
+            "CREATE TABLE {} (fid INTEGER PRIMARY KEY AUTOINCREMENT, geom LINESTRING, id TEXT NOT NULL);
+             CREATE TABLE {} (fid INTEGER PRIMARY KEY AUTOINCREMENT, id TEXT NOT NULL, traversal_id TEXT NOT NULL);
+             CREATE TABLE {} (fid INTEGER PRIMARY KEY AUTOINCREMENT, geom POINT, lrm_id TEXT NOT NULL, name TEXT, measure DOUBLE NOT NULL);",
+            quote(&layers.traversals),
+            quote(&layers.lrms),
+            quote(&layers.anchors),
+        ))?;
+
+        for traversal in &self.traversals {
+            let geom = traversal
+                .curve
+                .is_valid()
+                .then(|| write_linestring(&traversal.curve.as_linestring(), srs_id));
+            transaction.execute(
+                &format!(
+                    "INSERT INTO {} (geom, id) VALUES (?1, ?2)",
+                    quote(&layers.traversals)
+                ),
+                params![geom, traversal.id],
+            )?;
+        }
+        for lrm in &self.lrms {
+            let traversal = self.traversal(lrm.traversal)?;
+            transaction.execute(
+                &format!(
+                    "INSERT INTO {} (id, traversal_id) VALUES (?1, ?2)",
+                    quote(&layers.lrms)
+                ),
+                params![lrm.scale.id, traversal.id],
+            )?;
+            for anchor in &lrm.scale.anchors {
+                let geom = self
+                    .anchor_point(lrm, anchor)
+                    .map(|point| write_point(point, srs_id));
+                let name = match anchor {
+                    Anchor::Named(anchor) => Some(&anchor.name),
+                    Anchor::Unnamed(_) => None,
+                };
+                transaction.execute(
+                    &format!(
+                        "INSERT INTO {} (geom, lrm_id, name, measure) VALUES (?1, ?2, ?3, ?4)",
+                        quote(&layers.anchors)
+                    ),
+                    params![geom, lrm.scale.id, name, anchor.scale_position()],
+                )?;
+            }
+        }
+        Ok(transaction.commit()?)
+    }
+}
+
+// Tables required by the GeoPackage specification, version 1.3
+const METADATA: &str = r#"
+PRAGMA application_id = 1196444487;
+PRAGMA user_version = 10300;
+CREATE TABLE gpkg_spatial_ref_sys (
+    srs_name TEXT NOT NULL,
+    srs_id INTEGER PRIMARY KEY,
+    organization TEXT NOT NULL,
+    organization_coordsys_id INTEGER NOT NULL,
+    definition TEXT NOT NULL,
+    description TEXT
+);
+INSERT INTO gpkg_spatial_ref_sys VALUES
+    ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system'),
+    ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system'),
+    ('WGS 84 geodetic', 4326, 'EPSG', 4326, 'GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AUTHORITY["EPSG","4326"]]', 'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid');
+CREATE TABLE gpkg_contents (
+    table_name TEXT NOT NULL PRIMARY KEY,
+    data_type TEXT NOT NULL,
+    identifier TEXT UNIQUE,
+    description TEXT DEFAULT '',
+    last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    min_x DOUBLE,
+    min_y DOUBLE,
+    max_x DOUBLE,
+    max_y DOUBLE,
+    srs_id INTEGER REFERENCES gpkg_spatial_ref_sys(srs_id)
+);
+CREATE TABLE gpkg_geometry_columns (
+    table_name TEXT NOT NULL REFERENCES gpkg_contents(table_name),
+    column_name TEXT NOT NULL,
+    geometry_type_name TEXT NOT NULL,
+    srs_id INTEGER NOT NULL REFERENCES gpkg_spatial_ref_sys(srs_id),
+    z TINYINT NOT NULL,
+    m TINYINT NOT NULL,
+    PRIMARY KEY (table_name, column_name)
+);
+"#;
+
+// A table name as an SQL identifier
+fn quote(table: &str) -> String {
+    format!("\"{}\"", table.replace('"', "\"\""))
+}
+
+// The GeoPackage header, without envelope, before the WKB
+fn write_header(srs_id: i32) -> Vec<u8> {
+    let mut blob = vec![b'G', b'P', 0, 0b0000_0001];
+    blob.extend(srs_id.to_le_bytes());
+    blob
+}
+
+fn write_point(point: Point, srs_id: i32) -> Vec<u8> {
+    let mut blob = write_header(srs_id);
+    blob.extend(wkb::write_point(point));
+    blob
+}
+
+fn write_linestring(linestring: &LineString, srs_id: i32) -> Vec<u8> {
+    let mut blob = write_header(srs_id);
+    blob.extend(wkb::write_linestring(linestring));
+    blob
+}
+
+// The WKB of a GeoPackage geometry, after its header and envelope
+fn geometry_wkb(blob: &[u8]) -> Option<&[u8]> {
+    if blob.get(..2)? != b"GP" {
+        return None;
+    }
+    let envelope_len = match (blob.get(3)? >> 1) & 0b111 {
+        0 => 0,
+        1 => 32,
+        2 | 3 => 48,
+        4 => 64,
+        _ => return None,
+    };
+    blob.get(8 + envelope_len..)
+}
+
+fn read_point(blob: &[u8]) -> Option<Point> {
+    wkb::read_point(geometry_wkb(blob)?)
+}
+
+fn read_linestring(blob: &[u8]) -> Option<LineString> {
+    wkb::read_linestring(geometry_wkb(blob)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use geo::{line_string, point};
+
+    use crate::builder::tests::geographic_lrs;
+    use crate::curves::SphericalLineStringCurve;
+    use crate::lrm_scale::LrmScaleMeasure;
+    use crate::lrs::LrmHandle;
+
+    use super::*;
+
+    #[test]
+    fn geometries() {
+        let linestring = line_string![(x: 0., y: 1.), (x: 2., y: 3.)];
+        assert_eq!(
+            read_linestring(&write_linestring(&linestring, WGS84)),
+            Some(linestring)
+        );
+        let point = point! {x: 1., y: 2.};
+        assert_eq!(read_point(&write_point(point, WGS84)), Some(point));
+        assert_eq!(read_point(&write_linestring(&line_string![], WGS84)), None);
+        assert_eq!(read_point(b"GP"), None);
+
+        // A big endian point with Z and an envelope [minx, maxx, miny, maxy]
+        let mut blob = vec![b'G', b'P', 0, 0b0000_0010, 0, 0, 0x10, 0xE6];
+        for value in [1., 1., 2., 2.] {
+            blob.extend(f64::to_be_bytes(value));
+        }
+        blob.push(0);
+        blob.extend(1001_u32.to_be_bytes());
+        for value in [1., 2., 3.] {
+            blob.extend(f64::to_be_bytes(value));
+        }
+        assert_eq!(read_point(&blob), Some(point));
+    }
+
+    #[test]
+    fn write_and_read() {
+        let path = std::env::temp_dir().join(format!("liblrs-{}.gpkg", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let lrs = geographic_lrs();
+        lrs.to_geopackage(&path).unwrap();
+        let read =
+            Lrs::<SphericalLineStringCurve>::from_geopackage(&path, &LayerMapping::default());
+        std::fs::remove_file(&path).unwrap();
+
+        let read = read.unwrap();
+        assert_eq!(read.lrm_len(), 1);
+        assert_eq!(read.lrms[0].scale.id, "lrm");
+        assert_eq!(read.lrms[0].scale.anchors.len(), 2);
+        assert_eq!(read.traversals[0].id, "traversal");
+        assert_eq!(read.nodes.len(), 2);
+        assert_eq!(read.crs, Some("EPSG:4326".to_owned()));
+        let measure = LrmScaleMeasure::new("0", 1500.);
+        let expected = lrs.resolve(LrmHandle(0), &measure).unwrap();
+        let point = read.resolve(LrmHandle(0), &measure).unwrap();
+        assert_relative_eq!(point.x(), expected.x(), epsilon = 1e-6);
+        assert_relative_eq!(point.y(), expected.y(), epsilon = 1e-6);
+    }
+}
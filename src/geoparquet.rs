@@ -0,0 +1,513 @@
+//! Exports an [`Lrs`] as Arrow tables and GeoParquet files, to join it with analytics pipelines (DuckDB, Spark…),
+//! and builds an LRS from GeoParquet files with [`Builder::from_geoparquet`].
+//!
+//! There are three tables, each with a `geometry` column encoded as WKB:
+//! - `lrms`: the `id` of the LRM, the `traversal_id` of its traversal and the LineString of the traversal,
+//! - `anchors`: the `lrm_id` of their LRM, an optional `name`, their `measure` and their Point,
+//! - `events`: the `id` and `lrm_id` of the point and linear events, their `begin_anchor` and `begin_offset`,
+//!   the `end_anchor` and `end_offset` of the linear events, and their Point or LineString.
+//!
+//! The geometries that cannot be computed are null. The properties are not kept.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray, BinaryArray, Float64Array, RecordBatch, StringArray};
+use arrow::datatypes::{DataType, Field, Float64Type, Schema};
+use arrow::error::ArrowError;
+use geo::{LineString, Point};
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use parquet::format::KeyValue;
+use thiserror::Error;
+
+use crate::builder::{AnchorOnLrm, Builder};
+use crate::curves::Curve;
+use crate::lrm_scale::{Anchor, LrmScaleMeasure};
+use crate::lrs::{LrmHandle, Lrs, LrsError};
+use crate::properties;
+use crate::wkb;
+
+/// Errors when reading or writing GeoParquet.
+#[derive(Debug, Error)]
+pub enum GeoParquetError {
+    /// A table could not be built.
+    #[error(transparent)]
+    Arrow(#[from] ArrowError),
+    /// A table could not be written as Parquet.
+    #[error(transparent)]
+    Parquet(#[from] ParquetError),
+    /// A file could not be opened or created.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The [`Lrs`] is inconsistent.
+    #[error(transparent)]
+    Lrs(#[from] LrsError),
+    /// A column of the [`GeoParquetMapping`] is missing.
+    #[error("missing column {0}")]
+    MissingColumn(String),
+    /// A column has an unexpected type, or a null where a value is required.
+    #[error("invalid value in column {0}")]
+    InvalidValue(String),
+    /// The geometry of a traversal, or of an anchor of an LRM, is not a valid WKB LineString or Point.
+    #[error("invalid geometry for {0}")]
+    InvalidGeometry(String),
+    /// An anchor references an LRM that is not in the traversals table.
+    #[error("an anchor references the unknown lrm {0}")]
+    UnknownLrm(String),
+}
+
+/// Names of the columns read by [`Builder::from_geoparquet`].
+///
+/// The default names are the ones written by [`Lrs::to_geoparquet`].
+#[derive(Clone, Debug)]
+pub struct GeoParquetMapping {
+    /// Identifier of a traversal, and of its LRM.
+    pub traversal_id: String,
+    /// WKB LineString of a traversal.
+    pub traversal_geometry: String,
+    /// Identifier of the LRM of an anchor.
+    pub anchor_lrm_id: String,
+    /// Name of an anchor, null when the anchor is unnamed.
+    pub anchor_name: String,
+    /// Measure of an anchor along its LRM.
+    pub anchor_measure: String,
+    /// WKB Point of an anchor.
+    pub anchor_geometry: String,
+}
+
+impl Default for GeoParquetMapping {
+    fn default() -> Self {
+        Self {
+            traversal_id: "id".to_owned(),
+            traversal_geometry: "geometry".to_owned(),
+            anchor_lrm_id: "lrm_id".to_owned(),
+            anchor_name: "name".to_owned(),
+            anchor_measure: "measure".to_owned(),
+            anchor_geometry: "geometry".to_owned(),
+        }
+    }
+}
+
+impl Builder<'_> {
+    /// Reads the traversals and the anchors from two GeoParquet files,
+    /// such as the `lrms.parquet` and `anchors.parquet` written by [`Lrs::to_geoparquet`].
+    ///
+    /// Each traversal gets an LRM with the same id, and the anchors are projected on the traversal of their LRM.
+    /// The traversals continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    pub fn from_geoparquet<P: AsRef<Path>, Q: AsRef<Path>>(
+        traversals: P,
+        anchors: Q,
+        mapping: &GeoParquetMapping,
+    ) -> Result<Self, GeoParquetError> {
+        let mut builder = Self::new();
+        builder.add_source(&traversals.as_ref().to_string_lossy());
+
+        let mut lrms: Vec<(String, usize, Vec<AnchorOnLrm>)> = vec![];
+        let mut nodes = HashMap::new();
+        for batch in read_batches(traversals)? {
+            let batch = batch?;
+            let ids = string_column(&batch, &mapping.traversal_id)?;
+            let geometries = binary_column(&batch, &mapping.traversal_geometry)?;
+            for row in 0..batch.num_rows() {
+                let id = ids
+                    .is_valid(row)
+                    .then(|| ids.value(row))
+                    .ok_or_else(|| GeoParquetError::InvalidValue(mapping.traversal_id.clone()))?;
+                let traversal = geometries
+                    .is_valid(row)
+                    .then(|| wkb::read_linestring(geometries.value(row)))
+                    .flatten()
+                    .and_then(|geometry| {
+                        builder.add_traversal_with_shared_nodes(id, &geometry, &mut nodes)
+                    })
+                    .ok_or_else(|| GeoParquetError::InvalidGeometry(id.to_owned()))?;
+                lrms.push((id.to_owned(), traversal, vec![]));
+            }
+        }
+
+        for batch in read_batches(anchors)? {
+            let batch = batch?;
+            let lrm_ids = string_column(&batch, &mapping.anchor_lrm_id)?;
+            let names = string_column(&batch, &mapping.anchor_name)?;
+            let measures = column(&batch, &mapping.anchor_measure)?
+                .as_primitive_opt::<Float64Type>()
+                .ok_or_else(|| GeoParquetError::InvalidValue(mapping.anchor_measure.clone()))?;
+            let geometries = binary_column(&batch, &mapping.anchor_geometry)?;
+            for row in 0..batch.num_rows() {
+                let lrm_id = lrm_ids
+                    .is_valid(row)
+                    .then(|| lrm_ids.value(row))
+                    .ok_or_else(|| GeoParquetError::InvalidValue(mapping.anchor_lrm_id.clone()))?;
+                let distance_along_lrm = measures
+                    .is_valid(row)
+                    .then(|| measures.value(row))
+                    .ok_or_else(|| GeoParquetError::InvalidValue(mapping.anchor_measure.clone()))?;
+                let point = geometries
+                    .is_valid(row)
+                    .then(|| wkb::read_point(geometries.value(row)))
+                    .flatten()
+                    .ok_or_else(|| GeoParquetError::InvalidGeometry(lrm_id.to_owned()))?;
+                let Some((_, _, anchors)) = lrms.iter_mut().find(|(id, _, _)| id == lrm_id) else {
+                    return Err(GeoParquetError::UnknownLrm(lrm_id.to_owned()));
+                };
+                let name = names.is_valid(row).then(|| names.value(row));
+                let id = name.map_or_else(|| distance_along_lrm.to_string(), str::to_owned);
+                let anchor_index = builder.add_anchor(&id, name, point.into(), properties!());
+                anchors.push(AnchorOnLrm {
+                    anchor_index,
+                    distance_along_lrm,
+                });
+            }
+        }
+
+        for (id, traversal, anchors) in lrms {
+            builder.add_lrm(&id, traversal, &anchors, properties!());
+        }
+        builder.connect_traversal_extremities();
+        Ok(builder)
+    }
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// One row per LRM, with the geometry of its traversal.
+    pub fn lrms_record_batch(&self) -> Result<RecordBatch, GeoParquetError> {
+        let mut ids = vec![];
+        let mut traversal_ids = vec![];
+        let mut geometries = vec![];
+        for lrm in &self.lrms {
+            let traversal = self.traversal(lrm.traversal)?;
+            ids.push(lrm.scale.id.clone());
+            traversal_ids.push(traversal.id.clone());
+            geometries.push(
+                traversal
+                    .curve
+                    .is_valid()
+                    .then(|| wkb::write_linestring(&traversal.curve.as_linestring())),
+            );
+        }
+        record_batch(vec![
+            (
+                "id",
+                DataType::Utf8,
+                false,
+                Arc::new(StringArray::from(ids)) as ArrayRef,
+            ),
+            (
+                "traversal_id",
+                DataType::Utf8,
+                false,
+                Arc::new(StringArray::from(traversal_ids)) as ArrayRef,
+            ),
+            geometry_column(geometries),
+        ])
+    }
+
+    /// One row per anchor of each LRM.
+    pub fn anchors_record_batch(&self) -> Result<RecordBatch, GeoParquetError> {
+        let mut lrm_ids = vec![];
+        let mut names = vec![];
+        let mut measures = vec![];
+        let mut geometries = vec![];
+        for lrm in &self.lrms {
+            for anchor in &lrm.scale.anchors {
+                lrm_ids.push(lrm.scale.id.clone());
+                names.push(match anchor {
+                    Anchor::Named(anchor) => Some(anchor.name.clone()),
+                    Anchor::Unnamed(_) => None,
+                });
+                measures.push(anchor.scale_position());
+                geometries.push(self.anchor_point(lrm, anchor).map(wkb::write_point));
+            }
+        }
+        record_batch(vec![
+            (
+                "lrm_id",
+                DataType::Utf8,
+                false,
+                Arc::new(StringArray::from(lrm_ids)) as ArrayRef,
+            ),
+            (
+                "name",
+                DataType::Utf8,
+                true,
+                Arc::new(StringArray::from(names)) as ArrayRef,
+            ),
+            (
+                "measure",
+                DataType::Float64,
+                false,
+                Arc::new(Float64Array::from(measures)) as ArrayRef,
+            ),
+            geometry_column(geometries),
+        ])
+    }
+
+    /// One row per point event, then one per linear event.
+    pub fn events_record_batch(&self) -> Result<RecordBatch, GeoParquetError> {
+        let mut ids = vec![];
+        let mut lrm_ids = vec![];
+        let mut begins: Vec<&LrmScaleMeasure> = vec![];
+        let mut ends: Vec<Option<&LrmScaleMeasure>> = vec![];
+        let mut geometries = vec![];
+        for event in &self.point_events {
+            ids.push(event.id.clone());
+            lrm_ids.push(self.lrm(event.lrm)?.scale.id.clone());
+            begins.push(&event.measure);
+            ends.push(None);
+            geometries.push(
+                self.event_point(event.lrm, &event.measure)
+                    .map(wkb::write_point),
+            );
+        }
+        for event in &self.linear_events {
+            ids.push(event.id.clone());
+            lrm_ids.push(self.lrm(event.lrm)?.scale.id.clone());
+            begins.push(&event.begin);
+            ends.push(Some(&event.end));
+            geometries.push(
+                self.event_linestring(event.lrm, &event.begin, &event.end)
+                    .map(|linestring| wkb::write_linestring(&linestring)),
+            );
+        }
+        record_batch(vec![
+            (
+                "id",
+                DataType::Utf8,
+                false,
+                Arc::new(StringArray::from(ids)) as ArrayRef,
+            ),
+            (
+                "lrm_id",
+                DataType::Utf8,
+                false,
+                Arc::new(StringArray::from(lrm_ids)) as ArrayRef,
+            ),
+            (
+                "begin_anchor",
+                DataType::Utf8,
+                false,
+                Arc::new(StringArray::from_iter_values(
+                    begins.iter().map(|measure| &measure.anchor_name),
+                )) as ArrayRef,
+            ),
+            (
+                "begin_offset",
+                DataType::Float64,
+                false,
+                Arc::new(Float64Array::from_iter_values(
+                    begins.iter().map(|measure| measure.scale_offset),
+                )) as ArrayRef,
+            ),
+            (
+                "end_anchor",
+                DataType::Utf8,
+                true,
+                Arc::new(StringArray::from_iter(
+                    ends.iter().map(|measure| measure.map(|m| &m.anchor_name)),
+                )) as ArrayRef,
+            ),
+            (
+                "end_offset",
+                DataType::Float64,
+                true,
+                Arc::new(Float64Array::from_iter(
+                    ends.iter().map(|measure| measure.map(|m| m.scale_offset)),
+                )) as ArrayRef,
+            ),
+            geometry_column(geometries),
+        ])
+    }
+
+    /// Writes the `lrms.parquet`, `anchors.parquet` and `events.parquet` files in an existing `directory`.
+    ///
+    /// The coordinates are declared in longitude and latitude (OGC:CRS84) if the [`Lrs`] has no other coordinate reference system.
+    pub fn to_geoparquet<P: AsRef<Path>>(&self, directory: P) -> Result<(), GeoParquetError> {
+        let directory = directory.as_ref();
+        for (name, batch, geometry_types) in [
+            ("lrms", self.lrms_record_batch()?, r#"["LineString"]"#),
+            ("anchors", self.anchors_record_batch()?, r#"["Point"]"#),
+            (
+                "events",
+                self.events_record_batch()?,
+                r#"["Point", "LineString"]"#,
+            ),
+        ] {
+            let file = File::create(directory.join(format!("{name}.parquet")))?;
+            let properties = WriterProperties::builder()
+                .set_key_value_metadata(Some(vec![KeyValue::new(
+                    "geo".to_owned(),
+                    self.geo_metadata(geometry_types),
+                )]))
+                .build();
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(properties))?;
+            writer.write(&batch)?;
+            writer.close()?;
+        }
+        Ok(())
+    }
+
+    // The `geo` metadata of GeoParquet 1.1, where a missing `crs` means OGC:CRS84 and a null one an unknown system
+    fn geo_metadata(&self, geometry_types: &str) -> String {
+        let crs = match self.crs.as_deref() {
+            None | Some("EPSG:4326") | Some("OGC:CRS84") => "",
+            Some(_) => r#", "crs": null"#,
+        };
+        format!(
+            r#"{{"version": "1.1.0", "primary_column": "geometry", "columns": {{"geometry": {{"encoding": "WKB", "geometry_types": {geometry_types}{crs}}}}}}}"#
+        )
+    }
+
+    fn event_point(&self, lrm: LrmHandle, measure: &LrmScaleMeasure) -> Option<Point> {
+        let position = self
+            .get_lrm_by_handle(lrm)
+            .ok()?
+            .locate_point(measure)
+            .ok()?;
+        let curve = self.get_curve(self.lrm(lrm).ok()?.traversal).ok()?;
+        curve.resolve(position).ok()
+    }
+
+    fn event_linestring(
+        &self,
+        lrm: LrmHandle,
+        begin: &LrmScaleMeasure,
+        end: &LrmScaleMeasure,
+    ) -> Option<LineString> {
+        let scale = self.get_lrm_by_handle(lrm).ok()?;
+        let begin = scale.locate_point(begin).ok()?;
+        let end = scale.locate_point(end).ok()?;
+        let curve = self.get_curve(self.lrm(lrm).ok()?.traversal).ok()?;
+        curve.sublinestring(begin.min(end), begin.max(end))
+    }
+}
+
+fn record_batch(
+    columns: Vec<(&str, DataType, bool, ArrayRef)>,
+) -> Result<RecordBatch, GeoParquetError> {
+    let (fields, arrays): (Vec<_>, Vec<_>) = columns
+        .into_iter()
+        .map(|(name, data_type, nullable, array)| (Field::new(name, data_type, nullable), array))
+        .unzip();
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+fn read_batches<P: AsRef<Path>>(path: P) -> Result<ParquetRecordBatchReader, GeoParquetError> {
+    Ok(ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?)
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a ArrayRef, GeoParquetError> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| GeoParquetError::MissingColumn(name.to_owned()))
+}
+
+fn string_column<'a>(
+    batch: &'a RecordBatch,
+    name: &str,
+) -> Result<&'a StringArray, GeoParquetError> {
+    column(batch, name)?
+        .as_string_opt()
+        .ok_or_else(|| GeoParquetError::InvalidValue(name.to_owned()))
+}
+
+fn binary_column<'a>(
+    batch: &'a RecordBatch,
+    name: &str,
+) -> Result<&'a BinaryArray, GeoParquetError> {
+    column(batch, name)?
+        .as_binary_opt()
+        .ok_or_else(|| GeoParquetError::InvalidValue(name.to_owned()))
+}
+
+fn geometry_column(geometries: Vec<Option<Vec<u8>>>) -> (&'static str, DataType, bool, ArrayRef) {
+    (
+        "geometry",
+        DataType::Binary,
+        true,
+        Arc::new(BinaryArray::from_iter(geometries)) as ArrayRef,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::builder::tests::geographic_lrs;
+
+    use super::*;
+
+    #[test]
+    fn write() {
+        let lrs = geographic_lrs();
+        assert_eq!(lrs.lrms_record_batch().unwrap().num_rows(), 1);
+        assert_eq!(lrs.anchors_record_batch().unwrap().num_rows(), 2);
+        let events = lrs.events_record_batch().unwrap();
+        assert_eq!(events.num_rows(), 2);
+        let end_anchors = events.column_by_name("end_anchor").unwrap();
+        assert!(end_anchors.is_null(0) && !end_anchors.is_null(1));
+        let geometries = events.column_by_name("geometry").unwrap();
+        assert_eq!(geometries.null_count(), 0);
+
+        let directory = std::env::temp_dir().join(format!("liblrs-{}-parquet", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        lrs.to_geoparquet(&directory).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(
+            File::open(directory.join("events.parquet")).unwrap(),
+        )
+        .unwrap();
+        let geo = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .and_then(|metadata| metadata.iter().find(|kv| kv.key == "geo"))
+            .and_then(|kv| kv.value.clone());
+        let rows: usize = reader
+            .build()
+            .unwrap()
+            .map(|batch| batch.unwrap().num_rows())
+            .sum();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(rows, 2);
+        assert!(geo.unwrap().contains(r#""encoding": "WKB""#));
+    }
+
+    #[test]
+    fn read() {
+        let lrs = geographic_lrs();
+        let directory =
+            std::env::temp_dir().join(format!("liblrs-{}-parquet-read", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        lrs.to_geoparquet(&directory).unwrap();
+        let (traversals, anchors) = (
+            directory.join("lrms.parquet"),
+            directory.join("anchors.parquet"),
+        );
+        let read = Builder::from_geoparquet(&traversals, &anchors, &GeoParquetMapping::default())
+            .map(|mut builder| builder.build_lrs(properties!()));
+        let missing = GeoParquetMapping {
+            anchor_measure: "distance".to_owned(),
+            ..Default::default()
+        };
+        let error = Builder::from_geoparquet(&traversals, &anchors, &missing).err();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let read = read.unwrap().unwrap();
+        assert_eq!(read.lrm_len(), 1);
+        assert_eq!(read.lrms()[0].scale.id, "lrm");
+        assert_eq!(read.lrms()[0].scale.anchors.len(), 2);
+        let measure = LrmScaleMeasure::new("0", 1500.);
+        let expected = lrs.resolve(LrmHandle(0), &measure).unwrap();
+        let point = read.resolve(0, &measure).unwrap();
+        assert_relative_eq!(point.x(), expected.x(), epsilon = 1e-6);
+        assert_relative_eq!(point.y(), expected.y(), epsilon = 1e-6);
+        assert!(
+            matches!(error, Some(GeoParquetError::MissingColumn(column)) if column == "distance")
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Reads the tracks of GPX files, such as the logs of a measurement train or of an inspection app,
+//! and projects them on the LRMs with [`Lrs::project_trace`].
+//!
+//! The track points of all the tracks and segments are read in the order of the file, as a single trace.
+//! Their `time` is kept as written, usually an ISO 8601 date.
+
+use geo::Point;
+use thiserror::Error;
+
+use crate::curves::Curve;
+use crate::lrm_scale::LrmScaleMeasure;
+use crate::lrs::{LrmHandle, Lrs, LrsBase};
+use crate::trace::{TraceMatch, TraceOptions};
+
+/// Errors when reading a GPX file.
+#[derive(Debug, Error, PartialEq)]
+pub enum GpxError {
+    /// The file is not valid XML.
+    #[error("invalid gpx: {0}")]
+    Parse(String),
+    /// A track point has no valid `lat` or `lon` attribute.
+    #[error("track point {0} has an invalid position")]
+    InvalidPosition(usize),
+}
+
+/// A position of a GPX track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackPoint {
+    /// Longitude and latitude of the position.
+    pub point: Point,
+    /// When the position was recorded, if the file tells it.
+    pub time: Option<String>,
+}
+
+/// A track point matched on an LRM.
+#[derive(Clone, Debug)]
+pub struct ProjectedTrackPoint {
+    /// Index of the track point in the file.
+    pub index: usize,
+    /// The LRM where the track point was matched.
+    pub lrm: LrmHandle,
+    /// `id` of the LRM.
+    pub lrm_id: String,
+    /// The measure of the track point on the LRM.
+    pub measure: LrmScaleMeasure,
+    /// Distance from the LRM: positive if the track point is on the left of the LRM, negative on the right.
+    pub offset: f64,
+    /// When the track point was recorded.
+    pub time: Option<String>,
+}
+
+/// When the track entered and left an LRM.
+#[derive(Clone, Debug)]
+pub struct TrackPassage {
+    /// `id` of the LRM.
+    pub lrm_id: String,
+    /// The matched part of the trace, as given by [`Lrs::project_trace`].
+    pub trace_match: TraceMatch,
+    /// When the track entered the LRM.
+    pub entry_time: Option<String>,
+    /// When the track left the LRM.
+    pub exit_time: Option<String>,
+}
+
+/// The result of [`Lrs::project_gpx`].
+#[derive(Clone, Debug)]
+pub struct GpxProjection {
+    /// The matched track points, in the order of the file. The track points far from any LRM are missing.
+    pub points: Vec<ProjectedTrackPoint>,
+    /// One passage every time the track enters an LRM, in the order of the file.
+    pub passages: Vec<TrackPassage>,
+}
+
+/// Reads the track points (`trkpt`) of a GPX file.
+pub fn read_gpx(xml: &str) -> Result<Vec<TrackPoint>, GpxError> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| GpxError::Parse(e.to_string()))?;
+    document
+        .descendants()
+        .filter(|node| node.has_tag_name("trkpt"))
+        .enumerate()
+        .map(|(index, node)| {
+            let coordinate = |attribute| node.attribute(attribute)?.trim().parse::<f64>().ok();
+            let (Some(x), Some(y)) = (coordinate("lon"), coordinate("lat")) else {
+                return Err(GpxError::InvalidPosition(index));
+            };
+            let time = node
+                .children()
+                .find(|child| child.has_tag_name("time"))
+                .and_then(|child| child.text())
+                .map(|time| time.trim().to_owned());
+            Ok(TrackPoint {
+                point: Point::new(x, y),
+                time,
+            })
+        })
+        .collect()
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Reads the tracks of a GPX file and map-matches them on the LRMs, see [`Lrs::project_trace`].
+    ///
+    /// Each track point covered by a match is projected on the LRM of the match, even if it was ignored as noise.
+    pub fn project_gpx(&self, xml: &str, options: TraceOptions) -> Result<GpxProjection, GpxError> {
+        let track = read_gpx(xml)?;
+        let positions: Vec<_> = track.iter().map(|track_point| track_point.point).collect();
+        let time = |index: usize| track.get(index).and_then(|point| point.time.clone());
+
+        let mut projection = GpxProjection {
+            points: vec![],
+            passages: vec![],
+        };
+        for trace_match in self.project_trace(&positions, options) {
+            let Ok(lrm) = self.lrm(trace_match.lrm) else {
+                continue;
+            };
+            for index in trace_match.points.clone() {
+                let Ok(lrm_projection) = self.lookup(positions[index], trace_match.lrm) else {
+                    continue;
+                };
+                projection.points.push(ProjectedTrackPoint {
+                    index,
+                    lrm: trace_match.lrm,
+                    lrm_id: lrm.scale.id.clone(),
+                    measure: lrm_projection.measure.measure,
+                    offset: lrm_projection.orthogonal_offset,
+                    time: time(index),
+                });
+            }
+            projection.passages.push(TrackPassage {
+                lrm_id: lrm.scale.id.clone(),
+                entry_time: time(trace_match.points.start),
+                exit_time: trace_match.points.end.checked_sub(1).and_then(time),
+                trace_match,
+            });
+        }
+        Ok(projection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::lrs::tests::lrs;
+
+    use super::*;
+
+    const GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1">
+  <trk><trkseg>
+    <trkpt lat="0.2" lon="10"><time>2024-05-02T08:00:00Z</time></trkpt>
+    <trkpt lat="0.1" lon="50"><time>2024-05-02T08:00:10Z</time></trkpt>
+  </trkseg><trkseg>
+    <trkpt lat="0" lon="100"><time>2024-05-02T08:00:20Z</time></trkpt>
+    <trkpt lat="10" lon="160"/>
+  </trkseg></trk>
+</gpx>"#;
+
+    #[test]
+    fn read_gpx() {
+        let track = super::read_gpx(GPX).unwrap();
+        assert_eq!(track.len(), 4);
+        assert_eq!(track[1].point, Point::new(50., 0.1));
+        assert_eq!(track[2].time.as_deref(), Some("2024-05-02T08:00:20Z"));
+        assert_eq!(track[3].time, None);
+
+        assert_eq!(
+            super::read_gpx(r#"<gpx><trk><trkseg><trkpt lat="1"/></trkseg></trk></gpx>"#),
+            Err(GpxError::InvalidPosition(0))
+        );
+        assert!(matches!(super::read_gpx("<gpx>"), Err(GpxError::Parse(_))));
+    }
+
+    #[test]
+    fn project_gpx() {
+        let options = TraceOptions {
+            max_distance: 0.5,
+            backward_tolerance: 0.,
+        };
+        let projection = lrs().project_gpx(GPX, options).unwrap();
+
+        assert_eq!(projection.points.len(), 3);
+        let point = &projection.points[1];
+        assert_eq!(point.index, 1);
+        assert_eq!(point.lrm_id, "id");
+        assert_eq!(point.measure.anchor_name, "a");
+        assert_relative_eq!(point.measure.scale_offset, 5.);
+        assert_relative_eq!(point.offset, 0.1);
+        assert_eq!(point.time.as_deref(), Some("2024-05-02T08:00:10Z"));
+
+        assert_eq!(projection.passages.len(), 1);
+        let passage = &projection.passages[0];
+        assert_eq!(passage.lrm_id, "id");
+        assert_eq!(passage.trace_match.points, 0..3);
+        assert_eq!(passage.entry_time.as_deref(), Some("2024-05-02T08:00:00Z"));
+        assert_eq!(passage.exit_time.as_deref(), Some("2024-05-02T08:00:20Z"));
+    }
+}
@@ -0,0 +1,201 @@
+//! Exports an [`Lrs`] as KML or KMZ, to open it in Google Earth, e.g. on the tablets of the maintenance crews.
+//!
+//! Each LRM is a `Folder` with a `Placemark` for the LineString of its traversal, one for each anchor
+//! with its measure in the description and, when [`KmlOptions::tick_step`] is set, one for each graduation of the scale.
+//! The measures are written as `12+100`: the name of the anchor and the offset from it.
+//!
+//! The coordinates of the curves must be longitudes and latitudes. The geometries that cannot be computed are skipped.
+
+use std::fmt::Write as _;
+use std::io::{Seek, Write};
+
+use geo::Point;
+use thiserror::Error;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use crate::curves::Curve;
+use crate::lrm_scale::{Anchor, LrmScaleMeasure};
+use crate::lrs::{Lrm, LrmHandle, Lrs, Properties};
+
+/// Errors when writing a KMZ file.
+#[derive(Debug, Error)]
+pub enum KmlError {
+    /// The file could not be written.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The zip archive could not be written.
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+}
+
+/// What is written by [`Lrs::to_kml`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KmlOptions {
+    /// Adds a graduation every `tick_step` scale units, e.g. every 100 meters. There is no graduation when `None`.
+    pub tick_step: Option<f64>,
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Writes the LRMs, their anchors and, optionally, their graduations as a KML document, see [`crate::kml`].
+    pub fn to_kml(&self, options: &KmlOptions) -> String {
+        let mut kml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "\n",
+            r#"<kml xmlns="http://www.opengis.net/kml/2.2"><Document>"#,
+            "\n"
+        ));
+        for (index, lrm) in self.lrms.iter().enumerate() {
+            self.write_lrm(&mut kml, LrmHandle(index), lrm, options);
+        }
+        kml.push_str("</Document></kml>\n");
+        kml
+    }
+
+    /// Writes the KML document of [`Lrs::to_kml`] in a KMZ archive, as `doc.kml`.
+    pub fn to_kmz<W: Write + Seek>(&self, writer: W, options: &KmlOptions) -> Result<(), KmlError> {
+        let mut zip = ZipWriter::new(writer);
+        zip.start_file("doc.kml", SimpleFileOptions::default())?;
+        zip.write_all(self.to_kml(options).as_bytes())?;
+        zip.finish()?;
+        Ok(())
+    }
+
+    fn write_lrm(&self, kml: &mut String, handle: LrmHandle, lrm: &Lrm, options: &KmlOptions) {
+        let id = escape(&lrm.scale.id);
+        let _ = writeln!(kml, "<Folder><name>{id}</name>");
+        if let Ok(curve) = self.get_curve(lrm.traversal) {
+            let coordinates: Vec<_> = curve
+                .as_linestring()
+                .coords()
+                .map(|c| format!("{},{}", c.x, c.y))
+                .collect();
+            let _ = writeln!(
+                kml,
+                "<Placemark><name>{id}</name><description>{}</description><LineString><coordinates>{}</coordinates></LineString></Placemark>",
+                escape(&describe(&lrm.properties)),
+                coordinates.join(" ")
+            );
+        }
+
+        for anchor in &lrm.scale.anchors {
+            let (Some(point), Ok(measure)) = (
+                self.anchor_point(lrm, anchor),
+                lrm.scale.anchor_measure(anchor),
+            ) else {
+                continue;
+            };
+            let name = match anchor {
+                Anchor::Named(anchor) => anchor.name.clone(),
+                Anchor::Unnamed(_) => format_measure(&measure),
+            };
+            let description = format!(
+                "measure: {}\n{}",
+                format_measure(&measure),
+                describe(anchor.properties())
+            );
+            placemark(kml, &name, description.trim_end(), point);
+        }
+
+        for (measure, point) in self.graduations(handle, lrm, options.tick_step) {
+            placemark(kml, &format_measure(&measure), "graduation", point);
+        }
+        kml.push_str("</Folder>\n");
+    }
+
+    // The measures every `step` scale units between both ends of the LRM
+    fn graduations(
+        &self,
+        handle: LrmHandle,
+        lrm: &Lrm,
+        step: Option<f64>,
+    ) -> Vec<(LrmScaleMeasure, Point)> {
+        let Some(step) = step.filter(|step| step.is_finite() && *step > 0.) else {
+            return vec![];
+        };
+        let Ok(bounds) = self.lrm_bounds(handle) else {
+            return vec![];
+        };
+        let (Ok(min), Ok(max)) = (
+            lrm.scale.get_position(bounds.min_measure),
+            lrm.scale.get_position(bounds.max_measure),
+        ) else {
+            return vec![];
+        };
+        ((min / step).ceil() as i64..=(max / step).floor() as i64)
+            .filter_map(|graduation| {
+                let measure = lrm.scale.get_measure(graduation as f64 * step).ok()?;
+                let point = self.resolve(handle, &measure).ok()?;
+                Some((measure, point))
+            })
+            .collect()
+    }
+}
+
+fn placemark(kml: &mut String, name: &str, description: &str, point: Point) {
+    let _ = writeln!(
+        kml,
+        "<Placemark><name>{}</name><description>{}</description><Point><coordinates>{},{}</coordinates></Point></Placemark>",
+        escape(name),
+        escape(description),
+        point.x(),
+        point.y()
+    );
+}
+
+// Formats a measure as `12+100`, rounded to the thousandth of a scale unit
+fn format_measure(measure: &LrmScaleMeasure) -> String {
+    // Adding zero turns -0 into +0
+    let offset = (measure.scale_offset * 1000.).round() / 1000. + 0.;
+    format!("{}{offset:+}", measure.anchor_name)
+}
+
+// One `key: value` line per property, sorted by key
+fn describe(properties: &Properties) -> String {
+    let mut lines: Vec<_> = properties
+        .iter()
+        .map(|(key, value)| format!("{key}: {value}"))
+        .collect();
+    lines.sort();
+    lines.join("\n")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::builder::tests::geographic_lrs;
+
+    use super::*;
+
+    #[test]
+    fn to_kml() {
+        let lrs = geographic_lrs();
+        let kml = lrs.to_kml(&KmlOptions::default());
+        assert_eq!(kml.matches("<Placemark>").count(), 3);
+        assert!(kml.contains("<LineString><coordinates>0,0 "));
+        assert!(kml.contains("<name>1</name><description>measure: 1+0\nkind: &lt;post&gt;"));
+
+        let kml = lrs.to_kml(&KmlOptions {
+            tick_step: Some(500.),
+        });
+        assert!(kml.contains("<name>0+500</name><description>graduation</description>"));
+        assert!(kml.contains("<name>1+0</name><description>graduation</description>"));
+    }
+
+    #[test]
+    fn to_kmz() {
+        let mut kmz = Cursor::new(vec![]);
+        geographic_lrs()
+            .to_kmz(&mut kmz, &KmlOptions::default())
+            .unwrap();
+        assert!(kmz.into_inner().starts_with(b"PK"));
+    }
+}
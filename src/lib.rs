@@ -6,9 +6,21 @@
 #[rustfmt::skip]
 mod lrs_generated;
 
+#[cfg(feature = "osm")]
 #[deny(missing_docs)]
 mod osm_helpers;
 
+#[cfg(any(feature = "geopackage", feature = "geoparquet", feature = "postgres"))]
+#[deny(missing_docs)]
+mod wkb;
+
+#[cfg(any(feature = "railml", feature = "netex", feature = "rinf"))]
+#[deny(missing_docs)]
+mod xml;
+
+#[deny(missing_docs)]
+mod spatial_index;
+
 #[deny(missing_docs)]
 pub mod curves;
 #[deny(missing_docs)]
@@ -18,9 +30,99 @@ pub mod lrs;
 #[deny(missing_docs)]
 pub mod lrs_ext;
 
+#[deny(missing_docs)]
+pub mod error_code;
+
+#[deny(missing_docs)]
+pub mod file_format;
+
+#[deny(missing_docs)]
+pub mod events;
+
+#[deny(missing_docs)]
+pub mod diff;
+
+#[deny(missing_docs)]
+pub mod trace;
+
+#[deny(missing_docs)]
+pub mod stats;
+
+#[deny(missing_docs)]
+pub mod validation;
+
+#[deny(missing_docs)]
+pub mod search;
+
+#[deny(missing_docs)]
+pub mod batch;
+
+#[deny(missing_docs)]
+pub mod update;
+
+#[deny(missing_docs)]
+pub mod shared;
+
+#[cfg(feature = "mmap")]
+#[deny(missing_docs)]
+pub mod mmap;
+
+#[cfg(feature = "geopackage")]
+#[deny(missing_docs)]
+pub mod geopackage;
+
+#[cfg(feature = "geoparquet")]
+#[deny(missing_docs)]
+pub mod geoparquet;
+
+#[cfg(feature = "postgres")]
+#[deny(missing_docs)]
+pub mod postgis;
+
+#[cfg(feature = "yaml")]
+#[deny(missing_docs)]
+pub mod yaml;
+
+#[cfg(feature = "gpx")]
+#[deny(missing_docs)]
+pub mod gpx;
+
+#[cfg(feature = "kml")]
+#[deny(missing_docs)]
+pub mod kml;
+
+#[cfg(feature = "csv")]
+#[deny(missing_docs)]
+pub mod csv;
+
+#[cfg(feature = "shapefile")]
+#[deny(missing_docs)]
+pub mod shapefile;
+
+#[cfg(feature = "gdal")]
+#[deny(missing_docs)]
+pub mod gdal;
+
+#[cfg(feature = "railml")]
+#[deny(missing_docs)]
+pub mod railml;
+
+#[cfg(feature = "netex")]
+#[deny(missing_docs)]
+pub mod netex;
+
+#[cfg(feature = "rinf")]
+#[deny(missing_docs)]
+pub mod rinf;
+
+#[cfg(any(test, feature = "builder"))]
 #[deny(missing_docs)]
 pub mod builder;
 
+#[cfg(any(test, feature = "conformance"))]
+#[deny(missing_docs)]
+pub mod conformance;
+
 pub trait DataIssueReporter {
     fn report_ignoring_traversal_edges(
         &mut self,
@@ -0,0 +1,1162 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use geo::{Point, Rect, coord};
+use serde_json::json;
+
+#[cfg(any(feature = "osm", feature = "yaml", feature = "geojson"))]
+use liblrs::builder::Builder;
+use liblrs::curves::Curve;
+use liblrs::diff::{LrsDiff, lrs_diff};
+use liblrs::file_format::{SCHEMA_VERSION, schema_version};
+use liblrs::lrm_scale::{Anchor, LrmScaleMeasure};
+use liblrs::lrs::{AnyLrs, LoadFilter, LrmHandle, LrmProjection, Lrs, Properties};
+use liblrs::lrs_ext::{AnchorMarker, ExtLrs};
+#[cfg(any(feature = "osm", feature = "yaml", feature = "geojson"))]
+use liblrs::properties;
+use liblrs::validation::{Severity, ValidationOptions, ValidationReport};
+use liblrs::with_any_lrs;
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+/// Arguments given by the command line interface.
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the metadata of an LRS file and, for each LRM, its traversal, anchors and scale.
+    Inspect {
+        /// LRS file to inspect.
+        file: PathBuf,
+
+        /// Print the content as JSON instead of text.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the ids, scales and geometries of an LRS file.
+    ///
+    /// Exits with 1 if an error is found (or a warning with `--strict`), and with 2 if the file cannot be read.
+    Validate {
+        /// LRS file to validate.
+        file: PathBuf,
+
+        /// Print the report as JSON instead of text.
+        #[arg(long)]
+        json: bool,
+
+        /// Also fail on warnings.
+        #[arg(long)]
+        strict: bool,
+
+        /// Maximum distance between an anchor and its traversal, in meters for a geographic LRS and in coordinate units otherwise.
+        #[arg(long, default_value_t = ValidationOptions::default().max_anchor_distance)]
+        max_anchor_distance: f64,
+
+        /// Maximum relative difference between the length of a scale and of its traversal.
+        #[arg(long, default_value_t = ValidationOptions::default().max_length_mismatch)]
+        max_length_mismatch: f64,
+    },
+    /// Print the coordinates of a measure on an LRM.
+    Locate {
+        /// LRS file to read.
+        file: PathBuf,
+
+        /// `id` of the LRM.
+        #[arg(long)]
+        lrm: String,
+
+        /// The measure, as `anchor+offset`, e.g. `12+450`.
+        #[arg(long, value_parser = parse_measure)]
+        measure: LrmScaleMeasure,
+
+        /// Also print the direction of increasing measures, in degrees clockwise from the north.
+        #[arg(long)]
+        bearing: bool,
+
+        /// Also print the anchors before and after the measure.
+        #[arg(long)]
+        anchors: bool,
+
+        /// Print the location as JSON instead of text.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the LRM nearest to coordinates, the measure there and the lateral offset.
+    Project {
+        /// LRS file to read.
+        file: PathBuf,
+
+        /// Longitude of the position.
+        #[arg(long, allow_negative_numbers = true)]
+        lon: f64,
+
+        /// Latitude of the position.
+        #[arg(long, allow_negative_numbers = true)]
+        lat: f64,
+
+        /// Maximum distance between the position and the LRM, in meters for a geographic LRS and in coordinate units otherwise.
+        #[arg(long, default_value_t = 100.)]
+        max_distance: f64,
+
+        /// Print every LRM within `max_distance`, the nearest first.
+        #[arg(long)]
+        all: bool,
+
+        /// Print the projections as JSON instead of text.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write a smaller LRS file with only some LRMs, their traversals and their anchors.
+    Extract {
+        /// LRS file to read.
+        input: PathBuf,
+
+        /// LRS file to write.
+        output: PathBuf,
+
+        /// Keep the LRMs in this bounding box, given as `min_lon,min_lat,max_lon,max_lat`.
+        #[arg(long, value_parser = parse_bbox, conflicts_with = "lrm", required_unless_present = "lrm")]
+        bbox: Option<Rect>,
+
+        /// Keep the LRMs with these ids, separated by commas.
+        #[arg(long, value_delimiter = ',')]
+        lrm: Vec<String>,
+
+        /// Compress the output file. It is then only readable by liblrs with the `compression` feature.
+        #[cfg(feature = "compression")]
+        #[arg(long)]
+        compress: bool,
+    },
+    /// Print the LRMs added, removed and modified between two versions of an LRS file.
+    Diff {
+        /// The old version.
+        old: PathBuf,
+
+        /// The new version.
+        new: PathBuf,
+
+        /// Print the differences as JSON instead of text.
+        #[arg(long)]
+        json: bool,
+
+        /// Exit with 1 if the versions differ.
+        #[arg(long)]
+        exit_code: bool,
+    },
+    /// Rewrite an LRS file written by a previous version of liblrs with the current version of the format.
+    ///
+    /// A compressed file remains compressed.
+    Migrate {
+        /// LRS file to read.
+        input: PathBuf,
+
+        /// LRS file to write. It can be the same as the input.
+        output: PathBuf,
+    },
+    /// Convert a file between the formats supported by liblrs, e.g. to build an LRS file from YAML or GeoJSON.
+    ///
+    /// The formats are guessed from the file extensions, unknown extensions being LRS files.
+    Convert {
+        /// File to read.
+        input: PathBuf,
+
+        /// File to write.
+        output: PathBuf,
+
+        /// Format of the input file, when it cannot be guessed from its extension.
+        #[arg(long, value_enum)]
+        from: Option<Format>,
+
+        /// Format of the output file, when it cannot be guessed from its extension.
+        #[arg(long, value_enum)]
+        to: Option<Format>,
+
+        #[command(flatten)]
+        options: ConvertOptions,
+    },
+}
+
+/// Formats read or written by `convert`.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum Format {
+    /// LRS file (read and written).
+    Lrs,
+    /// YAML of `lrs_gaia` (read and written).
+    Yaml,
+    /// GeoJSON features (read and written).
+    Geojson,
+    /// GeoPackage (read and written).
+    Gpkg,
+    /// OpenStreetMap extract (only read).
+    Osm,
+    /// KML document (only written).
+    Kml,
+    /// Zipped KML document (only written).
+    Kmz,
+}
+
+impl Format {
+    /// Guesses the format from the extension of the file, LRS by default.
+    fn from_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let extensions = [
+            (".yaml", Self::Yaml),
+            (".yml", Self::Yaml),
+            (".geojson", Self::Geojson),
+            (".json", Self::Geojson),
+            (".gpkg", Self::Gpkg),
+            (".pbf", Self::Osm),
+            (".kml", Self::Kml),
+            (".kmz", Self::Kmz),
+        ];
+        extensions
+            .into_iter()
+            .find(|(extension, _)| name.ends_with(extension))
+            .map_or(Self::Lrs, |(_, format)| format)
+    }
+}
+
+/// Options of `convert` for specific formats.
+#[derive(clap::Args, Debug)]
+struct ConvertOptions {
+    /// OpenStreetMap tag identifying the LRM, required to read OpenStreetMap. The french railway network uses `ref:FR:SNCF_Reseau`.
+    #[arg(long)]
+    lrm_tag: Option<String>,
+
+    /// Build the traversals from the railway route relations of OpenStreetMap, with the milestones as anchors.
+    #[cfg(feature = "osm-routes")]
+    #[arg(long)]
+    routes: bool,
+
+    /// GeoJSON property identifying the traversal of a LineString.
+    #[cfg(feature = "geojson")]
+    #[arg(long, default_value = "id")]
+    traversal_id_property: String,
+
+    /// GeoJSON property identifying the LRM of a Point.
+    #[cfg(feature = "geojson")]
+    #[arg(long, default_value = "lrm_id")]
+    lrm_id_property: String,
+
+    /// GeoJSON property naming the anchor of a Point.
+    #[cfg(feature = "geojson")]
+    #[arg(long, default_value = "name")]
+    anchor_name_property: String,
+
+    /// GeoJSON property giving the measure of a Point.
+    #[cfg(feature = "geojson")]
+    #[arg(long, default_value = "measure")]
+    measure_property: String,
+
+    /// GeoPackage table of the traversals.
+    #[cfg(feature = "geopackage")]
+    #[arg(long, default_value = "traversals")]
+    traversals_layer: String,
+
+    /// GeoPackage table of the LRMs.
+    #[cfg(feature = "geopackage")]
+    #[arg(long, default_value = "lrms")]
+    lrms_layer: String,
+
+    /// GeoPackage table of the anchors.
+    #[cfg(feature = "geopackage")]
+    #[arg(long, default_value = "anchors")]
+    anchors_layer: String,
+
+    /// Add a KML graduation every `tick_step` scale units.
+    #[cfg(feature = "kml")]
+    #[arg(long)]
+    tick_step: Option<f64>,
+
+    /// Compress the output LRS file. It is then only readable by liblrs with the `compression` feature.
+    #[cfg(feature = "compression")]
+    #[arg(long)]
+    compress: bool,
+}
+
+/// What `inspect` prints about an LRM.
+struct LrmSummary {
+    id: String,
+    traversal_id: String,
+    /// `None` if the traversal has no valid geometry.
+    length: Option<f64>,
+    named_anchors: usize,
+    unnamed_anchors: usize,
+    /// The measures at both ends of the LRM, if they can be computed.
+    scale_range: Option<(LrmScaleMeasure, LrmScaleMeasure)>,
+    properties: Properties,
+}
+
+fn summarize<CurveImpl: Curve>(lrs: &Lrs<CurveImpl>) -> Vec<LrmSummary> {
+    lrs.lrms
+        .iter()
+        .enumerate()
+        .map(|(index, lrm)| {
+            let traversal = lrs.traversals.get(lrm.traversal.0);
+            let named_anchors = lrm
+                .scale
+                .anchors
+                .iter()
+                .filter(|anchor| matches!(anchor, Anchor::Named(_)))
+                .count();
+            LrmSummary {
+                id: lrm.scale.id.clone(),
+                traversal_id: traversal.map(|t| t.id.clone()).unwrap_or_default(),
+                length: traversal
+                    .map(|t| &*t.curve)
+                    .filter(|curve| curve.is_valid())
+                    .map(|curve| curve.length()),
+                named_anchors,
+                unnamed_anchors: lrm.scale.anchors.len() - named_anchors,
+                scale_range: lrs
+                    .lrm_bounds(LrmHandle(index))
+                    .ok()
+                    .map(|bounds| (bounds.min_measure, bounds.max_measure)),
+                properties: lrm.properties.clone(),
+            }
+        })
+        .collect()
+}
+
+fn format_measure(measure: &LrmScaleMeasure) -> String {
+    format!("{}+{}", measure.anchor_name, measure.scale_offset)
+}
+
+// `key=value`, sorted by key
+fn format_properties(properties: &Properties) -> String {
+    let properties: BTreeMap<_, _> = properties.iter().collect();
+    properties
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_text<CurveImpl: Curve>(file: &Path, lrs: &Lrs<CurveImpl>) {
+    println!("File: {}", file.display());
+    println!("CRS: {}", lrs.crs.as_deref().unwrap_or("unknown"));
+    if let Some(build_info) = &lrs.build_info {
+        println!(
+            "Built by liblrs {} from {}",
+            build_info.liblrs_version,
+            build_info.sources.join(", ")
+        );
+    }
+    if !lrs.properties.is_empty() {
+        println!("Properties: {}", format_properties(&lrs.properties));
+    }
+    print!("{}", lrs.stats());
+
+    for lrm in summarize(lrs) {
+        println!();
+        println!("LRM {}", lrm.id);
+        match lrm.length {
+            Some(length) => println!("  Traversal: {} ({length:.0})", lrm.traversal_id),
+            None => println!("  Traversal: {} (invalid geometry)", lrm.traversal_id),
+        }
+        println!(
+            "  Anchors: {} named, {} unnamed",
+            lrm.named_anchors, lrm.unnamed_anchors
+        );
+        if let Some((min, max)) = &lrm.scale_range {
+            println!("  Scale: {} – {}", format_measure(min), format_measure(max));
+        }
+        if !lrm.properties.is_empty() {
+            println!("  Properties: {}", format_properties(&lrm.properties));
+        }
+    }
+}
+
+fn print_json<CurveImpl: Curve>(file: &Path, lrs: &Lrs<CurveImpl>) {
+    let measure = |measure: &LrmScaleMeasure| json!({"anchor_name": measure.anchor_name, "scale_offset": measure.scale_offset});
+    let lrms: Vec<_> = summarize(lrs)
+        .iter()
+        .map(|lrm| {
+            json!({
+                "id": lrm.id,
+                "traversal_id": lrm.traversal_id,
+                "length": lrm.length,
+                "named_anchors": lrm.named_anchors,
+                "unnamed_anchors": lrm.unnamed_anchors,
+                "min_measure": lrm.scale_range.as_ref().map(|(min, _)| measure(min)),
+                "max_measure": lrm.scale_range.as_ref().map(|(_, max)| measure(max)),
+                "properties": lrm.properties,
+            })
+        })
+        .collect();
+    let stats = lrs.stats();
+    let content = json!({
+        "file": file.display().to_string(),
+        "crs": lrs.crs,
+        "build_info": lrs.build_info.as_ref().map(|build_info| json!({
+            "liblrs_version": build_info.liblrs_version,
+            "options": build_info.options,
+            "sources": build_info.sources,
+        })),
+        "properties": lrs.properties,
+        "stats": {
+            "lrm_count": stats.lrm_count,
+            "traversal_count": stats.traversal_count,
+            "invalid_traversal_count": stats.invalid_traversal_count,
+            "anchor_count": stats.anchor_count,
+            "unnamed_anchor_count": stats.unnamed_anchor_count,
+            "total_length": stats.total_length,
+            "bbox": stats.bbox.map(|bbox| [bbox.min().x, bbox.min().y, bbox.max().x, bbox.max().y]),
+        },
+        "lrms": lrms,
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&content).expect("the summary should be serializable")
+    );
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::Error => "error",
+    }
+}
+
+fn print_report_text(file: &Path, report: &ValidationReport) {
+    for issue in &report.issues {
+        println!(
+            "{}: {} [{}]: {issue}",
+            file.display(),
+            severity_name(issue.severity()),
+            issue.code()
+        );
+    }
+    println!(
+        "{} error(s), {} warning(s)",
+        report.with_severity(Severity::Error).count(),
+        report.with_severity(Severity::Warning).count()
+    );
+}
+
+fn print_report_json(file: &Path, report: &ValidationReport) {
+    let issues: Vec<_> = report
+        .issues
+        .iter()
+        .map(|issue| {
+            json!({
+                "severity": severity_name(issue.severity()),
+                "code": issue.code(),
+                "message": issue.to_string(),
+            })
+        })
+        .collect();
+    let content = json!({
+        "file": file.display().to_string(),
+        "errors": report.with_severity(Severity::Error).count(),
+        "warnings": report.with_severity(Severity::Warning).count(),
+        "issues": issues,
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&content).expect("the report should be serializable")
+    );
+}
+
+fn print_diff_text(diff: &LrsDiff) {
+    for id in &diff.added_lrms {
+        println!("+ {id}");
+    }
+    for id in &diff.removed_lrms {
+        println!("- {id}");
+    }
+    for lrm in &diff.modified_lrms {
+        println!("~ {}", lrm.id);
+        for name in &lrm.added_anchors {
+            println!("    + anchor {name}");
+        }
+        for name in &lrm.removed_anchors {
+            println!("    - anchor {name}");
+        }
+        for anchor in &lrm.moved_anchors {
+            println!(
+                "    ~ anchor {} moved by {:+.1} on the scale",
+                anchor.name, anchor.measure_delta
+            );
+        }
+        if let Some(distance) = lrm.geometry_distance {
+            println!("    ~ geometry moved by up to {distance:.1}");
+        }
+        if lrm.properties_changed {
+            println!("    ~ properties changed");
+        }
+    }
+    println!(
+        "{} added, {} removed, {} modified LRMs",
+        diff.added_lrms.len(),
+        diff.removed_lrms.len(),
+        diff.modified_lrms.len()
+    );
+}
+
+fn print_diff_json(diff: &LrsDiff) {
+    let modified_lrms: Vec<_> = diff
+        .modified_lrms
+        .iter()
+        .map(|lrm| {
+            let moved_anchors: Vec<_> = lrm
+                .moved_anchors
+                .iter()
+                .map(|anchor| {
+                    json!({
+                        "name": anchor.name,
+                        "measure_delta": anchor.measure_delta,
+                        "curve_position_delta": anchor.curve_position_delta,
+                    })
+                })
+                .collect();
+            json!({
+                "id": lrm.id,
+                "added_anchors": lrm.added_anchors,
+                "removed_anchors": lrm.removed_anchors,
+                "moved_anchors": moved_anchors,
+                "geometry_distance": lrm.geometry_distance,
+                "properties_changed": lrm.properties_changed,
+            })
+        })
+        .collect();
+    let content = json!({
+        "added_lrms": diff.added_lrms,
+        "removed_lrms": diff.removed_lrms,
+        "modified_lrms": modified_lrms,
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&content).expect("the diff should be serializable")
+    );
+}
+
+// `12+450` is 450 scale units after the anchor `12`, a measure without `+` is the anchor itself
+fn parse_measure(value: &str) -> Result<LrmScaleMeasure, String> {
+    match value.rsplit_once('+') {
+        Some((anchor_name, offset)) => offset
+            .trim()
+            .parse()
+            .map(|offset| LrmScaleMeasure::new(anchor_name.trim(), offset))
+            .map_err(|_| format!("invalid offset `{offset}`, expected e.g. `12+450`")),
+        None => Ok(LrmScaleMeasure::new(value.trim(), 0.)),
+    }
+}
+
+fn parse_bbox(value: &str) -> Result<Rect, String> {
+    let bounds = value
+        .split(',')
+        .map(|bound| bound.trim().parse::<f64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    match bounds[..] {
+        [min_x, min_y, max_x, max_y] => Ok(Rect::new(
+            coord! {x: min_x, y: min_y},
+            coord! {x: max_x, y: max_y},
+        )),
+        _ => Err("expected `min_lon,min_lat,max_lon,max_lat`".to_owned()),
+    }
+}
+
+/// What `locate` prints about a measure.
+struct Location {
+    point: Point,
+    bearing: f64,
+    /// The anchors just before and just after the measure, if there are any.
+    previous_anchor: Option<AnchorMarker>,
+    next_anchor: Option<AnchorMarker>,
+}
+
+fn locate(lrs: &ExtLrs, lrm_id: &str, measure: &LrmScaleMeasure) -> Result<Location, String> {
+    let lrm_index = lrs
+        .get_lrm(lrm_id)
+        .ok_or_else(|| format!("unknown lrm {lrm_id}"))?;
+    let oriented = lrs
+        .resolve_oriented(lrm_index, measure)
+        .map_err(|e| e.to_string())?;
+
+    let scale = &lrs.lrms()[lrm_index].scale;
+    let scale_position = scale
+        .get_position(measure.clone())
+        .map_err(|e| e.to_string())?;
+    let markers = lrs.anchor_markers(lrm_index).map_err(|e| e.to_string())?;
+    let mut previous_anchor: Option<(f64, &AnchorMarker)> = None;
+    let mut next_anchor: Option<(f64, &AnchorMarker)> = None;
+    for (anchor, marker) in scale.anchors.iter().zip(&markers) {
+        let position = anchor.scale_position();
+        if position <= scale_position {
+            if previous_anchor.is_none_or(|(best, _)| position > best) {
+                previous_anchor = Some((position, marker));
+            }
+        } else if next_anchor.is_none_or(|(best, _)| position < best) {
+            next_anchor = Some((position, marker));
+        }
+    }
+
+    Ok(Location {
+        point: oriented.point,
+        bearing: oriented.heading,
+        previous_anchor: previous_anchor.map(|(_, marker)| marker.clone()),
+        next_anchor: next_anchor.map(|(_, marker)| marker.clone()),
+    })
+}
+
+fn print_location_text(location: &Location, bearing: bool, anchors: bool) {
+    println!("{} {}", location.point.x(), location.point.y());
+    if bearing {
+        println!("Bearing: {:.1}°", location.bearing);
+    }
+    if anchors {
+        let anchor = |label: &str, marker: &Option<AnchorMarker>| match marker {
+            Some(marker) => println!(
+                "{label} anchor: {} at {} {}",
+                format_measure(&marker.measure),
+                marker.point.x(),
+                marker.point.y()
+            ),
+            None => println!("{label} anchor: none"),
+        };
+        anchor("Previous", &location.previous_anchor);
+        anchor("Next", &location.next_anchor);
+    }
+}
+
+fn print_location_json(
+    lrm_id: &str,
+    measure: &LrmScaleMeasure,
+    location: &Location,
+    bearing: bool,
+    anchors: bool,
+) {
+    let anchor = |marker: &AnchorMarker| {
+        json!({
+            "name": marker.name,
+            "anchor_name": marker.measure.anchor_name,
+            "scale_offset": marker.measure.scale_offset,
+            "lon": marker.point.x(),
+            "lat": marker.point.y(),
+        })
+    };
+    let mut content = json!({
+        "lrm_id": lrm_id,
+        "anchor_name": measure.anchor_name,
+        "scale_offset": measure.scale_offset,
+        "lon": location.point.x(),
+        "lat": location.point.y(),
+    });
+    if bearing {
+        content["bearing"] = json!(location.bearing);
+    }
+    if anchors {
+        content["previous_anchor"] = json!(location.previous_anchor.as_ref().map(anchor));
+        content["next_anchor"] = json!(location.next_anchor.as_ref().map(anchor));
+    }
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&content).expect("the location should be serializable")
+    );
+}
+
+fn print_projections(lrs: &ExtLrs, projections: &[LrmProjection], json: bool) {
+    let lrm_id = |projection: &LrmProjection| &lrs.lrms()[projection.measure.lrm.0].scale.id;
+    if !json {
+        for projection in projections {
+            println!(
+                "{} {} (lateral offset {:.1})",
+                lrm_id(projection),
+                format_measure(&projection.measure.measure),
+                projection.orthogonal_offset
+            );
+        }
+        return;
+    }
+    let projections: Vec<_> = projections
+        .iter()
+        .map(|projection| {
+            json!({
+                "lrm_id": lrm_id(projection),
+                "anchor_name": projection.measure.measure.anchor_name,
+                "scale_offset": projection.measure.measure.scale_offset,
+                "lateral_offset": projection.orthogonal_offset,
+            })
+        })
+        .collect();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&projections).expect("the projections should be serializable")
+    );
+}
+
+fn missing_feature<T>(format: Format) -> Result<T, String> {
+    let feature = match format {
+        Format::Lrs => "cli",
+        Format::Yaml => "yaml",
+        Format::Geojson => "geojson",
+        Format::Gpkg => "geopackage",
+        Format::Osm => "osm",
+        Format::Kml | Format::Kmz => "kml",
+    };
+    Err(format!("liblrs was built without the `{feature}` feature"))
+}
+
+// The LRS of a builder, without checking it: an OpenStreetMap extract has no anchor
+#[cfg(any(feature = "osm", feature = "yaml", feature = "geojson"))]
+fn build(mut builder: Builder, input: &Path) -> Result<AnyLrs, String> {
+    builder.add_source(&input.to_string_lossy());
+    Lrs::from_bytes(builder.build_data(properties!()))
+        .map(AnyLrs::Geographic)
+        .map_err(|e| e.to_string())
+}
+
+// `options` is only used by optional formats
+#[allow(unused_variables)]
+fn read_input(input: &Path, format: Format, options: &ConvertOptions) -> Result<AnyLrs, String> {
+    match format {
+        Format::Lrs => AnyLrs::new(input, None).map_err(|e| e.to_string()),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            let builder =
+                Builder::from_yaml(&std::fs::read_to_string(input).map_err(|e| e.to_string())?)
+                    .map_err(|e| e.to_string())?;
+            build(builder, input)
+        }
+        #[cfg(feature = "geojson")]
+        Format::Geojson => {
+            let collection = std::fs::read_to_string(input)
+                .map_err(|e| e.to_string())?
+                .parse::<geojson::FeatureCollection>()
+                .map_err(|e| e.to_string())?;
+            let mapping = liblrs::builder::GeoJsonMapping {
+                traversal_id: options.traversal_id_property.clone(),
+                lrm_id: options.lrm_id_property.clone(),
+                anchor_name: options.anchor_name_property.clone(),
+                measure: options.measure_property.clone(),
+            };
+            let builder =
+                Builder::from_geojson(&collection.features, &mapping).map_err(|e| e.to_string())?;
+            build(builder, input)
+        }
+        #[cfg(feature = "geopackage")]
+        Format::Gpkg => {
+            let mapping = liblrs::geopackage::LayerMapping {
+                traversals: options.traversals_layer.clone(),
+                lrms: options.lrms_layer.clone(),
+                anchors: options.anchors_layer.clone(),
+            };
+            Lrs::<liblrs::curves::SphericalLineStringCurve>::from_geopackage(input, &mapping)
+                .map(AnyLrs::Geographic)
+                .map_err(|e| e.to_string())
+        }
+        #[cfg(feature = "osm")]
+        Format::Osm => {
+            let lrm_tag = options
+                .lrm_tag
+                .as_deref()
+                .ok_or("--lrm-tag is required to read OpenStreetMap")?;
+            let mut builder = Builder::new();
+            #[cfg(feature = "osm-routes")]
+            let routes = options.routes;
+            #[cfg(not(feature = "osm-routes"))]
+            let routes = false;
+            if routes {
+                #[cfg(feature = "osm-routes")]
+                builder
+                    .read_osm_routes(
+                        &input.to_path_buf(),
+                        lrm_tag,
+                        Some(&mut liblrs::LoggingDataIssueReporter),
+                    )
+                    .map_err(|e| e.to_string())?;
+            } else {
+                // The same filters as `geometry_from_osm`
+                builder.read_from_osm(
+                    &input.to_path_buf(),
+                    lrm_tag,
+                    properties!("railway" => "rail"),
+                    properties!(
+                        "service"=> "siding",
+                        "service"=> "spur",
+                        "building"=> "*",
+                        "area"=> "yes",
+                        "gauge"=> "600",
+                        "roller_coaster"=> "*",
+                        "construction"=> "*"
+                    ),
+                    Some(&mut liblrs::LoggingDataIssueReporter),
+                );
+            }
+            build(builder, input)
+        }
+        Format::Kml | Format::Kmz => Err(format!("{format:?} files cannot be read")),
+        #[allow(unreachable_patterns)]
+        _ => missing_feature(format),
+    }
+}
+
+fn write_output(
+    lrs: &AnyLrs,
+    output: &Path,
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<(), String> {
+    with_any_lrs!(lrs, lrs => write_lrs(lrs, output, format, options))
+}
+
+// `options` is only used by optional formats
+#[allow(unused_variables)]
+fn write_lrs<CurveImpl: Curve>(
+    lrs: &Lrs<CurveImpl>,
+    output: &Path,
+    format: Format,
+    options: &ConvertOptions,
+) -> Result<(), String> {
+    let write = |content: &[u8]| std::fs::write(output, content).map_err(|e| e.to_string());
+    match format {
+        Format::Lrs => {
+            #[cfg(feature = "compression")]
+            if options.compress {
+                return write(&lrs.to_compressed_bytes());
+            }
+            write(&lrs.to_bytes())
+        }
+        #[cfg(feature = "yaml")]
+        Format::Yaml => write(lrs.to_yaml().as_bytes()),
+        #[cfg(feature = "geojson")]
+        Format::Geojson => write(lrs.to_geojson().to_string().as_bytes()),
+        #[cfg(feature = "geopackage")]
+        Format::Gpkg => lrs.to_geopackage(output).map_err(|e| e.to_string()),
+        #[cfg(feature = "kml")]
+        Format::Kml | Format::Kmz => {
+            let kml_options = liblrs::kml::KmlOptions {
+                tick_step: options.tick_step,
+            };
+            if format == Format::Kml {
+                write(lrs.to_kml(&kml_options).as_bytes())
+            } else {
+                let file = std::fs::File::create(output).map_err(|e| e.to_string())?;
+                lrs.to_kmz(file, &kml_options).map_err(|e| e.to_string())
+            }
+        }
+        Format::Osm => Err("OpenStreetMap files cannot be written".to_owned()),
+        #[allow(unreachable_patterns)]
+        _ => missing_feature(format),
+    }
+}
+
+// The value, or prints why a file cannot be read or written and exits with 2, as `validate`
+fn or_exit<T, E: std::fmt::Display>(result: Result<T, E>, file: &Path, action: &str) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}: could not {action}: {e}", file.display());
+        std::process::exit(2);
+    })
+}
+
+/// Example: to see what is inside an LRS file
+///
+/// `$ cargo run --release --bin liblrs -- inspect osm.lrs.bin`
+///
+/// or to check it before publishing it
+///
+/// `$ cargo run --release --bin liblrs -- validate osm.lrs.bin --json`
+///
+/// or to find a measure
+///
+/// `$ cargo run --release --bin liblrs -- locate osm.lrs.bin --lrm 830000 --measure 12+450 --anchors`
+///
+/// or to find the measure of a position
+///
+/// `$ cargo run --release --bin liblrs -- project osm.lrs.bin --lon 2.35 --lat 48.85 --all`
+///
+/// or to share a region
+///
+/// `$ cargo run --release --bin liblrs -- extract osm.lrs.bin region.lrs.bin --bbox 2.2,48.8,2.5,48.9`
+///
+/// or to review a new release
+///
+/// `$ cargo run --release --bin liblrs -- diff old.lrs.bin new.lrs.bin --json`
+///
+/// or to rewrite a file of a previous version
+///
+/// `$ cargo run --release --bin liblrs -- migrate old.lrs.bin osm.lrs.bin`
+///
+/// or to build it from YAML
+///
+/// `$ cargo run --release --features yaml --bin liblrs -- convert lines.yaml lines.lrs.bin`
+fn main() {
+    let cli_args = Args::parse();
+
+    match cli_args.command {
+        Command::Inspect { file, json } => {
+            let lrs = or_exit(AnyLrs::new(&file, None), &file, "read the LRS");
+            if json {
+                with_any_lrs!(&lrs, lrs => print_json(&file, lrs));
+            } else {
+                with_any_lrs!(&lrs, lrs => print_text(&file, lrs));
+            }
+        }
+        Command::Validate {
+            file,
+            json,
+            strict,
+            max_anchor_distance,
+            max_length_mismatch,
+        } => {
+            let lrs = or_exit(AnyLrs::new(&file, None), &file, "read the LRS");
+            let options = ValidationOptions {
+                max_anchor_distance,
+                max_length_mismatch,
+            };
+            let report = with_any_lrs!(&lrs, lrs => lrs.validate(&options));
+            if json {
+                print_report_json(&file, &report);
+            } else {
+                print_report_text(&file, &report);
+            }
+            if report.has_errors() || (strict && !report.issues.is_empty()) {
+                std::process::exit(1);
+            }
+        }
+        Command::Locate {
+            file,
+            lrm,
+            measure,
+            bearing,
+            anchors,
+            json,
+        } => {
+            let lrs = or_exit(ExtLrs::open(&file), &file, "read the LRS");
+            match locate(&lrs, &lrm, &measure) {
+                Ok(location) if json => {
+                    print_location_json(&lrm, &measure, &location, bearing, anchors)
+                }
+                Ok(location) => print_location_text(&location, bearing, anchors),
+                Err(e) => {
+                    eprintln!(
+                        "could not locate {} on {lrm}: {e}",
+                        format_measure(&measure)
+                    );
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Project {
+            file,
+            lon,
+            lat,
+            max_distance,
+            all,
+            json,
+        } => {
+            let lrs = or_exit(ExtLrs::open(&file), &file, "read the LRS");
+            let mut projections = lrs.lookup_all(Point::new(lon, lat), max_distance);
+            if projections.is_empty() {
+                eprintln!("no LRM within {max_distance} of {lon} {lat}");
+                std::process::exit(1);
+            }
+            if !all {
+                projections.truncate(1);
+            }
+            print_projections(&lrs, &projections, json);
+        }
+        Command::Extract {
+            input,
+            output,
+            bbox,
+            lrm,
+            #[cfg(feature = "compression")]
+            compress,
+        } => {
+            let filter = match bbox {
+                Some(bbox) => LoadFilter::BoundingBox(bbox),
+                None => LoadFilter::LrmIds(lrm),
+            };
+            let data = or_exit(std::fs::read(&input), &input, "read the LRS");
+            let lrs = or_exit(
+                AnyLrs::from_bytes_filtered(&data, None, &filter),
+                &input,
+                "read the LRS",
+            );
+            #[cfg(feature = "compression")]
+            let data = if compress {
+                with_any_lrs!(&lrs, lrs => lrs.to_compressed_bytes())
+            } else {
+                with_any_lrs!(&lrs, lrs => lrs.to_bytes())
+            };
+            #[cfg(not(feature = "compression"))]
+            let data = with_any_lrs!(&lrs, lrs => lrs.to_bytes());
+            or_exit(std::fs::write(&output, data), &output, "write the LRS");
+            eprintln!(
+                "{} LRMs extracted",
+                with_any_lrs!(&lrs, lrs => lrs.lrm_len())
+            );
+        }
+        Command::Diff {
+            old,
+            new,
+            json,
+            exit_code,
+        } => {
+            let old_lrs = or_exit(AnyLrs::new(&old, None), &old, "read the old LRS");
+            let new_lrs = or_exit(AnyLrs::new(&new, None), &new, "read the new LRS");
+            let diff = match (&old_lrs, &new_lrs) {
+                (AnyLrs::Geographic(old), AnyLrs::Geographic(new)) => lrs_diff(old, new),
+                (AnyLrs::Schematic(old), AnyLrs::Schematic(new)) => lrs_diff(old, new),
+                _ => {
+                    eprintln!(
+                        "could not compare a {:?} LRS with a {:?} one",
+                        old_lrs.geometry_type(),
+                        new_lrs.geometry_type()
+                    );
+                    std::process::exit(2);
+                }
+            };
+            if json {
+                print_diff_json(&diff);
+            } else {
+                print_diff_text(&diff);
+            }
+            if exit_code && !diff.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Migrate { input, output } => {
+            let data = or_exit(std::fs::read(&input), &input, "read the LRS");
+            let version = or_exit(schema_version(&data), &input, "read the LRS");
+            let upgraded = or_exit(AnyLrs::upgrade_bytes(&data), &input, "read the LRS");
+            or_exit(std::fs::write(&output, upgraded), &output, "write the LRS");
+            eprintln!("schema version {version} migrated to {SCHEMA_VERSION}");
+        }
+        Command::Convert {
+            input,
+            output,
+            from,
+            to,
+            options,
+        } => {
+            let from = from.unwrap_or_else(|| Format::from_path(&input));
+            let to = to.unwrap_or_else(|| Format::from_path(&output));
+            let result = read_input(&input, from, &options)
+                .and_then(|lrs| write_output(&lrs, &output, to, &options));
+            if let Err(e) = result {
+                eprintln!("could not convert {}: {e}", input.display());
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use geo::{LineString, Rect, coord};
+    use liblrs::builder::{AnchorOnLrm, Builder};
+    use liblrs::lrs::{AnyLrs, GeometryType};
+    use liblrs::lrs_ext::{AnchorMarker, ExtLrs};
+    use liblrs::{properties, with_any_lrs};
+    use std::path::Path;
+
+    use super::{
+        ConvertOptions, Format, locate, parse_bbox, parse_measure, read_input, write_output,
+    };
+
+    #[test]
+    fn measure() {
+        let measure = parse_measure("12+450").unwrap();
+        assert_eq!(measure.anchor_name, "12");
+        assert_eq!(measure.scale_offset, 450.);
+        let measure = parse_measure(" 12 ").unwrap();
+        assert_eq!(measure.anchor_name, "12");
+        assert_eq!(measure.scale_offset, 0.);
+        // Only the last `+` separates the offset
+        assert_eq!(parse_measure("a+b+5").unwrap().anchor_name, "a+b");
+        assert!(parse_measure("12+abc").is_err());
+    }
+
+    #[test]
+    fn bbox() {
+        assert_eq!(
+            parse_bbox("2.2, 48.8,2.5,48.9"),
+            Ok(Rect::new(
+                coord! {x: 2.2, y: 48.8},
+                coord! {x: 2.5, y: 48.9}
+            ))
+        );
+        assert!(parse_bbox("2.2,48.8,2.5").is_err());
+        assert!(parse_bbox("2.2,48.8,2.5,north").is_err());
+    }
+
+    #[test]
+    fn format_from_path() {
+        assert_eq!(Format::from_path(Path::new("lines.YAML")), Format::Yaml);
+        assert_eq!(Format::from_path(Path::new("lines.yml")), Format::Yaml);
+        assert_eq!(Format::from_path(Path::new("a/b.geojson")), Format::Geojson);
+        assert_eq!(Format::from_path(Path::new("b.gpkg")), Format::Gpkg);
+        assert_eq!(Format::from_path(Path::new("france.osm.pbf")), Format::Osm);
+        assert_eq!(Format::from_path(Path::new("b.kmz")), Format::Kmz);
+        assert_eq!(Format::from_path(Path::new("osm.lrs.bin")), Format::Lrs);
+        assert_eq!(Format::from_path(Path::new("")), Format::Lrs);
+    }
+
+    fn build() -> Vec<u8> {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 0.02, y: 0.}, properties!());
+        let geometry = LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 0.02, y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        let anchors: Vec<_> = [("0", 0., 0.), ("1", 0.01, 1000.), ("2", 0.02, 2000.)]
+            .into_iter()
+            .map(|(name, x, distance_along_lrm)| AnchorOnLrm {
+                anchor_index: b.add_anchor(name, Some(name), coord! {x: x, y: 0.}, properties!()),
+                distance_along_lrm,
+            })
+            .collect();
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+        b.build_data(properties!()).to_vec()
+    }
+
+    #[test]
+    fn surrounding_anchors() {
+        let lrs = ExtLrs::load(&build()).unwrap();
+        let name = |marker: Option<AnchorMarker>| marker.and_then(|m| m.name);
+
+        let location = locate(&lrs, "lrm", &parse_measure("0+500").unwrap()).unwrap();
+        assert_eq!(name(location.previous_anchor), Some("0".to_owned()));
+        assert_eq!(name(location.next_anchor), Some("1".to_owned()));
+
+        // An anchor at the measure is the previous one
+        let location = locate(&lrs, "lrm", &parse_measure("1").unwrap()).unwrap();
+        assert_eq!(name(location.previous_anchor), Some("1".to_owned()));
+        assert_eq!(name(location.next_anchor), Some("2".to_owned()));
+
+        let location = locate(&lrs, "lrm", &parse_measure("2").unwrap()).unwrap();
+        assert_eq!(name(location.next_anchor), None);
+
+        assert!(locate(&lrs, "unknown", &parse_measure("0").unwrap()).is_err());
+    }
+
+    #[test]
+    fn convert_schematic() {
+        let lrs = AnyLrs::from_bytes(&build(), Some(GeometryType::Schematic)).unwrap();
+        let path = std::env::temp_dir().join(format!("liblrs-{}-cli.lrs.bin", std::process::id()));
+        #[derive(Parser)]
+        struct Convert {
+            #[command(flatten)]
+            options: ConvertOptions,
+        }
+        let options = Convert::parse_from(["convert"]).options;
+        write_output(&lrs, &path, Format::Lrs, &options).unwrap();
+        let read = read_input(&path, Format::Lrs, &options);
+        std::fs::remove_file(&path).unwrap();
+
+        let read = read.unwrap();
+        assert_eq!(read.geometry_type(), GeometryType::Schematic);
+        assert_eq!(with_any_lrs!(&read, lrs => lrs.lrm_len()), 1);
+    }
+}
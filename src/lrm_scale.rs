@@ -4,7 +4,7 @@
 use geo::Point;
 use thiserror::Error;
 
-use crate::lrs::Properties;
+use crate::lrs::{NodeHandle, Properties, Validity};
 
 /// Measurement along the `Curve`. Typically in meters.
 pub type CurvePosition = f64;
@@ -48,6 +48,21 @@ pub struct UnnamedAnchor {
     /// Position of the anchor on the `Curve`.
     pub point: Option<Point>,
 
+    /// Surveyed position of the anchor, before its projection on the `Curve`.
+    pub surveyed_point: Option<Point>,
+
+    /// Signed distance between the surveyed position and the `Curve`, positive on the left.
+    pub projection_offset: Option<f64>,
+
+    /// When the anchor is valid, e.g. a milestone that was moved.
+    pub validity: Validity,
+
+    /// Identifier of the anchor in the file.
+    pub id: String,
+
+    /// The node the anchor is bound to, if any.
+    pub node: Option<NodeHandle>,
+
     /// Metadata to describe the node
     pub properties: Properties,
 }
@@ -70,6 +85,21 @@ pub struct NamedAnchor {
     /// Position of the anchor on the `Curve`.
     pub point: Option<Point>,
 
+    /// Surveyed position of the anchor, before its projection on the `Curve`.
+    pub surveyed_point: Option<Point>,
+
+    /// Signed distance between the surveyed position and the `Curve`, positive on the left.
+    pub projection_offset: Option<f64>,
+
+    /// When the anchor is valid, e.g. a milestone that was moved.
+    pub validity: Validity,
+
+    /// Identifier of the anchor in the file.
+    pub id: String,
+
+    /// The node the anchor is bound to, if any.
+    pub node: Option<NodeHandle>,
+
     /// Metadata to describe the node
     pub properties: Properties,
 }
@@ -99,6 +129,11 @@ impl Anchor {
             scale_position,
             curve_position,
             point,
+            surveyed_point: None,
+            projection_offset: None,
+            validity: Validity::default(),
+            id: name.to_owned(),
+            node: None,
             properties,
         })
     }
@@ -114,10 +149,52 @@ impl Anchor {
             scale_position,
             curve_position,
             point,
+            surveyed_point: None,
+            projection_offset: None,
+            validity: Validity::default(),
+            id: String::new(),
+            node: None,
             properties,
         })
     }
 
+    /// Records where the anchor was surveyed and how far it is from the `Curve`.
+    pub fn with_survey(
+        mut self,
+        surveyed_point: Option<Point>,
+        projection_offset: Option<f64>,
+    ) -> Self {
+        match &mut self {
+            Anchor::Named(anchor) => {
+                anchor.surveyed_point = surveyed_point;
+                anchor.projection_offset = projection_offset;
+            }
+            Anchor::Unnamed(anchor) => {
+                anchor.surveyed_point = surveyed_point;
+                anchor.projection_offset = projection_offset;
+            }
+        }
+        self
+    }
+
+    /// Records the identifier of the anchor in the file and the node it is bound to.
+    pub fn with_source(mut self, id: &str, node: Option<NodeHandle>) -> Self {
+        match &mut self {
+            Anchor::Named(anchor) => (anchor.id, anchor.node) = (id.to_owned(), node),
+            Anchor::Unnamed(anchor) => (anchor.id, anchor.node) = (id.to_owned(), node),
+        }
+        self
+    }
+
+    /// Records when the anchor is valid.
+    pub fn with_validity(mut self, validity: Validity) -> Self {
+        match &mut self {
+            Anchor::Named(anchor) => anchor.validity = validity,
+            Anchor::Unnamed(anchor) => anchor.validity = validity,
+        }
+        self
+    }
+
     /// Position of the anchor on the scale.
     ///
     /// This value is arbitrary. It typically is expressed in meters, but can be any unit.
@@ -140,6 +217,30 @@ impl Anchor {
         }
     }
 
+    /// When the anchor is valid
+    pub fn validity(&self) -> &Validity {
+        match self {
+            Anchor::Named(anchor) => &anchor.validity,
+            Anchor::Unnamed(anchor) => &anchor.validity,
+        }
+    }
+
+    /// Identifier of the anchor in the file
+    pub fn id(&self) -> &str {
+        match self {
+            Anchor::Named(anchor) => &anchor.id,
+            Anchor::Unnamed(anchor) => &anchor.id,
+        }
+    }
+
+    /// The node the anchor is bound to, if any
+    pub fn node(&self) -> Option<NodeHandle> {
+        match self {
+            Anchor::Named(anchor) => anchor.node,
+            Anchor::Unnamed(anchor) => anchor.node,
+        }
+    }
+
     /// Properties of the anchor
     pub fn properties(&self) -> &Properties {
         match self {
@@ -158,12 +259,32 @@ impl Anchor {
             Anchor::Unnamed(anchor) => anchor.point,
         }
     }
+
+    /// Surveyed position of the anchor, before its projection on the `Curve`
+    ///
+    /// It is the data quality information of the anchor, with [Anchor::projection_offset]
+    pub fn surveyed_point(&self) -> Option<Point> {
+        match self {
+            Anchor::Named(anchor) => anchor.surveyed_point,
+            Anchor::Unnamed(anchor) => anchor.surveyed_point,
+        }
+    }
+
+    /// Signed distance between the surveyed position and the `Curve`, positive on the left
+    ///
+    /// A post 12 m away from the track is probably misplaced or on another track
+    pub fn projection_offset(&self) -> Option<f64> {
+        match self {
+            Anchor::Named(anchor) => anchor.projection_offset,
+            Anchor::Unnamed(anchor) => anchor.projection_offset,
+        }
+    }
 }
 
 /// A measure defines a location on the [LrmScale].
 /// It is given as an [Anchor] name and an `offset` on that scale.
 /// It is often represented as `12+100` to say `“100 scale units after the Anchor 12`”.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LrmScaleMeasure {
     /// `Name` of the [Anchor]. While it is often named after a kilometer position,
     /// it can be anything (a letter, a landmark).
@@ -202,7 +323,14 @@ impl LrmScale {
             .find(|anchor| anchor.name == measure.anchor_name)
             .ok_or(LrmScaleError::UnknownAnchorName)?;
 
-        let scale_position = named_anchor.scale_position + measure.scale_offset;
+        self.curve_position_at(named_anchor.scale_position + measure.scale_offset)
+    }
+
+    // The curve position of a scale position, interpolated between the anchors around it
+    fn curve_position_at(
+        &self,
+        scale_position: ScalePosition,
+    ) -> Result<CurvePosition, LrmScaleError> {
         let anchors = self
             .anchors
             .windows(2)
@@ -230,6 +358,20 @@ impl LrmScale {
             .nearest_named(curve_position)
             .ok_or(LrmScaleError::NoAnchorFound)?;
 
+        let (anchor, ratio) = self.interpolation_anchor(curve_position)?;
+        Ok(LrmScaleMeasure {
+            anchor_name: named_anchor.name.clone(),
+            scale_offset: (anchor.scale_position() - named_anchor.scale_position)
+                + (curve_position - anchor.curve_position()) * ratio,
+        })
+    }
+
+    // The anchor from which the scale position of a curve position is interpolated,
+    // with how much the scale increases per unit of curve
+    fn interpolation_anchor(
+        &self,
+        curve_position: CurvePosition,
+    ) -> Result<(&Anchor, f64), LrmScaleError> {
         // We need the anchor just before and just after the position to interpolate the scale position
         // If we are looking for a curve position that is after the last anchor, we extrapolate from the last two
         let anchors = self
@@ -243,12 +385,16 @@ impl LrmScale {
         // This ratio isn’t always constant due to irregularities in anchor measurements
         let ratio = (anchors[0].scale_position() - anchors[1].scale_position())
             / (anchors[0].curve_position() - anchors[1].curve_position());
+        Ok((&anchors[0], ratio))
+    }
 
-        Ok(LrmScaleMeasure {
-            anchor_name: named_anchor.name.clone(),
-            scale_offset: (anchors[0].scale_position() - named_anchor.scale_position)
-                + (curve_position - anchors[0].curve_position()) * ratio,
-        })
+    // The scale position of a curve position, without naming the measure
+    fn scale_position_at(
+        &self,
+        curve_position: CurvePosition,
+    ) -> Result<ScalePosition, LrmScaleError> {
+        let (anchor, ratio) = self.interpolation_anchor(curve_position)?;
+        Ok(anchor.scale_position() + (curve_position - anchor.curve_position()) * ratio)
     }
 
     /// Returns a measure given a distance along the `LrmScale`.
@@ -271,6 +417,11 @@ impl LrmScale {
     /// Locates a point along the scale given an [Anchor] and an `offset`,
     /// which might be negative.
     pub fn get_position(&self, measure: LrmScaleMeasure) -> Result<ScalePosition, LrmScaleError> {
+        self.scale_position_of(&measure)
+    }
+
+    // Same as `get_position`, without taking the measure
+    fn scale_position_of(&self, measure: &LrmScaleMeasure) -> Result<ScalePosition, LrmScaleError> {
         let named_anchor = self
             .iter_named()
             .find(|anchor| anchor.name == measure.anchor_name)
@@ -279,6 +430,133 @@ impl LrmScale {
         Ok(named_anchor.scale_position + measure.scale_offset)
     }
 
+    /// The measure of an [Anchor] of the scale.
+    /// A named `Anchor` is its own reference. An unnamed one is measured from the named `Anchor` before it, see [LrmScale::get_measure].
+    pub fn anchor_measure(&self, anchor: &Anchor) -> Result<LrmScaleMeasure, LrmScaleError> {
+        match anchor {
+            Anchor::Named(anchor) => Ok(LrmScaleMeasure::new(&anchor.name, 0.)),
+            Anchor::Unnamed(anchor) => self.get_measure(anchor.scale_position),
+        }
+    }
+
+    /// The [Anchor] objects between two measures, both included, sorted by increasing scale position.
+    /// The measures can be given in any order.
+    pub fn anchors_between(
+        &self,
+        from: &LrmScaleMeasure,
+        to: &LrmScaleMeasure,
+    ) -> Result<Vec<&Anchor>, LrmScaleError> {
+        let from = self.get_position(from.clone())?;
+        let to = self.get_position(to.clone())?;
+        let range = from.min(to)..=from.max(to);
+        let mut anchors: Vec<_> = self
+            .anchors
+            .iter()
+            .filter(|anchor| range.contains(&anchor.scale_position()))
+            .collect();
+        anchors.sort_by(|a, b| a.scale_position().total_cmp(&b.scale_position()));
+        Ok(anchors)
+    }
+
+    /// Scale units covered by one lap of a loop: the difference between the scale positions at the end and at the start of the `Curve`.
+    ///
+    /// It is negative when the measures decrease along the `Curve`.
+    pub fn loop_period(&self) -> Result<ScalePosition, LrmScaleError> {
+        Ok(self.scale_position_at(1.)? - self.scale_position_at(0.)?)
+    }
+
+    /// Locates a point along a closed `Curve` given an [Anchor] and an `offset`.
+    ///
+    /// The measures are modular: a measure past the end of the loop continues from its start,
+    /// and a measure before its start comes from its end.
+    /// The `[CurvePosition]` is between 0.0 and 1.0, both included.
+    pub fn locate_point_wrapping(
+        &self,
+        measure: &LrmScaleMeasure,
+    ) -> Result<CurvePosition, LrmScaleError> {
+        let period = self.loop_period()?;
+        if period == 0. {
+            return Err(LrmScaleError::NoAnchorFound);
+        }
+        // Resolving is on the hot path: the measures are not named to avoid allocating their names
+        let origin = self.scale_position_at(0.)?;
+        let scale_position = self.scale_position_of(measure)?;
+        let lap_offset = ((scale_position - origin) / period).rem_euclid(1.) * period;
+        Ok(self.curve_position_at(origin + lap_offset)?.clamp(0., 1.))
+    }
+
+    /// Returns the named [Anchor] closest to the `measure` and the signed distance to it in scale units.
+    ///
+    /// The distance is positive when the `measure` is after the [Anchor].
+    /// Useful to normalize noisy inputs: `12+998` is usually meant to be `13+000`.
+    /// Fails if no [Anchor] is within `max_delta` scale units.
+    pub fn nearest_anchor(
+        &self,
+        measure: &LrmScaleMeasure,
+        max_delta: ScalePosition,
+    ) -> Result<(&NamedAnchor, ScalePosition), LrmScaleError> {
+        let scale_position = self
+            .iter_named()
+            .find(|anchor| anchor.name == measure.anchor_name)
+            .ok_or(LrmScaleError::UnknownAnchorName)?
+            .scale_position
+            + measure.scale_offset;
+
+        self.iter_named()
+            .map(|anchor| (anchor, scale_position - anchor.scale_position))
+            .filter(|(_, delta)| delta.abs() <= max_delta)
+            .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+            .ok_or(LrmScaleError::NoAnchorFound)
+    }
+
+    /// Generates named [Anchor] objects every `step` scale units, strictly between the first and last anchors.
+    ///
+    /// Each generated `Anchor` is at a multiple of `step` and named after it: with a scale in meters
+    /// and a `step` of 1000, the `Anchor` at 12000 is named `12`. Its curve position is interpolated
+    /// between its neighbours and it has the property `generated`.
+    /// The positions and the names already used by a named `Anchor` are skipped.
+    /// The generated anchors are not inserted in the scale.
+    pub fn generate_anchors(&self, step: ScalePosition) -> Vec<Anchor> {
+        let positions = self.anchors.iter().map(Anchor::scale_position);
+        let from = positions.clone().fold(f64::INFINITY, f64::min);
+        let to = positions.fold(f64::NEG_INFINITY, f64::max);
+        let mut generated = vec![];
+        if step <= 0. || from >= to {
+            return generated;
+        }
+
+        let mut k = (from / step).floor() as i64 + 1;
+        while (k as f64) * step < to {
+            let scale_position = k as f64 * step;
+            let name = k.to_string();
+            k += 1;
+            if self
+                .iter_named()
+                .any(|anchor| anchor.name == name || anchor.scale_position == scale_position)
+            {
+                continue;
+            }
+            let Some(window) = self.anchors.windows(2).find(|w| {
+                let (a, b) = (w[0].scale_position(), w[1].scale_position());
+                a != b && a.min(b) <= scale_position && scale_position <= a.max(b)
+            }) else {
+                continue;
+            };
+            let (a, b) = (&window[0], &window[1]);
+            let curve_position = a.curve_position()
+                + (b.curve_position() - a.curve_position()) * (scale_position - a.scale_position())
+                    / (b.scale_position() - a.scale_position());
+            generated.push(Anchor::new_named(
+                &name,
+                scale_position,
+                curve_position,
+                None,
+                crate::properties!("generated" => "true"),
+            ));
+        }
+        generated
+    }
+
     fn nearest_named(&self, curve_position: CurvePosition) -> Option<&NamedAnchor> {
         // Tries to find the Anchor whose curve_position is the biggest possible, yet smaller than Curve position
         // Otherwise take the first named
@@ -313,6 +591,8 @@ impl LrmScale {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use approx::assert_relative_eq;
+
     use crate::properties;
 
     use super::*;
@@ -327,6 +607,53 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn locate_point_wrapping() {
+        // One lap is 20 scale units
+        let scale = scale();
+        assert_eq!(scale.loop_period(), Ok(20.));
+        assert_eq!(
+            scale.locate_point_wrapping(&LrmScaleMeasure::new("a", 5.)),
+            Ok(0.25)
+        );
+        assert_eq!(
+            scale.locate_point_wrapping(&LrmScaleMeasure::new("b", 15.)),
+            Ok(0.25)
+        );
+        assert_eq!(
+            scale.locate_point_wrapping(&LrmScaleMeasure::new("a", -5.)),
+            Ok(0.75)
+        );
+    }
+
+    #[test]
+    fn generate_anchors() {
+        let scale = LrmScale {
+            id: "id".to_owned(),
+            anchors: vec![
+                Anchor::new_named("a", 500., 0., None, properties!()),
+                Anchor::new_unnamed(2000., 0.5, None, properties!()),
+                Anchor::new_named("3", 3000., 1., None, properties!()),
+            ],
+        };
+        let generated = scale.generate_anchors(1000.);
+        assert_eq!(generated.len(), 2);
+        assert_eq!(
+            generated[0],
+            Anchor::new_named(
+                "1",
+                1000.,
+                0.5 / 3.,
+                None,
+                properties!("generated" => "true")
+            )
+        );
+        // The unnamed anchor at 2000 does not prevent a named anchor
+        assert_eq!(generated[1].scale_position(), 2000.);
+        assert_relative_eq!(generated[1].curve_position(), 0.5);
+        assert!(scale.generate_anchors(0.).is_empty());
+    }
+
     #[test]
     fn locate_point() {
         // Everything a usual
@@ -439,6 +766,65 @@ pub(crate) mod tests {
         assert_eq!(measure.scale_offset, 15.);
     }
 
+    #[test]
+    fn anchors_between() {
+        // ----Unnamed(100)----A(200)----Unnamed(250)----B(300)----Unnamed(400)---
+        let scale = LrmScale {
+            id: "id".to_owned(),
+            anchors: vec![
+                Anchor::new_unnamed(0., 100., None, properties!()),
+                Anchor::new_named("a", 1., 200., None, properties!()),
+                Anchor::new_unnamed(1.5, 250., None, properties!()),
+                Anchor::new_named("b", 3., 300., None, properties!()),
+                Anchor::new_unnamed(4., 400., None, properties!()),
+            ],
+        };
+        let positions = |from, to| {
+            scale
+                .anchors_between(&from, &to)
+                .unwrap()
+                .iter()
+                .map(|anchor| anchor.scale_position())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            positions(LrmScaleMeasure::new("a", 0.), LrmScaleMeasure::new("b", 0.)),
+            [1., 1.5, 3.]
+        );
+        assert_eq!(
+            positions(
+                LrmScaleMeasure::new("b", 2.),
+                LrmScaleMeasure::new("a", -2.)
+            ),
+            [0., 1., 1.5, 3., 4.]
+        );
+        assert!(
+            positions(
+                LrmScaleMeasure::new("a", 0.1),
+                LrmScaleMeasure::new("a", 0.2)
+            )
+            .is_empty()
+        );
+        assert_eq!(
+            scale.anchors_between(
+                &LrmScaleMeasure::new("c", 0.),
+                &LrmScaleMeasure::new("a", 0.)
+            ),
+            Err(LrmScaleError::UnknownAnchorName)
+        );
+
+        let measure = scale.anchor_measure(&scale.anchors[2]).unwrap();
+        assert_eq!(
+            (measure.anchor_name.as_str(), measure.scale_offset),
+            ("a", 0.5)
+        );
+        let measure = scale.anchor_measure(&scale.anchors[3]).unwrap();
+        assert_eq!(
+            (measure.anchor_name.as_str(), measure.scale_offset),
+            ("b", 0.)
+        );
+    }
+
     #[test]
     fn get_position() {
         // a(scale 0)----position(scale a+5)----b(scale 10)
@@ -460,6 +846,33 @@ pub(crate) mod tests {
         assert_eq!(position, 25.);
     }
 
+    #[test]
+    fn nearest_anchor() {
+        // a(scale 0)----b(scale 10)
+        let scale = scale();
+
+        let (anchor, delta) = scale
+            .nearest_anchor(&LrmScaleMeasure::new("a", 9.8), 1.)
+            .unwrap();
+        assert_eq!(anchor.name, "b");
+        assert_relative_eq!(delta, -0.2, epsilon = 1e-9);
+
+        let (anchor, delta) = scale
+            .nearest_anchor(&LrmScaleMeasure::new("b", -9.5), 1.)
+            .unwrap();
+        assert_eq!(anchor.name, "a");
+        assert_eq!(delta, 0.5);
+
+        assert_eq!(
+            scale.nearest_anchor(&LrmScaleMeasure::new("a", 5.), 1.),
+            Err(LrmScaleError::NoAnchorFound)
+        );
+        assert_eq!(
+            scale.nearest_anchor(&LrmScaleMeasure::new("c", 5.), 1.),
+            Err(LrmScaleError::UnknownAnchorName)
+        );
+    }
+
     #[test]
     fn single_anchor() {
         // Scenario where the curve is to short to have an anchor
@@ -6,19 +6,29 @@
 
 extern crate flatbuffers;
 
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, OnceLock};
 
-use flatbuffers::{ForwardsUOffset, Vector};
+use flatbuffers::{FlatBufferBuilder, ForwardsUOffset, Vector, WIPOffset};
 use geo::orient::Direction;
 use geo_index::rtree::{RTreeIndex, RTreeRef};
 use thiserror::Error;
 
-use crate::curves::{Curve, CurveError};
+use crate::curves::{Curve, CurveError, PlanarLineStringCurve, SphericalLineStringCurve};
+use crate::events::{LinearEvent, PointEvent, measure_to_fb, typed_properties_to_fb};
+use crate::file_format;
 use crate::lrm_scale::{
     Anchor, CurvePosition, LrmScale, LrmScaleError, LrmScaleMeasure, ScalePosition,
 };
 use crate::lrs_generated;
-use geo::{Contains, LineString, Point, coord, point};
+use crate::spatial_index::{SpatialIndexes, spatial_indexes};
+use geo::line_intersection::{LineIntersection, line_intersection};
+use geo::{
+    BoundingRect, Contains, Coord, Intersects, LineString, Point, Polygon, Rect, coord, point,
+};
 
 /// Used as handle to identify a [`LrmScale`] within a specific [`Lrs`].
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
@@ -33,6 +43,7 @@ pub struct NodeHandle(pub usize);
 
 /// Represents an Linear Reference Method (LRM).
 /// It is the combination of one (or more) [`Traversal`]s for one [`LrmScale`].
+#[derive(Clone, Debug)]
 pub struct Lrm {
     /// The scale of this [`Lrm`].
     pub scale: LrmScale,
@@ -40,6 +51,29 @@ pub struct Lrm {
     pub traversal: TraversalHandle,
     /// Metadata to describe the Lrm
     pub properties: Properties,
+    /// Whether the measures increase ([`Direction::Default`]) or decrease ([`Direction::Reversed`])
+    /// along the geometry of the [`Traversal`].
+    pub measure_direction: Direction,
+    /// Other [`Traversal`]s sharing the scale of the [`Lrm`], such as the other track of a double-track line.
+    /// The positions are transferred perpendicularly from `traversal`, see [`LrsBase::traversal_project`].
+    pub alternate_traversals: Vec<TraversalHandle>,
+    /// When the [`Lrm`] is valid, see [`Lrs::lrms_at`].
+    pub validity: Validity,
+}
+
+impl Lrm {
+    /// The main [`Traversal`] and the alternate ones.
+    pub fn all_traversals(&self) -> impl Iterator<Item = TraversalHandle> + '_ {
+        std::iter::once(self.traversal).chain(self.alternate_traversals.iter().copied())
+    }
+
+    /// The direction of increasing measures, given the bearing of the [`Traversal`] in degrees.
+    pub fn measures_heading(&self, traversal_bearing: f64) -> f64 {
+        match self.measure_direction {
+            Direction::Default => traversal_bearing,
+            Direction::Reversed => (traversal_bearing + 180.).rem_euclid(360.),
+        }
+    }
 }
 
 /// A [`Traversal`] is a path in the network that ends [`Curve`].
@@ -48,9 +82,104 @@ pub struct Traversal<CurveImpl: Curve> {
     /// Identifies this [`Traversal`].
     pub id: String,
     /// The geometrical [`Curve`] of this [`Traversal`].
-    pub curve: CurveImpl,
+    pub curve: Deferred<CurveImpl>,
     /// All the [`Lrm`]s that use this [`Traversal`].
     pub lrms: Vec<LrmHandle>,
+    /// The [`Segment`]s that compose this [`Traversal`], in order.
+    pub segments: Vec<SegmentOfTraversal>,
+    /// Metadata to describe the [`Traversal`], such as the name of the line or its operator.
+    pub properties: Properties,
+}
+
+/// The bytes of a file shared with an [`Lrs`] that reads them when they are first used, see [`Lrs::from_shared_bytes`].
+pub type SharedBytes = Arc<dyn AsRef<[u8]> + Send + Sync>;
+
+/// A value of an [`Lrs`], such as the [`Curve`] of a [`Traversal`], that may only be read from the file when it is first used.
+///
+/// It dereferences to the value. Once read, the value is kept until the [`Lrs`] is dropped.
+pub struct Deferred<T> {
+    value: OnceLock<T>,
+    read: Option<Box<dyn Fn() -> T + Send + Sync>>,
+}
+
+impl<T> Deferred<T> {
+    fn new(read: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self {
+            value: OnceLock::new(),
+            read: Some(Box::new(read)),
+        }
+    }
+
+    /// `true` once the value has been read from the file, or if it was not read from a file.
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    fn get(&self) -> &T {
+        self.value.get_or_init(|| match &self.read {
+            Some(read) => read(),
+            None => unreachable!("a value without reader is set when created"),
+        })
+    }
+}
+
+impl<T> From<T> for Deferred<T> {
+    fn from(value: T) -> Self {
+        Self {
+            value: OnceLock::from(value),
+            read: None,
+        }
+    }
+}
+
+impl<T> Deref for Deferred<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T> DerefMut for Deferred<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.get();
+        self.value
+            .get_mut()
+            .expect("the value was read just before")
+    }
+}
+
+impl<T: PartialEq> PartialEq for Deferred<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Deferred<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// A [`Traversal`] is composed by many [`Segment`]s.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SegmentOfTraversal {
+    /// Index of the considered [`Segment`] in the [`Lrs`].
+    pub segment_index: usize,
+    /// When integrating the [`Segment`] in the [`Traversal`], if we should consider the coordinates in reverse order.
+    pub reversed: bool,
+}
+
+impl From<SegmentOfTraversal> for lrs_generated::SegmentOfTraversal {
+    fn from(val: SegmentOfTraversal) -> Self {
+        lrs_generated::SegmentOfTraversal::new(
+            val.segment_index as u64,
+            match val.reversed {
+                true => lrs_generated::Direction::Decreasing,
+                false => lrs_generated::Direction::Increasing,
+            },
+        )
+    }
 }
 
 /// The Linear Reference System. It must be specified for a given implementation
@@ -68,6 +197,226 @@ pub struct Lrs<CurveImpl: Curve> {
     pub segments: Vec<Segment>,
     /// An RTree spatial index of the LRM extents
     pub rtree_data: Option<Vec<u8>>,
+    /// How the file was produced, if it was recorded
+    pub build_info: Option<BuildInfo>,
+    /// An RTree spatial index of the fragments of the [`Traversal`]s
+    pub fragment_index: Option<FragmentIndex>,
+    /// All the [`Connection`]s between the [`Traversal`]s of this Lrs
+    pub connections: Vec<Connection>,
+    /// All the [`PointEvent`]s stored with this Lrs
+    pub point_events: Vec<PointEvent>,
+    /// All the [`LinearEvent`]s stored with this Lrs
+    pub linear_events: Vec<LinearEvent>,
+    /// Coordinate reference system of the coordinates, such as `EPSG:4326`, if it was recorded
+    pub crs: Option<String>,
+    /// When positive, the coordinates of the [`Segment`]s are written as multiples of this step
+    pub coordinate_precision: f64,
+}
+
+/// How the coordinates of an [`Lrs`] are interpreted, and which [`Curve`] implementation must be used.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GeometryType {
+    /// Longitude and latitude: lengths are computed on a sphere with [`SphericalLineStringCurve`].
+    Geographic,
+    /// Projected or schematic coordinates: lengths are computed on a plane with [`PlanarLineStringCurve`].
+    Schematic,
+}
+
+impl GeometryType {
+    /// The [`GeometryType`] handled by a [`Curve`] implementation.
+    pub fn of<CurveImpl: Curve>() -> Self {
+        if CurveImpl::IS_SPHERICAL {
+            Self::Geographic
+        } else {
+            Self::Schematic
+        }
+    }
+
+    /// Reads the [`GeometryType`] recorded in a file, without loading it.
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, LrsError> {
+        let payload = file_format::open(buf)?;
+        let lrs = lrs_generated::root_as_lrs(&payload).map_err(LrsError::InvalidArchive)?;
+        Ok(lrs.geometry_type().into())
+    }
+}
+
+impl From<lrs_generated::GeometryType> for GeometryType {
+    fn from(geometry_type: lrs_generated::GeometryType) -> Self {
+        match geometry_type {
+            lrs_generated::GeometryType::Schematic => Self::Schematic,
+            _ => Self::Geographic,
+        }
+    }
+}
+
+/// An [`Lrs`] whose [`Curve`] implementation is chosen from the [`GeometryType`] of the file.
+pub enum AnyLrs {
+    /// The file has geographic coordinates.
+    Geographic(Lrs<SphericalLineStringCurve>),
+    /// The file has projected or schematic coordinates.
+    Schematic(Lrs<PlanarLineStringCurve>),
+}
+
+impl AnyLrs {
+    /// Loads an [`Lrs`] with the [`Curve`] matching the [`GeometryType`] recorded in the file.
+    ///
+    /// When `geometry_type` is given, it overrides the one of the file, e.g. for a file where it was not recorded correctly.
+    pub fn from_bytes(buf: &[u8], geometry_type: Option<GeometryType>) -> Result<Self, LrsError> {
+        Self::load(buf, geometry_type, None)
+    }
+
+    /// Loads only some [`Lrm`]s, see [`Lrs::from_bytes_filtered`] and [`AnyLrs::from_bytes`].
+    pub fn from_bytes_filtered(
+        buf: &[u8],
+        geometry_type: Option<GeometryType>,
+        filter: &LoadFilter,
+    ) -> Result<Self, LrsError> {
+        Self::load(buf, geometry_type, Some(filter))
+    }
+
+    /// Loads some [`Lrm`]s from the beginning of a file that is still being received, see [`Lrs::from_partial_bytes`].
+    pub fn from_partial_bytes(
+        prefix: &[u8],
+        filter: &LoadFilter,
+    ) -> Result<Option<Self>, LrsError> {
+        let Some(lrs) = partial_root(prefix, filter) else {
+            return Ok(None);
+        };
+        match GeometryType::from(lrs.geometry_type()) {
+            GeometryType::Geographic => {
+                Lrs::load_root(lrs, None, None, Some(filter), true).map(Self::Geographic)
+            }
+            GeometryType::Schematic => {
+                Lrs::load_root(lrs, None, None, Some(filter), true).map(Self::Schematic)
+            }
+        }
+        .map(Some)
+    }
+
+    /// Loads an [`Lrs`] from shared bytes without decoding the geometries, see [`Lrs::from_shared_bytes`] and [`AnyLrs::from_bytes`].
+    pub fn from_shared_bytes(
+        bytes: SharedBytes,
+        geometry_type: Option<GeometryType>,
+    ) -> Result<Self, LrsError> {
+        let payload = shared_payload(bytes)?;
+        let check_geometry_type = geometry_type.is_none();
+        let geometry_type = match geometry_type {
+            Some(geometry_type) => geometry_type,
+            None => lrs_generated::root_as_lrs((*payload).as_ref())
+                .map_err(LrsError::InvalidArchive)?
+                .geometry_type()
+                .into(),
+        };
+        match geometry_type {
+            GeometryType::Geographic => {
+                Lrs::load_shared(&payload, check_geometry_type).map(Self::Geographic)
+            }
+            GeometryType::Schematic => {
+                Lrs::load_shared(&payload, check_geometry_type).map(Self::Schematic)
+            }
+        }
+    }
+
+    /// Loads an [`Lrs`] from a file, see [`AnyLrs::from_bytes`].
+    pub fn new<P: AsRef<std::path::Path>>(
+        filename: P,
+        geometry_type: Option<GeometryType>,
+    ) -> Result<Self, LrsError> {
+        let buf = std::fs::read(filename).map_err(|_| LrsError::OpenFileError)?;
+        Self::from_bytes(&buf, geometry_type)
+    }
+
+    fn load(
+        buf: &[u8],
+        geometry_type: Option<GeometryType>,
+        filter: Option<&LoadFilter>,
+    ) -> Result<Self, LrsError> {
+        // The geometry type of the file is only checked when it is not overridden
+        let check_geometry_type = geometry_type.is_none();
+        let geometry_type = match geometry_type {
+            Some(geometry_type) => geometry_type,
+            None => GeometryType::from_bytes(buf)?,
+        };
+        match geometry_type {
+            GeometryType::Geographic => {
+                Lrs::load(buf, None, filter, check_geometry_type).map(Self::Geographic)
+            }
+            GeometryType::Schematic => {
+                Lrs::load(buf, None, filter, check_geometry_type).map(Self::Schematic)
+            }
+        }
+    }
+
+    /// Rewrites a file with the current version of the schema and the [`Curve`] matching its [`GeometryType`],
+    /// see [`Lrs::upgrade_bytes`].
+    pub fn upgrade_bytes(buf: &[u8]) -> Result<Vec<u8>, LrsError> {
+        match GeometryType::from_bytes(buf)? {
+            GeometryType::Geographic => Lrs::<SphericalLineStringCurve>::upgrade_bytes(buf),
+            GeometryType::Schematic => Lrs::<PlanarLineStringCurve>::upgrade_bytes(buf),
+        }
+    }
+
+    /// The [`GeometryType`] of the loaded [`Lrs`].
+    pub fn geometry_type(&self) -> GeometryType {
+        match self {
+            Self::Geographic(_) => GeometryType::Geographic,
+            Self::Schematic(_) => GeometryType::Schematic,
+        }
+    }
+}
+
+#[macro_export]
+/// Evaluates an expression on the [`Lrs`] of an [`AnyLrs`], whatever its [`Curve`]:
+/// `with_any_lrs!(&any_lrs, lrs => lrs.lrm_len())`.
+macro_rules! with_any_lrs {
+    ($any_lrs:expr, $lrs:ident => $body:expr) => {
+        match $any_lrs {
+            $crate::lrs::AnyLrs::Geographic($lrs) => $body,
+            $crate::lrs::AnyLrs::Schematic($lrs) => $body,
+        }
+    };
+}
+
+/// A spatial index over small pieces (fragments) of the [`Curve`]s of the [`Traversal`]s.
+///
+/// A long and winding [`Traversal`] has a large bounding box. The fragments are much smaller
+/// and allow to discard most of the [`Traversal`]s when looking for the ones near a [`Point`].
+pub struct FragmentIndex {
+    /// The packed RTree, as serialized by [geo_index]
+    pub rtree_data: Vec<u8>,
+    /// The [`Traversal`] of every fragment of the RTree
+    pub traversals: Vec<TraversalHandle>,
+}
+
+/// Provenance of an [`Lrs`] file, as recorded by the [`crate::builder::Builder`].
+///
+/// It is useful to know how a problematic file was produced.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BuildInfo {
+    /// Version of liblrs that built the file.
+    pub liblrs_version: String,
+    /// Options given to the builder.
+    pub options: Properties,
+    /// Identifiers of the source datasets.
+    pub sources: Vec<String>,
+}
+
+impl From<lrs_generated::BuildInfo<'_>> for BuildInfo {
+    fn from(fb_build_info: lrs_generated::BuildInfo) -> Self {
+        Self {
+            liblrs_version: fb_build_info
+                .liblrs_version()
+                .unwrap_or_default()
+                .to_owned(),
+            options: from_fb(fb_build_info.options()),
+            sources: fb_build_info
+                .sources()
+                .unwrap_or_default()
+                .iter()
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
 }
 
 /// A Node is a topological element of the [`Lrs`] that represents a intersection (or an extremity) of an [`Lrm`]
@@ -102,6 +451,66 @@ impl From<lrs_generated::Node<'_>> for Node {
     }
 }
 
+/// An extremity of a [`Traversal`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TraversalEnd {
+    /// Where the [`Curve`] of the [`Traversal`] begins.
+    Start,
+    /// Where the [`Curve`] of the [`Traversal`] ends.
+    End,
+}
+
+impl From<lrs_generated::TraversalEnd> for TraversalEnd {
+    fn from(fb_end: lrs_generated::TraversalEnd) -> Self {
+        match fb_end {
+            lrs_generated::TraversalEnd::End => Self::End,
+            _ => Self::Start,
+        }
+    }
+}
+
+impl From<TraversalEnd> for lrs_generated::TraversalEnd {
+    fn from(end: TraversalEnd) -> Self {
+        match end {
+            TraversalEnd::Start => Self::Start,
+            TraversalEnd::End => Self::End,
+        }
+    }
+}
+
+/// A [`Connection`] is a topological element of the [`Lrs`]: at a [`Node`],
+/// it allows to go from an extremity of a [`Traversal`] to an extremity of an other [`Traversal`].
+///
+/// On a railway switch, it is possible to go from the main track to both branches, but not from one branch to the other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Connection {
+    /// Where the [`Traversal`]s meet.
+    pub node: NodeHandle,
+    /// The [`Traversal`] and its extremity where the movement begins.
+    pub from: (TraversalHandle, TraversalEnd),
+    /// The [`Traversal`] and its extremity where the movement ends.
+    pub to: (TraversalHandle, TraversalEnd),
+    /// The movement is also allowed from `to` to `from`.
+    pub bidirectional: bool,
+}
+
+impl From<lrs_generated::Connection<'_>> for Connection {
+    fn from(fb_connection: lrs_generated::Connection) -> Self {
+        Self {
+            node: NodeHandle(fb_connection.node_index() as usize),
+            from: (
+                TraversalHandle(fb_connection.from_traversal_index() as usize),
+                fb_connection.from_end().into(),
+            ),
+            to: (
+                TraversalHandle(fb_connection.to_traversal_index() as usize),
+                fb_connection.to_end().into(),
+            ),
+            bidirectional: fb_connection.bidirectional(),
+        }
+    }
+}
+
 /// A segment is a topological element of the [`Lrs`] that represents a piece of the [`Curve`] of an [`Lrm`]
 ///
 /// It has a start and end [`Node`].
@@ -114,15 +523,37 @@ pub struct Segment {
     pub start_node: NodeHandle,
     /// End [`Node`]
     pub end_node: NodeHandle,
+    /// The geometry of the [`Segment`], from the start to the end [`Node`]
+    pub geometry: Deferred<LineString>,
 }
 
-impl From<lrs_generated::Segment<'_>> for Segment {
-    fn from(fb_segment: lrs_generated::Segment) -> Self {
+impl Segment {
+    /// Reads a [`Segment`] of a file whose coordinates may be quantized to multiples of `precision`.
+    ///
+    /// With a `source`, the geometry is only decoded from it when it is first used.
+    fn from_fb(
+        fb_segment: lrs_generated::Segment,
+        segment_idx: usize,
+        precision: f64,
+        source: Option<&SharedBytes>,
+    ) -> Self {
+        let geometry = match source {
+            Some(source) => {
+                let source = source.clone();
+                Deferred::new(move || {
+                    let lrs = shared_root(&source);
+                    let segment = lrs.segments().unwrap_or_default().get(segment_idx);
+                    segment_coords(&segment, precision).into()
+                })
+            }
+            None => LineString::from(segment_coords(&fb_segment, precision)).into(),
+        };
         Self {
             id: fb_segment.id().to_owned(),
             properties: from_fb(fb_segment.properties()),
             start_node: NodeHandle(fb_segment.start_node_index() as usize),
             end_node: NodeHandle(fb_segment.end_node_index() as usize),
+            geometry,
         }
     }
 }
@@ -133,6 +564,30 @@ pub struct LrmProjection {
     pub measure: LrmMeasure,
     /// How far from the [`Lrm`] is the [`Point`] that has been projected.
     pub orthogonal_offset: f64,
+    /// The [`Traversal`] of the [`Lrm`] where the [`Point`] was projected: the main one or an alternate one.
+    pub traversal: TraversalHandle,
+}
+
+/// The extent of an [`Lrm`], see [`Lrs::lrm_bounds`].
+#[derive(Clone, Debug)]
+pub struct LrmBounds {
+    /// The smallest measure of the [`Lrm`], at one end of its [`Traversal`].
+    pub min_measure: LrmScaleMeasure,
+    /// The largest measure of the [`Lrm`], at the other end of its [`Traversal`].
+    pub max_measure: LrmScaleMeasure,
+    /// The length of the [`Traversal`], in meters for a geographic [`Lrs`].
+    pub length: f64,
+}
+
+/// Where two [`Lrm`]s cross or touch, see [`Lrs::intersections`].
+#[derive(Clone, Debug)]
+pub struct LrmIntersection {
+    /// The measure of the intersection on the first [`Lrm`].
+    pub measure_a: LrmScaleMeasure,
+    /// The measure of the intersection on the second [`Lrm`].
+    pub measure_b: LrmScaleMeasure,
+    /// The geographical position of the intersection.
+    pub point: Point,
 }
 
 /// Identifies a [`ScalePosition`] on an [`LrmScale`] by the distance from the start of the scale.
@@ -176,6 +631,7 @@ pub struct TraversalPosition {
 /// Describes an interval (= range) on a [`Traversal`].
 /// The borders are [`CurvePosition`]s.
 /// It can be used to identify a speed limit zone for instance.
+#[derive(Clone, Copy, Debug)]
 pub struct TraversalRange {
     /// Identifies the [`Traversal`].
     pub traversal: TraversalHandle,
@@ -201,19 +657,141 @@ pub struct LrmRange {
     pub direction: Direction,
 }
 
-/// Helper to project an [`Anchor`] on a [`Curve`].
-fn project<CurveImpl: Curve>(
-    anchor: &lrs_generated::Anchor,
-    curve: &CurveImpl,
-) -> (f64, Option<Point>) {
-    let p = anchor.geometry().map(|p| point! {x: p.x(), y: p.y()});
+/// A part of a geometry, located on an [`Lrm`].
+pub struct MeasuredGeometry {
+    /// The geometry of the part.
+    pub geometry: LineString,
+    /// The measures covered by the part.
+    pub range: LrmRange,
+}
+
+/// What [`Lrs::merge`] does when both [`Lrs`] have a [`Traversal`] or an [`Lrm`] with the same identifier.
+///
+/// [`Traversal`]s with the same geometry are always shared, whatever their identifiers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConflictPolicy {
+    /// Returns [`LrsError::DuplicateId`] without modifying the [`Lrs`].
+    Fail,
+    /// The element already in the [`Lrs`] is kept.
+    /// The [`Lrm`]s of a dropped [`Traversal`] are dropped too.
+    KeepExisting,
+    /// The element of the merged [`Lrs`] replaces the existing one.
+    /// The events of a replaced [`Lrm`] are dropped.
+    /// The [`Anchor`]s of the existing [`Lrm`]s of a replaced [`Traversal`] are projected on its new geometry,
+    /// as with [`Lrs::replace_traversal_geometry`].
+    KeepIncoming,
+}
+
+/// Which [`Lrm`]s to load with [`Lrs::from_bytes_filtered`].
+#[derive(Clone, Debug)]
+pub enum LoadFilter {
+    /// The [`Lrm`]s with one of these identifiers.
+    LrmIds(Vec<String>),
+    /// The [`Lrm`]s whose [`Traversal`] is in this bounding box.
+    /// The stored spatial index is used when it exists: [`Lrm`]s slightly outside the box might be included.
+    BoundingBox(Rect),
+}
+
+/// A path in the network between two positions, computed by [`Lrs::route`].
+#[derive(Clone, Debug)]
+pub struct Route {
+    /// The pieces of [`Traversal`]s to follow, in order.
+    pub ranges: Vec<TraversalRange>,
+    /// The total length of the [`Route`].
+    pub length: f64,
+    /// The geometry of the whole [`Route`], from the start to the end.
+    pub geometry: LineString,
+}
+
+/// A step of the shortest path search: entering a [`Traversal`] through a connection,
+/// standing at the extremity of a [`Traversal`] ready to leave it, or arrived.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum RouteStep {
+    Entry(TraversalHandle, TraversalEnd),
+    Extremity(TraversalHandle, TraversalEnd),
+    Arrival,
+}
+
+/// Entry of the priority queue of the shortest path search, the smallest cost first.
+struct RouteCandidate {
+    cost: f64,
+    step: RouteStep,
+}
+
+impl PartialEq for RouteCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost.total_cmp(&other.cost) == Ordering::Equal
+    }
+}
+
+impl Eq for RouteCandidate {}
+
+impl PartialOrd for RouteCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RouteCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// State of the shortest path search (Dijkstra’s algorithm).
+#[derive(Default)]
+struct RouteSearch {
+    costs: HashMap<RouteStep, f64>,
+    previous: HashMap<RouteStep, Option<RouteStep>>,
+    queue: BinaryHeap<RouteCandidate>,
+}
+
+impl RouteSearch {
+    /// Records the `step` if it is the cheapest way found so far to reach it.
+    fn push(&mut self, step: RouteStep, cost: f64, from: Option<RouteStep>) {
+        if self.costs.get(&step).is_none_or(|&known| cost < known) {
+            self.costs.insert(step, cost);
+            self.previous.insert(step, from);
+            self.queue.push(RouteCandidate { cost, step });
+        }
+    }
+
+    /// The cheapest step not visited yet.
+    fn pop(&mut self) -> Option<RouteCandidate> {
+        while let Some(candidate) = self.queue.pop() {
+            if self.costs[&candidate.step] == candidate.cost {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+}
+
+fn end_position(end: TraversalEnd) -> CurvePosition {
+    match end {
+        TraversalEnd::Start => 0.,
+        TraversalEnd::End => 1.,
+    }
+}
 
-    let distance_along_curve = curve
-        .project(p.unwrap())
-        .expect("Could not project anchor on the curve")
-        .distance_along_curve;
+fn opposite(end: TraversalEnd) -> TraversalEnd {
+    match end {
+        TraversalEnd::Start => TraversalEnd::End,
+        TraversalEnd::End => TraversalEnd::Start,
+    }
+}
 
-    (distance_along_curve, p)
+/// The item of a FlatBuffers vector, or an error if the file references an item that does not exist.
+fn item<'a, T: flatbuffers::Follow<'a> + 'a>(
+    vector: flatbuffers::Vector<'a, T>,
+    index: usize,
+    what: &str,
+) -> Result<T::Inner, LrsError> {
+    if index < vector.len() {
+        Ok(vector.get(index))
+    } else {
+        Err(LrsError::IncompleteArchive(format!("{what} {index}")))
+    }
 }
 
 impl<CurveImpl: Curve> Lrs<CurveImpl> {
@@ -222,13 +800,177 @@ impl<CurveImpl: Curve> Lrs<CurveImpl> {
         self.lrms.len()
     }
 
+    /// All the [`LrmHandle`]s, in the order the [`Lrm`]s are stored in the file.
+    ///
+    /// The order is the one of the builder and is the same every time the file is loaded.
+    /// A handle remains valid as long as the [`Lrs`] is not modified.
+    pub fn lrm_handles(&self) -> impl Iterator<Item = LrmHandle> + use<CurveImpl> {
+        (0..self.lrms.len()).map(LrmHandle)
+    }
+
+    /// All the [`TraversalHandle`]s, in the order the [`Traversal`]s are stored in the file.
+    ///
+    /// As for [`Lrs::lrm_handles`], the order is stable between two loadings of the same file.
+    pub fn traversal_handles(&self) -> impl Iterator<Item = TraversalHandle> + use<CurveImpl> {
+        (0..self.traversals.len()).map(TraversalHandle)
+    }
+
+    /// Returns the [`Lrm`] identified by the `handle`, or [`LrsError::InvalidHandle`] if it does not exist.
+    pub fn lrm(&self, handle: LrmHandle) -> Result<&Lrm, LrsError> {
+        self.lrms.get(handle.0).ok_or(LrsError::InvalidHandle)
+    }
+
+    /// Returns the [`Traversal`] identified by the `handle`, or [`LrsError::InvalidHandle`] if it does not exist.
+    pub fn traversal(&self, handle: TraversalHandle) -> Result<&Traversal<CurveImpl>, LrsError> {
+        self.traversals.get(handle.0).ok_or(LrsError::InvalidHandle)
+    }
+
+    /// Returns the [`Node`] identified by the `handle`, or [`LrsError::InvalidHandle`] if it does not exist.
+    pub fn node(&self, handle: NodeHandle) -> Result<&Node, LrsError> {
+        self.nodes.get(handle.0).ok_or(LrsError::InvalidHandle)
+    }
+
     /// Loads an [`Lrs`] from an byte array.
+    ///
+    /// Fails with [`LrsError::GeometryTypeMismatch`] if the [`GeometryType`] of the file is not the one of the [`Curve`],
+    /// see [`AnyLrs`] to choose the [`Curve`] from the file.
     pub fn from_bytes(buf: &[u8]) -> Result<Self, LrsError> {
-        let lrs = lrs_generated::root_as_lrs(buf).map_err(LrsError::InvalidArchive)?;
+        Self::load(buf, None, None, true)
+    }
+
+    /// Loads an [`Lrs`] from an byte array, even if the [`GeometryType`] of the file is not the one of the [`Curve`].
+    pub fn from_bytes_any_geometry_type(buf: &[u8]) -> Result<Self, LrsError> {
+        Self::load(buf, None, None, false)
+    }
+
+    /// Loads only some [`Lrm`]s from a byte array, with their [`Traversal`]s.
+    ///
+    /// The other [`Lrm`]s, [`Traversal`]s, [`Segment`]s and [`Node`]s are not deserialized.
+    /// The handles are renumbered, keeping the order of the file.
+    /// The [`Node`]s are loaded when they are an extremity of a loaded [`Segment`] or bound to an [`Anchor`] of a loaded [`Lrm`].
+    /// The [`Connection`]s and events are kept when they only refer to loaded items: a [`Connection`] to a [`Traversal`]
+    /// that is not loaded is dropped, so that [`Lrs::route`] stays within the loaded [`Traversal`]s.
+    /// The spatial indexes stored in the file are ignored as they refer to all the [`Traversal`]s.
+    pub fn from_bytes_filtered(buf: &[u8], filter: &LoadFilter) -> Result<Self, LrsError> {
+        Self::load(buf, None, Some(filter), true)
+    }
+
+    /// Loads an [`Lrs`] from an byte array and applies `transform` to every coordinate.
+    ///
+    /// It allows to adjust the data while loading it, e.g. with a datum shift or a local correction grid.
+    /// The spatial indexes stored in the file are ignored as they might not match the transformed geometries.
+    pub fn from_bytes_with_transform(
+        buf: &[u8],
+        transform: impl Fn(Coord) -> Coord,
+    ) -> Result<Self, LrsError> {
+        Self::load(buf, Some(&transform), None, true)
+    }
+
+    /// Loads some [`Lrm`]s from the beginning of a file that is still being received, see [`Lrs::from_bytes_filtered`].
+    ///
+    /// Returns `Ok(None)` as long as the `prefix` does not hold everything needed by the `filter`:
+    /// the [`Lrm`]s, the [`Anchor`]s, the [`Node`]s, the connections and the events,
+    /// and the selected [`Traversal`]s with their [`Segment`]s.
+    /// The checksum of the file is not checked. A compressed file, or a file without the `LRSD` file identifier,
+    /// can not be read before it is complete.
+    ///
+    /// [`Lrs::to_bytes`] writes the geometries of the [`Segment`]s last and in order, so that the [`Lrm`]s can be loaded early.
+    pub fn from_partial_bytes(
+        prefix: &[u8],
+        filter: &LoadFilter,
+    ) -> Result<Option<Self>, LrsError> {
+        partial_root(prefix, filter)
+            .map(|lrs| Self::load_root(lrs, None, None, Some(filter), true))
+            .transpose()
+    }
+
+    /// Loads an [`Lrs`] from shared bytes, such as a memory mapped file, without decoding the geometries.
+    ///
+    /// The [`Curve`] of a [`Traversal`] and the geometry of a [`Segment`] are only read from the bytes when they are first used,
+    /// see [`Deferred`]. The bytes are kept until the [`Lrs`] is dropped, and must not be modified in the meantime.
+    /// A compressed file is decompressed in memory first.
+    pub fn from_shared_bytes(bytes: SharedBytes) -> Result<Self, LrsError> {
+        Self::load_shared(&shared_payload(bytes)?, true)
+    }
+
+    fn load_shared(payload: &SharedBytes, check_geometry_type: bool) -> Result<Self, LrsError> {
+        Self::load_payload(
+            (**payload).as_ref(),
+            Some(payload),
+            None,
+            None,
+            check_geometry_type,
+        )
+    }
+
+    fn load(
+        buf: &[u8],
+        transform: Option<&dyn Fn(Coord) -> Coord>,
+        filter: Option<&LoadFilter>,
+        check_geometry_type: bool,
+    ) -> Result<Self, LrsError> {
+        let payload = file_format::open(buf)?;
+        Self::load_payload(&payload, None, transform, filter, check_geometry_type)
+    }
+
+    /// With a `source` holding the `payload`, the geometries are read from it when they are first used.
+    fn load_payload(
+        payload: &[u8],
+        source: Option<&SharedBytes>,
+        transform: Option<&dyn Fn(Coord) -> Coord>,
+        filter: Option<&LoadFilter>,
+        check_geometry_type: bool,
+    ) -> Result<Self, LrsError> {
+        let lrs = lrs_generated::root_as_lrs(payload).map_err(LrsError::InvalidArchive)?;
+        Self::load_root(lrs, source, transform, filter, check_geometry_type)
+    }
+
+    /// Loads from the `lrs` root of a payload, whose parts read with the `filter` were verified.
+    fn load_root(
+        lrs: lrs_generated::Lrs,
+        source: Option<&SharedBytes>,
+        transform: Option<&dyn Fn(Coord) -> Coord>,
+        filter: Option<&LoadFilter>,
+        check_geometry_type: bool,
+    ) -> Result<Self, LrsError> {
+        file_format::check_schema_version(lrs.schema_version())?;
+        let geometry_type = GeometryType::from(lrs.geometry_type());
+        if check_geometry_type && geometry_type != GeometryType::of::<CurveImpl>() {
+            return Err(LrsError::GeometryTypeMismatch(
+                geometry_type,
+                GeometryType::of::<CurveImpl>(),
+            ));
+        }
+        let selection = Selection::new(&lrs, filter);
+        // The stored spatial indexes are built on the original geometries, with all the traversals
+        let keep_indexes = transform.is_none() && filter.is_none();
+        let identity = |coord| coord;
+        let transform = transform.unwrap_or(&identity);
 
         let rtree_data = lrs
             .lrm_spatial_index()
+            .filter(|_| keep_indexes)
             .map(|buffer| buffer.bytes().to_vec());
+        let traversal_count = lrs.traversals().unwrap_or_default().len();
+        let fragment_index = lrs
+            .fragment_spatial_index()
+            .zip(lrs.fragment_traversals())
+            .filter(|_| keep_indexes)
+            .map(|(rtree_data, traversals)| {
+                Ok::<_, LrsError>(FragmentIndex {
+                    rtree_data: rtree_data.bytes().to_vec(),
+                    traversals: traversals
+                        .iter()
+                        .map(|idx| match idx as usize {
+                            idx if idx < traversal_count => Ok(TraversalHandle(idx)),
+                            _ => Err(LrsError::IncompleteArchive(format!(
+                                "traversal {idx} of the fragment index"
+                            ))),
+                        })
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .transpose()?;
         let mut result = Self {
             lrms: vec![],
             traversals: vec![],
@@ -236,35 +978,100 @@ impl<CurveImpl: Curve> Lrs<CurveImpl> {
             nodes: vec![],
             segments: vec![],
             rtree_data,
+            build_info: lrs.build_info().map(BuildInfo::from),
+            fragment_index,
+            connections: vec![],
+            point_events: lrs
+                .point_events()
+                .unwrap_or_default()
+                .iter()
+                .map(PointEvent::from)
+                .collect(),
+            linear_events: lrs
+                .linear_events()
+                .unwrap_or_default()
+                .iter()
+                .map(LinearEvent::from)
+                .collect(),
+            crs: lrs.crs().map(str::to_owned),
+            coordinate_precision: lrs.coordinate_precision(),
         };
 
+        for connection in lrs.connections().unwrap_or_default().iter() {
+            let connection = Connection::from(connection);
+            for traversal in [connection.from.0, connection.to.0] {
+                if traversal.0 >= traversal_count {
+                    return Err(LrsError::IncompleteArchive(format!(
+                        "traversal {} of a connection",
+                        traversal.0
+                    )));
+                }
+            }
+            // The connections to a traversal that is not loaded are dropped
+            let (Some(from), Some(to)) = (
+                selection.traversal(connection.from.0),
+                selection.traversal(connection.to.0),
+            ) else {
+                continue;
+            };
+            let node = selection.node(connection.node).ok_or_else(|| {
+                LrsError::IncompleteArchive(format!("node {} of a connection", connection.node.0))
+            })?;
+            result.connections.push(Connection {
+                node,
+                from: (from, connection.from.1),
+                to: (to, connection.to.1),
+                ..connection
+            });
+        }
+
         let source_anchors = lrs
             .anchors()
             .ok_or(LrsError::IncompleteArchive("anchors".to_owned()))?;
+        let source_segments = lrs.segments().unwrap_or_default();
+        let node_count = lrs.nodes().unwrap_or_default().len();
         // Read the traversals and build the curves
-        for traversal in lrs.traversals().unwrap_or_default() {
-            let mut coords = vec![];
-            for segment in traversal.segments() {
-                let mut geom: Vec<_> = lrs
-                    .segments()
-                    .expect("Bad index")
-                    .get(segment.segment_index() as usize)
-                    .geometry()
-                    .iter()
-                    .map(|p| coord! {x: p.x(),y: p.y()})
-                    .collect();
-                if segment.direction() == lrs_generated::Direction::Decreasing {
-                    geom.reverse();
-                }
-                coords.append(&mut geom);
+        for (traversal_idx, traversal) in lrs.traversals().unwrap_or_default().iter().enumerate() {
+            if selection
+                .traversal(TraversalHandle(traversal_idx))
+                .is_none()
+            {
+                continue;
             }
-
-            let line_string = geo::LineString::new(coords);
+            let curve = match source {
+                Some(source) => {
+                    // Only the references are checked, so that reading the curve later can not fail
+                    for segment in traversal.segments() {
+                        item(source_segments, segment.segment_index() as usize, "segment")?;
+                    }
+                    let source = source.clone();
+                    Deferred::new(move || {
+                        let lrs = shared_root(&source);
+                        let traversal = lrs.traversals().unwrap_or_default().get(traversal_idx);
+                        let coords = traversal_coords(&lrs, &traversal, &|coord| coord)
+                            .expect("the segments are checked when loading");
+                        CurveImpl::new(LineString::new(coords), 1000.)
+                    })
+                }
+                None => {
+                    let coords = traversal_coords(&lrs, &traversal, transform)?;
+                    CurveImpl::new(LineString::new(coords), 1000.).into()
+                }
+            };
 
             result.traversals.push(Traversal {
                 id: traversal.id().to_owned(),
-                curve: CurveImpl::new(line_string, 1000.),
+                curve,
                 lrms: vec![],
+                segments: traversal
+                    .segments()
+                    .iter()
+                    .map(|segment| SegmentOfTraversal {
+                        segment_index: selection.segment(segment.segment_index() as usize),
+                        reversed: segment.direction() == lrs_generated::Direction::Decreasing,
+                    })
+                    .collect(),
+                properties: from_fb(traversal.properties()),
             });
         }
 
@@ -275,33 +1082,89 @@ impl<CurveImpl: Curve> Lrs<CurveImpl> {
             .iter()
             .enumerate()
         {
-            let traversal_idx = raw_lrm.traversal_index() as usize;
-            let curve = &result
-                .traversals
-                .get(traversal_idx)
+            if selection.lrm(LrmHandle(lrm_idx)).is_none() {
+                continue;
+            }
+            let raw_traversal_idx = raw_lrm.traversal_index() as usize;
+            let traversal_idx = selection
+                .traversal(TraversalHandle(raw_traversal_idx))
                 .ok_or(LrsError::IncompleteArchive(format!(
-                    "traversal {traversal_idx} from lrm {lrm_idx}"
+                    "traversal {raw_traversal_idx} from lrm {lrm_idx}"
                 )))?
-                .curve;
+                .0;
+            let alternate_traversals = raw_lrm
+                .alternate_traversal_indices()
+                .unwrap_or_default()
+                .iter()
+                .map(|raw| {
+                    selection.traversal(TraversalHandle(raw as usize)).ok_or(
+                        LrsError::IncompleteArchive(format!(
+                            "alternate traversal {raw} from lrm {lrm_idx}"
+                        )),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            // Only read when an anchor has to be projected, so that a deferred curve stays unread
+            let curve = &result.traversals[traversal_idx].curve;
+
+            // Without geometry, the anchors are placed proportionally to their distance
+            // so that the measures can still be manipulated
+            let distances = raw_lrm.distances();
+            let first_distance = distances.iter().next().unwrap_or_default();
+            let scale_length = distances.iter().next_back().unwrap_or_default() - first_distance;
+            let proportional_position = |distance: f64| {
+                if scale_length == 0. {
+                    0.
+                } else {
+                    (distance - first_distance) / scale_length
+                }
+            };
 
-            let anchors: Vec<_> = raw_lrm
+            let anchors = raw_lrm
                 .anchor_indices()
                 .iter()
                 .enumerate()
                 .map(|(idx, anchor_idx)| {
-                    let anchor = source_anchors.get(anchor_idx as usize);
-                    let scale_position = raw_lrm.distances().get(idx);
-
-                    let (curve_position, coord) = raw_lrm
-                        .projected_anchors()
-                        .map(|anchors| {
-                            let projected_anchor = anchors.get(idx);
-                            let geometry = projected_anchor.geometry().map(Point::from);
-                            (projected_anchor.distance_along_curve(), geometry)
-                        })
-                        .unwrap_or_else(|| project(&anchor, curve));
+                    let anchor = item(source_anchors, anchor_idx as usize, "anchor")?;
+                    let scale_position = item(distances, idx, "distance")?;
+
+                    let surveyed = anchor
+                        .geometry()
+                        .map(|p| Point::from(transform(Point::from(p).0)));
+                    let (curve_position, coord, offset) =
+                        match (raw_lrm.projected_anchors(), surveyed) {
+                            (Some(anchors), _) => {
+                                let projected_anchor = item(anchors, idx, "projected anchor")?;
+                                let geometry = projected_anchor
+                                    .geometry()
+                                    .map(|p| Point::from(transform(Point::from(p).0)));
+                                (
+                                    projected_anchor.distance_along_curve(),
+                                    geometry,
+                                    projected_anchor.offset(),
+                                )
+                            }
+                            (None, Some(point)) if curve.is_valid() => {
+                                let projection = curve.project(point)?;
+                                (
+                                    projection.distance_along_curve,
+                                    Some(point),
+                                    Some(projection.offset),
+                                )
+                            }
+                            (None, geometry) => {
+                                (proportional_position(scale_position), geometry, None)
+                            }
+                        };
 
-                    match anchor.name() {
+                    let validity = Validity::from_fb(anchor.valid_from(), anchor.valid_to());
+                    let (id, node) = (anchor.id(), anchor.node().map(|node| node as usize));
+                    if let Some(node) = node.filter(|&node| node >= node_count) {
+                        return Err(LrsError::IncompleteArchive(format!(
+                            "node {node} of anchor {id}"
+                        )));
+                    }
+                    let anchor = match anchor.name() {
                         Some(name) => Anchor::new_named(
                             name,
                             scale_position,
@@ -315,9 +1178,18 @@ impl<CurveImpl: Curve> Lrs<CurveImpl> {
                             coord,
                             from_fb(anchor.properties()),
                         ),
-                    }
+                    };
+                    Ok::<_, LrsError>(
+                        anchor
+                            .with_survey(surveyed, offset)
+                            .with_validity(validity)
+                            .with_source(
+                                id,
+                                node.and_then(|node| selection.node(NodeHandle(node))),
+                            ),
+                    )
                 })
-                .collect();
+                .collect::<Result<Vec<_>, _>>()?;
 
             let lrm = Lrm {
                 scale: LrmScale {
@@ -326,25 +1198,1446 @@ impl<CurveImpl: Curve> Lrs<CurveImpl> {
                 },
                 traversal: TraversalHandle(traversal_idx),
                 properties: from_fb(raw_lrm.properties()),
+                measure_direction: match raw_lrm.measure_direction() {
+                    lrs_generated::Direction::Decreasing => Direction::Reversed,
+                    _ => Direction::Default,
+                },
+                alternate_traversals,
+                validity: Validity::from_fb(raw_lrm.valid_from(), raw_lrm.valid_to()),
             };
 
-            result.traversals[traversal_idx]
-                .lrms
-                .push(LrmHandle(lrm_idx));
+            for traversal in lrm.all_traversals() {
+                result.traversals[traversal.0]
+                    .lrms
+                    .push(LrmHandle(result.lrms.len()));
+            }
 
             result.lrms.push(lrm);
         }
 
-        for raw_node in lrs.nodes().unwrap_or_default().iter() {
-            result.nodes.push(Node::from(raw_node));
+        for (node_idx, raw_node) in lrs.nodes().unwrap_or_default().iter().enumerate() {
+            if selection.nodes[node_idx].is_none() {
+                continue;
+            }
+            let mut node = Node::from(raw_node);
+            node.geometry = node.geometry.map(|p| Point::from(transform(p.0)));
+            result.nodes.push(node);
         }
 
-        for raw_segment in lrs.segments().unwrap_or_default().iter() {
-            result.segments.push(Segment::from(raw_segment))
-        }
+        for (segment_idx, raw_segment) in lrs.segments().unwrap_or_default().iter().enumerate() {
+            if !selection.segments[segment_idx] {
+                continue;
+            }
+            let mut segment =
+                Segment::from_fb(raw_segment, segment_idx, lrs.coordinate_precision(), source);
+            for node in [&mut segment.start_node, &mut segment.end_node] {
+                *node = selection.node(*node).ok_or_else(|| {
+                    LrsError::IncompleteArchive(format!(
+                        "node {} of segment {}",
+                        node.0, segment.id
+                    ))
+                })?;
+            }
+            if source.is_none() {
+                segment
+                    .geometry
+                    .0
+                    .iter_mut()
+                    .for_each(|coord| *coord = transform(*coord));
+            }
+            result.segments.push(segment)
+        }
+
+        let event_lrms = result.point_events.iter().map(|event| event.lrm);
+        let event_lrms = event_lrms.chain(result.linear_events.iter().map(|event| event.lrm));
+        for lrm in event_lrms {
+            if lrm.0 >= selection.lrms.len() {
+                return Err(LrsError::IncompleteArchive(format!(
+                    "lrm {} of an event",
+                    lrm.0
+                )));
+            }
+        }
+        result.point_events.retain_mut(|event| {
+            selection
+                .lrm(event.lrm)
+                .map(|lrm| event.lrm = lrm)
+                .is_some()
+        });
+        result.linear_events.retain_mut(|event| {
+            selection
+                .lrm(event.lrm)
+                .map(|lrm| event.lrm = lrm)
+                .is_some()
+        });
         Ok(result)
     }
 
+    /// Serializes the [`Lrs`], for instance to save it after editing it.
+    ///
+    /// The spatial indexes are rebuilt from the current geometries.
+    /// Every [`Lrm`] gets its own copy of its [`Anchor`]s, stored with their position on the [`Curve`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        file_format::seal(self.build_flatbuffer(false).finished_data())
+    }
+
+    /// Serializes the [`Lrs`] and compresses it, see [`Lrs::to_bytes`].
+    ///
+    /// The file is smaller, e.g. to be downloaded by a web application, and is decompressed transparently when loaded.
+    /// It can not be read by FlatBuffers directly anymore.
+    #[cfg(feature = "compression")]
+    pub fn to_compressed_bytes(&self) -> Vec<u8> {
+        file_format::seal_compressed(self.build_flatbuffer(false).finished_data())
+    }
+
+    /// Exports the [`Lrm`]s and their [`Anchor`]s as GeoJSON features, e.g. to inspect them in a GIS.
+    ///
+    /// Each [`Lrm`] is a LineString, oriented by increasing measures, with its `id`, the `traversal` id and its properties.
+    /// Each [`Anchor`] is a Point with the `lrm_id`, its `name` if it has one, its `measure` and its properties.
+    /// The property names match the defaults of [`crate::builder::GeoJsonMapping`].
+    #[cfg(feature = "geojson")]
+    pub fn to_geojson(&self) -> geojson::FeatureCollection {
+        let feature = |value: Option<geojson::Value>, properties: &Properties| {
+            let mut object = geojson::JsonObject::new();
+            for (key, value) in properties {
+                object.insert(key.clone(), value.clone().into());
+            }
+            geojson::Feature {
+                bbox: None,
+                geometry: value.map(geojson::Geometry::new),
+                id: None,
+                properties: Some(object),
+                foreign_members: None,
+            }
+        };
+        let position = |p: Point| vec![p.x(), p.y()];
+
+        let mut features = vec![];
+        for (index, lrm) in self.lrms.iter().enumerate() {
+            let linestring = self.oriented_linestring(LrmHandle(index)).ok();
+            let geometry =
+                linestring.map(|l| geojson::Value::LineString(l.points().map(position).collect()));
+            let mut lrm_feature = feature(geometry, &lrm.properties);
+            let properties = lrm_feature.properties.get_or_insert_default();
+            properties.insert("id".to_owned(), lrm.scale.id.clone().into());
+            if let Ok(traversal) = self.traversal(lrm.traversal) {
+                properties.insert("traversal".to_owned(), traversal.id.clone().into());
+            }
+            features.push(lrm_feature);
+
+            for anchor in &lrm.scale.anchors {
+                let point = self.anchor_point(lrm, anchor);
+                let geometry = point.map(|p| geojson::Value::Point(position(p)));
+                let mut anchor_feature = feature(geometry, anchor.properties());
+                let properties = anchor_feature.properties.get_or_insert_default();
+                properties.insert("lrm_id".to_owned(), lrm.scale.id.clone().into());
+                if let Anchor::Named(named) = anchor {
+                    properties.insert("name".to_owned(), named.name.clone().into());
+                }
+                properties.insert("measure".to_owned(), anchor.scale_position().into());
+                features.push(anchor_feature);
+            }
+        }
+
+        geojson::FeatureCollection {
+            bbox: None,
+            features,
+            foreign_members: None,
+        }
+    }
+
+    /// Serializes the [`Lrs`] in a [`FlatBufferBuilder`].
+    ///
+    /// With `reuse_indexes`, the spatial indexes already stored in the [`Lrs`] are written as they are, if there are any.
+    /// It must only be used when no geometry changed since they were built.
+    pub(crate) fn build_flatbuffer(&self, reuse_indexes: bool) -> FlatBufferBuilder<'static> {
+        let mut fbb = FlatBufferBuilder::with_capacity(1024);
+
+        // FlatBuffers are built from the end: the segments are created first and in reverse order,
+        // so that their geometries come last and in order, after everything needed to load a few LRMs,
+        // see `Lrs::from_partial_bytes`
+        let mut segments: Vec<_> = self
+            .segments
+            .iter()
+            .rev()
+            .map(|segment| {
+                let quantized = Some(self.coordinate_precision)
+                    .filter(|precision| precision.is_finite() && *precision > 0.)
+                    .and_then(|precision| quantize(&segment.geometry.0, precision));
+                // A quantized segment keeps an empty geometry, as it is required
+                let points: Vec<_> = match quantized {
+                    Some(_) => vec![],
+                    None => segment
+                        .geometry
+                        .0
+                        .iter()
+                        .map(|c| lrs_generated::Point::new(c.x, c.y))
+                        .collect(),
+                };
+                let args = lrs_generated::SegmentArgs {
+                    id: Some(fbb.create_string(&segment.id)),
+                    properties: Some(to_fb(&mut fbb, &segment.properties)),
+                    geometry: Some(fbb.create_vector(&points)),
+                    start_node_index: segment.start_node.0 as u64,
+                    end_node_index: segment.end_node.0 as u64,
+                    quantized_geometry: quantized.map(|quantized| fbb.create_vector(&quantized)),
+                };
+                lrs_generated::Segment::create(&mut fbb, &args)
+            })
+            .collect();
+        segments.reverse();
+
+        let traversals: Vec<_> = self
+            .traversals
+            .iter()
+            .map(|traversal| {
+                let segments = traversal
+                    .segments
+                    .iter()
+                    .map(|s| lrs_generated::SegmentOfTraversal::from(*s));
+                let args = lrs_generated::TraversalArgs {
+                    id: Some(fbb.create_string(&traversal.id)),
+                    properties: Some(to_fb(&mut fbb, &traversal.properties)),
+                    segments: Some(fbb.create_vector_from_iter(segments)),
+                };
+                lrs_generated::Traversal::create(&mut fbb, &args)
+            })
+            .collect();
+
+        let nodes: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let geometry = node
+                    .geometry
+                    .map(|p| lrs_generated::Point::new(p.x(), p.y()));
+                let args = lrs_generated::NodeArgs {
+                    id: Some(fbb.create_string(&node.id)),
+                    properties: Some(to_fb(&mut fbb, &node.properties)),
+                    geometry: geometry.as_ref(),
+                };
+                lrs_generated::Node::create(&mut fbb, &args)
+            })
+            .collect();
+
+        let mut anchors = vec![];
+        let mut lrms = vec![];
+        for lrm in &self.lrms {
+            let mut anchor_indices = vec![];
+            let mut projected_anchors = vec![];
+            for anchor in &lrm.scale.anchors {
+                let geometry = anchor
+                    .point()
+                    .map(|p| lrs_generated::Point::new(p.x(), p.y()));
+                let surveyed = anchor
+                    .surveyed_point()
+                    .map(|p| lrs_generated::Point::new(p.x(), p.y()))
+                    .or(geometry);
+                let name = match anchor {
+                    Anchor::Named(anchor) => Some(anchor.name.as_str()),
+                    Anchor::Unnamed(_) => None,
+                };
+                let (valid_from, valid_to) = anchor.validity().to_fb(&mut fbb);
+                let args = lrs_generated::AnchorArgs {
+                    id: Some(fbb.create_string(anchor.id())),
+                    properties: Some(to_fb(&mut fbb, anchor.properties())),
+                    name: name.map(|name| fbb.create_string(name)),
+                    node: anchor.node().map(|node| node.0 as u32),
+                    geometry: surveyed.as_ref(),
+                    valid_from,
+                    valid_to,
+                };
+                anchor_indices.push(anchors.len() as u64);
+                anchors.push(lrs_generated::Anchor::create(&mut fbb, &args));
+
+                let args = lrs_generated::ProjectedAnchorArgs {
+                    geometry: geometry.as_ref(),
+                    distance_along_curve: anchor.curve_position(),
+                    offset: anchor.projection_offset(),
+                };
+                projected_anchors.push(lrs_generated::ProjectedAnchor::create(&mut fbb, &args));
+            }
+            let distances = lrm.scale.anchors.iter().map(Anchor::scale_position);
+            let (valid_from, valid_to) = lrm.validity.to_fb(&mut fbb);
+            let args =
+                lrs_generated::LinearReferencingMethodArgs {
+                    id: Some(fbb.create_string(&lrm.scale.id)),
+                    properties: Some(to_fb(&mut fbb, &lrm.properties)),
+                    traversal_index: lrm.traversal.0 as u32,
+                    anchor_indices: Some(fbb.create_vector(&anchor_indices)),
+                    distances: Some(fbb.create_vector_from_iter(distances)),
+                    projected_anchors: Some(fbb.create_vector(&projected_anchors)),
+                    measure_direction: match lrm.measure_direction {
+                        Direction::Default => lrs_generated::Direction::Increasing,
+                        Direction::Reversed => lrs_generated::Direction::Decreasing,
+                    },
+                    alternate_traversal_indices: Some(fbb.create_vector_from_iter(
+                        lrm.alternate_traversals.iter().map(|t| t.0 as u32),
+                    )),
+                    valid_from,
+                    valid_to,
+                    ..Default::default()
+                };
+            lrms.push(lrs_generated::LinearReferencingMethod::create(
+                &mut fbb, &args,
+            ));
+        }
+
+        let connections: Vec<_> = self
+            .connections
+            .iter()
+            .map(|connection| {
+                let args = lrs_generated::ConnectionArgs {
+                    node_index: connection.node.0 as u64,
+                    from_traversal_index: connection.from.0.0 as u64,
+                    from_end: connection.from.1.into(),
+                    to_traversal_index: connection.to.0.0 as u64,
+                    to_end: connection.to.1.into(),
+                    bidirectional: connection.bidirectional,
+                };
+                lrs_generated::Connection::create(&mut fbb, &args)
+            })
+            .collect();
+
+        let point_events: Vec<_> = self
+            .point_events
+            .iter()
+            .map(|event| {
+                let (valid_from, valid_to) = event.validity.to_fb(&mut fbb);
+                let args = lrs_generated::PointEventArgs {
+                    id: Some(fbb.create_string(&event.id)),
+                    lrm_index: event.lrm.0 as u32,
+                    measure: Some(measure_to_fb(&mut fbb, &event.measure)),
+                    properties: Some(typed_properties_to_fb(&mut fbb, &event.properties)),
+                    valid_from,
+                    valid_to,
+                };
+                lrs_generated::PointEvent::create(&mut fbb, &args)
+            })
+            .collect();
+
+        let linear_events: Vec<_> = self
+            .linear_events
+            .iter()
+            .map(|event| {
+                let (valid_from, valid_to) = event.validity.to_fb(&mut fbb);
+                let args = lrs_generated::LinearEventArgs {
+                    id: Some(fbb.create_string(&event.id)),
+                    lrm_index: event.lrm.0 as u32,
+                    begin: Some(measure_to_fb(&mut fbb, &event.begin)),
+                    end: Some(measure_to_fb(&mut fbb, &event.end)),
+                    properties: Some(typed_properties_to_fb(&mut fbb, &event.properties)),
+                    valid_from,
+                    valid_to,
+                };
+                lrs_generated::LinearEvent::create(&mut fbb, &args)
+            })
+            .collect();
+
+        let build_info = self.build_info.as_ref().map(|build_info| {
+            let sources: Vec<_> = build_info
+                .sources
+                .iter()
+                .map(|source| fbb.create_string(source))
+                .collect();
+            let args = lrs_generated::BuildInfoArgs {
+                liblrs_version: Some(fbb.create_string(&build_info.liblrs_version)),
+                options: Some(to_fb(&mut fbb, &build_info.options)),
+                sources: Some(fbb.create_vector(&sources)),
+            };
+            lrs_generated::BuildInfo::create(&mut fbb, &args)
+        });
+
+        let stored_indexes = self
+            .rtree_data
+            .as_ref()
+            .zip(self.fragment_index.as_ref())
+            .filter(|_| reuse_indexes)
+            .map(|(lrm_rtree, fragment_index)| SpatialIndexes {
+                lrm_rtree: lrm_rtree.clone(),
+                fragment_rtree: fragment_index.rtree_data.clone(),
+                fragment_traversals: fragment_index
+                    .traversals
+                    .iter()
+                    .map(|handle| handle.0 as u32)
+                    .collect(),
+            });
+        let indexes = stored_indexes
+            .unwrap_or_else(|| spatial_indexes(self.traversals.iter().map(|t| &*t.curve)));
+        let args = lrs_generated::LrsArgs {
+            properties: Some(to_fb(&mut fbb, &self.properties)),
+            segments: Some(fbb.create_vector(&segments)),
+            nodes: Some(fbb.create_vector(&nodes)),
+            traversals: Some(fbb.create_vector(&traversals)),
+            anchors: Some(fbb.create_vector(&anchors)),
+            linear_referencing_methods: Some(fbb.create_vector(&lrms)),
+            geometry_type: if CurveImpl::IS_SPHERICAL {
+                lrs_generated::GeometryType::Geographic
+            } else {
+                lrs_generated::GeometryType::Schematic
+            },
+            crs: self.crs.as_deref().map(|crs| fbb.create_string(crs)),
+            schema_version: file_format::SCHEMA_VERSION,
+            coordinate_precision: self.coordinate_precision.max(0.),
+            lrm_spatial_index: Some(fbb.create_vector(&indexes.lrm_rtree)),
+            build_info,
+            fragment_spatial_index: Some(fbb.create_vector(&indexes.fragment_rtree)),
+            fragment_traversals: Some(fbb.create_vector(&indexes.fragment_traversals)),
+            connections: Some(fbb.create_vector(&connections)),
+            point_events: Some(fbb.create_vector(&point_events)),
+            linear_events: Some(fbb.create_vector(&linear_events)),
+        };
+        let lrs = lrs_generated::Lrs::create(&mut fbb, &args);
+        lrs_generated::finish_lrs_buffer(&mut fbb, lrs);
+        fbb
+    }
+
+    /// Rewrites a file written by a previous version of the library with the current version of the schema.
+    ///
+    /// The data is loaded with the reader of its version, then serialized again.
+    /// The file remains compressed if it was.
+    pub fn upgrade_bytes(buf: &[u8]) -> Result<Vec<u8>, LrsError> {
+        let lrs = Self::from_bytes(buf)?;
+        #[cfg(feature = "compression")]
+        if file_format::is_compressed(buf) {
+            return Ok(lrs.to_compressed_bytes());
+        }
+        Ok(lrs.to_bytes())
+    }
+
+    /// Adds all the elements of `other`, for instance to combine regional files into a national one.
+    ///
+    /// [`Node`]s with the same identifier are considered to be the same.
+    /// [`Traversal`]s with the same geometry are shared, and the [`Lrm`]s of both [`Lrs`] use it.
+    /// Identifier collisions of [`Traversal`]s and [`Lrm`]s are resolved with the `policy`.
+    /// The spatial indexes are rebuilt.
+    ///
+    /// Fails with [`LrsError::InvalidHandle`], before changing anything, if `other` refers to a [`Node`], a [`Segment`],
+    /// a [`Traversal`] or an [`Lrm`] it does not have.
+    pub fn merge(&mut self, other: Lrs<CurveImpl>, policy: ConflictPolicy) -> Result<(), LrsError> {
+        let geometry_key = |curve: &Deferred<CurveImpl>| -> Vec<(u64, u64)> {
+            curve
+                .as_linestring()
+                .0
+                .iter()
+                .map(|c| (c.x.to_bits(), c.y.to_bits()))
+                .collect()
+        };
+        let by_geometry: HashMap<_, _> = self
+            .traversal_handles()
+            .filter(|handle| self.traversals[handle.0].curve.is_valid())
+            .map(|handle| (geometry_key(&self.traversals[handle.0].curve), handle))
+            .collect();
+        let same_geometry: Vec<_> = other
+            .traversals
+            .iter()
+            .map(|traversal| {
+                traversal
+                    .curve
+                    .is_valid()
+                    .then(|| by_geometry.get(&geometry_key(&traversal.curve)).copied())
+                    .flatten()
+            })
+            .collect();
+
+        if policy == ConflictPolicy::Fail {
+            for (traversal, shared) in other.traversals.iter().zip(&same_geometry) {
+                if shared.is_none() && self.get_traversal(&traversal.id).is_some() {
+                    return Err(LrsError::DuplicateId(traversal.id.clone()));
+                }
+            }
+            if let Some(lrm) = other
+                .lrms
+                .iter()
+                .find(|lrm| self.get_lrm(&lrm.scale.id).is_some())
+            {
+                return Err(LrsError::DuplicateId(lrm.scale.id.clone()));
+            }
+        }
+
+        let segment_nodes = other
+            .segments
+            .iter()
+            .flat_map(|segment| [segment.start_node, segment.end_node]);
+        let anchor_nodes = other
+            .lrms
+            .iter()
+            .flat_map(|lrm| lrm.scale.anchors.iter().filter_map(Anchor::node));
+        let connection_nodes = other.connections.iter().map(|connection| connection.node);
+        let mut other_nodes = segment_nodes.chain(anchor_nodes).chain(connection_nodes);
+        let mut traversal_segments = other
+            .traversals
+            .iter()
+            .flat_map(|traversal| &traversal.segments);
+        let lrm_traversals = other.lrms.iter().flat_map(|lrm| {
+            std::iter::once(lrm.traversal).chain(lrm.alternate_traversals.iter().copied())
+        });
+        let connection_traversals = other
+            .connections
+            .iter()
+            .flat_map(|connection| [connection.from.0, connection.to.0]);
+        let mut other_traversals = lrm_traversals.chain(connection_traversals);
+        let mut event_lrms = other
+            .point_events
+            .iter()
+            .map(|event| event.lrm)
+            .chain(other.linear_events.iter().map(|event| event.lrm));
+        if other_nodes.any(|node| node.0 >= other.nodes.len())
+            || traversal_segments.any(|segment| segment.segment_index >= other.segments.len())
+            || other_traversals.any(|traversal| traversal.0 >= other.traversals.len())
+            || event_lrms.any(|lrm| lrm.0 >= other.lrms.len())
+        {
+            return Err(LrsError::InvalidHandle);
+        }
+
+        // The first node wins when the identifier is duplicated
+        let mut node_ids: HashMap<_, _> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(idx, node)| (node.id.clone(), NodeHandle(idx)))
+            .collect();
+        let node_map: Vec<_> = other
+            .nodes
+            .into_iter()
+            .map(|node| {
+                *node_ids.entry(node.id.clone()).or_insert_with(|| {
+                    self.nodes.push(node);
+                    NodeHandle(self.nodes.len() - 1)
+                })
+            })
+            .collect();
+        let map_node =
+            |node: NodeHandle| node_map.get(node.0).copied().ok_or(LrsError::InvalidHandle);
+
+        let mut other_segments: Vec<_> = other.segments.into_iter().map(Some).collect();
+        let mut segment_map = HashMap::new();
+        let mut replaced_segments = HashSet::new();
+        let mut traversal_map = vec![];
+        for (traversal, shared) in other.traversals.into_iter().zip(same_geometry) {
+            if let Some(handle) = shared {
+                traversal_map.push(Some(handle));
+                continue;
+            }
+            let existing = self.get_traversal(&traversal.id);
+            if existing.is_some() && policy == ConflictPolicy::KeepExisting {
+                traversal_map.push(None);
+                continue;
+            }
+            let mut segments = vec![];
+            for segment in &traversal.segments {
+                let segment_index = match segment_map.get(&segment.segment_index) {
+                    Some(&segment_index) => segment_index,
+                    None => {
+                        let mut raw_segment = other_segments
+                            .get_mut(segment.segment_index)
+                            .and_then(Option::take)
+                            .ok_or(LrsError::InvalidHandle)?;
+                        raw_segment.start_node = map_node(raw_segment.start_node)?;
+                        raw_segment.end_node = map_node(raw_segment.end_node)?;
+                        self.segments.push(raw_segment);
+                        segment_map.insert(segment.segment_index, self.segments.len() - 1);
+                        self.segments.len() - 1
+                    }
+                };
+                segments.push(SegmentOfTraversal {
+                    segment_index,
+                    reversed: segment.reversed,
+                });
+            }
+            match existing {
+                Some(handle) => {
+                    let replaced = &mut self.traversals[handle.0];
+                    replaced_segments.extend(replaced.segments.iter().map(|s| s.segment_index));
+                    replaced.curve = traversal.curve;
+                    replaced.segments = segments;
+                    replaced.properties = traversal.properties;
+                    // The anchors of the lrms already on the traversal were placed on the old geometry.
+                    // The lrms using it as an alternate traversal have nothing to project:
+                    // their positions are transferred from their main traversal when they are used.
+                    for lrm in replaced.lrms.clone() {
+                        if self.lrms[lrm.0].traversal == handle {
+                            reproject_anchors(
+                                &mut self.lrms[lrm.0],
+                                &*self.traversals[handle.0].curve,
+                            );
+                        }
+                    }
+                    traversal_map.push(Some(handle));
+                }
+                None => {
+                    self.traversals.push(Traversal {
+                        id: traversal.id,
+                        curve: traversal.curve,
+                        lrms: vec![],
+                        segments,
+                        properties: traversal.properties,
+                    });
+                    traversal_map.push(Some(TraversalHandle(self.traversals.len() - 1)));
+                }
+            }
+        }
+        self.remove_unused_segments(&replaced_segments);
+
+        let mut lrm_map = vec![];
+        for lrm in other.lrms {
+            let Some(traversal) = traversal_map[lrm.traversal.0] else {
+                lrm_map.push(None);
+                continue;
+            };
+            let alternate_traversals = lrm
+                .alternate_traversals
+                .iter()
+                .filter_map(|alternate| traversal_map[alternate.0])
+                .filter(|&alternate| alternate != traversal)
+                .collect();
+            let mut lrm = Lrm {
+                traversal,
+                alternate_traversals,
+                ..lrm
+            };
+            for anchor in &mut lrm.scale.anchors {
+                let node = match anchor {
+                    Anchor::Named(anchor) => &mut anchor.node,
+                    Anchor::Unnamed(anchor) => &mut anchor.node,
+                };
+                if let Some(node) = node {
+                    *node = map_node(*node)?;
+                }
+            }
+            match self.get_lrm(&lrm.scale.id) {
+                Some(_) if policy == ConflictPolicy::KeepExisting => lrm_map.push(None),
+                Some(handle) => {
+                    self.detach_lrm(handle);
+                    self.lrms[handle.0] = lrm;
+                    self.attach_lrm(handle);
+                    // The events of the replaced lrm are measured on its scale
+                    self.point_events.retain(|event| event.lrm != handle);
+                    self.linear_events.retain(|event| event.lrm != handle);
+                    lrm_map.push(Some(handle));
+                }
+                None => {
+                    let handle = LrmHandle(self.lrms.len());
+                    self.lrms.push(lrm);
+                    self.attach_lrm(handle);
+                    lrm_map.push(Some(handle));
+                }
+            }
+        }
+
+        for connection in other.connections {
+            let from = traversal_map[connection.from.0.0];
+            let to = traversal_map[connection.to.0.0];
+            if let (Some(from), Some(to)) = (from, to) {
+                self.connections.push(Connection {
+                    node: map_node(connection.node)?,
+                    from: (from, connection.from.1),
+                    to: (to, connection.to.1),
+                    bidirectional: connection.bidirectional,
+                });
+            }
+        }
+        for event in other.point_events {
+            if let Some(lrm) = lrm_map[event.lrm.0] {
+                self.point_events.push(PointEvent { lrm, ..event });
+            }
+        }
+        for event in other.linear_events {
+            if let Some(lrm) = lrm_map[event.lrm.0] {
+                self.linear_events.push(LinearEvent { lrm, ..event });
+            }
+        }
+        for (key, value) in other.properties {
+            self.properties.entry(key).or_insert(value);
+        }
+        if self.crs.is_none() {
+            self.crs = other.crs;
+        }
+
+        let indexes = spatial_indexes(self.traversals.iter().map(|t| &*t.curve));
+        self.rtree_data = Some(indexes.lrm_rtree);
+        self.fragment_index = Some(FragmentIndex {
+            rtree_data: indexes.fragment_rtree,
+            traversals: indexes
+                .fragment_traversals
+                .into_iter()
+                .map(|idx| TraversalHandle(idx as usize))
+                .collect(),
+        });
+        Ok(())
+    }
+
+    /// Adds an [`Lrm`] on an existing [`Traversal`].
+    ///
+    /// The `anchors` are sorted by their position on the scale.
+    pub fn add_lrm(
+        &mut self,
+        id: &str,
+        traversal: TraversalHandle,
+        mut anchors: Vec<Anchor>,
+        properties: Properties,
+    ) -> Result<LrmHandle, LrsError> {
+        self.traversal(traversal)?;
+        anchors.sort_by(|a, b| a.scale_position().total_cmp(&b.scale_position()));
+        let handle = LrmHandle(self.lrms.len());
+        self.lrms.push(Lrm {
+            measure_direction: measure_direction(&anchors),
+            scale: LrmScale {
+                id: id.to_owned(),
+                anchors,
+            },
+            traversal,
+            properties,
+            alternate_traversals: vec![],
+            validity: Validity::default(),
+        });
+        self.traversals[traversal.0].lrms.push(handle);
+        Ok(handle)
+    }
+
+    /// Adds an alternate [`Traversal`] to an [`Lrm`], such as the second track of a double-track line.
+    /// It shares the scale of the [`Lrm`].
+    pub fn add_alternate_traversal(
+        &mut self,
+        lrm: LrmHandle,
+        traversal: TraversalHandle,
+    ) -> Result<(), LrsError> {
+        self.traversal(traversal)?;
+        if self.lrm(lrm)?.all_traversals().any(|t| t == traversal) {
+            return Ok(());
+        }
+        self.lrms[lrm.0].alternate_traversals.push(traversal);
+        self.traversals[traversal.0].lrms.push(lrm);
+        Ok(())
+    }
+
+    /// Adds the [`Lrm`] to the list of [`Lrm`]s of its [`Traversal`]s.
+    fn attach_lrm(&mut self, lrm: LrmHandle) {
+        for traversal in self.lrms[lrm.0].all_traversals() {
+            self.traversals[traversal.0].lrms.push(lrm);
+        }
+    }
+
+    /// Removes the `candidates` [`Segment`]s that no [`Traversal`] uses anymore, such as the old geometry of a replaced [`Traversal`].
+    ///
+    /// As handles are indices, the following [`Segment`]s are renumbered.
+    fn remove_unused_segments(&mut self, candidates: &HashSet<usize>) {
+        let used: HashSet<_> = self
+            .traversals
+            .iter()
+            .flat_map(|traversal| traversal.segments.iter().map(|s| s.segment_index))
+            .collect();
+        let removed: Vec<_> = (0..self.segments.len())
+            .map(|index| candidates.contains(&index) && !used.contains(&index))
+            .collect();
+        let new_index: Vec<_> = removed
+            .iter()
+            .scan(0, |kept, &removed| {
+                let index = *kept;
+                *kept += usize::from(!removed);
+                Some(index)
+            })
+            .collect();
+        let mut index = 0;
+        self.segments.retain(|_| {
+            index += 1;
+            !removed[index - 1]
+        });
+        for traversal in &mut self.traversals {
+            for segment in &mut traversal.segments {
+                segment.segment_index = new_index[segment.segment_index];
+            }
+        }
+    }
+
+    /// Removes the [`Lrm`] from the list of [`Lrm`]s of every [`Traversal`].
+    fn detach_lrm(&mut self, lrm: LrmHandle) {
+        for traversal in &mut self.traversals {
+            traversal.lrms.retain(|&handle| handle != lrm);
+        }
+    }
+
+    /// Removes an [`Lrm`] and the events located on it.
+    ///
+    /// As handles are indices, the handles of the following [`Lrm`]s are shifted by one.
+    pub fn remove_lrm(&mut self, lrm: LrmHandle) -> Result<Lrm, LrsError> {
+        self.lrm(lrm)?;
+        let removed = self.lrms.remove(lrm.0);
+        let shift = |handle: &mut LrmHandle| {
+            if handle.0 > lrm.0 {
+                handle.0 -= 1
+            }
+        };
+        for traversal in &mut self.traversals {
+            traversal.lrms.retain(|&handle| handle != lrm);
+            traversal.lrms.iter_mut().for_each(shift);
+        }
+        self.point_events.retain(|event| event.lrm != lrm);
+        self.point_events
+            .iter_mut()
+            .for_each(|event| shift(&mut event.lrm));
+        self.linear_events.retain(|event| event.lrm != lrm);
+        self.linear_events
+            .iter_mut()
+            .for_each(|event| shift(&mut event.lrm));
+        Ok(removed)
+    }
+
+    /// Changes the identifier of an [`Lrm`].
+    pub fn rename_lrm(&mut self, lrm: LrmHandle, id: &str) -> Result<(), LrsError> {
+        self.lrm(lrm)?;
+        id.clone_into(&mut self.lrms[lrm.0].scale.id);
+        Ok(())
+    }
+
+    /// Replaces the [`Anchor`]s of an [`Lrm`], for instance after a new survey of the milestones.
+    ///
+    /// The `anchors` are sorted by their position on the scale.
+    pub fn set_anchors(
+        &mut self,
+        lrm: LrmHandle,
+        mut anchors: Vec<Anchor>,
+    ) -> Result<(), LrsError> {
+        self.lrm(lrm)?;
+        anchors.sort_by(|a, b| a.scale_position().total_cmp(&b.scale_position()));
+        self.lrms[lrm.0].measure_direction = measure_direction(&anchors);
+        self.lrms[lrm.0].scale.anchors = anchors;
+        Ok(())
+    }
+
+    /// Replaces the geometry of a [`Traversal`], for instance after a realignment of the track.
+    ///
+    /// The [`Traversal`] is then made of a single new [`Segment`] between the same extremity [`Node`]s.
+    /// Its old [`Segment`]s are removed if no other [`Traversal`] uses them, which renumbers the following [`Segment`]s.
+    /// The new [`Segment`] has the id of the [`Traversal`], followed by `-1`, `-2`… if another [`Segment`] already has it.
+    /// The [`Anchor`]s with a geographical position are projected on the new geometry,
+    /// the others keep their relative position on the [`Curve`].
+    pub fn replace_traversal_geometry(
+        &mut self,
+        traversal: TraversalHandle,
+        geometry: LineString,
+    ) -> Result<(), LrsError> {
+        let old = self.traversal(traversal)?;
+        let curve = CurveImpl::new(geometry.clone(), old.curve.max_extent());
+        if !curve.is_valid() {
+            return Err(CurveError::InvalidGeometry.into());
+        }
+        let extremity = |segment: Option<&SegmentOfTraversal>, start: bool| {
+            let segment = segment.ok_or(CurveError::InvalidGeometry)?;
+            let raw_segment = self
+                .segments
+                .get(segment.segment_index)
+                .ok_or(LrsError::InvalidHandle)?;
+            Ok::<_, LrsError>(if segment.reversed == start {
+                raw_segment.end_node
+            } else {
+                raw_segment.start_node
+            })
+        };
+        let start_node = extremity(old.segments.first(), true)?;
+        let end_node = extremity(old.segments.last(), false)?;
+        let old_segments: HashSet<_> = old.segments.iter().map(|s| s.segment_index).collect();
+
+        for lrm in old.lrms.clone() {
+            // The anchors of an lrm are on its main traversal
+            if self.lrms[lrm.0].traversal == traversal {
+                reproject_anchors(&mut self.lrms[lrm.0], &curve);
+            }
+        }
+
+        self.segments.push(Segment {
+            id: String::new(),
+            properties: Properties::default(),
+            start_node,
+            end_node,
+            geometry: geometry.into(),
+        });
+        let replaced = &mut self.traversals[traversal.0];
+        replaced.segments = vec![SegmentOfTraversal {
+            segment_index: self.segments.len() - 1,
+            reversed: false,
+        }];
+        replaced.curve = curve.into();
+        self.remove_unused_segments(&old_segments);
+
+        // The new segment is named after the traversal, with a suffix if another segment has that id
+        let traversal_id = &self.traversals[traversal.0].id;
+        let mut id = traversal_id.clone();
+        for suffix in 1.. {
+            if !self.segments.iter().any(|segment| segment.id == id) {
+                break;
+            }
+            id = format!("{traversal_id}-{suffix}");
+        }
+        let segment_index = self.traversals[traversal.0].segments[0].segment_index;
+        self.segments[segment_index].id = id;
+        // The stored spatial indexes no longer match the geometries
+        self.rtree_data = None;
+        self.fragment_index = None;
+        Ok(())
+    }
+
+    /// Liblrs version, builder options and source datasets recorded when the file was built.
+    ///
+    /// Returns `None` for files built without that information.
+    pub fn build_info(&self) -> Option<&BuildInfo> {
+        self.build_info.as_ref()
+    }
+
+    /// All the [`Traversal`]s within an `area`, such as the viewport of a map (see [`geo::Rect::to_polygon`]).
+    ///
+    /// Without `exact`, the bounding boxes of the [`Curve`]s are used: it is fast, but a [`Traversal`] near the `area` might be included.
+    /// With `exact`, only the [`Traversal`]s whose geometry intersects the `area` are returned.
+    /// The result is sorted by handle.
+    pub fn traversals_in(&self, area: &Polygon, exact: bool) -> Vec<TraversalHandle> {
+        let Some(bbox) = area.bounding_rect() else {
+            return vec![];
+        };
+        let (min, max) = (bbox.min(), bbox.max());
+        let fragment_tree = self.fragment_index.as_ref().and_then(|index| {
+            RTreeRef::try_new(&index.rtree_data)
+                .ok()
+                .map(|tree| (tree, &index.traversals))
+        });
+        let lrm_tree = self
+            .rtree_data
+            .as_ref()
+            .and_then(|buf| RTreeRef::try_new(buf).ok());
+        let mut candidates: Vec<_> = match (fragment_tree, lrm_tree) {
+            (Some((tree, traversals)), _) => tree
+                .search(min.x, min.y, max.x, max.y)
+                .iter()
+                .filter_map(|&idx| traversals.get(idx as usize).copied())
+                .collect(),
+            (None, Some(tree)) => tree
+                .search(min.x, min.y, max.x, max.y)
+                .iter()
+                .map(|&idx| TraversalHandle(idx as usize))
+                .collect(),
+            (None, None) => self.traversal_handles().collect(),
+        };
+        candidates.sort_by_key(|handle| handle.0);
+        candidates.dedup();
+        // A stored spatial index could reference traversals that do not exist
+        candidates.retain(|handle| {
+            self.traversals.get(handle.0).is_some_and(|traversal| {
+                let curve = &*traversal.curve;
+                curve.is_valid()
+                    && if exact {
+                        curve.as_linestring().intersects(area)
+                    } else {
+                        curve.bbox().is_ok_and(|bbox| bbox.intersects(area))
+                    }
+            })
+        });
+        candidates
+    }
+
+    /// All the [`Lrm`]s whose [`Traversal`] is within an `area`, see [`Lrs::traversals_in`].
+    /// The result is sorted by handle.
+    pub fn lrms_in(&self, area: &Polygon, exact: bool) -> Vec<LrmHandle> {
+        let mut result: Vec<_> = self
+            .traversals_in(area, exact)
+            .into_iter()
+            .flat_map(|traversal| self.traversals[traversal.0].lrms.iter().copied())
+            .collect();
+        result.sort_by_key(|handle| handle.0);
+        result.dedup();
+        result
+    }
+
+    /// The parts of an [`Lrm`] inside an `area`, such as a work zone or a flooded area, see [`Curve::ranges_in`].
+    ///
+    /// Each [`LrmRange`] goes along the [`Traversal`], and the ranges are sorted along the [`Traversal`].
+    pub fn lrm_ranges_in(&self, lrm: LrmHandle, area: &Polygon) -> Result<Vec<LrmRange>, LrsError> {
+        let scale = &self.lrm(lrm)?.scale;
+        self.get_curve(self.lrms[lrm.0].traversal)?
+            .ranges_in(area)
+            .into_iter()
+            .map(|(begin, end)| {
+                Ok(LrmRange {
+                    lrm,
+                    begin: scale.locate_anchor(begin)?,
+                    end: scale.locate_anchor(end)?,
+                    direction: Direction::Default,
+                })
+            })
+            .collect()
+    }
+
+    /// The geographical position of a `measure` on an [`Lrm`].
+    /// A measure beyond the ends of the [`Lrm`] is placed at the nearest end.
+    pub fn resolve(&self, lrm: LrmHandle, measure: &LrmScaleMeasure) -> Result<Point, LrsError> {
+        let traversal = self.lrm(lrm)?.traversal;
+        self.resolve_on(lrm, measure, traversal)
+    }
+
+    /// The geographical position of a `measure` on one of the [`Traversal`]s of an [`Lrm`],
+    /// e.g. on the second track of a double-track line, see [`Lrm::alternate_traversals`].
+    pub fn resolve_on(
+        &self,
+        lrm: LrmHandle,
+        measure: &LrmScaleMeasure,
+        traversal: TraversalHandle,
+    ) -> Result<Point, LrsError> {
+        let lrm = self.lrm(lrm)?;
+        if lrm.all_traversals().all(|t| t != traversal) {
+            return Err(LrsError::TraversalNotInLrm);
+        }
+        let curve_position = self.curve_position(lrm, measure)?;
+        let position = TraversalPosition {
+            curve_position,
+            traversal: lrm.traversal,
+        };
+        if traversal == lrm.traversal {
+            return self.locate_traversal(position);
+        }
+        let projection = self.traversal_project(position, traversal)?;
+        self.locate_traversal(TraversalPosition {
+            curve_position: projection.distance_from_start,
+            traversal,
+        })
+    }
+
+    /// The geographical position of a `measure`, moved perpendicularly to the [`Traversal`] of the [`Lrm`],
+    /// e.g. a mast referenced as “3.5 m left” of the track.
+    /// `lateral_offset` is positive on the left when facing increasing measures, negative on the right.
+    pub fn resolve_with_offset(
+        &self,
+        lrm: LrmHandle,
+        measure: &LrmScaleMeasure,
+        lateral_offset: f64,
+    ) -> Result<Point, LrsError> {
+        let lrm = self.lrm(lrm)?;
+        let curve_position = self.curve_position(lrm, measure)?;
+        let offset = match lrm.measure_direction {
+            Direction::Default => lateral_offset,
+            Direction::Reversed => -lateral_offset,
+        };
+        Ok(self
+            .get_curve(lrm.traversal)?
+            .offset_point(curve_position, offset)?)
+    }
+
+    /// The direction of increasing measures at a `measure`, in degrees clockwise from the north, between 0 and 360,
+    /// e.g. to rotate the icon of a milepost or a train on a map.
+    pub fn heading(&self, lrm: LrmHandle, measure: &LrmScaleMeasure) -> Result<f64, LrsError> {
+        let lrm = self.lrm(lrm)?;
+        let curve_position = self.curve_position(lrm, measure)?;
+        let bearing = self.get_curve(lrm.traversal)?.bearing(curve_position)?;
+        Ok(lrm.measures_heading(bearing))
+    }
+
+    // Position of the measure on the main traversal: it wraps around on a loop, and stays on the curve otherwise
+    fn curve_position(&self, lrm: &Lrm, measure: &LrmScaleMeasure) -> Result<f64, LrsError> {
+        Ok(if self.get_curve(lrm.traversal)?.is_closed() {
+            lrm.scale.locate_point_wrapping(measure)?
+        } else {
+            lrm.scale.locate_point(measure)?.clamp(0., 1.)
+        })
+    }
+
+    /// Is the [`Traversal`] of the [`Lrm`] a loop, such as a test ring or a circular line.
+    /// On a loop, the measures wrap around: resolving a measure past the end continues from the start.
+    pub fn is_loop(&self, lrm: LrmHandle) -> Result<bool, LrsError> {
+        Ok(self.get_curve(self.lrm(lrm)?.traversal)?.is_closed())
+    }
+
+    /// The measures at both ends of an [`Lrm`] and the length of its [`Traversal`].
+    /// Use it to validate a measure given by a user before locating it.
+    pub fn lrm_bounds(&self, lrm: LrmHandle) -> Result<LrmBounds, LrsError> {
+        let lrm = self.lrm(lrm)?;
+        let length = self.get_curve(lrm.traversal)?.length();
+        let start = lrm.scale.locate_anchor(0.)?;
+        let end = lrm.scale.locate_anchor(1.)?;
+        let (min_measure, max_measure) =
+            if lrm.scale.get_position(start.clone())? <= lrm.scale.get_position(end.clone())? {
+                (start, end)
+            } else {
+                (end, start)
+            };
+        Ok(LrmBounds {
+            min_measure,
+            max_measure,
+            length,
+        })
+    }
+
+    /// Where the [`Traversal`]s of two [`Lrm`]s cross or touch, e.g. a junction or a flat crossing.
+    /// When they overlap, both ends of the common part are returned.
+    ///
+    /// The result is sorted along `lrm_a`.
+    pub fn intersections(
+        &self,
+        lrm_a: LrmHandle,
+        lrm_b: LrmHandle,
+    ) -> Result<Vec<LrmIntersection>, LrsError> {
+        let (lrm_a, lrm_b) = (self.lrm(lrm_a)?, self.lrm(lrm_b)?);
+        let curve_a = self.get_curve(lrm_a.traversal)?;
+        let curve_b = self.get_curve(lrm_b.traversal)?;
+        let geom_b = curve_b.as_linestring();
+        let Some(bbox_b) = geom_b.bounding_rect() else {
+            return Ok(vec![]);
+        };
+
+        let mut points = vec![];
+        for line_a in curve_a.as_linestring().lines() {
+            if !line_a.intersects(&bbox_b) {
+                continue;
+            }
+            for line_b in geom_b.lines() {
+                match line_intersection(line_a, line_b) {
+                    Some(LineIntersection::SinglePoint { intersection, .. }) => {
+                        points.push(Point::from(intersection))
+                    }
+                    Some(LineIntersection::Collinear { intersection }) => {
+                        points.extend([intersection.start_point(), intersection.end_point()])
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let mut result = points
+            .into_iter()
+            .map(|point| {
+                let position_a = curve_a.project(point)?.distance_along_curve;
+                let position_b = curve_b.project(point)?.distance_along_curve;
+                let intersection = LrmIntersection {
+                    measure_a: lrm_a.scale.locate_anchor(position_a)?,
+                    measure_b: lrm_b.scale.locate_anchor(position_b)?,
+                    point,
+                };
+                Ok((position_a, intersection))
+            })
+            .collect::<Result<Vec<_>, LrsError>>()?;
+        result.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        result.dedup_by(|(_, a), (_, b)| a.point == b.point);
+        Ok(result
+            .into_iter()
+            .map(|(_, intersection)| intersection)
+            .collect())
+    }
+
+    /// All the [`PointEvent`]s located on an [`Lrm`], in the order they are stored.
+    pub fn point_events(&self, lrm: LrmHandle) -> impl Iterator<Item = &PointEvent> {
+        self.point_events
+            .iter()
+            .filter(move |event| event.lrm == lrm)
+    }
+
+    /// All the [`LinearEvent`]s located on an [`Lrm`], in the order they are stored.
+    pub fn linear_events(&self, lrm: LrmHandle) -> impl Iterator<Item = &LinearEvent> {
+        self.linear_events
+            .iter()
+            .filter(move |event| event.lrm == lrm)
+    }
+
+    /// The [`Lrm`]s valid at a `date` (`YYYY-MM-DD`), see [`Validity`].
+    ///
+    /// Like the other queries at a `date`, returns [`LrsError::InvalidDate`] if the `date` is malformed.
+    pub fn lrms_at<'a>(
+        &'a self,
+        date: &'a str,
+    ) -> Result<impl Iterator<Item = LrmHandle> + 'a, LrsError> {
+        check_date(date)?;
+        Ok(self
+            .lrm_handles()
+            .filter(move |handle| self.lrms[handle.0].validity.contains(date)))
+    }
+
+    /// The [`Lrm`] identified by its `lrm_id` and valid at a `date`.
+    /// Several [`Lrm`]s can share an identifier, e.g. the kilometrage of a line before and after its realignment.
+    pub fn get_lrm_at(&self, lrm_id: &str, date: &str) -> Result<Option<LrmHandle>, LrsError> {
+        Ok(self
+            .lrms_at(date)?
+            .find(|handle| self.lrms[handle.0].scale.id == lrm_id))
+    }
+
+    /// A copy of the [`Lrm`] with only the [`Anchor`]s valid at a `date`.
+    pub fn lrm_at(&self, lrm: LrmHandle, date: &str) -> Result<Lrm, LrsError> {
+        check_date(date)?;
+        let mut lrm = self.lrm(lrm)?.clone();
+        lrm.scale
+            .anchors
+            .retain(|anchor| anchor.validity().contains(date));
+        Ok(lrm)
+    }
+
+    /// The geographical position of a `measure`, with the [`Anchor`]s valid at a `date`, see [`Lrs::resolve`].
+    pub fn resolve_at(
+        &self,
+        lrm: LrmHandle,
+        measure: &LrmScaleMeasure,
+        date: &str,
+    ) -> Result<Point, LrsError> {
+        let lrm = self.lrm_at(lrm, date)?;
+        let curve_position = self.curve_position(&lrm, measure)?;
+        self.locate_traversal(TraversalPosition {
+            curve_position,
+            traversal: lrm.traversal,
+        })
+    }
+
+    /// The [`PointEvent`]s located on an [`Lrm`] and valid at a `date`.
+    pub fn point_events_at<'a>(
+        &'a self,
+        lrm: LrmHandle,
+        date: &'a str,
+    ) -> Result<impl Iterator<Item = &'a PointEvent>, LrsError> {
+        check_date(date)?;
+        Ok(self
+            .point_events(lrm)
+            .filter(move |event| event.validity.contains(date)))
+    }
+
+    /// The [`LinearEvent`]s located on an [`Lrm`] and valid at a `date`.
+    pub fn linear_events_at<'a>(
+        &'a self,
+        lrm: LrmHandle,
+        date: &'a str,
+    ) -> Result<impl Iterator<Item = &'a LinearEvent>, LrsError> {
+        check_date(date)?;
+        Ok(self
+            .linear_events(lrm)
+            .filter(move |event| event.validity.contains(date)))
+    }
+
+    /// All the [`Connection`]s at a given [`Node`].
+    pub fn connections_at(&self, node: NodeHandle) -> Vec<&Connection> {
+        self.connections
+            .iter()
+            .filter(|connection| connection.node == node)
+            .collect()
+    }
+
+    /// Where it is possible to go when leaving a [`Traversal`] through one of its extremities.
+    /// Returns the [`Traversal`]s and the extremities through which they are entered.
+    pub fn connections_from(
+        &self,
+        traversal: TraversalHandle,
+        end: TraversalEnd,
+    ) -> Vec<(TraversalHandle, TraversalEnd)> {
+        self.connections
+            .iter()
+            .filter_map(|connection| {
+                if connection.from == (traversal, end) {
+                    Some(connection.to)
+                } else if connection.bidirectional && connection.to == (traversal, end) {
+                    Some(connection.from)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Splits an external geometry (e.g. the boundary of planned works) where it crosses the `breakpoints` of an [`Lrm`].
+    ///
+    /// Every point of the `line` is projected on the [`Lrm`], and each part is returned with the measures it covers.
+    pub fn segment_geometry_by_measures(
+        &self,
+        lrm: LrmHandle,
+        line: &LineString,
+        breakpoints: &[LrmScaleMeasure],
+    ) -> Result<Vec<MeasuredGeometry>, LrsError> {
+        if line.0.len() < 2 {
+            return Err(CurveError::InvalidGeometry.into());
+        }
+        let scale = self.get_lrm_by_handle(lrm)?;
+        let curve = self.get_curve(self.lrms[lrm.0].traversal)?;
+
+        let mut breakpoints = breakpoints
+            .iter()
+            .map(|measure| scale.locate_point(measure))
+            .collect::<Result<Vec<_>, _>>()?;
+        breakpoints.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let positions = line
+            .points()
+            .map(|point| curve.project(point).map(|p| p.distance_along_curve))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let measured = |geometry, begin: CurvePosition, end: CurvePosition| {
+            Ok::<_, LrsError>(MeasuredGeometry {
+                geometry,
+                range: LrmRange {
+                    lrm,
+                    begin: scale.locate_anchor(begin)?,
+                    end: scale.locate_anchor(end)?,
+                    direction: if end >= begin {
+                        Direction::Default
+                    } else {
+                        Direction::Reversed
+                    },
+                },
+            })
+        };
+
+        let mut parts = vec![];
+        let mut current = vec![line.0[0]];
+        let mut part_begin = positions[0];
+        for (idx, window) in line.0.windows(2).enumerate() {
+            let (from, to) = (positions[idx], positions[idx + 1]);
+            let mut crossed: Vec<_> = breakpoints
+                .iter()
+                .copied()
+                .filter(|&b| (from < b && b < to) || (to < b && b < from))
+                .collect();
+            if to < from {
+                crossed.reverse();
+            }
+            for breakpoint in crossed {
+                let ratio = (breakpoint - from) / (to - from);
+                let split = coord! {
+                    x: window[0].x + ratio * (window[1].x - window[0].x),
+                    y: window[0].y + ratio * (window[1].y - window[0].y),
+                };
+                current.push(split);
+                let geometry = LineString::new(std::mem::replace(&mut current, vec![split]));
+                parts.push(measured(geometry, part_begin, breakpoint)?);
+                part_begin = breakpoint;
+            }
+            current.push(window[1]);
+        }
+        parts.push(measured(
+            LineString::new(current),
+            part_begin,
+            positions[positions.len() - 1],
+        )?);
+        Ok(parts)
+    }
+
+    /// Finds the shortest path in the network between two measures, following the [`Connection`]s between [`Traversal`]s.
+    ///
+    /// The [`Traversal`]s without geometry are ignored as their length is unknown.
+    pub fn route(
+        &self,
+        from: (LrmHandle, &LrmScaleMeasure),
+        to: (LrmHandle, &LrmScaleMeasure),
+    ) -> Result<Route, LrsError> {
+        let locate = |(lrm, measure): (LrmHandle, &LrmScaleMeasure)| {
+            let position = self
+                .get_lrm_by_handle(lrm)?
+                .locate_point(measure)?
+                .clamp(0., 1.);
+            let traversal = self.lrms[lrm.0].traversal;
+            self.get_curve(traversal)?;
+            Ok::<_, LrsError>((traversal, position))
+        };
+        let (start_traversal, start_position) = locate(from)?;
+        let (end_traversal, end_position_on_curve) = locate(to)?;
+        let length = |traversal: TraversalHandle| self.traversals[traversal.0].curve.length();
+
+        let mut search = RouteSearch::default();
+        for end in [TraversalEnd::Start, TraversalEnd::End] {
+            let cost = (start_position - end_position(end)).abs() * length(start_traversal);
+            search.push(RouteStep::Extremity(start_traversal, end), cost, None);
+        }
+        if start_traversal == end_traversal {
+            let cost = (start_position - end_position_on_curve).abs() * length(start_traversal);
+            search.push(RouteStep::Arrival, cost, None);
+        }
+
+        let mut found = false;
+        while let Some(RouteCandidate { cost, step }) = search.pop() {
+            match step {
+                RouteStep::Arrival => {
+                    found = true;
+                    break;
+                }
+                RouteStep::Entry(traversal, end) => {
+                    if traversal == end_traversal {
+                        let to_end =
+                            (end_position(end) - end_position_on_curve).abs() * length(traversal);
+                        search.push(RouteStep::Arrival, cost + to_end, Some(step));
+                    }
+                    let through = RouteStep::Extremity(traversal, opposite(end));
+                    search.push(through, cost + length(traversal), Some(step));
+                }
+                RouteStep::Extremity(traversal, end) => {
+                    for (next, next_end) in self.connections_from(traversal, end) {
+                        if self.get_curve(next).is_ok() {
+                            search.push(RouteStep::Entry(next, next_end), cost, Some(step));
+                        }
+                    }
+                }
+            }
+        }
+        if !found {
+            return Err(LrsError::NoRoute);
+        }
+
+        // Walk back from the arrival to build the path
+        let mut steps = vec![];
+        let mut current = Some(RouteStep::Arrival);
+        while let Some(step) = current {
+            steps.push(step);
+            current = search.previous[&step];
+        }
+        steps.reverse();
+
+        let mut ranges = vec![];
+        let mut position = (start_traversal, start_position);
+        for step in steps {
+            let (traversal, end) = match step {
+                RouteStep::Entry(traversal, end) => {
+                    position = (traversal, end_position(end));
+                    continue;
+                }
+                RouteStep::Extremity(traversal, end) => (traversal, end_position(end)),
+                RouteStep::Arrival => (end_traversal, end_position_on_curve),
+            };
+            let begin = position.1;
+            if begin != end {
+                ranges.push(TraversalRange {
+                    traversal,
+                    begin,
+                    end,
+                    direction: if end > begin {
+                        Direction::Default
+                    } else {
+                        Direction::Reversed
+                    },
+                });
+            }
+            position = (traversal, end);
+        }
+
+        let mut coords: Vec<Coord> = vec![];
+        let mut total_length = 0.;
+        for range in &ranges {
+            total_length += (range.end - range.begin).abs() * length(range.traversal);
+            let part = self.traversals[range.traversal.0]
+                .curve
+                .sublinestring(range.begin, range.end)
+                .ok_or(CurveError::InvalidRange(range.begin, range.end))?;
+            for coord in part.0 {
+                if coords.last() != Some(&coord) {
+                    coords.push(coord);
+                }
+            }
+        }
+
+        Ok(Route {
+            ranges,
+            length: total_length,
+            geometry: LineString::new(coords),
+        })
+    }
+
+    /// The distance along the network between two measures, following the [`Connection`]s between [`Traversal`]s.
+    ///
+    /// Unlike the distance as the crow flies, it is the distance actually run by a train, see [`Lrs::route`].
+    /// Fails with [`LrsError::NoRoute`] when the positions are not connected.
+    pub fn network_distance(
+        &self,
+        from: (LrmHandle, &LrmScaleMeasure),
+        to: (LrmHandle, &LrmScaleMeasure),
+    ) -> Result<f64, LrsError> {
+        self.route(from, to).map(|route| route.length)
+    }
+
     /// Loads an [`Lrs`] from the file system.
     pub fn new<P: AsRef<std::path::Path>>(filename: P) -> Result<Self, LrsError> {
         use std::io::Read;
@@ -375,12 +2668,39 @@ pub enum LrsError {
     /// Could not read the LRS file.
     #[error("read file error")]
     ReadFileError,
+    /// The [`Traversal`] is neither the main nor an alternate [`Traversal`] of the [`Lrm`].
+    #[error("the traversal is not used by the lrm")]
+    TraversalNotInLrm,
     /// Could not parse the LRS file.
     #[error("invalid flatbuffer content {0}")]
     InvalidArchive(#[from] flatbuffers::InvalidFlatbuffer),
+    /// The file is truncated or its content was altered.
+    #[error("corrupted file: {0}")]
+    CorruptFile(String),
+    /// The file uses a feature of the format that is not supported by this build of the library.
+    #[error("unsupported file: {0}")]
+    UnsupportedFile(String),
     /// The archive does not have all the required data
     #[error("the archive does not have all the required data: {0} is missing")]
     IncompleteArchive(String),
+    /// The [`Lrm`]s are not defined on the same [`Traversal`].
+    #[error("the lrms do not share the same traversal")]
+    NoSharedTraversal,
+    /// The [`GeometryType`] of the file (first) is not the one of the [`Curve`] used to load it (second).
+    #[error("the file has a {0:?} geometry, but it is loaded as {1:?}")]
+    GeometryTypeMismatch(GeometryType, GeometryType),
+    /// There is no path in the network between the positions.
+    #[error("the positions are not connected")]
+    NoRoute,
+    /// The [`Traversal`] has no usable geometry: only the measures can be used, not the geographical positions.
+    #[error("the geometry of the traversal is not available")]
+    GeometryUnavailable,
+    /// Both [`Lrs`] have an element with the same identifier.
+    #[error("the identifier {0} is used in both lrs")]
+    DuplicateId(String),
+    /// A date is not an ISO 8601 date (`YYYY-MM-DD`), see [`check_date`].
+    #[error("invalid date {0}, expected YYYY-MM-DD")]
+    InvalidDate(String),
 }
 
 /// The basic functions to manipulate the [`Lrs`].
@@ -394,6 +2714,9 @@ pub trait LrsBase {
     /// If the implementation uses an other format (e.g. splines),
     /// it will be segmentized as a [`LineString`] and might not be as acurate as the underlying representation
     fn get_linestring(&self, traversal: TraversalHandle) -> Result<LineString, LrsError>;
+    /// Returns the geometry of the [`Traversal`] of an [`Lrm`] as a [`LineString`], ordered by increasing measures.
+    /// Use it to draw the direction of the [`Lrm`], e.g. with arrows.
+    fn oriented_linestring(&self, lrm: LrmHandle) -> Result<LineString, LrsError>;
 
     /// Projects a [`Point`] on the [`Traversal`]s to a given [`Lrm`].
     /// The [`Point`] must be in the bounding box of the [`Curve`] of the [`Traversal`].
@@ -401,20 +2724,28 @@ pub trait LrsBase {
     /// Projects a [`Point`] on all [`Lrm`] where the [`Point`] is in the bounding box.
     /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
     fn lookup_lrms(&self, point: Point) -> Vec<LrmProjection>;
+    /// Projects a [`Point`] on every [`Lrm`] of the network that is at most `max_distance` away.
+    /// Use it when the [`Lrm`] is not known in advance.
+    /// Like the `orthogonal_offset` and [`Curve::max_extent`], `max_distance` is in meters with a [`SphericalLineStringCurve`]
+    /// and in the units of the coordinates with a [`PlanarLineStringCurve`].
+    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+    fn lookup_all(&self, point: Point, max_distance: f64) -> Vec<LrmProjection>;
     /// Returns all the traversals whose bounding box include the given point
     ///
-    /// The function will use the spatial index if it is defined
+    /// The function will use the spatial index of the fragments if it is defined,
+    /// then the one of the lrms: only the traversals with a fragment near the point are returned
     fn traversals_containing(&self, point: Point) -> Vec<TraversalHandle>;
 
     /// Given a [`TraversalPosition`], returns it geographical position ([`Point`]).
     fn locate_traversal(&self, position: TraversalPosition) -> Result<Point, LrsError>;
 
-    /// This methods returns the [`TraversalHandle`] of the [`Lrm`].
-    fn get_lrm_traversal(&self, lrm: LrmHandle) -> TraversalHandle;
+    /// This methods returns the [`TraversalHandle`] of the [`Lrm`], or [`LrsError::InvalidHandle`] if it does not exist.
+    fn get_lrm_traversal(&self, lrm: LrmHandle) -> Result<TraversalHandle, LrsError>;
 
     /// A [`Traversal`] can be use for multiple [`Lrm`]s.
     /// For example, a highway could have milestones referenced in `miles` AND `kilometers`.
-    fn get_traversal_lrms(&self, traversal: TraversalHandle) -> &[LrmHandle];
+    /// Fails with [`LrsError::InvalidHandle`] if the [`Traversal`] does not exist.
+    fn get_traversal_lrms(&self, traversal: TraversalHandle) -> Result<&[LrmHandle], LrsError>;
 
     /// Projects a [`TraversalPosition`] on a [`Traversal`] onto an other [`Traversal`],
     /// e.g. when placing a point on both sides of the highway.
@@ -453,6 +2784,16 @@ pub trait LrsBase {
     /// Given an [`LrmMeasure`], returns its [`LrmPosition`].
     fn lrm_get_position(&self, measure: LrmMeasure) -> Result<LrmPosition, LrsError>;
 
+    /// Converts a [`LrmScaleMeasure`] of an [`Lrm`] into the [`LrmMeasure`] of an other [`Lrm`].
+    /// e.g. when an operator and the infrastructure manager use different scales for the same track.
+    /// Both [`Lrm`]s must be defined on the same [`Traversal`].
+    fn convert_measure(
+        &self,
+        from_lrm: LrmHandle,
+        to_lrm: LrmHandle,
+        measure: &LrmScaleMeasure,
+    ) -> Result<LrmMeasure, LrsError>;
+
     // TODO
     // fn traversal_get_segment(position: TraversalPosition) -> SegmentPosition;
     // fn traversal_range_get_segments(range: TraversalRange) -> Vec<SegmentRange>;
@@ -473,21 +2814,64 @@ impl<CurveImpl: Curve> LrsBase for Lrs<CurveImpl> {
             .map(TraversalHandle)
     }
 
-    fn lookup(&self, point: Point, lrm_handle: LrmHandle) -> Result<LrmProjection, LrsError> {
-        let lrm = &self.lrms[lrm_handle.0];
-        let traversal = &self.traversals[lrm.traversal.0];
-        let projection = traversal.curve.project(point)?;
-        let measure = lrm.scale.locate_anchor(projection.distance_along_curve)?;
+    fn lookup(&self, point: Point, lrm_handle: LrmHandle) -> Result<LrmProjection, LrsError> {
+        let lrm = self.lrm(lrm_handle)?;
+        let projection = self.get_curve(lrm.traversal)?.project(point)?;
+        let mut nearest = (
+            lrm.traversal,
+            projection.distance_along_curve,
+            projection.offset,
+        );
+        for &alternate in &lrm.alternate_traversals {
+            let Some(projection) = self
+                .get_curve(alternate)
+                .ok()
+                .and_then(|curve| curve.project(point).ok())
+            else {
+                continue;
+            };
+            if projection.offset.abs() >= nearest.2.abs() {
+                continue;
+            }
+            // The measure is the one of the main traversal, across the track
+            let position = TraversalPosition {
+                curve_position: projection.distance_along_curve,
+                traversal: alternate,
+            };
+            if let Ok(on_main) = self.traversal_project(position, lrm.traversal) {
+                nearest = (alternate, on_main.distance_from_start, projection.offset);
+            }
+        }
+
+        let (traversal, curve_position, orthogonal_offset) = nearest;
+        let measure = lrm.scale.locate_anchor(curve_position)?;
         Ok(LrmProjection {
             measure: LrmMeasure {
                 lrm: lrm_handle,
                 measure,
             },
-            orthogonal_offset: projection.offset,
+            orthogonal_offset,
+            traversal,
         })
     }
 
     fn traversals_containing(&self, point: Point) -> Vec<TraversalHandle> {
+        let fragment_tree = self.fragment_index.as_ref().and_then(|index| {
+            RTreeRef::try_new(&index.rtree_data)
+                .ok()
+                .map(|tree| (tree, &index.traversals))
+        });
+        if let Some((tree, traversals)) = fragment_tree {
+            let mut result: Vec<_> = tree
+                .search(point.x(), point.y(), point.x(), point.y())
+                .iter()
+                .filter_map(|&idx| traversals.get(idx as usize).copied())
+                .collect();
+            result.sort_by_key(|handle| handle.0);
+            result.dedup();
+            return result;
+        }
+
         let rtree = self
             .rtree_data
             .as_ref()
@@ -500,23 +2884,37 @@ impl<CurveImpl: Curve> LrsBase for Lrs<CurveImpl> {
                 tree.search(point.x(), point.y(), point.x(), point.y())
                     .iter()
                     .map(|idx| TraversalHandle(*idx as usize))
+                    .filter(|handle| handle.0 < self.traversals.len())
                     .collect()
             })
-            .unwrap_or(
+            .unwrap_or_else(|| {
                 self.traversals
                     .iter()
                     .enumerate()
-                    .filter(|(_idx, traversal)| traversal.curve.bbox().contains(&point))
+                    .filter(|(_idx, traversal)| {
+                        traversal.curve.is_valid()
+                            && traversal
+                                .curve
+                                .bbox()
+                                .is_ok_and(|bbox| bbox.contains(&point))
+                    })
                     .map(|(idx, _traversal)| TraversalHandle(idx))
-                    .collect(),
-            )
+                    .collect()
+            })
     }
 
     fn lookup_lrms(&self, point: Point) -> Vec<LrmProjection> {
-        let mut result: Vec<_> = self
+        let mut lrms: Vec<_> = self
             .traversals_containing(point)
             .iter()
-            .flat_map(|traversal_handle| &self.traversals[traversal_handle.0].lrms)
+            .filter_map(|traversal_handle| self.traversals.get(traversal_handle.0))
+            .flat_map(|traversal| traversal.lrms.iter().copied())
+            .collect();
+        // An lrm with alternate traversals can be found several times
+        lrms.sort_by_key(|handle| handle.0);
+        lrms.dedup();
+        let mut result: Vec<_> = lrms
+            .iter()
             .flat_map(|&lrm_handle| self.lookup(point, lrm_handle))
             .collect();
         result.sort_by(|a, b| {
@@ -528,18 +2926,51 @@ impl<CurveImpl: Curve> LrsBase for Lrs<CurveImpl> {
         result
     }
 
+    fn lookup_all(&self, point: Point, max_distance: f64) -> Vec<LrmProjection> {
+        // The spatial index only covers `max_extent` around each curve.
+        // Both distances are in the unit of the curve, as all the curves of an Lrs have the same type.
+        let candidates: HashSet<_> = if self
+            .traversals
+            .iter()
+            .all(|traversal| traversal.curve.max_extent() >= max_distance)
+        {
+            self.traversals_containing(point).into_iter().collect()
+        } else {
+            (0..self.traversals.len()).map(TraversalHandle).collect()
+        };
+
+        let mut result: Vec<_> = (0..self.lrms.len())
+            .map(LrmHandle)
+            .filter(|lrm_handle| {
+                self.lrms[lrm_handle.0]
+                    .all_traversals()
+                    .any(|traversal| candidates.contains(&traversal))
+            })
+            .flat_map(|lrm_handle| self.lookup(point, lrm_handle))
+            .filter(|projection| projection.orthogonal_offset.abs() <= max_distance)
+            .collect();
+        result.sort_by(|a, b| {
+            a.orthogonal_offset
+                .abs()
+                .partial_cmp(&b.orthogonal_offset.abs())
+                .unwrap_or(Ordering::Equal)
+        });
+        result
+    }
+
     fn locate_traversal(&self, position: TraversalPosition) -> Result<Point, LrsError> {
         Ok(self
             .get_curve(position.traversal)?
             .resolve(position.curve_position)?)
     }
 
-    fn get_lrm_traversal(&self, lrm: LrmHandle) -> TraversalHandle {
-        self.lrms[lrm.0].traversal
+    fn get_lrm_traversal(&self, lrm: LrmHandle) -> Result<TraversalHandle, LrsError> {
+        self.lrm(lrm).map(|lrm| lrm.traversal)
     }
 
-    fn get_traversal_lrms(&self, traversal: TraversalHandle) -> &[LrmHandle] {
-        &self.traversals[traversal.0].lrms
+    fn get_traversal_lrms(&self, traversal: TraversalHandle) -> Result<&[LrmHandle], LrsError> {
+        self.traversal(traversal)
+            .map(|traversal| traversal.lrms.as_slice())
     }
 
     fn traversal_project(
@@ -596,6 +3027,7 @@ impl<CurveImpl: Curve> LrsBase for Lrs<CurveImpl> {
         Ok(LrmProjection {
             measure: LrmMeasure { lrm: onto, measure },
             orthogonal_offset: 0.,
+            traversal: lrm.traversal,
         })
     }
 
@@ -623,121 +3055,800 @@ impl<CurveImpl: Curve> LrsBase for Lrs<CurveImpl> {
             end: end_projection.measure.measure,
             direction: range.direction,
         })
-    }
-
-    fn lrm_get_measure(&self, position: LrmPosition) -> Result<LrmMeasure, LrsError> {
-        let scale = self.get_lrm_by_handle(position.lrm)?;
-        Ok(LrmMeasure {
-            measure: scale.get_measure(position.distance_from_start)?,
-            lrm: position.lrm,
+    }
+
+    fn lrm_get_measure(&self, position: LrmPosition) -> Result<LrmMeasure, LrsError> {
+        let scale = self.get_lrm_by_handle(position.lrm)?;
+        Ok(LrmMeasure {
+            measure: scale.get_measure(position.distance_from_start)?,
+            lrm: position.lrm,
+        })
+    }
+
+    fn lrm_get_position(&self, measure: LrmMeasure) -> Result<LrmPosition, LrsError> {
+        let scale = self.get_lrm_by_handle(measure.lrm)?;
+        Ok(LrmPosition {
+            distance_from_start: scale.locate_point(&measure.measure)?,
+            lrm: measure.lrm,
+        })
+    }
+
+    fn convert_measure(
+        &self,
+        from_lrm: LrmHandle,
+        to_lrm: LrmHandle,
+        measure: &LrmScaleMeasure,
+    ) -> Result<LrmMeasure, LrsError> {
+        let from = self.lrms.get(from_lrm.0).ok_or(LrsError::InvalidHandle)?;
+        let to = self.lrms.get(to_lrm.0).ok_or(LrsError::InvalidHandle)?;
+        if from.traversal != to.traversal {
+            return Err(LrsError::NoSharedTraversal);
+        }
+
+        let curve_position = from.scale.locate_point(measure)?;
+        Ok(LrmMeasure {
+            measure: to.scale.locate_anchor(curve_position)?,
+            lrm: to_lrm,
+        })
+    }
+
+    fn get_linestring(&self, traversal: TraversalHandle) -> Result<LineString, LrsError> {
+        self.get_curve(traversal).map(|c| c.as_linestring())
+    }
+
+    fn oriented_linestring(&self, lrm: LrmHandle) -> Result<LineString, LrsError> {
+        let lrm = self.lrm(lrm)?;
+        let mut linestring = self.get_linestring(lrm.traversal)?;
+        if matches!(lrm.measure_direction, Direction::Reversed) {
+            linestring.0.reverse();
+        }
+        Ok(linestring)
+    }
+}
+
+/// Streams the [`Traversal`]s to any [`geozero`] sink, such as FlatGeobuf, MVT or WKB.
+///
+/// Each [`Traversal`] is a feature with its `id` and its properties, and the geometry of its [`Curve`].
+#[cfg(feature = "geozero")]
+impl<CurveImpl: Curve + geozero::GeozeroGeometry> geozero::GeozeroDatasource for Lrs<CurveImpl> {
+    fn process<P: geozero::FeatureProcessor>(
+        &mut self,
+        processor: &mut P,
+    ) -> geozero::error::Result<()> {
+        use geozero::ColumnValue;
+
+        processor.dataset_begin(Some("traversals"))?;
+        for (idx, traversal) in self.traversals.iter().enumerate() {
+            let idx = idx as u64;
+            processor.feature_begin(idx)?;
+            processor.properties_begin()?;
+            processor.property(0, "id", &ColumnValue::String(&traversal.id))?;
+            for (i, (key, value)) in traversal.properties.iter().enumerate() {
+                processor.property(i + 1, key, &ColumnValue::String(value))?;
+            }
+            processor.properties_end()?;
+            processor.geometry_begin()?;
+            traversal.curve.process_geom(processor)?;
+            processor.geometry_end()?;
+            processor.feature_end(idx)?;
+        }
+        processor.dataset_end()
+    }
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    pub(crate) fn get_curve(&self, handle: TraversalHandle) -> Result<&CurveImpl, LrsError> {
+        let curve = &*self.traversal(handle)?.curve;
+        if curve.is_valid() {
+            Ok(curve)
+        } else {
+            Err(LrsError::GeometryUnavailable)
+        }
+    }
+
+    /// Position of an [`Anchor`] of an [`Lrm`]: its own point, or its position on the [`Curve`] of the [`Traversal`].
+    #[cfg(any(
+        feature = "geojson",
+        feature = "geopackage",
+        feature = "geoparquet",
+        feature = "kml"
+    ))]
+    pub(crate) fn anchor_point(&self, lrm: &Lrm, anchor: &Anchor) -> Option<Point> {
+        anchor.point().or_else(|| {
+            let curve = self.get_curve(lrm.traversal).ok()?;
+            curve.resolve(anchor.curve_position()).ok()
+        })
+    }
+
+    pub(crate) fn get_lrm_by_handle(&self, handle: LrmHandle) -> Result<&LrmScale, LrsError> {
+        self.lrm(handle).map(|lrm| &lrm.scale)
+    }
+
+    fn orthogonal_segment(
+        &self,
+        handle: TraversalHandle,
+        from_start: CurvePosition,
+    ) -> Result<geo::Line, LrsError> {
+        let from_curve = self.get_curve(handle)?;
+        let normal = from_curve.get_normal(from_start)?;
+
+        let position = from_curve.resolve(from_start)?;
+        let start = geo::coord! {
+            x: position.x() + normal.0 * from_curve.max_extent(),
+            y: position.y() + normal.1 * from_curve.max_extent()
+        };
+        let end = geo::coord! {
+            x: position.x() - normal.0 * from_curve.max_extent(),
+            y: position.y() - normal.1 * from_curve.max_extent()
+        };
+
+        Ok(geo::Line::new(start, end))
+    }
+}
+
+/// A key-value `HashMap` to add metadata to the objects.
+pub type Properties = std::collections::HashMap<String, String>;
+
+/// The period during which an object is valid, e.g. the kilometrage of a line before its realignment.
+///
+/// The dates are ISO 8601 dates (`YYYY-MM-DD`), checked by [`check_date`]: they are compared as strings.
+/// The first day is included and the last one excluded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Validity {
+    /// First day of validity, `None` if the object has always been valid.
+    pub from: Option<String>,
+    /// First day the object is no longer valid, `None` if it is still valid.
+    pub to: Option<String>,
+}
+
+impl Validity {
+    /// Builds a [`Validity`] from its first day, included, and its last day, excluded.
+    ///
+    /// Returns [`LrsError::InvalidDate`] if a date is not a `YYYY-MM-DD` date.
+    pub fn new(from: Option<&str>, to: Option<&str>) -> Result<Self, LrsError> {
+        let validity = Self::from_fb(from, to);
+        validity.check()?;
+        Ok(validity)
+    }
+
+    // The dates of a file were checked when it was built
+    pub(crate) fn from_fb(from: Option<&str>, to: Option<&str>) -> Self {
+        Self {
+            from: from.map(str::to_owned),
+            to: to.map(str::to_owned),
+        }
+    }
+
+    /// Checks that both dates are `YYYY-MM-DD` dates, as the fields can be set directly.
+    pub fn check(&self) -> Result<(), LrsError> {
+        self.from
+            .iter()
+            .chain(&self.to)
+            .try_for_each(|date| check_date(date))
+    }
+
+    /// Whether the object is valid at a `date` (`YYYY-MM-DD`). A malformed `date` is in no period.
+    pub fn contains(&self, date: &str) -> bool {
+        check_date(date).is_ok()
+            && self.from.as_deref().is_none_or(|from| from <= date)
+            && self.to.as_deref().is_none_or(|to| date < to)
+    }
+
+    /// Whether both periods have at least one day in common.
+    pub fn overlaps(&self, other: &Validity) -> bool {
+        let ends_before = |a: &Validity, b: &Validity| matches!((&a.to, &b.from), (Some(to), Some(from)) if to <= from);
+        !ends_before(self, other) && !ends_before(other, self)
+    }
+
+    pub(crate) fn to_fb<'fbb>(
+        &self,
+        fbb: &mut FlatBufferBuilder<'fbb>,
+    ) -> (Option<WIPOffset<&'fbb str>>, Option<WIPOffset<&'fbb str>>) {
+        (
+            self.from.as_deref().map(|from| fbb.create_string(from)),
+            self.to.as_deref().map(|to| fbb.create_string(to)),
+        )
+    }
+}
+
+#[macro_export]
+/// Build a properties map:
+/// `properties!("source" => "openstreetmap", "licence" => "ODbL")`.
+macro_rules! properties {
+    ($($k:expr => $v:expr),* $(,)?) => {{
+        core::convert::From::from([$(($k.to_owned(), $v.to_owned()),)*])
+    }};
+}
+
+/// Checks that a `date` is an ISO 8601 date (`YYYY-MM-DD`) that exists, such as `2024-02-29`.
+///
+/// In this format only, the order of the strings is the chronological order.
+pub fn check_date(date: &str) -> Result<(), LrsError> {
+    let bytes = date.as_bytes();
+    let number = |range: std::ops::Range<usize>| {
+        bytes[range].iter().try_fold(0, |number, &digit| {
+            digit
+                .is_ascii_digit()
+                .then(|| number * 10 + u32::from(digit - b'0'))
+        })
+    };
+    let valid = bytes.len() == 10 && bytes[4] == b'-' && bytes[7] == b'-' && {
+        match (number(0..4), number(5..7), number(8..10)) {
+            (Some(year), Some(month), Some(day)) => {
+                let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+                let days = match month {
+                    2 if leap => 29,
+                    2 => 28,
+                    4 | 6 | 9 | 11 => 30,
+                    1..=12 => 31,
+                    _ => 0,
+                };
+                (1..=days).contains(&day)
+            }
+            _ => false,
+        }
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(LrsError::InvalidDate(date.to_owned()))
+    }
+}
+
+/// Builds a [`Properties`] from a FlatBuffer vector of Property
+///
+/// Implementation note: as [`Properties`] is just an alias, we cannot `impl` for it (e.g. Into)
+pub fn from_fb(properties: Option<Vector<ForwardsUOffset<lrs_generated::Property>>>) -> Properties {
+    properties
+        .unwrap_or_default()
+        .iter()
+        .map(|property| (property.key().to_string(), property.value().to_string()))
+        .collect()
+}
+
+/// The items of a file to load, and their new index.
+struct Selection {
+    lrms: Vec<Option<usize>>,
+    traversals: Vec<Option<usize>>,
+    segments: Vec<bool>,
+    segment_indices: Vec<usize>,
+    nodes: Vec<Option<usize>>,
+}
+
+impl Selection {
+    fn new(lrs: &lrs_generated::Lrs, filter: Option<&LoadFilter>) -> Self {
+        let raw_lrms = lrs.linear_referencing_methods().unwrap_or_default();
+        let raw_traversals = lrs.traversals().unwrap_or_default();
+        let raw_segments = lrs.segments().unwrap_or_default();
+
+        let selected_lrms = selected_lrms(lrs, filter);
+        let selected_traversals = match filter {
+            None => vec![true; raw_traversals.len()],
+            Some(_) => traversals_of_lrms(lrs, &selected_lrms),
+        };
+
+        let mut segments = vec![filter.is_none(); raw_segments.len()];
+        for (traversal, _) in raw_traversals
+            .iter()
+            .zip(&selected_traversals)
+            .filter(|(_, s)| **s)
+        {
+            for segment in traversal.segments() {
+                if let Some(selected) = segments.get_mut(segment.segment_index() as usize) {
+                    *selected = true;
+                }
+            }
+        }
+
+        // The extremities of the selected segments, where the selected traversals are connected
+        // and the nodes the anchors of the selected lrms are bound to
+        let raw_nodes = lrs.nodes().unwrap_or_default();
+        let mut selected_nodes = vec![filter.is_none(); raw_nodes.len()];
+        let segment_nodes = raw_segments
+            .iter()
+            .zip(&segments)
+            .filter(|(_, s)| **s)
+            .flat_map(|(segment, _)| [segment.start_node_index(), segment.end_node_index()]);
+        let connection_nodes = lrs
+            .connections()
+            .unwrap_or_default()
+            .iter()
+            .filter(|connection| {
+                [
+                    connection.from_traversal_index(),
+                    connection.to_traversal_index(),
+                ]
+                .iter()
+                .all(|&t| selected_traversals.get(t as usize) == Some(&true))
+            })
+            .map(|connection| connection.node_index());
+        let raw_anchors = lrs.anchors().unwrap_or_default();
+        let anchor_nodes = raw_lrms
+            .iter()
+            .zip(&selected_lrms)
+            .filter(|(_, s)| **s)
+            .flat_map(|(lrm, _)| lrm.anchor_indices().iter())
+            .filter(|&anchor| (anchor as usize) < raw_anchors.len())
+            .filter_map(|anchor| raw_anchors.get(anchor as usize).node())
+            .map(u64::from);
+        for node in segment_nodes.chain(connection_nodes).chain(anchor_nodes) {
+            if let Some(selected) = selected_nodes.get_mut(node as usize) {
+                *selected = true;
+            }
+        }
+
+        let segment_indices = renumber(segments.iter().copied())
+            .iter()
+            .map(|index| index.unwrap_or_default())
+            .collect();
+        Self {
+            lrms: renumber(selected_lrms.into_iter()),
+            traversals: renumber(selected_traversals.into_iter()),
+            segments,
+            segment_indices,
+            nodes: renumber(selected_nodes.into_iter()),
+        }
+    }
+
+    /// The new handle of a loaded [`Lrm`].
+    fn lrm(&self, raw: LrmHandle) -> Option<LrmHandle> {
+        self.lrms.get(raw.0).copied().flatten().map(LrmHandle)
+    }
+
+    /// The new handle of a loaded [`Traversal`].
+    fn traversal(&self, raw: TraversalHandle) -> Option<TraversalHandle> {
+        self.traversals
+            .get(raw.0)
+            .copied()
+            .flatten()
+            .map(TraversalHandle)
+    }
+
+    /// The new index of a loaded [`Segment`].
+    fn segment(&self, raw: usize) -> usize {
+        self.segment_indices.get(raw).copied().unwrap_or(raw)
+    }
+
+    /// The new handle of a loaded [`Node`], `None` if it is not loaded.
+    fn node(&self, raw: NodeHandle) -> Option<NodeHandle> {
+        self.nodes.get(raw.0).copied().flatten().map(NodeHandle)
+    }
+}
+
+/// Whether each [`Lrm`] of a file is loaded with the `filter`.
+fn selected_lrms(lrs: &lrs_generated::Lrs, filter: Option<&LoadFilter>) -> Vec<bool> {
+    let raw_lrms = lrs.linear_referencing_methods().unwrap_or_default();
+    match filter {
+        None => vec![true; raw_lrms.len()],
+        Some(LoadFilter::LrmIds(ids)) => raw_lrms
+            .iter()
+            .map(|lrm| ids.iter().any(|id| id == lrm.id()))
+            .collect(),
+        Some(LoadFilter::BoundingBox(bbox)) => {
+            let traversals = traversals_in_bbox(lrs, bbox);
+            raw_lrms
+                .iter()
+                .map(|lrm| {
+                    let alternates = lrm.alternate_traversal_indices().unwrap_or_default();
+                    std::iter::once(lrm.traversal_index())
+                        .chain(alternates.iter())
+                        .any(|traversal| traversals.contains(&(traversal as usize)))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Whether each [`Traversal`] of a file is the main or an alternate [`Traversal`] of a selected [`Lrm`].
+fn traversals_of_lrms(lrs: &lrs_generated::Lrs, selected_lrms: &[bool]) -> Vec<bool> {
+    let raw_lrms = lrs.linear_referencing_methods().unwrap_or_default();
+    let mut selected_traversals = vec![false; lrs.traversals().unwrap_or_default().len()];
+    for (lrm, _) in raw_lrms.iter().zip(selected_lrms).filter(|(_, s)| **s) {
+        let alternates = lrm.alternate_traversal_indices().unwrap_or_default();
+        for traversal in std::iter::once(lrm.traversal_index()).chain(alternates.iter()) {
+            if let Some(selected) = selected_traversals.get_mut(traversal as usize) {
+                *selected = true;
+            }
+        }
+    }
+    selected_traversals
+}
+
+/// Gives a consecutive index to every selected item.
+fn renumber(selected: impl Iterator<Item = bool>) -> Vec<Option<usize>> {
+    let mut count = 0;
+    selected
+        .map(|selected| {
+            selected.then(|| {
+                count += 1;
+                count - 1
+            })
+        })
+        .collect()
+}
+
+/// The indices of the traversals of a file in a bounding box, using the spatial index if it exists.
+fn traversals_in_bbox(lrs: &lrs_generated::Lrs, bbox: &Rect) -> HashSet<usize> {
+    if let Some(traversals) = indexed_traversals_in_bbox(lrs, bbox) {
+        return traversals;
+    }
+
+    let segments = lrs.segments().unwrap_or_default();
+    lrs.traversals()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .filter(|(_, traversal)| {
+            traversal.segments().iter().any(|segment| {
+                let index = segment.segment_index() as usize;
+                index < segments.len()
+                    && segment_coords(&segments.get(index), lrs.coordinate_precision())
+                        .iter()
+                        .any(|coord| bbox.intersects(coord))
+            })
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// The indices of the traversals of a file in a bounding box, if the file has a spatial index.
+fn indexed_traversals_in_bbox(lrs: &lrs_generated::Lrs, bbox: &Rect) -> Option<HashSet<usize>> {
+    let (min, max) = (bbox.min(), bbox.max());
+    let bytes = lrs.lrm_spatial_index()?.bytes();
+    let tree = RTreeRef::<f64>::try_new(&bytes).ok()?;
+    Some(
+        tree.search(min.x, min.y, max.x, max.y)
+            .iter()
+            .map(|&idx| idx as usize)
+            .collect(),
+    )
+}
+
+/// Places the [`Anchor`]s of an [`Lrm`] on the new `curve` of its main [`Traversal`].
+///
+/// The [`Anchor`]s with a geographical position are projected on the `curve`,
+/// the others keep their relative position.
+fn reproject_anchors<CurveImpl: Curve>(lrm: &mut Lrm, curve: &CurveImpl) {
+    for anchor in &mut lrm.scale.anchors {
+        // The surveyed position is more accurate than the projection on the old geometry
+        let surveyed = anchor.surveyed_point();
+        let (curve_position, point, offset) = match anchor {
+            Anchor::Named(a) => (
+                &mut a.curve_position,
+                &mut a.point,
+                &mut a.projection_offset,
+            ),
+            Anchor::Unnamed(a) => (
+                &mut a.curve_position,
+                &mut a.point,
+                &mut a.projection_offset,
+            ),
+        };
+        if let Some(projection) = surveyed.or(*point).and_then(|p| curve.project(p).ok()) {
+            *curve_position = projection.distance_along_curve;
+            *point = Some(projection.projected_coords);
+            if surveyed.is_some() {
+                *offset = Some(projection.offset);
+            }
+        }
+    }
+    lrm.measure_direction = measure_direction(&lrm.scale.anchors);
+}
+
+/// Whether the measures of the `anchors`, sorted by scale position, decrease along the [`Curve`].
+fn measure_direction(anchors: &[Anchor]) -> Direction {
+    match (anchors.first(), anchors.last()) {
+        (Some(first), Some(last)) if last.curve_position() < first.curve_position() => {
+            Direction::Reversed
+        }
+        _ => Direction::Default,
+    }
+}
+
+/// Quantizes the coordinates to multiples of `precision`: x and y of the first coordinate,
+/// then the difference with the previous coordinate. See `Segment.quantized_geometry` in the schema.
+///
+/// Returns `None` if a value does not fit in 32 bits, e.g. when the precision is too small for the coordinates.
+pub(crate) fn quantize(coords: &[Coord], precision: f64) -> Option<Vec<i32>> {
+    let mut previous = (0, 0);
+    let mut quantized = Vec::with_capacity(coords.len() * 2);
+    for coord in coords {
+        let position = (to_i32(coord.x / precision)?, to_i32(coord.y / precision)?);
+        quantized.push(position.0.checked_sub(previous.0)?);
+        quantized.push(position.1.checked_sub(previous.1)?);
+        previous = position;
+    }
+    Some(quantized)
+}
+
+fn to_i32(value: f64) -> Option<i32> {
+    let value = value.round();
+    (value >= i32::MIN as f64 && value <= i32::MAX as f64).then_some(value as i32)
+}
+
+/// The FlatBuffers payload of shared bytes, decompressed if needed.
+fn shared_payload(bytes: SharedBytes) -> Result<SharedBytes, LrsError> {
+    let payload: SharedBytes = match file_format::open((*bytes).as_ref())? {
+        // The payload starts the file, FlatBuffers ignore the footer
+        Cow::Borrowed(_) => bytes.clone(),
+        Cow::Owned(payload) => Arc::new(payload),
+    };
+    Ok(payload)
+}
+
+/// The root of the beginning of a payload, if it holds everything [`Lrs::load_root`] reads with the `filter`.
+///
+/// The whole payload is verified, except the [`Traversal`]s and [`Segment`]s that are not selected:
+/// they might not have been received yet.
+/// The fields of the root are those of the generated verifier, [`partial_root_fields`] checks that none is missing.
+fn partial_root<'a>(prefix: &'a [u8], filter: &LoadFilter) -> Option<lrs_generated::Lrs<'a>> {
+    use flatbuffers::{ForwardsUOffset, Vector, Verifiable};
+    type Tables<'b, T> = ForwardsUOffset<Vector<'b, ForwardsUOffset<T>>>;
+    type Root<'b> = lrs_generated::Lrs<'b>;
+
+    if prefix.len() < 8 || !lrs_generated::lrs_buffer_has_identifier(prefix) {
+        return None;
+    }
+    let options = flatbuffers::VerifierOptions::default();
+    let mut verifier = flatbuffers::Verifier::new(&options, prefix);
+    let root = verifier.get_uoffset(0).ok()? as usize;
+    let mut table = verifier.visit_table(root).ok()?;
+    // Only the offsets of the traversals and segments are checked, the tables they point to are checked when selected
+    let traversals = table.deref(Root::VT_TRAVERSALS).ok()?;
+    let segments = table.deref(Root::VT_SEGMENTS).ok()?;
+    for position in traversals.iter().chain(&segments) {
+        ForwardsUOffset::<Vector<u32>>::run_verifier(table.verifier(), *position).ok()?;
+    }
+    table
+        .visit_field::<Tables<lrs_generated::Property>>("properties", Root::VT_PROPERTIES, false)
+        .and_then(|t| t.visit_field::<Tables<lrs_generated::Node>>("nodes", Root::VT_NODES, false))
+        .and_then(|t| {
+            t.visit_field::<Tables<lrs_generated::Anchor>>("anchors", Root::VT_ANCHORS, false)
+        })
+        .and_then(|t| {
+            t.visit_field::<Tables<lrs_generated::LinearReferencingMethod>>(
+                "linear_referencing_methods",
+                Root::VT_LINEAR_REFERENCING_METHODS,
+                false,
+            )
+        })
+        .and_then(|t| {
+            t.visit_field::<lrs_generated::GeometryType>(
+                "geometry_type",
+                Root::VT_GEOMETRY_TYPE,
+                false,
+            )
+        })
+        .and_then(|t| {
+            t.visit_field::<ForwardsUOffset<Vector<u8>>>(
+                "lrm_spatial_index",
+                Root::VT_LRM_SPATIAL_INDEX,
+                false,
+            )
+        })
+        .and_then(|t| {
+            t.visit_field::<ForwardsUOffset<lrs_generated::BuildInfo>>(
+                "build_info",
+                Root::VT_BUILD_INFO,
+                false,
+            )
+        })
+        .and_then(|t| {
+            t.visit_field::<ForwardsUOffset<Vector<u8>>>(
+                "fragment_spatial_index",
+                Root::VT_FRAGMENT_SPATIAL_INDEX,
+                false,
+            )
+        })
+        .and_then(|t| {
+            t.visit_field::<ForwardsUOffset<Vector<u32>>>(
+                "fragment_traversals",
+                Root::VT_FRAGMENT_TRAVERSALS,
+                false,
+            )
+        })
+        .and_then(|t| {
+            t.visit_field::<Tables<lrs_generated::Connection>>(
+                "connections",
+                Root::VT_CONNECTIONS,
+                false,
+            )
+        })
+        .and_then(|t| {
+            t.visit_field::<Tables<lrs_generated::PointEvent>>(
+                "point_events",
+                Root::VT_POINT_EVENTS,
+                false,
+            )
         })
-    }
-
-    fn lrm_get_position(&self, measure: LrmMeasure) -> Result<LrmPosition, LrsError> {
-        let scale = self.get_lrm_by_handle(measure.lrm)?;
-        Ok(LrmPosition {
-            distance_from_start: scale.locate_point(&measure.measure)?,
-            lrm: measure.lrm,
+        .and_then(|t| {
+            t.visit_field::<Tables<lrs_generated::LinearEvent>>(
+                "linear_events",
+                Root::VT_LINEAR_EVENTS,
+                false,
+            )
         })
-    }
+        .and_then(|t| t.visit_field::<ForwardsUOffset<&str>>("crs", Root::VT_CRS, false))
+        .and_then(|t| t.visit_field::<u32>("schema_version", Root::VT_SCHEMA_VERSION, false))
+        .and_then(|t| {
+            t.visit_field::<f64>("coordinate_precision", Root::VT_COORDINATE_PRECISION, false)
+        })
+        .ok()?
+        .finish();
 
-    fn get_linestring(&self, traversal: TraversalHandle) -> Result<LineString, LrsError> {
-        self.get_curve(traversal).map(|c| c.as_linestring())
+    // SAFETY: the root table and everything but the traversals and segments were verified just before
+    let lrs = unsafe { lrs_generated::root_as_lrs_unchecked(prefix) };
+    let selected = match filter {
+        // Without a spatial index, the geometries of all the traversals are read to find the lrms in the box
+        LoadFilter::BoundingBox(bbox) if indexed_traversals_in_bbox(&lrs, bbox).is_none() => {
+            vec![true; lrs.traversals().unwrap_or_default().len()]
+        }
+        _ => traversals_of_lrms(&lrs, &selected_lrms(&lrs, Some(filter))),
+    };
+    let segment_count = lrs.segments().unwrap_or_default().len();
+    for (idx, _) in selected.iter().enumerate().filter(|(_, s)| **s) {
+        verify_item::<lrs_generated::Traversal>(&mut verifier, traversals?, idx)?;
+        let traversal = lrs.traversals().unwrap_or_default().get(idx);
+        for segment in traversal.segments() {
+            let segment = segment.segment_index() as usize;
+            // A missing segment is reported when loading
+            if segment < segment_count {
+                verify_item::<lrs_generated::Segment>(&mut verifier, segments?, segment)?;
+            }
+        }
     }
+    Some(lrs)
 }
 
-impl<CurveImpl: Curve> Lrs<CurveImpl> {
-    fn get_curve(&self, handle: TraversalHandle) -> Result<&CurveImpl, LrsError> {
-        self.traversals
-            .get(handle.0)
-            .map(|traversal| &traversal.curve)
-            .ok_or(LrsError::InvalidHandle)
-    }
-
-    fn get_lrm_by_handle(&self, handle: LrmHandle) -> Result<&LrmScale, LrsError> {
-        self.lrms
-            .get(handle.0)
-            .map(|lrm| &lrm.scale)
-            .ok_or(LrsError::InvalidHandle)
-    }
+/// Fails to compile when a field is added to the root table of the schema:
+/// [`partial_root`] lists the fields of the generated verifier one by one, and must verify the new field too.
+#[allow(dead_code)]
+fn partial_root_fields(args: lrs_generated::LrsArgs) {
+    let lrs_generated::LrsArgs {
+        properties: _,
+        segments: _,
+        nodes: _,
+        traversals: _,
+        anchors: _,
+        linear_referencing_methods: _,
+        geometry_type: _,
+        lrm_spatial_index: _,
+        build_info: _,
+        fragment_spatial_index: _,
+        fragment_traversals: _,
+        connections: _,
+        point_events: _,
+        linear_events: _,
+        crs: _,
+        schema_version: _,
+        coordinate_precision: _,
+    } = args;
+}
 
-    fn orthogonal_segment(
-        &self,
-        handle: TraversalHandle,
-        from_start: CurvePosition,
-    ) -> Result<geo::Line, LrsError> {
-        let from_curve = self.get_curve(handle)?;
-        let normal = from_curve.get_normal(from_start)?;
+/// Verifies the table at `index` of the vector whose offset is at `field`, once the offsets of the vector are verified.
+fn verify_item<T: flatbuffers::Verifiable>(
+    verifier: &mut flatbuffers::Verifier,
+    field: usize,
+    index: usize,
+) -> Option<()> {
+    let vector = field + verifier.get_uoffset(field).ok()? as usize;
+    let position = vector + flatbuffers::SIZE_UOFFSET * (index + 1);
+    <flatbuffers::ForwardsUOffset<T> as flatbuffers::Verifiable>::run_verifier(verifier, position)
+        .ok()
+}
 
-        let position = from_curve.resolve(from_start)?;
-        let start = geo::coord! {
-            x: position.x() + normal.0 * from_curve.max_extent(),
-            y: position.y() + normal.1 * from_curve.max_extent()
-        };
-        let end = geo::coord! {
-            x: position.x() - normal.0 * from_curve.max_extent(),
-            y: position.y() - normal.1 * from_curve.max_extent()
-        };
+/// The root of a payload that was verified when the [`Lrs`] was loaded from it.
+fn shared_root(source: &SharedBytes) -> lrs_generated::Lrs<'_> {
+    // SAFETY: the payload was verified by `Lrs::load_payload` and the shared bytes are not modified
+    unsafe { lrs_generated::root_as_lrs_unchecked((**source).as_ref()) }
+}
 
-        Ok(geo::Line::new(start, end))
+/// The coordinates of a traversal of a file, from its segments in order.
+fn traversal_coords(
+    lrs: &lrs_generated::Lrs,
+    traversal: &lrs_generated::Traversal,
+    transform: &dyn Fn(Coord) -> Coord,
+) -> Result<Vec<Coord>, LrsError> {
+    let source_segments = lrs.segments().unwrap_or_default();
+    let mut coords = vec![];
+    for segment in traversal.segments() {
+        let raw_segment = item(source_segments, segment.segment_index() as usize, "segment")?;
+        let mut geom: Vec<_> = segment_coords(&raw_segment, lrs.coordinate_precision())
+            .into_iter()
+            .map(transform)
+            .collect();
+        if segment.direction() == lrs_generated::Direction::Decreasing {
+            geom.reverse();
+        }
+        coords.append(&mut geom);
     }
+    Ok(coords)
 }
 
-/// A key-value `HashMap` to add metadata to the objects.
-pub type Properties = std::collections::HashMap<String, String>;
-
-#[macro_export]
-/// Build a properties map:
-/// `properties!("source" => "openstreetmap", "licence" => "ODbL")`.
-macro_rules! properties {
-    ($($k:expr => $v:expr),* $(,)?) => {{
-        core::convert::From::from([$(($k.to_owned(), $v.to_owned()),)*])
-    }};
+/// The coordinates of a segment of a file, decoded if they are quantized to multiples of `precision`.
+fn segment_coords(segment: &lrs_generated::Segment, precision: f64) -> Vec<Coord> {
+    match segment.quantized_geometry() {
+        Some(quantized) if precision > 0. => {
+            let deltas: Vec<_> = quantized.iter().collect();
+            let mut position = (0i64, 0i64);
+            deltas
+                .chunks_exact(2)
+                .map(|delta| {
+                    position = (position.0 + delta[0] as i64, position.1 + delta[1] as i64);
+                    coord! {x: position.0 as f64 * precision, y: position.1 as f64 * precision}
+                })
+                .collect()
+        }
+        _ => segment
+            .geometry()
+            .iter()
+            .map(|p| coord! {x: p.x(), y: p.y()})
+            .collect(),
+    }
 }
 
-/// Builds a [`Properties`] from a FlatBuffer vector of Property
-///
-/// Implementation note: as [`Properties`] is just an alias, we cannot `impl` for it (e.g. Into)
-pub fn from_fb(properties: Option<Vector<ForwardsUOffset<lrs_generated::Property>>>) -> Properties {
-    properties
-        .unwrap_or_default()
+/// Builds the FlatBuffer vector of Property of [`Properties`]
+pub(crate) fn to_fb<'fbb>(
+    fbb: &mut FlatBufferBuilder<'fbb>,
+    properties: &Properties,
+) -> WIPOffset<Vector<'fbb, ForwardsUOffset<lrs_generated::Property<'fbb>>>> {
+    let properties_vec: Vec<_> = properties
         .iter()
-        .map(|property| (property.key().to_string(), property.value().to_string()))
-        .collect()
+        .map(|(k, v)| {
+            let key = Some(fbb.create_string(k));
+            let value = Some(fbb.create_string(v));
+            lrs_generated::Property::create(fbb, &lrs_generated::PropertyArgs { key, value })
+        })
+        .collect();
+    fbb.create_vector(&properties_vec)
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use approx::assert_relative_eq;
-    use geo::line_string;
+    use geo::{Rect, coord, line_string, point, polygon};
 
+    use crate::builder::tests::{add_nodes, build_traversal, geographic};
+    use crate::builder::{AnchorOnLrm, Builder, SegmentOfTraversal};
     use crate::curves::PlanarLineStringCurve;
 
     use super::*;
 
-    fn lrs() -> Lrs<PlanarLineStringCurve> {
+    pub(crate) fn lrs() -> Lrs<PlanarLineStringCurve> {
         let traversal = Traversal {
-            curve: PlanarLineStringCurve::new(line_string![(x: 0., y:0.), (x: 200., y:0.)], 1.),
+            curve: PlanarLineStringCurve::new(line_string![(x: 0., y:0.), (x: 200., y:0.)], 1.)
+                .into(),
             id: "curve".to_owned(),
             lrms: vec![LrmHandle(0), LrmHandle(1)],
+            segments: vec![],
+            properties: properties!("line" => "1"),
         };
 
         let traversal2 = Traversal {
-            curve: PlanarLineStringCurve::new(line_string![(x: 0., y:-1.), (x: 200., y:-1.)], 1.),
+            curve: PlanarLineStringCurve::new(line_string![(x: 0., y:-1.), (x: 200., y:-1.)], 1.)
+                .into(),
             id: "curve".to_owned(),
             lrms: vec![LrmHandle(1)],
+            segments: vec![],
+            properties: properties!(),
         };
 
         let lrm = Lrm {
             scale: crate::lrm_scale::tests::scale(),
             traversal: TraversalHandle(0),
             properties: properties!("some key" => "some value"),
+            measure_direction: Direction::Default,
+            alternate_traversals: vec![],
+            validity: Validity::default(),
         };
 
         let mut lrm2 = Lrm {
             traversal: TraversalHandle(1),
             scale: crate::lrm_scale::tests::scale(),
             properties: properties!(),
+            measure_direction: Direction::Default,
+            alternate_traversals: vec![],
+            validity: Validity::default(),
         };
         "id2".clone_into(&mut lrm2.scale.id);
 
@@ -748,7 +3859,109 @@ mod tests {
             nodes: vec![],
             segments: vec![],
             rtree_data: None,
+            build_info: None,
+            fragment_index: None,
+            connections: vec![],
+            point_events: vec![],
+            linear_events: vec![],
+            crs: None,
+            coordinate_precision: 0.,
+        }
+    }
+
+    #[cfg(feature = "geozero")]
+    #[test]
+    fn geozero_datasource() {
+        use geozero::{
+            ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, PropertyProcessor,
+        };
+
+        #[derive(Default)]
+        struct Collect {
+            ids: Vec<String>,
+            coords: usize,
+        }
+        impl GeomProcessor for Collect {
+            fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> geozero::error::Result<()> {
+                self.coords += 1;
+                Ok(())
+            }
+        }
+        impl PropertyProcessor for Collect {
+            fn property(
+                &mut self,
+                _idx: usize,
+                name: &str,
+                value: &ColumnValue,
+            ) -> geozero::error::Result<bool> {
+                if let ("id", ColumnValue::String(id)) = (name, value) {
+                    self.ids.push(id.to_string());
+                }
+                Ok(false)
+            }
         }
+        impl FeatureProcessor for Collect {}
+
+        let mut collect = Collect::default();
+        lrs().process(&mut collect).unwrap();
+        assert_eq!(collect.ids, ["curve", "curve"]);
+        assert_eq!(collect.coords, 4);
+    }
+
+    #[cfg(feature = "geojson")]
+    #[test]
+    fn to_geojson() {
+        let features = lrs().to_geojson().features;
+        assert_eq!(features.len(), 6);
+
+        let lrm = &features[0];
+        assert_eq!(
+            lrm.geometry.as_ref().unwrap().value,
+            geojson::Value::LineString(vec![vec![0., 0.], vec![200., 0.]])
+        );
+        assert_eq!(lrm.property("id").unwrap(), "id");
+        assert_eq!(lrm.property("traversal").unwrap(), "curve");
+        assert_eq!(lrm.property("some key").unwrap(), "some value");
+
+        let anchor = &features[2];
+        assert_eq!(
+            anchor.geometry.as_ref().unwrap().value,
+            geojson::Value::Point(vec![100., 0.])
+        );
+        assert_eq!(anchor.property("lrm_id").unwrap(), "id");
+        assert_eq!(anchor.property("name").unwrap(), "b");
+        assert_eq!(anchor.property("measure").unwrap(), 10.);
+        assert_eq!(features[3].property("id").unwrap(), "id2");
+    }
+
+    #[test]
+    fn intersections() {
+        let mut lrs = lrs();
+        *lrs.traversals[1].curve = PlanarLineStringCurve::new(
+            line_string![(x: 100., y: -1.), (x: 110., y: 1.), (x: 120., y: -1.)],
+            1.,
+        );
+        let intersections = lrs.intersections(LrmHandle(0), LrmHandle(1)).unwrap();
+        assert_eq!(intersections.len(), 2);
+
+        assert_eq!(intersections[0].point, point! {x: 105., y: 0.});
+        assert_eq!(intersections[0].measure_a.anchor_name, "b");
+        assert_relative_eq!(intersections[0].measure_a.scale_offset, 0.5);
+        assert_eq!(intersections[0].measure_b.anchor_name, "a");
+        assert_relative_eq!(intersections[0].measure_b.scale_offset, 5.);
+
+        assert_eq!(intersections[1].point, point! {x: 115., y: 0.});
+        assert_relative_eq!(intersections[1].measure_a.scale_offset, 1.5);
+        assert_eq!(intersections[1].measure_b.anchor_name, "b");
+        assert_relative_eq!(intersections[1].measure_b.scale_offset, 5.);
+
+        // Parallel lrms never cross
+        let lrs = super::tests::lrs();
+        assert!(
+            lrs.intersections(LrmHandle(0), LrmHandle(1))
+                .unwrap()
+                .is_empty()
+        );
     }
 
     #[test]
@@ -768,6 +3981,24 @@ mod tests {
         assert!(lrs().get_traversal("Achtung, die Kurve!").is_none());
     }
 
+    #[test]
+    fn handles() {
+        let lrs = lrs();
+        let ids: Vec<_> = lrs
+            .lrm_handles()
+            .map(|handle| lrs.lrm(handle).unwrap().scale.id.as_str())
+            .collect();
+        assert_eq!(ids, ["id", "id2"]);
+        assert_eq!(
+            lrs.traversal_handles().collect::<Vec<_>>(),
+            [TraversalHandle(0), TraversalHandle(1)]
+        );
+
+        assert_eq!(lrs.lrm(LrmHandle(2)).err(), Some(LrsError::InvalidHandle));
+        assert!(lrs.traversal(TraversalHandle(2)).is_err());
+        assert!(lrs.node(NodeHandle(0)).is_err());
+    }
+
     #[test]
     fn lookup_single_lrm() {
         let lrs = lrs();
@@ -776,6 +4007,11 @@ mod tests {
             .unwrap();
         assert_eq!(result.orthogonal_offset, 0.5);
         assert_eq!(result.measure.measure.scale_offset, 5.);
+
+        assert_eq!(
+            lrs.lookup(point! {x: 50., y:0.5}, LrmHandle(2)).err(),
+            Some(LrsError::InvalidHandle)
+        );
     }
 
     #[test]
@@ -801,6 +4037,291 @@ mod tests {
         assert_eq!(result[1].measure.measure.scale_offset, 5.);
     }
 
+    #[test]
+    fn lookup_all() {
+        let lrs = lrs();
+        let result = lrs.lookup_all(point! {x: 50., y:0.5}, 1.);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].measure.lrm, LrmHandle(0));
+        assert_eq!(result[0].orthogonal_offset, 0.5);
+
+        // Farther than the extent of the spatial index
+        let result = lrs.lookup_all(point! {x: 50., y:3.}, 10.);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].measure.lrm, LrmHandle(0));
+        assert_eq!(result[0].orthogonal_offset, 3.);
+        assert_eq!(result[1].measure.lrm, LrmHandle(1));
+        assert_eq!(result[1].orthogonal_offset, 4.);
+
+        assert!(lrs.lookup_all(point! {x: 50., y:3.}, 1.).is_empty());
+    }
+
+    #[test]
+    fn segment_geometry_by_measures() {
+        // The scale goes from a@0 to b@10 on the first half of the curve (x from 0 to 100)
+        let lrs = lrs();
+        let line = line_string![(x: 20., y: 5.), (x: 120., y: 5.), (x: 130., y: -5.)];
+        let breakpoints = [LrmScaleMeasure::new("a", 5.), LrmScaleMeasure::new("b", 0.)];
+        let parts = lrs
+            .segment_geometry_by_measures(LrmHandle(0), &line, &breakpoints)
+            .unwrap();
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(
+            parts[0].geometry,
+            line_string![(x: 20., y: 5.), (x: 50., y: 5.)]
+        );
+        assert_eq!(parts[0].range.begin.anchor_name, "a");
+        assert_relative_eq!(parts[0].range.begin.scale_offset, 2.);
+        assert_relative_eq!(parts[0].range.end.scale_offset, 5.);
+        assert_eq!(
+            parts[1].geometry,
+            line_string![(x: 50., y: 5.), (x: 100., y: 5.)]
+        );
+        assert_eq!(
+            parts[2].geometry,
+            line_string![(x: 100., y: 5.), (x: 120., y: 5.), (x: 130., y: -5.)]
+        );
+        assert_eq!(parts[2].range.begin.anchor_name, "b");
+        assert_relative_eq!(parts[2].range.end.scale_offset, 3.);
+
+        assert!(
+            lrs.segment_geometry_by_measures(LrmHandle(0), &line_string![(x: 0., y: 0.)], &[])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn geometry_type() {
+        let mut lrs = lrs();
+        lrs.crs = Some("EPSG:2154".to_owned());
+        let bytes = lrs.to_bytes();
+        assert_eq!(
+            GeometryType::from_bytes(&bytes),
+            Ok(GeometryType::Schematic)
+        );
+        assert_eq!(
+            Lrs::<SphericalLineStringCurve>::from_bytes(&bytes).err(),
+            Some(LrsError::GeometryTypeMismatch(
+                GeometryType::Schematic,
+                GeometryType::Geographic
+            ))
+        );
+
+        match AnyLrs::from_bytes(&bytes, None).unwrap() {
+            AnyLrs::Schematic(lrs) => assert_eq!(lrs.crs.as_deref(), Some("EPSG:2154")),
+            AnyLrs::Geographic(_) => panic!("the file is schematic"),
+        }
+        let forced = AnyLrs::from_bytes(&bytes, Some(GeometryType::Geographic)).unwrap();
+        assert_eq!(forced.geometry_type(), GeometryType::Geographic);
+    }
+
+    fn nodes(ids: &[&str]) -> Vec<Node> {
+        ids.iter()
+            .map(|id| Node {
+                id: id.to_string(),
+                geometry: None,
+                properties: properties!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn anchor_sources() {
+        let mut lrs = lrs();
+        lrs.nodes = nodes(&["n0", "n1", "n2"]);
+        let anchors = vec![
+            Anchor::new_named("a", 0., 0., None, properties!())
+                .with_source("post-a", Some(NodeHandle(1))),
+            Anchor::new_unnamed(5., 0.5, None, properties!())
+                .with_source("post-u", Some(NodeHandle(0))),
+            Anchor::new_named("b", 10., 1., None, properties!()),
+        ];
+        lrs.set_anchors(LrmHandle(0), anchors).unwrap();
+
+        let lrs = Lrs::<PlanarLineStringCurve>::from_bytes(&lrs.to_bytes()).unwrap();
+        let anchors = &lrs.lrms[0].scale.anchors;
+        assert_eq!(
+            (anchors[0].id(), anchors[0].node()),
+            ("post-a", Some(NodeHandle(1)))
+        );
+        assert_eq!(
+            (anchors[1].id(), anchors[1].node()),
+            ("post-u", Some(NodeHandle(0)))
+        );
+        assert_eq!((anchors[2].id(), anchors[2].node()), ("b", None));
+    }
+
+    #[test]
+    fn filtered_nodes_and_connections() {
+        let mut lrs = lrs();
+        lrs.nodes = nodes(&["n0", "n1", "n2"]);
+        let anchors = vec![
+            Anchor::new_named("a", 0., 0., None, properties!())
+                .with_source("post-a", Some(NodeHandle(2))),
+            Anchor::new_named("b", 10., 1., None, properties!()),
+        ];
+        lrs.set_anchors(LrmHandle(1), anchors).unwrap();
+        lrs.connections = vec![Connection {
+            node: NodeHandle(1),
+            from: (TraversalHandle(0), TraversalEnd::End),
+            to: (TraversalHandle(1), TraversalEnd::Start),
+            bidirectional: false,
+        }];
+
+        let filter = LoadFilter::LrmIds(vec!["id2".to_owned()]);
+        let lrs =
+            Lrs::<PlanarLineStringCurve>::from_bytes_filtered(&lrs.to_bytes(), &filter).unwrap();
+        assert_eq!(lrs.nodes.len(), 1);
+        assert_eq!(lrs.nodes[0].id, "n2");
+        assert_eq!(lrs.lrms[0].scale.anchors[0].node(), Some(NodeHandle(0)));
+        // The first traversal is not loaded
+        assert!(lrs.connections.is_empty());
+    }
+
+    // The test lrs with a segment for each traversal, so that the curves are kept when it is serialized
+    pub(crate) fn lrs_with_segments() -> Lrs<PlanarLineStringCurve> {
+        let mut lrs = lrs();
+        lrs.nodes = nodes(&["n0", "n1"]);
+        for (idx, traversal) in lrs.traversals.iter_mut().enumerate() {
+            lrs.segments.push(Segment {
+                id: format!("s{idx}"),
+                properties: properties!(),
+                start_node: NodeHandle(0),
+                end_node: NodeHandle(1),
+                geometry: traversal.curve.as_linestring().into(),
+            });
+            traversal.segments = vec![SegmentOfTraversal {
+                segment_index: idx,
+                reversed: false,
+            }];
+        }
+        lrs
+    }
+
+    #[test]
+    fn partial_bytes() {
+        let lrs = lrs_with_segments();
+        let data = lrs.to_bytes();
+        let filter = LoadFilter::LrmIds(vec!["id".to_owned()]);
+        let partial = |len: usize| {
+            Lrs::<PlanarLineStringCurve>::from_partial_bytes(&data[..len], &filter).unwrap()
+        };
+
+        // The lrm on the first traversal is available before the segment of the second one is received
+        let len = (0..=data.len())
+            .find(|&len| partial(len).is_some())
+            .unwrap();
+        assert!(len < data.len() - file_format::FOOTER_LEN);
+        let loaded = partial(len).unwrap();
+        assert_eq!(loaded.lrms.len(), 1);
+        assert_eq!(loaded.traversals.len(), 1);
+        assert_eq!(loaded.segments.len(), 1);
+        assert_eq!(
+            loaded.traversals[0].curve.as_linestring(),
+            lrs.traversals[0].curve.as_linestring()
+        );
+        assert!((len..=data.len()).all(|len| partial(len).is_some()));
+
+        // A compressed file is only read once complete
+        #[cfg(feature = "compression")]
+        assert!(
+            Lrs::<PlanarLineStringCurve>::from_partial_bytes(&lrs.to_compressed_bytes(), &filter)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn oriented_linestring() {
+        let mut lrs = lrs();
+        let linestring = lrs.get_linestring(TraversalHandle(0)).unwrap();
+        assert_eq!(lrs.oriented_linestring(LrmHandle(0)).unwrap(), linestring);
+
+        // The measures decrease along the traversal: a is at the end of the curve
+        let anchors = vec![
+            Anchor::new_named("a", 0., 1., None, properties!()),
+            Anchor::new_named("b", 10., 0.5, None, properties!()),
+        ];
+        lrs.set_anchors(LrmHandle(0), anchors).unwrap();
+        assert!(matches!(lrs.lrms[0].measure_direction, Direction::Reversed));
+        let oriented = lrs.oriented_linestring(LrmHandle(0)).unwrap();
+        assert_eq!(oriented.0.first(), linestring.0.last());
+
+        let lrs = Lrs::<PlanarLineStringCurve>::from_bytes(&lrs.to_bytes()).unwrap();
+        assert!(matches!(lrs.lrms[0].measure_direction, Direction::Reversed));
+        assert!(matches!(lrs.lrms[1].measure_direction, Direction::Default));
+    }
+
+    #[test]
+    fn check_date() {
+        for date in ["2024-01-05", "2024-02-29", "2000-02-29", "1999-12-31"] {
+            assert!(super::check_date(date).is_ok(), "{date}");
+        }
+        for date in [
+            "2024-1-5",
+            "2024-01-05T00:00",
+            "05/01/2024",
+            "2023-02-29",
+            "1900-02-29",
+            "2024-04-31",
+            "2024-13-01",
+            "2024-00-10",
+            "+024-01-05",
+            "",
+        ] {
+            assert_eq!(
+                super::check_date(date),
+                Err(LrsError::InvalidDate(date.to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn lrm_bounds() {
+        let lrs = lrs();
+        let bounds = lrs.lrm_bounds(LrmHandle(0)).unwrap();
+        assert_eq!(bounds.min_measure.anchor_name, "a");
+        assert_eq!(bounds.min_measure.scale_offset, 0.);
+        assert_eq!(bounds.max_measure.anchor_name, "b");
+        assert_eq!(bounds.max_measure.scale_offset, 10.);
+        assert_eq!(bounds.length, 200.);
+
+        assert!(lrs.lrm_bounds(LrmHandle(2)).is_err());
+    }
+
+    #[test]
+    fn traversals_in() {
+        let lrs = lrs();
+        // Around the first traversal only
+        let area = Rect::new(coord! {x: 50., y: 0.5}, coord! {x: 60., y: 10.}).to_polygon();
+        assert_eq!(lrs.traversals_in(&area, true), []);
+        assert_eq!(lrs.traversals_in(&area, false), [TraversalHandle(0)]);
+        assert_eq!(lrs.lrms_in(&area, false), [LrmHandle(0), LrmHandle(1)]);
+
+        let area = Rect::new(coord! {x: 50., y: -0.5}, coord! {x: 60., y: 10.}).to_polygon();
+        assert_eq!(lrs.traversals_in(&area, true), [TraversalHandle(0)]);
+
+        let area = polygon![(x: 50., y: -5.), (x: 60., y: -5.), (x: 60., y: -10.)];
+        assert!(lrs.lrms_in(&area, false).is_empty());
+    }
+
+    #[test]
+    fn lrm_ranges_in() {
+        let lrs = lrs();
+        let area = Rect::new(coord! {x: 40., y: -0.5}, coord! {x: 100., y: 0.5}).to_polygon();
+        let ranges = lrs.lrm_ranges_in(LrmHandle(0), &area).unwrap();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].begin.anchor_name, "a");
+        assert_eq!(ranges[0].begin.scale_offset, 4.);
+        assert_eq!(ranges[0].end.anchor_name, "b");
+        assert_eq!(ranges[0].end.scale_offset, 0.);
+
+        let area = Rect::new(coord! {x: 50., y: 0.5}, coord! {x: 100., y: 10.}).to_polygon();
+        assert!(lrs.lrm_ranges_in(LrmHandle(0), &area).unwrap().is_empty());
+        assert!(lrs.lrm_ranges_in(LrmHandle(2), &area).is_err());
+    }
+
     #[test]
     fn locate_traversal() {
         let result = lrs()
@@ -823,19 +4344,22 @@ mod tests {
     #[test]
     fn get_lrm_traversal() {
         let result = lrs().get_lrm_traversal(LrmHandle(0));
-        assert_eq!(TraversalHandle(0), result);
+        assert_eq!(Ok(TraversalHandle(0)), result);
         let result = lrs().get_lrm_traversal(LrmHandle(1));
-        assert_eq!(TraversalHandle(1), result);
+        assert_eq!(Ok(TraversalHandle(1)), result);
+        let result = lrs().get_lrm_traversal(LrmHandle(2));
+        assert_eq!(Err(LrsError::InvalidHandle), result);
     }
 
     #[test]
     fn get_traversal_lrms() {
         let lrs = lrs();
-        let result = lrs.get_traversal_lrms(TraversalHandle(0));
+        let result = lrs.get_traversal_lrms(TraversalHandle(0)).unwrap();
         assert_eq!(result, &[LrmHandle(0), LrmHandle(1)]);
 
-        let result = lrs.get_traversal_lrms(TraversalHandle(1));
+        let result = lrs.get_traversal_lrms(TraversalHandle(1)).unwrap();
         assert_eq!(result, &[LrmHandle(1)]);
+        assert!(lrs.get_traversal_lrms(TraversalHandle(2)).is_err());
     }
 
     #[test]
@@ -931,4 +4455,564 @@ mod tests {
 
         assert_eq!(result.distance_from_start, 0.25);
     }
+
+    #[test]
+    fn resolve_with_offset() {
+        let mut lrs = lrs();
+        let measure = LrmScaleMeasure::new("a", 5.);
+        assert_eq!(
+            lrs.resolve_with_offset(LrmHandle(0), &measure, 3.5),
+            Ok(point! {x: 50., y: 3.5})
+        );
+        assert_eq!(
+            lrs.resolve_with_offset(LrmHandle(0), &measure, -3.5),
+            Ok(point! {x: 50., y: -3.5})
+        );
+
+        // The left side follows the measures, not the geometry
+        lrs.lrms[0].measure_direction = Direction::Reversed;
+        assert_eq!(
+            lrs.resolve_with_offset(LrmHandle(0), &measure, 3.5),
+            Ok(point! {x: 50., y: -3.5})
+        );
+        assert_eq!(
+            lrs.resolve_with_offset(LrmHandle(2), &measure, 3.5),
+            Err(LrsError::InvalidHandle)
+        );
+    }
+
+    #[test]
+    fn heading() {
+        let mut lrs = lrs();
+        let measure = LrmScaleMeasure::new("a", 5.);
+        assert_eq!(lrs.heading(LrmHandle(0), &measure), Ok(90.));
+        lrs.lrms[0].measure_direction = Direction::Reversed;
+        assert_eq!(lrs.heading(LrmHandle(0), &measure), Ok(270.));
+    }
+
+    #[test]
+    fn convert_measure() {
+        let mut lrs = lrs();
+        // Same traversal, but the scale is twice as long: a(0)----b(20)
+        lrs.lrms[1] = Lrm {
+            scale: LrmScale {
+                id: "id2".to_owned(),
+                anchors: vec![
+                    Anchor::new_named("c", 0., 0., None, properties!()),
+                    Anchor::new_named("d", 20., 0.5, None, properties!()),
+                ],
+            },
+            traversal: TraversalHandle(0),
+            properties: properties!(),
+            measure_direction: Direction::Default,
+            alternate_traversals: vec![],
+            validity: Validity::default(),
+        };
+
+        let result = lrs
+            .convert_measure(LrmHandle(0), LrmHandle(1), &LrmScaleMeasure::new("a", 5.))
+            .unwrap();
+        assert_eq!(result.lrm, LrmHandle(1));
+        assert_eq!(result.measure.anchor_name, "c");
+        assert_eq!(result.measure.scale_offset, 10.);
+
+        lrs.lrms[1].traversal = TraversalHandle(1);
+        assert_eq!(
+            lrs.convert_measure(LrmHandle(0), LrmHandle(1), &LrmScaleMeasure::new("a", 5.))
+                .err(),
+            Some(LrsError::NoSharedTraversal)
+        );
+        assert_eq!(
+            lrs.convert_measure(LrmHandle(0), LrmHandle(5), &LrmScaleMeasure::new("a", 5.))
+                .err(),
+            Some(LrsError::InvalidHandle)
+        );
+    }
+
+    // A file whose items reference items that do not exist
+    fn file_with_indices(
+        segment_index: u64,
+        anchor_index: u64,
+        anchor_node: Option<u32>,
+        fragment_traversal: u32,
+    ) -> Vec<u8> {
+        let mut fbb = FlatBufferBuilder::new();
+        let args = lrs_generated::NodeArgs {
+            id: Some(fbb.create_string("node")),
+            ..Default::default()
+        };
+        let node = lrs_generated::Node::create(&mut fbb, &args);
+        let points = [
+            lrs_generated::Point::new(0., 0.),
+            lrs_generated::Point::new(1., 0.),
+        ];
+        // Both extremities of the segment are the node 0
+        let args = lrs_generated::SegmentArgs {
+            id: Some(fbb.create_string("segment")),
+            geometry: Some(fbb.create_vector(&points)),
+            ..Default::default()
+        };
+        let segment = lrs_generated::Segment::create(&mut fbb, &args);
+        let segments_of_traversal = [lrs_generated::SegmentOfTraversal::new(
+            segment_index,
+            lrs_generated::Direction::Increasing,
+        )];
+        let args = lrs_generated::TraversalArgs {
+            id: Some(fbb.create_string("traversal")),
+            segments: Some(fbb.create_vector(&segments_of_traversal)),
+            ..Default::default()
+        };
+        let traversal = lrs_generated::Traversal::create(&mut fbb, &args);
+        let args = lrs_generated::AnchorArgs {
+            id: Some(fbb.create_string("a")),
+            name: Some(fbb.create_string("a")),
+            node: anchor_node,
+            ..Default::default()
+        };
+        let anchor = lrs_generated::Anchor::create(&mut fbb, &args);
+        let args = lrs_generated::LinearReferencingMethodArgs {
+            id: Some(fbb.create_string("lrm")),
+            anchor_indices: Some(fbb.create_vector(&[anchor_index])),
+            distances: Some(fbb.create_vector(&[0.])),
+            ..Default::default()
+        };
+        let lrm = lrs_generated::LinearReferencingMethod::create(&mut fbb, &args);
+        let args = lrs_generated::LrsArgs {
+            nodes: Some(fbb.create_vector(&[node])),
+            segments: Some(fbb.create_vector(&[segment])),
+            traversals: Some(fbb.create_vector(&[traversal])),
+            anchors: Some(fbb.create_vector(&[anchor])),
+            linear_referencing_methods: Some(fbb.create_vector(&[lrm])),
+            geometry_type: lrs_generated::GeometryType::Schematic,
+            fragment_spatial_index: Some(fbb.create_vector(&[0u8])),
+            fragment_traversals: Some(fbb.create_vector(&[fragment_traversal])),
+            ..Default::default()
+        };
+        let lrs = lrs_generated::Lrs::create(&mut fbb, &args);
+        fbb.finish(lrs, None);
+        fbb.finished_data().to_vec()
+    }
+
+    #[test]
+    fn malformed_file() {
+        // The anchor has no geometry: it is placed proportionally instead of being projected
+        let lrs =
+            Lrs::<PlanarLineStringCurve>::from_bytes(&file_with_indices(0, 0, None, 0)).unwrap();
+        assert_eq!(lrs.lrms[0].scale.anchors[0].curve_position(), 0.);
+
+        for (segment_index, anchor_index, anchor_node, fragment_traversal) in [
+            (3, 0, None, 0),
+            (0, 7, None, 0),
+            (0, 0, Some(1), 0),
+            (0, 0, None, 2),
+        ] {
+            assert!(matches!(
+                Lrs::<PlanarLineStringCurve>::from_bytes(&file_with_indices(
+                    segment_index,
+                    anchor_index,
+                    anchor_node,
+                    fragment_traversal
+                )),
+                Err(LrsError::IncompleteArchive(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn edit_and_serialize() {
+        let mut b = Builder::new();
+        let traversal = build_traversal(&mut b);
+        let anchor_index = b.add_anchor("a", Some("a"), coord! {x: 0., y: 0.}, properties!());
+        b.add_lrm(
+            "lrm",
+            traversal,
+            &[AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm: 0.,
+            }],
+            properties!(),
+        );
+        b.add_point_event(
+            "signal",
+            0,
+            &LrmScaleMeasure::new("a", 100.),
+            HashMap::new(),
+        );
+        let mut lrs = Lrs::<SphericalLineStringCurve>::from_bytes(
+            b.build_data(properties!("source" => "test")),
+        )
+        .unwrap();
+
+        lrs.rename_lrm(LrmHandle(0), "renamed").unwrap();
+        let anchors = vec![
+            Anchor::new_named("b", 100., 1., Some(point! {x: 2., y: 0.}), properties!()),
+            Anchor::new_named("a", 0., 0., Some(point! {x: 0., y: 0.}), properties!()),
+        ];
+        let other = lrs
+            .add_lrm("other", TraversalHandle(0), anchors.clone(), properties!())
+            .unwrap();
+        assert_eq!(other, LrmHandle(1));
+        assert_eq!(lrs.lrms[1].scale.anchors[0].scale_position(), 0.);
+        assert_eq!(
+            lrs.add_lrm("invalid", TraversalHandle(1), vec![], properties!())
+                .err(),
+            Some(LrsError::InvalidHandle)
+        );
+
+        // The track is moved north
+        let geometry = LineString::from(vec![coord! {x: 0., y: 1.}, coord! {x: 2., y: 1.}]);
+        lrs.replace_traversal_geometry(TraversalHandle(0), geometry)
+            .unwrap();
+        assert_eq!(
+            lrs.lrms[1].scale.anchors[1].point(),
+            Some(point! {x: 2., y: 1.})
+        );
+
+        let edited = Lrs::<SphericalLineStringCurve>::from_bytes(&lrs.to_bytes()).unwrap();
+        assert_eq!(edited.lrm_len(), 2);
+        assert_eq!(edited.lrms[0].scale.id, "renamed");
+        assert_eq!(edited.properties, properties!("source" => "test"));
+        assert_eq!(edited.point_events.len(), 1);
+        assert_eq!(edited.traversals[0].lrms, [LrmHandle(0), LrmHandle(1)]);
+        // The old segments of the traversal are replaced by the new one
+        assert_eq!(edited.segments.len(), 1);
+        assert_eq!(edited.segments[0].id, "traversal");
+        assert_eq!(edited.segments[0].start_node, NodeHandle(0));
+        assert_eq!(edited.segments[0].end_node, NodeHandle(2));
+        assert_eq!(edited.traversals[0].segments[0].segment_index, 0);
+        assert_eq!(
+            edited.get_linestring(TraversalHandle(0)).unwrap(),
+            lrs.get_linestring(TraversalHandle(0)).unwrap()
+        );
+        assert_eq!(
+            edited.traversals_containing(point! {x: 1., y: 1.}),
+            [TraversalHandle(0)]
+        );
+        let measure = LrmScaleMeasure::new("a", 50.);
+        assert_relative_eq!(
+            edited.lrms[1].scale.locate_point(&measure).unwrap(),
+            lrs.lrms[1].scale.locate_point(&measure).unwrap()
+        );
+
+        // Removing an LRM shifts the following handles and drops its events
+        lrs.remove_lrm(LrmHandle(0)).unwrap();
+        assert_eq!(lrs.lrms[0].scale.id, "other");
+        assert_eq!(lrs.traversals[0].lrms, [LrmHandle(0)]);
+        assert!(lrs.point_events.is_empty());
+        lrs.set_anchors(LrmHandle(0), anchors[1..].to_vec())
+            .unwrap();
+        assert_eq!(lrs.lrms[0].scale.anchors.len(), 1);
+    }
+
+    #[test]
+    fn loop_traversal() {
+        let mut b = Builder::new();
+        let ring = [
+            coord! {x: 0., y: 0.},
+            coord! {x: 0.01, y: 0.},
+            coord! {x: 0.01, y: 0.01},
+            coord! {x: 0., y: 0.01},
+            coord! {x: 0., y: 0.},
+        ];
+        let node = b.add_node("depot", ring[0], properties!());
+        let segment_index = b.add_segment("ring", &ring, node, node);
+        let traversal = b.add_traversal(
+            "ring",
+            &[SegmentOfTraversal {
+                segment_index,
+                reversed: false,
+            }],
+        );
+        let start = b.add_anchor("0", Some("0"), ring[0], properties!());
+        let corner = b.add_anchor("1", Some("1"), ring[1], properties!());
+        let anchors = [(start, 0.), (corner, 1000.)].map(|(anchor_index, distance)| AnchorOnLrm {
+            anchor_index,
+            distance_along_lrm: distance,
+        });
+        b.add_lrm("ring", traversal, &anchors, properties!());
+        let lrs = b.build_lrs(properties!()).unwrap();
+        assert!(lrs.is_loop(0).unwrap());
+
+        // One lap is about 4000 scale units: the measures wrap around the origin
+        let period = geographic(&lrs).lrms[0].scale.loop_period().unwrap();
+        assert_relative_eq!(period, 4000., epsilon = 20.);
+        let resolve = |offset| lrs.resolve(0, &LrmScaleMeasure::new("0", offset)).unwrap();
+        assert_relative_eq!(resolve(period + 250.), resolve(250.), epsilon = 1e-9);
+        assert_relative_eq!(resolve(-500.), resolve(period - 500.), epsilon = 1e-9);
+        assert_relative_eq!(resolve(-500.).x(), 0., epsilon = 1e-9);
+
+        // The range goes past the origin of the loop
+        let range = lrs
+            .resolve_range(
+                0,
+                &LrmScaleMeasure::new("0", -500.),
+                &LrmScaleMeasure::new("0", 500.),
+                None,
+            )
+            .unwrap();
+        assert_eq!(range.len(), 3);
+        assert_relative_eq!(range[0].y, 0.005, epsilon = 1e-4);
+        assert_relative_eq!(range[1], ring[0], epsilon = 1e-9);
+        assert_relative_eq!(range[2].x, 0.005, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn from_bytes_filtered() {
+        let mut b = Builder::new();
+        for i in 0..3 {
+            let id = format!("line {i}");
+            let x = 10. * i as f64;
+            let geometry = [coord! {x: x, y: 0.}, coord! {x: x + 1., y: 0.}];
+            let start = b.add_node(&format!("{id} start"), geometry[0], properties!());
+            let end = b.add_node(&format!("{id} end"), geometry[1], properties!());
+            let segment_index = b.add_segment(&id, &geometry, start, end);
+            let traversal = b.add_traversal(
+                &id,
+                &[SegmentOfTraversal {
+                    segment_index,
+                    reversed: false,
+                }],
+            );
+            let anchor_index = b.add_anchor(&id, Some("0"), geometry[0], properties!());
+            let anchors = [AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm: 0.,
+            }];
+            b.add_lrm(&id, traversal, &anchors, properties!());
+            b.add_point_event(&id, i, &LrmScaleMeasure::new("0", 0.), HashMap::new());
+        }
+        let data = b.build_data(properties!()).to_vec();
+
+        let filter = LoadFilter::LrmIds(vec!["line 2".to_owned(), "line 0".to_owned()]);
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes_filtered(&data, &filter).unwrap();
+        assert_eq!(lrs.lrm_len(), 2);
+        assert_eq!(lrs.lrms[1].scale.id, "line 2");
+        assert_eq!(lrs.lrms[1].traversal, TraversalHandle(1));
+        assert_eq!(lrs.traversals[1].id, "line 2");
+        assert_eq!(lrs.traversals[1].lrms, [LrmHandle(1)]);
+        assert_eq!(lrs.segments.len(), 2);
+        assert_eq!(lrs.traversals[1].segments[0].segment_index, 1);
+        assert_eq!(lrs.nodes.len(), 4);
+        assert_eq!(lrs.segments[1].start_node, NodeHandle(2));
+        assert_eq!(lrs.nodes[2].id, "line 2 start");
+        assert_eq!(lrs.point_events.len(), 2);
+        assert_eq!(lrs.point_events[1].id, "line 2");
+        assert_eq!(lrs.point_events[1].lrm, LrmHandle(1));
+
+        let bbox = geo::Rect::new(coord! {x: 9., y: -1.}, coord! {x: 12., y: 1.});
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes_filtered(
+            &data,
+            &LoadFilter::BoundingBox(bbox),
+        )
+        .unwrap();
+        assert_eq!(lrs.lrm_len(), 1);
+        assert_eq!(lrs.lrms[0].scale.id, "line 1");
+        assert!(lrs.get_curve(TraversalHandle(0)).is_ok());
+    }
+
+    #[test]
+    fn merge() {
+        let region = |items: &[(&str, f64, &str)]| {
+            let mut b = Builder::new();
+            let start = b.add_node("start", coord! {x: 0., y: 0.}, properties!());
+            for (lrm_index, &(traversal_id, y, lrm_id)) in items.iter().enumerate() {
+                let end = b.add_node(traversal_id, coord! {x: 1., y: y}, properties!());
+                let geometry = [coord! {x: 0., y: 0.}, coord! {x: 1., y: y}];
+                let segment_index = b.add_segment(traversal_id, &geometry, start, end);
+                let traversal = b.add_traversal(
+                    traversal_id,
+                    &[SegmentOfTraversal {
+                        segment_index,
+                        reversed: false,
+                    }],
+                );
+                let anchor_index = b.add_anchor(lrm_id, Some("0"), geometry[0], properties!());
+                let anchors = [AnchorOnLrm {
+                    anchor_index,
+                    distance_along_lrm: 0.,
+                }];
+                b.add_lrm(lrm_id, traversal, &anchors, properties!());
+                b.add_point_event(
+                    lrm_id,
+                    lrm_index,
+                    &LrmScaleMeasure::new("0", 0.),
+                    HashMap::new(),
+                );
+            }
+            Lrs::<SphericalLineStringCurve>::from_bytes(b.build_data(properties!())).unwrap()
+        };
+        let west = || region(&[("a", 0., "line a"), ("b", 1., "line b")]);
+        // `shared` has the same geometry as `a`, `b` is an other track with the same id
+        let east = || region(&[("shared", 0., "line shared"), ("b", -1., "line b")]);
+
+        let mut lrs = west();
+        assert_eq!(
+            lrs.merge(east(), ConflictPolicy::Fail),
+            Err(LrsError::DuplicateId("b".to_owned()))
+        );
+        assert_eq!(lrs.lrm_len(), 2);
+
+        let mut invalid = east();
+        invalid.segments[0].start_node = NodeHandle(9);
+        assert_eq!(
+            lrs.merge(invalid, ConflictPolicy::KeepExisting),
+            Err(LrsError::InvalidHandle)
+        );
+        assert_eq!(lrs.nodes.len(), 3);
+
+        // The anchor of `line shared` is bound to the node `shared`
+        let mut bound = east();
+        let anchor = bound.lrms[0].scale.anchors[0].clone();
+        bound.lrms[0].scale.anchors[0] = anchor.with_source("post", Some(NodeHandle(1)));
+        lrs.merge(bound, ConflictPolicy::KeepExisting).unwrap();
+        assert_eq!(lrs.traversals.len(), 2);
+        assert_eq!(lrs.nodes.len(), 4);
+        assert_eq!(lrs.nodes[3].id, "shared");
+        assert_eq!(lrs.lrms[2].scale.anchors[0].node(), Some(NodeHandle(3)));
+        assert_eq!(lrs.lrm_len(), 3);
+        assert_eq!(lrs.lrms[2].scale.id, "line shared");
+        assert_eq!(lrs.lrms[2].traversal, TraversalHandle(0));
+        assert_eq!(lrs.traversals[0].lrms, [LrmHandle(0), LrmHandle(2)]);
+        assert_eq!(lrs.point_events.len(), 3);
+        assert_eq!(lrs.point_events[2].lrm, LrmHandle(2));
+        assert_eq!(
+            lrs.traversals_containing(point! {x: 0.5, y: -0.5}),
+            Vec::<TraversalHandle>::new()
+        );
+
+        // An lrm on a traversal that `other` does not have is refused before anything changes
+        let mut invalid = east();
+        invalid.lrms[0].alternate_traversals = vec![TraversalHandle(9)];
+        assert_eq!(
+            lrs.merge(invalid, ConflictPolicy::KeepIncoming),
+            Err(LrsError::InvalidHandle)
+        );
+        assert_eq!(lrs.lrm_len(), 3);
+
+        let mut lrs = west();
+        lrs.add_alternate_traversal(LrmHandle(0), TraversalHandle(1))
+            .unwrap();
+        lrs.merge(east(), ConflictPolicy::KeepIncoming).unwrap();
+        assert_eq!(lrs.traversals.len(), 2);
+        assert_eq!(lrs.lrm_len(), 3);
+        // The old segment of the replaced traversal `b` is removed
+        assert_eq!(lrs.segments.len(), 2);
+        // The event of the replaced `line b` is dropped
+        assert_eq!(lrs.point_events.len(), 3);
+        assert_eq!(
+            lrs.point_events
+                .iter()
+                .filter(|event| event.lrm == LrmHandle(1))
+                .count(),
+            1
+        );
+        assert_eq!(lrs.traversals[1].segments[0].segment_index, 1);
+        assert_eq!(lrs.segments[1].id, "b");
+        assert_eq!(
+            lrs.traversals_containing(point! {x: 0.5, y: -0.5}),
+            [TraversalHandle(1)]
+        );
+        // `line a` keeps `b` as alternate traversal: its positions there are transferred from `a`
+        assert_eq!(lrs.lrms[0].alternate_traversals, [TraversalHandle(1)]);
+        assert!(lrs.traversals[1].lrms.contains(&LrmHandle(0)));
+        let reloaded = Lrs::<SphericalLineStringCurve>::from_bytes(&lrs.to_bytes()).unwrap();
+        assert_eq!(
+            reloaded.get_linestring(TraversalHandle(1)).unwrap().0[1],
+            coord! {x: 1., y: -1.}
+        );
+
+        // `line b` stays on the replaced traversal `b`: its anchor is projected on the new geometry
+        let mut lrs = west();
+        if let Anchor::Named(anchor) = &mut lrs.lrms[1].scale.anchors[0] {
+            anchor.point = Some(point! {x: 1., y: 1.});
+            anchor.curve_position = 1.;
+        }
+        lrs.merge(
+            region(&[("b", -1., "line c")]),
+            ConflictPolicy::KeepIncoming,
+        )
+        .unwrap();
+        assert_eq!(lrs.lrms[1].scale.id, "line b");
+        assert_eq!(lrs.lrms[1].traversal, TraversalHandle(1));
+        assert_relative_eq!(lrs.lrms[1].scale.anchors[0].curve_position(), 0.);
+    }
+
+    #[test]
+    fn route() {
+        // A railway switch at node 1: from `main`, one can go to `straight` or `diverging`
+        let mut b = Builder::new();
+        add_nodes(&mut b, 4);
+        let add = |b: &mut Builder, id: &str, nodes: (usize, usize), end: Coord| {
+            let segment_index = b.add_segment(id, &[coord! {x: 1., y: 0.}, end], nodes.0, nodes.1);
+            let traversal = b.add_traversal(
+                id,
+                &[SegmentOfTraversal {
+                    segment_index,
+                    reversed: false,
+                }],
+            );
+            let start = b.add_projected_anchor(id, Some(id), 0., properties!());
+            let end = b.add_projected_anchor(id, None, 1., properties!());
+            let anchors = [(start, 0.), (end, 1000.)].map(|(anchor_index, distance)| AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm: distance,
+            });
+            b.add_lrm(id, traversal, &anchors, properties!());
+            traversal
+        };
+        let main = add(&mut b, "main", (1, 0), coord! {x: 0., y: 0.});
+        let straight = add(&mut b, "straight", (1, 2), coord! {x: 2., y: 0.});
+        let diverging = add(&mut b, "diverging", (1, 3), coord! {x: 1., y: 1.});
+        b.add_connection(1, main, straight, true).unwrap();
+        b.add_connection(1, main, diverging, true).unwrap();
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(b.build_data(properties!())).unwrap();
+        let length = |traversal: usize| lrs.traversals[traversal].curve.length();
+
+        let from = LrmScaleMeasure::new("main", 500.);
+        let to = LrmScaleMeasure::new("diverging", 250.);
+        let route = lrs
+            .route((LrmHandle(main), &from), (LrmHandle(diverging), &to))
+            .unwrap();
+        assert_eq!(route.ranges.len(), 2);
+        assert_eq!(route.ranges[0].traversal, TraversalHandle(main));
+        assert_relative_eq!(route.ranges[0].begin, 0.5);
+        assert_relative_eq!(route.ranges[0].end, 0.);
+        assert_eq!(route.ranges[1].traversal, TraversalHandle(diverging));
+        assert_relative_eq!(route.ranges[1].end, 0.25);
+        assert_relative_eq!(route.length, 0.5 * length(main) + 0.25 * length(diverging));
+        assert_eq!(route.geometry.0.first(), Some(&coord! {x: 0.5, y: 0.}));
+        assert_eq!(route.geometry.0[1], coord! {x: 1., y: 0.});
+
+        // On the same traversal
+        let route = lrs
+            .route(
+                (LrmHandle(main), &from),
+                (LrmHandle(main), &LrmScaleMeasure::new("main", 100.)),
+            )
+            .unwrap();
+        assert_eq!(route.ranges.len(), 1);
+        assert_relative_eq!(route.length, 0.4 * length(main));
+
+        // The branches of the switch are not connected
+        let from = LrmScaleMeasure::new("diverging", 250.);
+        let to = LrmScaleMeasure::new("straight", 250.);
+        assert_eq!(
+            lrs.route((LrmHandle(diverging), &from), (LrmHandle(straight), &to))
+                .unwrap_err(),
+            LrsError::NoRoute
+        );
+        assert_eq!(
+            lrs.network_distance((LrmHandle(diverging), &from), (LrmHandle(straight), &to)),
+            Err(LrsError::NoRoute)
+        );
+
+        // Through the switch, unlike the distance as the crow flies
+        let from = LrmScaleMeasure::new("straight", 250.);
+        let to = LrmScaleMeasure::new("main", 250.);
+        assert_relative_eq!(
+            lrs.network_distance((LrmHandle(straight), &from), (LrmHandle(main), &to))
+                .unwrap(),
+            0.25 * length(straight) + 0.25 * length(main)
+        );
+    }
 }
@@ -1,120 +1,1014 @@
 //! High level extensions meant for an easy usage
 //! Those functions are exposed in wasm-bindings
 
-use geo::{Coord, Point};
+use geo::orient::Direction;
+use geo::{BoundingRect, Coord, LineString, Point, Polygon, Rect};
+use thiserror::Error;
 
-use crate::curves::{Curve, SphericalLineStringCurve};
-use crate::lrm_scale::{Anchor, LrmScaleMeasure};
-use crate::lrs::{self, LrmProjection, LrsBase, LrsError, Properties, TraversalPosition};
+use crate::curves::{Curve, CurveError};
+use crate::error_code::LrsErrorCode;
+use crate::events::{LinearEvent, PointEvent};
+use crate::lrm_scale::{self, Anchor, LrmScaleError, LrmScaleMeasure};
+use crate::lrs::{
+    self, AnyLrs, GeometryType, LoadFilter, LrmBounds, LrmHandle, LrmIntersection, LrmProjection,
+    LrsBase, LrsError, Properties, TraversalHandle,
+};
+use crate::search;
+use crate::stats::LrsStats;
+use crate::trace::{TraceMatch, TraceOptions};
+use crate::with_any_lrs;
 
-type Lrs = lrs::Lrs<SphericalLineStringCurve>;
+/// Errors of [`ExtLrs`].
+///
+/// The error of the underlying layer is kept as is. [`LrsErrorCode`] is the flat form exposed by the bindings.
+#[derive(Debug, Error, PartialEq)]
+pub enum ExtLrsError {
+    /// There is no LRM at this index.
+    #[error("there is no lrm at index {0}")]
+    LrmNotFound(usize),
+    /// The anchor of a measure could not be used on the scale of the LRM.
+    #[error("measure on anchor {anchor_name} of lrm {lrm_index}: {source}")]
+    Measure {
+        /// Index of the LRM of the measure.
+        lrm_index: usize,
+        /// Name of the anchor of the measure.
+        anchor_name: String,
+        /// What went wrong on the scale.
+        source: LrmScaleError,
+    },
+    /// Error of the [`Lrs`](lrs::Lrs).
+    #[error(transparent)]
+    Lrs(#[from] LrsError),
+    /// Error of a [`LrmScale`](lrm_scale::LrmScale).
+    #[error(transparent)]
+    LrmScale(#[from] LrmScaleError),
+    /// Error of a [`Curve`].
+    #[error(transparent)]
+    Curve(#[from] CurveError),
+}
+
+/// The nearest position of a [`Point`] on all the LRMs, see [`ExtLrs::locate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocatedPoint {
+    /// Index of the LRM.
+    pub lrm_index: usize,
+    /// `id` of the LRM, as given by [`ExtLrs::get_lrm_scale_id`].
+    pub lrm_id: String,
+    /// Name of the anchor of the measure.
+    pub anchor_name: String,
+    /// Offset of the measure from the anchor, in the unit of the scale.
+    pub scale_offset: f64,
+    /// Distance in meters from the LRM: positive if the [`Point`] is on the left of the LRM, negative on the right.
+    pub lateral_offset_m: f64,
+    /// Distance in meters between the [`Point`] and the LRM.
+    pub distance_m: f64,
+}
+
+/// A position on an LRM and the direction of increasing measures there, see [`ExtLrs::resolve_oriented`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrientedPoint {
+    /// The geographical position.
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    pub heading: f64,
+}
+
+/// A graduation of the scale of an LRM, to display it on a map, see [`ExtLrs::measure_ticks`].
+#[derive(Clone, Debug)]
+pub struct MeasureTick {
+    /// The measure of the graduation.
+    pub measure: LrmScaleMeasure,
+    /// The geographical position of the graduation on the LRM.
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    pub heading: f64,
+    /// The end of the tick on the left of increasing measures.
+    pub left: Point,
+    /// The end of the tick on the right of increasing measures.
+    pub right: Point,
+}
+
+/// An [`Anchor`] placed on its LRM, to display it on a map, see [`ExtLrs::anchor_markers`].
+#[derive(Clone, Debug)]
+pub struct AnchorMarker {
+    /// Name of the anchor, `None` for an unnamed anchor.
+    pub name: Option<String>,
+    /// The measure of the anchor. An unnamed anchor is measured from the previous named one.
+    pub measure: LrmScaleMeasure,
+    /// The position of the anchor on the LRM.
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    pub heading: f64,
+}
+
+/// Where an [`Anchor`] was surveyed and where it is on its LRM, see [`ExtLrs::anchor_surveys`].
+#[derive(Clone, Debug)]
+pub struct AnchorSurvey {
+    /// Name of the anchor, `None` for an unnamed anchor.
+    pub name: Option<String>,
+    /// The surveyed position, `None` if the anchor was only placed along the curve.
+    pub surveyed: Option<Point>,
+    /// The position of the anchor on the LRM.
+    pub projected: Point,
+    /// Signed distance between the surveyed position and the LRM, positive on the left.
+    pub offset: Option<f64>,
+}
+
+/// An [`Anchor`] and its measure, see [`ExtLrs::anchors_between`].
+#[derive(Clone, Debug)]
+pub struct MeasuredAnchor {
+    /// The anchor.
+    pub anchor: Anchor,
+    /// The measure of the anchor. An unnamed anchor is measured from the previous named one.
+    pub measure: LrmScaleMeasure,
+}
+
+/// A range of measures of an LRM, see [`ExtLrs::measure_ranges_in_polygon`].
+#[derive(Clone, Debug)]
+pub struct MeasureRange {
+    /// The smallest measure of the range.
+    pub from: LrmScaleMeasure,
+    /// The largest measure of the range.
+    pub to: LrmScaleMeasure,
+}
+
+/// An LRM whose id matches a query, see [`ExtLrs::search_lrms`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LrmMatch {
+    /// Index of the LRM.
+    pub lrm_index: usize,
+    /// `id` of the LRM, as given by [`ExtLrs::get_lrm_scale_id`].
+    pub lrm_id: String,
+    /// How well the LRM matches the query, between 0 (excluded) and 1 for an exact match, see [`search::match_score`].
+    pub score: f64,
+}
+
+/// Where an LRM is, to frame it on a map, see [`ExtLrs::get_lrm_bbox`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LrmBbox {
+    /// The bounding box of the geometry of the LRM.
+    pub bbox: Rect,
+    /// The middle of the LRM, half way along its geometry.
+    pub center: Point,
+}
+
+impl From<ExtLrsError> for LrsErrorCode {
+    fn from(error: ExtLrsError) -> Self {
+        match error {
+            ExtLrsError::LrmNotFound(lrm_index) => Self::LrmNotFound { lrm_index },
+            ExtLrsError::Measure {
+                lrm_index,
+                anchor_name,
+                source,
+            } => Self::from(source).on_measure(lrm_index, &anchor_name),
+            ExtLrsError::Lrs(error) => error.into(),
+            ExtLrsError::LrmScale(error) => error.into(),
+            ExtLrsError::Curve(error) => error.into(),
+        }
+    }
+}
 
 /// Struct exposed to js.
+///
+/// The [`Curve`] of the LRS matches the [`GeometryType`] recorded in the file:
+/// the lengths and offsets are in meters for geographic coordinates, and in the unit of the coordinates otherwise.
 pub struct ExtLrs {
     /// The linear referencing system
-    pub lrs: Lrs,
+    pub lrs: AnyLrs,
 }
 
 impl ExtLrs {
     /// Load the data.
-    pub fn load(data: &[u8]) -> Result<ExtLrs, String> {
-        Lrs::from_bytes(data)
+    pub fn load(data: &[u8]) -> Result<ExtLrs, ExtLrsError> {
+        Self::load_as(data, None)
+    }
+
+    /// Load the data, with `geometry_type` overriding the one recorded in the file, see [`AnyLrs::from_bytes`].
+    pub fn load_as(
+        data: &[u8],
+        geometry_type: Option<GeometryType>,
+    ) -> Result<ExtLrs, ExtLrsError> {
+        AnyLrs::from_bytes(data, geometry_type)
             .map(|lrs| Self { lrs })
-            .map_err(|err| err.to_string())
+            .map_err(ExtLrsError::from)
+    }
+
+    /// Load only some LRMs from the data, see [`lrs::Lrs::from_bytes_filtered`].
+    /// It is much faster than loading a whole national network, e.g. to display the viewport of a map first.
+    pub fn load_filtered(data: &[u8], filter: &LoadFilter) -> Result<ExtLrs, ExtLrsError> {
+        AnyLrs::from_bytes_filtered(data, None, filter)
+            .map(|lrs| Self { lrs })
+            .map_err(ExtLrsError::from)
+    }
+
+    /// Load only some LRMs from the beginning of the data, while it is still being received, see [`lrs::Lrs::from_partial_bytes`].
+    /// Returns `Ok(None)` if more data is needed.
+    pub fn load_partial(data: &[u8], filter: &LoadFilter) -> Result<Option<ExtLrs>, ExtLrsError> {
+        AnyLrs::from_partial_bytes(data, filter)
+            .map(|lrs| lrs.map(|lrs| Self { lrs }))
+            .map_err(ExtLrsError::from)
+    }
+
+    /// Load the data from a file, through a memory map with the `mmap` feature, see [`lrs::Lrs::open_mapped`].
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<ExtLrs, ExtLrsError> {
+        Self::open_as(path, None)
+    }
+
+    /// Load the data from a file, with `geometry_type` overriding the one recorded in the file, see [`ExtLrs::open`].
+    pub fn open_as<P: AsRef<std::path::Path>>(
+        path: P,
+        geometry_type: Option<GeometryType>,
+    ) -> Result<ExtLrs, ExtLrsError> {
+        #[cfg(feature = "mmap")]
+        let lrs = AnyLrs::open_mapped(path, geometry_type);
+        #[cfg(not(feature = "mmap"))]
+        let lrs = AnyLrs::new(path, geometry_type);
+        lrs.map(|lrs| Self { lrs }).map_err(ExtLrsError::from)
+    }
+
+    /// Releases the memory of the LRS before it is dropped. It has no LRM anymore.
+    pub fn close(&mut self) {
+        with_any_lrs!(&mut self.lrs, lrs => {
+            lrs.lrms = vec![];
+            lrs.traversals = vec![];
+            lrs.nodes = vec![];
+            lrs.segments = vec![];
+            lrs.connections = vec![];
+            lrs.point_events = vec![];
+            lrs.linear_events = vec![];
+            lrs.properties = Default::default();
+            lrs.rtree_data = None;
+            lrs.fragment_index = None;
+        })
+    }
+
+    /// How the coordinates are interpreted: the lengths and offsets are in meters for [`GeometryType::Geographic`].
+    pub fn geometry_type(&self) -> GeometryType {
+        self.lrs.geometry_type()
+    }
+
+    /// Coordinate reference system of the coordinates, such as `EPSG:4326`, if it was recorded.
+    pub fn crs(&self) -> Option<String> {
+        with_any_lrs!(&self.lrs, lrs => lrs.crs.clone())
+    }
+
+    /// Serializes the LRS, see [`lrs::Lrs::to_bytes`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        with_any_lrs!(&self.lrs, lrs => lrs.to_bytes())
     }
 
     /// How many LRMs compose the LRS.
     pub fn lrm_len(&self) -> usize {
-        self.lrs.lrm_len()
+        with_any_lrs!(&self.lrs, lrs => lrs.lrm_len())
+    }
+
+    /// Index of the LRM with the given `id`, see [`LrsBase::get_lrm`].
+    pub fn get_lrm(&self, lrm_id: &str) -> Option<usize> {
+        with_any_lrs!(&self.lrs, lrs => lrs.get_lrm(lrm_id)).map(|handle| handle.0)
+    }
+
+    /// All the LRMs of the LRS.
+    pub fn lrms(&self) -> &[lrs::Lrm] {
+        with_any_lrs!(&self.lrs, lrs => &lrs.lrms)
+    }
+
+    /// All the [`lrs::Node`]s of the LRS.
+    pub fn nodes(&self) -> &[lrs::Node] {
+        with_any_lrs!(&self.lrs, lrs => &lrs.nodes)
+    }
+
+    /// All the [`lrs::Segment`]s of the LRS.
+    pub fn segments(&self) -> &[lrs::Segment] {
+        with_any_lrs!(&self.lrs, lrs => &lrs.segments)
+    }
+
+    /// All the [`PointEvent`]s of the LRS, see [`ExtLrs::point_events`] for the ones of a LRM.
+    pub fn all_point_events(&self) -> &[PointEvent] {
+        with_any_lrs!(&self.lrs, lrs => &lrs.point_events)
+    }
+
+    /// All the [`LinearEvent`]s of the LRS, see [`ExtLrs::linear_events`] for the ones of a LRM.
+    pub fn all_linear_events(&self) -> &[LinearEvent] {
+        with_any_lrs!(&self.lrs, lrs => &lrs.linear_events)
+    }
+
+    // Fails with the index of the LRM when it does not exist
+    fn lrm(&self, lrm_index: usize) -> Result<&lrs::Lrm, ExtLrsError> {
+        with_any_lrs!(&self.lrs, lrs => lrs.lrm(LrmHandle(lrm_index)))
+            .map_err(|_| ExtLrsError::LrmNotFound(lrm_index))
     }
 
     /// Return the geometry of the LRM.
-    pub fn get_lrm_geom(&self, index: usize) -> Result<Vec<geo::Coord>, String> {
-        let lrm = self.lrs.lrms.get(index).ok_or("Invalid index")?;
-        self.lrs
-            .get_linestring(lrm.traversal)
-            .map_err(|err| err.to_string())
-            .map(|linestring| linestring.0)
+    pub fn get_lrm_geom(&self, index: usize) -> Result<Vec<geo::Coord>, ExtLrsError> {
+        let lrm = self.lrm(index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.get_linestring(lrm.traversal))?.0)
+    }
+
+    /// Return the geometry of the LRM, ordered by increasing measures.
+    pub fn get_oriented_lrm_geom(&self, index: usize) -> Result<Vec<geo::Coord>, ExtLrsError> {
+        self.lrm(index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.oriented_linestring(LrmHandle(index)))?.0)
+    }
+
+    /// The bounding box of the LRM and its middle, e.g. to zoom on it without loading its geometry.
+    pub fn get_lrm_bbox(&self, lrm_index: usize) -> Result<LrmBbox, ExtLrsError> {
+        let traversal = self.lrm(lrm_index)?.traversal;
+        with_any_lrs!(&self.lrs, lrs => {
+            let curve = lrs.get_curve(traversal)?;
+            Ok(LrmBbox {
+                bbox: curve
+                    .geom
+                    .bounding_rect()
+                    .ok_or(CurveError::InvalidGeometry)?,
+                center: curve.resolve(0.5)?,
+            })
+        })
+    }
+
+    /// Whether the measures of the LRM increase along its geometry, as given by [`ExtLrs::get_lrm_geom`].
+    pub fn measures_increase(&self, lrm_index: usize) -> Result<bool, ExtLrsError> {
+        self.lrm(lrm_index)
+            .map(|lrm| matches!(lrm.measure_direction, geo::orient::Direction::Default))
+    }
+
+    /// The measures at both ends of the LRM and its length, see [`ExtLrs::geometry_type`] for the unit.
+    pub fn lrm_bounds(&self, lrm_index: usize) -> Result<LrmBounds, ExtLrsError> {
+        self.lrm(lrm_index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.lrm_bounds(LrmHandle(lrm_index)))?)
+    }
+
+    /// Where the traversals of two LRMs cross or touch, sorted along the first LRM.
+    pub fn intersections(
+        &self,
+        lrm_index_a: usize,
+        lrm_index_b: usize,
+    ) -> Result<Vec<LrmIntersection>, ExtLrsError> {
+        self.lrm(lrm_index_a)?;
+        self.lrm(lrm_index_b)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => {
+            lrs.intersections(LrmHandle(lrm_index_a), LrmHandle(lrm_index_b))
+        })?)
+    }
+
+    /// The distance along the network between measures of two LRMs, following the connections between traversals.
+    pub fn network_distance(
+        &self,
+        from_lrm_index: usize,
+        from: &LrmScaleMeasure,
+        to_lrm_index: usize,
+        to: &LrmScaleMeasure,
+    ) -> Result<f64, ExtLrsError> {
+        self.locate_measure(from_lrm_index, from)?;
+        self.locate_measure(to_lrm_index, to)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.network_distance(
+            (LrmHandle(from_lrm_index), from),
+            (LrmHandle(to_lrm_index), to),
+        ))?)
+    }
+
+    /// Summary of the content of the LRS, to sanity-check a file.
+    pub fn stats(&self) -> LrsStats {
+        with_any_lrs!(&self.lrs, lrs => lrs.stats())
+    }
+
+    /// The LRMs and their anchors as a GeoJSON FeatureCollection, see [`lrs::Lrs::to_geojson`].
+    #[cfg(feature = "geojson")]
+    pub fn to_geojson(&self) -> String {
+        with_any_lrs!(&self.lrs, lrs => lrs.to_geojson()).to_string()
+    }
+
+    /// The geometry of the LRM as a GeoJSON Feature, with its `id` and its properties.
+    #[cfg(feature = "geojson")]
+    pub fn lrm_geojson(&self, lrm_index: usize) -> Result<String, ExtLrsError> {
+        let mut properties = geojson_properties(self.lrm_properties(lrm_index)?);
+        properties.insert("id".to_owned(), self.get_lrm_scale_id(lrm_index)?.into());
+        let geometry = geojson_linestring(&self.get_lrm_geom(lrm_index)?);
+        Ok(geojson_feature(geometry, properties).to_string())
     }
 
-    /// `id` of the [`LrmScale`].
-    pub fn get_lrm_scale_id(&self, lrm_index: usize) -> String {
-        self.lrs.lrms[lrm_index].scale.id.clone()
+    /// The range between two measures as a GeoJSON Feature, see [`ExtLrs::resolve_range`].
+    /// Its properties are the `lrm_id` and both measures.
+    #[cfg(feature = "geojson")]
+    pub fn range_geojson(
+        &self,
+        lrm_index: usize,
+        from: &LrmScaleMeasure,
+        to: &LrmScaleMeasure,
+        lateral_offset_m: Option<f64>,
+    ) -> Result<String, ExtLrsError> {
+        let coords = self.resolve_range(lrm_index, from, to, lateral_offset_m)?;
+        let mut properties = geojson::JsonObject::new();
+        properties.insert(
+            "lrm_id".to_owned(),
+            self.get_lrm_scale_id(lrm_index)?.into(),
+        );
+        insert_measure(&mut properties, "from", from);
+        insert_measure(&mut properties, "to", to);
+        Ok(geojson_feature(geojson_linestring(&coords), properties).to_string())
+    }
+
+    /// The [`AnchorMarker`]s of a LRM as a GeoJSON FeatureCollection of Points.
+    /// Their properties are the `name`, the measure (`anchor_name` and `scale_offset`) and the `heading`.
+    #[cfg(feature = "geojson")]
+    pub fn anchor_markers_geojson(&self, lrm_index: usize) -> Result<String, ExtLrsError> {
+        let features = self.anchor_markers(lrm_index)?.into_iter().map(|marker| {
+            let mut properties = geojson::JsonObject::new();
+            properties.insert("name".to_owned(), marker.name.into());
+            insert_measure(&mut properties, "", &marker.measure);
+            properties.insert("heading".to_owned(), marker.heading.into());
+            let point = geojson::Value::Point(vec![marker.point.x(), marker.point.y()]);
+            geojson_feature(point, properties)
+        });
+        Ok(geojson_collection(features))
+    }
+
+    /// The [`MeasureTick`]s of a LRM as a GeoJSON FeatureCollection of LineStrings, from the left to the right end of each tick.
+    /// Their properties are the measure (`anchor_name` and `scale_offset`) and the `heading`.
+    #[cfg(feature = "geojson")]
+    pub fn measure_ticks_geojson(
+        &self,
+        lrm_index: usize,
+        step: f64,
+        tick_length_m: f64,
+    ) -> Result<String, ExtLrsError> {
+        let features = self
+            .measure_ticks(lrm_index, step, tick_length_m)?
+            .into_iter()
+            .map(|tick| {
+                let mut properties = geojson::JsonObject::new();
+                insert_measure(&mut properties, "", &tick.measure);
+                properties.insert("heading".to_owned(), tick.heading.into());
+                let line = geojson_linestring(&[tick.left.0, tick.right.0]);
+                geojson_feature(line, properties)
+            });
+        Ok(geojson_collection(features))
+    }
+
+    /// `id` of the [`LrmScale`].
+    pub fn get_lrm_scale_id(&self, lrm_index: usize) -> Result<String, ExtLrsError> {
+        self.lrm(lrm_index).map(|lrm| lrm.scale.id.clone())
     }
 
     /// All the [`Anchor`]s of a LRM.
-    pub fn get_anchors(&self, lrm_index: usize) -> Vec<Anchor> {
-        self.lrs.lrms[lrm_index].scale.anchors.to_vec()
+    pub fn get_anchors(&self, lrm_index: usize) -> Result<Vec<Anchor>, ExtLrsError> {
+        self.lrm(lrm_index).map(|lrm| lrm.scale.anchors.to_vec())
+    }
+
+    /// The [`Anchor`]s of a LRM between two measures, both included, sorted by increasing measures.
+    /// It allows to go through the anchors of a long LRM page by page.
+    pub fn anchors_between(
+        &self,
+        lrm_index: usize,
+        from: &LrmScaleMeasure,
+        to: &LrmScaleMeasure,
+    ) -> Result<Vec<MeasuredAnchor>, ExtLrsError> {
+        self.locate_measure(lrm_index, from)?;
+        self.locate_measure(lrm_index, to)?;
+        let scale = &self.lrm(lrm_index)?.scale;
+        scale
+            .anchors_between(from, to)?
+            .into_iter()
+            .map(|anchor| {
+                Ok(MeasuredAnchor {
+                    anchor: anchor.clone(),
+                    measure: scale.anchor_measure(anchor)?,
+                })
+            })
+            .collect()
+    }
+
+    /// All the [`Anchor`]s of a LRM, in the same order as [`ExtLrs::get_anchors`], placed on the LRM.
+    /// An anchor before the start or after the end of the LRM is placed at that end.
+    pub fn anchor_markers(&self, lrm_index: usize) -> Result<Vec<AnchorMarker>, ExtLrsError> {
+        let lrm = self.lrm(lrm_index)?;
+        with_any_lrs!(&self.lrs, lrs => {
+            let curve = &*lrs.traversal(lrm.traversal)?.curve;
+            lrm.scale
+                .anchors
+                .iter()
+                .map(|anchor| {
+                    let curve_position = anchor.curve_position().clamp(0., 1.);
+                    let name = match anchor {
+                        Anchor::Named(anchor) => Some(anchor.name.clone()),
+                        Anchor::Unnamed(_) => None,
+                    };
+                    Ok(AnchorMarker {
+                        name,
+                        measure: lrm.scale.anchor_measure(anchor)?,
+                        point: curve.resolve(curve_position)?,
+                        heading: lrm.measures_heading(curve.bearing(curve_position)?),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// All the [`Anchor`]s of a LRM, in the same order as [`ExtLrs::get_anchors`], with their surveyed position.
+    /// The offset between the surveyed and projected positions tells how reliable an anchor is.
+    pub fn anchor_surveys(&self, lrm_index: usize) -> Result<Vec<AnchorSurvey>, ExtLrsError> {
+        let lrm = self.lrm(lrm_index)?;
+        with_any_lrs!(&self.lrs, lrs => {
+            let curve = &*lrs.traversal(lrm.traversal)?.curve;
+            lrm.scale
+                .anchors
+                .iter()
+                .map(|anchor| {
+                    Ok(AnchorSurvey {
+                        name: match anchor {
+                            Anchor::Named(anchor) => Some(anchor.name.clone()),
+                            Anchor::Unnamed(_) => None,
+                        },
+                        surveyed: anchor.surveyed_point(),
+                        projected: curve.resolve(anchor.curve_position().clamp(0., 1.))?,
+                        offset: anchor.projection_offset(),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    // Checks that the anchor of the measure exists, to report which LRM and anchor are concerned
+    fn locate_measure(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> Result<lrm_scale::CurvePosition, ExtLrsError> {
+        self.lrm(lrm_index)?
+            .scale
+            .locate_point(measure)
+            .map_err(|source| ExtLrsError::Measure {
+                lrm_index,
+                anchor_name: measure.anchor_name.clone(),
+                source,
+            })
+    }
+
+    /// The [`LrmScaleMeasure`] at a position along the geometry of the LRM, between 0 and 1, e.g. from a [`LrmProjection`].
+    /// It is the inverse of locating a measure: the measure is given from the nearest named anchor.
+    pub fn measure_at(
+        &self,
+        lrm_index: usize,
+        curve_position: f64,
+    ) -> Result<LrmScaleMeasure, ExtLrsError> {
+        let scale = &self.lrm(lrm_index)?.scale;
+        if !curve_position.is_finite() {
+            return Err(CurveError::NotOnTheCurve.into());
+        }
+        Ok(scale.locate_anchor(curve_position)?)
     }
 
     /// Get the position given a [`LrmScaleMeasure`].
-    pub fn resolve(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> Result<Point, LrsError> {
-        let lrm = &self.lrs.lrms[lrm_index];
-        let curve_position = lrm.scale.locate_point(measure)?.clamp(0., 1.0);
+    pub fn resolve(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> Result<Point, ExtLrsError> {
+        self.locate_measure(lrm_index, measure)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.resolve(LrmHandle(lrm_index), measure))?)
+    }
 
-        let traversal_position = TraversalPosition {
-            curve_position,
-            traversal: lrm.traversal,
-        };
-        self.lrs.locate_traversal(traversal_position)
+    /// Get the positions of many [`LrmScaleMeasure`]s of the same LRM, see [`ExtLrs::resolve`].
+    /// The LRM and its curve are looked up once for all the measures.
+    /// There is one item in the result for every measure, in the same order.
+    pub fn resolve_many(
+        &self,
+        lrm_index: usize,
+        measures: &[LrmScaleMeasure],
+    ) -> Vec<Result<Point, ExtLrsError>> {
+        with_any_lrs!(&self.lrs, lrs => {
+            let Ok((lrm, curve)) = self
+                .lrm(lrm_index)
+                .and_then(|lrm| Ok((lrm, lrs.get_curve(lrm.traversal)?)))
+            else {
+                // Every measure fails the same way
+                return measures
+                    .iter()
+                    .map(|measure| self.resolve(lrm_index, measure))
+                    .collect();
+            };
+            let resolve = curve.resolver();
+            measures
+                .iter()
+                .map(|measure| {
+                    let curve_position = self.locate_measure(lrm_index, measure)?;
+                    let curve_position = if curve.is_closed() {
+                        lrm.scale
+                            .locate_point_wrapping(measure)
+                            .map_err(LrsError::from)?
+                    } else {
+                        curve_position.clamp(0., 1.)
+                    };
+                    Ok(resolve(curve_position).map_err(LrsError::from)?)
+                })
+                .collect()
+        })
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`] and the direction of increasing measures there,
+    /// e.g. to rotate the icon of a train on a map.
+    pub fn resolve_oriented(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> Result<OrientedPoint, ExtLrsError> {
+        self.locate_measure(lrm_index, measure)?;
+        let lrm = LrmHandle(lrm_index);
+        with_any_lrs!(&self.lrs, lrs => Ok(OrientedPoint {
+            point: lrs.resolve(lrm, measure)?,
+            heading: lrs.heading(lrm, measure)?,
+        }))
+    }
+
+    /// Graduations of the scale of the LRM every `step` scale units, e.g. every 100 meters.
+    /// Each tick is a segment of `tick_length_m` across the LRM, centered on the graduation,
+    /// in meters for geographic coordinates, see [`ExtLrs::geometry_type`].
+    /// There is no graduation if `step` is not a positive finite number.
+    pub fn measure_ticks(
+        &self,
+        lrm_index: usize,
+        step: f64,
+        tick_length_m: f64,
+    ) -> Result<Vec<MeasureTick>, ExtLrsError> {
+        let scale = &self.lrm(lrm_index)?.scale;
+        if !step.is_finite() || step <= 0. {
+            return Ok(vec![]);
+        }
+        let handle = LrmHandle(lrm_index);
+        with_any_lrs!(&self.lrs, lrs => {
+            let bounds = lrs.lrm_bounds(handle)?;
+            let min = scale.get_position(bounds.min_measure)?;
+            let max = scale.get_position(bounds.max_measure)?;
+            let (first, last) = ((min / step).ceil() as i64, (max / step).floor() as i64);
+
+            (first..=last)
+                .map(|graduation| {
+                    let measure = scale.get_measure(graduation as f64 * step)?;
+                    Ok(MeasureTick {
+                        point: lrs.resolve(handle, &measure)?,
+                        heading: lrs.heading(handle, &measure)?,
+                        left: lrs.resolve_with_offset(handle, &measure, tick_length_m / 2.)?,
+                        right: lrs.resolve_with_offset(handle, &measure, -tick_length_m / 2.)?,
+                        measure,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`], moved perpendicularly to the LRM by `lateral_offset_m`,
+    /// in meters for geographic coordinates, see [`ExtLrs::geometry_type`].
+    /// The offset is positive on the left when facing increasing measures, negative on the right.
+    pub fn resolve_with_offset(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+        lateral_offset_m: f64,
+    ) -> Result<Point, ExtLrsError> {
+        self.locate_measure(lrm_index, measure)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => {
+            lrs.resolve_with_offset(LrmHandle(lrm_index), measure, lateral_offset_m)
+        })?)
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`] on one of the traversals of the LRM, e.g. an alternate track.
+    pub fn resolve_on_traversal(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+        traversal_index: usize,
+    ) -> Result<Point, ExtLrsError> {
+        self.locate_measure(lrm_index, measure)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.resolve_on(
+            LrmHandle(lrm_index),
+            measure,
+            TraversalHandle(traversal_index),
+        ))?)
+    }
+
+    /// Indices of the traversals of an LRM: the main one first, then the alternate ones.
+    pub fn lrm_traversals(&self, lrm_index: usize) -> Result<Vec<usize>, ExtLrsError> {
+        self.lrm(lrm_index)
+            .map(|lrm| lrm.all_traversals().map(|traversal| traversal.0).collect())
     }
 
     /// Given two [`LrmScaleMeasure`]s, return a range of [`LineString`].
+    /// On a loop, the range follows increasing measures and can go past the origin of the loop.
+    /// With a `lateral_offset_m`, the range is moved perpendicularly to the LRM by that distance,
+    /// positive on the left when facing increasing measures, negative on the right.
+    /// It is in meters for geographic coordinates, see [`ExtLrs::geometry_type`].
     pub fn resolve_range(
         &self,
         lrm_index: usize,
         from: &LrmScaleMeasure,
         to: &LrmScaleMeasure,
-    ) -> Result<Vec<Coord>, String> {
-        let lrm = &self.lrs.lrms[lrm_index];
-        let scale = &lrm.scale;
-        let curve = &self.lrs.traversals[lrm.traversal.0].curve;
-        let from = scale
-            .locate_point(from)
-            .map_err(|e| e.to_string())?
-            .clamp(0., 1.);
-        let to = scale
-            .locate_point(to)
-            .map_err(|e| e.to_string())?
-            .clamp(0., 1.);
-
-        match curve.sublinestring(from, to) {
-            Some(linestring) => Ok(linestring.0),
-            None => Err("Could not find sublinestring".to_string()),
+        lateral_offset_m: Option<f64>,
+    ) -> Result<Vec<Coord>, ExtLrsError> {
+        let lrm = self.lrm(lrm_index)?;
+        with_any_lrs!(&self.lrs, lrs => {
+            let curve = &*lrs.traversal(lrm.traversal)?.curve;
+            if !curve.is_valid() {
+                return Err(LrsError::GeometryUnavailable.into());
+            }
+            let from_position = self.locate_measure(lrm_index, from)?;
+            let to_position = self.locate_measure(lrm_index, to)?;
+            let (linestring, along_measures) = if curve.is_closed() {
+                (Self::resolve_loop_range(lrm, curve, from, to)?, true)
+            } else {
+                let from = from_position.clamp(0., 1.);
+                let to = to_position.clamp(0., 1.);
+                let linestring = curve
+                    .sublinestring(from, to)
+                    .ok_or(CurveError::InvalidRange(from, to))?;
+                // The range follows the geometry from `from` to `to`
+                let along_geometry = from <= to;
+                let measures_increase = matches!(lrm.measure_direction, Direction::Default);
+                (linestring, along_geometry == measures_increase)
+            };
+
+            match lateral_offset_m {
+                None => Ok(linestring.0),
+                Some(offset) => {
+                    let offset = if along_measures { offset } else { -offset };
+                    let range = curve_like(curve, linestring);
+                    Ok(range.offset_linestring(offset)?.0)
+                }
+            }
+        })
+    }
+
+    // On a loop, the range always follows increasing measures and wraps past the origin if needed
+    fn resolve_loop_range<CurveImpl: Curve>(
+        lrm: &lrs::Lrm,
+        curve: &CurveImpl,
+        from: &LrmScaleMeasure,
+        to: &LrmScaleMeasure,
+    ) -> Result<LineString, ExtLrsError> {
+        let from = lrm.scale.locate_point_wrapping(from)?;
+        let to = lrm.scale.locate_point_wrapping(to)?;
+        let linestring = match lrm.measure_direction {
+            Direction::Default => curve.sublinestring_wrapping(from, to),
+            Direction::Reversed => curve
+                .sublinestring_wrapping(to, from)
+                .map(|linestring| linestring.into_iter().rev().collect()),
+        };
+        Ok(linestring.ok_or(CurveError::InvalidRange(from, to))?)
+    }
+
+    /// The ranges of measures of the LRM inside a polygon, such as a work zone, a flooded area or an administrative boundary.
+    /// The ranges are sorted by increasing measures. A part of the LRM only touching the boundary of the polygon is ignored.
+    pub fn measure_ranges_in_polygon(
+        &self,
+        lrm_index: usize,
+        polygon: &Polygon,
+    ) -> Result<Vec<MeasureRange>, ExtLrsError> {
+        let measures_increase = self.measures_increase(lrm_index)?;
+        let mut ranges: Vec<_> = with_any_lrs!(&self.lrs, lrs => {
+            lrs.lrm_ranges_in(LrmHandle(lrm_index), polygon)
+        })?
+        .into_iter()
+        .map(|range| {
+            let (from, to) = if measures_increase {
+                (range.begin, range.end)
+            } else {
+                (range.end, range.begin)
+            };
+            MeasureRange { from, to }
+        })
+        .collect();
+        // The ranges are sorted along the traversal
+        if !measures_increase {
+            ranges.reverse();
         }
+        Ok(ranges)
+    }
+
+    /// Whether the traversal of the LRM is a loop, where measures wrap around.
+    pub fn is_loop(&self, lrm_index: usize) -> Result<bool, ExtLrsError> {
+        self.lrm(lrm_index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.is_loop(LrmHandle(lrm_index)))?)
+    }
+
+    /// Converts a [`LrmScaleMeasure`] of an LRM into a [`LrmScaleMeasure`] of an other LRM.
+    /// Both LRMs must share the same traversal.
+    pub fn convert_measure(
+        &self,
+        from_lrm_index: usize,
+        to_lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> Result<LrmScaleMeasure, ExtLrsError> {
+        self.locate_measure(from_lrm_index, measure)?;
+        self.lrm(to_lrm_index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => {
+            lrs.convert_measure(LrmHandle(from_lrm_index), LrmHandle(to_lrm_index), measure)
+        })?
+        .measure)
+    }
+
+    /// All the [`PointEvent`]s located on a LRM.
+    pub fn point_events(&self, lrm_index: usize) -> Result<Vec<PointEvent>, ExtLrsError> {
+        self.lrm(lrm_index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => {
+            lrs.point_events(LrmHandle(lrm_index)).cloned().collect()
+        }))
+    }
+
+    /// All the [`LinearEvent`]s located on a LRM.
+    pub fn linear_events(&self, lrm_index: usize) -> Result<Vec<LinearEvent>, ExtLrsError> {
+        self.lrm(lrm_index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => {
+            lrs.linear_events(LrmHandle(lrm_index)).cloned().collect()
+        }))
     }
 
     /// [`Properties`] of the lrs
     pub fn lrs_properties(&self) -> &Properties {
-        &self.lrs.properties
+        with_any_lrs!(&self.lrs, lrs => &lrs.properties)
     }
 
     /// [`Properties`] for a given lrm
-    pub fn lrm_properties(&self, lrm_index: usize) -> &Properties {
-        &self.lrs.lrms[lrm_index].properties
+    pub fn lrm_properties(&self, lrm_index: usize) -> Result<&Properties, ExtLrsError> {
+        self.lrm(lrm_index).map(|lrm| &lrm.properties)
+    }
+
+    /// [`Properties`] of the traversal of a given lrm
+    pub fn traversal_properties(&self, lrm_index: usize) -> Result<&Properties, ExtLrsError> {
+        let lrm = self.lrm(lrm_index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => &lrs.traversal(lrm.traversal)?.properties))
     }
 
     /// [`Properties`] for a given anchor
-    pub fn anchor_properties(&self, lrm_index: usize, anchor_index: usize) -> &Properties {
-        self.lrs.lrms[lrm_index].scale.anchors[anchor_index].properties()
+    pub fn anchor_properties(
+        &self,
+        lrm_index: usize,
+        anchor_index: usize,
+    ) -> Result<&Properties, ExtLrsError> {
+        self.lrm(lrm_index)?
+            .scale
+            .anchors
+            .get(anchor_index)
+            .map(Anchor::properties)
+            .ok_or(LrsError::InvalidHandle.into())
+    }
+
+    /// Projects a [`Point`] on a LRM, see [`LrsBase::lookup`].
+    pub fn lookup(&self, point: Point, lrm_index: usize) -> Result<LrmProjection, ExtLrsError> {
+        self.lrm(lrm_index)?;
+        Ok(with_any_lrs!(&self.lrs, lrs => lrs.lookup(point, LrmHandle(lrm_index)))?)
+    }
+
+    /// Projects every [`Point`] on every LRM at most `max_distance` away, see [`lrs::Lrs::lookup_batch`].
+    pub fn lookup_batch(&self, points: &[Point], max_distance: f64) -> Vec<Vec<LrmProjection>> {
+        with_any_lrs!(&self.lrs, lrs => lrs.lookup_batch(points, max_distance))
+    }
+
+    /// Finds the position of every measure on the same LRM, see [`lrs::Lrs::resolve_batch`].
+    pub fn resolve_batch(
+        &self,
+        lrm_index: usize,
+        measures: &[LrmScaleMeasure],
+    ) -> Vec<Result<Point, LrsError>> {
+        with_any_lrs!(&self.lrs, lrs => lrs.resolve_batch(LrmHandle(lrm_index), measures))
     }
 
     /// Projects a [`Point`] on all [`Lrm`] where the [`Point`] is in the bounding box.
     /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
     pub fn lookup_lrms(&self, point: Point) -> Vec<LrmProjection> {
-        self.lrs.lookup_lrms(point)
+        with_any_lrs!(&self.lrs, lrs => lrs.lookup_lrms(point))
+    }
+
+    /// All the LRMs whose geometry intersects the bounding box between `min` and `max`, such as the viewport of a map.
+    pub fn lrms_in_bbox(&self, min: Coord, max: Coord) -> Vec<usize> {
+        with_any_lrs!(&self.lrs, lrs => lrs.lrms_in(&Rect::new(min, max).to_polygon(), true))
+            .into_iter()
+            .map(|handle| handle.0)
+            .collect()
+    }
+
+    /// The LRMs whose id, or the id of their traversal, matches a `query` typed in a search box.
+    /// The id can start with the `query`, contain it, or be close to it with a few typos.
+    /// The best `limit` matches are returned, the best one first.
+    pub fn search_lrms(&self, query: &str, limit: usize) -> Vec<LrmMatch> {
+        let mut matches: Vec<_> = self
+            .lrms()
+            .iter()
+            .enumerate()
+            .filter_map(|(lrm_index, lrm)| {
+                let traversal_id = with_any_lrs!(&self.lrs, lrs => {
+                    lrs.traversal(lrm.traversal).map(|t| t.id.as_str())
+                });
+                let score = std::iter::once(lrm.scale.id.as_str())
+                    .chain(traversal_id)
+                    .filter_map(|id| search::match_score(query, id))
+                    .reduce(f64::max)?;
+                Some(LrmMatch {
+                    lrm_index,
+                    lrm_id: lrm.scale.id.clone(),
+                    score,
+                })
+            })
+            .collect();
+        // The sort is stable: LRMs with the same score stay sorted by index
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        matches
+    }
+
+    /// Projects a [`Point`] on every LRM at most `max_distance` away: in meters for geographic coordinates,
+    /// in the units of the coordinates for schematic ones, see [`ExtLrs::geometry_type`].
+    /// The result is sorted by `orthogonal_offset`: the nearest LRM to the [`Point`] is the first item.
+    pub fn lookup_all(&self, point: Point, max_distance: f64) -> Vec<LrmProjection> {
+        with_any_lrs!(&self.lrs, lrs => lrs.lookup_all(point, max_distance))
+    }
+
+    /// Finds the nearest LRM at most `max_distance` away from a [`Point`], and the measure of the [`Point`] on it.
+    /// The distances are in the same unit as in [`ExtLrs::lookup_all`].
+    /// Returns `None` when no LRM is close enough.
+    pub fn locate(&self, point: Point, max_distance: f64) -> Option<LocatedPoint> {
+        let projection = self.lookup_all(point, max_distance).into_iter().next()?;
+        let lrm_index = projection.measure.lrm.0;
+        Some(LocatedPoint {
+            lrm_index,
+            lrm_id: self.lrm(lrm_index).ok()?.scale.id.clone(),
+            anchor_name: projection.measure.measure.anchor_name,
+            scale_offset: projection.measure.measure.scale_offset,
+            lateral_offset_m: projection.orthogonal_offset,
+            distance_m: projection.orthogonal_offset.abs(),
+        })
+    }
+
+    /// Map-matches an ordered sequence of positions, such as a GNSS log, onto the LRMs.
+    /// The measures are monotonic along each LRM.
+    pub fn project_trace(&self, points: &[Point], options: TraceOptions) -> Vec<TraceMatch> {
+        with_any_lrs!(&self.lrs, lrs => lrs.project_trace(points, options))
     }
 }
 
+// A curve of the same implementation as `curve`, such as a part of it
+fn curve_like<CurveImpl: Curve>(curve: &CurveImpl, geom: LineString) -> CurveImpl {
+    CurveImpl::new(geom, curve.max_extent())
+}
+
+#[cfg(feature = "geojson")]
+fn geojson_feature(geometry: geojson::Value, properties: geojson::JsonObject) -> geojson::Feature {
+    geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(geometry)),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+#[cfg(feature = "geojson")]
+fn geojson_collection(features: impl Iterator<Item = geojson::Feature>) -> String {
+    geojson::FeatureCollection {
+        bbox: None,
+        features: features.collect(),
+        foreign_members: None,
+    }
+    .to_string()
+}
+
+#[cfg(feature = "geojson")]
+fn geojson_linestring(coords: &[Coord]) -> geojson::Value {
+    geojson::Value::LineString(coords.iter().map(|c| vec![c.x, c.y]).collect())
+}
+
+#[cfg(feature = "geojson")]
+fn geojson_properties(properties: &Properties) -> geojson::JsonObject {
+    properties
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone().into()))
+        .collect()
+}
+
+// Inserts `anchor_name` and `scale_offset`, or `from_anchor_name` and `from_scale_offset` with the prefix `from`
+#[cfg(feature = "geojson")]
+fn insert_measure(properties: &mut geojson::JsonObject, prefix: &str, measure: &LrmScaleMeasure) {
+    let key = |name: &str| match prefix {
+        "" => name.to_owned(),
+        prefix => format!("{prefix}_{name}"),
+    };
+    properties.insert(key("anchor_name"), measure.anchor_name.clone().into());
+    properties.insert(key("scale_offset"), measure.scale_offset.into());
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
-    use geo::{Coord, coord, point};
+    use geo::{Coord, Intersects, coord, point, polygon};
 
     use crate::builder::{AnchorOnLrm, Builder, SegmentOfTraversal};
+    use crate::curves::CurveError;
+    use crate::error_code::LrsErrorCode;
+    use crate::lrm_scale::{LrmScaleError, LrmScaleMeasure};
+    use crate::lrs::{GeometryType, LoadFilter, LrsError};
+    use crate::lrs_ext::{ExtLrs, ExtLrsError};
     use crate::{lrs, properties};
 
     fn build_lrm(builder: &mut Builder, name: &str, coords: &[Coord]) {
-        let segment_index = builder.add_segment("name", coords, 0, 1);
+        let start = builder.add_node("start", coords[0], properties!());
+        let end = builder.add_node("end", coords[coords.len() - 1], properties!());
+        let segment_index = builder.add_segment("name", coords, start, end);
         let sot = SegmentOfTraversal {
             segment_index,
             reversed: false,
@@ -166,4 +1060,399 @@ pub(crate) mod tests {
         let nearest3 = lrs.lookup_lrms(point! {x:2.35, y:48.98});
         assert!(nearest3.is_empty());
     }
+
+    #[test]
+    fn open_and_close() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        let path = std::env::temp_dir().join(format!("liblrs-ext-{}.lrs", std::process::id()));
+        std::fs::write(&path, b.build_data(properties!())).unwrap();
+
+        let mut lrs = ExtLrs::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(lrs.lrm_len(), 1);
+        lrs.close();
+        assert_eq!(lrs.lrm_len(), 0);
+        assert!(ExtLrs::open(&path).is_err());
+    }
+
+    #[test]
+    fn load_schematic() {
+        let data = lrs::tests::lrs_with_segments().to_bytes();
+        let lrs = ExtLrs::load(&data).unwrap();
+        assert_eq!(lrs.geometry_type(), GeometryType::Schematic);
+        assert_eq!(lrs.lrm_len(), 2);
+        // The planar curve measures in the unit of the coordinates
+        let located = lrs.locate(point! {x: 50., y: 0.5}, 1.).unwrap();
+        assert!((located.distance_m - 0.5).abs() < 1e-9);
+
+        let forced = ExtLrs::load_as(&data, Some(GeometryType::Geographic)).unwrap();
+        assert_eq!(forced.geometry_type(), GeometryType::Geographic);
+        assert!(forced.locate(point! {x: 50., y: 0.5}, 1.).is_none());
+    }
+
+    #[test]
+    fn load_filtered() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        build_lrm(&mut b, "lrm2", &[coord! {x:0., y:1.}, coord! {x:2., y:1.}]);
+        let data = b.build_data(properties!()).to_vec();
+
+        let filter = LoadFilter::LrmIds(vec!["lrm2".to_owned()]);
+        let lrs = ExtLrs::load_filtered(&data, &filter).unwrap();
+        assert_eq!(lrs.lrm_len(), 1);
+        assert_eq!(lrs.get_lrm_scale_id(0).unwrap(), "lrm2");
+    }
+
+    #[test]
+    fn locate() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        build_lrm(&mut b, "lrm2", &[coord! {x:0., y:1.}, coord! {x:2., y:1.}]);
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let located = lrs.locate(point! {x: 0.5, y: -0.0005}, 100.).unwrap();
+        assert_eq!(located.lrm_index, 0);
+        assert_eq!(located.lrm_id, "lrm1");
+        assert_eq!(located.anchor_name, "start");
+        assert!((located.scale_offset - 0.25).abs() < 1e-3);
+        assert!(located.lateral_offset_m < 0.);
+        assert!((located.distance_m - 55.3).abs() < 1.);
+        assert_eq!(located.distance_m, located.lateral_offset_m.abs());
+
+        assert!(lrs.locate(point! {x: 0.5, y: -0.0005}, 10.).is_none());
+
+        let measure = LrmScaleMeasure::new("start", 0.75);
+        let point = lrs.resolve_with_offset(0, &measure, 3.5).unwrap();
+        let located = lrs.locate(point, 10.).unwrap();
+        assert!((located.scale_offset - 0.75).abs() < 1e-6);
+        assert!((located.lateral_offset_m - 3.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn resolve_many() {
+        let mut b = Builder::new();
+        let coords = [
+            coord! {x:0., y:0.},
+            coord! {x:1., y:0.},
+            coord! {x:1., y:1.},
+        ];
+        build_lrm(&mut b, "lrm1", &coords);
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let measures = [
+            LrmScaleMeasure::new("start", 0.3),
+            LrmScaleMeasure::new("unknown", 0.),
+            LrmScaleMeasure::new("end", 0.),
+            LrmScaleMeasure::new("start", -1.),
+        ];
+        let points = lrs.resolve_many(0, &measures);
+        assert_eq!(points.len(), measures.len());
+        for (point, measure) in points.into_iter().zip(&measures) {
+            assert_eq!(point, lrs.resolve(0, measure));
+        }
+        assert!(lrs.resolve_many(0, &measures)[1].is_err());
+        assert!(lrs.resolve_many(0, &[]).is_empty());
+
+        let points = lrs.resolve_many(1, &measures[..1]);
+        assert_eq!(points, [Err(ExtLrsError::LrmNotFound(1))]);
+    }
+
+    #[test]
+    fn resolve_offset_and_heading() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        let lrs = b.build_lrs(properties!()).unwrap();
+        let begin = LrmScaleMeasure::new("start", 0.25);
+        let end = LrmScaleMeasure::new("start", 0.75);
+
+        // The offset is on the left of increasing measures, whatever the order of the measures
+        for (from, to) in [(&begin, &end), (&end, &begin)] {
+            let range = lrs.resolve_range(0, from, to, Some(50.)).unwrap();
+            assert_eq!(range.len(), 2);
+            for coord in range {
+                let located = lrs.locate(coord.into(), 100.).unwrap();
+                assert!((located.lateral_offset_m - 50.).abs() < 1e-6);
+            }
+        }
+
+        let oriented = lrs.resolve_oriented(0, &begin).unwrap();
+        assert_eq!(oriented.point, lrs.resolve(0, &begin).unwrap());
+        assert!((oriented.heading - 90.).abs() < 1e-6);
+
+        let range = lrs.resolve_range(0, &begin, &end, Some(-50.)).unwrap();
+        assert!(range.iter().all(|coord| coord.y < 0.));
+        let range = lrs.resolve_range(0, &begin, &end, None).unwrap();
+        assert!(range.iter().all(|coord| coord.y == 0.));
+    }
+
+    #[test]
+    fn measure_ticks() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let ticks = lrs.measure_ticks(0, 0.25, 10.).unwrap();
+        let measures: Vec<_> = ticks
+            .iter()
+            .map(|tick| (tick.measure.anchor_name.as_str(), tick.measure.scale_offset))
+            .collect();
+        assert_eq!(
+            measures,
+            [
+                ("start", 0.),
+                ("start", 0.25),
+                ("start", 0.5),
+                ("start", 0.75),
+                ("end", 0.)
+            ]
+        );
+        let tick = &ticks[2];
+        assert!((tick.point.x() - 1.).abs() < 1e-9);
+        assert!((tick.heading - 90.).abs() < 1e-6);
+        assert!(tick.left.y() > 0. && tick.right.y() < 0.);
+        assert!((tick.left.y() + tick.right.y()).abs() < 1e-9);
+
+        assert!(lrs.measure_ticks(0, 0., 10.).unwrap().is_empty());
+        assert!(lrs.measure_ticks(1, 0.25, 10.).is_err());
+    }
+
+    #[test]
+    fn anchor_markers() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let markers = lrs.anchor_markers(0).unwrap();
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].name.as_deref(), Some("start"));
+        assert_eq!(markers[1].measure.anchor_name, "end");
+        assert_eq!(markers[1].measure.scale_offset, 0.);
+        assert!((markers[1].point.x() - 2.).abs() < 1e-9);
+        assert!(
+            markers
+                .iter()
+                .all(|marker| (marker.heading - 90.).abs() < 1e-6)
+        );
+        assert!(lrs.anchor_markers(1).is_err());
+
+        #[cfg(feature = "geojson")]
+        {
+            let geojson = lrs.anchor_markers_geojson(0).unwrap();
+            assert!(geojson.starts_with(r#"{"type":"FeatureCollection""#));
+            assert_eq!(geojson.matches(r#""type":"Point""#).count(), 2);
+            assert!(geojson.contains(r#""anchor_name":"end""#));
+
+            let geojson = lrs.measure_ticks_geojson(0, 0.25, 10.).unwrap();
+            assert_eq!(geojson.matches(r#""type":"LineString""#).count(), 5);
+
+            let geojson = lrs.lrm_geojson(0).unwrap();
+            assert!(geojson.contains(r#""id":"lrm1""#));
+            let (start, end) = (
+                LrmScaleMeasure::new("start", 0.),
+                LrmScaleMeasure::new("start", 0.5),
+            );
+            let geojson = lrs.range_geojson(0, &start, &end, None).unwrap();
+            assert!(geojson.contains(r#""to_scale_offset":0.5"#));
+            assert!(lrs.lrm_geojson(1).is_err());
+        }
+
+        let start = LrmScaleMeasure::new("start", 0.);
+        let anchors = lrs.anchors_between(0, &start, &start).unwrap();
+        assert_eq!(anchors.len(), 1);
+        assert_eq!(anchors[0].measure.anchor_name, "start");
+        let unknown = LrmScaleMeasure::new("unknown", 0.);
+        assert!(lrs.anchors_between(0, &start, &unknown).is_err());
+    }
+
+    #[test]
+    fn anchor_surveys() {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 0.01, y: 0.}, properties!());
+        let geometry = geo::LineString::from(vec![coord! {x: 0., y: 0.}, coord! {x: 0.01, y: 0.}]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        // About 11 m north of the traversal
+        let surveyed = coord! {x: 0.005, y: 0.0001};
+        let anchors = [
+            AnchorOnLrm {
+                anchor_index: b.add_anchor("a", Some("a"), surveyed, properties!()),
+                distance_along_lrm: 0.,
+            },
+            AnchorOnLrm {
+                anchor_index: b.add_projected_anchor("b", Some("b"), 1., properties!()),
+                distance_along_lrm: 500.,
+            },
+        ];
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+        let data = b.build_data(properties!()).to_vec();
+
+        for lrs in [
+            ExtLrs::load(&data).unwrap(),
+            ExtLrs::load(&ExtLrs::load(&data).unwrap().to_bytes()).unwrap(),
+        ] {
+            let surveys = lrs.anchor_surveys(0).unwrap();
+            assert_eq!(surveys[0].surveyed, Some(surveyed.into()));
+            assert!((surveys[0].projected.y()).abs() < 1e-9);
+            assert!((surveys[0].offset.unwrap() - 11.1).abs() < 0.1);
+            assert_eq!(surveys[1].surveyed, None);
+            assert_eq!(surveys[1].offset, None);
+        }
+    }
+
+    #[test]
+    fn measure_ranges_in_polygon() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        // The measures decrease along the geometry
+        let (start, end) = (coord! {x:0., y:1.}, coord! {x:2., y:1.});
+        let nodes = (
+            b.add_node("start", start, properties!()),
+            b.add_node("end", end, properties!()),
+        );
+        let segment_index = b.add_segment("name", &[start, end], nodes.0, nodes.1);
+        let sot = SegmentOfTraversal {
+            segment_index,
+            reversed: false,
+        };
+        let traversal_index = b.add_traversal("lrm2", &[sot]);
+        let anchors = [
+            ("start", coord! {x:2., y:1.}, 0.),
+            ("end", coord! {x:0., y:1.}, 1.),
+        ]
+        .map(|(name, coord, distance_along_lrm)| AnchorOnLrm {
+            anchor_index: b.add_anchor(name, Some(name), coord, properties!()),
+            distance_along_lrm,
+        });
+        b.add_lrm("lrm2", traversal_index, &anchors, properties!());
+        let lrs = b.build_lrs(properties!()).unwrap();
+        assert!(!lrs.measures_increase(1).unwrap());
+
+        let area = polygon![(x: 0.5, y: -1.), (x: 1.5, y: -1.), (x: 1.5, y: 2.), (x: 0.5, y: 2.)];
+        // The same measures, given from the last named anchor before each position along the curve
+        for (lrm_index, anchor_name, from, to) in
+            [(0, "start", 0.25, 0.75), (1, "end", -0.75, -0.25)]
+        {
+            let ranges = lrs.measure_ranges_in_polygon(lrm_index, &area).unwrap();
+            assert_eq!(ranges.len(), 1);
+            assert_eq!(ranges[0].from.anchor_name, anchor_name);
+            assert!((ranges[0].from.scale_offset - from).abs() < 1e-3);
+            assert_eq!(ranges[0].to.anchor_name, anchor_name);
+            assert!((ranges[0].to.scale_offset - to).abs() < 1e-3);
+        }
+
+        let area = polygon![(x: 3., y: -1.), (x: 4., y: -1.), (x: 4., y: 2.)];
+        assert!(lrs.measure_ranges_in_polygon(0, &area).unwrap().is_empty());
+        assert!(lrs.measure_ranges_in_polygon(2, &area).is_err());
+    }
+
+    #[test]
+    fn measure_at() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let measure = LrmScaleMeasure::new("start", 0.25);
+        let scale = &lrs.lrms()[0].scale;
+        let measure = lrs
+            .measure_at(0, scale.locate_point(&measure).unwrap())
+            .unwrap();
+        assert_eq!(measure.anchor_name, "start");
+        assert!((measure.scale_offset - 0.25).abs() < 1e-9);
+
+        // The measure is given from the last named anchor before the position
+        let measure = lrs.measure_at(0, 0.9).unwrap();
+        assert_eq!(measure.anchor_name, "start");
+        assert!((measure.scale_offset - 0.9).abs() < 1e-9);
+
+        assert_eq!(
+            lrs.measure_at(0, f64::NAN),
+            Err(ExtLrsError::Curve(CurveError::NotOnTheCurve))
+        );
+        assert_eq!(lrs.measure_at(1, 0.5), Err(ExtLrsError::LrmNotFound(1)));
+        assert_eq!(
+            lrs.lookup(point! {x: 0., y: 0.}, 1).err(),
+            Some(ExtLrsError::LrmNotFound(1))
+        );
+    }
+
+    #[test]
+    fn get_lrm_bbox() {
+        let mut b = Builder::new();
+        let coords = [
+            coord! {x:0., y:0.},
+            coord! {x:2., y:0.},
+            coord! {x:2., y:1.},
+        ];
+        build_lrm(&mut b, "lrm1", &coords);
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let bbox = lrs.get_lrm_bbox(0).unwrap();
+        assert_eq!(bbox.bbox.min(), coord! {x:0., y:0.});
+        assert_eq!(bbox.bbox.max(), coord! {x:2., y:1.});
+        assert!(bbox.bbox.intersects(&bbox.center));
+        assert!(bbox.center.y().abs() < 1e-9);
+        assert_eq!(lrs.get_lrm_bbox(1), Err(ExtLrsError::LrmNotFound(1)));
+    }
+
+    #[test]
+    fn search_lrms() {
+        let mut b = Builder::new();
+        for id in ["line-1", "line-12", "branch"] {
+            build_lrm(&mut b, id, &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        }
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let ids = |query, limit| {
+            lrs.search_lrms(query, limit)
+                .into_iter()
+                .map(|found| found.lrm_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("LINE-1", 10), ["line-1", "line-12"]);
+        assert_eq!(ids("line", 1), ["line-1"]);
+        assert_eq!(ids("ranch", 10), ["branch"]);
+        assert_eq!(ids("brnach", 10), ["branch"]);
+        assert!(ids("tunnel", 10).is_empty());
+
+        let found = lrs.search_lrms("line-1", 10);
+        assert_eq!((found[0].lrm_index, found[0].score), (0, 1.));
+        assert!(found[1].score < 1.);
+    }
+
+    #[test]
+    fn error_codes() {
+        let mut b = Builder::new();
+        build_lrm(&mut b, "lrm1", &[coord! {x:0., y:0.}, coord! {x:2., y:0.}]);
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        let measure = LrmScaleMeasure::new("start", 0.5);
+        let error = lrs.resolve(3, &measure).unwrap_err();
+        assert_eq!(error, ExtLrsError::LrmNotFound(3));
+        let error = LrsErrorCode::from(error);
+        assert_eq!(error.code(), "LRM_NOT_FOUND");
+        assert_eq!(error.lrm_index(), Some(3));
+
+        let unknown = LrmScaleMeasure::new("km 42", 0.5);
+        let error = lrs.resolve_range(0, &measure, &unknown, None).unwrap_err();
+        assert_eq!(
+            error,
+            ExtLrsError::Measure {
+                lrm_index: 0,
+                anchor_name: "km 42".to_owned(),
+                source: LrmScaleError::UnknownAnchorName,
+            }
+        );
+        let error = LrsErrorCode::from(error);
+        assert_eq!(error.code(), "UNKNOWN_ANCHOR");
+        assert_eq!(error.lrm_index(), Some(0));
+        assert_eq!(error.anchor_name(), Some("km 42"));
+
+        let error = ExtLrs::load(b"not an lrs").map(|_| ()).unwrap_err();
+        assert!(matches!(
+            error,
+            ExtLrsError::Lrs(LrsError::InvalidArchive(_))
+        ));
+        assert_eq!(LrsErrorCode::from(error).code(), "INVALID_FILE");
+    }
 }
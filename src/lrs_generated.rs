@@ -183,6 +183,186 @@ impl<'a> flatbuffers::Verifiable for Direction {
 
 impl flatbuffers::SimpleToVerifyInSlice for Direction {}
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_TRAVERSAL_END: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_TRAVERSAL_END: i8 = 1;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_TRAVERSAL_END: [TraversalEnd; 2] = [
+  TraversalEnd::Start,
+  TraversalEnd::End,
+];
+
+/// An extremity of a traversal.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct TraversalEnd(pub i8);
+#[allow(non_upper_case_globals)]
+impl TraversalEnd {
+  pub const Start: Self = Self(0);
+  pub const End: Self = Self(1);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 1;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Start,
+    Self::End,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Start => Some("Start"),
+      Self::End => Some("End"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for TraversalEnd {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for TraversalEnd {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for TraversalEnd {
+    type Output = TraversalEnd;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for TraversalEnd {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for TraversalEnd {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for TraversalEnd {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_VALUE_TYPE: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_VALUE_TYPE: i8 = 3;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_VALUE_TYPE: [ValueType; 4] = [
+  ValueType::Text,
+  ValueType::Integer,
+  ValueType::Float,
+  ValueType::Boolean,
+];
+
+/// The type of the value of a TypedProperty.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ValueType(pub i8);
+#[allow(non_upper_case_globals)]
+impl ValueType {
+  pub const Text: Self = Self(0);
+  pub const Integer: Self = Self(1);
+  pub const Float: Self = Self(2);
+  pub const Boolean: Self = Self(3);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 3;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Text,
+    Self::Integer,
+    Self::Float,
+    Self::Boolean,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Text => Some("Text"),
+      Self::Integer => Some("Integer"),
+      Self::Float => Some("Float"),
+      Self::Boolean => Some("Boolean"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ValueType {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ValueType {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ValueType {
+    type Output = ValueType;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ValueType {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ValueType {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ValueType {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MIN_DISTANCE_UNIT: i8 = 0;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MAX_DISTANCE_UNIT: i8 = 1;
@@ -673,6 +853,15 @@ impl<'a> Lrs<'a> {
   pub const VT_LINEAR_REFERENCING_METHODS: flatbuffers::VOffsetT = 14;
   pub const VT_GEOMETRY_TYPE: flatbuffers::VOffsetT = 16;
   pub const VT_LRM_SPATIAL_INDEX: flatbuffers::VOffsetT = 18;
+  pub const VT_BUILD_INFO: flatbuffers::VOffsetT = 20;
+  pub const VT_FRAGMENT_SPATIAL_INDEX: flatbuffers::VOffsetT = 22;
+  pub const VT_FRAGMENT_TRAVERSALS: flatbuffers::VOffsetT = 24;
+  pub const VT_CONNECTIONS: flatbuffers::VOffsetT = 26;
+  pub const VT_POINT_EVENTS: flatbuffers::VOffsetT = 28;
+  pub const VT_LINEAR_EVENTS: flatbuffers::VOffsetT = 30;
+  pub const VT_CRS: flatbuffers::VOffsetT = 32;
+  pub const VT_SCHEMA_VERSION: flatbuffers::VOffsetT = 34;
+  pub const VT_COORDINATE_PRECISION: flatbuffers::VOffsetT = 36;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -684,6 +873,15 @@ impl<'a> Lrs<'a> {
     args: &'args LrsArgs<'args>
   ) -> flatbuffers::WIPOffset<Lrs<'bldr>> {
     let mut builder = LrsBuilder::new(_fbb);
+    builder.add_coordinate_precision(args.coordinate_precision);
+    builder.add_schema_version(args.schema_version);
+    if let Some(x) = args.crs { builder.add_crs(x); }
+    if let Some(x) = args.linear_events { builder.add_linear_events(x); }
+    if let Some(x) = args.point_events { builder.add_point_events(x); }
+    if let Some(x) = args.connections { builder.add_connections(x); }
+    if let Some(x) = args.fragment_traversals { builder.add_fragment_traversals(x); }
+    if let Some(x) = args.fragment_spatial_index { builder.add_fragment_spatial_index(x); }
+    if let Some(x) = args.build_info { builder.add_build_info(x); }
     if let Some(x) = args.lrm_spatial_index { builder.add_lrm_spatial_index(x); }
     if let Some(x) = args.linear_referencing_methods { builder.add_linear_referencing_methods(x); }
     if let Some(x) = args.anchors { builder.add_anchors(x); }
@@ -760,6 +958,82 @@ impl<'a> Lrs<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Lrs::VT_LRM_SPATIAL_INDEX, None)}
   }
+  /// How the file was produced. Helps to diagnose issues with a file.
+  #[inline]
+  pub fn build_info(&self) -> Option<BuildInfo<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<BuildInfo>>(Lrs::VT_BUILD_INFO, None)}
+  }
+  /// The spatial index of the fragments of the traversals: each fragment is a few consecutive points of a curve.
+  /// It is more precise than `lrm_spatial_index` to find the traversals near a point.
+  /// The serialization is the same as `lrm_spatial_index`.
+  #[inline]
+  pub fn fragment_spatial_index(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Lrs::VT_FRAGMENT_SPATIAL_INDEX, None)}
+  }
+  /// For every item of `fragment_spatial_index`, the index of the traversal it belongs to.
+  #[inline]
+  pub fn fragment_traversals(&self) -> Option<flatbuffers::Vector<'a, u32>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u32>>>(Lrs::VT_FRAGMENT_TRAVERSALS, None)}
+  }
+  /// The allowed movements between traversals at the nodes of the network.
+  #[inline]
+  pub fn connections(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Connection<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Connection>>>>(Lrs::VT_CONNECTIONS, None)}
+  }
+  /// Objects located at a single measure, such as signals or level crossings.
+  #[inline]
+  pub fn point_events(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PointEvent<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PointEvent>>>>(Lrs::VT_POINT_EVENTS, None)}
+  }
+  /// Objects located over a range of measures, such as bridges or speed restrictions.
+  #[inline]
+  pub fn linear_events(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<LinearEvent<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<LinearEvent>>>>(Lrs::VT_LINEAR_EVENTS, None)}
+  }
+  /// Coordinate reference system of the coordinates, as an authority code such as `EPSG:4326`.
+  #[inline]
+  pub fn crs(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Lrs::VT_CRS, None)}
+  }
+  /// Version of the schema used to write the file. It is 0 for the files written before it was recorded.
+  /// It is incremented when a change of the schema requires a migration of the previous files.
+  #[inline]
+  pub fn schema_version(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Lrs::VT_SCHEMA_VERSION, Some(0)).unwrap()}
+  }
+  /// When positive, the geometries of the segments are quantized to multiples of this step,
+  /// in the unit of the coordinates (e.g. 1e-7 degrees is about 1 cm). See `Segment.quantized_geometry`.
+  #[inline]
+  pub fn coordinate_precision(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(Lrs::VT_COORDINATE_PRECISION, Some(0.0)).unwrap()}
+  }
 }
 
 impl flatbuffers::Verifiable for Lrs<'_> {
@@ -777,6 +1051,15 @@ impl flatbuffers::Verifiable for Lrs<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<LinearReferencingMethod>>>>("linear_referencing_methods", Self::VT_LINEAR_REFERENCING_METHODS, false)?
      .visit_field::<GeometryType>("geometry_type", Self::VT_GEOMETRY_TYPE, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("lrm_spatial_index", Self::VT_LRM_SPATIAL_INDEX, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<BuildInfo>>("build_info", Self::VT_BUILD_INFO, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("fragment_spatial_index", Self::VT_FRAGMENT_SPATIAL_INDEX, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("fragment_traversals", Self::VT_FRAGMENT_TRAVERSALS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Connection>>>>("connections", Self::VT_CONNECTIONS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<PointEvent>>>>("point_events", Self::VT_POINT_EVENTS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<LinearEvent>>>>("linear_events", Self::VT_LINEAR_EVENTS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("crs", Self::VT_CRS, false)?
+     .visit_field::<u32>("schema_version", Self::VT_SCHEMA_VERSION, false)?
+     .visit_field::<f64>("coordinate_precision", Self::VT_COORDINATE_PRECISION, false)?
      .finish();
     Ok(())
   }
@@ -790,6 +1073,15 @@ pub struct LrsArgs<'a> {
     pub linear_referencing_methods: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<LinearReferencingMethod<'a>>>>>,
     pub geometry_type: GeometryType,
     pub lrm_spatial_index: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub build_info: Option<flatbuffers::WIPOffset<BuildInfo<'a>>>,
+    pub fragment_spatial_index: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub fragment_traversals: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
+    pub connections: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Connection<'a>>>>>,
+    pub point_events: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PointEvent<'a>>>>>,
+    pub linear_events: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<LinearEvent<'a>>>>>,
+    pub crs: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub schema_version: u32,
+    pub coordinate_precision: f64,
 }
 impl<'a> Default for LrsArgs<'a> {
   #[inline]
@@ -803,6 +1095,15 @@ impl<'a> Default for LrsArgs<'a> {
       linear_referencing_methods: None,
       geometry_type: GeometryType::Geographic,
       lrm_spatial_index: None,
+      build_info: None,
+      fragment_spatial_index: None,
+      fragment_traversals: None,
+      connections: None,
+      point_events: None,
+      linear_events: None,
+      crs: None,
+      schema_version: 0,
+      coordinate_precision: 0.0,
     }
   }
 }
@@ -845,6 +1146,42 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> LrsBuilder<'a, 'b, A> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_LRM_SPATIAL_INDEX, lrm_spatial_index);
   }
   #[inline]
+  pub fn add_build_info(&mut self, build_info: flatbuffers::WIPOffset<BuildInfo<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_BUILD_INFO, build_info);
+  }
+  #[inline]
+  pub fn add_fragment_spatial_index(&mut self, fragment_spatial_index: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u8>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_FRAGMENT_SPATIAL_INDEX, fragment_spatial_index);
+  }
+  #[inline]
+  pub fn add_fragment_traversals(&mut self, fragment_traversals: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_FRAGMENT_TRAVERSALS, fragment_traversals);
+  }
+  #[inline]
+  pub fn add_connections(&mut self, connections: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Connection<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_CONNECTIONS, connections);
+  }
+  #[inline]
+  pub fn add_point_events(&mut self, point_events: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<PointEvent<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_POINT_EVENTS, point_events);
+  }
+  #[inline]
+  pub fn add_linear_events(&mut self, linear_events: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<LinearEvent<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_LINEAR_EVENTS, linear_events);
+  }
+  #[inline]
+  pub fn add_crs(&mut self, crs: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Lrs::VT_CRS, crs);
+  }
+  #[inline]
+  pub fn add_schema_version(&mut self, schema_version: u32) {
+    self.fbb_.push_slot::<u32>(Lrs::VT_SCHEMA_VERSION, schema_version, 0);
+  }
+  #[inline]
+  pub fn add_coordinate_precision(&mut self, coordinate_precision: f64) {
+    self.fbb_.push_slot::<f64>(Lrs::VT_COORDINATE_PRECISION, coordinate_precision, 0.0);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> LrsBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
     LrsBuilder {
@@ -870,214 +1207,1144 @@ impl core::fmt::Debug for Lrs<'_> {
       ds.field("linear_referencing_methods", &self.linear_referencing_methods());
       ds.field("geometry_type", &self.geometry_type());
       ds.field("lrm_spatial_index", &self.lrm_spatial_index());
+      ds.field("build_info", &self.build_info());
+      ds.field("fragment_spatial_index", &self.fragment_spatial_index());
+      ds.field("fragment_traversals", &self.fragment_traversals());
+      ds.field("connections", &self.connections());
+      ds.field("point_events", &self.point_events());
+      ds.field("linear_events", &self.linear_events());
+      ds.field("crs", &self.crs());
+      ds.field("schema_version", &self.schema_version());
+      ds.field("coordinate_precision", &self.coordinate_precision());
       ds.finish()
   }
 }
-pub enum SegmentOffset {}
+pub enum BuildInfoOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
-/// A continuous link between two network nodes. Segments can be located in space.
-/// It could be a section of roads between intersections, a piece of railway tracks without switches, a continuous piece of sewer pipe.
-/// Segments are directed: one of its ends is its begining, and the other one its end.
-pub struct Segment<'a> {
+/// Provenance of a file, recorded by the builder.
+pub struct BuildInfo<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for Segment<'a> {
-  type Inner = Segment<'a>;
+impl<'a> flatbuffers::Follow<'a> for BuildInfo<'a> {
+  type Inner = BuildInfo<'a>;
   #[inline]
   unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
     Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
 }
 
-impl<'a> Segment<'a> {
-  pub const VT_ID: flatbuffers::VOffsetT = 4;
-  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 6;
-  pub const VT_GEOMETRY: flatbuffers::VOffsetT = 8;
-  pub const VT_START_NODE_INDEX: flatbuffers::VOffsetT = 10;
-  pub const VT_END_NODE_INDEX: flatbuffers::VOffsetT = 12;
+impl<'a> BuildInfo<'a> {
+  pub const VT_LIBLRS_VERSION: flatbuffers::VOffsetT = 4;
+  pub const VT_OPTIONS: flatbuffers::VOffsetT = 6;
+  pub const VT_SOURCES: flatbuffers::VOffsetT = 8;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-    Segment { _tab: table }
+    BuildInfo { _tab: table }
   }
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args SegmentArgs<'args>
-  ) -> flatbuffers::WIPOffset<Segment<'bldr>> {
-    let mut builder = SegmentBuilder::new(_fbb);
-    builder.add_end_node_index(args.end_node_index);
-    builder.add_start_node_index(args.start_node_index);
-    if let Some(x) = args.geometry { builder.add_geometry(x); }
-    if let Some(x) = args.properties { builder.add_properties(x); }
-    if let Some(x) = args.id { builder.add_id(x); }
+    args: &'args BuildInfoArgs<'args>
+  ) -> flatbuffers::WIPOffset<BuildInfo<'bldr>> {
+    let mut builder = BuildInfoBuilder::new(_fbb);
+    if let Some(x) = args.sources { builder.add_sources(x); }
+    if let Some(x) = args.options { builder.add_options(x); }
+    if let Some(x) = args.liblrs_version { builder.add_liblrs_version(x); }
     builder.finish()
   }
 
 
+  /// Version of liblrs used to build the file.
   #[inline]
-  pub fn id(&self) -> &'a str {
-    // Safety:
-    // Created from valid Table for this object
-    // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Segment::VT_ID, None).unwrap()}
-  }
-  #[inline]
-  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>> {
-    // Safety:
-    // Created from valid Table for this object
-    // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property>>>>(Segment::VT_PROPERTIES, None)}
-  }
-  #[inline]
-  pub fn geometry(&self) -> flatbuffers::Vector<'a, Point> {
+  pub fn liblrs_version(&self) -> Option<&'a str> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, Point>>>(Segment::VT_GEOMETRY, None).unwrap()}
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(BuildInfo::VT_LIBLRS_VERSION, None)}
   }
+  /// Options given to the builder.
   #[inline]
-  pub fn start_node_index(&self) -> u64 {
+  pub fn options(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<u64>(Segment::VT_START_NODE_INDEX, Some(0)).unwrap()}
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property>>>>(BuildInfo::VT_OPTIONS, None)}
   }
+  /// Identifiers of the source datasets, such as the path of an OpenStreetMap extract.
   #[inline]
-  pub fn end_node_index(&self) -> u64 {
+  pub fn sources(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<u64>(Segment::VT_END_NODE_INDEX, Some(0)).unwrap()}
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(BuildInfo::VT_SOURCES, None)}
   }
 }
 
-impl flatbuffers::Verifiable for Segment<'_> {
+impl flatbuffers::Verifiable for BuildInfo<'_> {
   #[inline]
   fn run_verifier(
     v: &mut flatbuffers::Verifier, pos: usize
   ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
     use self::flatbuffers::Verifiable;
     v.visit_table(pos)?
-     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("id", Self::VT_ID, true)?
-     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Property>>>>("properties", Self::VT_PROPERTIES, false)?
-     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, Point>>>("geometry", Self::VT_GEOMETRY, true)?
-     .visit_field::<u64>("start_node_index", Self::VT_START_NODE_INDEX, false)?
-     .visit_field::<u64>("end_node_index", Self::VT_END_NODE_INDEX, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("liblrs_version", Self::VT_LIBLRS_VERSION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Property>>>>("options", Self::VT_OPTIONS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("sources", Self::VT_SOURCES, false)?
      .finish();
     Ok(())
   }
 }
-pub struct SegmentArgs<'a> {
-    pub id: Option<flatbuffers::WIPOffset<&'a str>>,
-    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
-    pub geometry: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, Point>>>,
-    pub start_node_index: u64,
-    pub end_node_index: u64,
+pub struct BuildInfoArgs<'a> {
+    pub liblrs_version: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub options: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
+    pub sources: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
 }
-impl<'a> Default for SegmentArgs<'a> {
+impl<'a> Default for BuildInfoArgs<'a> {
   #[inline]
   fn default() -> Self {
-    SegmentArgs {
-      id: None, // required field
-      properties: None,
-      geometry: None, // required field
-      start_node_index: 0,
-      end_node_index: 0,
+    BuildInfoArgs {
+      liblrs_version: None,
+      options: None,
+      sources: None,
     }
   }
 }
 
-pub struct SegmentBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+pub struct BuildInfoBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SegmentBuilder<'a, 'b, A> {
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> BuildInfoBuilder<'a, 'b, A> {
   #[inline]
-  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<&'b  str>) {
+  pub fn add_liblrs_version(&mut self, liblrs_version: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(BuildInfo::VT_LIBLRS_VERSION, liblrs_version);
+  }
+  #[inline]
+  pub fn add_options(&mut self, options: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Property<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(BuildInfo::VT_OPTIONS, options);
+  }
+  #[inline]
+  pub fn add_sources(&mut self, sources: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(BuildInfo::VT_SOURCES, sources);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> BuildInfoBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    BuildInfoBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<BuildInfo<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for BuildInfo<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("BuildInfo");
+      ds.field("liblrs_version", &self.liblrs_version());
+      ds.field("options", &self.options());
+      ds.field("sources", &self.sources());
+      ds.finish()
+  }
+}
+pub enum SegmentOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+/// A continuous link between two network nodes. Segments can be located in space.
+/// It could be a section of roads between intersections, a piece of railway tracks without switches, a continuous piece of sewer pipe.
+/// Segments are directed: one of its ends is its begining, and the other one its end.
+pub struct Segment<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Segment<'a> {
+  type Inner = Segment<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Segment<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 6;
+  pub const VT_GEOMETRY: flatbuffers::VOffsetT = 8;
+  pub const VT_START_NODE_INDEX: flatbuffers::VOffsetT = 10;
+  pub const VT_END_NODE_INDEX: flatbuffers::VOffsetT = 12;
+  pub const VT_QUANTIZED_GEOMETRY: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Segment { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args SegmentArgs<'args>
+  ) -> flatbuffers::WIPOffset<Segment<'bldr>> {
+    let mut builder = SegmentBuilder::new(_fbb);
+    builder.add_end_node_index(args.end_node_index);
+    builder.add_start_node_index(args.start_node_index);
+    if let Some(x) = args.quantized_geometry { builder.add_quantized_geometry(x); }
+    if let Some(x) = args.geometry { builder.add_geometry(x); }
+    if let Some(x) = args.properties { builder.add_properties(x); }
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> &'a str {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Segment::VT_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property>>>>(Segment::VT_PROPERTIES, None)}
+  }
+  #[inline]
+  pub fn geometry(&self) -> flatbuffers::Vector<'a, Point> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, Point>>>(Segment::VT_GEOMETRY, None).unwrap()}
+  }
+  #[inline]
+  pub fn start_node_index(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Segment::VT_START_NODE_INDEX, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn end_node_index(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Segment::VT_END_NODE_INDEX, Some(0)).unwrap()}
+  }
+  /// Set instead of `geometry`, that is then empty, when `Lrs.coordinate_precision` is positive.
+  /// The x and y of the first point as multiples of the precision, then the difference with the previous point for each other point.
+  #[inline]
+  pub fn quantized_geometry(&self) -> Option<flatbuffers::Vector<'a, i32>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, i32>>>(Segment::VT_QUANTIZED_GEOMETRY, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Segment<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("id", Self::VT_ID, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Property>>>>("properties", Self::VT_PROPERTIES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, Point>>>("geometry", Self::VT_GEOMETRY, true)?
+     .visit_field::<u64>("start_node_index", Self::VT_START_NODE_INDEX, false)?
+     .visit_field::<u64>("end_node_index", Self::VT_END_NODE_INDEX, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, i32>>>("quantized_geometry", Self::VT_QUANTIZED_GEOMETRY, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct SegmentArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
+    pub geometry: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, Point>>>,
+    pub start_node_index: u64,
+    pub end_node_index: u64,
+    pub quantized_geometry: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, i32>>>,
+}
+impl<'a> Default for SegmentArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    SegmentArgs {
+      id: None, // required field
+      properties: None,
+      geometry: None, // required field
+      start_node_index: 0,
+      end_node_index: 0,
+      quantized_geometry: None,
+    }
+  }
+}
+
+pub struct SegmentBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SegmentBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<&'b  str>) {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Segment::VT_ID, id);
   }
   #[inline]
-  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Property<'b >>>>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Segment::VT_PROPERTIES, properties);
+  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Property<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Segment::VT_PROPERTIES, properties);
+  }
+  #[inline]
+  pub fn add_geometry(&mut self, geometry: flatbuffers::WIPOffset<flatbuffers::Vector<'b , Point>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Segment::VT_GEOMETRY, geometry);
+  }
+  #[inline]
+  pub fn add_start_node_index(&mut self, start_node_index: u64) {
+    self.fbb_.push_slot::<u64>(Segment::VT_START_NODE_INDEX, start_node_index, 0);
+  }
+  #[inline]
+  pub fn add_end_node_index(&mut self, end_node_index: u64) {
+    self.fbb_.push_slot::<u64>(Segment::VT_END_NODE_INDEX, end_node_index, 0);
+  }
+  #[inline]
+  pub fn add_quantized_geometry(&mut self, quantized_geometry: flatbuffers::WIPOffset<flatbuffers::Vector<'b , i32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Segment::VT_QUANTIZED_GEOMETRY, quantized_geometry);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> SegmentBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    SegmentBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Segment<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, Segment::VT_ID,"id");
+    self.fbb_.required(o, Segment::VT_GEOMETRY,"geometry");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Segment<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Segment");
+      ds.field("id", &self.id());
+      ds.field("properties", &self.properties());
+      ds.field("geometry", &self.geometry());
+      ds.field("start_node_index", &self.start_node_index());
+      ds.field("end_node_index", &self.end_node_index());
+      ds.field("quantized_geometry", &self.quantized_geometry());
+      ds.finish()
+  }
+}
+pub enum NodeOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+/// Nodes are connections between segment endpoints.
+/// A node can have a single connection (at the end of a dead end), or multiple segment connections (at an intersection).
+/// Nodes can be used to model point of interest in a network, such as road intersections, railway switches, the end of a dead ends.
+pub struct Node<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Node<'a> {
+  type Inner = Node<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Node<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 6;
+  pub const VT_GEOMETRY: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Node { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args NodeArgs<'args>
+  ) -> flatbuffers::WIPOffset<Node<'bldr>> {
+    let mut builder = NodeBuilder::new(_fbb);
+    if let Some(x) = args.geometry { builder.add_geometry(x); }
+    if let Some(x) = args.properties { builder.add_properties(x); }
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> &'a str {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Node::VT_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property>>>>(Node::VT_PROPERTIES, None)}
+  }
+  #[inline]
+  pub fn geometry(&self) -> Option<&'a Point> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Point>(Node::VT_GEOMETRY, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Node<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("id", Self::VT_ID, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Property>>>>("properties", Self::VT_PROPERTIES, false)?
+     .visit_field::<Point>("geometry", Self::VT_GEOMETRY, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct NodeArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
+    pub geometry: Option<&'a Point>,
+}
+impl<'a> Default for NodeArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    NodeArgs {
+      id: None, // required field
+      properties: None,
+      geometry: None,
+    }
+  }
+}
+
+pub struct NodeBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> NodeBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Node::VT_ID, id);
+  }
+  #[inline]
+  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Property<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Node::VT_PROPERTIES, properties);
+  }
+  #[inline]
+  pub fn add_geometry(&mut self, geometry: &Point) {
+    self.fbb_.push_slot_always::<&Point>(Node::VT_GEOMETRY, geometry);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> NodeBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    NodeBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Node<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, Node::VT_ID,"id");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Node<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Node");
+      ds.field("id", &self.id());
+      ds.field("properties", &self.properties());
+      ds.field("geometry", &self.geometry());
+      ds.finish()
+  }
+}
+pub enum TraversalOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Traversal<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Traversal<'a> {
+  type Inner = Traversal<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Traversal<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 6;
+  pub const VT_SEGMENTS: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Traversal { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args TraversalArgs<'args>
+  ) -> flatbuffers::WIPOffset<Traversal<'bldr>> {
+    let mut builder = TraversalBuilder::new(_fbb);
+    if let Some(x) = args.segments { builder.add_segments(x); }
+    if let Some(x) = args.properties { builder.add_properties(x); }
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> &'a str {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Traversal::VT_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property>>>>(Traversal::VT_PROPERTIES, None)}
+  }
+  #[inline]
+  pub fn segments(&self) -> flatbuffers::Vector<'a, SegmentOfTraversal> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, SegmentOfTraversal>>>(Traversal::VT_SEGMENTS, None).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Traversal<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("id", Self::VT_ID, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Property>>>>("properties", Self::VT_PROPERTIES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, SegmentOfTraversal>>>("segments", Self::VT_SEGMENTS, true)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct TraversalArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
+    pub segments: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, SegmentOfTraversal>>>,
+}
+impl<'a> Default for TraversalArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    TraversalArgs {
+      id: None, // required field
+      properties: None,
+      segments: None, // required field
+    }
+  }
+}
+
+pub struct TraversalBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TraversalBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Traversal::VT_ID, id);
+  }
+  #[inline]
+  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Property<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Traversal::VT_PROPERTIES, properties);
+  }
+  #[inline]
+  pub fn add_segments(&mut self, segments: flatbuffers::WIPOffset<flatbuffers::Vector<'b , SegmentOfTraversal>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Traversal::VT_SEGMENTS, segments);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> TraversalBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    TraversalBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Traversal<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, Traversal::VT_ID,"id");
+    self.fbb_.required(o, Traversal::VT_SEGMENTS,"segments");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Traversal<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Traversal");
+      ds.field("id", &self.id());
+      ds.field("properties", &self.properties());
+      ds.field("segments", &self.segments());
+      ds.finish()
+  }
+}
+pub enum ConnectionOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+/// At a node, a connection allows to go from an extremity of a traversal to an extremity of an other traversal.
+/// For instance on a railway switch, it is possible to go from the main track to both branches, but not from one branch to the other.
+pub struct Connection<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Connection<'a> {
+  type Inner = Connection<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Connection<'a> {
+  pub const VT_NODE_INDEX: flatbuffers::VOffsetT = 4;
+  pub const VT_FROM_TRAVERSAL_INDEX: flatbuffers::VOffsetT = 6;
+  pub const VT_FROM_END: flatbuffers::VOffsetT = 8;
+  pub const VT_TO_TRAVERSAL_INDEX: flatbuffers::VOffsetT = 10;
+  pub const VT_TO_END: flatbuffers::VOffsetT = 12;
+  pub const VT_BIDIRECTIONAL: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Connection { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ConnectionArgs
+  ) -> flatbuffers::WIPOffset<Connection<'bldr>> {
+    let mut builder = ConnectionBuilder::new(_fbb);
+    builder.add_to_traversal_index(args.to_traversal_index);
+    builder.add_from_traversal_index(args.from_traversal_index);
+    builder.add_node_index(args.node_index);
+    builder.add_bidirectional(args.bidirectional);
+    builder.add_to_end(args.to_end);
+    builder.add_from_end(args.from_end);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn node_index(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Connection::VT_NODE_INDEX, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn from_traversal_index(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Connection::VT_FROM_TRAVERSAL_INDEX, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn from_end(&self) -> TraversalEnd {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<TraversalEnd>(Connection::VT_FROM_END, Some(TraversalEnd::Start)).unwrap()}
+  }
+  #[inline]
+  pub fn to_traversal_index(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Connection::VT_TO_TRAVERSAL_INDEX, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn to_end(&self) -> TraversalEnd {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<TraversalEnd>(Connection::VT_TO_END, Some(TraversalEnd::Start)).unwrap()}
+  }
+  /// The connection can also be used from `to` to `from`.
+  #[inline]
+  pub fn bidirectional(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(Connection::VT_BIDIRECTIONAL, Some(true)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Connection<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("node_index", Self::VT_NODE_INDEX, false)?
+     .visit_field::<u64>("from_traversal_index", Self::VT_FROM_TRAVERSAL_INDEX, false)?
+     .visit_field::<TraversalEnd>("from_end", Self::VT_FROM_END, false)?
+     .visit_field::<u64>("to_traversal_index", Self::VT_TO_TRAVERSAL_INDEX, false)?
+     .visit_field::<TraversalEnd>("to_end", Self::VT_TO_END, false)?
+     .visit_field::<bool>("bidirectional", Self::VT_BIDIRECTIONAL, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ConnectionArgs {
+    pub node_index: u64,
+    pub from_traversal_index: u64,
+    pub from_end: TraversalEnd,
+    pub to_traversal_index: u64,
+    pub to_end: TraversalEnd,
+    pub bidirectional: bool,
+}
+impl Default for ConnectionArgs {
+  #[inline]
+  fn default() -> Self {
+    ConnectionArgs {
+      node_index: 0,
+      from_traversal_index: 0,
+      from_end: TraversalEnd::Start,
+      to_traversal_index: 0,
+      to_end: TraversalEnd::Start,
+      bidirectional: true,
+    }
+  }
+}
+
+pub struct ConnectionBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ConnectionBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_node_index(&mut self, node_index: u64) {
+    self.fbb_.push_slot::<u64>(Connection::VT_NODE_INDEX, node_index, 0);
+  }
+  #[inline]
+  pub fn add_from_traversal_index(&mut self, from_traversal_index: u64) {
+    self.fbb_.push_slot::<u64>(Connection::VT_FROM_TRAVERSAL_INDEX, from_traversal_index, 0);
+  }
+  #[inline]
+  pub fn add_from_end(&mut self, from_end: TraversalEnd) {
+    self.fbb_.push_slot::<TraversalEnd>(Connection::VT_FROM_END, from_end, TraversalEnd::Start);
+  }
+  #[inline]
+  pub fn add_to_traversal_index(&mut self, to_traversal_index: u64) {
+    self.fbb_.push_slot::<u64>(Connection::VT_TO_TRAVERSAL_INDEX, to_traversal_index, 0);
+  }
+  #[inline]
+  pub fn add_to_end(&mut self, to_end: TraversalEnd) {
+    self.fbb_.push_slot::<TraversalEnd>(Connection::VT_TO_END, to_end, TraversalEnd::Start);
+  }
+  #[inline]
+  pub fn add_bidirectional(&mut self, bidirectional: bool) {
+    self.fbb_.push_slot::<bool>(Connection::VT_BIDIRECTIONAL, bidirectional, true);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ConnectionBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ConnectionBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Connection<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Connection<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Connection");
+      ds.field("node_index", &self.node_index());
+      ds.field("from_traversal_index", &self.from_traversal_index());
+      ds.field("from_end", &self.from_end());
+      ds.field("to_traversal_index", &self.to_traversal_index());
+      ds.field("to_end", &self.to_end());
+      ds.field("bidirectional", &self.bidirectional());
+      ds.finish()
+  }
+}
+pub enum TypedPropertyOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+/// Unlike Property, the value keeps its type. Only the field matching `value_type` is set.
+pub struct TypedProperty<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for TypedProperty<'a> {
+  type Inner = TypedProperty<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> TypedProperty<'a> {
+  pub const VT_KEY: flatbuffers::VOffsetT = 4;
+  pub const VT_VALUE_TYPE: flatbuffers::VOffsetT = 6;
+  pub const VT_TEXT_VALUE: flatbuffers::VOffsetT = 8;
+  pub const VT_INTEGER_VALUE: flatbuffers::VOffsetT = 10;
+  pub const VT_FLOAT_VALUE: flatbuffers::VOffsetT = 12;
+  pub const VT_BOOLEAN_VALUE: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    TypedProperty { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args TypedPropertyArgs<'args>
+  ) -> flatbuffers::WIPOffset<TypedProperty<'bldr>> {
+    let mut builder = TypedPropertyBuilder::new(_fbb);
+    builder.add_float_value(args.float_value);
+    builder.add_integer_value(args.integer_value);
+    if let Some(x) = args.text_value { builder.add_text_value(x); }
+    if let Some(x) = args.key { builder.add_key(x); }
+    builder.add_boolean_value(args.boolean_value);
+    builder.add_value_type(args.value_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn key(&self) -> &'a str {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(TypedProperty::VT_KEY, None).unwrap()}
+  }
+  #[inline]
+  pub fn key_compare_less_than(&self, o: &TypedProperty) -> bool {
+    self.key() < o.key()
+  }
+
+  #[inline]
+  pub fn key_compare_with_value(&self, val: & str) -> ::core::cmp::Ordering {
+    let key = self.key();
+    key.cmp(val)
+  }
+  #[inline]
+  pub fn value_type(&self) -> ValueType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<ValueType>(TypedProperty::VT_VALUE_TYPE, Some(ValueType::Text)).unwrap()}
+  }
+  #[inline]
+  pub fn text_value(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(TypedProperty::VT_TEXT_VALUE, None)}
+  }
+  #[inline]
+  pub fn integer_value(&self) -> i64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i64>(TypedProperty::VT_INTEGER_VALUE, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn float_value(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(TypedProperty::VT_FLOAT_VALUE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn boolean_value(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(TypedProperty::VT_BOOLEAN_VALUE, Some(false)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for TypedProperty<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, true)?
+     .visit_field::<ValueType>("value_type", Self::VT_VALUE_TYPE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("text_value", Self::VT_TEXT_VALUE, false)?
+     .visit_field::<i64>("integer_value", Self::VT_INTEGER_VALUE, false)?
+     .visit_field::<f64>("float_value", Self::VT_FLOAT_VALUE, false)?
+     .visit_field::<bool>("boolean_value", Self::VT_BOOLEAN_VALUE, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct TypedPropertyArgs<'a> {
+    pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub value_type: ValueType,
+    pub text_value: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub integer_value: i64,
+    pub float_value: f64,
+    pub boolean_value: bool,
+}
+impl<'a> Default for TypedPropertyArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    TypedPropertyArgs {
+      key: None, // required field
+      value_type: ValueType::Text,
+      text_value: None,
+      integer_value: 0,
+      float_value: 0.0,
+      boolean_value: false,
+    }
+  }
+}
+
+pub struct TypedPropertyBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TypedPropertyBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(TypedProperty::VT_KEY, key);
+  }
+  #[inline]
+  pub fn add_value_type(&mut self, value_type: ValueType) {
+    self.fbb_.push_slot::<ValueType>(TypedProperty::VT_VALUE_TYPE, value_type, ValueType::Text);
+  }
+  #[inline]
+  pub fn add_text_value(&mut self, text_value: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(TypedProperty::VT_TEXT_VALUE, text_value);
+  }
+  #[inline]
+  pub fn add_integer_value(&mut self, integer_value: i64) {
+    self.fbb_.push_slot::<i64>(TypedProperty::VT_INTEGER_VALUE, integer_value, 0);
+  }
+  #[inline]
+  pub fn add_float_value(&mut self, float_value: f64) {
+    self.fbb_.push_slot::<f64>(TypedProperty::VT_FLOAT_VALUE, float_value, 0.0);
+  }
+  #[inline]
+  pub fn add_boolean_value(&mut self, boolean_value: bool) {
+    self.fbb_.push_slot::<bool>(TypedProperty::VT_BOOLEAN_VALUE, boolean_value, false);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> TypedPropertyBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    TypedPropertyBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<TypedProperty<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, TypedProperty::VT_KEY,"key");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for TypedProperty<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("TypedProperty");
+      ds.field("key", &self.key());
+      ds.field("value_type", &self.value_type());
+      ds.field("text_value", &self.text_value());
+      ds.field("integer_value", &self.integer_value());
+      ds.field("float_value", &self.float_value());
+      ds.field("boolean_value", &self.boolean_value());
+      ds.finish()
+  }
+}
+pub enum MeasureOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+/// A position on a linear referencing method, expressed as an offset from a named anchor (12+100).
+pub struct Measure<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Measure<'a> {
+  type Inner = Measure<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
+}
+
+impl<'a> Measure<'a> {
+  pub const VT_ANCHOR_NAME: flatbuffers::VOffsetT = 4;
+  pub const VT_OFFSET: flatbuffers::VOffsetT = 6;
+
   #[inline]
-  pub fn add_geometry(&mut self, geometry: flatbuffers::WIPOffset<flatbuffers::Vector<'b , Point>>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Segment::VT_GEOMETRY, geometry);
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Measure { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args MeasureArgs<'args>
+  ) -> flatbuffers::WIPOffset<Measure<'bldr>> {
+    let mut builder = MeasureBuilder::new(_fbb);
+    builder.add_offset(args.offset);
+    if let Some(x) = args.anchor_name { builder.add_anchor_name(x); }
+    builder.finish()
   }
+
+
   #[inline]
-  pub fn add_start_node_index(&mut self, start_node_index: u64) {
-    self.fbb_.push_slot::<u64>(Segment::VT_START_NODE_INDEX, start_node_index, 0);
+  pub fn anchor_name(&self) -> &'a str {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Measure::VT_ANCHOR_NAME, None).unwrap()}
   }
   #[inline]
-  pub fn add_end_node_index(&mut self, end_node_index: u64) {
-    self.fbb_.push_slot::<u64>(Segment::VT_END_NODE_INDEX, end_node_index, 0);
+  pub fn offset(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(Measure::VT_OFFSET, Some(0.0)).unwrap()}
   }
+}
+
+impl flatbuffers::Verifiable for Measure<'_> {
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> SegmentBuilder<'a, 'b, A> {
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("anchor_name", Self::VT_ANCHOR_NAME, true)?
+     .visit_field::<f64>("offset", Self::VT_OFFSET, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct MeasureArgs<'a> {
+    pub anchor_name: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub offset: f64,
+}
+impl<'a> Default for MeasureArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    MeasureArgs {
+      anchor_name: None, // required field
+      offset: 0.0,
+    }
+  }
+}
+
+pub struct MeasureBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> MeasureBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_anchor_name(&mut self, anchor_name: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Measure::VT_ANCHOR_NAME, anchor_name);
+  }
+  #[inline]
+  pub fn add_offset(&mut self, offset: f64) {
+    self.fbb_.push_slot::<f64>(Measure::VT_OFFSET, offset, 0.0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> MeasureBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
-    SegmentBuilder {
+    MeasureBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<Segment<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<Measure<'a>> {
     let o = self.fbb_.end_table(self.start_);
-    self.fbb_.required(o, Segment::VT_ID,"id");
-    self.fbb_.required(o, Segment::VT_GEOMETRY,"geometry");
+    self.fbb_.required(o, Measure::VT_ANCHOR_NAME,"anchor_name");
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for Segment<'_> {
+impl core::fmt::Debug for Measure<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let mut ds = f.debug_struct("Segment");
-      ds.field("id", &self.id());
-      ds.field("properties", &self.properties());
-      ds.field("geometry", &self.geometry());
-      ds.field("start_node_index", &self.start_node_index());
-      ds.field("end_node_index", &self.end_node_index());
+    let mut ds = f.debug_struct("Measure");
+      ds.field("anchor_name", &self.anchor_name());
+      ds.field("offset", &self.offset());
       ds.finish()
   }
 }
-pub enum NodeOffset {}
+pub enum PointEventOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
-/// Nodes are connections between segment endpoints.
-/// A node can have a single connection (at the end of a dead end), or multiple segment connections (at an intersection).
-/// Nodes can be used to model point of interest in a network, such as road intersections, railway switches, the end of a dead ends.
-pub struct Node<'a> {
+/// An object located at a single measure of a linear referencing method.
+pub struct PointEvent<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for Node<'a> {
-  type Inner = Node<'a>;
+impl<'a> flatbuffers::Follow<'a> for PointEvent<'a> {
+  type Inner = PointEvent<'a>;
   #[inline]
   unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
     Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
 }
 
-impl<'a> Node<'a> {
+impl<'a> PointEvent<'a> {
   pub const VT_ID: flatbuffers::VOffsetT = 4;
-  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 6;
-  pub const VT_GEOMETRY: flatbuffers::VOffsetT = 8;
+  pub const VT_LRM_INDEX: flatbuffers::VOffsetT = 6;
+  pub const VT_MEASURE: flatbuffers::VOffsetT = 8;
+  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 10;
+  pub const VT_VALID_FROM: flatbuffers::VOffsetT = 12;
+  pub const VT_VALID_TO: flatbuffers::VOffsetT = 14;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-    Node { _tab: table }
+    PointEvent { _tab: table }
   }
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args NodeArgs<'args>
-  ) -> flatbuffers::WIPOffset<Node<'bldr>> {
-    let mut builder = NodeBuilder::new(_fbb);
-    if let Some(x) = args.geometry { builder.add_geometry(x); }
+    args: &'args PointEventArgs<'args>
+  ) -> flatbuffers::WIPOffset<PointEvent<'bldr>> {
+    let mut builder = PointEventBuilder::new(_fbb);
+    if let Some(x) = args.valid_to { builder.add_valid_to(x); }
+    if let Some(x) = args.valid_from { builder.add_valid_from(x); }
     if let Some(x) = args.properties { builder.add_properties(x); }
+    if let Some(x) = args.measure { builder.add_measure(x); }
+    builder.add_lrm_index(args.lrm_index);
     if let Some(x) = args.id { builder.add_id(x); }
     builder.finish()
   }
@@ -1088,25 +2355,50 @@ impl<'a> Node<'a> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Node::VT_ID, None).unwrap()}
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PointEvent::VT_ID, None).unwrap()}
   }
   #[inline]
-  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>> {
+  pub fn lrm_index(&self) -> u32 {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property>>>>(Node::VT_PROPERTIES, None)}
+    unsafe { self._tab.get::<u32>(PointEvent::VT_LRM_INDEX, Some(0)).unwrap()}
   }
   #[inline]
-  pub fn geometry(&self) -> Option<&'a Point> {
+  pub fn measure(&self) -> Measure<'a> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<Point>(Node::VT_GEOMETRY, None)}
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Measure>>(PointEvent::VT_MEASURE, None).unwrap()}
+  }
+  #[inline]
+  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TypedProperty<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TypedProperty>>>>(PointEvent::VT_PROPERTIES, None)}
+  }
+  /// First day of validity, as an ISO 8601 date (YYYY-MM-DD), included
+  /// Null when the object has always been valid
+  #[inline]
+  pub fn valid_from(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PointEvent::VT_VALID_FROM, None)}
+  }
+  /// First day the object is no longer valid, as an ISO 8601 date (YYYY-MM-DD)
+  /// Null when the object is still valid
+  #[inline]
+  pub fn valid_to(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PointEvent::VT_VALID_TO, None)}
   }
 }
 
-impl flatbuffers::Verifiable for Node<'_> {
+impl flatbuffers::Verifiable for PointEvent<'_> {
   #[inline]
   fn run_verifier(
     v: &mut flatbuffers::Verifier, pos: usize
@@ -1114,102 +2406,136 @@ impl flatbuffers::Verifiable for Node<'_> {
     use self::flatbuffers::Verifiable;
     v.visit_table(pos)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("id", Self::VT_ID, true)?
-     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Property>>>>("properties", Self::VT_PROPERTIES, false)?
-     .visit_field::<Point>("geometry", Self::VT_GEOMETRY, false)?
+     .visit_field::<u32>("lrm_index", Self::VT_LRM_INDEX, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Measure>>("measure", Self::VT_MEASURE, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<TypedProperty>>>>("properties", Self::VT_PROPERTIES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_from", Self::VT_VALID_FROM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_to", Self::VT_VALID_TO, false)?
      .finish();
     Ok(())
   }
 }
-pub struct NodeArgs<'a> {
+pub struct PointEventArgs<'a> {
     pub id: Option<flatbuffers::WIPOffset<&'a str>>,
-    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
-    pub geometry: Option<&'a Point>,
+    pub lrm_index: u32,
+    pub measure: Option<flatbuffers::WIPOffset<Measure<'a>>>,
+    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TypedProperty<'a>>>>>,
+    pub valid_from: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub valid_to: Option<flatbuffers::WIPOffset<&'a str>>,
 }
-impl<'a> Default for NodeArgs<'a> {
+impl<'a> Default for PointEventArgs<'a> {
   #[inline]
   fn default() -> Self {
-    NodeArgs {
+    PointEventArgs {
       id: None, // required field
+      lrm_index: 0,
+      measure: None, // required field
       properties: None,
-      geometry: None,
+      valid_from: None,
+      valid_to: None,
     }
   }
 }
 
-pub struct NodeBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+pub struct PointEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> NodeBuilder<'a, 'b, A> {
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PointEventBuilder<'a, 'b, A> {
   #[inline]
   pub fn add_id(&mut self, id: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Node::VT_ID, id);
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PointEvent::VT_ID, id);
   }
   #[inline]
-  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Property<'b >>>>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Node::VT_PROPERTIES, properties);
+  pub fn add_lrm_index(&mut self, lrm_index: u32) {
+    self.fbb_.push_slot::<u32>(PointEvent::VT_LRM_INDEX, lrm_index, 0);
   }
   #[inline]
-  pub fn add_geometry(&mut self, geometry: &Point) {
-    self.fbb_.push_slot_always::<&Point>(Node::VT_GEOMETRY, geometry);
+  pub fn add_measure(&mut self, measure: flatbuffers::WIPOffset<Measure<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PointEvent::VT_MEASURE, measure);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> NodeBuilder<'a, 'b, A> {
+  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<TypedProperty<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PointEvent::VT_PROPERTIES, properties);
+  }
+  #[inline]
+  pub fn add_valid_from(&mut self, valid_from: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PointEvent::VT_VALID_FROM, valid_from);
+  }
+  #[inline]
+  pub fn add_valid_to(&mut self, valid_to: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PointEvent::VT_VALID_TO, valid_to);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PointEventBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
-    NodeBuilder {
+    PointEventBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<Node<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<PointEvent<'a>> {
     let o = self.fbb_.end_table(self.start_);
-    self.fbb_.required(o, Node::VT_ID,"id");
+    self.fbb_.required(o, PointEvent::VT_ID,"id");
+    self.fbb_.required(o, PointEvent::VT_MEASURE,"measure");
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for Node<'_> {
+impl core::fmt::Debug for PointEvent<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let mut ds = f.debug_struct("Node");
+    let mut ds = f.debug_struct("PointEvent");
       ds.field("id", &self.id());
+      ds.field("lrm_index", &self.lrm_index());
+      ds.field("measure", &self.measure());
       ds.field("properties", &self.properties());
-      ds.field("geometry", &self.geometry());
+      ds.field("valid_from", &self.valid_from());
+      ds.field("valid_to", &self.valid_to());
       ds.finish()
   }
 }
-pub enum TraversalOffset {}
+pub enum LinearEventOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
-pub struct Traversal<'a> {
+/// An object located over a range of measures of a linear referencing method.
+pub struct LinearEvent<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for Traversal<'a> {
-  type Inner = Traversal<'a>;
+impl<'a> flatbuffers::Follow<'a> for LinearEvent<'a> {
+  type Inner = LinearEvent<'a>;
   #[inline]
   unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
     Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
 }
 
-impl<'a> Traversal<'a> {
+impl<'a> LinearEvent<'a> {
   pub const VT_ID: flatbuffers::VOffsetT = 4;
-  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 6;
-  pub const VT_SEGMENTS: flatbuffers::VOffsetT = 8;
+  pub const VT_LRM_INDEX: flatbuffers::VOffsetT = 6;
+  pub const VT_BEGIN: flatbuffers::VOffsetT = 8;
+  pub const VT_END: flatbuffers::VOffsetT = 10;
+  pub const VT_PROPERTIES: flatbuffers::VOffsetT = 12;
+  pub const VT_VALID_FROM: flatbuffers::VOffsetT = 14;
+  pub const VT_VALID_TO: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-    Traversal { _tab: table }
+    LinearEvent { _tab: table }
   }
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args TraversalArgs<'args>
-  ) -> flatbuffers::WIPOffset<Traversal<'bldr>> {
-    let mut builder = TraversalBuilder::new(_fbb);
-    if let Some(x) = args.segments { builder.add_segments(x); }
+    args: &'args LinearEventArgs<'args>
+  ) -> flatbuffers::WIPOffset<LinearEvent<'bldr>> {
+    let mut builder = LinearEventBuilder::new(_fbb);
+    if let Some(x) = args.valid_to { builder.add_valid_to(x); }
+    if let Some(x) = args.valid_from { builder.add_valid_from(x); }
     if let Some(x) = args.properties { builder.add_properties(x); }
+    if let Some(x) = args.end { builder.add_end(x); }
+    if let Some(x) = args.begin { builder.add_begin(x); }
+    builder.add_lrm_index(args.lrm_index);
     if let Some(x) = args.id { builder.add_id(x); }
     builder.finish()
   }
@@ -1220,25 +2546,57 @@ impl<'a> Traversal<'a> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Traversal::VT_ID, None).unwrap()}
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LinearEvent::VT_ID, None).unwrap()}
   }
   #[inline]
-  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>> {
+  pub fn lrm_index(&self) -> u32 {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property>>>>(Traversal::VT_PROPERTIES, None)}
+    unsafe { self._tab.get::<u32>(LinearEvent::VT_LRM_INDEX, Some(0)).unwrap()}
   }
   #[inline]
-  pub fn segments(&self) -> flatbuffers::Vector<'a, SegmentOfTraversal> {
+  pub fn begin(&self) -> Measure<'a> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, SegmentOfTraversal>>>(Traversal::VT_SEGMENTS, None).unwrap()}
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Measure>>(LinearEvent::VT_BEGIN, None).unwrap()}
+  }
+  #[inline]
+  pub fn end(&self) -> Measure<'a> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Measure>>(LinearEvent::VT_END, None).unwrap()}
+  }
+  #[inline]
+  pub fn properties(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TypedProperty<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TypedProperty>>>>(LinearEvent::VT_PROPERTIES, None)}
+  }
+  /// First day of validity, as an ISO 8601 date (YYYY-MM-DD), included
+  /// Null when the object has always been valid
+  #[inline]
+  pub fn valid_from(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LinearEvent::VT_VALID_FROM, None)}
+  }
+  /// First day the object is no longer valid, as an ISO 8601 date (YYYY-MM-DD)
+  /// Null when the object is still valid
+  #[inline]
+  pub fn valid_to(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LinearEvent::VT_VALID_TO, None)}
   }
 }
 
-impl flatbuffers::Verifiable for Traversal<'_> {
+impl flatbuffers::Verifiable for LinearEvent<'_> {
   #[inline]
   fn run_verifier(
     v: &mut flatbuffers::Verifier, pos: usize
@@ -1246,68 +2604,101 @@ impl flatbuffers::Verifiable for Traversal<'_> {
     use self::flatbuffers::Verifiable;
     v.visit_table(pos)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("id", Self::VT_ID, true)?
-     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Property>>>>("properties", Self::VT_PROPERTIES, false)?
-     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, SegmentOfTraversal>>>("segments", Self::VT_SEGMENTS, true)?
+     .visit_field::<u32>("lrm_index", Self::VT_LRM_INDEX, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Measure>>("begin", Self::VT_BEGIN, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Measure>>("end", Self::VT_END, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<TypedProperty>>>>("properties", Self::VT_PROPERTIES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_from", Self::VT_VALID_FROM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_to", Self::VT_VALID_TO, false)?
      .finish();
     Ok(())
   }
 }
-pub struct TraversalArgs<'a> {
+pub struct LinearEventArgs<'a> {
     pub id: Option<flatbuffers::WIPOffset<&'a str>>,
-    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
-    pub segments: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, SegmentOfTraversal>>>,
+    pub lrm_index: u32,
+    pub begin: Option<flatbuffers::WIPOffset<Measure<'a>>>,
+    pub end: Option<flatbuffers::WIPOffset<Measure<'a>>>,
+    pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<TypedProperty<'a>>>>>,
+    pub valid_from: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub valid_to: Option<flatbuffers::WIPOffset<&'a str>>,
 }
-impl<'a> Default for TraversalArgs<'a> {
+impl<'a> Default for LinearEventArgs<'a> {
   #[inline]
   fn default() -> Self {
-    TraversalArgs {
+    LinearEventArgs {
       id: None, // required field
+      lrm_index: 0,
+      begin: None, // required field
+      end: None, // required field
       properties: None,
-      segments: None, // required field
+      valid_from: None,
+      valid_to: None,
     }
   }
 }
 
-pub struct TraversalBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+pub struct LinearEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TraversalBuilder<'a, 'b, A> {
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> LinearEventBuilder<'a, 'b, A> {
   #[inline]
   pub fn add_id(&mut self, id: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Traversal::VT_ID, id);
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearEvent::VT_ID, id);
   }
   #[inline]
-  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Property<'b >>>>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Traversal::VT_PROPERTIES, properties);
+  pub fn add_lrm_index(&mut self, lrm_index: u32) {
+    self.fbb_.push_slot::<u32>(LinearEvent::VT_LRM_INDEX, lrm_index, 0);
   }
   #[inline]
-  pub fn add_segments(&mut self, segments: flatbuffers::WIPOffset<flatbuffers::Vector<'b , SegmentOfTraversal>>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Traversal::VT_SEGMENTS, segments);
+  pub fn add_begin(&mut self, begin: flatbuffers::WIPOffset<Measure<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearEvent::VT_BEGIN, begin);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> TraversalBuilder<'a, 'b, A> {
+  pub fn add_end(&mut self, end: flatbuffers::WIPOffset<Measure<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearEvent::VT_END, end);
+  }
+  #[inline]
+  pub fn add_properties(&mut self, properties: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<TypedProperty<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearEvent::VT_PROPERTIES, properties);
+  }
+  #[inline]
+  pub fn add_valid_from(&mut self, valid_from: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearEvent::VT_VALID_FROM, valid_from);
+  }
+  #[inline]
+  pub fn add_valid_to(&mut self, valid_to: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearEvent::VT_VALID_TO, valid_to);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> LinearEventBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
-    TraversalBuilder {
+    LinearEventBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<Traversal<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<LinearEvent<'a>> {
     let o = self.fbb_.end_table(self.start_);
-    self.fbb_.required(o, Traversal::VT_ID,"id");
-    self.fbb_.required(o, Traversal::VT_SEGMENTS,"segments");
+    self.fbb_.required(o, LinearEvent::VT_ID,"id");
+    self.fbb_.required(o, LinearEvent::VT_BEGIN,"begin");
+    self.fbb_.required(o, LinearEvent::VT_END,"end");
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for Traversal<'_> {
+impl core::fmt::Debug for LinearEvent<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let mut ds = f.debug_struct("Traversal");
+    let mut ds = f.debug_struct("LinearEvent");
       ds.field("id", &self.id());
+      ds.field("lrm_index", &self.lrm_index());
+      ds.field("begin", &self.begin());
+      ds.field("end", &self.end());
       ds.field("properties", &self.properties());
-      ds.field("segments", &self.segments());
+      ds.field("valid_from", &self.valid_from());
+      ds.field("valid_to", &self.valid_to());
       ds.finish()
   }
 }
@@ -1336,6 +2727,8 @@ impl<'a> Anchor<'a> {
   pub const VT_NAME: flatbuffers::VOffsetT = 8;
   pub const VT_NODE: flatbuffers::VOffsetT = 10;
   pub const VT_GEOMETRY: flatbuffers::VOffsetT = 12;
+  pub const VT_VALID_FROM: flatbuffers::VOffsetT = 14;
+  pub const VT_VALID_TO: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1347,8 +2740,10 @@ impl<'a> Anchor<'a> {
     args: &'args AnchorArgs<'args>
   ) -> flatbuffers::WIPOffset<Anchor<'bldr>> {
     let mut builder = AnchorBuilder::new(_fbb);
+    if let Some(x) = args.valid_to { builder.add_valid_to(x); }
+    if let Some(x) = args.valid_from { builder.add_valid_from(x); }
     if let Some(x) = args.geometry { builder.add_geometry(x); }
-    builder.add_node(args.node);
+    if let Some(x) = args.node { builder.add_node(x); }
     if let Some(x) = args.name { builder.add_name(x); }
     if let Some(x) = args.properties { builder.add_properties(x); }
     if let Some(x) = args.id { builder.add_id(x); }
@@ -1381,12 +2776,13 @@ impl<'a> Anchor<'a> {
   }
   /// Anchors can be bound to a node, or defined independently.
   /// If the anchor is bound to a node, its location is deduced from location of the node.
+  /// Null when the anchor is not bound to a node.
   #[inline]
-  pub fn node(&self) -> u32 {
+  pub fn node(&self) -> Option<u32> {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<u32>(Anchor::VT_NODE, Some(0)).unwrap()}
+    unsafe { self._tab.get::<u32>(Anchor::VT_NODE, None)}
   }
   /// The anchor can also be defined by a geographical position.
   #[inline]
@@ -1396,6 +2792,24 @@ impl<'a> Anchor<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<Point>(Anchor::VT_GEOMETRY, None)}
   }
+  /// First day of validity, as an ISO 8601 date (YYYY-MM-DD), included
+  /// Null when the object has always been valid
+  #[inline]
+  pub fn valid_from(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Anchor::VT_VALID_FROM, None)}
+  }
+  /// First day the object is no longer valid, as an ISO 8601 date (YYYY-MM-DD)
+  /// Null when the object is still valid
+  #[inline]
+  pub fn valid_to(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Anchor::VT_VALID_TO, None)}
+  }
 }
 
 impl flatbuffers::Verifiable for Anchor<'_> {
@@ -1410,6 +2824,8 @@ impl flatbuffers::Verifiable for Anchor<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, false)?
      .visit_field::<u32>("node", Self::VT_NODE, false)?
      .visit_field::<Point>("geometry", Self::VT_GEOMETRY, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_from", Self::VT_VALID_FROM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_to", Self::VT_VALID_TO, false)?
      .finish();
     Ok(())
   }
@@ -1418,8 +2834,10 @@ pub struct AnchorArgs<'a> {
     pub id: Option<flatbuffers::WIPOffset<&'a str>>,
     pub properties: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Property<'a>>>>>,
     pub name: Option<flatbuffers::WIPOffset<&'a str>>,
-    pub node: u32,
+    pub node: Option<u32>,
     pub geometry: Option<&'a Point>,
+    pub valid_from: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub valid_to: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for AnchorArgs<'a> {
   #[inline]
@@ -1428,8 +2846,10 @@ impl<'a> Default for AnchorArgs<'a> {
       id: None, // required field
       properties: None,
       name: None,
-      node: 0,
+      node: None,
       geometry: None,
+      valid_from: None,
+      valid_to: None,
     }
   }
 }
@@ -1453,13 +2873,21 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> AnchorBuilder<'a, 'b, A> {
   }
   #[inline]
   pub fn add_node(&mut self, node: u32) {
-    self.fbb_.push_slot::<u32>(Anchor::VT_NODE, node, 0);
+    self.fbb_.push_slot_always::<u32>(Anchor::VT_NODE, node);
   }
   #[inline]
   pub fn add_geometry(&mut self, geometry: &Point) {
     self.fbb_.push_slot_always::<&Point>(Anchor::VT_GEOMETRY, geometry);
   }
   #[inline]
+  pub fn add_valid_from(&mut self, valid_from: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Anchor::VT_VALID_FROM, valid_from);
+  }
+  #[inline]
+  pub fn add_valid_to(&mut self, valid_to: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Anchor::VT_VALID_TO, valid_to);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> AnchorBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
     AnchorBuilder {
@@ -1483,6 +2911,8 @@ impl core::fmt::Debug for Anchor<'_> {
       ds.field("name", &self.name());
       ds.field("node", &self.node());
       ds.field("geometry", &self.geometry());
+      ds.field("valid_from", &self.valid_from());
+      ds.field("valid_to", &self.valid_to());
       ds.finish()
   }
 }
@@ -1506,6 +2936,7 @@ impl<'a> flatbuffers::Follow<'a> for ProjectedAnchor<'a> {
 impl<'a> ProjectedAnchor<'a> {
   pub const VT_GEOMETRY: flatbuffers::VOffsetT = 4;
   pub const VT_DISTANCE_ALONG_CURVE: flatbuffers::VOffsetT = 6;
+  pub const VT_OFFSET: flatbuffers::VOffsetT = 8;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1517,6 +2948,7 @@ impl<'a> ProjectedAnchor<'a> {
     args: &'args ProjectedAnchorArgs<'args>
   ) -> flatbuffers::WIPOffset<ProjectedAnchor<'bldr>> {
     let mut builder = ProjectedAnchorBuilder::new(_fbb);
+    if let Some(x) = args.offset { builder.add_offset(x); }
     builder.add_distance_along_curve(args.distance_along_curve);
     if let Some(x) = args.geometry { builder.add_geometry(x); }
     builder.finish()
@@ -1539,6 +2971,15 @@ impl<'a> ProjectedAnchor<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<f64>(ProjectedAnchor::VT_DISTANCE_ALONG_CURVE, Some(0.0)).unwrap()}
   }
+  /// The signed distance between the geographical position of the anchor and the curve, positive on the left
+  /// It is null when the anchor has no geographical position
+  #[inline]
+  pub fn offset(&self) -> Option<f64> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(ProjectedAnchor::VT_OFFSET, None)}
+  }
 }
 
 impl flatbuffers::Verifiable for ProjectedAnchor<'_> {
@@ -1550,6 +2991,7 @@ impl flatbuffers::Verifiable for ProjectedAnchor<'_> {
     v.visit_table(pos)?
      .visit_field::<Point>("geometry", Self::VT_GEOMETRY, false)?
      .visit_field::<f64>("distance_along_curve", Self::VT_DISTANCE_ALONG_CURVE, false)?
+     .visit_field::<f64>("offset", Self::VT_OFFSET, false)?
      .finish();
     Ok(())
   }
@@ -1557,6 +2999,7 @@ impl flatbuffers::Verifiable for ProjectedAnchor<'_> {
 pub struct ProjectedAnchorArgs<'a> {
     pub geometry: Option<&'a Point>,
     pub distance_along_curve: f64,
+    pub offset: Option<f64>,
 }
 impl<'a> Default for ProjectedAnchorArgs<'a> {
   #[inline]
@@ -1564,6 +3007,7 @@ impl<'a> Default for ProjectedAnchorArgs<'a> {
     ProjectedAnchorArgs {
       geometry: None,
       distance_along_curve: 0.0,
+      offset: None,
     }
   }
 }
@@ -1582,6 +3026,10 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ProjectedAnchorBuilder<'a, 'b,
     self.fbb_.push_slot::<f64>(ProjectedAnchor::VT_DISTANCE_ALONG_CURVE, distance_along_curve, 0.0);
   }
   #[inline]
+  pub fn add_offset(&mut self, offset: f64) {
+    self.fbb_.push_slot_always::<f64>(ProjectedAnchor::VT_OFFSET, offset);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ProjectedAnchorBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
     ProjectedAnchorBuilder {
@@ -1601,6 +3049,7 @@ impl core::fmt::Debug for ProjectedAnchor<'_> {
     let mut ds = f.debug_struct("ProjectedAnchor");
       ds.field("geometry", &self.geometry());
       ds.field("distance_along_curve", &self.distance_along_curve());
+      ds.field("offset", &self.offset());
       ds.finish()
   }
 }
@@ -1633,6 +3082,10 @@ impl<'a> LinearReferencingMethod<'a> {
   pub const VT_PROJECTED_ANCHORS: flatbuffers::VOffsetT = 14;
   pub const VT_DISTANCE_UNIT: flatbuffers::VOffsetT = 16;
   pub const VT_MEASURE_UNIT: flatbuffers::VOffsetT = 18;
+  pub const VT_MEASURE_DIRECTION: flatbuffers::VOffsetT = 20;
+  pub const VT_ALTERNATE_TRAVERSAL_INDICES: flatbuffers::VOffsetT = 22;
+  pub const VT_VALID_FROM: flatbuffers::VOffsetT = 24;
+  pub const VT_VALID_TO: flatbuffers::VOffsetT = 26;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1644,12 +3097,16 @@ impl<'a> LinearReferencingMethod<'a> {
     args: &'args LinearReferencingMethodArgs<'args>
   ) -> flatbuffers::WIPOffset<LinearReferencingMethod<'bldr>> {
     let mut builder = LinearReferencingMethodBuilder::new(_fbb);
+    if let Some(x) = args.valid_to { builder.add_valid_to(x); }
+    if let Some(x) = args.valid_from { builder.add_valid_from(x); }
+    if let Some(x) = args.alternate_traversal_indices { builder.add_alternate_traversal_indices(x); }
     if let Some(x) = args.projected_anchors { builder.add_projected_anchors(x); }
     if let Some(x) = args.distances { builder.add_distances(x); }
     if let Some(x) = args.anchor_indices { builder.add_anchor_indices(x); }
     builder.add_traversal_index(args.traversal_index);
     if let Some(x) = args.properties { builder.add_properties(x); }
     if let Some(x) = args.id { builder.add_id(x); }
+    builder.add_measure_direction(args.measure_direction);
     builder.add_measure_unit(args.measure_unit);
     builder.add_distance_unit(args.distance_unit);
     builder.finish()
@@ -1716,6 +3173,41 @@ impl<'a> LinearReferencingMethod<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<DistanceUnit>(LinearReferencingMethod::VT_MEASURE_UNIT, Some(DistanceUnit::Meters)).unwrap()}
   }
+  /// Whether the measures increase or decrease along the traversal, in the direction of its geometry.
+  #[inline]
+  pub fn measure_direction(&self) -> Direction {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Direction>(LinearReferencingMethod::VT_MEASURE_DIRECTION, Some(Direction::Increasing)).unwrap()}
+  }
+  /// Other traversals sharing the same scale, such as the other track of a double-track line.
+  /// Positions are transferred from `traversal_index` to them perpendicularly to the traversal.
+  #[inline]
+  pub fn alternate_traversal_indices(&self) -> Option<flatbuffers::Vector<'a, u32>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u32>>>(LinearReferencingMethod::VT_ALTERNATE_TRAVERSAL_INDICES, None)}
+  }
+  /// First day of validity, as an ISO 8601 date (YYYY-MM-DD), included
+  /// Null when the object has always been valid
+  #[inline]
+  pub fn valid_from(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LinearReferencingMethod::VT_VALID_FROM, None)}
+  }
+  /// First day the object is no longer valid, as an ISO 8601 date (YYYY-MM-DD)
+  /// Null when the object is still valid
+  #[inline]
+  pub fn valid_to(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(LinearReferencingMethod::VT_VALID_TO, None)}
+  }
 }
 
 impl flatbuffers::Verifiable for LinearReferencingMethod<'_> {
@@ -1733,6 +3225,10 @@ impl flatbuffers::Verifiable for LinearReferencingMethod<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<ProjectedAnchor>>>>("projected_anchors", Self::VT_PROJECTED_ANCHORS, false)?
      .visit_field::<DistanceUnit>("distance_unit", Self::VT_DISTANCE_UNIT, false)?
      .visit_field::<DistanceUnit>("measure_unit", Self::VT_MEASURE_UNIT, false)?
+     .visit_field::<Direction>("measure_direction", Self::VT_MEASURE_DIRECTION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u32>>>("alternate_traversal_indices", Self::VT_ALTERNATE_TRAVERSAL_INDICES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_from", Self::VT_VALID_FROM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("valid_to", Self::VT_VALID_TO, false)?
      .finish();
     Ok(())
   }
@@ -1746,6 +3242,10 @@ pub struct LinearReferencingMethodArgs<'a> {
     pub projected_anchors: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ProjectedAnchor<'a>>>>>,
     pub distance_unit: DistanceUnit,
     pub measure_unit: DistanceUnit,
+    pub measure_direction: Direction,
+    pub alternate_traversal_indices: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u32>>>,
+    pub valid_from: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub valid_to: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for LinearReferencingMethodArgs<'a> {
   #[inline]
@@ -1759,6 +3259,10 @@ impl<'a> Default for LinearReferencingMethodArgs<'a> {
       projected_anchors: None,
       distance_unit: DistanceUnit::Meters,
       measure_unit: DistanceUnit::Meters,
+      measure_direction: Direction::Increasing,
+      alternate_traversal_indices: None,
+      valid_from: None,
+      valid_to: None,
     }
   }
 }
@@ -1801,6 +3305,22 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> LinearReferencingMethodBuilder<
     self.fbb_.push_slot::<DistanceUnit>(LinearReferencingMethod::VT_MEASURE_UNIT, measure_unit, DistanceUnit::Meters);
   }
   #[inline]
+  pub fn add_measure_direction(&mut self, measure_direction: Direction) {
+    self.fbb_.push_slot::<Direction>(LinearReferencingMethod::VT_MEASURE_DIRECTION, measure_direction, Direction::Increasing);
+  }
+  #[inline]
+  pub fn add_alternate_traversal_indices(&mut self, alternate_traversal_indices: flatbuffers::WIPOffset<flatbuffers::Vector<'b , u32>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearReferencingMethod::VT_ALTERNATE_TRAVERSAL_INDICES, alternate_traversal_indices);
+  }
+  #[inline]
+  pub fn add_valid_from(&mut self, valid_from: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearReferencingMethod::VT_VALID_FROM, valid_from);
+  }
+  #[inline]
+  pub fn add_valid_to(&mut self, valid_to: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(LinearReferencingMethod::VT_VALID_TO, valid_to);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> LinearReferencingMethodBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
     LinearReferencingMethodBuilder {
@@ -1829,6 +3349,10 @@ impl core::fmt::Debug for LinearReferencingMethod<'_> {
       ds.field("projected_anchors", &self.projected_anchors());
       ds.field("distance_unit", &self.distance_unit());
       ds.field("measure_unit", &self.measure_unit());
+      ds.field("measure_direction", &self.measure_direction());
+      ds.field("alternate_traversal_indices", &self.alternate_traversal_indices());
+      ds.field("valid_from", &self.valid_from());
+      ds.field("valid_to", &self.valid_to());
       ds.finish()
   }
 }
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+use liblrs::{curves::SphericalLineStringCurve, lrs::Lrs};
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+/// Arguments given by the command line interface.
+struct Args {
+    /// LRS file to export.
+    #[arg(short, long)]
+    input_lrs: PathBuf,
+
+    /// Output file where the GeoJSON FeatureCollection will be written.
+    #[arg(short, long)]
+    output_geojson: PathBuf,
+}
+
+/// Example: to inspect an LRS in QGIS
+///
+/// `$ cargo run --release --features geojson --bin lrs_to_geojson -- -i osm.lrs.bin -o osm.geojson`
+fn main() {
+    let cli_args = Args::parse();
+
+    let lrs = Lrs::<SphericalLineStringCurve>::new(&cli_args.input_lrs)
+        .expect("the LRS file should be readable");
+    std::fs::write(&cli_args.output_geojson, lrs.to_geojson().to_string())
+        .expect("could not write the GeoJSON file");
+}
@@ -0,0 +1,95 @@
+//! Opens an LRS file with a memory map instead of reading it.
+//!
+//! The file is not copied in memory: the operating system loads the pages that are read, and can evict them.
+//! The loading is lazy: the [`crate::curves::Curve`] of a [`crate::lrs::Traversal`] and the geometry of a
+//! [`crate::lrs::Segment`] are only decoded from the map when they are first used, see [`crate::lrs::Deferred`].
+//! The map is kept by the loaded [`Lrs`] until it is dropped.
+//! A compressed file can not be read in place: its payload is decompressed in memory, and the curves are still built lazily.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use memmap2::Mmap;
+
+use crate::curves::Curve;
+use crate::lrs::{AnyLrs, GeometryType, Lrs, LrsError, SharedBytes};
+
+// The map is read-only, the callers must document that the file must not be modified while it is loaded
+fn map<P: AsRef<Path>>(path: P) -> Result<SharedBytes, LrsError> {
+    let file = File::open(path).map_err(|_| LrsError::OpenFileError)?;
+    // SAFETY: the map is only read, and the file must not be modified as long as the LRS is loaded.
+    let map: SharedBytes =
+        Arc::new(unsafe { Mmap::map(&file) }.map_err(|_| LrsError::ReadFileError)?);
+    Ok(map)
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Loads an [`Lrs`] from a file through a memory map, see [`Lrs::from_shared_bytes`].
+    ///
+    /// The file must not be modified as long as the [`Lrs`] is loaded.
+    pub fn open_mapped<P: AsRef<Path>>(path: P) -> Result<Self, LrsError> {
+        Self::from_shared_bytes(map(path)?)
+    }
+}
+
+impl AnyLrs {
+    /// Loads an [`AnyLrs`] from a file through a memory map, see [`AnyLrs::from_shared_bytes`].
+    ///
+    /// The file must not be modified as long as the [`AnyLrs`] is loaded.
+    pub fn open_mapped<P: AsRef<Path>>(
+        path: P,
+        geometry_type: Option<GeometryType>,
+    ) -> Result<Self, LrsError> {
+        Self::from_shared_bytes(map(path)?, geometry_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{coord, point};
+
+    use crate::builder::tests::build_traversal;
+    use crate::builder::{AnchorOnLrm, Builder};
+    use crate::curves::SphericalLineStringCurve;
+    use crate::lrs::{LrmHandle, LrsBase};
+    use crate::properties;
+
+    use super::*;
+
+    #[test]
+    fn open_mapped() {
+        let mut builder = Builder::new();
+        let traversal = build_traversal(&mut builder);
+        let anchors = [("a", 0.), ("b", 2.)].map(|(name, x)| AnchorOnLrm {
+            anchor_index: builder.add_anchor(name, Some(name), coord! {x: x, y: 0.}, properties!()),
+            distance_along_lrm: x,
+        });
+        builder.add_lrm("lrm", traversal, &anchors, properties!());
+        let data = builder.build_data(properties!()).to_vec();
+        let path = std::env::temp_dir().join(format!("liblrs-mmap-{}.lrs", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+
+        let mapped = Lrs::<SphericalLineStringCurve>::open_mapped(&path).unwrap();
+        assert_eq!(mapped.lrms.len(), 1);
+        let any = AnyLrs::open_mapped(&path, None).unwrap();
+        assert_eq!(
+            any.geometry_type(),
+            GeometryType::from_bytes(&data).unwrap()
+        );
+
+        // The geometries are decoded when they are first used
+        assert!(!mapped.traversals[0].curve.is_loaded());
+        assert!(!mapped.segments[0].geometry.is_loaded());
+        assert!(mapped.lookup(point! {x: 1., y: 0.}, LrmHandle(0)).is_ok());
+        assert!(mapped.traversals[0].curve.is_loaded());
+        assert!(!mapped.segments[0].geometry.is_loaded());
+        assert_eq!(mapped.segments[1].geometry.0.len(), 2);
+        assert!(mapped.segments[1].geometry.is_loaded());
+        // The map is kept until the LRS is dropped
+        drop((mapped, any));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(Lrs::<SphericalLineStringCurve>::open_mapped(&path).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Reads the railway network of NeTEx documents with [`Builder::read_netex`].
+
+use std::collections::HashMap;
+
+use geo::{Coord, LineString};
+
+use crate::builder::{AnchorOnLrm, Builder, BuilderError};
+use crate::lrs::Properties;
+use crate::properties;
+use crate::xml::{xml_elements, xml_text};
+
+impl Builder<'_> {
+    /// Read the railway network of a NeTEx document.
+    ///
+    /// Each `RailwayElement` with a `gml:LineString` becomes a `Traversal` with the same `id`.
+    /// The `Traversal`s continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    /// The coordinates are read as latitude and longitude, the axis order of `EPSG:4326` in GML.
+    /// Each `PointProjection` on a `RailwayElement` (`ProjectToLinkRef`) is an `Anchor` named after its `ProjectedPointRef`,
+    /// at its `Distance` from the start of the element. The elements with projections get an LRM with the same `id`.
+    pub fn read_netex(&mut self, xml: &str) -> Result<(), BuilderError> {
+        let document =
+            roxmltree::Document::parse(xml).map_err(|e| BuilderError::NeTEx(e.to_string()))?;
+        let root = document.root_element();
+
+        let mut nodes = HashMap::new();
+        // Traversal, length and properties of each railway element
+        let mut links = HashMap::new();
+        for element in xml_elements(root, "RailwayElement") {
+            let Some(id) = element.attribute("id") else {
+                continue;
+            };
+            let values: Vec<f64> = xml_elements(element, "posList")
+                .chain(xml_elements(element, "pos"))
+                .filter_map(|positions| positions.text())
+                .flat_map(str::split_whitespace)
+                .filter_map(|value| value.parse().ok())
+                .collect();
+            let geometry: LineString = values
+                .chunks_exact(2)
+                .map(|lat_lon| Coord {
+                    x: lat_lon[1],
+                    y: lat_lon[0],
+                })
+                .collect();
+            if geometry.0.len() < 2 {
+                continue;
+            }
+            let Some(traversal) = self.add_traversal_with_shared_nodes(id, &geometry, &mut nodes)
+            else {
+                continue;
+            };
+            let length = xml_text(element, "Distance")
+                .and_then(|distance| distance.trim().parse::<f64>().ok())
+                .unwrap_or_else(|| self.traversal_length(traversal));
+            let properties: Properties = xml_text(element, "Name")
+                .map(|name| properties!("name" => name.trim()))
+                .unwrap_or_default();
+            self.set_traversal_properties(traversal, properties.clone());
+            links.insert(id, (traversal, length, properties, vec![]));
+        }
+
+        for projection in xml_elements(root, "PointProjection") {
+            let link = xml_elements(projection, "ProjectToLinkRef")
+                .find_map(|reference| reference.attribute("ref"));
+            let distance = xml_elements(projection, "Distance")
+                .filter(|distance| distance.parent() == Some(projection))
+                .find_map(|distance| distance.text()?.trim().parse::<f64>().ok());
+            let (Some((_, length, _, anchors)), Some(distance)) =
+                (link.and_then(|link| links.get_mut(link)), distance)
+            else {
+                continue;
+            };
+            let point = xml_elements(projection, "ProjectedPointRef")
+                .find_map(|reference| reference.attribute("ref"))
+                .or(projection.attribute("id"));
+            let id = projection.attribute("id").or(point).unwrap_or_default();
+            let anchor_index = self.add_projected_anchor(
+                id,
+                point,
+                (distance / *length).clamp(0., 1.),
+                properties!(),
+            );
+            anchors.push(AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm: distance,
+            });
+        }
+
+        let mut links: Vec<_> = links.into_iter().collect();
+        links.sort_by_key(|(_, (traversal, _, _, _))| *traversal);
+        for (id, (traversal, _, properties, anchors)) in links {
+            if !anchors.is_empty() {
+                self.add_lrm(id, traversal, &anchors, properties);
+            }
+        }
+        self.connect_traversal_extremities();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::builder::tests::geographic;
+    use crate::lrm_scale::LrmScaleMeasure;
+
+    use super::*;
+
+    #[test]
+    fn read_netex() {
+        let xml = r#"<PublicationDelivery xmlns="http://www.netex.org.uk/netex" xmlns:gml="http://www.opengis.net/gml/3.2">
+          <dataObjects><InfrastructureFrame id="if1">
+            <infrastructureElements>
+              <RailwayElement id="re1" version="1">
+                <Name>Main line</Name>
+                <Distance>2000</Distance>
+                <gml:LineString gml:id="ls1" srsName="EPSG:4326">
+                  <gml:posList>0 0 0 0.018</gml:posList>
+                </gml:LineString>
+              </RailwayElement>
+            </infrastructureElements>
+          </InfrastructureFrame>
+          <ServiceFrame id="sf1"><projections>
+            <PointProjection id="pp1" version="1">
+              <ProjectedPointRef ref="ssp1"/>
+              <ProjectToLinkRef ref="re1"/>
+              <Distance>0</Distance>
+            </PointProjection>
+            <PointProjection id="pp2" version="1">
+              <ProjectedPointRef ref="ssp2"/>
+              <ProjectToLinkRef ref="re1"/>
+              <Distance>1000</Distance>
+            </PointProjection>
+          </projections></ServiceFrame></dataObjects>
+        </PublicationDelivery>"#;
+        let mut b = Builder::new();
+        b.read_netex(xml).unwrap();
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        assert_eq!(geographic(&lrs).traversals[0].id, "re1");
+        assert_eq!(
+            geographic(&lrs).traversals[0].properties["name"],
+            "Main line"
+        );
+        let scale = &geographic(&lrs).lrms[0].scale;
+        assert_eq!(scale.id, "re1");
+        assert_eq!(scale.anchors.len(), 2);
+        assert_relative_eq!(scale.anchors[1].curve_position(), 0.5);
+        let point = lrs.resolve(0, &LrmScaleMeasure::new("ssp2", 0.)).unwrap();
+        assert_relative_eq!(point.x(), 0.009, epsilon = 1e-9);
+        assert_relative_eq!(point.y(), 0., epsilon = 1e-9);
+    }
+}
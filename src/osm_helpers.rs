@@ -110,6 +110,24 @@ pub fn sort_edges(
     sorted
 }
 
+/// Position in meters of an OpenStreetMap milestone, from a tag such as `railway:position`.
+///
+/// The value is either in kilometers (`12.345` or `12,345`) or in kilometers and meters (`12+345`).
+#[cfg(feature = "osm-routes")]
+pub fn milestone_position(value: &str) -> Option<f64> {
+    let value = value.trim();
+    match value.split_once('+') {
+        Some((km, m)) => {
+            Some(km.trim().parse::<f64>().ok()? * 1000. + m.trim().parse::<f64>().ok()?)
+        }
+        None => value
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok()
+            .map(|km| km * 1000.),
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use osm4routing::{Edge, NodeId};
@@ -209,4 +227,16 @@ pub mod tests {
         assert!(sorted[0].1);
         assert!(!sorted[1].1);
     }
+
+    #[cfg(feature = "osm-routes")]
+    #[test]
+    fn milestone_positions() {
+        assert_eq!(milestone_position("12"), Some(12000.));
+        assert_eq!(milestone_position("12.5"), Some(12500.));
+        assert_eq!(milestone_position("12,5"), Some(12500.));
+        assert_eq!(milestone_position("12+345"), Some(12345.));
+        assert_eq!(milestone_position(" 0+050 "), Some(50.));
+        assert_eq!(milestone_position("km 12"), None);
+        assert_eq!(milestone_position("12+"), None);
+    }
 }
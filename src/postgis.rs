@@ -0,0 +1,117 @@
+//! Builds an LRS from the tables of a PostGIS database, without exporting them to files first.
+//!
+//! The rows are streamed from the queries of [`PostGisQueries`], that return the geometries as WKB:
+//!
+//! ```no_run
+//! use liblrs::builder::Builder;
+//! use liblrs::postgis::PostGisQueries;
+//! use liblrs::properties;
+//!
+//! let mut client = postgres::Client::connect("host=localhost user=lrs", postgres::NoTls).unwrap();
+//! let queries = PostGisQueries {
+//!     traversals: "SELECT line_id, ST_AsBinary(geom) FROM lines".to_owned(),
+//!     anchors: "SELECT line_id, km, pk::float8, ST_AsBinary(geom) FROM kilometer_posts".to_owned(),
+//! };
+//! let lrs = Builder::from_postgis(&mut client, &queries)
+//!     .unwrap()
+//!     .build_bytes(properties!())
+//!     .unwrap();
+//! ```
+
+use std::collections::HashMap;
+
+use postgres::Client;
+use postgres::fallible_iterator::FallibleIterator;
+use postgres::types::ToSql;
+use thiserror::Error;
+
+use crate::builder::{AnchorOnLrm, Builder};
+use crate::properties;
+use crate::wkb;
+
+/// Errors when reading from PostGIS.
+#[derive(Debug, Error)]
+pub enum PostGisError {
+    /// A query failed, or a column does not have the expected type.
+    #[error(transparent)]
+    Postgres(#[from] postgres::Error),
+    /// The geometry of a traversal, or of an anchor of an LRM, is not a valid WKB LineString or Point.
+    #[error("invalid geometry for {0}")]
+    InvalidGeometry(String),
+    /// An anchor references an LRM that is not returned by the traversals query.
+    #[error("an anchor references the unknown lrm {0}")]
+    UnknownLrm(String),
+}
+
+/// The SQL queries returning the traversals and the anchors, column by column in this order.
+#[derive(Clone, Debug)]
+pub struct PostGisQueries {
+    /// The `id` (text) and the WKB LineString (bytea) of each traversal.
+    pub traversals: String,
+    /// The LRM `id` (text), the `name` (nullable text), the `measure` (float8) and the WKB Point (bytea) of each anchor.
+    pub anchors: String,
+}
+
+impl Default for PostGisQueries {
+    fn default() -> Self {
+        Self {
+            traversals: "SELECT id, ST_AsBinary(geom) FROM traversals".to_owned(),
+            anchors: "SELECT lrm_id, name, measure::float8, ST_AsBinary(geom) FROM anchors"
+                .to_owned(),
+        }
+    }
+}
+
+impl Builder<'_> {
+    /// Reads the traversals and the anchors returned by the `queries` on a PostGIS database.
+    ///
+    /// Each traversal gets an LRM with the same id, and the anchors are projected on the traversal of their LRM.
+    /// The traversals continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    pub fn from_postgis(
+        client: &mut Client,
+        queries: &PostGisQueries,
+    ) -> Result<Self, PostGisError> {
+        let mut builder = Self::new();
+        builder.add_source("postgis");
+
+        let mut lrms: Vec<(String, usize, Vec<AnchorOnLrm>)> = vec![];
+        let mut nodes = HashMap::new();
+        let mut rows = client.query_raw(&queries.traversals, std::iter::empty::<&dyn ToSql>())?;
+        while let Some(row) = rows.next()? {
+            let id: String = row.try_get(0)?;
+            let traversal = wkb::read_linestring(row.try_get(1)?)
+                .and_then(|geometry| {
+                    builder.add_traversal_with_shared_nodes(&id, &geometry, &mut nodes)
+                })
+                .ok_or_else(|| PostGisError::InvalidGeometry(id.clone()))?;
+            lrms.push((id, traversal, vec![]));
+        }
+
+        let mut rows = client.query_raw(&queries.anchors, std::iter::empty::<&dyn ToSql>())?;
+        while let Some(row) = rows.next()? {
+            let lrm_id: String = row.try_get(0)?;
+            let name: Option<String> = row.try_get(1)?;
+            let distance_along_lrm: f64 = row.try_get(2)?;
+            let point = wkb::read_point(row.try_get(3)?)
+                .ok_or_else(|| PostGisError::InvalidGeometry(lrm_id.clone()))?;
+            let Some((_, _, anchors)) = lrms.iter_mut().find(|(id, _, _)| *id == lrm_id) else {
+                return Err(PostGisError::UnknownLrm(lrm_id));
+            };
+            let id = name
+                .clone()
+                .unwrap_or_else(|| distance_along_lrm.to_string());
+            let anchor_index =
+                builder.add_anchor(&id, name.as_deref(), point.into(), properties!());
+            anchors.push(AnchorOnLrm {
+                anchor_index,
+                distance_along_lrm,
+            });
+        }
+
+        for (id, traversal, anchors) in lrms {
+            builder.add_lrm(&id, traversal, &anchors, properties!());
+        }
+        builder.connect_traversal_extremities();
+        Ok(builder)
+    }
+}
@@ -0,0 +1,229 @@
+//! Reads the `infrastructure` of railML 3 documents with [`Builder::read_railml`].
+
+use std::collections::HashMap;
+
+use geo::{Coord, LineString};
+
+use crate::builder::{AnchorOnLrm, Builder, BuilderError};
+use crate::lrs::Properties;
+use crate::properties;
+use crate::xml::{xml_elements, xml_number};
+
+impl Builder<'_> {
+    /// Read the `infrastructure` of a railML 3 document.
+    ///
+    /// Each `netElement` with at least two `geometricCoordinate`s becomes a `Traversal` with the same `id`.
+    /// The `Traversal`s continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    /// For each `linearPositioningSystem` used by its `linearCoordinate`s, it gets an LRM with the id `{positioning system}/{net element}`.
+    /// The `linearCoordinate`s are the `Anchor`s of the LRM: the first one is named after its measure, the others are unnamed.
+    /// A `mileageChange` located on the `netElement` by a `spotLocation` is an `Anchor` named after its `measureAfter`,
+    /// so that the measures after the change are expressed from it.
+    pub fn read_railml(&mut self, xml: &str) -> Result<(), BuilderError> {
+        let railml_error = |message: &str| BuilderError::RailMl(message.to_owned());
+        let document = roxmltree::Document::parse(xml).map_err(|e| railml_error(&e.to_string()))?;
+        let infrastructure = document
+            .descendants()
+            .find(|node| node.has_tag_name("infrastructure"))
+            .ok_or_else(|| railml_error("no infrastructure"))?;
+
+        let systems: HashMap<_, _> = xml_elements(infrastructure, "linearPositioningSystem")
+            .filter_map(|system| {
+                let properties: Properties = ["name", "units"]
+                    .into_iter()
+                    .filter_map(|key| Some((key.to_owned(), system.attribute(key)?.to_owned())))
+                    .collect();
+                Some((system.attribute("id")?, properties))
+            })
+            .collect();
+
+        // Position on the net element, measure before and measure after the change
+        let mut mileage_changes = HashMap::<&str, Vec<(f64, f64, f64)>>::new();
+        for change in xml_elements(infrastructure, "mileageChange") {
+            let (Some(before), Some(after)) = (
+                xml_number(change, "measureBefore"),
+                xml_number(change, "measureAfter"),
+            ) else {
+                continue;
+            };
+            for spot in xml_elements(change, "spotLocation") {
+                if let (Some(net_element), Some(position)) = (
+                    spot.attribute("netElementRef"),
+                    xml_number(spot, "intrinsicCoord"),
+                ) {
+                    mileage_changes
+                        .entry(net_element)
+                        .or_default()
+                        .push((position, before, after));
+                }
+            }
+        }
+
+        let mut nodes = HashMap::new();
+        for net_element in xml_elements(infrastructure, "netElement") {
+            let Some(id) = net_element.attribute("id") else {
+                continue;
+            };
+            // Position on the net element, geographical position and measures on the positioning systems
+            let mut coordinates: Vec<_> = xml_elements(net_element, "intrinsicCoordinate")
+                .filter_map(|coordinate| {
+                    let position = xml_number(coordinate, "intrinsicCoord")?;
+                    let coord = xml_elements(coordinate, "geometricCoordinate").find_map(|g| {
+                        Some(Coord {
+                            x: xml_number(g, "x")?,
+                            y: xml_number(g, "y")?,
+                        })
+                    });
+                    let measures: Vec<_> = xml_elements(coordinate, "linearCoordinate")
+                        .filter_map(|l| {
+                            Some((
+                                l.attribute("positioningSystemRef")?,
+                                xml_number(l, "measure")?,
+                            ))
+                        })
+                        .collect();
+                    Some((position, coord, measures))
+                })
+                .collect();
+            coordinates.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let geometry: LineString = coordinates
+                .iter()
+                .filter_map(|(_, coord, _)| *coord)
+                .collect();
+            if geometry.0.len() < 2 {
+                continue;
+            }
+            let Some(traversal) = self.add_traversal_with_shared_nodes(id, &geometry, &mut nodes)
+            else {
+                continue;
+            };
+
+            let mut changes = mileage_changes.remove(id).unwrap_or_default();
+            changes.sort_by(|a, b| a.0.total_cmp(&b.0));
+            // How much the measures jumped before a position
+            let shift = |position: f64| -> f64 {
+                changes
+                    .iter()
+                    .filter(|(change_position, _, _)| *change_position <= position)
+                    .map(|(_, before, after)| after - before)
+                    .sum()
+            };
+
+            let mut lrm_systems: Vec<&str> = coordinates
+                .iter()
+                .flat_map(|(_, _, measures)| measures.iter().map(|(system, _)| *system))
+                .collect();
+            lrm_systems.sort();
+            lrm_systems.dedup();
+            for system in lrm_systems {
+                let lrm_id = format!("{system}/{id}");
+                let mut anchors = vec![];
+                for (position, coord, measures) in &coordinates {
+                    for (_, measure) in measures.iter().filter(|(s, _)| *s == system) {
+                        let anchor_id = format!("{lrm_id}/{}", anchors.len());
+                        let name = anchors.is_empty().then(|| measure.to_string());
+                        let anchor_index = match coord {
+                            Some(coord) => {
+                                self.add_anchor(&anchor_id, name.as_deref(), *coord, properties!())
+                            }
+                            None => self.add_projected_anchor(
+                                &anchor_id,
+                                name.as_deref(),
+                                *position,
+                                properties!(),
+                            ),
+                        };
+                        anchors.push(AnchorOnLrm {
+                            anchor_index,
+                            distance_along_lrm: measure - shift(*position),
+                        });
+                    }
+                }
+                for &(position, before, after) in &changes {
+                    let anchor_id = format!("{lrm_id}/{}", anchors.len());
+                    let anchor_index = self.add_projected_anchor(
+                        &anchor_id,
+                        Some(&after.to_string()),
+                        position,
+                        properties!(),
+                    );
+                    anchors.push(AnchorOnLrm {
+                        anchor_index,
+                        distance_along_lrm: before - shift(position) + (after - before),
+                    });
+                }
+                let properties = systems.get(system).cloned().unwrap_or_default();
+                self.add_lrm(&lrm_id, traversal, &anchors, properties);
+            }
+        }
+
+        self.connect_traversal_extremities();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::tests::geographic;
+    use crate::lrm_scale;
+
+    use super::*;
+
+    #[test]
+    fn read_railml() {
+        let xml = r#"<railML xmlns="https://www.railml.org/schemas/3.2">
+          <infrastructure id="is01">
+            <topology><netElements>
+              <netElement id="ne01">
+                <associatedPositioningSystem id="aps01">
+                  <intrinsicCoordinate intrinsicCoord="0">
+                    <linearCoordinate positioningSystemRef="lps01" measure="0"/>
+                    <geometricCoordinate positioningSystemRef="gps01" x="0" y="0"/>
+                  </intrinsicCoordinate>
+                  <intrinsicCoordinate intrinsicCoord="1">
+                    <linearCoordinate positioningSystemRef="lps01" measure="2500"/>
+                    <geometricCoordinate positioningSystemRef="gps01" x="0.01" y="0"/>
+                  </intrinsicCoordinate>
+                </associatedPositioningSystem>
+              </netElement>
+              <netElement id="ne02"/>
+            </netElements></topology>
+            <functionalInfrastructure><mileageChanges>
+              <mileageChange id="mc01" measureBefore="500" measureAfter="2000">
+                <spotLocation netElementRef="ne01" intrinsicCoord="0.5"/>
+              </mileageChange>
+            </mileageChanges></functionalInfrastructure>
+            <positioning><linearPositioningSystems>
+              <linearPositioningSystem id="lps01" units="m"/>
+            </linearPositioningSystems></positioning>
+          </infrastructure>
+        </railML>"#;
+        let mut b = Builder::new();
+        b.read_railml(xml).unwrap();
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        assert_eq!(geographic(&lrs).traversals.len(), 1);
+        let lrm = &geographic(&lrs).lrms[0];
+        assert_eq!(lrm.scale.id, "lps01/ne01");
+        assert_eq!(lrm.properties["units"], "m");
+        let anchors: Vec<_> = lrm
+            .scale
+            .anchors
+            .iter()
+            .map(|anchor| match anchor {
+                lrm_scale::Anchor::Named(anchor) => {
+                    (Some(anchor.name.as_str()), anchor.scale_position)
+                }
+                lrm_scale::Anchor::Unnamed(anchor) => (None, anchor.scale_position),
+            })
+            .collect();
+        assert_eq!(
+            anchors,
+            [(Some("0"), 0.), (Some("2000"), 500.), (None, 1000.)]
+        );
+
+        assert!(matches!(
+            Builder::new().read_railml("<railML/>"),
+            Err(BuilderError::RailMl(_))
+        ));
+    }
+}
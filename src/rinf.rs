@@ -0,0 +1,147 @@
+//! Reads the operational points of the Register of Infrastructure of the European Union with [`Builder::read_rinf`].
+
+use std::collections::HashMap;
+
+use geo::{Coord, LineString};
+
+use crate::builder::{AnchorOnLrm, Builder, BuilderError};
+use crate::lrs::Properties;
+use crate::properties;
+use crate::xml::xml_elements;
+
+impl Builder<'_> {
+    /// Read the operational points of an export of the Register of Infrastructure (RINF) of the European Union Agency for Railways.
+    ///
+    /// Each national line (`NationalIdentNum` of an `OPRailwayLocation`) gets an LRM with the same `id`.
+    /// Its `Anchor`s are the operational points on the line, named after their `Kilometer`, at the position given by `OPGeographicLocation`.
+    /// The `Anchor`s are projected on the `Traversal` with the same `id` if there is one, e.g. from OpenStreetMap.
+    /// Otherwise, as RINF has no track geometry, a `Traversal` is drawn through the operational points of the line.
+    /// The `Traversal`s continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    pub fn read_rinf(&mut self, xml: &str) -> Result<(), BuilderError> {
+        let document =
+            roxmltree::Document::parse(xml).map_err(|e| BuilderError::Rinf(e.to_string()))?;
+        // The values are attributes, the decimal separator can be a comma
+        let value = |node: roxmltree::Node, tag: &'static str, attribute: &str| {
+            xml_elements(node, tag)
+                .find_map(|child| child.attribute(attribute))
+                .map(|value| value.trim().to_owned())
+        };
+        let number = |value: &str| value.replace(',', ".").parse::<f64>().ok();
+
+        // Kilometer, position, id and name of the operational points of each line
+        let mut lines = HashMap::<String, Vec<(f64, String, Coord, Properties)>>::new();
+        for point in xml_elements(document.root_element(), "OperationalPoint") {
+            let coord = xml_elements(point, "OPGeographicLocation").find_map(|location| {
+                Some(Coord {
+                    x: number(location.attribute("Longitude")?)?,
+                    y: number(location.attribute("Latitude")?)?,
+                })
+            });
+            let Some(coord) = coord else {
+                continue;
+            };
+            let id = value(point, "UniqueOPID", "Value").unwrap_or_default();
+            let mut properties: Properties = properties!("op_id" => id);
+            if let Some(name) = value(point, "OPName", "Value") {
+                properties.insert("name".to_owned(), name);
+            }
+            for location in xml_elements(point, "OPRailwayLocation") {
+                let (Some(line), Some(kilometer)) = (
+                    location.attribute("NationalIdentNum"),
+                    location.attribute("Kilometer"),
+                ) else {
+                    continue;
+                };
+                if let Some(km) = number(kilometer) {
+                    lines.entry(line.trim().to_owned()).or_default().push((
+                        km,
+                        kilometer.trim().to_owned(),
+                        coord,
+                        properties.clone(),
+                    ));
+                }
+            }
+        }
+
+        let traversals = self.get_traversal_indexes();
+        let mut nodes = HashMap::new();
+        let mut lines: Vec<_> = lines.into_iter().collect();
+        lines.sort_by(|a, b| a.0.cmp(&b.0));
+        for (line, mut points) in lines {
+            points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let traversal = match traversals.get(&line) {
+                Some(&traversal) => Some(traversal),
+                None => {
+                    let mut geometry: LineString =
+                        points.iter().map(|(_, _, coord, _)| *coord).collect();
+                    geometry.0.dedup();
+                    if geometry.0.len() < 2 {
+                        None
+                    } else {
+                        self.add_traversal_with_shared_nodes(&line, &geometry, &mut nodes)
+                    }
+                }
+            };
+            let Some(traversal) = traversal else {
+                continue;
+            };
+            let anchors: Vec<_> = points
+                .into_iter()
+                .map(|(km, kilometer, coord, properties)| AnchorOnLrm {
+                    anchor_index: self.add_anchor(
+                        &format!("{line}/{kilometer}"),
+                        Some(&kilometer),
+                        coord,
+                        properties,
+                    ),
+                    distance_along_lrm: km * 1000.,
+                })
+                .collect();
+            self.add_lrm(&line, traversal, &anchors, properties!());
+        }
+
+        self.connect_traversal_extremities();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::tests::geographic;
+    use crate::lrm_scale;
+
+    use super::*;
+
+    #[test]
+    fn read_rinf() {
+        let xml = r#"<RINFData>
+          <OperationalPoint>
+            <OPName Value="Alpha"/>
+            <UniqueOPID Value="XX00001"/>
+            <OPGeographicLocation Longitude="0,0" Latitude="0,0"/>
+            <OPRailwayLocation NationalIdentNum="100000" Kilometer="1,000"/>
+          </OperationalPoint>
+          <OperationalPoint>
+            <OPName Value="Beta"/>
+            <UniqueOPID Value="XX00002"/>
+            <OPGeographicLocation Longitude="0,02" Latitude="0,0"/>
+            <OPRailwayLocation NationalIdentNum="100000" Kilometer="3,200"/>
+            <OPRailwayLocation NationalIdentNum="200000" Kilometer="0,000"/>
+          </OperationalPoint>
+        </RINFData>"#;
+        let mut b = Builder::new();
+        b.read_rinf(xml).unwrap();
+        let lrs = b.build_lrs(properties!()).unwrap();
+
+        // Line 200000 has a single operational point, it can not be drawn
+        assert_eq!(geographic(&lrs).lrms.len(), 1);
+        let scale = &geographic(&lrs).lrms[0].scale;
+        assert_eq!(scale.id, "100000");
+        assert_eq!(scale.anchors[1].scale_position(), 3200.);
+        assert_eq!(scale.anchors[1].properties()["name"], "Beta");
+        let lrm_scale::Anchor::Named(anchor) = &scale.anchors[0] else {
+            panic!("the anchor should be named");
+        };
+        assert_eq!(anchor.name, "1,000");
+    }
+}
@@ -0,0 +1,94 @@
+//! Approximate matching of identifiers, such as the ids of the LRMs typed in a search box.
+//!
+//! The comparison ignores the case. An exact match is better than a prefix, that is better than a substring,
+//! that is better than an id with a few typos.
+
+/// Score of an exact match.
+const EXACT: f64 = 1.;
+/// Best score of an id starting with the query.
+const PREFIX: f64 = 0.9;
+/// Best score of an id containing the query.
+const SUBSTRING: f64 = 0.7;
+/// Best score of an id close to the query.
+const FUZZY: f64 = 0.5;
+/// Below this similarity, an id is not considered close to the query.
+const MIN_SIMILARITY: f64 = 0.6;
+
+/// How well a `candidate` matches a `query`, between 0 (excluded) and 1, or `None` if it does not match.
+///
+/// Among the candidates matching the same way, the shortest ones have the best scores.
+/// The fuzzy matching tolerates a few typos in the query, compared to the whole candidate or to its start.
+pub fn match_score(query: &str, candidate: &str) -> Option<f64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    if query.is_empty() || candidate.is_empty() {
+        return None;
+    }
+    // Up to 0.1 less when the query covers only a small part of the candidate
+    let uncovered = 0.1 * (1. - query.len().min(candidate.len()) as f64 / candidate.len() as f64);
+
+    if query == candidate {
+        Some(EXACT)
+    } else if candidate.starts_with(&query) {
+        Some(PREFIX - uncovered)
+    } else if candidate.windows(query.len()).any(|window| window == query) {
+        Some(SUBSTRING - uncovered)
+    } else {
+        let start = &candidate[..query.len().min(candidate.len())];
+        let similarity = similarity(&query, &candidate).max(similarity(&query, start));
+        (similarity >= MIN_SIMILARITY).then_some(FUZZY * similarity)
+    }
+}
+
+// 1 for identical strings, 0 when all the characters differ, from the Levenshtein distance
+fn similarity(a: &[char], b: &[char]) -> f64 {
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 1.;
+    }
+    1. - levenshtein(a, b) as f64 / len as f64
+}
+
+// How many characters to insert, remove or replace to turn `a` into `b`
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, char_a) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, char_b) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(char_a != char_b);
+            current[j + 1] = replace.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores() {
+        assert_eq!(match_score("Line-A", "line-a"), Some(1.));
+        let prefix = match_score("line", "line-a").unwrap();
+        let longer_prefix = match_score("line", "line-abc").unwrap();
+        let substring = match_score("ne-a", "line-a").unwrap();
+        let typo = match_score("lime-a", "line-a").unwrap();
+        let typo_while_typing = match_score("lime", "line-a").unwrap();
+        assert!(1. > prefix && prefix > longer_prefix && longer_prefix > substring);
+        assert!(substring > typo && typo > typo_while_typing && typo_while_typing > 0.);
+
+        assert_eq!(match_score("xyz", "line-a"), None);
+        assert_eq!(match_score("", "line-a"), None);
+        assert_eq!(match_score("line", ""), None);
+    }
+
+    #[test]
+    fn levenshtein_distance() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(levenshtein(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(levenshtein(&chars(""), &chars("abc")), 3);
+        assert_eq!(levenshtein(&chars("abc"), &chars("abc")), 0);
+    }
+}
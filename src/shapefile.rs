@@ -0,0 +1,184 @@
+//! Reads the polylines of shapefiles with [`Builder::read_shapefile`].
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use geo::{Coord, LineString};
+
+use crate::builder::{AnchorOnLrm, Builder, BuilderError};
+use crate::properties;
+
+/// Names of the attributes read by [`Builder::read_shapefile`].
+#[derive(Clone, Debug)]
+pub struct ShapefileMapping {
+    /// Identifier of the `Traversal` and of its LRM. The index of the record is used when it is missing.
+    pub id: String,
+    /// Measure at the start of a polyline without M coordinates.
+    pub from_measure: String,
+    /// Measure at the end of a polyline without M coordinates.
+    pub to_measure: String,
+}
+
+impl Default for ShapefileMapping {
+    fn default() -> Self {
+        Self {
+            id: "id".to_owned(),
+            from_measure: "from_m".to_owned(),
+            to_measure: "to_m".to_owned(),
+        }
+    }
+}
+
+// A dBase attribute as a string
+fn dbase_string(value: &::shapefile::dbase::FieldValue) -> Option<String> {
+    use ::shapefile::dbase::FieldValue;
+    match value {
+        FieldValue::Character(Some(value)) => Some(value.trim().to_owned()),
+        FieldValue::Numeric(Some(value)) => Some(value.to_string()),
+        FieldValue::Float(Some(value)) => Some(value.to_string()),
+        FieldValue::Integer(value) => Some(value.to_string()),
+        FieldValue::Double(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+// The vertices of the parts of a polyline, with their measure.
+// The vertex shared by two parts is kept once, and the M values below the no data value are ignored.
+fn measured_vertices(parts: impl Iterator<Item = (Coord, f64)>) -> Vec<(Coord, Option<f64>)> {
+    let mut vertices: Vec<(Coord, Option<f64>)> = vec![];
+    for (coord, m) in parts {
+        let m = (m.is_finite() && m > ::shapefile::NO_DATA).then_some(m);
+        match vertices.last_mut() {
+            Some((last, last_m)) if *last == coord => *last_m = last_m.or(m),
+            _ => vertices.push((coord, m)),
+        }
+    }
+    vertices
+}
+
+impl Builder<'_> {
+    /// Read the polylines of a shapefile, such as legacy route data.
+    ///
+    /// Each polyline becomes a `Traversal` and an LRM with the same `id`, its parts are joined.
+    /// The `Traversal`s continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    /// The vertices with an M coordinate are the `Anchor`s of the LRM, so that no calibration point is lost:
+    /// the first one is named after its measure, the others are unnamed.
+    /// Without M coordinates, the measures at both ends are read from the attributes given by the `mapping`.
+    /// The other attributes are the properties of the LRM.
+    pub fn read_shapefile(
+        &mut self,
+        input_file: &PathBuf,
+        mapping: &ShapefileMapping,
+    ) -> Result<(), BuilderError> {
+        use ::shapefile::Shape;
+
+        let shapefile_error = |e: ::shapefile::Error| BuilderError::Shapefile(e.to_string());
+        self.add_source(&input_file.to_string_lossy());
+        let mut reader = ::shapefile::Reader::from_path(input_file).map_err(shapefile_error)?;
+        let mut nodes = HashMap::new();
+        for (index, shape_and_record) in reader.iter_shapes_and_records().enumerate() {
+            let (shape, record) = shape_and_record.map_err(shapefile_error)?;
+            let vertices = match shape {
+                Shape::Polyline(polyline) => measured_vertices(
+                    polyline
+                        .parts()
+                        .iter()
+                        .flatten()
+                        .map(|p| (Coord { x: p.x, y: p.y }, f64::NAN)),
+                ),
+                Shape::PolylineM(polyline) => measured_vertices(
+                    polyline
+                        .parts()
+                        .iter()
+                        .flatten()
+                        .map(|p| (Coord { x: p.x, y: p.y }, p.m)),
+                ),
+                Shape::PolylineZ(polyline) => measured_vertices(
+                    polyline
+                        .parts()
+                        .iter()
+                        .flatten()
+                        .map(|p| (Coord { x: p.x, y: p.y }, p.m)),
+                ),
+                _ => continue,
+            };
+
+            let attributes: HashMap<String, ::shapefile::dbase::FieldValue> = record.into();
+            let attribute = |name: &str| attributes.get(name).and_then(dbase_string);
+            let id = attribute(&mapping.id).unwrap_or_else(|| index.to_string());
+            let geometry: LineString = vertices.iter().map(|(coord, _)| *coord).collect();
+            let Some(traversal) = self.add_traversal_with_shared_nodes(&id, &geometry, &mut nodes)
+            else {
+                continue;
+            };
+
+            let mut calibration: Vec<_> = vertices
+                .iter()
+                .filter_map(|(coord, m)| Some((*coord, (*m)?)))
+                .collect();
+            if calibration.is_empty() {
+                let measure = |name: &str| attribute(name)?.parse::<f64>().ok();
+                if let (Some(from), Some(to)) =
+                    (measure(&mapping.from_measure), measure(&mapping.to_measure))
+                {
+                    calibration = vec![
+                        (geometry.0[0], from),
+                        (geometry.0[geometry.0.len() - 1], to),
+                    ];
+                }
+            }
+            let anchors: Vec<_> = calibration
+                .into_iter()
+                .enumerate()
+                .map(|(i, (coord, m))| {
+                    let name = (i == 0).then(|| m.to_string());
+                    AnchorOnLrm {
+                        anchor_index: self.add_anchor(
+                            &format!("{id}/{i}"),
+                            name.as_deref(),
+                            coord,
+                            properties!(),
+                        ),
+                        distance_along_lrm: m,
+                    }
+                })
+                .collect();
+            if !anchors.is_empty() {
+                let properties = attributes
+                    .iter()
+                    .filter_map(|(key, value)| Some((key.clone(), dbase_string(value)?)))
+                    .collect();
+                self.add_lrm(&id, traversal, &anchors, properties);
+            }
+        }
+
+        self.connect_traversal_extremities();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::coord;
+
+    use super::*;
+
+    #[test]
+    fn shapefile_measured_vertices() {
+        let parts = [
+            (coord! {x: 0., y: 0.}, 0.),
+            (coord! {x: 1., y: 0.}, 100.),
+            // Start of the second part
+            (coord! {x: 1., y: 0.}, 100.),
+            (coord! {x: 2., y: 0.}, -1e39),
+        ];
+        assert_eq!(
+            measured_vertices(parts.into_iter()),
+            [
+                (coord! {x: 0., y: 0.}, Some(0.)),
+                (coord! {x: 1., y: 0.}, Some(100.)),
+                (coord! {x: 2., y: 0.}, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Sharing one loaded [`Lrs`] between threads, e.g. the workers of a web server.
+//!
+//! An [`Lrs`] is immutable once loaded and all the queries take `&self`: it can be queried from
+//! many threads at the same time without any lock. [`SharedLrs`] is a cheap to clone handle on it.
+//!
+//! ```no_run
+//! use liblrs::curves::SphericalLineStringCurve;
+//! use liblrs::shared::SharedLrs;
+//! use liblrs::lrs::LrsBase;
+//! use geo::point;
+//!
+//! let lrs = SharedLrs::<SphericalLineStringCurve>::new("network.lrs").unwrap();
+//! let workers: Vec<_> = (0..4)
+//!     .map(|i| {
+//!         let lrs = lrs.clone();
+//!         std::thread::spawn(move || lrs.lookup_lrms(point! {x: 2.35 + i as f64 * 0.01, y: 48.85}))
+//!     })
+//!     .collect();
+//! for worker in workers {
+//!     println!("{} lrms found", worker.join().unwrap().len());
+//! }
+//! ```
+
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::curves::{Curve, PlanarLineStringCurve, SphericalLineStringCurve};
+use crate::lrs::{Lrs, LrsError};
+use crate::lrs_ext::ExtLrs;
+
+/// A handle on an [`Lrs`] that can be cloned and sent to other threads.
+///
+/// Cloning the handle does not copy the [`Lrs`]. It dereferences to the [`Lrs`] to query it.
+pub struct SharedLrs<CurveImpl: Curve>(Arc<Lrs<CurveImpl>>);
+
+impl<CurveImpl: Curve> SharedLrs<CurveImpl> {
+    /// Loads an [`Lrs`] from the file system, see [`Lrs::new`].
+    pub fn new<P: AsRef<std::path::Path>>(filename: P) -> Result<Self, LrsError> {
+        Lrs::new(filename).map(Self::from)
+    }
+
+    /// Loads an [`Lrs`] from a byte array, see [`Lrs::from_bytes`].
+    pub fn from_bytes(buf: &[u8]) -> Result<Self, LrsError> {
+        Lrs::from_bytes(buf).map(Self::from)
+    }
+
+    /// How many handles share the [`Lrs`].
+    pub fn handle_count(&self) -> usize {
+        Arc::strong_count(&self.0)
+    }
+
+    /// Returns the [`Lrs`] if this is its last handle, e.g. to modify it. Otherwise, returns the handle.
+    pub fn try_unwrap(self) -> Result<Lrs<CurveImpl>, Self> {
+        Arc::try_unwrap(self.0).map_err(Self)
+    }
+}
+
+impl<CurveImpl: Curve> Clone for SharedLrs<CurveImpl> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<CurveImpl: Curve> Deref for SharedLrs<CurveImpl> {
+    type Target = Lrs<CurveImpl>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<CurveImpl: Curve> From<Lrs<CurveImpl>> for SharedLrs<CurveImpl> {
+    fn from(lrs: Lrs<CurveImpl>) -> Self {
+        Self(Arc::new(lrs))
+    }
+}
+
+impl<CurveImpl: Curve> From<Arc<Lrs<CurveImpl>>> for SharedLrs<CurveImpl> {
+    fn from(lrs: Arc<Lrs<CurveImpl>>) -> Self {
+        Self(lrs)
+    }
+}
+
+// Fails to compile if a type meant to be shared between threads stops being `Send` and `Sync`,
+// for instance because of a cache with interior mutability
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<PlanarLineStringCurve>();
+    assert_send_sync::<SphericalLineStringCurve>();
+    assert_send_sync::<Lrs<PlanarLineStringCurve>>();
+    assert_send_sync::<Lrs<SphericalLineStringCurve>>();
+    assert_send_sync::<ExtLrs>();
+    assert_send_sync::<SharedLrs<SphericalLineStringCurve>>();
+};
+
+#[cfg(test)]
+mod tests {
+    use geo::point;
+
+    use crate::lrs::LrsBase;
+    use crate::lrs::tests::lrs;
+
+    use super::*;
+
+    #[test]
+    fn concurrent_queries() {
+        let shared = SharedLrs::from(lrs());
+        let workers: Vec<_> = (0..4)
+            .map(|i| {
+                let lrs = shared.clone();
+                std::thread::spawn(move || lrs.lookup_lrms(point! {x: 50. * i as f64, y: 0.5}))
+            })
+            .collect();
+        for worker in workers {
+            assert_eq!(worker.join().unwrap().len(), 2);
+        }
+
+        assert_eq!(shared.handle_count(), 1);
+        assert!(shared.try_unwrap().is_ok());
+    }
+}
@@ -0,0 +1,110 @@
+//! The spatial indexes of the [`crate::lrs::Traversal`]s, written by the [`crate::builder::Builder`] and by [`crate::lrs::Lrs::to_bytes`].
+
+use geo::{Coord, LineString, Rect};
+use geo_index::rtree::RTreeBuilder;
+use geo_index::rtree::sort::HilbertSort;
+
+use crate::curves::Curve;
+
+/// Number of points of the fragments of a [`crate::lrs::Traversal`] stored in the spatial index.
+const FRAGMENT_SIZE: usize = 16;
+
+/// Splits the coordinates of a curve in fragments of at most [`FRAGMENT_SIZE`] points.
+/// Consecutive fragments share a point, so that every line of the curve belongs to a fragment.
+fn fragments(coords: &[Coord]) -> impl Iterator<Item = &[Coord]> {
+    (0..coords.len().saturating_sub(1))
+        .step_by(FRAGMENT_SIZE - 1)
+        .map(move |start| &coords[start..(start + FRAGMENT_SIZE).min(coords.len())])
+}
+
+/// The serialized spatial indexes of the [`crate::lrs::Traversal`]s.
+pub(crate) struct SpatialIndexes {
+    /// One bounding box per [`crate::lrs::Traversal`].
+    pub lrm_rtree: Vec<u8>,
+    /// One bounding box per fragment of [`crate::lrs::Traversal`].
+    pub fragment_rtree: Vec<u8>,
+    /// The index of the [`crate::lrs::Traversal`] of every fragment.
+    pub fragment_traversals: Vec<u32>,
+}
+
+/// Adds the bounding box of an item to the RTree.
+fn add_bbox(rtree_builder: &mut RTreeBuilder<f64>, bbox: Option<Rect>) {
+    match bbox {
+        Some(bbox) => {
+            rtree_builder.add_rect(&bbox);
+        }
+        // An inverted rectangle never matches a search, but keeps the indices aligned
+        None => {
+            rtree_builder.add(
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            );
+        }
+    }
+}
+
+/// Builds the spatial indexes of the curves of the [`crate::lrs::Traversal`]s, given in the order of the [`crate::lrs::Traversal`]s.
+pub(crate) fn spatial_indexes<'a, C: Curve + 'a>(
+    curves: impl Iterator<Item = &'a C> + Clone,
+) -> SpatialIndexes {
+    let mut rtree_builder = RTreeBuilder::<f64>::new(curves.clone().count() as u32);
+    for curve in curves.clone() {
+        add_bbox(
+            &mut rtree_builder,
+            curve.bbox().ok().filter(|_| curve.is_valid()),
+        );
+    }
+    let tree = rtree_builder.finish::<HilbertSort>();
+
+    let geometries: Vec<_> = curves
+        .map(|curve| {
+            curve
+                .is_valid()
+                .then(|| (curve.as_linestring(), curve.max_extent()))
+        })
+        .collect();
+    let mut fragment_builder = RTreeBuilder::<f64>::new(
+        geometries
+            .iter()
+            .flatten()
+            .map(|(geometry, _)| fragments(&geometry.0).count() as u32)
+            .sum(),
+    );
+    let mut fragment_traversals = vec![];
+    for (traversal_idx, geometry) in geometries.iter().enumerate() {
+        let Some((geometry, max_extent)) = geometry else {
+            continue;
+        };
+        for coords in fragments(&geometry.0) {
+            let fragment = C::new(LineString::from(coords.to_vec()), *max_extent);
+            add_bbox(&mut fragment_builder, fragment.bbox().ok());
+            fragment_traversals.push(traversal_idx as u32);
+        }
+    }
+    let fragment_tree = fragment_builder.finish::<HilbertSort>();
+
+    SpatialIndexes {
+        lrm_rtree: tree.into_inner(),
+        fragment_rtree: fragment_tree.into_inner(),
+        fragment_traversals,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::coord;
+
+    use super::*;
+
+    #[test]
+    fn split_fragments() {
+        let coords: Vec<_> = (0..31).map(|i| coord! {x: i as f64, y: 0.}).collect();
+        let lengths: Vec<_> = fragments(&coords).map(|f| f.len()).collect();
+        assert_eq!(lengths, [16, 16]);
+        let lengths: Vec<_> = fragments(&coords[..20]).map(|f| f.len()).collect();
+        assert_eq!(lengths, [16, 5]);
+        assert_eq!(fragments(&coords[..1]).count(), 0);
+    }
+}
@@ -0,0 +1,192 @@
+//! Summary statistics of an [`Lrs`], to sanity-check a generated file before publishing it.
+
+use std::fmt;
+
+use geo::{BoundingRect, Rect};
+
+use crate::curves::Curve;
+use crate::lrm_scale::Anchor;
+use crate::lrs::Lrs;
+
+/// Counts and sizes describing an [`Lrs`], see [`Lrs::stats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LrsStats {
+    /// Number of LRMs.
+    pub lrm_count: usize,
+    /// Number of traversals.
+    pub traversal_count: usize,
+    /// Number of traversals without a valid geometry. They cannot be used to locate a measure.
+    pub invalid_traversal_count: usize,
+    /// Number of named anchors over all the LRMs.
+    pub anchor_count: usize,
+    /// Number of unnamed anchors over all the LRMs.
+    pub unnamed_anchor_count: usize,
+    /// Sum of the lengths of the valid traversals, in meters for a geographic [`Lrs`].
+    pub total_length: f64,
+    /// Bounding box of all the valid traversals, without any buffer.
+    pub bbox: Option<Rect>,
+    /// How the lengths of the LRMs are distributed. `None` if there is no LRM on a valid traversal.
+    pub lrm_lengths: Option<LengthDistribution>,
+}
+
+/// Distribution of the lengths of the LRMs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LengthDistribution {
+    /// Length of the shortest LRM.
+    pub min: f64,
+    /// Length of the longest LRM.
+    pub max: f64,
+    /// Average length of the LRMs.
+    pub mean: f64,
+    /// Median length of the LRMs.
+    pub median: f64,
+}
+
+impl LengthDistribution {
+    fn new(mut lengths: Vec<f64>) -> Option<Self> {
+        if lengths.is_empty() {
+            return None;
+        }
+        lengths.sort_by(f64::total_cmp);
+        let count = lengths.len();
+        let median = if count.is_multiple_of(2) {
+            (lengths[count / 2 - 1] + lengths[count / 2]) / 2.
+        } else {
+            lengths[count / 2]
+        };
+        Some(Self {
+            min: lengths[0],
+            max: lengths[count - 1],
+            mean: lengths.iter().sum::<f64>() / count as f64,
+            median,
+        })
+    }
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Summary of the content of the [`Lrs`]: how many objects it has, how long and how large the network is.
+    pub fn stats(&self) -> LrsStats {
+        let (anchor_count, unnamed_anchor_count) = self
+            .lrms
+            .iter()
+            .flat_map(|lrm| &lrm.scale.anchors)
+            .fold((0, 0), |(named, unnamed), anchor| match anchor {
+                Anchor::Named(_) => (named + 1, unnamed),
+                Anchor::Unnamed(_) => (named, unnamed + 1),
+            });
+
+        let valid_curves = || {
+            self.traversals
+                .iter()
+                .map(|traversal| &*traversal.curve)
+                .filter(|curve| curve.is_valid())
+        };
+        let bbox = valid_curves()
+            .filter_map(|curve| curve.as_linestring().bounding_rect())
+            .reduce(|a, b| {
+                Rect::new(
+                    (a.min().x.min(b.min().x), a.min().y.min(b.min().y)),
+                    (a.max().x.max(b.max().x), a.max().y.max(b.max().y)),
+                )
+            });
+
+        let lrm_lengths = self
+            .lrms
+            .iter()
+            .filter_map(|lrm| self.traversals.get(lrm.traversal.0))
+            .map(|traversal| &*traversal.curve)
+            .filter(|curve| curve.is_valid())
+            .map(|curve| curve.length())
+            .collect();
+
+        LrsStats {
+            lrm_count: self.lrms.len(),
+            traversal_count: self.traversals.len(),
+            invalid_traversal_count: self.traversals.len() - valid_curves().count(),
+            anchor_count,
+            unnamed_anchor_count,
+            total_length: valid_curves().map(|curve| curve.length()).sum(),
+            bbox,
+            lrm_lengths: LengthDistribution::new(lrm_lengths),
+        }
+    }
+}
+
+impl fmt::Display for LrsStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "LRMs: {}", self.lrm_count)?;
+        writeln!(
+            f,
+            "Traversals: {} ({} without a valid geometry)",
+            self.traversal_count, self.invalid_traversal_count
+        )?;
+        writeln!(
+            f,
+            "Anchors: {} named, {} unnamed",
+            self.anchor_count, self.unnamed_anchor_count
+        )?;
+        writeln!(f, "Total length: {:.0}", self.total_length)?;
+        if let Some(bbox) = self.bbox {
+            writeln!(
+                f,
+                "Bounding box: ({}, {}) – ({}, {})",
+                bbox.min().x,
+                bbox.min().y,
+                bbox.max().x,
+                bbox.max().y
+            )?;
+        }
+        if let Some(lengths) = self.lrm_lengths {
+            writeln!(
+                f,
+                "LRM lengths: min {:.0}, median {:.0}, mean {:.0}, max {:.0}",
+                lengths.min, lengths.median, lengths.mean, lengths.max
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{coord, line_string};
+
+    use crate::curves::PlanarLineStringCurve;
+    use crate::lrs::tests::lrs;
+
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let mut lrs = lrs();
+        *lrs.traversals[1].curve =
+            PlanarLineStringCurve::new(line_string![(x: 0., y: -1.), (x: 100., y: -1.)], 1.);
+        let stats = lrs.stats();
+        assert_eq!(stats.lrm_count, 2);
+        assert_eq!(stats.traversal_count, 2);
+        assert_eq!(stats.invalid_traversal_count, 0);
+        assert_eq!(stats.anchor_count, 4);
+        assert_eq!(stats.unnamed_anchor_count, 0);
+        assert_eq!(stats.total_length, 300.);
+        assert_eq!(
+            stats.bbox,
+            Some(Rect::new(coord! {x: 0., y: -1.}, coord! {x: 200., y: 0.}))
+        );
+        assert_eq!(
+            stats.lrm_lengths,
+            Some(LengthDistribution {
+                min: 100.,
+                max: 200.,
+                mean: 150.,
+                median: 150.,
+            })
+        );
+
+        *lrs.traversals[1].curve = PlanarLineStringCurve::new(line_string![(x: 0., y: 0.)], 1.);
+        let stats = lrs.stats();
+        assert_eq!(stats.invalid_traversal_count, 1);
+        assert_eq!(stats.total_length, 200.);
+        assert_eq!(stats.lrm_lengths.unwrap().median, 200.);
+        assert!(stats.to_string().starts_with("LRMs: 2\n"));
+    }
+}
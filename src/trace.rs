@@ -0,0 +1,165 @@
+//! Map-matching of an ordered sequence of positions, such as a GNSS log of a train, onto the [`crate::lrs::Lrm`]s.
+//!
+//! Projecting each position independently makes the measures jump between parallel LRMs
+//! and back and forth along an LRM because of the noise of the positions.
+//! The trace stays on the same LRM as long as it is near enough, and the measures only move in one direction.
+
+use std::ops::Range;
+
+use geo::Point;
+
+use crate::curves::Curve;
+use crate::lrm_scale::{LrmScaleMeasure, ScalePosition};
+use crate::lrs::{LrmHandle, LrmProjection, Lrs, LrsBase};
+
+/// Parameters of [`Lrs::project_trace`].
+#[derive(Clone, Copy, Debug)]
+pub struct TraceOptions {
+    /// A position farther than `max_distance` from an LRM cannot be matched on it.
+    /// In meters for a geographic [`Lrs`].
+    pub max_distance: f64,
+    /// How much the measure can go backward, in scale units, before the position is considered as noise and ignored.
+    pub backward_tolerance: ScalePosition,
+}
+
+impl Default for TraceOptions {
+    fn default() -> Self {
+        Self {
+            max_distance: 20.,
+            backward_tolerance: 0.,
+        }
+    }
+}
+
+/// A part of a trace that was matched on a single LRM.
+#[derive(Clone, Debug)]
+pub struct TraceMatch {
+    /// The LRM where the positions were matched.
+    pub lrm: LrmHandle,
+    /// The measure of the first matched position.
+    pub entry: LrmScaleMeasure,
+    /// The measure of the farthest matched position.
+    pub exit: LrmScaleMeasure,
+    /// The indices of the positions of the trace covered by this match.
+    /// Some of them might have been ignored as noise.
+    pub points: Range<usize>,
+}
+
+/// The match being built, with the positions on the scale to enforce monotonic measures.
+struct PendingMatch {
+    trace_match: TraceMatch,
+    entry_position: ScalePosition,
+    exit_position: ScalePosition,
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Map-matches an ordered sequence of `points` onto the LRMs.
+    ///
+    /// Each position is matched on the LRM of the previous position as long as it is within `max_distance`,
+    /// otherwise on the nearest LRM. Along an LRM, the measures are monotonic:
+    /// the direction is given by the first positions, and a position going backward is ignored.
+    /// A position that is not near any LRM ends the current match.
+    ///
+    /// The result has one [`TraceMatch`] every time the trace enters an LRM, in the order of the trace.
+    pub fn project_trace(&self, points: &[Point], options: TraceOptions) -> Vec<TraceMatch> {
+        let mut result = vec![];
+        let mut pending: Option<PendingMatch> = None;
+
+        for (index, &point) in points.iter().enumerate() {
+            let candidates = self.lookup_all(point, options.max_distance);
+            let current = pending.as_ref().and_then(|pending| {
+                candidates
+                    .iter()
+                    .find(|projection| projection.measure.lrm == pending.trace_match.lrm)
+            });
+
+            match (current, pending.as_mut()) {
+                (Some(projection), Some(pending)) => {
+                    let Some(position) = self.scale_position(projection) else {
+                        continue;
+                    };
+                    let direction = (pending.exit_position - pending.entry_position).signum();
+                    let progress = position - pending.exit_position;
+                    if direction == 0. || progress * direction >= 0. {
+                        pending.exit_position = position;
+                        pending.trace_match.exit = projection.measure.measure.clone();
+                    } else if progress.abs() > options.backward_tolerance {
+                        continue;
+                    }
+                    pending.trace_match.points.end = index + 1;
+                }
+                _ => {
+                    result.extend(pending.take().map(|pending| pending.trace_match));
+                    pending = candidates.first().and_then(|projection| {
+                        let position = self.scale_position(projection)?;
+                        Some(PendingMatch {
+                            trace_match: TraceMatch {
+                                lrm: projection.measure.lrm,
+                                entry: projection.measure.measure.clone(),
+                                exit: projection.measure.measure.clone(),
+                                points: index..index + 1,
+                            },
+                            entry_position: position,
+                            exit_position: position,
+                        })
+                    });
+                }
+            }
+        }
+        result.extend(pending.map(|pending| pending.trace_match));
+        result
+    }
+
+    fn scale_position(&self, projection: &LrmProjection) -> Option<ScalePosition> {
+        self.lrm(projection.measure.lrm)
+            .ok()?
+            .scale
+            .get_position(projection.measure.measure.clone())
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::point;
+
+    use crate::lrs::tests::lrs;
+
+    use super::*;
+
+    #[test]
+    fn project_trace() {
+        let lrs = lrs();
+        let trace = [
+            point! {x: 10., y: 0.2},
+            point! {x: 50., y: 0.1},
+            // Noise: the measure goes backward
+            point! {x: 40., y: 0.1},
+            point! {x: 100., y: 0.},
+            // Only near the second lrm
+            point! {x: 150., y: -0.9},
+            point! {x: 160., y: -1.},
+            // Not near any lrm
+            point! {x: 160., y: 10.},
+        ];
+        let options = TraceOptions {
+            max_distance: 0.5,
+            backward_tolerance: 0.1,
+        };
+        let matches = lrs.project_trace(&trace, options);
+        assert_eq!(matches.len(), 2);
+
+        assert_eq!(matches[0].lrm, LrmHandle(0));
+        assert_eq!(matches[0].entry.anchor_name, "a");
+        assert_eq!(matches[0].entry.scale_offset, 1.);
+        assert_eq!(matches[0].exit.anchor_name, "b");
+        assert_eq!(matches[0].exit.scale_offset, 0.);
+        assert_eq!(matches[0].points, 0..4);
+
+        assert_eq!(matches[1].lrm, LrmHandle(1));
+        assert_eq!(matches[1].entry.anchor_name, "b");
+        assert_eq!(matches[1].entry.scale_offset, 5.);
+        assert_eq!(matches[1].exit.scale_offset, 6.);
+        assert_eq!(matches[1].points, 4..6);
+    }
+}
@@ -0,0 +1,232 @@
+//! Small updates of an existing [`Lrs`] file, without going through the full source pipeline.
+//!
+//! A new milestone, a corrected measure or a new event only touches a few objects of the file.
+//! An [`LrsUpdate`] lists those changes: they are applied on the loaded [`Lrs`] and the whole file is written again.
+//! Only the spatial indexes are copied as they are, as no geometry changed: the other objects, geometries included,
+//! are decoded and encoded again, so an update costs about as much as loading and saving the file.
+
+use std::collections::HashMap;
+
+use geo::Point;
+
+use crate::curves::Curve;
+use crate::events::{LinearEvent, PointEvent};
+use crate::file_format;
+use crate::lrm_scale::{Anchor, LrmScaleError, ScalePosition};
+use crate::lrs::{LrmHandle, Lrs, LrsError, Properties};
+
+/// A new [`Anchor`] surveyed on an [`Lrm`](crate::lrs::Lrm).
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewAnchor {
+    /// The [`Lrm`](crate::lrs::Lrm) on which the anchor is added.
+    pub lrm: LrmHandle,
+    /// Name of the anchor, `None` for an unnamed anchor.
+    pub name: Option<String>,
+    /// Position of the anchor on the scale.
+    pub scale_position: ScalePosition,
+    /// Where the anchor is. It is projected on the main [`Traversal`](crate::lrs::Traversal) of the [`Lrm`](crate::lrs::Lrm).
+    pub point: Point,
+    /// Metadata to describe the anchor.
+    pub properties: Properties,
+}
+
+/// A new position on the scale of an existing named [`Anchor`], e.g. after a milestone was found to be mislabeled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeasureCorrection {
+    /// The [`Lrm`](crate::lrs::Lrm) of the anchor.
+    pub lrm: LrmHandle,
+    /// Name of the corrected anchor.
+    pub anchor_name: String,
+    /// The corrected position of the anchor on the scale.
+    pub scale_position: ScalePosition,
+}
+
+/// Changes to apply on an [`Lrs`], see [`Lrs::apply_update`].
+#[derive(Clone, Debug, Default)]
+pub struct LrsUpdate {
+    /// Anchors to add.
+    pub anchors: Vec<NewAnchor>,
+    /// Corrections of the scale position of existing anchors. They are applied after the new anchors are added.
+    pub measure_corrections: Vec<MeasureCorrection>,
+    /// Point events to add.
+    pub point_events: Vec<PointEvent>,
+    /// Linear events to add.
+    pub linear_events: Vec<LinearEvent>,
+}
+
+impl LrsUpdate {
+    /// `true` if the update has no change.
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+            && self.measure_corrections.is_empty()
+            && self.point_events.is_empty()
+            && self.linear_events.is_empty()
+    }
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Applies the changes of an [`LrsUpdate`].
+    ///
+    /// Either all the changes are applied, or none if one of them is invalid.
+    /// The geometries are not modified, so the spatial indexes remain valid.
+    pub fn apply_update(&mut self, update: LrsUpdate) -> Result<(), LrsError> {
+        let mut scales: HashMap<LrmHandle, Vec<Anchor>> = HashMap::new();
+
+        for new_anchor in update.anchors {
+            let lrm = self.lrm(new_anchor.lrm)?;
+            let projection = self
+                .traversal(lrm.traversal)?
+                .curve
+                .project(new_anchor.point)?;
+            let anchors = scales
+                .entry(new_anchor.lrm)
+                .or_insert_with(|| lrm.scale.anchors.clone());
+            let anchor = match new_anchor.name {
+                Some(name) => Anchor::new_named(
+                    &name,
+                    new_anchor.scale_position,
+                    projection.distance_along_curve,
+                    Some(projection.projected_coords),
+                    new_anchor.properties,
+                ),
+                None => Anchor::new_unnamed(
+                    new_anchor.scale_position,
+                    projection.distance_along_curve,
+                    Some(projection.projected_coords),
+                    new_anchor.properties,
+                ),
+            };
+            anchors.push(anchor.with_survey(Some(new_anchor.point), Some(projection.offset)));
+        }
+
+        for correction in update.measure_corrections {
+            let lrm = self.lrm(correction.lrm)?;
+            let anchors = scales
+                .entry(correction.lrm)
+                .or_insert_with(|| lrm.scale.anchors.clone());
+            let anchor = anchors
+                .iter_mut()
+                .find_map(|anchor| match anchor {
+                    Anchor::Named(anchor) if anchor.name == correction.anchor_name => Some(anchor),
+                    _ => None,
+                })
+                .ok_or(LrmScaleError::UnknownAnchorName)?;
+            anchor.scale_position = correction.scale_position;
+        }
+
+        let lrm_handles = update
+            .point_events
+            .iter()
+            .map(|event| event.lrm)
+            .chain(update.linear_events.iter().map(|event| event.lrm));
+        for lrm in lrm_handles {
+            self.lrm(lrm)?;
+        }
+
+        for (lrm, anchors) in scales {
+            self.set_anchors(lrm, anchors)?;
+        }
+        self.point_events.extend(update.point_events);
+        self.linear_events.extend(update.linear_events);
+        Ok(())
+    }
+
+    /// Applies an [`LrsUpdate`] on a file and returns the updated file.
+    ///
+    /// The file is loaded and fully written again, see [`Lrs::to_bytes`]: only the spatial indexes
+    /// are kept instead of being computed again. The file remains compressed if it was.
+    pub fn update_bytes(buf: &[u8], update: LrsUpdate) -> Result<Vec<u8>, LrsError> {
+        let mut lrs = Self::from_bytes(buf)?;
+        lrs.apply_update(update)?;
+        let fbb = lrs.build_flatbuffer(true);
+        #[cfg(feature = "compression")]
+        if file_format::is_compressed(buf) {
+            return Ok(file_format::seal_compressed(fbb.finished_data()));
+        }
+        Ok(file_format::seal(fbb.finished_data()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{coord, point};
+
+    use crate::builder::tests::build_traversal;
+    use crate::builder::{AnchorOnLrm, Builder};
+    use crate::curves::SphericalLineStringCurve;
+    use crate::events::TypedProperties;
+    use crate::lrm_scale::LrmScaleMeasure;
+    use crate::lrs::Validity;
+    use crate::properties;
+
+    use super::*;
+
+    #[test]
+    fn update_bytes() {
+        let mut builder = Builder::new();
+        let traversal = build_traversal(&mut builder);
+        let a = builder.add_anchor("a", Some("a"), coord! {x: 0., y: 0.}, properties!());
+        let b = builder.add_anchor("b", Some("b"), coord! {x: 2., y: 0.}, properties!());
+        let anchors = [
+            AnchorOnLrm {
+                anchor_index: a,
+                distance_along_lrm: 0.,
+            },
+            AnchorOnLrm {
+                anchor_index: b,
+                distance_along_lrm: 2000.,
+            },
+        ];
+        builder.add_lrm("lrm", traversal, &anchors, properties!());
+        let data = builder.build_data(properties!()).to_vec();
+        let lrm = LrmHandle(0);
+
+        let update = LrsUpdate {
+            anchors: vec![NewAnchor {
+                lrm,
+                name: Some("c".to_owned()),
+                scale_position: 1100.,
+                point: point! {x: 1., y: 0.1},
+                properties: properties!(),
+            }],
+            measure_corrections: vec![MeasureCorrection {
+                lrm,
+                anchor_name: "b".to_owned(),
+                scale_position: 2200.,
+            }],
+            point_events: vec![PointEvent {
+                id: "signal".to_owned(),
+                lrm,
+                measure: LrmScaleMeasure::new("c", 10.),
+                properties: TypedProperties::default(),
+                validity: Validity::default(),
+            }],
+            linear_events: vec![],
+        };
+        let updated = Lrs::<SphericalLineStringCurve>::update_bytes(&data, update).unwrap();
+        let lrs = Lrs::<SphericalLineStringCurve>::from_bytes(&updated).unwrap();
+
+        let scale = &lrs.lrms[0].scale;
+        let positions: Vec<_> = scale.anchors.iter().map(Anchor::scale_position).collect();
+        assert_eq!(positions, [0., 1100., 2200.]);
+        assert_eq!(scale.anchors[1].point(), Some(point! {x: 1., y: 0.}));
+        assert_eq!(lrs.point_events.len(), 1);
+        assert!(scale.locate_point(&lrs.point_events[0].measure).is_ok());
+
+        let original = Lrs::<SphericalLineStringCurve>::from_bytes(&data).unwrap();
+        assert_eq!(lrs.rtree_data, original.rtree_data);
+
+        let invalid = LrsUpdate {
+            measure_corrections: vec![MeasureCorrection {
+                lrm,
+                anchor_name: "unknown".to_owned(),
+                scale_position: 0.,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            Lrs::<SphericalLineStringCurve>::update_bytes(&data, invalid).err(),
+            Some(LrsError::LrmScaleError(LrmScaleError::UnknownAnchorName))
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Checks of the data of an [`Lrs`], to catch bad data before a broken file is published.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::curves::Curve;
+use crate::lrm_scale::Anchor;
+use crate::lrs::{Lrs, Validity};
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The data is suspicious, but the [`Lrs`] can be used.
+    Warning,
+    /// Some measures cannot be located or some objects cannot be found.
+    Error,
+}
+
+/// A problem found by [`Lrs::validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationIssue {
+    /// Several objects of the same `kind` (`node`, `segment`, `traversal` or `lrm`) have the same `id`.
+    /// LRMs that are not valid at the same time can share an `id`.
+    DuplicatedId {
+        /// Kind of the objects.
+        kind: &'static str,
+        /// The shared id.
+        id: String,
+        /// How many objects have it.
+        count: usize,
+    },
+    /// An LRM has no anchor.
+    EmptyLrm(String),
+    /// An LRM has anchors, but none is named: no measure can be expressed.
+    NoNamedAnchor(String),
+    /// The traversal of an LRM has no valid geometry: nothing can be located on it.
+    InvalidGeometry {
+        /// `id` of the LRM.
+        lrm_id: String,
+        /// `id` of the traversal.
+        traversal_id: String,
+    },
+    /// An anchor is farther from its traversal than [`ValidationOptions::max_anchor_distance`].
+    AnchorTooFar {
+        /// `id` of the LRM.
+        lrm_id: String,
+        /// Index of the anchor in the LRM.
+        anchor_index: usize,
+        /// Distance between the anchor and its projection on the traversal.
+        distance: f64,
+    },
+    /// The distance between the first and last anchors differs between the scale and the curve
+    /// by more than [`ValidationOptions::max_length_mismatch`].
+    LengthMismatch {
+        /// `id` of the LRM.
+        lrm_id: String,
+        /// Distance according to the measures of the anchors.
+        scale_length: f64,
+        /// Distance along the traversal.
+        curve_length: f64,
+    },
+}
+
+impl ValidationIssue {
+    /// How serious the issue is.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::DuplicatedId { kind: "lrm", .. }
+            | Self::EmptyLrm(_)
+            | Self::NoNamedAnchor(_)
+            | Self::InvalidGeometry { .. } => Severity::Error,
+            Self::DuplicatedId { .. } | Self::AnchorTooFar { .. } | Self::LengthMismatch { .. } => {
+                Severity::Warning
+            }
+        }
+    }
+
+    /// A stable name of the kind of issue, for machine-readable reports.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DuplicatedId { .. } => "DUPLICATED_ID",
+            Self::EmptyLrm(_) => "EMPTY_LRM",
+            Self::NoNamedAnchor(_) => "NO_NAMED_ANCHOR",
+            Self::InvalidGeometry { .. } => "INVALID_GEOMETRY",
+            Self::AnchorTooFar { .. } => "ANCHOR_TOO_FAR",
+            Self::LengthMismatch { .. } => "LENGTH_MISMATCH",
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicatedId { kind, id, count } => write!(f, "{count} {kind}s have the id {id}"),
+            Self::EmptyLrm(lrm_id) => write!(f, "lrm {lrm_id} has no anchor"),
+            Self::NoNamedAnchor(lrm_id) => write!(f, "lrm {lrm_id} has no named anchor"),
+            Self::InvalidGeometry {
+                lrm_id,
+                traversal_id,
+            } => write!(
+                f,
+                "lrm {lrm_id} has no valid geometry: traversal {traversal_id} is empty or degenerated"
+            ),
+            Self::AnchorTooFar {
+                lrm_id,
+                anchor_index,
+                distance,
+            } => write!(
+                f,
+                "anchor {anchor_index} of lrm {lrm_id} is {distance:.1} from its traversal"
+            ),
+            Self::LengthMismatch {
+                lrm_id,
+                scale_length,
+                curve_length,
+            } => write!(
+                f,
+                "lrm {lrm_id} measures {scale_length:.1} between its anchors, but its traversal {curve_length:.1}"
+            ),
+        }
+    }
+}
+
+/// Thresholds of [`Lrs::validate`], in the units of the curves (meters for a geographic [`Lrs`]).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValidationOptions {
+    /// Maximum distance between an anchor and its projection on the traversal.
+    pub max_anchor_distance: f64,
+    /// Maximum relative difference between the length of an LRM according to its anchors and along its traversal.
+    pub max_length_mismatch: f64,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self {
+            max_anchor_distance: 50.,
+            max_length_mismatch: 0.1,
+        }
+    }
+}
+
+/// The issues found by [`Lrs::validate`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Every issue, in the order of the checks.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether an issue prevents using the [`Lrs`].
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity() == Severity::Error)
+    }
+
+    /// The issues of a given severity.
+    pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.severity() == severity)
+    }
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Checks the data of the [`Lrs`]: duplicated ids, empty LRMs, LRMs without geometry, anchors far from their traversal
+    /// and LRMs whose measures do not match the length of their traversal.
+    pub fn validate(&self, options: &ValidationOptions) -> ValidationReport {
+        let mut issues = vec![];
+        // LRMs can share an id if they are not valid at the same time
+        let mut validities = HashMap::<&String, Vec<&Validity>>::new();
+        for lrm in &self.lrms {
+            validities
+                .entry(&lrm.scale.id)
+                .or_default()
+                .push(&lrm.validity);
+        }
+        let lrm_ids = self.lrms.iter().filter(|lrm| {
+            validities[&lrm.scale.id]
+                .iter()
+                .filter(|validity| validity.overlaps(&lrm.validity))
+                .count()
+                > 1
+        });
+        let ids = [
+            ("node", self.nodes.iter().map(|n| &n.id).collect::<Vec<_>>()),
+            ("segment", self.segments.iter().map(|s| &s.id).collect()),
+            ("traversal", self.traversals.iter().map(|t| &t.id).collect()),
+            ("lrm", lrm_ids.map(|l| &l.scale.id).collect()),
+        ];
+        for (kind, ids) in ids {
+            let mut counts = HashMap::<&String, usize>::new();
+            for id in &ids {
+                *counts.entry(id).or_default() += 1;
+            }
+            let mut duplicated: Vec<_> =
+                counts.into_iter().filter(|(_, count)| *count > 1).collect();
+            duplicated.sort();
+            issues.extend(duplicated.into_iter().map(|(id, count)| {
+                ValidationIssue::DuplicatedId {
+                    kind,
+                    id: id.clone(),
+                    count,
+                }
+            }));
+        }
+
+        for lrm in &self.lrms {
+            let anchors = &lrm.scale.anchors;
+            let lrm_id = &lrm.scale.id;
+            if anchors.is_empty() {
+                issues.push(ValidationIssue::EmptyLrm(lrm_id.clone()));
+                continue;
+            }
+            if !anchors.iter().any(|a| matches!(a, Anchor::Named(_))) {
+                issues.push(ValidationIssue::NoNamedAnchor(lrm_id.clone()));
+            }
+            let Ok(traversal) = self.traversal(lrm.traversal) else {
+                continue;
+            };
+            let curve = &*traversal.curve;
+            if !curve.is_valid() {
+                issues.push(ValidationIssue::InvalidGeometry {
+                    lrm_id: lrm_id.clone(),
+                    traversal_id: traversal.id.clone(),
+                });
+                continue;
+            }
+
+            for (anchor_index, anchor) in anchors.iter().enumerate() {
+                // The point of a loaded anchor is on the curve, its surveyed position is not
+                let surveyed = anchor.surveyed_point().or(anchor.point());
+                let Some(projection) = surveyed.and_then(|p| curve.project(p).ok()) else {
+                    continue;
+                };
+                let distance = projection.offset.abs();
+                if distance > options.max_anchor_distance {
+                    issues.push(ValidationIssue::AnchorTooFar {
+                        lrm_id: lrm_id.clone(),
+                        anchor_index,
+                        distance,
+                    });
+                }
+            }
+
+            if let (Some(first), Some(last)) = (anchors.first(), anchors.last()) {
+                let scale_length = (last.scale_position() - first.scale_position()).abs();
+                let curve_length =
+                    (last.curve_position() - first.curve_position()).abs() * curve.length();
+                if curve_length > 0.
+                    && (scale_length - curve_length).abs() / curve_length
+                        > options.max_length_mismatch
+                {
+                    issues.push(ValidationIssue::LengthMismatch {
+                        lrm_id: lrm_id.clone(),
+                        scale_length,
+                        curve_length,
+                    });
+                }
+            }
+        }
+        ValidationReport { issues }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::line_string;
+
+    use crate::curves::PlanarLineStringCurve;
+    use crate::lrs::tests::lrs;
+
+    use super::*;
+
+    #[test]
+    fn validate() {
+        let lrs = lrs();
+        let report = lrs.validate(&ValidationOptions::default());
+        assert_eq!(
+            report.issues[0],
+            ValidationIssue::DuplicatedId {
+                kind: "traversal",
+                id: "curve".to_owned(),
+                count: 2
+            }
+        );
+        assert!(!report.has_errors());
+
+        let mut lrs = lrs;
+        lrs.lrms[1].scale.anchors.clear();
+        let report = lrs.validate(&ValidationOptions::default());
+        assert!(report.has_errors());
+        assert!(
+            report
+                .issues
+                .contains(&ValidationIssue::EmptyLrm("id2".to_owned()))
+        );
+        assert_eq!(report.with_severity(Severity::Error).count(), 1);
+
+        lrs.lrms[1].scale.anchors = lrs.lrms[0].scale.anchors.clone();
+        *lrs.traversals[1].curve = PlanarLineStringCurve::new(line_string![(x: 0., y: 0.)], 1.);
+        let report = lrs.validate(&ValidationOptions::default());
+        let errors: Vec<_> = report.with_severity(Severity::Error).collect();
+        assert_eq!(
+            errors,
+            [&ValidationIssue::InvalidGeometry {
+                lrm_id: "id2".to_owned(),
+                traversal_id: "curve".to_owned(),
+            }]
+        );
+        assert_eq!(errors[0].code(), "INVALID_GEOMETRY");
+    }
+}
@@ -0,0 +1,139 @@
+//! Reads and writes the geometries as Well-Known Binary (WKB), the encoding of GeoPackage, GeoParquet and PostGIS.
+
+use geo::{Coord, LineString, Point};
+
+const POINT: u32 = 1;
+const LINESTRING: u32 = 2;
+
+/// Little endian WKB of a [`Point`].
+pub fn write_point(point: Point) -> Vec<u8> {
+    let mut wkb = write_header(POINT);
+    wkb.extend(point.x().to_le_bytes());
+    wkb.extend(point.y().to_le_bytes());
+    wkb
+}
+
+/// Little endian WKB of a [`LineString`].
+pub fn write_linestring(linestring: &LineString) -> Vec<u8> {
+    let mut wkb = write_header(LINESTRING);
+    wkb.extend((linestring.0.len() as u32).to_le_bytes());
+    for coord in linestring.coords() {
+        wkb.extend(coord.x.to_le_bytes());
+        wkb.extend(coord.y.to_le_bytes());
+    }
+    wkb
+}
+
+/// Reads a [`Point`], ignoring its Z and M coordinates.
+pub fn read_point(wkb: &[u8]) -> Option<Point> {
+    match read_header(wkb)? {
+        (POINT, dimensions, mut reader) => reader.coord(dimensions).map(Point::from),
+        _ => None,
+    }
+}
+
+/// Reads a [`LineString`], ignoring its Z and M coordinates.
+pub fn read_linestring(wkb: &[u8]) -> Option<LineString> {
+    match read_header(wkb)? {
+        (LINESTRING, dimensions, mut reader) => {
+            let len = reader.u32()?;
+            (0..len).map(|_| reader.coord(dimensions)).collect()
+        }
+        _ => None,
+    }
+}
+
+// The byte order and the type
+fn write_header(wkb_type: u32) -> Vec<u8> {
+    let mut wkb = vec![1];
+    wkb.extend(wkb_type.to_le_bytes());
+    wkb
+}
+
+// The type, the number of dimensions and a cursor over the coordinates
+fn read_header(wkb: &[u8]) -> Option<(u32, usize, Reader<'_>)> {
+    let mut reader = Reader {
+        data: wkb.get(1..)?,
+        little_endian: *wkb.first()? == 1,
+    };
+    let wkb_type = reader.u32()?;
+    // The ISO WKB codes of the geometries with Z, M or ZM
+    let dimensions = match wkb_type / 1000 {
+        0 => 2,
+        1 | 2 => 3,
+        3 => 4,
+        _ => return None,
+    };
+    Some((wkb_type % 1000, dimensions, reader))
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    little_endian: bool,
+}
+
+impl Reader<'_> {
+    fn bytes<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let bytes = self.data.get(..N)?.try_into().ok()?;
+        self.data = &self.data[N..];
+        Some(bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.bytes()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        let bytes = self.bytes()?;
+        Some(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    // The extra dimensions are ignored
+    fn coord(&mut self, dimensions: usize) -> Option<Coord> {
+        let coord = Coord {
+            x: self.f64()?,
+            y: self.f64()?,
+        };
+        for _ in 2..dimensions {
+            self.f64()?;
+        }
+        Some(coord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{line_string, point};
+
+    use super::*;
+
+    #[test]
+    fn geometries() {
+        let linestring = line_string![(x: 0., y: 1.), (x: 2., y: 3.)];
+        assert_eq!(
+            read_linestring(&write_linestring(&linestring)),
+            Some(linestring)
+        );
+        let point = point! {x: 1., y: 2.};
+        assert_eq!(read_point(&write_point(point)), Some(point));
+        assert_eq!(read_point(&write_linestring(&line_string![])), None);
+        assert_eq!(read_point(&[1]), None);
+
+        // A big endian point with Z
+        let mut wkb = vec![0];
+        wkb.extend(1001_u32.to_be_bytes());
+        for value in [1., 2., 3.] {
+            wkb.extend(f64::to_be_bytes(value));
+        }
+        assert_eq!(read_point(&wkb), Some(point));
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers to read the XML formats: railML, NeTEx and RINF.
+
+// The descendants of an XML node with the given tag
+pub(crate) fn xml_elements<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.descendants()
+        .filter(move |child| child.has_tag_name(tag))
+}
+
+// A numeric attribute of an XML node
+#[cfg(feature = "railml")]
+pub(crate) fn xml_number(node: roxmltree::Node, attribute: &str) -> Option<f64> {
+    node.attribute(attribute)?.parse().ok()
+}
+
+// The text of the first descendant of an XML node with the given tag
+#[cfg(feature = "netex")]
+pub(crate) fn xml_text<'a>(node: roxmltree::Node<'a, '_>, tag: &'static str) -> Option<&'a str> {
+    xml_elements(node, tag).find_map(|child| child.text())
+}
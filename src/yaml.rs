@@ -0,0 +1,228 @@
+//! Reads and writes the YAML format of the `lrs_gaia` files, where each traversal lists its anchors.
+//!
+//! ```yaml
+//! traversals:
+//!   - id: "830000"
+//!     geometry: [[2.35, 48.85], [2.37, 48.85]]
+//!     anchors: ["7", "8", "9"]
+//!     distances: [-206.0, 794.0, 1794.0]
+//! ```
+//!
+//! The `distances` are the positions of the `anchors` along the traversal, in meters.
+//! The first one can be negative, when the anchor is before the start of the traversal.
+//! When there is one distance less than anchors, the first anchor is implied at the origin of the traversal.
+
+use geo::{Coord, LineString};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::builder::{AnchorOnLrm, Builder};
+use crate::curves::{Curve, SphericalLineStringCurve};
+use crate::lrm_scale::Anchor;
+use crate::lrs::Lrs;
+use crate::properties;
+
+/// Errors when reading a YAML file.
+#[derive(Debug, Error, PartialEq)]
+pub enum YamlError {
+    /// The file is not valid YAML or does not have the expected structure.
+    #[error("invalid yaml: {0}")]
+    Parse(String),
+    /// The geometry of the traversal has less than two coordinates.
+    #[error("traversal {0} has an invalid geometry")]
+    InvalidGeometry(String),
+    /// There must be as many distances as anchors, or one less.
+    #[error("traversal {0} has {1} anchors but {2} distances")]
+    DistancesMismatch(String, usize, usize),
+}
+
+/// The content of a YAML file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct YamlLrs {
+    /// The traversals, each with its own LRM.
+    pub traversals: Vec<YamlTraversal>,
+}
+
+/// A traversal and the anchors of its LRM.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct YamlTraversal {
+    /// Identifies the traversal and its LRM.
+    pub id: String,
+    /// Longitude and latitude of each point.
+    pub geometry: Vec<[f64; 2]>,
+    /// Names of the anchors, in order along the traversal.
+    pub anchors: Vec<String>,
+    /// Distance of each anchor from the start of the traversal, in meters.
+    pub distances: Vec<f64>,
+}
+
+impl YamlTraversal {
+    // The distances with the implied origin
+    fn full_distances(&self) -> Result<Vec<f64>, YamlError> {
+        if self.distances.len() == self.anchors.len() {
+            Ok(self.distances.clone())
+        } else if self.distances.len() + 1 == self.anchors.len() {
+            Ok(std::iter::once(0.)
+                .chain(self.distances.iter().copied())
+                .collect())
+        } else {
+            Err(YamlError::DistancesMismatch(
+                self.id.clone(),
+                self.anchors.len(),
+                self.distances.len(),
+            ))
+        }
+    }
+}
+
+impl Builder<'_> {
+    /// Reads a YAML file in the `lrs_gaia` format, see [`crate::yaml`].
+    ///
+    /// The traversals continuing each other at the same coordinates are connected, see [`Builder::connect_traversal_extremities`].
+    pub fn from_yaml(yaml: &str) -> Result<Self, YamlError> {
+        let content: YamlLrs =
+            serde_yaml_ng::from_str(yaml).map_err(|e| YamlError::Parse(e.to_string()))?;
+        let mut builder = Self::new();
+        let mut nodes = std::collections::HashMap::new();
+        for traversal in &content.traversals {
+            let distances = traversal.full_distances()?;
+            let geometry: LineString = traversal
+                .geometry
+                .iter()
+                .map(|&[x, y]| Coord { x, y })
+                .collect();
+            let curve = SphericalLineStringCurve::new(geometry.clone(), 100.);
+            if !curve.is_valid() {
+                return Err(YamlError::InvalidGeometry(traversal.id.clone()));
+            }
+            let length = curve.length();
+            let index = builder
+                .add_traversal_with_shared_nodes(&traversal.id, &geometry, &mut nodes)
+                .ok_or_else(|| YamlError::InvalidGeometry(traversal.id.clone()))?;
+
+            let anchors: Vec<_> = traversal
+                .anchors
+                .iter()
+                .zip(distances)
+                .map(|(name, distance)| AnchorOnLrm {
+                    anchor_index: builder.add_projected_anchor(
+                        &format!("{}/{name}", traversal.id),
+                        Some(name),
+                        distance / length,
+                        properties!(),
+                    ),
+                    distance_along_lrm: distance,
+                })
+                .collect();
+            builder.add_lrm(&traversal.id, index, &anchors, properties!());
+        }
+        builder.connect_traversal_extremities();
+        Ok(builder)
+    }
+}
+
+impl<CurveImpl: Curve> Lrs<CurveImpl> {
+    /// Writes the [`Lrs`] in the `lrs_gaia` YAML format, see [`crate::yaml`].
+    ///
+    /// Each LRM is written with the geometry of its traversal and its named anchors.
+    /// The distances are the positions of the anchors on the traversal, whatever their measures.
+    pub fn to_yaml(&self) -> String {
+        let traversals = self
+            .lrms
+            .iter()
+            .filter_map(|lrm| {
+                let curve = &*self.traversal(lrm.traversal).ok()?.curve;
+                let (anchors, distances) = lrm
+                    .scale
+                    .anchors
+                    .iter()
+                    .filter_map(|anchor| match anchor {
+                        Anchor::Named(anchor) => {
+                            Some((anchor.name.clone(), anchor.curve_position * curve.length()))
+                        }
+                        Anchor::Unnamed(_) => None,
+                    })
+                    .unzip();
+                Some(YamlTraversal {
+                    id: lrm.scale.id.clone(),
+                    geometry: curve.as_linestring().coords().map(|c| [c.x, c.y]).collect(),
+                    anchors,
+                    distances,
+                })
+            })
+            .collect();
+        serde_yaml_ng::to_string(&YamlLrs { traversals })
+            .expect("an Lrs can always be written as yaml")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use crate::lrm_scale::LrmScaleMeasure;
+
+    use super::*;
+
+    const YAML: &str = r#"
+traversals:
+  - id: "830000"
+    geometry: [[0.0, 0.0], [0.02, 0.0]]
+    anchors: ["7", "8", "9"]
+    distances: [-206.0, 794.0, 1794.0]
+  - id: "840000"
+    geometry: [[0.02, 0.0], [0.02, 0.02]]
+    anchors: ["0", "1"]
+    distances: [1000.0]
+"#;
+
+    #[test]
+    fn read_and_write() {
+        let lrs = Builder::from_yaml(YAML)
+            .unwrap()
+            .build_lrs(properties!())
+            .unwrap();
+        assert_eq!(lrs.lrm_len(), 2);
+
+        // 7+206 is the origin of the traversal
+        let origin = lrs.resolve(0, &LrmScaleMeasure::new("7", 206.)).unwrap();
+        assert_relative_eq!(origin.x(), 0., epsilon = 1e-9);
+        // The first anchor of the second traversal is implied at its origin
+        let origin = lrs.resolve(1, &LrmScaleMeasure::new("0", 0.)).unwrap();
+        assert_relative_eq!(origin.y(), 0., epsilon = 1e-9);
+
+        let written: YamlLrs =
+            serde_yaml_ng::from_str(&crate::builder::tests::geographic(&lrs).to_yaml()).unwrap();
+        let expected: YamlLrs = serde_yaml_ng::from_str(YAML).unwrap();
+        assert_eq!(
+            written.traversals[0].anchors,
+            expected.traversals[0].anchors
+        );
+        assert_eq!(written.traversals[1].distances.len(), 2);
+        for (written, expected) in written.traversals[0]
+            .distances
+            .iter()
+            .zip(&expected.traversals[0].distances)
+        {
+            assert_relative_eq!(written, expected, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let yaml = r#"traversals: [{id: a, geometry: [[0, 0], [1, 0]], anchors: ["0"], distances: [0, 1, 2]}]"#;
+        assert_eq!(
+            Builder::from_yaml(yaml).err(),
+            Some(YamlError::DistancesMismatch("a".to_owned(), 1, 3))
+        );
+        let yaml = r#"traversals: [{id: a, geometry: [[0, 0]], anchors: [], distances: []}]"#;
+        assert_eq!(
+            Builder::from_yaml(yaml).err(),
+            Some(YamlError::InvalidGeometry("a".to_owned()))
+        );
+        assert!(matches!(
+            Builder::from_yaml("traversals: 1"),
+            Err(YamlError::Parse(_))
+        ));
+    }
+}
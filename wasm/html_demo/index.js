@@ -264,7 +264,7 @@ Alpine.store('lrms', {
             this.anchors_features = lrs.anchors_features
         } catch (e) {
             this.status = 'error'
-            this.error_text = e
+            this.error_text = e.message ?? e
         }
 
     },
@@ -1,10 +1,7 @@
 //! High level extensions meant for an easy usage
 //! Those functions are exposed in wasm-bindings
 
-use liblrs::{
-    lrs::{LrmHandle, LrsBase},
-    lrs_ext::*,
-};
+use liblrs::{conformance::ConformanceAdapter, error_code::LrsErrorCode, lrs_ext::*};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -98,6 +95,7 @@ impl From<&LrmScaleMeasure> for liblrs::lrm_scale::LrmScaleMeasure {
     }
 }
 
+#[derive(Clone)]
 #[wasm_bindgen]
 /// An `Anchor` is a reference point for a given [`Curve`].
 pub struct Anchor {
@@ -110,6 +108,10 @@ pub struct Anchor {
     pub curve_position: f64,
     /// Position on the scale.
     pub scale_position: f64,
+    /// Surveyed position, before the projection on the [`Curve`].
+    pub surveyed_position: Option<Point>,
+    /// Signed distance between the surveyed position and the [`Curve`], positive on the left.
+    pub projection_offset: Option<f64>,
 }
 
 impl From<&liblrs::lrm_scale::Anchor> for Anchor {
@@ -124,6 +126,8 @@ impl From<&liblrs::lrm_scale::Anchor> for Anchor {
             position: value.point().map(|p| p.into()),
             curve_position: value.curve_position(),
             scale_position: value.scale_position(),
+            surveyed_position: value.surveyed_point().map(|p| p.into()),
+            projection_offset: value.projection_offset(),
         }
     }
 }
@@ -131,17 +135,445 @@ impl From<&liblrs::lrm_scale::Anchor> for Anchor {
 #[wasm_bindgen(getter_with_clone)]
 /// The result of a projection onto an [`LrmScale`].
 pub struct LrmProjection {
+    /// Handle of the [`Lrm`] where this measurement applies to
+    pub lrm_handle: usize,
     /// Contains `measure` ([`LrmScaleMeasure`]) and `lrm` ([`LrmHandle`]).
     pub measure: LrmScaleMeasure,
     /// How far from the [`Lrm`] is the [`Point`] that has been projected.
     pub orthogonal_offset: f64,
+    /// Handle of the [`Traversal`] of the [`Lrm`] nearest to the [`Point`]: the main one or an alternate one.
+    pub traversal_handle: usize,
+}
+
+impl From<&liblrs::lrs::LrmProjection> for LrmProjection {
+    fn from(value: &liblrs::lrs::LrmProjection) -> Self {
+        Self {
+            lrm_handle: value.measure.lrm.0,
+            measure: (&value.measure.measure).into(),
+            orthogonal_offset: value.orthogonal_offset,
+            traversal_handle: value.traversal.0,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// The nearest position of a [`Point`] on all the [`Lrm`]s.
+pub struct LocatedPoint {
+    /// Index of the [`Lrm`].
+    pub lrm_index: usize,
+    /// `id` of the [`Lrm`].
+    pub lrm_id: String,
+    /// Name of the [`Anchor`] of the measure.
+    pub anchor_name: String,
+    /// Offset of the measure from the [`Anchor`], in the unit of the scale.
+    pub scale_offset: f64,
+    /// Distance in meters from the [`Lrm`]: positive if the [`Point`] is on the left of the [`Lrm`], negative on the right.
+    pub lateral_offset_m: f64,
+    /// Distance in meters between the [`Point`] and the [`Lrm`].
+    pub distance_m: f64,
+}
+
+impl From<liblrs::lrs_ext::LocatedPoint> for LocatedPoint {
+    fn from(value: liblrs::lrs_ext::LocatedPoint) -> Self {
+        Self {
+            lrm_index: value.lrm_index,
+            lrm_id: value.lrm_id,
+            anchor_name: value.anchor_name,
+            scale_offset: value.scale_offset,
+            lateral_offset_m: value.lateral_offset_m,
+            distance_m: value.distance_m,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// The measures at both ends of an [`Lrm`] and its length.
+pub struct LrmBounds {
+    /// The smallest measure of the [`Lrm`].
+    pub min_measure: LrmScaleMeasure,
+    /// The largest measure of the [`Lrm`].
+    pub max_measure: LrmScaleMeasure,
+    /// The length of the [`Lrm`] in meters.
+    pub length: f64,
+}
+
+impl From<&liblrs::lrs::LrmBounds> for LrmBounds {
+    fn from(value: &liblrs::lrs::LrmBounds) -> Self {
+        Self {
+            min_measure: (&value.min_measure).into(),
+            max_measure: (&value.max_measure).into(),
+            length: value.length,
+        }
+    }
+}
+
+#[wasm_bindgen]
+/// Where an [`Lrm`] is, to frame it on a map, see `get_lrm_bbox`.
+pub struct LrmBbox {
+    /// South-west corner of the bounding box of the [`Lrm`].
+    pub bbox_min: Point,
+    /// North-east corner of the bounding box of the [`Lrm`].
+    pub bbox_max: Point,
+    /// The middle of the [`Lrm`], half way along its geometry.
+    pub center: Point,
+}
+
+impl From<liblrs::lrs_ext::LrmBbox> for LrmBbox {
+    fn from(value: liblrs::lrs_ext::LrmBbox) -> Self {
+        Self {
+            bbox_min: value.bbox.min().into(),
+            bbox_max: value.bbox.max().into(),
+            center: value.center.into(),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// Where two [`Lrm`]s cross or touch.
+pub struct LrmIntersection {
+    /// The measure of the intersection on the first [`Lrm`].
+    pub measure_a: LrmScaleMeasure,
+    /// The measure of the intersection on the second [`Lrm`].
+    pub measure_b: LrmScaleMeasure,
+    /// The geographical position of the intersection.
+    pub point: Point,
+}
+
+impl From<&liblrs::lrs::LrmIntersection> for LrmIntersection {
+    fn from(value: &liblrs::lrs::LrmIntersection) -> Self {
+        Self {
+            measure_a: (&value.measure_a).into(),
+            measure_b: (&value.measure_b).into(),
+            point: value.point.into(),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// An [`Lrm`] whose id matches a query, see `search_lrms`.
+pub struct LrmMatch {
+    /// Index of the [`Lrm`].
+    pub lrm_index: usize,
+    /// `id` of the [`Lrm`].
+    pub lrm_id: String,
+    /// How well the [`Lrm`] matches the query, between 0 (excluded) and 1 for an exact match.
+    pub score: f64,
+}
+
+impl From<liblrs::lrs_ext::LrmMatch> for LrmMatch {
+    fn from(value: liblrs::lrs_ext::LrmMatch) -> Self {
+        Self {
+            lrm_index: value.lrm_index,
+            lrm_id: value.lrm_id,
+            score: value.score,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// An [`Anchor`] placed on its [`Lrm`], to display it on a map.
+pub struct AnchorMarker {
+    /// Name of the [`Anchor`], `None` for an unnamed [`Anchor`].
+    pub name: Option<String>,
+    /// The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+    pub measure: LrmScaleMeasure,
+    /// The position of the [`Anchor`] on the [`Lrm`].
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    pub heading: f64,
+}
+
+impl From<liblrs::lrs_ext::AnchorMarker> for AnchorMarker {
+    fn from(value: liblrs::lrs_ext::AnchorMarker) -> Self {
+        Self {
+            name: value.name,
+            measure: (&value.measure).into(),
+            point: value.point.into(),
+            heading: value.heading,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// An [`Anchor`] and its measure, see `anchors_between`.
+pub struct MeasuredAnchor {
+    /// The anchor.
+    pub anchor: Anchor,
+    /// The measure of the [`Anchor`]. An unnamed [`Anchor`] is measured from the previous named one.
+    pub measure: LrmScaleMeasure,
+}
+
+impl From<liblrs::lrs_ext::MeasuredAnchor> for MeasuredAnchor {
+    fn from(value: liblrs::lrs_ext::MeasuredAnchor) -> Self {
+        Self {
+            anchor: (&value.anchor).into(),
+            measure: (&value.measure).into(),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// A range of measures of an [`Lrm`], see `measure_ranges_in_polygon`.
+pub struct MeasureRange {
+    /// The smallest measure of the range.
+    pub from: LrmScaleMeasure,
+    /// The largest measure of the range.
+    pub to: LrmScaleMeasure,
+}
+
+impl From<liblrs::lrs_ext::MeasureRange> for MeasureRange {
+    fn from(value: liblrs::lrs_ext::MeasureRange) -> Self {
+        Self {
+            from: (&value.from).into(),
+            to: (&value.to).into(),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// A graduation of the scale of an [`Lrm`], to display it on a map.
+pub struct MeasureTick {
+    /// The measure of the graduation.
+    pub measure: LrmScaleMeasure,
+    /// The geographical position of the graduation on the [`Lrm`].
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    pub heading: f64,
+    /// The end of the tick on the left of increasing measures.
+    pub left: Point,
+    /// The end of the tick on the right of increasing measures.
+    pub right: Point,
+}
+
+impl From<liblrs::lrs_ext::MeasureTick> for MeasureTick {
+    fn from(value: liblrs::lrs_ext::MeasureTick) -> Self {
+        Self {
+            measure: (&value.measure).into(),
+            point: value.point.into(),
+            heading: value.heading,
+            left: value.left.into(),
+            right: value.right.into(),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// A position on an [`Lrm`] and the direction of increasing measures there.
+pub struct OrientedPoint {
+    /// The geographical position.
+    pub point: Point,
+    /// The direction of increasing measures, in degrees clockwise from the north, between 0 and 360.
+    pub heading: f64,
+}
+
+impl From<liblrs::lrs_ext::OrientedPoint> for OrientedPoint {
+    fn from(value: liblrs::lrs_ext::OrientedPoint) -> Self {
+        Self {
+            point: value.point.into(),
+            heading: value.heading,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// Counts and sizes describing an [`Lrs`], to sanity-check a file.
+pub struct LrsStats {
+    /// Number of [`Lrm`]s.
+    pub lrm_count: usize,
+    /// Number of [`Traversal`]s.
+    pub traversal_count: usize,
+    /// Number of [`Traversal`]s without a valid geometry.
+    pub invalid_traversal_count: usize,
+    /// Number of named [`Anchor`]s over all the [`Lrm`]s.
+    pub anchor_count: usize,
+    /// Number of unnamed [`Anchor`]s over all the [`Lrm`]s.
+    pub unnamed_anchor_count: usize,
+    /// Sum of the lengths of the valid [`Traversal`]s, in meters.
+    pub total_length: f64,
+    /// South-west corner of the bounding box of the network.
+    pub bbox_min: Option<Point>,
+    /// North-east corner of the bounding box of the network.
+    pub bbox_max: Option<Point>,
+    /// Length of the shortest [`Lrm`], 0 if there is none.
+    pub min_lrm_length: f64,
+    /// Median length of the [`Lrm`]s, 0 if there is none.
+    pub median_lrm_length: f64,
+    /// Average length of the [`Lrm`]s, 0 if there is none.
+    pub mean_lrm_length: f64,
+    /// Length of the longest [`Lrm`], 0 if there is none.
+    pub max_lrm_length: f64,
+}
+
+impl From<&liblrs::stats::LrsStats> for LrsStats {
+    fn from(value: &liblrs::stats::LrsStats) -> Self {
+        let lengths = value.lrm_lengths;
+        Self {
+            lrm_count: value.lrm_count,
+            traversal_count: value.traversal_count,
+            invalid_traversal_count: value.invalid_traversal_count,
+            anchor_count: value.anchor_count,
+            unnamed_anchor_count: value.unnamed_anchor_count,
+            total_length: value.total_length,
+            bbox_min: value.bbox.map(|bbox| bbox.min().into()),
+            bbox_max: value.bbox.map(|bbox| bbox.max().into()),
+            min_lrm_length: lengths.map_or(0., |lengths| lengths.min),
+            median_lrm_length: lengths.map_or(0., |lengths| lengths.median),
+            mean_lrm_length: lengths.map_or(0., |lengths| lengths.mean),
+            max_lrm_length: lengths.map_or(0., |lengths| lengths.max),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// A part of a trace matched on a single [`Lrm`].
+pub struct TraceMatch {
+    /// Handle of the [`Lrm`] where the positions were matched.
+    pub lrm_handle: usize,
+    /// Measure of the first matched position.
+    pub entry: LrmScaleMeasure,
+    /// Measure of the farthest matched position.
+    pub exit: LrmScaleMeasure,
+    /// Index of the first position of the trace covered by this match.
+    pub first_point: usize,
+    /// Index of the last position of the trace covered by this match.
+    pub last_point: usize,
+}
+
+impl From<&liblrs::trace::TraceMatch> for TraceMatch {
+    fn from(value: &liblrs::trace::TraceMatch) -> Self {
+        Self {
+            lrm_handle: value.lrm.0,
+            entry: (&value.entry).into(),
+            exit: (&value.exit).into(),
+            first_point: value.points.start,
+            last_point: value.points.end - 1,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+/// Error thrown by the functions of the [`Lrs`].
+///
+/// Match on `code`, such as `LRM_NOT_FOUND`, rather than on the `message`.
+pub struct LrsError {
+    /// Stable identifier of the kind of error.
+    pub code: String,
+    /// Description of the error, meant for humans.
+    pub message: String,
+    /// Index of the [`Lrm`] concerned by the error, if it is known.
+    pub lrm_index: Option<usize>,
+    /// Name of the [`Anchor`] concerned by the error, if it is known.
+    pub anchor_name: Option<String>,
+    /// Start of the invalid range of positions on the [`Curve`], if the range is not valid.
+    pub begin: Option<f64>,
+    /// End of the invalid range of positions on the [`Curve`], if the range is not valid.
+    pub end: Option<f64>,
+}
+
+impl From<LrsErrorCode> for LrsError {
+    fn from(value: LrsErrorCode) -> Self {
+        Self {
+            code: value.code().to_owned(),
+            message: value.to_string(),
+            lrm_index: value.lrm_index(),
+            anchor_name: value.anchor_name().map(str::to_owned),
+            begin: value.range().map(|range| range.0),
+            end: value.range().map(|range| range.1),
+        }
+    }
+}
+
+impl From<ExtLrsError> for LrsError {
+    fn from(value: ExtLrsError) -> Self {
+        let message = value.to_string();
+        Self {
+            message,
+            ..LrsErrorCode::from(value).into()
+        }
+    }
+}
+
+#[wasm_bindgen]
+/// Receives an LRS file chunk by chunk, e.g. from the `ReadableStream` of a `fetch` response,
+/// without concatenating the chunks in JavaScript.
+///
+/// The LRMs of the viewport or a few LRMs can be loaded with `load_bbox` or `load_lrms` as soon as the
+/// beginning of the file describing them is received, to answer the first queries before `finish` loads the whole LRS.
+/// The files written by `liblrs convert` store the geometries last so that the LRMs are available early.
+/// A compressed file can only be loaded once complete.
+pub struct LrsLoader {
+    data: Vec<u8>,
+    expected_len: Option<usize>,
+}
+
+#[wasm_bindgen]
+impl LrsLoader {
+    /// A loader for a file of `expected_len` bytes, e.g. the `Content-Length` of the response, if it is known.
+    #[wasm_bindgen(constructor)]
+    pub fn new(expected_len: Option<usize>) -> Self {
+        Self {
+            data: Vec::with_capacity(expected_len.unwrap_or_default()),
+            expected_len,
+        }
+    }
+
+    /// Appends the next chunk of the file.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.data.extend_from_slice(chunk);
+    }
+
+    /// How many bytes have been received, e.g. to display a progress bar.
+    pub fn received(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Loads only the LRMs with these ids.
+    /// Returns `undefined` if they are not available yet: try again after pushing more chunks.
+    pub fn load_lrms(&self, lrm_ids: Vec<String>) -> Result<Option<Lrs>, LrsError> {
+        self.load_filtered(&liblrs::lrs::LoadFilter::LrmIds(lrm_ids))
+    }
+
+    /// Loads only the LRMs in the bounding box between `min` and `max`, such as the viewport of a map.
+    /// Returns `undefined` if they are not available yet: try again after pushing more chunks.
+    pub fn load_bbox(&self, min: Point, max: Point) -> Result<Option<Lrs>, LrsError> {
+        let bbox = geo_types::Rect::new(
+            geo_types::Coord { x: min.x, y: min.y },
+            geo_types::Coord { x: max.x, y: max.y },
+        );
+        self.load_filtered(&liblrs::lrs::LoadFilter::BoundingBox(bbox))
+    }
+
+    /// Loads the whole LRS once the whole file has been received. The loader cannot be used anymore.
+    pub fn finish(self) -> Result<Lrs, LrsError> {
+        Lrs::load(&self.data)
+    }
+}
+
+impl LrsLoader {
+    fn load_filtered(&self, filter: &liblrs::lrs::LoadFilter) -> Result<Option<Lrs>, LrsError> {
+        // Once complete, the file is checked and can be compressed
+        let lrs = if self.expected_len == Some(self.data.len()) {
+            ExtLrs::load_filtered(&self.data, filter).map(Some)
+        } else {
+            ExtLrs::load_partial(&self.data, filter)
+        };
+        lrs.map(|lrs| lrs.map(|lrs| Lrs { lrs }))
+            .map_err(LrsError::from)
+    }
 }
 
 #[wasm_bindgen]
 impl Lrs {
     /// Load the data.
-    pub fn load(data: &[u8]) -> Result<Lrs, String> {
-        ExtLrs::load(data).map(|lrs| Self { lrs })
+    pub fn load(data: &[u8]) -> Result<Lrs, LrsError> {
+        ExtLrs::load(data)
+            .map(|lrs| Self { lrs })
+            .map_err(LrsError::from)
+    }
+
+    /// Coordinate reference system of the coordinates, such as `EPSG:4326`, if it was recorded.
+    pub fn crs(&self) -> Option<String> {
+        self.lrs.crs()
     }
 
     /// How many LRMs compose the LRS.
@@ -150,45 +582,310 @@ impl Lrs {
     }
 
     /// Return the geometry of the LRM.
-    pub fn get_lrm_geom(&self, index: usize) -> Result<Vec<Point>, String> {
+    pub fn get_lrm_geom(&self, index: usize) -> Result<Vec<Point>, LrsError> {
         self.lrs
             .get_lrm_geom(index)
             .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
-            .map_err(|e| e.to_string())
+            .map_err(LrsError::from)
+    }
+
+    /// Return the geometry of the LRM, ordered by increasing measures.
+    /// Use it to draw the direction of the LRM.
+    pub fn get_oriented_lrm_geom(&self, index: usize) -> Result<Vec<Point>, LrsError> {
+        self.lrs
+            .get_oriented_lrm_geom(index)
+            .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// The bounding box and the center of the LRM, to frame it on a map.
+    pub fn get_lrm_bbox(&self, index: usize) -> Result<LrmBbox, LrsError> {
+        self.lrs
+            .get_lrm_bbox(index)
+            .map(LrmBbox::from)
+            .map_err(LrsError::from)
+    }
+
+    /// Whether the measures of the LRM increase along the geometry returned by `get_lrm_geom`.
+    pub fn measures_increase(&self, index: usize) -> Result<bool, LrsError> {
+        self.lrs.measures_increase(index).map_err(LrsError::from)
+    }
+
+    /// Whether the LRM is a loop, where measures wrap around past its origin.
+    pub fn is_loop(&self, index: usize) -> Result<bool, LrsError> {
+        self.lrs.is_loop(index).map_err(LrsError::from)
+    }
+
+    /// The measures at both ends of the LRM and its length in meters.
+    /// Use it to validate a measure before resolving it.
+    pub fn lrm_bounds(&self, index: usize) -> Result<LrmBounds, LrsError> {
+        self.lrs
+            .lrm_bounds(index)
+            .map(|bounds| LrmBounds::from(&bounds))
+            .map_err(LrsError::from)
     }
 
     /// `id` of the [`LrmScale`].
-    pub fn get_lrm_scale_id(&self, index: usize) -> String {
-        self.lrs.get_lrm_scale_id(index)
+    pub fn get_lrm_scale_id(&self, index: usize) -> Result<String, LrsError> {
+        self.lrs.get_lrm_scale_id(index).map_err(LrsError::from)
     }
 
     /// All the [`Anchor`]s of a LRM.
-    pub fn get_anchors(&self, lrm_index: usize) -> Vec<Anchor> {
+    pub fn get_anchors(&self, lrm_index: usize) -> Result<Vec<Anchor>, LrsError> {
         self.lrs
             .get_anchors(lrm_index)
-            .iter()
-            .map(Anchor::from)
-            .collect()
+            .map(|anchors| anchors.iter().map(Anchor::from).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// The [`Anchor`]s of a LRM between two measures, both included, sorted by increasing measures.
+    /// It allows to go through the anchors of a long LRM page by page.
+    pub fn anchors_between(
+        &self,
+        lrm_index: usize,
+        from_measure: &LrmScaleMeasure,
+        to_measure: &LrmScaleMeasure,
+    ) -> Result<Vec<MeasuredAnchor>, LrsError> {
+        self.lrs
+            .anchors_between(lrm_index, &from_measure.into(), &to_measure.into())
+            .map(|anchors| anchors.into_iter().map(MeasuredAnchor::from).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// Where the [`Traversal`]s of two [`Lrm`]s cross or touch, sorted along the first [`Lrm`].
+    pub fn intersections(
+        &self,
+        lrm_index_a: usize,
+        lrm_index_b: usize,
+    ) -> Result<Vec<LrmIntersection>, LrsError> {
+        self.lrs
+            .intersections(lrm_index_a, lrm_index_b)
+            .map(|intersections| intersections.iter().map(LrmIntersection::from).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// The distance in meters along the network between measures of two [`Lrm`]s, following the connections between [`Traversal`]s.
+    /// Fails when they are not connected.
+    pub fn network_distance(
+        &self,
+        from_lrm_index: usize,
+        from_measure: &LrmScaleMeasure,
+        to_lrm_index: usize,
+        to_measure: &LrmScaleMeasure,
+    ) -> Result<f64, LrsError> {
+        self.lrs
+            .network_distance(
+                from_lrm_index,
+                &from_measure.into(),
+                to_lrm_index,
+                &to_measure.into(),
+            )
+            .map_err(LrsError::from)
+    }
+
+    /// Summary of the content of the [`Lrs`], to sanity-check a file.
+    pub fn stats(&self) -> LrsStats {
+        LrsStats::from(&self.lrs.stats())
+    }
+
+    /// The LRMs and their anchors as a GeoJSON FeatureCollection, e.g. to inspect them in a GIS.
+    pub fn to_geojson(&self) -> String {
+        self.lrs.to_geojson()
+    }
+
+    /// The geometry of the LRM as a GeoJSON Feature string, to be given to a map source as is.
+    pub fn lrm_geojson(&self, lrm_index: usize) -> Result<String, LrsError> {
+        self.lrs.lrm_geojson(lrm_index).map_err(LrsError::from)
+    }
+
+    /// The range between two [`LrmScaleMeasure`]s as a GeoJSON Feature string, see `resolve_range`.
+    pub fn range_geojson(
+        &self,
+        lrm_index: usize,
+        from: &LrmScaleMeasure,
+        to: &LrmScaleMeasure,
+        lateral_offset_m: Option<f64>,
+    ) -> Result<String, LrsError> {
+        self.lrs
+            .range_geojson(lrm_index, &from.into(), &to.into(), lateral_offset_m)
+            .map_err(LrsError::from)
+    }
+
+    /// The markers of the [`Anchor`]s of a LRM as a GeoJSON FeatureCollection string, see `anchor_markers`.
+    pub fn anchor_markers_geojson(&self, lrm_index: usize) -> Result<String, LrsError> {
+        self.lrs
+            .anchor_markers_geojson(lrm_index)
+            .map_err(LrsError::from)
+    }
+
+    /// The graduations of a LRM as a GeoJSON FeatureCollection string, see `measure_ticks`.
+    pub fn measure_ticks_geojson(
+        &self,
+        lrm_index: usize,
+        step: f64,
+        tick_length_m: f64,
+    ) -> Result<String, LrsError> {
+        self.lrs
+            .measure_ticks_geojson(lrm_index, step, tick_length_m)
+            .map_err(LrsError::from)
+    }
+
+    /// Get the [`LrmScaleMeasure`] given a position along the curve, between 0 and 1.
+    /// The measure is given from the nearest named [`Anchor`].
+    pub fn measure_at(
+        &self,
+        lrm_index: usize,
+        curve_position: f64,
+    ) -> Result<LrmScaleMeasure, LrsError> {
+        self.lrs
+            .measure_at(lrm_index, curve_position)
+            .map(|measure| LrmScaleMeasure::from(&measure))
+            .map_err(LrsError::from)
     }
 
     /// Get the position given a [`LrmScaleMeasure`].
-    pub fn resolve(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> Result<Point, String> {
+    pub fn resolve(&self, lrm_index: usize, measure: &LrmScaleMeasure) -> Result<Point, LrsError> {
         self.lrs
             .resolve(lrm_index, &measure.into())
             .map(Point::from)
-            .map_err(|e| e.to_string())
+            .map_err(LrsError::from)
+    }
+
+    /// Get the positions of many [`LrmScaleMeasure`]s of the same LRM in a single call.
+    /// Fails if any of them cannot be resolved.
+    pub fn resolve_many(
+        &self,
+        lrm_index: usize,
+        measures: Vec<LrmScaleMeasure>,
+    ) -> Result<Vec<Point>, LrsError> {
+        let measures: Vec<_> = measures.iter().map(|measure| measure.into()).collect();
+        self.lrs
+            .resolve_many(lrm_index, &measures)
+            .into_iter()
+            .map(|point| point.map(Point::from).map_err(LrsError::from))
+            .collect()
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`] and the direction of increasing measures there,
+    /// e.g. to rotate the icon of a train on a map.
+    pub fn resolve_oriented(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> Result<OrientedPoint, LrsError> {
+        self.lrs
+            .resolve_oriented(lrm_index, &measure.into())
+            .map(OrientedPoint::from)
+            .map_err(LrsError::from)
+    }
+
+    /// All the [`Anchor`]s of a LRM, in the same order as `get_anchors`, placed on the [`Lrm`].
+    /// An [`Anchor`] before the start or after the end of the [`Lrm`] is placed at that end.
+    pub fn anchor_markers(&self, lrm_index: usize) -> Result<Vec<AnchorMarker>, LrsError> {
+        self.lrs
+            .anchor_markers(lrm_index)
+            .map(|markers| markers.into_iter().map(AnchorMarker::from).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// Graduations of the scale of the [`Lrm`] every `step` scale units, e.g. every 100 meters.
+    /// Each tick is a segment of `tick_length_m` meters across the [`Lrm`], centered on the graduation.
+    /// There is no graduation if `step` is not a positive finite number.
+    pub fn measure_ticks(
+        &self,
+        lrm_index: usize,
+        step: f64,
+        tick_length_m: f64,
+    ) -> Result<Vec<MeasureTick>, LrsError> {
+        self.lrs
+            .measure_ticks(lrm_index, step, tick_length_m)
+            .map(|ticks| ticks.into_iter().map(MeasureTick::from).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`], moved perpendicularly to the [`Lrm`] by `lateral_offset_m` meters.
+    /// The offset is positive on the left when facing increasing measures, negative on the right.
+    pub fn resolve_with_offset(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+        lateral_offset_m: f64,
+    ) -> Result<Point, LrsError> {
+        self.lrs
+            .resolve_with_offset(lrm_index, &measure.into(), lateral_offset_m)
+            .map(Point::from)
+            .map_err(LrsError::from)
+    }
+
+    /// Get the position given a [`LrmScaleMeasure`] on one of the [`Traversal`]s of the [`Lrm`], e.g. an alternate track.
+    pub fn resolve_on_traversal(
+        &self,
+        lrm_index: usize,
+        measure: &LrmScaleMeasure,
+        traversal_index: usize,
+    ) -> Result<Point, LrsError> {
+        self.lrs
+            .resolve_on_traversal(lrm_index, &measure.into(), traversal_index)
+            .map(Point::from)
+            .map_err(LrsError::from)
+    }
+
+    /// Indices of the [`Traversal`]s of an [`Lrm`]: the main one first, then the alternate ones.
+    pub fn lrm_traversals(&self, lrm_index: usize) -> Result<Vec<usize>, LrsError> {
+        self.lrs.lrm_traversals(lrm_index).map_err(LrsError::from)
     }
 
     /// Given two [`LrmScaleMeasure`]s, return a range of [`LineString`].
+    /// With a `lateral_offset_m`, the range is moved perpendicularly to the [`Lrm`] by that many meters,
+    /// positive on the left when facing increasing measures, negative on the right.
     pub fn resolve_range(
         &self,
         lrm_index: usize,
         from: &LrmScaleMeasure,
         to: &LrmScaleMeasure,
-    ) -> Result<Vec<Point>, String> {
+        lateral_offset_m: Option<f64>,
+    ) -> Result<Vec<Point>, LrsError> {
         self.lrs
-            .resolve_range(lrm_index, &from.into(), &to.into())
+            .resolve_range(lrm_index, &from.into(), &to.into(), lateral_offset_m)
             .map(|coords| coords.into_iter().map(|coord| coord.into()).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// The ranges of measures of the [`Lrm`] inside a polygon, such as a work zone, a flooded area or an administrative boundary.
+    /// The polygon is given by the [`Point`]s of its boundary. The ranges are sorted by increasing measures.
+    pub fn measure_ranges_in_polygon(
+        &self,
+        lrm_index: usize,
+        polygon: Vec<Point>,
+    ) -> Result<Vec<MeasureRange>, LrsError> {
+        let polygon = geo_types::Polygon::new(
+            polygon
+                .into_iter()
+                .map(|point| geo_types::Coord {
+                    x: point.x,
+                    y: point.y,
+                })
+                .collect(),
+            vec![],
+        );
+        self.lrs
+            .measure_ranges_in_polygon(lrm_index, &polygon)
+            .map(|ranges| ranges.into_iter().map(MeasureRange::from).collect())
+            .map_err(LrsError::from)
+    }
+
+    /// Converts a [`LrmScaleMeasure`] of an LRM into a [`LrmScaleMeasure`] of an other LRM.
+    /// Both LRMs must share the same traversal.
+    pub fn convert_measure(
+        &self,
+        from_lrm_index: usize,
+        to_lrm_index: usize,
+        measure: &LrmScaleMeasure,
+    ) -> Result<LrmScaleMeasure, LrsError> {
+        self.lrs
+            .convert_measure(from_lrm_index, to_lrm_index, &measure.into())
+            .map(|measure| LrmScaleMeasure::from(&measure))
+            .map_err(LrsError::from)
     }
 
     /// Projects a [`Point`] on all applicable [`Traversal`]s to a given [`Lrm`].
@@ -196,16 +893,83 @@ impl Lrs {
     /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
     pub fn lookup(&self, point: Point, lrm_handle: usize) -> Vec<LrmProjection> {
         self.lrs
-            .lrs
-            .lookup(point.into(), LrmHandle(lrm_handle))
+            .lookup(point.into(), lrm_handle)
+            .iter()
+            .map(LrmProjection::from)
+            .collect()
+    }
+
+    /// Projects a [`Point`] on all the [`Lrm`]s where the [`Point`] is in the bounding box.
+    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+    pub fn lookup_lrms(&self, point: Point) -> Vec<LrmProjection> {
+        self.lrs
+            .lookup_lrms(point.into())
             .iter()
-            .map(|p| LrmProjection {
-                measure: LrmScaleMeasure {
-                    anchor_name: p.measure.measure.anchor_name.to_owned(),
-                    scale_offset: p.measure.measure.scale_offset,
-                },
-                orthogonal_offset: p.orthogonal_offset,
-            })
+            .map(LrmProjection::from)
+            .collect()
+    }
+
+    /// Indices of all the [`Lrm`]s whose geometry intersects the bounding box between `min` and `max`,
+    /// e.g. to load only the [`Lrm`]s of the viewport of a map.
+    pub fn lrms_in_bbox(&self, min: Point, max: Point) -> Vec<usize> {
+        self.lrs.lrms_in_bbox(
+            geo_types::Coord { x: min.x, y: min.y },
+            geo_types::Coord { x: max.x, y: max.y },
+        )
+    }
+
+    /// Projects a [`Point`] on every [`Lrm`] at most `max_distance` away: meters for geographic coordinates, coordinate units for schematic ones.
+    /// The result is sorted by `orthogonal_offset`: the nearest [`Lrm`] to the [`Point`] is the first item.
+    pub fn lookup_all(&self, point: Point, max_distance: f64) -> Vec<LrmProjection> {
+        self.lrs
+            .lookup_all(point.into(), max_distance)
+            .iter()
+            .map(LrmProjection::from)
+            .collect()
+    }
+
+    /// Index of the [`Lrm`] with an `id`, or `undefined` if there is none.
+    pub fn find_lrm(&self, lrm_id: &str) -> Option<usize> {
+        self.lrs.get_lrm(lrm_id)
+    }
+
+    /// The [`Lrm`]s whose id, or the id of their [`Traversal`], matches a `query` typed in a search box.
+    /// The id can start with the `query`, contain it, or be close to it with a few typos.
+    /// The best `limit` matches are returned, the best one first.
+    pub fn search_lrms(&self, query: &str, limit: usize) -> Vec<LrmMatch> {
+        self.lrs
+            .search_lrms(query, limit)
+            .into_iter()
+            .map(LrmMatch::from)
+            .collect()
+    }
+
+    /// Finds the nearest [`Lrm`] at most `max_distance` away, in the unit of `lookup_all` from a [`Point`], and the measure of the [`Point`] on it.
+    /// Returns `undefined` when no [`Lrm`] is close enough.
+    pub fn locate(&self, point: Point, max_distance: f64) -> Option<LocatedPoint> {
+        self.lrs
+            .locate(point.into(), max_distance)
+            .map(LocatedPoint::from)
+    }
+
+    /// Map-matches an ordered sequence of positions, such as a GNSS log, onto the [`Lrm`]s.
+    /// A position can be matched on an [`Lrm`] at most `max_distance` away, in the unit of `lookup_all`.
+    /// The measures are monotonic along each [`Lrm`]: a position going backward by more than `backward_tolerance` is ignored.
+    pub fn project_trace(
+        &self,
+        points: Vec<Point>,
+        max_distance: f64,
+        backward_tolerance: f64,
+    ) -> Vec<TraceMatch> {
+        let points: Vec<_> = points.into_iter().map(|point| point.into()).collect();
+        let options = liblrs::trace::TraceOptions {
+            max_distance,
+            backward_tolerance,
+        };
+        self.lrs
+            .project_trace(&points, options)
+            .iter()
+            .map(TraceMatch::from)
             .collect()
     }
 
@@ -216,15 +980,68 @@ impl Lrs {
     }
 
     /// [`Properties`] for a given lrm
-    pub fn lrm_properties(&self, lrm_index: usize) -> JsValue {
-        serde_wasm_bindgen::to_value(self.lrs.lrm_properties(lrm_index))
-            .expect("Could not convert Property to JsValue")
+    pub fn lrm_properties(&self, lrm_index: usize) -> Result<JsValue, LrsError> {
+        let properties = self.lrs.lrm_properties(lrm_index).map_err(LrsError::from)?;
+        Ok(
+            serde_wasm_bindgen::to_value(properties)
+                .expect("Could not convert Property to JsValue"),
+        )
+    }
+
+    /// [`Properties`] of the traversal of a given lrm
+    pub fn traversal_properties(&self, lrm_index: usize) -> Result<JsValue, LrsError> {
+        let properties = self
+            .lrs
+            .traversal_properties(lrm_index)
+            .map_err(LrsError::from)?;
+        Ok(
+            serde_wasm_bindgen::to_value(properties)
+                .expect("Could not convert Property to JsValue"),
+        )
     }
 
     /// [`Properties`] for a given anchor
-    pub fn anchor_properties(&self, lrm_index: usize, anchor_index: usize) -> JsValue {
-        serde_wasm_bindgen::to_value(self.lrs.anchor_properties(lrm_index, anchor_index))
-            .expect("Could not convert Property to JsValue")
+    pub fn anchor_properties(
+        &self,
+        lrm_index: usize,
+        anchor_index: usize,
+    ) -> Result<JsValue, LrsError> {
+        let properties = self
+            .lrs
+            .anchor_properties(lrm_index, anchor_index)
+            .map_err(LrsError::from)?;
+        Ok(
+            serde_wasm_bindgen::to_value(properties)
+                .expect("Could not convert Property to JsValue"),
+        )
+    }
+}
+
+/// Runs the golden dataset through the javascript facing functions.
+impl ConformanceAdapter for Lrs {
+    fn find_lrm(&self, lrm_id: &str) -> Option<usize> {
+        (0..self.lrm_len()).find(|&index| self.get_lrm_scale_id(index).is_ok_and(|id| id == lrm_id))
+    }
+
+    fn resolve(
+        &self,
+        lrm_index: usize,
+        measure: &liblrs::lrm_scale::LrmScaleMeasure,
+    ) -> Result<geo_types::Point, String> {
+        Lrs::resolve(self, lrm_index, &measure.into())
+            .map(|point| point.into())
+            .map_err(|e| e.message)
+    }
+
+    fn lookup(
+        &self,
+        point: geo_types::Point,
+        lrm_index: usize,
+    ) -> Result<(liblrs::lrm_scale::LrmScaleMeasure, f64), String> {
+        Lrs::lookup(self, point.into(), lrm_index)
+            .first()
+            .map(|projection| ((&projection.measure).into(), projection.orthogonal_offset))
+            .ok_or("could not project the point".to_owned())
     }
 }
 
@@ -239,3 +1056,44 @@ pub fn set_panic_hook() {
     // https://github.com/rustwasm/console_error_panic_hook#readme
     console_error_panic_hook::set_once();
 }
+
+#[cfg(test)]
+mod tests {
+    use geo_types::coord;
+    use liblrs::builder::{AnchorOnLrm, Builder};
+    use liblrs::properties;
+
+    use super::*;
+
+    fn lrs() -> Lrs {
+        let mut b = Builder::new();
+        let start = b.add_node("a", coord! {x: 0., y: 0.}, properties!());
+        let end = b.add_node("b", coord! {x: 0.01, y: 0.}, properties!());
+        let geometry = geo_types::LineString::from(vec![(0., 0.), (0.01, 0.)]);
+        let traversal = b.add_traversal_from_geometry("t", &geometry, start, end);
+        let anchor_index = b.add_anchor("0", Some("0"), coord! {x: 0., y: 0.}, properties!());
+        let anchors = [AnchorOnLrm {
+            anchor_index,
+            distance_along_lrm: 0.,
+        }];
+        b.add_lrm("lrm", traversal, &anchors, properties!());
+        Lrs::load(b.build_data(properties!())).unwrap_or_else(|_| panic!("the LRS should load"))
+    }
+
+    #[test]
+    fn lookup_invalid_lrm() {
+        let lrs = lrs();
+        let point = Point { x: 0.005, y: 0. };
+        assert_eq!(lrs.lookup(point, 0).len(), 1);
+        assert!(lrs.lookup(point, 42).is_empty());
+    }
+    #[test]
+    fn golden_dataset() {
+        use liblrs::conformance::{GOLDEN_DATASET, parse_cases, parse_network, run};
+
+        let data = parse_network(GOLDEN_DATASET).unwrap().build().unwrap();
+        let lrs = Lrs::load(&data).unwrap_or_else(|_| panic!("the LRS should load"));
+        let report = run(&lrs, &parse_cases(GOLDEN_DATASET).unwrap(), 1e-6);
+        assert!(report.is_success(), "{:?}", report.failures);
+    }
+}